@@ -3,7 +3,7 @@ use bitcoin_da::service::{BitcoinService, DaServiceConfig};
 use bitcoin_da::spec::{BitcoinSpec, RollupParams};
 use bitcoin_da::verifier::BitcoinVerifier;
 use citrea_stf::genesis_config::StorageConfig;
-use citrea_stf::runtime::Runtime;
+use citrea_stf::runtime::{GenesisConfig, Runtime};
 use const_rollup_config::{DA_TX_ID_LEADING_ZEROS, ROLLUP_NAME};
 use sequencer_client::SequencerClient;
 use sov_db::ledger_db::LedgerDB;
@@ -82,6 +82,14 @@ impl RollupBlueprint for BitcoinRollup {
             sequencer_client,
         )?;
 
+        crate::da_proof::register_da_inclusion_proof(
+            da_service.clone(),
+            ledger_db.clone(),
+            &mut rpc_methods,
+        )?;
+
+        crate::openrpc::register_openrpc(&mut rpc_methods)?;
+
         Ok(rpc_methods)
     }
 
@@ -95,6 +103,10 @@ impl RollupBlueprint for BitcoinRollup {
         ProverStorageManager::new(storage_config)
     }
 
+    fn chain_id(&self, rt_genesis: &GenesisConfig<Self::NativeContext, Self::DaSpec>) -> u64 {
+        rt_genesis.chain_id()
+    }
+
     async fn create_da_service(
         &self,
         rollup_config: &RollupConfig<Self::DaConfig>,
@@ -0,0 +1,89 @@
+use anyhow::Context as _;
+use bitcoin_da::service::BitcoinService;
+use jsonrpsee::types::ErrorObjectOwned;
+use serde::Serialize;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::BatchNumber;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+
+const DA_RPC_ERROR: &str = "DA_RPC_ERROR";
+
+/// Proof that the sequencer commitment covering an L2 block is available on Bitcoin,
+/// as returned by `citrea_getDaInclusionProof`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DaInclusionProof {
+    /// First L2 height covered by the commitment
+    l2_start_height: u64,
+    /// Last L2 height covered by the commitment
+    l2_end_height: u64,
+    /// Height of the Bitcoin block containing the commitment
+    l1_height: u64,
+    /// Id of the transaction carrying the commitment
+    txid: String,
+    /// Position of the transaction in the Bitcoin block
+    tx_index: u32,
+    /// The transaction with its witness, hex encoded
+    raw_tx: String,
+    /// The 80 bytes Bitcoin block header, hex encoded
+    block_header: String,
+    /// Sibling hashes from the transaction up to the merkle root of the header,
+    /// hex encoded in internal byte order
+    merkle_path: Vec<String>,
+    /// Position of the commitment among the rollup blobs of the Bitcoin block
+    blob_offset: u32,
+}
+
+// register the method returning the DA inclusion proof of the commitment covering an L2 block.
+pub(crate) fn register_da_inclusion_proof(
+    da_service: BitcoinService,
+    ledger_db: LedgerDB,
+    methods: &mut jsonrpsee::RpcModule<()>,
+) -> Result<(), anyhow::Error> {
+    methods
+        .register_async_method("citrea_getDaInclusionProof", move |params, _| {
+            let da_service = da_service.clone();
+            let ledger_db = ledger_db.clone();
+            async move {
+                let l2_height: u64 = params.one()?;
+
+                // Only commitments the node has seen on the DA layer are known
+                let Some((l2_end_height, location)) = ledger_db
+                    .get_commitment_location_by_l2_height(BatchNumber(l2_height))
+                    .map_err(|e| to_jsonrpsee_error_object(e, DA_RPC_ERROR))?
+                else {
+                    return Ok::<Option<DaInclusionProof>, ErrorObjectOwned>(None);
+                };
+
+                let proof = da_service
+                    .get_blob_inclusion_proof(location.l1_height.0, location.blob_hash)
+                    .await
+                    .map_err(|e| to_jsonrpsee_error_object(e, DA_RPC_ERROR))?
+                    .ok_or_else(|| {
+                        // The block was reorged out since the commitment was seen
+                        to_jsonrpsee_error_object(
+                            "Commitment not found in its DA block",
+                            DA_RPC_ERROR,
+                        )
+                    })?;
+
+                Ok(Some(DaInclusionProof {
+                    l2_start_height: location.l2_start_height.0,
+                    l2_end_height: l2_end_height.0,
+                    l1_height: location.l1_height.0,
+                    txid: proof.txid.to_string(),
+                    tx_index: proof.tx_index,
+                    raw_tx: format!("0x{}", hex::encode(proof.raw_tx)),
+                    block_header: format!("0x{}", hex::encode(proof.header)),
+                    merkle_path: proof
+                        .merkle_path
+                        .iter()
+                        .map(|hash| format!("0x{}", hex::encode(hash)))
+                        .collect(),
+                    blob_offset: proof.blob_offset,
+                }))
+            }
+        })
+        .context("Failed to register DA inclusion proof method")?;
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+use citrea_stf::genesis_config::StorageConfig;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::BatchNumber;
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::Spec;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::storage::NativeStorage;
+use sov_state::DefaultStorageSpec;
+use tracing::{error, info};
+
+/// Verifies the ledger and state databases under `storage_path` up to the soft batch at `height`.
+///
+/// Ledger entries are cross-checked by [`LedgerDB::verify_soft_batches`], then the post state root
+/// of every soft batch is compared to the root hash the JMT stores for its version. Every soft
+/// batch creates a version, the state of soft batch N being stored at version N + 1.
+///
+/// The root is read from the root node of the version, the tree below it is not walked.
+/// Missing soft batches are reported by the ledger checks and skipped here.
+///
+/// Returns an error if any inconsistency is found.
+pub(crate) fn verify_db(storage_path: &Path, height: u64) -> anyhow::Result<()> {
+    let ledger_db = LedgerDB::with_path(storage_path).context("Failed to open ledger db")?;
+
+    let head = ledger_db
+        .get_head_soft_batch()?
+        .map(|(number, _)| number.0)
+        .unwrap_or_default();
+    anyhow::ensure!(
+        height <= head,
+        "Requested height {height} is above the head soft batch {head}"
+    );
+
+    let tx_hasher =
+        |body: &[u8]| -> [u8; 32] { <DefaultContext as Spec>::Hasher::digest(body).into() };
+    let mut issues = ledger_db.verify_soft_batches(BatchNumber(height), Some(&tx_hasher))?;
+
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(StorageConfig {
+            path: storage_path.to_path_buf(),
+        })?;
+    let storage = storage_manager.create_finalized_storage()?;
+
+    for number in 1..=height {
+        let Some(soft_batch) = ledger_db.get_stored_soft_batch(BatchNumber(number))? else {
+            continue;
+        };
+        let reason = match storage.get_root_hash(number + 1) {
+            Ok(root) if root.0.as_slice() == soft_batch.post_state_root.as_slice() => continue,
+            Ok(root) => format!(
+                "post state root {} does not match the root {} of version {}",
+                hex::encode(&soft_batch.post_state_root),
+                hex::encode(root.0),
+                number + 1
+            ),
+            Err(_) => format!("version {} not found in the state tree", number + 1),
+        };
+        issues.push(sov_db::ledger_db::IntegrityIssue {
+            height: BatchNumber(number),
+            reason,
+        });
+    }
+
+    if issues.is_empty() {
+        info!("Verified soft batches up to {}, no issues found", height);
+        return Ok(());
+    }
+
+    for issue in issues.iter() {
+        error!("{}", issue);
+    }
+    anyhow::bail!("Found {} integrity issues", issues.len())
+}
@@ -1,4 +1,6 @@
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
 
 use anyhow::Context as _;
 use ethereum_rpc::{EthRpcConfig, FeeHistoryCacheConfig, GasPriceOracleConfig};
@@ -7,6 +9,50 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_prover_storage_manager::SnapshotManager;
 use sov_rollup_interface::services::da::DaService;
 use sov_state::ProverStorage;
+use sov_stf_runner::{GasOracleConfig, IndexerConfig, StreamingConfig, WalletChainConfig};
+
+static DEV_SIGNING: AtomicBool = AtomicBool::new(false);
+
+static WALLET_CHAIN: OnceLock<WalletChainConfig> = OnceLock::new();
+
+static STREAMING: OnceLock<StreamingConfig> = OnceLock::new();
+
+static INDEXER: OnceLock<IndexerConfig> = OnceLock::new();
+
+static GAS_ORACLE: OnceLock<GasOracleConfig> = OnceLock::new();
+
+static ACCEPTANCE_FLOOR: AtomicU64 = AtomicU64::new(0);
+
+/// Makes the node serve `eth_sign` and `eth_signTransaction` with its dev accounts.
+pub fn enable_dev_signing() {
+    DEV_SIGNING.store(true, Ordering::Relaxed);
+}
+
+/// Sets the network metadata served by `citrea_getAddEthereumChainParameters`.
+pub fn set_wallet_chain(config: WalletChainConfig) {
+    let _ = WALLET_CHAIN.set(config);
+}
+
+/// Sets the NATS server full nodes stream the chain data to.
+pub fn set_streaming(config: StreamingConfig) {
+    let _ = STREAMING.set(config);
+}
+
+/// Sets the database the node mirrors the chain data into.
+pub fn set_indexer(config: IndexerConfig) {
+    let _ = INDEXER.set(config);
+}
+
+/// Sets the policy of the gas price oracle serving `eth_gasPrice` and
+/// `eth_maxPriorityFeePerGas`.
+pub fn set_gas_oracle(config: GasOracleConfig) {
+    let _ = GAS_ORACLE.set(config);
+}
+
+/// Sets the lowest tip the sequencer accepts, under which the gas price oracle never goes.
+pub fn set_acceptance_floor(min_priority_fee_per_gas: u64) {
+    ACCEPTANCE_FLOOR.store(min_priority_fee_per_gas, Ordering::Relaxed);
+}
 
 // register ethereum methods.
 pub(crate) fn register_ethereum<Da: DaService>(
@@ -19,8 +65,17 @@ pub(crate) fn register_ethereum<Da: DaService>(
         let eth_signer = eth_dev_signer();
         EthRpcConfig {
             eth_signer,
-            gas_price_oracle_config: GasPriceOracleConfig::default(),
+            gas_price_oracle_config: GAS_ORACLE
+                .get()
+                .cloned()
+                .map(GasPriceOracleConfig::from)
+                .unwrap_or_default()
+                .with_acceptance_floor(ACCEPTANCE_FLOOR.load(Ordering::Relaxed)),
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
+            enable_dev_signing: DEV_SIGNING.load(Ordering::Relaxed),
+            wallet_chain: WALLET_CHAIN.get().cloned(),
+            streaming: STREAMING.get().cloned(),
+            indexer: INDEXER.get().cloned(),
         }
     };
 
@@ -0,0 +1,203 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+use citrea_evm::Evm;
+use citrea_stf::genesis_config::StorageConfig;
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::WorkingSet;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::DefaultStorageSpec;
+
+/// EIP-1559 parameters the fee evolution is replayed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FeeParams {
+    /// Ratio of the gas limit to the gas target of a block
+    pub elasticity_multiplier: u64,
+    /// Bound divisor of the base fee change between blocks
+    pub max_change_denominator: u64,
+    /// Gas target of every block, `gas_limit / elasticity_multiplier` if not set
+    pub gas_target: Option<u64>,
+    /// Base fee the simulated base fee never goes below
+    pub min_base_fee: u64,
+}
+
+/// Gas usage and base fee of a historical block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockGas {
+    pub number: u64,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee: u64,
+}
+
+/// Base fees and base fee revenues of a block, as recorded and as simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FeePoint {
+    pub number: u64,
+    pub gas_used: u64,
+    pub base_fee: u64,
+    pub simulated_base_fee: u64,
+    pub revenue: u128,
+    pub simulated_revenue: u128,
+}
+
+impl FeeParams {
+    /// Returns the base fee of the block after a block which used `gas_used` of `gas_limit`
+    /// with `base_fee`, following EIP-1559.
+    pub(crate) fn next_base_fee(&self, gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
+        let gas_target = self
+            .gas_target
+            .unwrap_or(gas_limit / self.elasticity_multiplier.max(1))
+            .max(1);
+        let denominator = (gas_target as u128) * (self.max_change_denominator.max(1) as u128);
+
+        let next_base_fee = if gas_used > gas_target {
+            let delta = (base_fee as u128) * ((gas_used - gas_target) as u128) / denominator;
+            base_fee.saturating_add((delta as u64).max(1))
+        } else {
+            let delta = (base_fee as u128) * ((gas_target - gas_used) as u128) / denominator;
+            base_fee.saturating_sub(delta as u64)
+        };
+        next_base_fee.max(self.min_base_fee)
+    }
+
+    /// Replays the base fees of `blocks` from the base fee of the first one, assuming the
+    /// blocks would have used the same gas.
+    pub(crate) fn simulate(&self, blocks: &[BlockGas]) -> Vec<FeePoint> {
+        let mut points = Vec::with_capacity(blocks.len());
+        let mut simulated_base_fee = match blocks.first() {
+            Some(block) => block.base_fee.max(self.min_base_fee),
+            None => return points,
+        };
+        for block in blocks {
+            points.push(FeePoint {
+                number: block.number,
+                gas_used: block.gas_used,
+                base_fee: block.base_fee,
+                simulated_base_fee,
+                revenue: block.base_fee as u128 * block.gas_used as u128,
+                simulated_revenue: simulated_base_fee as u128 * block.gas_used as u128,
+            });
+            simulated_base_fee =
+                self.next_base_fee(block.gas_used, block.gas_limit, simulated_base_fee);
+        }
+        points
+    }
+}
+
+/// Reads the EIP-1559 parameters of the chain, without a gas target nor a min. base fee,
+/// and the gas usage and base fees of the EVM blocks `from..=to` from the state database
+/// under `storage_path`.
+pub(crate) fn read_blocks(
+    storage_path: &Path,
+    from: u64,
+    to: u64,
+) -> anyhow::Result<(FeeParams, Vec<BlockGas>)> {
+    anyhow::ensure!(from <= to, "Block range {from}..={to} is empty");
+
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(StorageConfig {
+            path: storage_path.to_path_buf(),
+        })?;
+    let storage = storage_manager.create_finalized_storage()?;
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage);
+    let evm = Evm::<DefaultContext>::default();
+
+    let base_fee_params = evm.get_chain_config(&mut working_set).base_fee_params;
+    let params = FeeParams {
+        elasticity_multiplier: base_fee_params.elasticity_multiplier as u64,
+        max_change_denominator: base_fee_params.max_change_denominator as u64,
+        gas_target: None,
+        min_base_fee: 0,
+    };
+
+    let headers = evm
+        .sealed_headers_range(from..=to, &mut working_set)
+        .with_context(|| format!("Blocks {from}..={to} are not all in the database"))?;
+    let blocks = headers
+        .into_iter()
+        .map(|header| BlockGas {
+            number: header.number,
+            gas_used: header.gas_used,
+            gas_limit: header.gas_limit,
+            base_fee: header.base_fee_per_gas.unwrap_or_default(),
+        })
+        .collect();
+    Ok((params, blocks))
+}
+
+/// Formats `points` as CSV, with a header row.
+pub(crate) fn to_csv(points: &[FeePoint]) -> String {
+    let mut csv =
+        String::from("block,gas_used,base_fee,simulated_base_fee,revenue,simulated_revenue\n");
+    for point in points {
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            point.number,
+            point.gas_used,
+            point.base_fee,
+            point.simulated_base_fee,
+            point.revenue,
+            point.simulated_revenue
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: FeeParams = FeeParams {
+        elasticity_multiplier: 2,
+        max_change_denominator: 8,
+        gas_target: None,
+        min_base_fee: 0,
+    };
+
+    #[test]
+    fn test_next_base_fee() {
+        // At target, the base fee is unchanged
+        assert_eq!(PARAMS.next_base_fee(15_000_000, 30_000_000, 1000), 1000);
+        // Full blocks raise it by 1/8, empty ones lower it by 1/8
+        assert_eq!(PARAMS.next_base_fee(30_000_000, 30_000_000, 1000), 1125);
+        assert_eq!(PARAMS.next_base_fee(0, 30_000_000, 1000), 875);
+        // Blocks over target always raise it
+        assert_eq!(PARAMS.next_base_fee(15_000_001, 30_000_000, 1000), 1001);
+
+        let params = FeeParams {
+            gas_target: Some(10_000_000),
+            min_base_fee: 900,
+            ..PARAMS
+        };
+        assert_eq!(params.next_base_fee(10_000_000, 30_000_000, 1000), 1000);
+        assert_eq!(params.next_base_fee(0, 30_000_000, 1000), 900);
+    }
+
+    #[test]
+    fn test_simulate() {
+        let blocks: Vec<_> = (1..=3)
+            .map(|number| BlockGas {
+                number,
+                gas_used: 30_000_000,
+                gas_limit: 30_000_000,
+                base_fee: 1000,
+            })
+            .collect();
+
+        let points = PARAMS.simulate(&blocks);
+        let simulated_base_fees: Vec<_> = points.iter().map(|p| p.simulated_base_fee).collect();
+        assert_eq!(simulated_base_fees, vec![1000, 1125, 1265]);
+        assert_eq!(points[2].revenue, 30_000_000_000);
+        assert_eq!(points[2].simulated_revenue, 37_950_000_000);
+
+        assert_eq!(
+            to_csv(&points[..1]),
+            "block,gas_used,base_fee,simulated_base_fee,revenue,simulated_revenue\n\
+             1,30000000,1000,1000,30000000000,30000000000\n"
+        );
+        assert!(PARAMS.simulate(&[]).is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use std::str::FromStr;
+
+use anyhow::Context as _;
+use soft_confirmation_rule_enforcer::SoftConfirmationRuleEnforcerConfig;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::Spec;
+
+/// Returns the JSON of the soft confirmation rule enforcer genesis with the given fields,
+/// the defaults of [`SoftConfirmationRuleEnforcerConfig::builder`] for the unset ones.
+pub(crate) fn rule_enforcer_genesis(
+    authority: &str,
+    limiting_number: Option<u64>,
+    l1_fee_rate_change_percentage: Option<u64>,
+) -> anyhow::Result<String> {
+    let authority = <DefaultContext as Spec>::Address::from_str(authority)
+        .context("authority: invalid address")?;
+
+    let mut builder = SoftConfirmationRuleEnforcerConfig::<DefaultContext>::builder(authority);
+    if let Some(limiting_number) = limiting_number {
+        builder = builder.limiting_number(limiting_number);
+    }
+    if let Some(percentage) = l1_fee_rate_change_percentage {
+        builder = builder.l1_fee_rate_change_percentage(percentage);
+    }
+    let config = builder
+        .build()
+        .context("Invalid soft confirmation rule enforcer genesis")?;
+
+    Ok(serde_json::to_string_pretty(&config)?)
+}
@@ -10,7 +10,13 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{fmt, EnvFilter};
 
+mod da_proof;
 mod eth;
+pub use eth::{
+    enable_dev_signing, set_acceptance_floor, set_gas_oracle, set_indexer, set_streaming,
+    set_wallet_chain,
+};
+mod openrpc;
 
 mod bitcoin_rollup;
 pub use bitcoin_rollup::*;
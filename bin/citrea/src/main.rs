@@ -2,10 +2,14 @@ use core::fmt::Debug as DebugTrait;
 
 use anyhow::{anyhow, Context as _};
 use bitcoin_da::service::DaServiceConfig;
-use citrea::{initialize_logging, BitcoinRollup, MockDemoRollup};
+use citrea::{
+    enable_dev_signing, initialize_logging, set_acceptance_floor, set_gas_oracle, set_indexer,
+    set_streaming, set_wallet_chain, BitcoinRollup, MockDemoRollup,
+};
 use citrea_sequencer::SequencerConfig;
 use citrea_stf::genesis_config::GenesisPaths;
 use clap::Parser;
+use serde::de::DeserializeOwned;
 use sov_mock_da::MockDaConfig;
 use sov_modules_api::runtime::capabilities::Kernel;
 use sov_modules_api::Spec;
@@ -16,15 +20,19 @@ use sov_modules_stf_blueprint::kernels::basic::{
 use sov_state::storage::NativeStorage;
 use sov_stf_runner::{from_toml_path, RollupConfig, RollupProverConfig};
 
+mod db_verify;
+mod fee_simulation;
+mod genesis;
 #[cfg(test)]
 mod test_rpc;
+mod test_vectors;
 
 /// Main demo runner. Initializes a DA chain, and starts a demo-rollup using the provided.
 /// If you're trying to sign or submit transactions to the rollup, the `sov-cli` binary
 /// is the one you want. You can run it `cargo run --bin sov-cli`.
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
     /// Path to the genesis configuration.
     /// Defines the genesis of module states like evm.
@@ -47,6 +55,120 @@ struct Args {
     /// Can't be set if sequencer_config_path is set.
     #[arg(long, conflicts_with = "sequencer_config_path")]
     prover: bool,
+
+    /// If set, records the internal transactions of executed transactions,
+    /// so that `citrea_getInternalTransactions` can serve them to block explorers.
+    #[arg(long)]
+    index_internal_txs: bool,
+
+    /// If set, serves `citrea_getStateDiff`, which re-executes historical blocks
+    /// to return the accounts and storage slots they changed.
+    #[arg(long)]
+    state_diff_rpc: bool,
+
+    /// If set, records the state keys read by the transactions of every block, so that
+    /// `citrea_getAccessHints` can serve them to full nodes prefetching state.
+    #[arg(long)]
+    access_hints: bool,
+
+    /// Maximum size in bytes of the traces returned by a `debug_trace*` request, 0 for no
+    /// limit. Larger traces can be streamed over WebSocket with `debug_subscribeTrace`.
+    #[arg(long, default_value_t = citrea_evm::DEFAULT_MAX_TRACE_RESULT_SIZE)]
+    max_trace_result_size: usize,
+
+    /// Maximum number of blocks scanned by an `eth_getLogs` request, and by a page of
+    /// `citrea_getLogsPage`.
+    #[arg(long, default_value_t = citrea_evm::DEFAULT_MAX_BLOCKS_PER_FILTER)]
+    max_logs_block_range: u64,
+
+    /// Maximum number of logs returned by an `eth_getLogs` request, and by a page of
+    /// `citrea_getLogsPage`.
+    #[arg(long, default_value_t = citrea_evm::DEFAULT_MAX_LOGS_PER_RESPONSE)]
+    max_logs_per_response: usize,
+
+    /// If set, serves `eth_sign` and `eth_signTransaction` with the node's dev accounts.
+    /// Only meant for dev and test networks.
+    #[arg(long)]
+    dev_signing: bool,
+
+    /// Maintenance commands. If set, the node is not started.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Database maintenance commands.
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Genesis file generation commands.
+    #[command(subcommand)]
+    Genesis(GenesisCommand),
+    /// Replays the base fees of a range of blocks with alternative EIP-1559 parameters,
+    /// assuming the blocks would have used the same gas. Prints the recorded and simulated
+    /// base fees and base fee revenues of every block as CSV.
+    SimulateFees {
+        /// First block of the range.
+        #[arg(long)]
+        from_block: u64,
+        /// Last block of the range.
+        #[arg(long)]
+        to_block: u64,
+        /// Ratio of the gas limit to the gas target. The chain's if not set.
+        #[arg(long)]
+        elasticity_multiplier: Option<u64>,
+        /// Bound divisor of the base fee change between blocks. The chain's if not set.
+        #[arg(long)]
+        max_change_denominator: Option<u64>,
+        /// Gas target of every block, overriding the elasticity multiplier.
+        #[arg(long)]
+        gas_target: Option<u64>,
+        /// Base fee the simulated base fee never goes below.
+        #[arg(long, default_value_t = 0)]
+        min_base_fee: u64,
+        /// The file to write the CSV to. Printed if not set.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Exports the canonical serialized forms and hashes of the core types as JSON test
+    /// vectors, for external implementations to validate their parsers against.
+    TestVectors {
+        /// The file to write the test vectors to. Printed if not set.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum DbCommand {
+    /// Verifies the ledger and state databases up to the given soft batch height.
+    /// Reports corruption such as broken state root chains, dangling indexes or post state
+    /// roots that differ from the JMT root hash stored for their version.
+    Verify {
+        /// The soft batch height to verify up to.
+        #[arg(long)]
+        height: u64,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum GenesisCommand {
+    /// Writes a validated `soft_confirmation_rule_enforcer.json`.
+    /// Fails naming the invalid field otherwise.
+    RuleEnforcer {
+        /// Address of the sequencer, allowed to modify the rules.
+        #[arg(long)]
+        authority: String,
+        /// Maximum number of L2 blocks per L1 slot.
+        #[arg(long)]
+        limiting_number: Option<u64>,
+        /// Maximum L1 fee rate change between L2 blocks, out of 100.
+        #[arg(long)]
+        l1_fee_rate_change_percentage: Option<u64>,
+        /// The file to write the genesis to. Printed if not set.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -62,6 +184,79 @@ async fn main() -> Result<(), anyhow::Error> {
     let args = Args::parse();
     let rollup_config_path = args.rollup_config_path.as_str();
 
+    if let Some(Command::TestVectors { out }) = args.command {
+        let vectors = serde_json::to_string_pretty(&test_vectors::test_vectors())?;
+        match out {
+            Some(path) => std::fs::write(path, vectors + "\n")?,
+            None => println!("{}", vectors),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Genesis(GenesisCommand::RuleEnforcer {
+        authority,
+        limiting_number,
+        l1_fee_rate_change_percentage,
+        out,
+    })) = args.command
+    {
+        let genesis = genesis::rule_enforcer_genesis(
+            &authority,
+            limiting_number,
+            l1_fee_rate_change_percentage,
+        )?;
+        match out {
+            Some(path) => std::fs::write(path, genesis + "\n")?,
+            None => println!("{}", genesis),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Db(DbCommand::Verify { height })) = args.command {
+        let storage_path = storage_path(&args.da_layer, rollup_config_path)?;
+        return db_verify::verify_db(&storage_path, height);
+    }
+
+    if let Some(Command::SimulateFees {
+        from_block,
+        to_block,
+        elasticity_multiplier,
+        max_change_denominator,
+        gas_target,
+        min_base_fee,
+        out,
+    }) = args.command
+    {
+        let storage_path = storage_path(&args.da_layer, rollup_config_path)?;
+        let (chain_params, blocks) =
+            fee_simulation::read_blocks(&storage_path, from_block, to_block)?;
+        let params = fee_simulation::FeeParams {
+            elasticity_multiplier: elasticity_multiplier
+                .unwrap_or(chain_params.elasticity_multiplier),
+            max_change_denominator: max_change_denominator
+                .unwrap_or(chain_params.max_change_denominator),
+            gas_target,
+            min_base_fee,
+        };
+        let points = params.simulate(&blocks);
+        tracing::info!(
+            "Base fee revenue of blocks {}..={}: {} recorded, {} simulated",
+            from_block,
+            to_block,
+            points.iter().map(|point| point.revenue).sum::<u128>(),
+            points
+                .iter()
+                .map(|point| point.simulated_revenue)
+                .sum::<u128>(),
+        );
+        let csv = fee_simulation::to_csv(&points);
+        match out {
+            Some(path) => std::fs::write(path, csv)?,
+            None => print!("{}", csv),
+        }
+        return Ok(());
+    }
+
     let sequencer_config: Option<SequencerConfig> =
         args.sequencer_config_path.clone().map(|path| {
             from_toml_path(path)
@@ -69,6 +264,28 @@ async fn main() -> Result<(), anyhow::Error> {
                 .unwrap()
         });
 
+    if args.index_internal_txs {
+        citrea_evm::enable_internal_tx_index();
+    }
+
+    if args.state_diff_rpc {
+        citrea_evm::enable_state_diff_rpc();
+    }
+
+    if args.access_hints {
+        citrea_evm::enable_access_hints();
+    }
+
+    citrea_evm::set_max_trace_result_size(args.max_trace_result_size);
+    citrea_evm::set_log_query_limits(args.max_logs_block_range, args.max_logs_per_response);
+
+    if args.dev_signing {
+        tracing::warn!(
+            "Dev signing is enabled, anyone reaching the RPC can sign with the dev accounts"
+        );
+        enable_dev_signing();
+    }
+
     let is_prover = args.prover;
     match args.da_layer {
         SupportedDaLayer::Mock => {
@@ -81,6 +298,7 @@ async fn main() -> Result<(), anyhow::Error> {
                     &std::fs::read_to_string(&kernel_genesis_paths.chain_state)
                         .context("Failed to read chain state")?,
                 )?,
+                da_light_client: read_da_light_client(&args.genesis_paths)?,
             };
 
             start_rollup::<MockDemoRollup, MockDaConfig>(
@@ -103,6 +321,7 @@ async fn main() -> Result<(), anyhow::Error> {
                     &std::fs::read_to_string(&kernel_genesis_paths.chain_state)
                         .context("Failed to read chain state")?,
                 )?,
+                da_light_client: read_da_light_client(&args.genesis_paths)?,
             };
 
             start_rollup::<BitcoinRollup, DaServiceConfig>(
@@ -120,6 +339,38 @@ async fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Reads the trusted DA block the DA light client starts from.
+/// The light client is disabled if the genesis directory has no `da_light_client.json`.
+fn read_da_light_client<T: DeserializeOwned>(genesis_dir: &str) -> anyhow::Result<Option<T>> {
+    let path = std::path::Path::new(genesis_dir).join("da_light_client.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let tip = serde_json::from_str(
+        &std::fs::read_to_string(path).context("Failed to read DA light client")?,
+    )?;
+    Ok(Some(tip))
+}
+
+/// Returns the storage path of the rollup config at `rollup_config_path`.
+fn storage_path(
+    da_layer: &SupportedDaLayer,
+    rollup_config_path: &str,
+) -> anyhow::Result<std::path::PathBuf> {
+    Ok(match da_layer {
+        SupportedDaLayer::Mock => {
+            from_toml_path::<_, RollupConfig<MockDaConfig>>(rollup_config_path)?
+                .storage
+                .path
+        }
+        SupportedDaLayer::Bitcoin => {
+            from_toml_path::<_, RollupConfig<DaServiceConfig>>(rollup_config_path)?
+                .storage
+                .path
+        }
+    })
+}
+
 async fn start_rollup<S, DaC>(
     rt_genesis_paths: &<<S as RollupBlueprint>::NativeRuntime as sov_modules_stf_blueprint::Runtime<
         <S as RollupBlueprint>::NativeContext,
@@ -148,8 +399,22 @@ where
         .unwrap();
     let rollup_blueprint = S::new();
 
+    if let Some(wallet_chain) = rollup_config.wallet_chain.clone() {
+        set_wallet_chain(wallet_chain);
+    }
+    if let Some(streaming) = rollup_config.streaming.clone() {
+        set_streaming(streaming);
+    }
+    if let Some(indexer) = rollup_config.indexer.clone() {
+        set_indexer(indexer);
+    }
+    if let Some(gas_oracle) = rollup_config.gas_oracle.clone() {
+        set_gas_oracle(gas_oracle);
+    }
+
     if let Some(sequencer_config) = sequencer_config {
         rollup_config.sequencer_client = None;
+        set_acceptance_floor(sequencer_config.min_priority_fee_per_gas);
 
         let sequencer_rollup = rollup_blueprint
             .create_new_sequencer(
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use citrea_stf::genesis_config::StorageConfig;
-use citrea_stf::runtime::Runtime;
+use citrea_stf::runtime::{GenesisConfig, Runtime};
 use sequencer_client::SequencerClient;
 use sov_db::ledger_db::LedgerDB;
 use sov_mock_da::{MockDaConfig, MockDaService, MockDaSpec};
@@ -11,10 +13,14 @@ use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
 use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
-use sov_rollup_interface::zk::ZkvmHost;
+use sov_risc0_adapter::Risc0MethodId;
+use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
 use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
 
+/// Guest version of the proofs of the MockDa guest
+const MOCK_DA_GUEST_VERSION: u32 = 1;
+
 /// Rollup with MockDa
 pub struct MockDemoRollup {}
 
@@ -78,9 +84,19 @@ impl RollupBlueprint for MockDemoRollup {
             sequencer_client,
         )?;
 
+        crate::openrpc::register_openrpc(&mut rpc_methods)?;
+
         Ok(rpc_methods)
     }
 
+    fn chain_id(&self, rt_genesis: &GenesisConfig<Self::NativeContext, Self::DaSpec>) -> u64 {
+        rt_genesis.chain_id()
+    }
+
+    fn proof_code_commitments(&self) -> HashMap<u32, <Self::Vm as Zkvm>::CodeCommitment> {
+        HashMap::from([(MOCK_DA_GUEST_VERSION, Risc0MethodId::new(risc0::MOCK_DA_ID))])
+    }
+
     async fn create_da_service(
         &self,
         rollup_config: &RollupConfig<Self::DaConfig>,
@@ -0,0 +1,88 @@
+use anyhow::Context as _;
+use citrea_client::openrpc::{openrpc_document, DISCOVER_METHOD};
+use jsonrpsee::types::ErrorObjectOwned;
+
+// register the method serving the OpenRPC document of the Citrea methods.
+pub(crate) fn register_openrpc(
+    methods: &mut jsonrpsee::RpcModule<()>,
+) -> Result<(), anyhow::Error> {
+    let document = openrpc_document();
+    methods
+        .register_method(DISCOVER_METHOD, move |_, _| {
+            Ok::<_, ErrorObjectOwned>(document.clone())
+        })
+        .context("Failed to register OpenRPC discover method")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use citrea_client::openrpc::documented_methods;
+    use citrea_stf::genesis_config::StorageConfig;
+    use sequencer_client::SequencerClient;
+    use sov_db::ledger_db::LedgerDB;
+    use sov_mock_da::{MockAddress, MockDaService, MockDaSpec};
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_rollup_blueprint::RollupBlueprint;
+    use sov_prover_storage_manager::ProverStorageManager;
+    use sov_rollup_interface::storage::HierarchicalStorageManager;
+    use sov_state::DefaultStorageSpec;
+    use sov_stf_runner::{checkpoint_status_rpc, prover_stats_rpc};
+
+    use crate::MockDemoRollup;
+
+    // the other namespaces follow the Ethereum JSON-RPC specification
+    const DOCUMENTED_NAMESPACES: [&str; 4] = [
+        "citrea_",
+        "ledger_",
+        "sequencer_",
+        "softConfirmationRuleEnforcer_",
+    ];
+
+    #[tokio::test]
+    async fn test_all_node_methods_are_documented() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(dir.path().join("ledger")).unwrap();
+        let mut storage_manager =
+            ProverStorageManager::<MockDaSpec, DefaultStorageSpec>::new(StorageConfig {
+                path: dir.path().join("state"),
+            })
+            .unwrap();
+        let storage = storage_manager.create_finalized_storage().unwrap();
+        let sequencer_client = SequencerClient::new("http://127.0.0.1:12345".to_string());
+
+        // methods of full nodes and provers, the sequencer ones are registered by the sequencer
+        let mut methods = MockDemoRollup::new()
+            .create_rpc_methods(
+                &storage,
+                &ledger_db,
+                &MockDaService::new(MockAddress::default()),
+                Some(sequencer_client.clone()),
+            )
+            .unwrap();
+        methods.merge(prover_stats_rpc()).unwrap();
+        methods
+            .merge(checkpoint_status_rpc::<DefaultContext>(
+                ledger_db,
+                sequencer_client,
+                vec![],
+            ))
+            .unwrap();
+
+        let documented: BTreeSet<String> = documented_methods().into_iter().collect();
+        // Subscriptions can't be described by OpenRPC
+        let undocumented: Vec<&str> = methods
+            .method_names()
+            .filter(|name| DOCUMENTED_NAMESPACES.iter().any(|ns| name.starts_with(ns)))
+            .filter(|name| !name.contains("subscribe"))
+            .filter(|name| !documented.contains(*name))
+            .collect();
+        assert!(
+            undocumented.is_empty(),
+            "Methods missing from the OpenRPC document: {:?}",
+            undocumented
+        );
+    }
+}
@@ -76,6 +76,7 @@ fn test_helper(test_queries: Vec<TestExpect>, slots: Vec<SlotCommit<MockBlock, u
         let rpc_config = RpcConfig {
             bind_host: "127.0.0.1".to_string(),
             bind_port: addr.port(),
+            response_cache_entries: 0,
         };
 
         queries_test_runner(test_queries, rpc_config).await;
@@ -126,11 +127,13 @@ fn regular_test_helper(payload: serde_json::Value, expected: &serde_json::Value)
                 },
             ],
             phantom_data: PhantomData,
+            soft_confirmation_outcome: None,
         },
         BatchReceipt {
             batch_hash: ::sha2::Sha256::digest(b"batch_receipt2"),
             tx_receipts: batch2_tx_receipts(),
             phantom_data: PhantomData,
+            soft_confirmation_outcome: None,
         },
     ];
 
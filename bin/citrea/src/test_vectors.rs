@@ -0,0 +1,233 @@
+use borsh::BorshSerialize;
+use rs_merkle::algorithms::Sha256;
+use rs_merkle::MerkleTree;
+use serde::{Deserialize, Serialize};
+use sov_mock_da::{MockDaSpec, MockHash, MockValidityCond};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{PrivateKey, Spec};
+use sov_rollup_interface::da::{BatchProof, DaData, SequencerCommitment};
+use sov_rollup_interface::soft_confirmation::{
+    SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+};
+use sov_rollup_interface::zk::StateTransition;
+
+/// Canonical serialized forms and hashes of the core types, for external implementations
+/// such as explorers and verifiers in other languages to check their parsers against.
+///
+/// Byte strings are hex encoded. Serialized forms are borsh, as signed, hashed and written
+/// to DA by the node. Locked by `bin/test-data/test-vectors.json`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TestVectors {
+    pub soft_confirmation: SoftConfirmationVector,
+    pub sequencer_commitment: SequencerCommitmentVector,
+    pub batch_proof: BatchProofVector,
+    pub state_transition: StateTransitionVector,
+}
+
+/// A soft confirmation signed with Ed25519
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SoftConfirmationVector {
+    pub da_slot_height: u64,
+    pub da_slot_hash: String,
+    pub pre_state_root: String,
+    pub txs: Vec<String>,
+    pub l1_fee_rate: u64,
+    /// Omitted from `unsigned_borsh` when not set, as by soft confirmations given before the
+    /// chain required a timestamp
+    pub timestamp: Option<u64>,
+    /// Omitted from `unsigned_borsh` when not set, as by soft confirmations given before the
+    /// chain required an ordering commitment
+    pub ordering_commitment: Option<String>,
+    /// `UnsignedSoftConfirmationBatch`, the message signed by the sequencer
+    pub unsigned_borsh: String,
+    /// SHA-256 of `unsigned_borsh`
+    pub hash: String,
+    /// Ed25519 secret key of the sequencer
+    pub private_key: String,
+    pub public_key: String,
+    /// Ed25519 signature of `unsigned_borsh`
+    pub signature: String,
+    /// `SignedSoftConfirmationBatch`, as served by the sequencer
+    pub signed_borsh: String,
+}
+
+/// A sequencer commitment to the soft confirmations of four L2 blocks
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SequencerCommitmentVector {
+    /// Hashes of the committed soft confirmations, in L2 height order
+    pub soft_confirmation_hashes: Vec<String>,
+    /// SHA-256 Merkle root of `soft_confirmation_hashes`, whose inner nodes hash the
+    /// concatenation of their children
+    pub merkle_root: String,
+    pub l1_start_block_hash: String,
+    pub l1_end_block_hash: String,
+    /// `SequencerCommitment`
+    pub borsh: String,
+    /// `DaData::SequencerCommitment` in its versioned envelope, as written to DA
+    pub da_envelope: String,
+}
+
+/// A proof written to DA
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BatchProofVector {
+    /// `DaData::ZKProof` in its versioned envelope, as written to DA
+    pub da_envelope: String,
+}
+
+/// The public output of a proof, with the types of the mock DA layer
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StateTransitionVector {
+    pub initial_state_root: String,
+    pub final_state_root: String,
+    pub slot_hash: String,
+    pub is_valid: bool,
+    /// `StateTransition<MockDaSpec, [u8; 32]>`
+    pub borsh: String,
+}
+
+const SEQUENCER_PRIVATE_KEY: [u8; 32] = [1; 32];
+const TIMESTAMP: u64 = 1_700_000_000;
+const ORDERING_COMMITMENT: [u8; 32] = [11; 32];
+
+fn unsigned_soft_confirmation(timestamp: u64) -> UnsignedSoftConfirmationBatch {
+    UnsignedSoftConfirmationBatch::new(
+        1,
+        [2; 32],
+        vec![3; 32],
+        vec![vec![4, 5, 6], vec![7]],
+        10,
+        Some(timestamp),
+        Some(ORDERING_COMMITMENT),
+    )
+}
+
+fn hash(data: &[u8]) -> [u8; 32] {
+    <DefaultContext as Spec>::Hasher::digest(data).into()
+}
+
+fn to_borsh<T: BorshSerialize>(value: &T) -> Vec<u8> {
+    value
+        .try_to_vec()
+        .expect("Serialization to vec is infallible")
+}
+
+fn soft_confirmation_vector() -> SoftConfirmationVector {
+    let unsigned = unsigned_soft_confirmation(TIMESTAMP);
+    let unsigned_borsh = to_borsh(&unsigned);
+    let hash = hash(&unsigned_borsh);
+
+    let private_key = DefaultPrivateKey::try_from(SEQUENCER_PRIVATE_KEY.as_slice())
+        .expect("Ed25519 secret keys are 32 bytes");
+    let signature = to_borsh(&private_key.sign(&unsigned_borsh));
+    let public_key = to_borsh(&private_key.pub_key());
+
+    let signed = SignedSoftConfirmationBatch::new(
+        hash,
+        unsigned.da_slot_height(),
+        unsigned.da_slot_hash(),
+        unsigned.pre_state_root(),
+        unsigned.l1_fee_rate(),
+        unsigned.txs(),
+        signature.clone(),
+        public_key.clone(),
+        unsigned.timestamp(),
+        unsigned.ordering_commitment(),
+    );
+
+    SoftConfirmationVector {
+        da_slot_height: unsigned.da_slot_height(),
+        da_slot_hash: hex::encode(unsigned.da_slot_hash()),
+        pre_state_root: hex::encode(unsigned.pre_state_root()),
+        txs: unsigned.txs().iter().map(hex::encode).collect(),
+        l1_fee_rate: unsigned.l1_fee_rate(),
+        timestamp: unsigned.timestamp(),
+        ordering_commitment: unsigned.ordering_commitment().map(hex::encode),
+        unsigned_borsh: hex::encode(&unsigned_borsh),
+        hash: hex::encode(hash),
+        private_key: hex::encode(SEQUENCER_PRIVATE_KEY),
+        public_key: hex::encode(public_key),
+        signature: hex::encode(signature),
+        signed_borsh: hex::encode(to_borsh(&signed)),
+    }
+}
+
+fn sequencer_commitment_vector() -> SequencerCommitmentVector {
+    let hashes: Vec<[u8; 32]> = (0..4)
+        .map(|i| hash(&to_borsh(&unsigned_soft_confirmation(TIMESTAMP + i))))
+        .collect();
+    let merkle_root = MerkleTree::<Sha256>::from_leaves(&hashes)
+        .root()
+        .expect("The tree has leaves");
+    let commitment = SequencerCommitment {
+        merkle_root,
+        l1_start_block_hash: [8; 32],
+        l1_end_block_hash: [9; 32],
+    };
+
+    SequencerCommitmentVector {
+        soft_confirmation_hashes: hashes.iter().map(hex::encode).collect(),
+        merkle_root: hex::encode(commitment.merkle_root),
+        l1_start_block_hash: hex::encode(commitment.l1_start_block_hash),
+        l1_end_block_hash: hex::encode(commitment.l1_end_block_hash),
+        borsh: hex::encode(to_borsh(&commitment)),
+        da_envelope: hex::encode(DaData::SequencerCommitment(commitment).to_envelope()),
+    }
+}
+
+fn batch_proof_vector() -> BatchProofVector {
+    BatchProofVector {
+        da_envelope: hex::encode(
+            DaData::ZKProof(BatchProof {
+                guest_version: 1,
+                l2_start_height: 1,
+                l2_end_height: 4,
+                proof: vec![13, 14, 15],
+            })
+            .to_envelope(),
+        ),
+    }
+}
+
+fn state_transition_vector() -> StateTransitionVector {
+    let transition: StateTransition<MockDaSpec, [u8; 32]> = StateTransition {
+        initial_state_root: [10; 32],
+        final_state_root: [11; 32],
+        slot_hash: MockHash([12; 32]),
+        validity_condition: MockValidityCond { is_valid: true },
+    };
+
+    StateTransitionVector {
+        initial_state_root: hex::encode(transition.initial_state_root),
+        final_state_root: hex::encode(transition.final_state_root),
+        slot_hash: hex::encode(transition.slot_hash.0),
+        is_valid: transition.validity_condition.is_valid,
+        borsh: hex::encode(to_borsh(&transition)),
+    }
+}
+
+/// Returns the test vectors of the core types.
+pub(crate) fn test_vectors() -> TestVectors {
+    TestVectors {
+        soft_confirmation: soft_confirmation_vector(),
+        sequencer_commitment: sequencer_commitment_vector(),
+        batch_proof: batch_proof_vector(),
+        state_transition: state_transition_vector(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails if the serialized form or the hash of a core type changes, which breaks the
+    /// external implementations. Regenerate the file with `citrea test-vectors` only if the
+    /// change is intended.
+    #[test]
+    fn test_vectors_are_locked() {
+        let locked: TestVectors =
+            serde_json::from_str(include_str!("../../test-data/test-vectors.json")).unwrap();
+        assert_eq!(test_vectors(), locked);
+    }
+}
@@ -4,21 +4,31 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use citrea_evm::smart_contracts::SimpleStorageContract;
+use citrea_sequencer::{
+    FileLease, SequencerConfig, SequencerLease, SequencerLeaseConfig, StandbyConfig,
+};
 use citrea_stf::genesis_config::GenesisPaths;
 use ethereum_types::H256;
 use ethers::abi::Address;
 use reth_primitives::{BlockNumberOrTag, TxHash};
+use sov_db::native_db::NativeDB;
+use sov_db::state_db::StateDB;
+use sov_mock_da::chaos::{self, Fault};
 use sov_mock_da::{MockAddress, MockDaService, MockDaSpec, MockHash};
 use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisPaths;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::rpc::SoftConfirmationStatus;
+use sov_schema_db::snapshot::NoopQueryManager;
 use sov_stf_runner::RollupProverConfig;
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 
 use crate::evm::{init_test_rollup, make_test_client};
 use crate::test_client::TestClient;
-use crate::test_helpers::{start_rollup, NodeMode};
+use crate::test_helpers::{
+    create_default_sequencer_config, start_rollup, start_rollup_with_sequencer_config,
+    wait_for_l2_block, NodeMode,
+};
 use crate::DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT;
 
 struct TestConfig {
@@ -220,6 +230,64 @@ async fn test_full_node_send_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// The full node replays the blocks of the sequencer with their transactions, whose hashes
+/// are recorded by the EVM in the same way on both nodes
+#[tokio::test]
+async fn test_full_node_replays_blocks_with_transactions() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let (seq_test_client, full_node_test_client, seq_task, full_node_task, addr) =
+        initialize_test(Default::default()).await;
+
+    let mut tx_hashes = vec![];
+    for block in 1..=2 {
+        for _ in 0..5 {
+            let tx = seq_test_client
+                .send_eth(addr, None, None, None, 0u128)
+                .await
+                .unwrap();
+            tx_hashes.push(tx.tx_hash());
+        }
+        seq_test_client.send_publish_batch_request().await;
+        wait_for_l2_block(&full_node_test_client, block, Duration::from_secs(30)).await;
+    }
+
+    for block in 1..=2 {
+        let seq_block = seq_test_client
+            .eth_get_block_by_number(Some(BlockNumberOrTag::Number(block)))
+            .await;
+        let full_node_block = full_node_test_client
+            .eth_get_block_by_number(Some(BlockNumberOrTag::Number(block)))
+            .await;
+
+        let block_tx_hashes = &tx_hashes[(block as usize - 1) * 5..block as usize * 5];
+        assert_eq!(seq_block.transactions, block_tx_hashes);
+        assert_eq!(full_node_block.transactions, block_tx_hashes);
+        assert_eq!(seq_block.state_root, full_node_block.state_root);
+        assert_eq!(seq_block.hash, full_node_block.hash);
+
+        // The full node verified the signed ordering commitment of the soft batch
+        let seq_soft_batch = seq_test_client
+            .ledger_get_soft_batch_by_number::<MockDaSpec>(block)
+            .await
+            .unwrap();
+        let full_node_soft_batch = full_node_test_client
+            .ledger_get_soft_batch_by_number::<MockDaSpec>(block)
+            .await
+            .unwrap();
+        assert!(seq_soft_batch.ordering_commitment.is_some());
+        assert_eq!(
+            seq_soft_batch.ordering_commitment,
+            full_node_soft_batch.ordering_commitment
+        );
+    }
+
+    seq_task.abort();
+    full_node_task.abort();
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delayed_sync_ten_blocks() -> Result<(), anyhow::Error> {
     // citrea::initialize_logging();
@@ -961,6 +1029,19 @@ async fn test_soft_confirmations_status_one_l1() -> Result<(), anyhow::Error> {
         assert_eq!(SoftConfirmationStatus::Finalized, status_node.unwrap());
     }
 
+    // The soft confirmations published fast are all anchored to the same L1 block
+    let l1_height = full_node_test_client
+        .ledger_get_soft_batch_by_number::<MockDaSpec>(1)
+        .await
+        .unwrap()
+        .da_slot_height;
+    let range = full_node_test_client
+        .ledger_get_soft_confirmations_by_l1_height(l1_height)
+        .await
+        .unwrap();
+    assert_eq!(range.start, 1);
+    assert!(range.end >= 6);
+
     seq_task.abort();
     full_node_task.abort();
 
@@ -1047,3 +1128,629 @@ async fn test_soft_confirmations_status_two_l1() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_full_node_recovers_from_dropped_connections() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let (seq_test_client, full_node_test_client, seq_task, full_node_task, _) =
+        initialize_test(Default::default()).await;
+
+    // The next requests of the full node to the sequencer fail with connection errors
+    chaos::inject(Fault::RpcConnectionDrop, 3);
+
+    for _ in 0..3 {
+        seq_test_client.send_publish_batch_request().await;
+    }
+
+    // every dropped request is retried after a delay
+    wait_for_l2_block(&full_node_test_client, 3, Duration::from_secs(60)).await;
+    assert_eq!(chaos::remaining(Fault::RpcConnectionDrop), 0);
+
+    let seq_block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    let full_node_block = full_node_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+
+    assert_eq!(seq_block.number.unwrap().as_u64(), 3);
+    assert_eq!(seq_block.number, full_node_block.number);
+    assert_eq!(seq_block.state_root, full_node_block.state_root);
+    assert_eq!(seq_block.hash, full_node_block.hash);
+
+    seq_task.abort();
+    full_node_task.abort();
+
+    Ok(())
+}
+
+/// The sequencer fails after persisting the state of a soft confirmation, before writing it
+/// to the ledger. After a restart the state is rolled back and the chain continues from the
+/// last soft confirmation in the ledger.
+#[tokio::test]
+async fn test_sequencer_recovers_from_ledger_write_error() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let _ = fs::remove_dir_all(Path::new("demo_data_test_ledger_write_error"));
+    let _ = fs::remove_dir_all(Path::new("demo_data_test_ledger_write_error_copy"));
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            Some("demo_data_test_ledger_write_error"),
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let seq_test_client = make_test_client(seq_port).await;
+
+    seq_test_client.send_publish_batch_request().await;
+    let block_1 = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block_1.number.unwrap().as_u64(), 1);
+
+    chaos::inject(Fault::LedgerWriteError, 1);
+    seq_test_client.send_publish_batch_request().await;
+
+    // The sequencer stops instead of serving a soft confirmation missing from the ledger
+    let seq_result = tokio::time::timeout(Duration::from_secs(10), seq_task).await;
+    assert!(matches!(seq_result, Ok(Err(e)) if e.is_panic()));
+    assert_eq!(chaos::remaining(Fault::LedgerWriteError), 0);
+
+    // Copy the db to a new path with the same contents because
+    // the lock is not released on the db directory by the rpc server
+    copy_dir_recursive(
+        Path::new("demo_data_test_ledger_write_error"),
+        Path::new("demo_data_test_ledger_write_error_copy"),
+    )?;
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            Some("demo_data_test_ledger_write_error_copy"),
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let seq_test_client = make_test_client(seq_port).await;
+
+    let seq_block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(seq_block.hash, block_1.hash);
+    assert_eq!(seq_block.state_root, block_1.state_root);
+
+    seq_test_client.send_publish_batch_request().await;
+    assert_eq!(seq_test_client.eth_block_number().await, 2);
+
+    // A full node re-executing the chain arrives at the same state
+    let (full_node_port_tx, full_node_port_rx) = tokio::sync::oneshot::channel();
+
+    let full_node_task = tokio::spawn(async move {
+        start_rollup(
+            full_node_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::FullNode(seq_port),
+            None,
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    let full_node_port = full_node_port_rx.await.unwrap();
+    let full_node_test_client = make_test_client(full_node_port).await;
+
+    wait_for_l2_block(&full_node_test_client, 2, Duration::from_secs(60)).await;
+
+    let seq_block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    let full_node_block = full_node_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(seq_block.state_root, full_node_block.state_root);
+    assert_eq!(seq_block.hash, full_node_block.hash);
+
+    seq_task.abort();
+    full_node_task.abort();
+
+    fs::remove_dir_all(Path::new("demo_data_test_ledger_write_error_copy")).unwrap();
+    fs::remove_dir_all(Path::new("demo_data_test_ledger_write_error")).unwrap();
+
+    Ok(())
+}
+
+/// The state of the last soft confirmation of the sequencer is lost, for example because it
+/// was not flushed before a crash. After a restart the soft confirmation is rolled back and
+/// its transactions are put back into the mempool.
+#[tokio::test]
+async fn test_sequencer_resubmits_unwound_soft_batch() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let _ = fs::remove_dir_all(Path::new("demo_data_test_unwound_soft_batch"));
+    let _ = fs::remove_dir_all(Path::new("demo_data_test_unwound_soft_batch_copy"));
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            Some("demo_data_test_unwound_soft_batch"),
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let seq_test_client = make_test_client(seq_port).await;
+
+    seq_test_client.send_publish_batch_request().await;
+    let block_1 = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let pending_tx = seq_test_client
+        .send_eth(addr, None, None, None, 1_000_000_000u128)
+        .await
+        .unwrap();
+    let tx_hash = pending_tx.tx_hash();
+    seq_test_client.send_publish_batch_request().await;
+
+    let block_2 = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block_2.number.unwrap().as_u64(), 2);
+    assert!(block_2.transactions.contains(&tx_hash));
+
+    seq_task.abort();
+    sleep(Duration::from_secs(1)).await;
+
+    // Copy the db to a new path with the same contents because
+    // the lock is not released on the db directory even though the task is aborted
+    copy_dir_recursive(
+        Path::new("demo_data_test_unwound_soft_batch"),
+        Path::new("demo_data_test_unwound_soft_batch_copy"),
+    )?;
+
+    // Drop the state of soft batch 2, stored at version 3, and its accessory state
+    {
+        let storage_path = Path::new("demo_data_test_unwound_soft_batch_copy");
+        let state_db = StateDB::<NoopQueryManager>::setup_schema_db(storage_path)?;
+        StateDB::<NoopQueryManager>::rollback_version(&state_db, 3)?;
+        let native_db = NativeDB::<NoopQueryManager>::setup_schema_db(storage_path)?;
+        NativeDB::<NoopQueryManager>::rollback_version(&native_db, 2)?;
+    }
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            Some("demo_data_test_unwound_soft_batch_copy"),
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let seq_test_client = make_test_client(seq_port).await;
+
+    // Soft batch 2 was rolled back and its transaction is pending again
+    let seq_block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(seq_block.hash, block_1.hash);
+    let tx = seq_test_client
+        .eth_get_transaction_by_hash(tx_hash, Some(true))
+        .await
+        .unwrap();
+    assert_eq!(tx.hash, tx_hash);
+
+    seq_test_client.send_publish_batch_request().await;
+    let seq_block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(seq_block.number.unwrap().as_u64(), 2);
+    assert!(seq_block.transactions.contains(&tx_hash));
+
+    seq_task.abort();
+
+    fs::remove_dir_all(Path::new("demo_data_test_unwound_soft_batch_copy")).unwrap();
+    fs::remove_dir_all(Path::new("demo_data_test_unwound_soft_batch")).unwrap();
+
+    Ok(())
+}
+
+/// Blocks whose working set grows over the limit are produced again with half of their
+/// transactions, down to a single one, and the others stay in the mempool. A single
+/// transaction over the limit is evicted, and the block is produced without it.
+#[tokio::test]
+async fn test_working_set_limit_splits_block() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup_with_sequencer_config(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            None,
+            SequencerConfig {
+                // Every transaction exceeds it
+                max_working_set_bytes: Some(1),
+                ..create_default_sequencer_config(DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT)
+            },
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let seq_test_client = make_test_client(seq_port).await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let mut tx_hashes = vec![];
+    for nonce in 0..4 {
+        let tx = seq_test_client
+            .send_eth(addr, None, None, Some(nonce), 0u128)
+            .await
+            .unwrap();
+        tx_hashes.push(tx.tx_hash());
+    }
+
+    // 4 transactions are retried as 2, then as 1, which is evicted
+    seq_test_client.send_publish_batch_request().await;
+    let block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block.number.unwrap().as_u64(), 1);
+    assert!(block.transactions.is_empty());
+    assert!(seq_test_client
+        .eth_get_transaction_by_hash(tx_hashes[0], Some(true))
+        .await
+        .is_none());
+
+    // The others stay in the mempool
+    for tx_hash in &tx_hashes[1..] {
+        assert!(seq_test_client
+            .eth_get_transaction_by_hash(*tx_hash, Some(true))
+            .await
+            .is_some());
+    }
+
+    seq_task.abort();
+
+    Ok(())
+}
+
+async fn start_dry_running_sequencer() -> (Box<TestClient>, JoinHandle<()>) {
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup_with_sequencer_config(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            None,
+            SequencerConfig {
+                dry_run_blocks: true,
+                ..create_default_sequencer_config(DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT)
+            },
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    (make_test_client(seq_port).await, seq_task)
+}
+
+/// Blocks matching their dry run are published
+#[tokio::test]
+async fn test_dry_run_accepts_block() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let (seq_test_client, seq_task) = start_dry_running_sequencer().await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let tx_hash = seq_test_client
+        .send_eth(addr, None, None, None, 0u128)
+        .await
+        .unwrap()
+        .tx_hash();
+
+    seq_test_client.send_publish_batch_request().await;
+    let block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block.number.unwrap().as_u64(), 1);
+    assert_eq!(block.transactions, vec![tx_hash]);
+
+    // The inclusion is recorded once the block is committed
+    let latency = seq_test_client.citrea_get_latency_summary().await;
+    assert_eq!(latency.inclusion.samples, 1);
+
+    seq_task.abort();
+
+    Ok(())
+}
+
+/// A block whose dry run differs from its execution is not published, and its offending
+/// transactions are evicted instead of being counted as included
+#[tokio::test]
+async fn test_dry_run_mismatch_does_not_publish_block() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let (seq_test_client, seq_task) = start_dry_running_sequencer().await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let tx_hash = seq_test_client
+        .send_eth(addr, None, None, None, 0u128)
+        .await
+        .unwrap()
+        .tx_hash();
+
+    chaos::inject(Fault::DryRunMismatch, 1);
+    seq_test_client.send_publish_batch_request().await;
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(chaos::remaining(Fault::DryRunMismatch), 0);
+    let block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block.number.unwrap().as_u64(), 0);
+
+    // The offending transaction is evicted and its inclusion is not recorded
+    assert!(seq_test_client
+        .eth_get_transaction_by_hash(tx_hash, Some(true))
+        .await
+        .is_none());
+    let latency = seq_test_client.citrea_get_latency_summary().await;
+    assert_eq!(latency.inclusion.samples, 0);
+
+    // The next block is built without it
+    seq_test_client.send_publish_batch_request().await;
+    let block = seq_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(block.number.unwrap().as_u64(), 1);
+    assert!(block.transactions.is_empty());
+
+    seq_task.abort();
+
+    Ok(())
+}
+
+async fn start_leased_sequencer(
+    lease: SequencerLeaseConfig,
+    standby: Option<StandbyConfig>,
+) -> (Box<TestClient>, JoinHandle<()>) {
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup_with_sequencer_config(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            None,
+            SequencerConfig {
+                lease,
+                standby,
+                ..create_default_sequencer_config(DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT)
+            },
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    (make_test_client(seq_port).await, seq_task)
+}
+
+fn standby_of(primary_test_client: &TestClient) -> StandbyConfig {
+    StandbyConfig {
+        primary_url: format!("http://localhost:{}", primary_test_client.rpc_addr.port()),
+        poll_interval_ms: 100,
+    }
+}
+
+/// A standby follows the primary and takes over block production with its mempool once
+/// the primary stops
+#[tokio::test]
+async fn test_standby_takes_over_when_primary_stops() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let lease_dir = tempfile::tempdir()?;
+    let lease = SequencerLeaseConfig {
+        path: Some(lease_dir.path().join("sequencer.lock")),
+        ttl_secs: 2,
+        force: false,
+    };
+
+    let (primary_test_client, primary_task) = start_leased_sequencer(lease.clone(), None).await;
+    let (standby_test_client, standby_task) =
+        start_leased_sequencer(lease, Some(standby_of(&primary_test_client))).await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    for nonce in 0..2 {
+        primary_test_client
+            .send_eth(addr, None, None, Some(nonce), 0u128)
+            .await
+            .unwrap();
+        primary_test_client.send_publish_batch_request().await;
+    }
+
+    // The standby follows the primary without producing blocks itself
+    standby_test_client.send_publish_batch_request().await;
+    for _ in 0..100 {
+        if standby_test_client.eth_block_number().await >= 2 {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    let primary_head = primary_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    let standby_head = standby_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(primary_head.number.unwrap().as_u64(), 2);
+    assert_eq!(standby_head.hash, primary_head.hash);
+    assert_eq!(standby_head.state_root, primary_head.state_root);
+
+    // Sent to the primary and mirrored by the standby before the primary stops
+    let pending_tx = primary_test_client
+        .send_eth(addr, None, None, Some(2), 0u128)
+        .await
+        .unwrap();
+    for _ in 0..100 {
+        if standby_test_client
+            .eth_get_transaction_by_hash(pending_tx.tx_hash(), Some(true))
+            .await
+            .is_some()
+        {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    primary_task.abort();
+
+    let mut taken_over = false;
+    for _ in 0..100 {
+        standby_test_client.send_publish_batch_request().await;
+        if standby_test_client.eth_block_number().await > 2 {
+            taken_over = true;
+            break;
+        }
+    }
+    assert!(taken_over, "Standby did not take over block production");
+
+    // Block production continues from the head of the primary, with its mempool
+    let block = standby_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Number(3)))
+        .await;
+    assert_eq!(block.parent_hash, primary_head.hash.unwrap());
+    assert_eq!(block.transactions, vec![pending_tx.tx_hash()]);
+
+    standby_task.abort();
+
+    Ok(())
+}
+
+/// An idle primary keeps renewing its lease, so the standby does not take over, and stops
+/// as soon as the lease is taken over instead of forking the chain
+#[tokio::test]
+async fn test_idle_primary_keeps_lease() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+
+    let lease_dir = tempfile::tempdir()?;
+    let lease_path = lease_dir.path().join("sequencer.lock");
+    let lease = SequencerLeaseConfig {
+        path: Some(lease_path.clone()),
+        ttl_secs: 2,
+        force: false,
+    };
+
+    let (primary_test_client, primary_task) = start_leased_sequencer(lease.clone(), None).await;
+    let (standby_test_client, standby_task) =
+        start_leased_sequencer(lease, Some(standby_of(&primary_test_client))).await;
+
+    primary_test_client.send_publish_batch_request().await;
+    for _ in 0..100 {
+        if standby_test_client.eth_block_number().await >= 1 {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(standby_test_client.eth_block_number().await, 1);
+
+    // The primary produces no blocks for several lease TTLs
+    sleep(Duration::from_secs(6)).await;
+
+    // The standby is still following, so it does not produce blocks itself
+    standby_test_client.send_publish_batch_request().await;
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(standby_test_client.eth_block_number().await, 1);
+
+    // The primary still produces blocks, which the standby follows
+    primary_test_client.send_publish_batch_request().await;
+    let primary_head = primary_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(primary_head.number.unwrap().as_u64(), 2);
+    for _ in 0..100 {
+        if standby_test_client.eth_block_number().await >= 2 {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    let standby_head = standby_test_client
+        .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+        .await;
+    assert_eq!(standby_head.hash, primary_head.hash);
+
+    // The idle primary fails to renew the lease once it is taken over, and stops
+    standby_task.abort();
+    let mut other = FileLease::new(&lease_path);
+    other.acquire(Duration::from_secs(60), true)?;
+    let primary_result = tokio::time::timeout(Duration::from_secs(10), primary_task).await;
+    assert!(matches!(primary_result, Ok(Err(e)) if e.is_panic()));
+
+    Ok(())
+}
@@ -172,5 +172,22 @@ async fn execute(
         .reward
         .is_some());
 
+    // percentiles out of the predefined set are calculated from the blocks, the pending block
+    // is the next one on top of the latest
+    let pending_fee_history = client
+        .eth_fee_history(
+            "0x100".to_string(),
+            BlockNumberOrTag::Pending,
+            Some(vec![12.34, 99.99]),
+        )
+        .await;
+    assert_eq!(
+        pending_fee_history.gas_used_ratio,
+        latest_fee_history.gas_used_ratio
+    );
+    let rewards = pending_fee_history.reward.unwrap();
+    assert_eq!(rewards.len(), latest_fee_history.gas_used_ratio.len());
+    assert!(rewards.iter().all(|block_rewards| block_rewards.len() == 2));
+
     Ok(())
 }
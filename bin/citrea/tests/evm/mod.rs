@@ -3,10 +3,14 @@ use std::str::FromStr;
 
 // use citrea::initialize_logging;
 use citrea_evm::smart_contracts::{LogsContract, SimpleStorageContract, TestContract};
+use citrea_evm::LogResponse;
 use citrea_stf::genesis_config::GenesisPaths;
 use ethers_core::abi::Address;
 use ethers_core::types::{BlockId, Bytes, U256};
 use ethers_signers::{LocalWallet, Signer};
+use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::WsClientBuilder;
 use reth_primitives::BlockNumberOrTag;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisPaths;
 use sov_stf_runner::RollupProverConfig;
@@ -59,6 +63,82 @@ async fn web3_rpc_tests() -> Result<(), anyhow::Error> {
         "0x47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad".to_string()
     );
 
+    // Legacy mining and uncle endpoints answer with empty values
+    assert!(!test_client.eth_mining().await);
+    assert_eq!(
+        test_client
+            .eth_get_uncle_count_by_block_number(BlockNumberOrTag::Latest)
+            .await,
+        Some(U256::zero())
+    );
+    assert_eq!(
+        test_client
+            .eth_get_uncle_count_by_block_number(BlockNumberOrTag::Number(1000))
+            .await,
+        None
+    );
+
+    rollup_task.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_eth_subscribe_new_heads() -> Result<(), anyhow::Error> {
+    // citrea::initialize_logging();
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            NodeMode::SequencerNode,
+            None,
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    // Wait for rollup task to start:
+    let port = port_rx.await.unwrap();
+
+    let test_client = make_test_client(port).await;
+    let ws_client = WsClientBuilder::default()
+        .build(format!("ws://localhost:{}", port.port()))
+        .await?;
+
+    // Unknown subscriptions are rejected
+    assert!(ws_client
+        .subscribe::<serde_json::Value, _>(
+            "eth_subscribe",
+            rpc_params!["newBlocks"],
+            "eth_unsubscribe"
+        )
+        .await
+        .is_err());
+
+    let mut new_heads: Subscription<reth_rpc_types::Header> = ws_client
+        .subscribe("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+        .await?;
+
+    for _ in 0..2 {
+        test_client.send_publish_batch_request().await;
+
+        let header = new_heads.next().await.unwrap()?;
+        let block = test_client
+            .eth_get_block_by_number(Some(BlockNumberOrTag::Latest))
+            .await;
+        assert_eq!(header.hash.unwrap().0, block.hash.unwrap().0);
+        assert_eq!(
+            header.number.unwrap().to::<u64>(),
+            block.number.unwrap().as_u64()
+        );
+    }
+
+    new_heads.unsubscribe().await?;
     rollup_task.abort();
     Ok(())
 }
@@ -132,6 +212,145 @@ async fn test_eth_get_logs() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_eth_subscribe_logs() -> Result<(), anyhow::Error> {
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            NodeMode::SequencerNode,
+            None,
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    // Wait for rollup task to start:
+    let port = port_rx.await.unwrap();
+
+    let client = init_test_rollup(port).await;
+    let contract = LogsContract::default();
+    let deploy_contract_req = client
+        .deploy_contract(contract.byte_code(), None)
+        .await
+        .unwrap();
+    client.send_publish_batch_request().await;
+    let contract_address = deploy_contract_req
+        .await?
+        .unwrap()
+        .contract_address
+        .unwrap();
+
+    let ws_client = WsClientBuilder::default()
+        .build(format!("ws://localhost:{}", port.port()))
+        .await?;
+    let filter = serde_json::json!({
+        "address": contract_address,
+        "topics": [
+            "0xa9943ee9804b5d456d8ad7b3b1b975a5aefa607e16d13936959976e776c4bec7"
+        ]
+    });
+    let mut logs: Subscription<LogResponse> = ws_client
+        .subscribe(
+            "eth_subscribe",
+            rpc_params!["logs", filter],
+            "eth_unsubscribe",
+        )
+        .await?;
+
+    // The event emits two logs, only the first one has the topic
+    client
+        .contract_transaction(
+            contract_address,
+            contract.publish_event("hello".to_string()),
+            None,
+        )
+        .await;
+    client.send_publish_batch_request().await;
+
+    let log = logs.next().await.unwrap()?;
+    assert_eq!(log.address.as_slice(), contract_address.as_bytes());
+    assert_eq!(
+        hex::encode(log.topics[0]),
+        "a9943ee9804b5d456d8ad7b3b1b975a5aefa607e16d13936959976e776c4bec7"
+    );
+    assert_eq!(
+        log.block_number,
+        Some(reth_primitives::U256::from(client.eth_block_number().await))
+    );
+
+    logs.unsubscribe().await?;
+    rollup_task.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_eth_subscribe_new_pending_transactions() -> Result<(), anyhow::Error> {
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            NodeMode::SequencerNode,
+            None,
+            DEFAULT_MIN_SOFT_CONFIRMATIONS_PER_COMMITMENT,
+            true,
+        )
+        .await;
+    });
+
+    // Wait for rollup task to start:
+    let port = port_rx.await.unwrap();
+
+    let client = init_test_rollup(port).await;
+    let ws_client = WsClientBuilder::default()
+        .build(format!("ws://localhost:{}", port.port()))
+        .await?;
+    let mut hashes: Subscription<reth_primitives::B256> = ws_client
+        .subscribe(
+            "eth_subscribe",
+            rpc_params!["newPendingTransactions"],
+            "eth_unsubscribe",
+        )
+        .await?;
+    let mut transactions: Subscription<serde_json::Value> = ws_client
+        .subscribe(
+            "eth_subscribe",
+            rpc_params!["newPendingTransactions", true],
+            "eth_unsubscribe",
+        )
+        .await?;
+
+    let to = Address::from_str("0x0000000000000000000000000000000000000001").unwrap();
+    let pending_tx = client.send_eth(to, None, None, None, 1).await?;
+    let tx_hash = pending_tx.tx_hash();
+
+    let hash = hashes.next().await.unwrap()?;
+    assert_eq!(hash.as_slice(), tx_hash.as_bytes());
+    let transaction = transactions.next().await.unwrap()?;
+    assert_eq!(
+        transaction["hash"],
+        serde_json::json!(format!("{:?}", tx_hash))
+    );
+
+    hashes.unsubscribe().await?;
+    transactions.unsubscribe().await?;
+    rollup_task.abort();
+    Ok(())
+}
+
 #[allow(clippy::borrowed_box)]
 async fn test_getlogs(client: &Box<TestClient>) -> Result<(), Box<dyn std::error::Error>> {
     let (contract_address, contract) = {
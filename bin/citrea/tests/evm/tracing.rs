@@ -5,6 +5,7 @@ use citrea_evm::smart_contracts::{CallerContract, SimpleStorageContract};
 use citrea_stf::genesis_config::GenesisPaths;
 use ethers::abi::Address;
 use reth_primitives::BlockNumberOrTag;
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::GethTrace::{self, CallTracer, FourByteTracer};
 use reth_rpc_types::trace::geth::{
     CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
@@ -217,6 +218,17 @@ async fn tracing_tests() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(traces[1], CallTracer(expected_send_eth_trace.clone()));
     assert_eq!(traces[0], CallTracer(expected_call_get_trace.clone()));
 
+    // The genesis block has no transactions
+    let traces = test_client
+        .debug_trace_block_by_number(
+            BlockNumberOrTag::Earliest,
+            Some(GethDebugTracingOptions::default().with_tracer(
+                GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer),
+            )),
+        )
+        .await;
+    assert!(traces.is_empty());
+
     let block_hash = test_client
         .eth_get_block_by_number(Some(BlockNumberOrTag::Number(3)))
         .await
@@ -293,6 +305,54 @@ async fn tracing_tests() -> Result<(), Box<dyn std::error::Error>> {
         CallTracer(expected_top_call_only_call_get_trace)
     );
 
+    // Only the internal calls of the caller contract reach the simple storage contract
+    let ss_address = reth_primitives::Address::from_slice(ss_contract_address.as_ref());
+    let filter = TraceFilter {
+        from_block: Some(2),
+        to_block: Some(3),
+        to_address: vec![ss_address],
+        ..Default::default()
+    };
+    let traces = test_client.trace_filter(filter.clone()).await;
+    assert_eq!(traces.len(), 2);
+    assert_eq!(traces[0].transaction_hash, Some(tx_hash.0.into()));
+    assert_eq!(traces[0].trace.trace_address, vec![0]);
+    assert_eq!(traces[1].transaction_hash, Some(call_tx_hash.0.into()));
+
+    let traces = test_client
+        .trace_filter(TraceFilter {
+            after: Some(1),
+            count: Some(1),
+            ..filter
+        })
+        .await;
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0].transaction_hash, Some(call_tx_hash.0.into()));
+
+    // The set call of the caller contract and its internal call
+    let tx_traces = test_client.trace_transaction(tx_hash).await.unwrap();
+    assert_eq!(tx_traces.len(), 2);
+    assert_eq!(tx_traces[0].trace.trace_address, Vec::<usize>::new());
+    assert_eq!(tx_traces[0].trace.subtraces, 1);
+    assert_eq!(tx_traces[1].trace.trace_address, vec![0]);
+
+    // The get call with its internal call, and the eth transfer
+    let block_number = traces[0].block_number.unwrap();
+    let block_traces = test_client
+        .trace_block(BlockNumberOrTag::Number(block_number))
+        .await
+        .unwrap();
+    assert_eq!(block_traces.len(), 3);
+    assert_eq!(block_traces[1], traces[0]);
+    assert_eq!(
+        block_traces[2].transaction_hash,
+        Some(send_eth_tx_hash.0.into())
+    );
+    assert!(test_client
+        .trace_block(BlockNumberOrTag::Number(u32::MAX as u64))
+        .await
+        .is_none());
+
     rollup_task.abort();
     Ok(())
 }
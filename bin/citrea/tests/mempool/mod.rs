@@ -3,7 +3,7 @@ use std::str::FromStr;
 use citrea_stf::genesis_config::GenesisPaths;
 use ethers::abi::Address;
 use ethers_signers::{LocalWallet, Signer};
-use reth_primitives::BlockNumberOrTag;
+use reth_primitives::{BlockNumberOrTag, U256, U64};
 use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisPaths;
 use sov_stf_runner::RollupProverConfig;
 use tokio::task::JoinHandle;
@@ -128,6 +128,49 @@ async fn test_nonce_too_high() {
     seq_task.abort();
 }
 
+/// The txpool namespace serves the executable transactions as pending and the ones with a
+/// nonce gap as queued, grouped by sender and nonce.
+#[tokio::test]
+async fn test_txpool_rpcs() {
+    let (seq_task, test_client) = initialize_test().await;
+
+    let addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let sender = reth_primitives::Address::from_slice(test_client.from_addr.as_bytes());
+
+    for nonce in [0, 1, 3] {
+        test_client
+            .send_eth(addr, None, None, Some(nonce), 0u128)
+            .await
+            .unwrap();
+    }
+
+    let status = test_client.txpool_status().await;
+    assert_eq!(status.pending, U64::from(2));
+    assert_eq!(status.queued, U64::from(1));
+
+    let content = test_client.txpool_content().await;
+    let pending_nonces: Vec<_> = content.pending[&sender].keys().cloned().collect();
+    assert_eq!(pending_nonces, vec!["0", "1"]);
+    let queued_nonces: Vec<_> = content.queued[&sender].keys().cloned().collect();
+    assert_eq!(queued_nonces, vec!["3"]);
+
+    let inspect = test_client.txpool_inspect().await;
+    let summary = &inspect.queued[&sender]["3"];
+    assert_eq!(
+        summary.to,
+        Some(reth_primitives::Address::from_slice(addr.as_bytes()))
+    );
+    assert_eq!(summary.gas, U256::from(900000));
+
+    // The pending transactions leave the pool once included
+    test_client.send_publish_batch_request().await;
+    let status = test_client.txpool_status().await;
+    assert_eq!(status.pending, U64::ZERO);
+    assert_eq!(status.queued, U64::from(1));
+
+    seq_task.abort();
+}
+
 #[tokio::test]
 async fn test_order_by_fee() {
     let (seq_task, test_client) = initialize_test().await;
@@ -189,6 +232,23 @@ async fn test_order_by_fee() {
     assert!(block.transactions[0] == tx_hash_rich.tx_hash());
     assert!(block.transactions[1] == tx_hash_poor.tx_hash());
 
+    // the soft confirmation commits to the order the txs were received in, not the block order
+    let l2_height = block.number.unwrap().as_u64();
+    let received = [tx_hash_poor.tx_hash().0, tx_hash_rich.tx_hash().0];
+    assert_eq!(
+        test_client
+            .ledger_verify_ordering_commitment(l2_height, &received)
+            .await,
+        Some(true)
+    );
+    let block_order = [tx_hash_rich.tx_hash().0, tx_hash_poor.tx_hash().0];
+    assert_eq!(
+        test_client
+            .ledger_verify_ordering_commitment(l2_height, &block_order)
+            .await,
+        Some(false)
+    );
+
     // now change the order the txs are sent, the assertions should be the same
     let tx_hash_rich = test_client
         .send_eth(
@@ -1,9 +1,9 @@
 use std::time::Duration;
 
-use borsh::BorshDeserialize;
 use citrea_stf::genesis_config::GenesisPaths;
 use rs_merkle::algorithms::Sha256;
 use rs_merkle::MerkleTree;
+use sov_mock_da::chaos::{self, Fault};
 use sov_mock_da::{MockAddress, MockDaService, MockDaSpec};
 use sov_modules_api::{BlobReaderTrait, SignedSoftConfirmationBatch};
 use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisPaths;
@@ -114,6 +114,69 @@ async fn sequencer_sends_commitments_to_da_layer() {
     seq_task.abort();
 }
 
+#[tokio::test]
+async fn sequencer_retries_commitment_after_da_submission_error() {
+    // citrea::initialize_logging();
+
+    let (seq_port_tx, seq_port_rx) = tokio::sync::oneshot::channel();
+
+    let seq_task = tokio::spawn(async {
+        start_rollup(
+            seq_port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state:
+                    "../test-data/genesis/integration-tests-low-limiting-number/chain_state.json"
+                        .into(),
+            },
+            RollupProverConfig::Execute,
+            NodeMode::SequencerNode,
+            None,
+            4,
+            true,
+        )
+        .await;
+    });
+
+    let seq_port = seq_port_rx.await.unwrap();
+    let test_client = make_test_client(seq_port).await;
+    let da_service = MockDaService::new(MockAddress::from([0; 32]));
+
+    // the first commitment fails to be submitted
+    chaos::inject(Fault::DaSubmissionError, 1);
+
+    for _ in 0..4 {
+        test_client.send_publish_batch_request().await;
+    }
+    da_service.publish_test_block().await.unwrap();
+    test_client.send_publish_batch_request().await;
+    sleep(Duration::from_secs(1)).await;
+    assert_eq!(chaos::remaining(Fault::DaSubmissionError), 0);
+
+    // no commitment landed on the DA layer
+    let last_finalized_height = da_service
+        .get_last_finalized_block_header()
+        .await
+        .unwrap()
+        .height;
+    let block = da_service
+        .get_block_at(last_finalized_height)
+        .await
+        .unwrap();
+    for mut blob in da_service.extract_relevant_blobs(&block) {
+        assert_eq!(blob.full_data(), &[] as &[u8]);
+    }
+
+    // the commitment is retried with the next L1 block and covers all soft confirmations
+    da_service.publish_test_block().await.unwrap();
+    test_client.send_publish_batch_request().await;
+    sleep(Duration::from_secs(1)).await;
+
+    check_sequencer_commitment(test_client.as_ref(), &da_service, 1, 5, 1).await;
+
+    seq_task.abort();
+}
+
 async fn check_sequencer_commitment(
     test_client: &TestClient,
     da_service: &MockDaService,
@@ -140,7 +203,7 @@ async fn check_sequencer_commitment(
 
     let data = blob.full_data();
 
-    let commitment = DaData::try_from_slice(data).unwrap();
+    let commitment = DaData::from_envelope(data).unwrap().into_known().unwrap();
 
     matches!(commitment, DaData::SequencerCommitment(_));
 
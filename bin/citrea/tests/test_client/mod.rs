@@ -2,6 +2,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
+use citrea_client::{CitreaClient, GetSoftBatchResponse, LatencySummary};
 use citrea_evm::LogResponse;
 use ethereum_types::H160;
 use ethers_core::abi::Address;
@@ -15,9 +16,11 @@ use jsonrpsee::core::client::ClientT;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
 use reth_primitives::BlockNumberOrTag;
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
-use sequencer_client::GetSoftBatchResponse;
-use sov_rollup_interface::rpc::SoftConfirmationStatus;
+use reth_rpc_types::trace::parity::LocalizedTransactionTrace;
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolInspect, TxpoolStatus};
+use sov_rollup_interface::rpc::{SoftConfirmationRange, SoftConfirmationStatus};
 
 pub const MAX_FEE_PER_GAS: u64 = 1000000001;
 const GAS: u64 = 900000u64;
@@ -27,6 +30,7 @@ pub struct TestClient {
     pub(crate) from_addr: Address,
     client: SignerMiddleware<Provider<Http>, Wallet<SigningKey>>,
     http_client: HttpClient,
+    citrea_client: CitreaClient<HttpClient>,
     current_nonce: AtomicU64,
     pub(crate) rpc_addr: std::net::SocketAddr,
 }
@@ -45,13 +49,15 @@ impl TestClient {
             .await
             .unwrap();
 
-        let http_client = HttpClientBuilder::default().build(host).unwrap();
+        let http_client = HttpClientBuilder::default().build(&host).unwrap();
+        let citrea_client = CitreaClient::new_http(&host).unwrap();
 
         let client = Self {
             chain_id,
             from_addr,
             client,
             http_client,
+            citrea_client,
             current_nonce: AtomicU64::new(0),
             rpc_addr,
         };
@@ -62,18 +68,14 @@ impl TestClient {
     pub(crate) async fn spam_publish_batch_request(
         &self,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.http_client
-            .request("eth_publishBatch", rpc_params![])
+        self.citrea_client
+            .publish_batch()
             .await
             .map_err(|e| e.into())
     }
 
     pub(crate) async fn send_publish_batch_request(&self) {
-        let _: () = self
-            .http_client
-            .request("eth_publishBatch", rpc_params![])
-            .await
-            .unwrap();
+        self.citrea_client.publish_batch().await.unwrap();
         // Do not decrease the sleep time, otherwise the test will fail!
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
@@ -271,6 +273,23 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn eth_mining(&self) -> bool {
+        self.http_client
+            .request("eth_mining", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn eth_get_uncle_count_by_block_number(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> Option<ethereum_types::U256> {
+        self.http_client
+            .request("eth_getUncleCountByBlockNumber", rpc_params![block_number])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_accounts(&self) -> Vec<Address> {
         self.http_client
             .request("eth_accounts", rpc_params![])
@@ -514,8 +533,8 @@ impl TestClient {
         &self,
         num: u64,
     ) -> Option<GetSoftBatchResponse> {
-        self.http_client
-            .request("ledger_getSoftBatchByNumber", rpc_params![num])
+        self.citrea_client
+            .get_soft_batch_by_number(num)
             .await
             .unwrap()
     }
@@ -524,25 +543,42 @@ impl TestClient {
         &self,
         soft_batch_receipt: u64,
     ) -> Result<Option<SoftConfirmationStatus>, Box<dyn std::error::Error>> {
-        self.http_client
-            .request(
-                "ledger_getSoftConfirmationStatus",
-                rpc_params![soft_batch_receipt],
-            )
+        self.citrea_client
+            .get_soft_confirmation_status(soft_batch_receipt)
             .await
+            .map(Some)
             .map_err(|e| e.into())
     }
 
-    pub(crate) async fn get_limiting_number(&self) -> u64 {
-        self.http_client
-            .request(
-                "softConfirmationRuleEnforcer_getLimitingNumber",
-                rpc_params![],
-            )
+    pub(crate) async fn ledger_get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Option<SoftConfirmationRange> {
+        self.citrea_client
+            .get_soft_confirmations_by_l1_height(l1_height)
             .await
             .unwrap()
     }
 
+    pub(crate) async fn ledger_verify_ordering_commitment(
+        &self,
+        num: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Option<bool> {
+        self.citrea_client
+            .verify_ordering_commitment(num, tx_hashes)
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn get_limiting_number(&self) -> u64 {
+        self.citrea_client.get_limiting_number().await.unwrap()
+    }
+
+    pub(crate) async fn citrea_get_latency_summary(&self) -> LatencySummary {
+        self.citrea_client.get_latency_summary().await.unwrap()
+    }
+
     pub(crate) async fn debug_trace_transaction(
         &self,
         tx_hash: TxHash,
@@ -576,6 +612,54 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn trace_filter(&self, filter: TraceFilter) -> Vec<LocalizedTransactionTrace> {
+        self.http_client
+            .request("trace_filter", rpc_params![filter])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_block(
+        &self,
+        block_number: BlockNumberOrTag,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.http_client
+            .request("trace_block", rpc_params![block_number])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn trace_transaction(
+        &self,
+        tx_hash: ethereum_types::H256,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        self.http_client
+            .request("trace_transaction", rpc_params![tx_hash])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_content(&self) -> TxpoolContent {
+        self.http_client
+            .request("txpool_content", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_inspect(&self) -> TxpoolInspect {
+        self.http_client
+            .request("txpool_inspect", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn txpool_status(&self) -> TxpoolStatus {
+        self.http_client
+            .request("txpool_status", rpc_params![])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_block_number(&self) -> u64 {
         let block_number: ethereum_types::U256 = self
             .http_client
@@ -1,5 +1,6 @@
 use std::net::SocketAddr;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use citrea::MockDemoRollup;
 use citrea_sequencer::SequencerConfig;
@@ -17,8 +18,11 @@ use sov_stf_runner::{
     SequencerClientRpcConfig, StorageConfig,
 };
 use tokio::sync::oneshot;
+use tokio::time::sleep;
 use tracing::warn;
 
+use crate::test_client::TestClient;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeMode {
     FullNode(SocketAddr),
@@ -37,6 +41,54 @@ pub async fn start_rollup(
     db_path: Option<&str>,
     min_soft_confirmations_per_commitment: u64,
     include_tx_body: bool,
+) {
+    start_rollup_with_sequencer_config(
+        rpc_reporting_channel,
+        rt_genesis_paths,
+        kernel_genesis_paths,
+        rollup_prover_config,
+        node_mode,
+        db_path,
+        create_default_sequencer_config(min_soft_confirmations_per_commitment),
+        include_tx_body,
+    )
+    .await
+}
+
+pub fn create_default_sequencer_config(
+    min_soft_confirmations_per_commitment: u64,
+) -> SequencerConfig {
+    SequencerConfig {
+        min_soft_confirmations_per_commitment,
+        max_working_set_bytes: None,
+        target_da_cost_per_block: None,
+        lease: Default::default(),
+        standby: None,
+        rpc_worker_threads: None,
+        priority_lane: Default::default(),
+        schnorr_private_key: None,
+        proving_claim_ttl_secs: 3600,
+        checkpoint_interval: None,
+        reverting_txs: Default::default(),
+        tx_execution_budget_ms: None,
+        tx_chunk_size: None,
+        dry_run_blocks: false,
+        watchdog: None,
+        min_priority_fee_per_gas: 0,
+    }
+}
+
+/// Starts a node like [`start_rollup`], with `sequencer_config` if it is a sequencer.
+#[allow(clippy::too_many_arguments)]
+pub async fn start_rollup_with_sequencer_config(
+    rpc_reporting_channel: oneshot::Sender<SocketAddr>,
+    rt_genesis_paths: GenesisPaths,
+    kernel_genesis_paths: BasicKernelGenesisPaths,
+    rollup_prover_config: RollupProverConfig,
+    node_mode: NodeMode,
+    db_path: Option<&str>,
+    sequencer_config: SequencerConfig,
+    include_tx_body: bool,
 ) {
     let mut path = db_path.map(Path::new);
     let mut temp_dir: Option<tempfile::TempDir> = None;
@@ -51,6 +103,7 @@ pub async fn start_rollup(
             32, 64, 64, 227, 100, 193, 15, 43, 236, 156, 31, 229, 0, 161, 205, 76, 36, 124, 137,
             214, 80, 160, 30, 215, 232, 44, 171, 168, 103, 135, 124, 33,
         ],
+        sequencer_schnorr_public_key: None,
         storage: StorageConfig {
             path: path.unwrap().to_path_buf(),
         },
@@ -59,13 +112,20 @@ pub async fn start_rollup(
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".into(),
                 bind_port: 0,
+                response_cache_entries: 1024,
             },
+            golden_batches_path: None,
+            prover_id: None,
+            webhooks: vec![],
+            prefetch_state: false,
         },
         da: MockDaConfig {
             sender_address: MockAddress::from([0; 32]),
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            max_memory_bytes: None,
+            memory_per_witness_byte: 16,
         },
         sequencer_client: match node_mode {
             NodeMode::FullNode(socket_addr) | NodeMode::Prover(socket_addr) => {
@@ -76,10 +136,11 @@ pub async fn start_rollup(
             NodeMode::SequencerNode => None,
         },
         include_tx_body,
-    };
-
-    let sequencer_config = SequencerConfig {
-        min_soft_confirmations_per_commitment,
+        telemetry: None,
+        wallet_chain: None,
+        streaming: None,
+        indexer: None,
+        gas_oracle: None,
     };
 
     let mock_demo_rollup = MockDemoRollup {};
@@ -90,6 +151,7 @@ pub async fn start_rollup(
                 .expect("Failed to read chain_state genesis config"),
         )
         .expect("Failed to parse chain_state genesis config"),
+        da_light_client: None,
     };
 
     match node_mode {
@@ -154,3 +216,17 @@ pub async fn start_rollup(
         temp_dir.unwrap().close().unwrap();
     }
 }
+
+/// Polls the node behind `test_client` until it has the L2 block `number`.
+/// Panics if it does not have it within `timeout`.
+pub async fn wait_for_l2_block(test_client: &TestClient, number: u64, timeout: Duration) {
+    let start = Instant::now();
+    while test_client.eth_block_number().await < number {
+        assert!(
+            start.elapsed() < timeout,
+            "Timed out waiting for L2 block {}",
+            number
+        );
+        sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -7,4 +7,6 @@ pub mod spec;
 pub mod service;
 pub mod verifier;
 
+pub use helpers::builders::compress_blob;
+
 const REVEAL_OUTPUT_AMOUNT: u64 = 546;
@@ -6,9 +6,9 @@ use core::time::Duration;
 use async_trait::async_trait;
 use bitcoin::address::NetworkUnchecked;
 use bitcoin::consensus::encode;
-use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::hashes::Hash;
 use bitcoin::secp256k1::SecretKey;
-use bitcoin::{Address, Txid};
+use bitcoin::{Address, Transaction, Txid};
 use hex::ToHex;
 use serde::{Deserialize, Serialize};
 use sov_rollup_interface::da::DaSpec;
@@ -19,12 +19,12 @@ use crate::helpers::builders::{
     compress_blob, create_inscription_transactions, decompress_blob, sign_blob_with_private_key,
     write_reveal_tx,
 };
-use crate::helpers::parsers::parse_transaction;
+use crate::helpers::parsers::{parse_transaction, ParsedInscription};
 use crate::rpc::{BitcoinNode, RPCError};
 use crate::spec::blob::BlobWithSender;
 use crate::spec::block::BitcoinBlock;
 use crate::spec::header_stream::BitcoinHeaderStream;
-use crate::spec::proof::InclusionMultiProof;
+use crate::spec::proof::{merkle_path, BlobInclusionProof, InclusionMultiProof};
 use crate::spec::utxo::UTXO;
 use crate::spec::{BitcoinSpec, RollupParams};
 use crate::verifier::BitcoinVerifier;
@@ -236,6 +236,72 @@ impl BitcoinService {
 
         self.client.estimate_smart_fee().await
     }
+
+    /// Returns the proof that the rollup blob with hash `blob_hash` is part of the block
+    /// at `height`, or `None` if the block does not contain such a blob.
+    pub async fn get_blob_inclusion_proof(
+        &self,
+        height: u64,
+        blob_hash: [u8; 32],
+    ) -> Result<Option<BlobInclusionProof>, anyhow::Error> {
+        let block = self.get_block_at(height).await?;
+        Ok(self.blob_inclusion_proof(&block, blob_hash))
+    }
+
+    fn blob_inclusion_proof(
+        &self,
+        block: &BitcoinBlock,
+        blob_hash: [u8; 32],
+    ) -> Option<BlobInclusionProof> {
+        // The offset is counted over the blobs `extract_relevant_blobs` returns
+        let (tx_index, blob_offset) = block
+            .txdata
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tx)| {
+                self.parse_relevant_inscription(tx)
+                    .map(|(_, hash)| (index, hash))
+            })
+            .enumerate()
+            .find_map(|(offset, (index, hash))| (hash == blob_hash).then_some((index, offset)))?;
+
+        let txids: Vec<[u8; 32]> = block
+            .txdata
+            .iter()
+            .map(|tx| tx.txid().to_raw_hash().to_byte_array())
+            .collect();
+        let tx = &block.txdata[tx_index];
+
+        Some(BlobInclusionProof {
+            txid: tx.txid(),
+            tx_index: tx_index as u32,
+            raw_tx: encode::serialize(tx),
+            header: block.header.to_consensus_bytes(),
+            merkle_path: merkle_path(&txids, tx_index),
+            blob_offset: blob_offset as u32,
+        })
+    }
+
+    // Parses the inscription of `tx` if it is a reveal transaction of the rollup with
+    // a valid signature. Returns the inscription along with the hash of its body.
+    fn parse_relevant_inscription(
+        &self,
+        tx: &Transaction,
+    ) -> Option<(ParsedInscription, [u8; 32])> {
+        if !tx
+            .txid()
+            .to_byte_array()
+            .as_slice()
+            .starts_with(self.reveal_tx_id_prefix.as_slice())
+        {
+            return None;
+        }
+
+        // check if the inscription in script is relevant to the rollup
+        let inscription = parse_transaction(tx, &self.rollup_name).ok()?;
+        let hash = inscription.get_sig_verified_hash()?;
+        Some((inscription, hash))
+    }
 }
 
 #[async_trait]
@@ -332,31 +398,14 @@ impl DaService for BitcoinService {
 
         // iterate over all transactions in the block
         for tx in block.txdata.iter() {
-            if !tx
-                .txid()
-                .to_byte_array()
-                .as_slice()
-                .starts_with(self.reveal_tx_id_prefix.as_slice())
-            {
-                continue;
-            }
-
-            // check if the inscription in script is relevant to the rollup
-            let parsed_inscription = parse_transaction(tx, &self.rollup_name);
-
-            if let Ok(inscription) = parsed_inscription {
-                if inscription.get_sig_verified_hash().is_some() {
-                    // Decompress the blob
-                    let decompressed_blob = decompress_blob(&inscription.body);
+            if let Some((inscription, hash)) = self.parse_relevant_inscription(tx) {
+                // Decompress the blob
+                let decompressed_blob = decompress_blob(&inscription.body);
 
-                    let relevant_tx = BlobWithSender::new(
-                        decompressed_blob,
-                        inscription.public_key,
-                        sha256d::Hash::hash(&inscription.body).to_byte_array(),
-                    );
+                let relevant_tx =
+                    BlobWithSender::new(decompressed_blob, inscription.public_key, hash);
 
-                    txs.push(relevant_tx);
-                }
+                txs.push(relevant_tx);
             }
         }
         txs
@@ -473,7 +522,7 @@ mod tests {
     use bitcoin::secp256k1::Keypair;
     use bitcoin::string::FromHexStr;
     use bitcoin::{BlockHash, CompactTarget, Transaction};
-    use sov_rollup_interface::da::DaVerifier;
+    use sov_rollup_interface::da::{BlobReaderTrait, DaVerifier};
     use sov_rollup_interface::services::da::{DaService, SlotData};
 
     use super::BitcoinService;
@@ -591,6 +640,28 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    async fn blob_inclusion_proof() {
+        let da_service = get_service().await;
+        let (header, _inclusion_proof, _completeness_proof, relevant_txs) = get_mock_data();
+        let block_txs = get_mock_txs();
+
+        let block = BitcoinBlock {
+            header,
+            txdata: block_txs,
+        };
+
+        let last = relevant_txs.len() - 1;
+        let proof = da_service
+            .blob_inclusion_proof(&block, relevant_txs[last].hash())
+            .unwrap();
+
+        assert_eq!(proof.blob_offset as usize, last);
+        assert_eq!(proof.txid, block.txdata[proof.tx_index as usize].txid());
+        assert_eq!(proof.header, block.header.to_consensus_bytes());
+        assert!(da_service.blob_inclusion_proof(&block, [0; 32]).is_none());
+    }
+
     #[tokio::test]
     async fn incorrect_private_key_signature_should_fail() {
         // The transaction was sent with this service and the tx data is stored in false_signature_txs.txt
@@ -9,7 +9,7 @@ use super::block_hash::BlockHashWrapper;
 // BlockHashWrapper is a wrapper around BlockHash to implement BlockHashTrait
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HeaderWrapper {
-    header: Header, // not pub to prevent uses like block.header.header.merkle_root
+    pub(super) header: Header, // not pub to prevent uses like block.header.header.merkle_root
     pub tx_count: u32,
     pub height: u64,
 }
@@ -50,4 +50,9 @@ impl HeaderWrapper {
     pub fn merkle_root(&self) -> TxMerkleNode {
         self.header.merkle_root
     }
+
+    /// Returns the 80 bytes of the header as serialized by Bitcoin
+    pub fn to_consensus_bytes(&self) -> Vec<u8> {
+        bitcoin::consensus::serialize(&self.header)
+    }
 }
@@ -0,0 +1,263 @@
+use bitcoin::block::Header;
+use bitcoin::hashes::Hash;
+use bitcoin::params::Params;
+use bitcoin::{CompactTarget, Network, Target};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlockHeaderTrait, LightClientError, LightClientHeader};
+
+use super::header::HeaderWrapper;
+
+/// Number of blocks between two difficulty adjustments
+const DIFFICULTY_ADJUSTMENT_INTERVAL: u64 = 2016;
+/// Number of blocks used to compute the median time past
+const MEDIAN_TIME_SPAN: usize = 11;
+
+/// State of the Bitcoin light client, enough to validate the header building on the tip.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitcoinChainTip {
+    /// Network whose consensus parameters are used
+    pub network: Network,
+    /// Height of the header at the tip
+    pub height: u64,
+    /// Hash of the header at the tip
+    pub hash: [u8; 32],
+    /// Difficulty bits of the header at the tip
+    pub bits: u32,
+    /// Bits of the last header not mined at minimum difficulty, on networks allowing it
+    pub last_regular_bits: u32,
+    /// Timestamp of the first header of the current difficulty epoch
+    pub epoch_start_time: u32,
+    /// Timestamps of the last headers, oldest first, used for the median time past
+    pub recent_timestamps: Vec<u32>,
+}
+
+impl BitcoinChainTip {
+    /// Starts the light client at a trusted header.
+    /// `epoch_start_time` is the timestamp of the first header of the difficulty epoch of `tip`,
+    /// `recent_timestamps` are the timestamps of the headers up to `tip`, oldest first.
+    pub fn new(
+        network: Network,
+        tip: &HeaderWrapper,
+        epoch_start_time: u32,
+        mut recent_timestamps: Vec<u32>,
+    ) -> Self {
+        let bits = tip.header.bits.to_consensus();
+        let skip = recent_timestamps.len().saturating_sub(MEDIAN_TIME_SPAN);
+        recent_timestamps.drain(..skip);
+        Self {
+            network,
+            height: tip.height,
+            hash: tip.hash().into(),
+            bits,
+            last_regular_bits: bits,
+            epoch_start_time,
+            recent_timestamps,
+        }
+    }
+
+    fn median_time_past(&self) -> u32 {
+        let mut timestamps = self.recent_timestamps.clone();
+        timestamps.sort_unstable();
+        timestamps.get(timestamps.len() / 2).copied().unwrap_or(0)
+    }
+
+    fn last_timestamp(&self) -> u32 {
+        self.recent_timestamps.last().copied().unwrap_or(0)
+    }
+
+    /// Bits `header`, building on the tip, must have
+    fn expected_bits(&self, params: &Params, header: &Header) -> CompactTarget {
+        let height = self.height + 1;
+        let pow_limit_bits = params.pow_limit.to_compact_lossy();
+
+        if height % DIFFICULTY_ADJUSTMENT_INTERVAL != 0 {
+            if params.allow_min_difficulty_blocks {
+                // Blocks mined long after their parent may use the minimum difficulty
+                let min_difficulty_time =
+                    self.last_timestamp() as u64 + 2 * params.pow_target_spacing;
+                if header.time as u64 > min_difficulty_time {
+                    return pow_limit_bits;
+                }
+                return CompactTarget::from_consensus(self.last_regular_bits);
+            }
+            return CompactTarget::from_consensus(self.bits);
+        }
+
+        if params.no_pow_retargeting {
+            return CompactTarget::from_consensus(self.bits);
+        }
+
+        let timespan = params.pow_target_timespan;
+        let actual_timespan = (self.last_timestamp() as u64)
+            .saturating_sub(self.epoch_start_time as u64)
+            .clamp(timespan / 4, timespan * 4);
+        let target = Target::from_compact(CompactTarget::from_consensus(self.bits));
+        retarget(target, actual_timespan, timespan, params.pow_limit).to_compact_lossy()
+    }
+}
+
+/// Scales `target` by `actual_timespan / timespan`, without going above `pow_limit`.
+fn retarget(target: Target, actual_timespan: u64, timespan: u64, pow_limit: Target) -> Target {
+    let bytes = target.to_le_bytes();
+    let mut limbs = [0u64; 5];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("Chunk is 8 bytes"));
+    }
+
+    let mut carry = 0u128;
+    for limb in limbs.iter_mut() {
+        let product = *limb as u128 * actual_timespan as u128 + carry;
+        *limb = product as u64;
+        carry = product >> 64;
+    }
+
+    let mut remainder = 0u128;
+    for limb in limbs.iter_mut().rev() {
+        let dividend = (remainder << 64) | *limb as u128;
+        *limb = (dividend / timespan as u128) as u64;
+        remainder = dividend % timespan as u128;
+    }
+
+    if limbs[4] != 0 {
+        return pow_limit;
+    }
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter()) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    Target::from_le_bytes(bytes).min(pow_limit)
+}
+
+impl LightClientHeader for HeaderWrapper {
+    type ChainTip = BitcoinChainTip;
+
+    fn tip_hash(tip: &Self::ChainTip) -> [u8; 32] {
+        tip.hash
+    }
+
+    fn extend_tip(&self, tip: &Self::ChainTip) -> Result<Self::ChainTip, LightClientError> {
+        let header = &self.header;
+        if self.height != tip.height + 1 || header.prev_blockhash.to_byte_array() != tip.hash {
+            return Err(LightClientError::NotSuccessor);
+        }
+        if header.time <= tip.median_time_past() {
+            return Err(LightClientError::InvalidTimestamp);
+        }
+
+        let params = Params::new(tip.network);
+        if header.bits != tip.expected_bits(&params, header) {
+            return Err(LightClientError::InvalidDifficulty);
+        }
+        header
+            .validate_pow(header.target())
+            .map_err(|_| LightClientError::InvalidProofOfWork)?;
+
+        let bits = header.bits.to_consensus();
+        let pow_limit_bits = params.pow_limit.to_compact_lossy().to_consensus();
+        let starts_epoch = self.height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0;
+
+        let mut recent_timestamps = tip.recent_timestamps.clone();
+        recent_timestamps.push(header.time);
+        let skip = recent_timestamps.len().saturating_sub(MEDIAN_TIME_SPAN);
+        recent_timestamps.drain(..skip);
+
+        Ok(BitcoinChainTip {
+            network: tip.network,
+            height: self.height,
+            hash: self.hash().into(),
+            bits,
+            last_regular_bits: if bits != pow_limit_bits || starts_epoch {
+                bits
+            } else {
+                tip.last_regular_bits
+            },
+            epoch_start_time: if starts_epoch {
+                header.time
+            } else {
+                tip.epoch_start_time
+            },
+            recent_timestamps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::blockdata::constants::genesis_block;
+    use bitcoin::consensus::deserialize;
+
+    use super::*;
+
+    const BLOCK_1_HEADER: &str = "010000006fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000982051fd1e4ba744bbbe680e1fee14677ba1a3c3540bf7b1cdb606e857233e0e61bc6649ffff001d01e36299";
+
+    fn genesis_tip() -> BitcoinChainTip {
+        let genesis = genesis_block(Network::Bitcoin).header;
+        BitcoinChainTip::new(
+            Network::Bitcoin,
+            &HeaderWrapper::new(genesis, 1, 0),
+            genesis.time,
+            vec![genesis.time],
+        )
+    }
+
+    fn block_1() -> Header {
+        deserialize(&hex::decode(BLOCK_1_HEADER).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn extends_mainnet_genesis() {
+        let header = HeaderWrapper::new(block_1(), 1, 1);
+        let tip = header.extend_tip(&genesis_tip()).unwrap();
+
+        assert_eq!(tip.height, 1);
+        assert_eq!(tip.hash, header.block_hash().to_byte_array());
+        assert_eq!(tip.bits, 0x1d00ffff);
+        assert_eq!(tip.recent_timestamps.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut header = block_1();
+        header.nonce += 1;
+        assert_eq!(
+            HeaderWrapper::new(header, 1, 1).extend_tip(&genesis_tip()),
+            Err(LightClientError::InvalidProofOfWork)
+        );
+
+        assert_eq!(
+            HeaderWrapper::new(block_1(), 1, 2).extend_tip(&genesis_tip()),
+            Err(LightClientError::NotSuccessor)
+        );
+
+        let mut header = block_1();
+        header.prev_blockhash = bitcoin::BlockHash::all_zeros();
+        assert_eq!(
+            HeaderWrapper::new(header, 1, 1).extend_tip(&genesis_tip()),
+            Err(LightClientError::NotSuccessor)
+        );
+
+        let mut header = block_1();
+        header.bits = CompactTarget::from_consensus(0x1c7fff80);
+        assert_eq!(
+            HeaderWrapper::new(header, 1, 1).extend_tip(&genesis_tip()),
+            Err(LightClientError::InvalidDifficulty)
+        );
+    }
+
+    #[test]
+    fn retargets_difficulty() {
+        let params = Params::new(Network::Bitcoin);
+        let timespan = params.pow_target_timespan;
+        let target = Target::from_compact(CompactTarget::from_consensus(0x1d00ffff));
+        let retarget = |actual_timespan| {
+            retarget(target, actual_timespan, timespan, params.pow_limit)
+                .to_compact_lossy()
+                .to_consensus()
+        };
+
+        assert_eq!(retarget(timespan), 0x1d00ffff);
+        // Difficulty can't go below the minimum
+        assert_eq!(retarget(timespan * 2), 0x1d00ffff);
+        assert_eq!(retarget(timespan / 2), 0x1c7fff80);
+    }
+}
@@ -14,6 +14,7 @@ pub mod block;
 mod block_hash;
 pub mod header;
 pub mod header_stream;
+pub mod light_client;
 pub mod proof;
 pub mod transaction;
 pub mod utxo;
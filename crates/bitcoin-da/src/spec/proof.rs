@@ -1,5 +1,6 @@
 use bitcoin::absolute::{LockTime, Time};
-use bitcoin::Transaction;
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::{Transaction, Txid};
 use serde::{Deserialize, Serialize};
 
 // Set of proofs for inclusion of a transaction in a block
@@ -38,3 +39,77 @@ impl Default for InclusionMultiProof {
         }
     }
 }
+
+/// Proof that a rollup blob is part of a Bitcoin block, verifiable without the rollup
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlobInclusionProof {
+    /// Id of the reveal transaction carrying the blob
+    pub txid: Txid,
+    /// Position of the transaction in the block
+    pub tx_index: u32,
+    /// The transaction, serialized with its witness
+    pub raw_tx: Vec<u8>,
+    /// Header of the block, serialized as by Bitcoin
+    pub header: Vec<u8>,
+    /// Sibling txids from the transaction up to the merkle root of the header,
+    /// in internal byte order
+    pub merkle_path: Vec<[u8; 32]>,
+    /// Position of the blob among the rollup blobs of the block
+    pub blob_offset: u32,
+}
+
+/// Returns the sibling hashes on the path from the `index`th txid to the merkle root,
+/// duplicating the last hash of levels with an odd number of hashes as Bitcoin does.
+pub(crate) fn merkle_path(txids: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut path = Vec::new();
+    let mut level = txids.to_vec();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        path.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut concat = pair[0].to_vec();
+                concat.extend_from_slice(&pair[1]);
+                sha256d::Hash::hash(&concat).to_byte_array()
+            })
+            .collect();
+        index /= 2;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::merkle_tree;
+
+    use super::*;
+
+    #[test]
+    fn merkle_path_leads_to_root() {
+        let txids: Vec<[u8; 32]> = (0..5u8).map(|i| [i; 32]).collect();
+        let root =
+            merkle_tree::calculate_root(txids.iter().map(|txid| Txid::from_byte_array(*txid)))
+                .unwrap()
+                .to_byte_array();
+
+        for index in 0..txids.len() {
+            let mut hash = txids[index];
+            let mut position = index;
+            for sibling in merkle_path(&txids, index) {
+                let (left, right) = if position % 2 == 0 {
+                    (hash, sibling)
+                } else {
+                    (sibling, hash)
+                };
+                let mut concat = left.to_vec();
+                concat.extend_from_slice(&right);
+                hash = sha256d::Hash::hash(&concat).to_byte_array();
+                position /= 2;
+            }
+            assert_eq!(hash, root);
+        }
+    }
+}
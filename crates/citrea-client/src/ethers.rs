@@ -0,0 +1,135 @@
+use alloy_primitives::{Bytes, B256};
+use async_trait::async_trait;
+use ethers_providers::{JsonRpcClient, Provider, ProviderError};
+use sov_rollup_interface::rpc::{SoftConfirmationRange, SoftConfirmationStatus};
+use sov_rollup_interface::stf::SoftConfirmationOutcome;
+
+use crate::{GetSoftBatchResponse, LatencySummary};
+
+/// Citrea specific RPC methods on top of an `ethers` [`Provider`], so that integrators already
+/// using `ethers` don't need a second client. See [`CitreaClient`](crate::CitreaClient) for
+/// the documentation of each method.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait CitreaMiddleware {
+    async fn get_soft_batch_by_number(
+        &self,
+        num: u64,
+    ) -> Result<Option<GetSoftBatchResponse>, ProviderError>;
+
+    async fn get_soft_batch_by_hash(
+        &self,
+        hash: [u8; 32],
+    ) -> Result<Option<GetSoftBatchResponse>, ProviderError>;
+
+    async fn get_soft_confirmation_status(
+        &self,
+        num: u64,
+    ) -> Result<SoftConfirmationStatus, ProviderError>;
+
+    async fn get_soft_confirmation_outcome(
+        &self,
+        num: u64,
+    ) -> Result<Option<SoftConfirmationOutcome>, ProviderError>;
+
+    async fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Result<Option<SoftConfirmationRange>, ProviderError>;
+
+    async fn verify_ordering_commitment(
+        &self,
+        num: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<Option<bool>, ProviderError>;
+
+    async fn publish_batch(&self) -> Result<(), ProviderError>;
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, ProviderError>;
+
+    async fn get_mempool_transactions(&self) -> Result<Vec<Bytes>, ProviderError>;
+
+    async fn get_latency_summary(&self) -> Result<LatencySummary, ProviderError>;
+
+    async fn get_limiting_number(&self) -> Result<u64, ProviderError>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P: JsonRpcClient> CitreaMiddleware for Provider<P> {
+    async fn get_soft_batch_by_number(
+        &self,
+        num: u64,
+    ) -> Result<Option<GetSoftBatchResponse>, ProviderError> {
+        self.request("ledger_getSoftBatchByNumber", [num]).await
+    }
+
+    async fn get_soft_batch_by_hash(
+        &self,
+        hash: [u8; 32],
+    ) -> Result<Option<GetSoftBatchResponse>, ProviderError> {
+        self.request(
+            "ledger_getSoftBatchByHash",
+            [format!("0x{}", hex::encode(hash))],
+        )
+        .await
+    }
+
+    async fn get_soft_confirmation_status(
+        &self,
+        num: u64,
+    ) -> Result<SoftConfirmationStatus, ProviderError> {
+        self.request("ledger_getSoftConfirmationStatus", [num])
+            .await
+    }
+
+    async fn get_soft_confirmation_outcome(
+        &self,
+        num: u64,
+    ) -> Result<Option<SoftConfirmationOutcome>, ProviderError> {
+        self.request("ledger_getSoftConfirmationOutcome", [num])
+            .await
+    }
+
+    async fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Result<Option<SoftConfirmationRange>, ProviderError> {
+        self.request("ledger_getSoftConfirmationsByL1Height", [l1_height])
+            .await
+    }
+
+    async fn verify_ordering_commitment(
+        &self,
+        num: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<Option<bool>, ProviderError> {
+        let tx_hashes: Vec<String> = tx_hashes
+            .iter()
+            .map(|hash| format!("0x{}", hex::encode(hash)))
+            .collect();
+        self.request("ledger_verifyOrderingCommitment", (num, tx_hashes))
+            .await
+    }
+
+    async fn publish_batch(&self) -> Result<(), ProviderError> {
+        self.request("eth_publishBatch", ()).await
+    }
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, ProviderError> {
+        self.request("eth_sendRawTransaction", [tx]).await
+    }
+
+    async fn get_mempool_transactions(&self) -> Result<Vec<Bytes>, ProviderError> {
+        self.request("citrea_getMempoolTransactions", ()).await
+    }
+
+    async fn get_latency_summary(&self) -> Result<LatencySummary, ProviderError> {
+        self.request("citrea_getLatencySummary", ()).await
+    }
+
+    async fn get_limiting_number(&self) -> Result<u64, ProviderError> {
+        self.request("softConfirmationRuleEnforcer_getLimitingNumber", ())
+            .await
+    }
+}
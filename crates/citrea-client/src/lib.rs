@@ -0,0 +1,246 @@
+#![doc = include_str!("../README.md")]
+
+#[cfg(feature = "ethers")]
+mod ethers;
+#[cfg(feature = "openrpc")]
+pub mod openrpc;
+mod types;
+
+use alloy_primitives::{Bytes, B256};
+#[cfg(feature = "ethers")]
+pub use ethers::CitreaMiddleware;
+use jsonrpsee::core::client::ClientT;
+pub use jsonrpsee::core::Error;
+#[cfg(feature = "http")]
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+use sov_rollup_interface::rpc::{
+    CheckpointWithStatus, SignedCheckpoint, SoftConfirmationRange, SoftConfirmationStatus,
+};
+use sov_rollup_interface::stf::SoftConfirmationOutcome;
+pub use types::*;
+
+/// Typed client for the Citrea specific RPC methods of the sequencer, full nodes and provers.
+#[derive(Debug, Clone)]
+pub struct CitreaClient<T> {
+    client: T,
+}
+
+#[cfg(feature = "http")]
+impl CitreaClient<HttpClient> {
+    /// Creates a client which connects to `url` over HTTP
+    pub fn new_http(url: impl AsRef<str>) -> Result<Self, Error> {
+        let client = HttpClientBuilder::default().build(url)?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl CitreaClient<jsonrpsee::core::client::Client> {
+    /// Creates a client which connects to `url` over WebSocket, from a WASM environment
+    pub async fn new_wasm(url: impl AsRef<str>) -> Result<Self, Error> {
+        let client = jsonrpsee::wasm_client::WasmClientBuilder::default()
+            .build(url)
+            .await?;
+        Ok(Self { client })
+    }
+}
+
+impl<T: ClientT + Sync> CitreaClient<T> {
+    /// Wraps an existing jsonrpsee client
+    pub fn from_client(client: T) -> Self {
+        Self { client }
+    }
+
+    /// Returns the underlying jsonrpsee client, to send requests which are not covered here
+    pub fn inner(&self) -> &T {
+        &self.client
+    }
+
+    /// Gets the soft batch at the given L2 height
+    pub async fn get_soft_batch_by_number(
+        &self,
+        num: u64,
+    ) -> Result<Option<GetSoftBatchResponse>, Error> {
+        self.client
+            .request("ledger_getSoftBatchByNumber", rpc_params![num])
+            .await
+    }
+
+    /// Gets the soft batch with the given hash
+    pub async fn get_soft_batch_by_hash(
+        &self,
+        hash: [u8; 32],
+    ) -> Result<Option<GetSoftBatchResponse>, Error> {
+        self.client
+            .request(
+                "ledger_getSoftBatchByHash",
+                rpc_params![format!("0x{}", hex::encode(hash))],
+            )
+            .await
+    }
+
+    /// Gets whether the soft batch at the given L2 height is trusted, finalized on DA or proven
+    pub async fn get_soft_confirmation_status(
+        &self,
+        num: u64,
+    ) -> Result<SoftConfirmationStatus, Error> {
+        self.client
+            .request("ledger_getSoftConfirmationStatus", rpc_params![num])
+            .await
+    }
+
+    /// Gets the gas used, fees, sequencer reward and rule enforcement results of the soft
+    /// batch at the given L2 height
+    pub async fn get_soft_confirmation_outcome(
+        &self,
+        num: u64,
+    ) -> Result<Option<SoftConfirmationOutcome>, Error> {
+        self.client
+            .request("ledger_getSoftConfirmationOutcome", rpc_params![num])
+            .await
+    }
+
+    /// Gets the range of L2 heights of the soft confirmations anchored to the given L1 height.
+    /// Returns `None` if none are known.
+    pub async fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Result<Option<SoftConfirmationRange>, Error> {
+        self.client
+            .request(
+                "ledger_getSoftConfirmationsByL1Height",
+                rpc_params![l1_height],
+            )
+            .await
+    }
+
+    /// Checks the transaction hashes, in the order the sequencer received them, against the
+    /// ordering commitment of the soft batch at the given L2 height. Returns `None` if the
+    /// soft batch or its commitment is unknown.
+    pub async fn verify_ordering_commitment(
+        &self,
+        num: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<Option<bool>, Error> {
+        let tx_hashes: Vec<String> = tx_hashes
+            .iter()
+            .map(|hash| format!("0x{}", hex::encode(hash)))
+            .collect();
+        self.client
+            .request(
+                "ledger_verifyOrderingCommitment",
+                rpc_params![num, tx_hashes],
+            )
+            .await
+    }
+
+    /// Gets the last checkpoint of the state signed by the sequencer. Sequencer only.
+    pub async fn get_latest_checkpoint(&self) -> Result<Option<SignedCheckpoint>, Error> {
+        self.client
+            .request("citrea_getLatestCheckpoint", rpc_params![])
+            .await
+    }
+
+    /// Gets the last checkpoint signed by the sequencer, verified by a full node, with whether
+    /// it is only trusted or proven
+    pub async fn get_checkpoint_status(&self) -> Result<Option<CheckpointWithStatus>, Error> {
+        self.client
+            .request("citrea_getCheckpointStatus", rpc_params![])
+            .await
+    }
+
+    /// Asks the sequencer to publish a soft batch now
+    pub async fn publish_batch(&self) -> Result<(), Error> {
+        self.client.request("eth_publishBatch", rpc_params![]).await
+    }
+
+    /// Sends a signed transaction to the sequencer, returns its hash
+    pub async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Error> {
+        self.client
+            .request("eth_sendRawTransaction", rpc_params![tx])
+            .await
+    }
+
+    /// Gets the raw transactions in the sequencer's mempool
+    pub async fn get_mempool_transactions(&self) -> Result<Vec<Bytes>, Error> {
+        self.client
+            .request("citrea_getMempoolTransactions", rpc_params![])
+            .await
+    }
+
+    /// Gets the sequencer's transaction inclusion and commitment latencies
+    pub async fn get_latency_summary(&self) -> Result<LatencySummary, Error> {
+        self.client
+            .request("citrea_getLatencySummary", rpc_params![])
+            .await
+    }
+
+    /// Claims the commitment range between the given L2 heights on the sequencer for the
+    /// prover `prover_id`. The range is this prover's to prove if the returned claim is held
+    /// by it and not proven yet.
+    pub async fn claim_proving_range(
+        &self,
+        prover_id: &str,
+        start_l2_height: u64,
+        end_l2_height: u64,
+    ) -> Result<ProvingClaim, Error> {
+        self.client
+            .request(
+                "citrea_claimProvingRange",
+                rpc_params![prover_id, start_l2_height, end_l2_height],
+            )
+            .await
+    }
+
+    /// Reports the commitment range between the given L2 heights as proven by `prover_id`
+    pub async fn finish_proving_range(
+        &self,
+        prover_id: &str,
+        start_l2_height: u64,
+        end_l2_height: u64,
+    ) -> Result<ProvingClaim, Error> {
+        self.client
+            .request(
+                "citrea_finishProvingRange",
+                rpc_params![prover_id, start_l2_height, end_l2_height],
+            )
+            .await
+    }
+
+    /// Gets the claims of provers on the most recent commitment ranges
+    pub async fn get_proving_claims(&self) -> Result<Vec<ProvingClaim>, Error> {
+        self.client
+            .request("citrea_getProvingClaims", rpc_params![])
+            .await
+    }
+
+    /// Gets the maximum number of soft batches the sequencer can publish per DA block
+    pub async fn get_limiting_number(&self) -> Result<u64, Error> {
+        self.client
+            .request(
+                "softConfirmationRuleEnforcer_getLimitingNumber",
+                rpc_params![],
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_latency_summary() {
+        let summary: LatencySummary = serde_json::from_str(
+            r#"{
+                "inclusion": {"samples": 2, "p50Ms": 10, "p90Ms": 20, "p99Ms": 20, "maxMs": 20},
+                "commitment": {"samples": 0, "p50Ms": 0, "p90Ms": 0, "p99Ms": 0, "maxMs": 0}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(summary.inclusion.samples, 2);
+        assert_eq!(summary.inclusion.p90_ms, 20);
+        assert_eq!(summary.commitment, LatencyPercentiles::default());
+    }
+}
@@ -0,0 +1,1151 @@
+//! [OpenRPC](https://spec.open-rpc.org) description of the Citrea specific RPC methods.
+//!
+//! Nodes serve the document with the [`DISCOVER_METHOD`] method, and a copy is kept in
+//! `openrpc.json` at the root of this crate for generating clients in other languages.
+
+use serde_json::{json, Value};
+
+/// Name of the method which returns the OpenRPC document, as defined by the specification
+pub const DISCOVER_METHOD: &str = "rpc.discover";
+
+/// Error code of the `LEDGER_RPC_ERROR` errors returned by the `ledger` methods
+const LEDGER_RPC_ERROR_CODE: i32 = -32001;
+
+/// Returns the OpenRPC document describing the `ledger`, `citrea` and `sequencer` methods.
+pub fn openrpc_document() -> Value {
+    json!({
+        "openrpc": "1.2.6",
+        "info": {
+            "title": "Citrea JSON-RPC API",
+            "description": "Citrea specific methods. The Ethereum methods follow the Ethereum JSON-RPC specification.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods(),
+        "components": {
+            "schemas": schemas(),
+            "errors": {
+                "LedgerRpcError": {
+                    "code": LEDGER_RPC_ERROR_CODE,
+                    "message": "LEDGER_RPC_ERROR",
+                },
+            },
+        },
+    })
+}
+
+/// Returns the names of all the methods in the document.
+pub fn documented_methods() -> Vec<String> {
+    methods()
+        .into_iter()
+        .map(|method| method["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn methods() -> Vec<Value> {
+    vec![
+        // ledger namespace, served by all nodes
+        ledger_method(
+            "ledger_getHead",
+            "Returns the latest slot processed by the node",
+            vec![query_mode_param()],
+            nullable(schema_ref("SlotResponse")),
+        ),
+        ledger_method(
+            "ledger_getSlots",
+            "Returns the slots with the given identifiers",
+            with_query_mode(param("slotIds", array(schema_ref("SlotIdentifier")), true)),
+            array(nullable(schema_ref("SlotResponse"))),
+        ),
+        ledger_method(
+            "ledger_getBatches",
+            "Returns the batches with the given identifiers",
+            with_query_mode(param("batchIds", array(schema_ref("BatchIdentifier")), true)),
+            array(nullable(schema_ref("BatchResponse"))),
+        ),
+        ledger_method(
+            "ledger_getTransactions",
+            "Returns the transactions with the given identifiers",
+            with_query_mode(param("txIds", array(schema_ref("TxIdentifier")), true)),
+            array(nullable(schema_ref("TxResponse"))),
+        ),
+        ledger_method(
+            "ledger_getEvents",
+            "Returns the events with the given identifiers",
+            vec![param("eventIds", array(schema_ref("EventIdentifier")), true)],
+            array(nullable(schema_ref("Event"))),
+        ),
+        ledger_method(
+            "ledger_getSlotByHash",
+            "Returns the slot with the given DA block hash",
+            with_query_mode(param("hash", schema_ref("Hash"), true)),
+            nullable(schema_ref("SlotResponse")),
+        ),
+        ledger_method(
+            "ledger_getBatchByHash",
+            "Returns the batch with the given hash",
+            with_query_mode(param("hash", schema_ref("Hash"), true)),
+            nullable(schema_ref("BatchResponse")),
+        ),
+        ledger_method(
+            "ledger_getSoftBatchByHash",
+            "Returns the soft batch with the given hash",
+            vec![param("hash", schema_ref("Hash"), true)],
+            nullable(schema_ref("SoftBatchResponse")),
+        ),
+        ledger_method(
+            "ledger_getTransactionByHash",
+            "Returns the transaction with the given hash",
+            with_query_mode(param("hash", schema_ref("Hash"), true)),
+            nullable(schema_ref("TxResponse")),
+        ),
+        ledger_method(
+            "ledger_getEventByNumber",
+            "Returns the event with the given number",
+            vec![param("number", uint(), true)],
+            nullable(schema_ref("Event")),
+        ),
+        ledger_method(
+            "ledger_getSlotByNumber",
+            "Returns the slot with the given number",
+            with_query_mode(param("number", uint(), true)),
+            nullable(schema_ref("SlotResponse")),
+        ),
+        ledger_method(
+            "ledger_getBatchByNumber",
+            "Returns the batch with the given number",
+            with_query_mode(param("number", uint(), true)),
+            nullable(schema_ref("BatchResponse")),
+        ),
+        ledger_method(
+            "ledger_getSoftBatchByNumber",
+            "Returns the soft batch at the given L2 height",
+            vec![param("number", uint(), true)],
+            nullable(schema_ref("SoftBatchResponse")),
+        ),
+        ledger_method(
+            "ledger_getTransactionByNumber",
+            "Returns the transaction with the given number",
+            with_query_mode(param("number", uint(), true)),
+            nullable(schema_ref("TxResponse")),
+        ),
+        ledger_method(
+            "ledger_getSlotsRange",
+            "Returns the slots with numbers in the given inclusive range",
+            range_params(),
+            array(nullable(schema_ref("SlotResponse"))),
+        ),
+        ledger_method(
+            "ledger_getBatchesRange",
+            "Returns the batches with numbers in the given inclusive range",
+            range_params(),
+            array(nullable(schema_ref("BatchResponse"))),
+        ),
+        ledger_method(
+            "ledger_getTransactionsRange",
+            "Returns the transactions with numbers in the given inclusive range",
+            range_params(),
+            array(nullable(schema_ref("TxResponse"))),
+        ),
+        ledger_method(
+            "ledger_getSoftConfirmationStatus",
+            "Returns whether the soft batch at the given L2 height is trusted, finalized on DA or proven",
+            vec![param("number", uint(), true)],
+            schema_ref("SoftConfirmationStatus"),
+        ),
+        ledger_method(
+            "ledger_getSoftConfirmationOutcome",
+            "Returns the gas used, fees, sequencer reward and rule enforcement results of the soft batch at the given L2 height, or null if the node has not recorded them",
+            vec![param("l2Height", uint(), true)],
+            nullable(schema_ref("SoftConfirmationOutcome")),
+        ),
+        ledger_method(
+            "ledger_getSoftConfirmationsByL1Height",
+            "Returns the range of L2 heights of the soft confirmations anchored to the given L1 height, or null if none are known",
+            vec![param("l1Height", uint(), true)],
+            nullable(schema_ref("SoftConfirmationRange")),
+        ),
+        ledger_method(
+            "ledger_verifyOrderingCommitment",
+            "Returns whether the transaction hashes, in the order the sequencer received them, match the ordering commitment of the soft batch at the given L2 height, or null if the soft batch or its commitment is unknown",
+            vec![
+                param("l2Height", uint(), true),
+                param("txHashes", array(schema_ref("Hash")), true),
+            ],
+            nullable(json!({ "type": "boolean" })),
+        ),
+        // citrea namespace, served by all nodes
+        method(
+            "citrea_getReceiptProof",
+            "Returns the Merkle proof of the receipt of the transaction with the given hash against the receipts root of its block, or null if the transaction is unknown",
+            vec![param("hash", schema_ref("Hash"), true)],
+            nullable(schema_ref("ReceiptProof")),
+            vec![],
+        ),
+        method(
+            "citrea_getContractCreationTx",
+            "Returns the transaction which created the contract at the given address, including creations through factories, or null if no contract was created there",
+            vec![param("address", schema_ref("Address"), true)],
+            nullable(schema_ref("ContractCreation")),
+            vec![],
+        ),
+        method(
+            "citrea_getContractVerificationData",
+            "Returns the deployed bytecode of the contract at the given address and, if a transaction created it directly, its creation input and constructor arguments, or null if there is no account there",
+            vec![param("address", schema_ref("Address"), true)],
+            nullable(schema_ref("ContractVerificationData")),
+            vec![],
+        ),
+        method(
+            "citrea_getInternalTransactions",
+            "Returns the value transfers and contract creations made by contracts while executing the transaction with the given hash, or null if the transaction is unknown. Empty unless the node records internal transactions.",
+            vec![param("hash", schema_ref("Hash"), true)],
+            nullable(array(schema_ref("InternalTransaction"))),
+            vec![],
+        ),
+        method(
+            "citrea_getAccessHints",
+            "Returns the keys of the state read by the transactions of the block with the given number, or null if the block is unknown. Empty unless the node records access hints.",
+            vec![param("blockNumber", uint(), true)],
+            nullable(array(schema_ref("Bytes"))),
+            vec![],
+        ),
+        method(
+            "citrea_getTotalSupply",
+            "Returns the total supply of the native token",
+            vec![],
+            schema_ref("Quantity"),
+            vec![],
+        ),
+        method(
+            "citrea_getSupplyEvents",
+            "Returns the mints and burns of the native token in the block with the given number, or null if the block is unknown",
+            vec![param("blockNumber", uint(), true)],
+            nullable(array(schema_ref("SupplyEvent"))),
+            vec![],
+        ),
+        method(
+            "citrea_estimateCompressedSize",
+            "Returns the size in bytes the L1 fee of a transaction with the given calldata is charged for, 0 if the chain does not charge calldata",
+            vec![param("data", schema_ref("Bytes"), true)],
+            schema_ref("Quantity"),
+            vec![],
+        ),
+        method(
+            "citrea_getChainStats",
+            "Returns the activity of the blocks in the given inclusive range, aggregated into consecutive buckets of granularity blocks",
+            vec![
+                param("fromBlock", schema_ref("Quantity"), true),
+                param("toBlock", schema_ref("Quantity"), true),
+                param("granularity", schema_ref("Quantity"), true),
+            ],
+            array(schema_ref("ChainStats")),
+            vec![],
+        ),
+        method(
+            "citrea_getChainCapabilities",
+            "Returns the EVM features active in the latest block",
+            vec![],
+            schema_ref("ChainCapabilities"),
+            vec![],
+        ),
+        method(
+            "citrea_getStateDiff",
+            "Returns the accounts and storage slots changed by the block, with their values before and after it. Only served by nodes which enable it.",
+            vec![param("blockNumber", schema_ref("BlockNumberOrTag"), true)],
+            schema_ref("StateDiff"),
+            vec![],
+        ),
+        method(
+            "citrea_getLogsPage",
+            "Returns a page of the logs matching the filter, starting at the cursor, or at the start of the block range of the filter if unset",
+            vec![
+                param("filter", schema_ref("LogFilter"), true),
+                param("cursor", nullable(schema_ref("LogCursor")), false),
+            ],
+            schema_ref("LogsPage"),
+            vec![],
+        ),
+        method(
+            "citrea_getAddEthereumChainParameters",
+            "Returns the parameters of wallet_addEthereumChain (EIP-3085) for the chain, or null if the node is not configured with them",
+            vec![],
+            nullable(schema_ref("AddEthereumChainParameters")),
+            vec![],
+        ),
+        method(
+            "citrea_getIndexedBlocks",
+            "Returns a page of the blocks mirrored in the indexer database, newest first. Only served by nodes with an indexer.",
+            vec![param("page", schema_ref("IndexerPage"), false)],
+            array(schema_ref("IndexedBlock")),
+            vec![],
+        ),
+        method(
+            "citrea_getAddressTransactions",
+            "Returns a page of the transactions sent by or to the given address from the indexer database, newest first. Only served by nodes with an indexer.",
+            vec![
+                param("address", schema_ref("Address"), true),
+                param("page", schema_ref("IndexerPage"), false),
+            ],
+            array(schema_ref("IndexedTransaction")),
+            vec![],
+        ),
+        method(
+            "citrea_getIndexedLogs",
+            "Returns a page of the logs matching the filter from the indexer database, newest first. Only served by nodes with an indexer.",
+            vec![
+                param("filter", schema_ref("IndexedLogsFilter"), true),
+                param("page", schema_ref("IndexerPage"), false),
+            ],
+            array(schema_ref("IndexedLog")),
+            vec![],
+        ),
+        // citrea namespace, served by full nodes on Bitcoin
+        method(
+            "citrea_getDaInclusionProof",
+            "Returns the proof that the sequencer commitment covering the given L2 height is part of a Bitcoin block, or null if the node has not seen such a commitment yet",
+            vec![param("l2Height", uint(), true)],
+            nullable(schema_ref("DaInclusionProof")),
+            vec![],
+        ),
+        method(
+            "citrea_getCheckpointStatus",
+            "Returns the last checkpoint of the state signed by the sequencer, once its signature is verified, and whether it is only trusted or proven. Full nodes and provers only.",
+            vec![],
+            nullable(schema_ref("CheckpointWithStatus")),
+            vec![],
+        ),
+        // citrea namespace, served by provers
+        method(
+            "citrea_getProverStats",
+            "Returns the totals and recent records of the cycles, time and DA fees of the proofs produced since the prover started. Provers only.",
+            vec![],
+            schema_ref("ProverStats"),
+            vec![],
+        ),
+        // citrea namespace, served by the sequencer
+        method(
+            "citrea_getMempoolTransactions",
+            "Returns the raw transactions in the sequencer's mempool. Sequencer only.",
+            vec![],
+            array(schema_ref("Bytes")),
+            vec![],
+        ),
+        method(
+            "citrea_getLatencySummary",
+            "Returns the transaction inclusion and commitment latencies of the sequencer. Sequencer only.",
+            vec![],
+            schema_ref("LatencySummary"),
+            vec![],
+        ),
+        method(
+            "citrea_getTransactionStatus",
+            "Returns whether the transaction with the given hash is pending, queued, dropped or included, and how final its block is. Sequencer only.",
+            vec![param("hash", schema_ref("Hash"), true)],
+            schema_ref("TransactionStatus"),
+            vec![],
+        ),
+        method(
+            "citrea_claimProvingRange",
+            "Claims the commitment range between the given L2 heights for a prover, or renews its claim, unless another prover holds a live claim on it or it is proven. Returns the claim on the range. Sequencer only.",
+            proving_range_params(),
+            schema_ref("ProvingClaim"),
+            vec![],
+        ),
+        method(
+            "citrea_finishProvingRange",
+            "Reports the commitment range between the given L2 heights as proven by a prover, so that it is not handed out anymore. Sequencer only.",
+            proving_range_params(),
+            schema_ref("ProvingClaim"),
+            vec![],
+        ),
+        method(
+            "citrea_getLatestCheckpoint",
+            "Returns the last checkpoint of the state signed by the sequencer, or null if it signs no checkpoints. Sequencer only.",
+            vec![],
+            nullable(schema_ref("SignedCheckpoint")),
+            vec![],
+        ),
+        method(
+            "citrea_getProvingClaims",
+            "Returns the claims of provers on the most recent commitment ranges. Sequencer only.",
+            vec![],
+            array(schema_ref("ProvingClaim")),
+            vec![],
+        ),
+        // sequencer specific methods of the other namespaces
+        method(
+            "eth_publishBatch",
+            "Makes the sequencer publish a soft batch now. Sequencer only.",
+            vec![],
+            json!({ "type": "null" }),
+            vec![],
+        ),
+        // sequencer namespace, served by nodes running the batch builder of the Sovereign SDK
+        method(
+            "sequencer_publishBatch",
+            "Accepts the given raw transactions, each passed as a positional parameter, and submits a batch of the accepted transactions to the DA layer. Returns a message with the number of submitted transactions.",
+            vec![param("tx", schema_ref("RawBytes"), false)],
+            json!({ "type": "string" }),
+            vec![],
+        ),
+        method(
+            "sequencer_acceptTx",
+            "Adds the transaction to the batch builder",
+            vec![param("tx", schema_ref("SubmitTransaction"), true)],
+            schema_ref("SubmitTransactionResponse"),
+            vec![],
+        ),
+        method(
+            "softConfirmationRuleEnforcer_getLimitingNumber",
+            "Returns the maximum number of soft batches the sequencer can publish per DA block",
+            vec![],
+            uint(),
+            vec![],
+        ),
+    ]
+}
+
+fn schemas() -> Value {
+    let hex = |description: &str| {
+        json!({
+            "type": "string",
+            "pattern": "^(0x)?[0-9a-fA-F]*$",
+            "description": description,
+        })
+    };
+    let bytes =
+        json!({ "type": "array", "items": { "type": "integer", "minimum": 0, "maximum": 255 } });
+    let range = json!({
+        "type": "object",
+        "required": ["start", "end"],
+        "properties": { "start": uint(), "end": uint() },
+    });
+    json!({
+        "Hash": hex("32 bytes hash, hex encoded"),
+        "Bytes": {
+            "type": "string",
+            "pattern": "^0x[0-9a-fA-F]*$",
+            "description": "0x prefixed hex encoded bytes",
+        },
+        "RawBytes": bytes,
+        "Address": {
+            "type": "string",
+            "pattern": "^0x[0-9a-fA-F]{40}$",
+            "description": "0x prefixed hex encoded 20 bytes address",
+        },
+        "Quantity": {
+            "type": "string",
+            "pattern": "^0x([1-9a-fA-F][0-9a-fA-F]*|0)$",
+            "description": "0x prefixed hex encoded integer",
+        },
+        "BlockNumberOrTag": {
+            "oneOf": [
+                schema_ref("Quantity"),
+                { "type": "string", "enum": ["latest", "earliest", "pending", "safe", "finalized"] },
+            ],
+        },
+        "QueryMode": {
+            "type": "string",
+            "enum": ["Compact", "Standard", "Full"],
+            "description": "How much of the children of the returned items to include, defaults to Standard",
+        },
+        "SoftConfirmationStatus": {
+            "type": "string",
+            "enum": ["Trusted", "Finalized", "Proven"],
+        },
+        "SoftConfirmationRange": {
+            "type": "object",
+            "required": ["start", "end"],
+            "properties": { "start": uint(), "end": uint() },
+        },
+        "SlotIdentifier": { "oneOf": [schema_ref("Hash"), uint()] },
+        "BatchIdentifier": {
+            "oneOf": [
+                schema_ref("Hash"),
+                {
+                    "type": "object",
+                    "required": ["slot_id", "offset"],
+                    "properties": { "slot_id": schema_ref("SlotIdentifier"), "offset": uint() },
+                },
+                uint(),
+            ],
+        },
+        "TxIdentifier": {
+            "oneOf": [
+                schema_ref("Hash"),
+                {
+                    "type": "object",
+                    "required": ["batch_id", "offset"],
+                    "properties": { "batch_id": schema_ref("BatchIdentifier"), "offset": uint() },
+                },
+                uint(),
+            ],
+        },
+        "EventIdentifier": {
+            "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["tx_id", "offset"],
+                    "properties": { "tx_id": schema_ref("TxIdentifier"), "offset": uint() },
+                },
+                {
+                    "type": "object",
+                    "required": ["tx_id", "key"],
+                    "properties": { "tx_id": schema_ref("TxIdentifier"), "key": bytes },
+                },
+                uint(),
+            ],
+        },
+        "Event": {
+            "type": "object",
+            "required": ["key", "value"],
+            "properties": { "key": bytes, "value": bytes },
+        },
+        "SlotResponse": {
+            "type": "object",
+            "required": ["number", "hash", "batch_range"],
+            "properties": {
+                "number": uint(),
+                "hash": schema_ref("Hash"),
+                "batch_range": range,
+                "batches": array({ "oneOf": [schema_ref("Hash"), schema_ref("BatchResponse")] }),
+            },
+        },
+        "BatchResponse": {
+            "type": "object",
+            "required": ["hash", "tx_range"],
+            "properties": {
+                "hash": schema_ref("Hash"),
+                "tx_range": range,
+                "txs": array({ "oneOf": [schema_ref("Hash"), schema_ref("TxResponse")] }),
+            },
+        },
+        "TxResponse": {
+            "type": "object",
+            "required": ["hash", "event_range"],
+            "properties": {
+                "hash": schema_ref("Hash"),
+                "event_range": range,
+                "body": bytes,
+            },
+        },
+        "SoftBatchResponse": {
+            "type": "object",
+            "required": [
+                "da_slot_height",
+                "da_slot_hash",
+                "hash",
+                "pre_state_root",
+                "post_state_root",
+                "soft_confirmation_signature",
+                "pub_key",
+                "l1_fee_rate",
+                "timestamp",
+                "ordering_commitment",
+            ],
+            "properties": {
+                "da_slot_height": uint(),
+                "da_slot_hash": hex("DA block hash, hex encoded without 0x prefix"),
+                "hash": hex("Soft batch hash, hex encoded without 0x prefix"),
+                "txs": array(bytes),
+                "pre_state_root": hex("State root before the soft batch, hex encoded without 0x prefix"),
+                "post_state_root": hex("State root after the soft batch, hex encoded without 0x prefix"),
+                "soft_confirmation_signature": hex("Sequencer signature, hex encoded without 0x prefix"),
+                "pub_key": hex("Sequencer public key, hex encoded without 0x prefix"),
+                "l1_fee_rate": uint(),
+                "timestamp": nullable(uint()),
+                "ordering_commitment": nullable(hex("Hash of the transaction hashes in the order the sequencer received them, signed with the soft batch, hex encoded without 0x prefix. Null for soft batches given before the chain required one")),
+            },
+        },
+        "LatencyPercentiles": {
+            "type": "object",
+            "required": ["samples", "p50Ms", "p90Ms", "p99Ms", "maxMs"],
+            "properties": {
+                "samples": uint(),
+                "p50Ms": uint(),
+                "p90Ms": uint(),
+                "p99Ms": uint(),
+                "maxMs": uint(),
+            },
+        },
+        "LatencySummary": {
+            "type": "object",
+            "required": ["inclusion", "commitment", "proof"],
+            "properties": {
+                "inclusion": schema_ref("LatencyPercentiles"),
+                "commitment": schema_ref("LatencyPercentiles"),
+                "proof": schema_ref("LatencyPercentiles"),
+            },
+        },
+        "DaInclusionProof": {
+            "type": "object",
+            "required": [
+                "l2StartHeight",
+                "l2EndHeight",
+                "l1Height",
+                "txid",
+                "txIndex",
+                "rawTx",
+                "blockHeader",
+                "merklePath",
+                "blobOffset",
+            ],
+            "properties": {
+                "l2StartHeight": uint(),
+                "l2EndHeight": uint(),
+                "l1Height": uint(),
+                "txid": hex("Id of the transaction carrying the commitment, as displayed by Bitcoin"),
+                "txIndex": uint(),
+                "rawTx": schema_ref("Bytes"),
+                "blockHeader": schema_ref("Bytes"),
+                "merklePath": array(schema_ref("Bytes")),
+                "blobOffset": uint(),
+            },
+        },
+        "TransactionStatus": {
+            "oneOf": [
+                tx_status("unknown", json!({})),
+                tx_status("pending", json!({})),
+                tx_status("queued", json!({ "expectedNonce": hex("Nonce the sender has to fill, hex encoded") })),
+                tx_status("dropped", json!({ "reason": { "type": "string" } })),
+                tx_status(
+                    "included",
+                    json!({
+                        "blockNumber": hex("Number of the block including the transaction, hex encoded"),
+                        "finality": { "type": "string", "enum": ["softConfirmed", "committed"] },
+                    }),
+                ),
+            ],
+        },
+        "ProvingClaim": {
+            "type": "object",
+            "required": ["proverId", "startL2Height", "endL2Height", "expiresAt", "proven"],
+            "properties": {
+                "proverId": { "type": "string" },
+                "startL2Height": uint(),
+                "endL2Height": uint(),
+                "expiresAt": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Unix timestamp in seconds after which the range is handed out to other provers, unless it is proven",
+                },
+                "proven": { "type": "boolean" },
+            },
+        },
+        "SignedCheckpoint": {
+            "type": "object",
+            "required": ["checkpoint", "signature", "pub_key"],
+            "properties": {
+                "checkpoint": {
+                    "type": "object",
+                    "required": ["l2_height", "soft_batch_hash", "state_root"],
+                    "properties": {
+                        "l2_height": uint(),
+                        "soft_batch_hash": schema_ref("Hash"),
+                        "state_root": hex("State root after the L2 block, hex encoded"),
+                    },
+                },
+                "signature": hex("ed25519 signature of the borsh serialized checkpoint, hex encoded"),
+                "pub_key": hex("Public key of the sequencer, hex encoded"),
+            },
+        },
+        "CheckpointWithStatus": {
+            "type": "object",
+            "required": ["checkpoint", "status"],
+            "properties": {
+                "checkpoint": schema_ref("SignedCheckpoint"),
+                "status": { "type": "string", "enum": ["trustedCheckpoint", "proven"] },
+            },
+        },
+        "ReceiptProof": {
+            "type": "object",
+            "required": [
+                "transactionHash",
+                "blockHash",
+                "blockNumber",
+                "transactionIndex",
+                "receiptsRoot",
+                "key",
+                "receipt",
+                "proof",
+            ],
+            "properties": {
+                "transactionHash": schema_ref("Hash"),
+                "blockHash": schema_ref("Hash"),
+                "blockNumber": schema_ref("Quantity"),
+                "transactionIndex": schema_ref("Quantity"),
+                "receiptsRoot": schema_ref("Hash"),
+                "key": schema_ref("Bytes"),
+                "receipt": schema_ref("Bytes"),
+                "proof": array(schema_ref("Bytes")),
+            },
+        },
+        "ContractCreation": {
+            "type": "object",
+            "required": ["contractAddress", "transactionHash", "from", "blockHash", "blockNumber"],
+            "properties": {
+                "contractAddress": schema_ref("Address"),
+                "transactionHash": schema_ref("Hash"),
+                "from": schema_ref("Address"),
+                "blockHash": schema_ref("Hash"),
+                "blockNumber": schema_ref("Quantity"),
+            },
+        },
+        "ContractVerificationData": {
+            "type": "object",
+            "required": [
+                "contractAddress",
+                "deployedBytecode",
+                "deployedCodeHash",
+                "creationTransactionHash",
+                "creationBytecode",
+                "constructorArguments",
+            ],
+            "properties": {
+                "contractAddress": schema_ref("Address"),
+                "deployedBytecode": schema_ref("Bytes"),
+                "deployedCodeHash": schema_ref("Hash"),
+                "creationTransactionHash": nullable(schema_ref("Hash")),
+                "creationBytecode": nullable(schema_ref("Bytes")),
+                "constructorArguments": nullable(schema_ref("Bytes")),
+            },
+        },
+        "InternalTransaction": {
+            "type": "object",
+            "required": ["kind", "from", "to", "value", "depth"],
+            "properties": {
+                "kind": { "type": "string", "enum": ["call", "create", "selfDestruct"] },
+                "from": schema_ref("Address"),
+                "to": schema_ref("Address"),
+                "value": schema_ref("Quantity"),
+                "depth": uint(),
+            },
+        },
+        "SupplyEvent": {
+            "type": "object",
+            "required": ["kind", "amount", "reference"],
+            "properties": {
+                "kind": { "type": "string", "enum": ["mint", "burn"] },
+                "amount": schema_ref("Quantity"),
+                "reference": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "required": ["genesis"],
+                            "properties": { "genesis": schema_ref("Address") },
+                        },
+                        {
+                            "type": "object",
+                            "required": ["bitcoinTx"],
+                            "properties": { "bitcoinTx": schema_ref("Hash") },
+                        },
+                        {
+                            "type": "object",
+                            "required": ["l2Tx"],
+                            "properties": { "l2Tx": schema_ref("Hash") },
+                        },
+                    ],
+                },
+            },
+        },
+        "ChainStats": {
+            "type": "object",
+            "required": [
+                "fromBlock",
+                "toBlock",
+                "fromTimestamp",
+                "toTimestamp",
+                "gasUsed",
+                "avgBaseFeePerGas",
+                "txCount",
+                "daBytes",
+                "daFees",
+            ],
+            "properties": {
+                "fromBlock": schema_ref("Quantity"),
+                "toBlock": schema_ref("Quantity"),
+                "fromTimestamp": schema_ref("Quantity"),
+                "toTimestamp": schema_ref("Quantity"),
+                "gasUsed": schema_ref("Quantity"),
+                "avgBaseFeePerGas": schema_ref("Quantity"),
+                "txCount": schema_ref("Quantity"),
+                "daBytes": schema_ref("Quantity"),
+                "daFees": schema_ref("Quantity"),
+            },
+        },
+        "ChainCapabilities": {
+            "type": "object",
+            "required": [
+                "protocolVersion",
+                "specId",
+                "precompiles",
+                "maxCodeSize",
+                "blobTransactions",
+                "eip6780Selfdestruct",
+                "blockGasLimit",
+                "maxTxsPerBlock",
+            ],
+            "properties": {
+                "protocolVersion": schema_ref("Quantity"),
+                "specId": { "type": "string", "description": "EVM hardfork active in the latest block" },
+                "precompiles": array(schema_ref("Address")),
+                "maxCodeSize": schema_ref("Quantity"),
+                "blobTransactions": { "type": "boolean" },
+                "eip6780Selfdestruct": { "type": "boolean" },
+                "blockGasLimit": schema_ref("Quantity"),
+                "maxTxsPerBlock": nullable(schema_ref("Quantity")),
+            },
+        },
+        "StateDiff": {
+            "type": "object",
+            "required": ["blockNumber", "accounts", "storage"],
+            "properties": {
+                "blockNumber": schema_ref("Quantity"),
+                "accounts": array({
+                    "type": "object",
+                    "required": ["address", "before", "after"],
+                    "properties": {
+                        "address": schema_ref("Address"),
+                        "before": nullable(schema_ref("AccountState")),
+                        "after": nullable(schema_ref("AccountState")),
+                    },
+                }),
+                "storage": array({
+                    "type": "object",
+                    "required": ["address", "slot", "before", "after"],
+                    "properties": {
+                        "address": schema_ref("Address"),
+                        "slot": schema_ref("Quantity"),
+                        "before": schema_ref("Quantity"),
+                        "after": schema_ref("Quantity"),
+                    },
+                }),
+            },
+        },
+        "AccountState": {
+            "type": "object",
+            "required": ["balance", "nonce", "codeHash"],
+            "properties": {
+                "balance": schema_ref("Quantity"),
+                "nonce": schema_ref("Quantity"),
+                "codeHash": schema_ref("Hash"),
+            },
+        },
+        "LogFilter": {
+            "type": "object",
+            "description": "Filter of the logs, as taken by eth_getLogs",
+        },
+        "LogCursor": {
+            "type": "object",
+            "required": ["blockNumber", "logIndex"],
+            "properties": {
+                "blockNumber": schema_ref("Quantity"),
+                "logIndex": schema_ref("Quantity"),
+            },
+        },
+        "LogsPage": {
+            "type": "object",
+            "required": ["logs", "nextCursor"],
+            "properties": {
+                "logs": array({ "type": "object", "description": "Log, as returned by eth_getLogs" }),
+                "nextCursor": nullable(schema_ref("LogCursor")),
+            },
+        },
+        "AddEthereumChainParameters": {
+            "type": "object",
+            "required": ["chainId", "chainName", "nativeCurrency", "rpcUrls"],
+            "properties": {
+                "chainId": schema_ref("Quantity"),
+                "chainName": { "type": "string" },
+                "nativeCurrency": {
+                    "type": "object",
+                    "required": ["name", "symbol", "decimals"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "symbol": { "type": "string" },
+                        "decimals": uint(),
+                    },
+                },
+                "rpcUrls": array({ "type": "string" }),
+                "blockExplorerUrls": array({ "type": "string" }),
+                "iconUrls": array({ "type": "string" }),
+            },
+        },
+        "IndexerPage": {
+            "type": "object",
+            "properties": {
+                "limit": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Number of rows, capped by the node",
+                },
+                "offset": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Number of rows skipped, defaults to 0",
+                },
+            },
+        },
+        "IndexedBlock": {
+            "type": "object",
+            "required": [
+                "number",
+                "hash",
+                "parentHash",
+                "timestamp",
+                "gasUsed",
+                "baseFeePerGas",
+                "transactionCount",
+            ],
+            "properties": {
+                "number": uint(),
+                "hash": schema_ref("Hash"),
+                "parentHash": schema_ref("Hash"),
+                "timestamp": uint(),
+                "gasUsed": uint(),
+                "baseFeePerGas": nullable(uint()),
+                "transactionCount": uint(),
+            },
+        },
+        "IndexedTransaction": {
+            "type": "object",
+            "required": [
+                "hash",
+                "blockNumber",
+                "transactionIndex",
+                "from",
+                "to",
+                "contractAddress",
+                "value",
+                "gasUsed",
+                "status",
+            ],
+            "properties": {
+                "hash": schema_ref("Hash"),
+                "blockNumber": uint(),
+                "transactionIndex": uint(),
+                "from": schema_ref("Address"),
+                "to": nullable(schema_ref("Address")),
+                "contractAddress": nullable(schema_ref("Address")),
+                "value": { "type": "string", "description": "Value in wei, as a decimal string" },
+                "gasUsed": uint(),
+                "status": { "type": "boolean" },
+            },
+        },
+        "IndexedLogsFilter": {
+            "type": "object",
+            "properties": {
+                "address": schema_ref("Address"),
+                "topic0": schema_ref("Hash"),
+                "fromBlock": uint(),
+                "toBlock": uint(),
+            },
+        },
+        "IndexedLog": {
+            "type": "object",
+            "required": ["blockNumber", "logIndex", "transactionHash", "address", "topics", "data"],
+            "properties": {
+                "blockNumber": uint(),
+                "logIndex": uint(),
+                "transactionHash": schema_ref("Hash"),
+                "address": schema_ref("Address"),
+                "topics": array(schema_ref("Hash")),
+                "data": schema_ref("Bytes"),
+            },
+        },
+        "ProverStats": {
+            "type": "object",
+            "required": ["proofs", "totalCycles", "totalWallTimeMs", "totalDaFee", "recent"],
+            "properties": {
+                "proofs": uint(),
+                "totalCycles": uint(),
+                "totalWallTimeMs": uint(),
+                "totalDaFee": uint(),
+                "recent": array({
+                    "type": "object",
+                    "required": ["daBlockHash", "cycles", "wallTimeMs", "daFee"],
+                    "properties": {
+                        "daBlockHash": hex("DA block hash, hex encoded without 0x prefix"),
+                        "cycles": nullable(uint()),
+                        "wallTimeMs": uint(),
+                        "daFee": nullable(uint()),
+                    },
+                }),
+            },
+        },
+        "SubmitTransaction": {
+            "type": "object",
+            "required": ["body"],
+            "properties": { "body": bytes },
+        },
+        "SubmitTransactionResponse": {
+            "oneOf": [
+                { "type": "string", "enum": ["Registered"] },
+                {
+                    "type": "object",
+                    "required": ["Failed"],
+                    "properties": { "Failed": { "type": "string" } },
+                },
+            ],
+        },
+        "SoftConfirmationOutcome": {
+            "type": "object",
+            "required": [
+                "gasUsed",
+                "feesCollected",
+                "sequencerReward",
+                "sequencerPenalty",
+                "successfulTxs",
+                "revertedTxs",
+                "enforcement",
+            ],
+            "properties": {
+                "gasUsed": uint(),
+                "feesCollected": uint(),
+                "sequencerReward": uint(),
+                "sequencerPenalty": uint(),
+                "successfulTxs": uint(),
+                "revertedTxs": uint(),
+                "enforcement": {
+                    "type": "object",
+                    "required": [
+                        "l2BlocksOnDaSlot",
+                        "maxL2BlocksOnDaSlot",
+                        "l1FeeRate",
+                        "maxL1FeeRateChangePercentage",
+                    ],
+                    "properties": {
+                        "l2BlocksOnDaSlot": uint(),
+                        "maxL2BlocksOnDaSlot": uint(),
+                        "l1FeeRate": uint(),
+                        "maxL1FeeRateChangePercentage": uint(),
+                    },
+                },
+            },
+        },
+    })
+}
+
+fn method(
+    name: &str,
+    summary: &str,
+    params: Vec<Value>,
+    result: Value,
+    errors: Vec<Value>,
+) -> Value {
+    json!({
+        "name": name,
+        "summary": summary,
+        "params": params,
+        "result": { "name": "result", "schema": result },
+        "errors": errors,
+    })
+}
+
+fn ledger_method(name: &str, summary: &str, params: Vec<Value>, result: Value) -> Value {
+    method(
+        name,
+        summary,
+        params,
+        result,
+        vec![json!({ "$ref": "#/components/errors/LedgerRpcError" })],
+    )
+}
+
+fn param(name: &str, schema: Value, required: bool) -> Value {
+    json!({ "name": name, "schema": schema, "required": required })
+}
+
+fn with_query_mode(param: Value) -> Vec<Value> {
+    vec![param, query_mode_param()]
+}
+
+fn range_params() -> Vec<Value> {
+    vec![
+        param("start", uint(), true),
+        param("end", uint(), true),
+        query_mode_param(),
+    ]
+}
+
+fn proving_range_params() -> Vec<Value> {
+    vec![
+        param("proverId", json!({ "type": "string" }), true),
+        param("startL2Height", uint(), true),
+        param("endL2Height", uint(), true),
+    ]
+}
+
+fn query_mode_param() -> Value {
+    param("queryMode", schema_ref("QueryMode"), false)
+}
+
+/// Returns the schema of one variant of `TransactionStatus`, tagged by its `status` field.
+fn tx_status(status: &str, fields: Value) -> Value {
+    let mut required = vec!["status".to_string()];
+    required.extend(fields.as_object().unwrap().keys().cloned());
+    let mut properties = json!({ "status": { "type": "string", "enum": [status] } });
+    properties
+        .as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    json!({ "type": "object", "required": required, "properties": properties })
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "oneOf": [schema, { "type": "null" }] })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn uint() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_openrpc_json_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/openrpc.json");
+        let document = openrpc_document();
+        if std::env::var("UPDATE_OPENRPC").is_ok() {
+            let json = serde_json::to_string_pretty(&document).unwrap() + "\n";
+            std::fs::write(path, json).unwrap();
+            return;
+        }
+        let stored: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(
+            stored, document,
+            "openrpc.json is outdated, regenerate it with UPDATE_OPENRPC=1 cargo test -p citrea-client --features openrpc"
+        );
+    }
+
+    #[test]
+    fn test_all_ledger_methods_are_documented() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = sov_db::ledger_db::LedgerDB::with_path(dir.path()).unwrap();
+        let module = sov_ledger_rpc::server::rpc_module::<_, u32, u32>(ledger_db).unwrap();
+
+        // Subscriptions can't be described by OpenRPC
+        let served: BTreeSet<String> = module
+            .method_names()
+            .filter(|name| !name.contains("subscribe"))
+            .map(str::to_string)
+            .collect();
+        let documented: BTreeSet<String> = documented_methods()
+            .into_iter()
+            .filter(|name| name.starts_with("ledger_"))
+            .collect();
+        assert_eq!(served, documented);
+    }
+}
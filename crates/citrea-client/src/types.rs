@@ -0,0 +1,84 @@
+use serde::Deserialize;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+
+/// Soft batch returned by `ledger_getSoftBatchByNumber` and `ledger_getSoftBatchByHash`
+#[derive(Deserialize, Debug, Clone)]
+pub struct GetSoftBatchResponse {
+    #[serde(with = "hex::serde")]
+    pub hash: [u8; 32],
+    pub da_slot_height: u64,
+    #[serde(with = "hex::serde")]
+    pub da_slot_hash: [u8; 32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txs: Option<Vec<Vec<u8>>>,
+    #[serde(with = "hex::serde")]
+    pub pre_state_root: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub post_state_root: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub soft_confirmation_signature: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    pub pub_key: Vec<u8>,
+    pub l1_fee_rate: u64,
+    pub timestamp: Option<u64>,
+    #[serde(default, with = "sov_rollup_interface::rpc::utils::hex_option")]
+    pub ordering_commitment: Option<[u8; 32]>,
+}
+
+impl From<GetSoftBatchResponse> for SignedSoftConfirmationBatch {
+    fn from(val: GetSoftBatchResponse) -> Self {
+        SignedSoftConfirmationBatch::new(
+            val.hash,
+            val.da_slot_height,
+            val.da_slot_hash,
+            val.pre_state_root,
+            val.l1_fee_rate,
+            val.txs.unwrap_or_default(),
+            val.soft_confirmation_signature,
+            val.pub_key,
+            val.timestamp,
+            val.ordering_commitment,
+        )
+    }
+}
+
+/// Latency percentiles of a single sequencer stage, in milliseconds
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPercentiles {
+    /// Number of samples the percentiles are computed from
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Claim of a prover on a commitment range, returned by `citrea_claimProvingRange` and
+/// `citrea_finishProvingRange`
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvingClaim {
+    /// Id of the prover holding the claim
+    pub prover_id: String,
+    pub start_l2_height: u64,
+    pub end_l2_height: u64,
+    /// Unix timestamp in seconds after which the range is handed out to other provers,
+    /// unless it is proven
+    pub expires_at: u64,
+    /// Whether the prover reported the range as proven
+    pub proven: bool,
+}
+
+/// Latency summary returned by `citrea_getLatencySummary`
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    /// Mempool arrival to soft confirmation inclusion, per transaction
+    pub inclusion: LatencyPercentiles,
+    /// Soft confirmation production to sequencer commitment submission, per soft confirmation
+    pub commitment: LatencyPercentiles,
+    /// Soft confirmation production to a prover reporting the proof of its commitment,
+    /// per soft confirmation
+    pub proof: LatencyPercentiles,
+}
@@ -59,7 +59,10 @@ pub fn get_genesis_config<C: Context, Da: DaSpec>(
 pub(crate) fn validate_config<C: Context, Da: DaSpec>(
     genesis_config: <Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig,
 ) -> Result<<Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig, anyhow::Error> {
-    // TODO
+    genesis_config
+        .soft_confirmation_rule_enforcer
+        .validate()
+        .context("Invalid soft confirmation rule enforcer genesis")?;
 
     Ok(genesis_config)
 }
@@ -4,12 +4,23 @@ use sov_modules_api::hooks::{
     HookSoftConfirmationInfo, SlotHooks, TxHooks,
 };
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{AccessoryWorkingSet, Context, Spec, WorkingSet};
-use sov_modules_stf_blueprint::{RuntimeTxHook, SequencerOutcome};
+use sov_modules_api::{
+    AccessoryWorkingSet, Context, DispatchCall, SoftConfirmationOutcome, Spec, WorkingSet,
+};
+use sov_modules_stf_blueprint::{time_hook, RuntimeTxHook, SequencerOutcome};
 use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 use sov_state::Storage;
 
-use crate::runtime::Runtime;
+use crate::runtime::{Runtime, RuntimeCall};
+
+/// Returns the name of the module `call` is dispatched to, as in the module call budget.
+fn module_name<C: Context, Da: DaSpec>(call: &RuntimeCall<C, Da>) -> &'static str {
+    match call {
+        RuntimeCall::accounts(_) => "accounts",
+        RuntimeCall::evm(_) => "evm",
+        RuntimeCall::soft_confirmation_rule_enforcer(_) => "soft_confirmation_rule_enforcer",
+    }
+}
 
 impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
     type Context = C;
@@ -24,8 +35,15 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
     ) -> anyhow::Result<C> {
         let RuntimeTxHook { height, sequencer } = arg;
         let AccountsTxHook { sender, sequencer } =
-            self.accounts
-                .pre_dispatch_tx_hook(tx, working_set, sequencer)?;
+            time_hook("accounts.pre_dispatch_tx", working_set, |working_set| {
+                self.accounts
+                    .pre_dispatch_tx_hook(tx, working_set, sequencer)
+            })?;
+
+        // Calls to the modules other than the EVM spend the module call budget
+        let call = Self::decode_call(tx.runtime_msg())?;
+        self.soft_confirmation_rule_enforcer
+            .spend_module_call_budget(module_name(&call), tx.runtime_msg().len(), working_set)?;
 
         Ok(C::new(sender, sequencer, *height))
     }
@@ -36,7 +54,9 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
         ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        self.accounts.post_dispatch_tx_hook(tx, ctx, working_set)?;
+        time_hook("accounts.post_dispatch_tx", working_set, |working_set| {
+            self.accounts.post_dispatch_tx_hook(tx, ctx, working_set)
+        })?;
 
         Ok(())
     }
@@ -62,33 +82,54 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
 
 impl<C: Context, Da: DaSpec> ApplySoftConfirmationHooks<Da> for Runtime<C, Da> {
     type Context = C;
-    type SoftConfirmationResult =
-        SequencerOutcome<<<Da as DaSpec>::BlobTransaction as BlobReaderTrait>::Address>;
+    type SoftConfirmationResult = SoftConfirmationOutcome;
 
     fn begin_soft_confirmation_hook(
         &self,
         soft_batch: &mut HookSoftConfirmationInfo,
         working_set: &mut WorkingSet<Self::Context>,
     ) -> Result<(), ApplySoftConfirmationError> {
-        self.soft_confirmation_rule_enforcer
-            .begin_soft_confirmation_hook(soft_batch, working_set)?;
-
-        self.evm.begin_soft_confirmation_hook(
-            soft_batch.da_slot_hash(),
-            &soft_batch.pre_state_root(),
-            soft_batch.l1_fee_rate(),
+        time_hook(
+            "soft_confirmation_rule_enforcer.begin_soft_confirmation",
             working_set,
-        );
+            |working_set| {
+                self.soft_confirmation_rule_enforcer
+                    .begin_soft_confirmation_hook(soft_batch, working_set)
+            },
+        )?;
+
+        time_hook("evm.begin_soft_confirmation", working_set, |working_set| {
+            self.evm.begin_soft_confirmation_hook(
+                soft_batch.da_slot_hash(),
+                &soft_batch.pre_state_root(),
+                soft_batch.l1_fee_rate(),
+                soft_batch.timestamp(),
+                working_set,
+            )
+        });
 
         Ok(())
     }
 
     fn end_soft_confirmation_hook(
         &self,
+        soft_batch: &HookSoftConfirmationInfo,
         working_set: &mut WorkingSet<C>,
-    ) -> Result<(), ApplySoftConfirmationError> {
-        self.evm.end_soft_confirmation_hook(working_set);
-        Ok(())
+    ) -> Result<SoftConfirmationOutcome, ApplySoftConfirmationError> {
+        self.evm.check_block_tx_count(working_set)?;
+
+        let outcome = time_hook("evm.end_soft_confirmation", working_set, |working_set| {
+            self.evm.end_soft_confirmation_hook(working_set)
+        });
+        let enforcement = self
+            .soft_confirmation_rule_enforcer
+            .end_soft_confirmation_hook(soft_batch, working_set);
+
+        // Sequencers are not penalized for now
+        Ok(SoftConfirmationOutcome {
+            enforcement,
+            ..outcome
+        })
     }
 }
 
@@ -90,3 +90,14 @@ where
         crate::genesis_config::get_genesis_config(genesis_paths)
     }
 }
+
+impl<C, Da> GenesisConfig<C, Da>
+where
+    C: Context,
+    Da: DaSpec,
+{
+    /// Returns the chain id of the EVM.
+    pub fn chain_id(&self) -> u64 {
+        self.evm.chain_id
+    }
+}
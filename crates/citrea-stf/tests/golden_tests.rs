@@ -0,0 +1,98 @@
+//! Replays the soft batches recorded by full nodes under `tests/golden`, failing if the
+//! state transition function now produces different state roots or receipts.
+//!
+//! Each fixture is a directory under `tests/golden/<da layer>/` holding the
+//! `batches.jsonl` written by a full node synced from genesis with
+//! `golden_batches_path` set in its `[runner]` config, and a `genesis` directory with the
+//! genesis files the node was started with.
+
+use std::path::{Path, PathBuf};
+
+use bitcoin_da::spec::BitcoinSpec;
+use citrea_stf::genesis_config::{get_genesis_config, GenesisPaths};
+use citrea_stf::runtime::Runtime;
+use sov_mock_da::MockDaSpec;
+use sov_mock_zkvm::MockZkvm;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::DaSpec;
+use sov_modules_stf_blueprint::kernels::basic::{BasicKernel, BasicKernelGenesisConfig};
+use sov_modules_stf_blueprint::{GenesisParams, StfBlueprint};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_state::DefaultStorageSpec;
+use sov_stf_runner::golden::{read_golden_batches, replay_golden_batches, GOLDEN_BATCHES_FILE};
+use sov_stf_runner::read_json_file;
+
+type C = DefaultContext;
+
+type StfBlueprintTest<Da> = StfBlueprint<
+    C,
+    Da,
+    MockZkvm<<Da as DaSpec>::ValidityCondition>,
+    Runtime<C, Da>,
+    BasicKernel<C, Da>,
+>;
+
+fn fixture_dirs(da_layer: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(da_layer);
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join(GOLDEN_BATCHES_FILE).exists())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn genesis_params<Da: DaSpec>(
+    genesis_dir: &Path,
+) -> GenesisParams<
+    <Runtime<C, Da> as sov_modules_stf_blueprint::Runtime<C, Da>>::GenesisConfig,
+    BasicKernelGenesisConfig<C, Da>,
+> {
+    let runtime = get_genesis_config::<C, Da>(&GenesisPaths::from_dir(genesis_dir)).unwrap();
+    let da_light_client_path = genesis_dir.join("da_light_client.json");
+    let kernel = BasicKernelGenesisConfig {
+        chain_state: read_json_file(genesis_dir.join("chain_state.json")).unwrap(),
+        da_light_client: da_light_client_path
+            .exists()
+            .then(|| read_json_file(da_light_client_path).unwrap()),
+    };
+
+    GenesisParams { runtime, kernel }
+}
+
+fn replay_fixtures<Da: DaSpec>(da_layer: &str) {
+    for fixture in fixture_dirs(da_layer) {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let mut storage_manager =
+            ProverStorageManager::<Da, DefaultStorageSpec>::new(sov_state::config::Config {
+                path: tmpdir.path().to_path_buf(),
+            })
+            .unwrap();
+        let stf = StfBlueprintTest::<Da>::new();
+
+        let batches = read_golden_batches(&fixture).unwrap();
+        replay_golden_batches(
+            &stf,
+            &mut storage_manager,
+            genesis_params::<Da>(&fixture.join("genesis")),
+            batches,
+        )
+        .unwrap_or_else(|e| panic!("Golden fixture {} failed: {:?}", fixture.display(), e));
+    }
+}
+
+#[test]
+fn replay_golden_batches_mock_da() {
+    replay_fixtures::<MockDaSpec>("mock");
+}
+
+#[test]
+fn replay_golden_batches_bitcoin() {
+    replay_fixtures::<BitcoinSpec>("bitcoin");
+}
@@ -0,0 +1,305 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use borsh::BorshSerialize;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rs_merkle::algorithms::Sha256 as MerkleSha256;
+use rs_merkle::MerkleTree;
+use secp256k1::{schnorr, Message, Secp256k1, XOnlyPublicKey};
+use sha2::{Digest, Sha256};
+pub use sov_rollup_interface::da::SequencerCommitment;
+use sov_rollup_interface::soft_confirmation::UnsignedSoftConfirmationBatch;
+pub use sov_rollup_interface::soft_confirmation::{SignatureScheme, SignedSoftConfirmationBatch};
+
+/// Reasons a claim fails verification
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The hash of the soft confirmation is not the hash of its content
+    #[error("Soft confirmation hash 0x{claimed} does not match its content")]
+    HashMismatch {
+        /// Hash the soft confirmation claims, hex encoded
+        claimed: String,
+    },
+    /// The soft confirmation is not signed by the sequencer
+    #[error("Soft confirmation is not signed with the key of the sequencer")]
+    PublicKeyMismatch,
+    /// The public key is not a key of the signature scheme
+    #[error("Invalid public key")]
+    InvalidPublicKey,
+    /// The signature is malformed or doesn't verify
+    #[error("Invalid signature")]
+    InvalidSignature,
+    /// A commitment has to cover at least one soft confirmation
+    #[error("A commitment covers at least one soft confirmation")]
+    EmptyCommitment,
+    /// The Merkle root of the soft confirmations is not the root of the commitment
+    #[error("Merkle root of the soft confirmations does not match the commitment")]
+    MerkleRootMismatch,
+    /// The proof output is not a state transition
+    #[error("Invalid proof output: {0}")]
+    InvalidProofOutput(&'static str),
+    /// The state transition of the proof is not the expected one
+    #[error("The proof does not prove the transition between the given state roots")]
+    StateRootMismatch,
+}
+
+/// Returns the hash of the soft confirmation, the SHA-256 digest of its unsigned borsh
+/// serialization, as computed by the sequencer.
+pub fn soft_confirmation_hash(soft_confirmation: &SignedSoftConfirmationBatch) -> [u8; 32] {
+    Sha256::digest(unsigned_message(soft_confirmation)).into()
+}
+
+/// Borsh serialization of the unsigned soft confirmation, the message signed by the sequencer
+fn unsigned_message(soft_confirmation: &SignedSoftConfirmationBatch) -> Vec<u8> {
+    UnsignedSoftConfirmationBatch::new(
+        soft_confirmation.da_slot_height(),
+        soft_confirmation.da_slot_hash(),
+        soft_confirmation.pre_state_root(),
+        soft_confirmation.txs(),
+        soft_confirmation.l1_fee_rate(),
+        soft_confirmation.timestamp(),
+        soft_confirmation.ordering_commitment(),
+    )
+    .try_to_vec()
+    .expect("Serialization to vec is infallible")
+}
+
+/// Verifies that the soft confirmation is signed by the sequencer with the given public key,
+/// and that its hash matches its content.
+///
+/// `scheme` is the signature scheme of the DA height of the soft confirmation, as activated
+/// in the chain state of the rollup.
+pub fn verify_soft_confirmation(
+    soft_confirmation: &SignedSoftConfirmationBatch,
+    sequencer_public_key: &[u8],
+    scheme: SignatureScheme,
+) -> Result<(), VerifyError> {
+    let message = unsigned_message(soft_confirmation);
+    let hash: [u8; 32] = Sha256::digest(&message).into();
+    if hash != soft_confirmation.hash() {
+        return Err(VerifyError::HashMismatch {
+            claimed: to_hex(&soft_confirmation.hash()),
+        });
+    }
+    if soft_confirmation.sequencer_pub_key() != sequencer_public_key {
+        return Err(VerifyError::PublicKeyMismatch);
+    }
+
+    let signature = soft_confirmation.signature();
+    match scheme {
+        SignatureScheme::Ed25519 => {
+            let public_key = <[u8; 32]>::try_from(sequencer_public_key)
+                .ok()
+                .and_then(|key| VerifyingKey::from_bytes(&key).ok())
+                .ok_or(VerifyError::InvalidPublicKey)?;
+            let signature =
+                Signature::from_slice(&signature).map_err(|_| VerifyError::InvalidSignature)?;
+            public_key
+                .verify(&message, &signature)
+                .map_err(|_| VerifyError::InvalidSignature)
+        }
+        SignatureScheme::Secp256k1Schnorr => {
+            let public_key = XOnlyPublicKey::from_slice(sequencer_public_key)
+                .map_err(|_| VerifyError::InvalidPublicKey)?;
+            let signature = schnorr::Signature::from_slice(&signature)
+                .map_err(|_| VerifyError::InvalidSignature)?;
+            // Schnorr signatures sign the SHA-256 digest of the message, which is the hash
+            let message = Message::from_slice(&hash).expect("SHA-256 digests are 32 bytes");
+            Secp256k1::verification_only()
+                .verify_schnorr(&signature, &message, &public_key)
+                .map_err(|_| VerifyError::InvalidSignature)
+        }
+    }
+}
+
+/// Returns the Merkle root of the hashes of the soft confirmations of a commitment, in L2
+/// height order, or `None` if there are none.
+pub fn commitment_merkle_root(soft_confirmation_hashes: &[[u8; 32]]) -> Option<[u8; 32]> {
+    MerkleTree::<MerkleSha256>::from_leaves(soft_confirmation_hashes).root()
+}
+
+/// Verifies that the commitment covers exactly the soft confirmations with the given hashes,
+/// in L2 height order.
+pub fn verify_commitment(
+    commitment: &SequencerCommitment,
+    soft_confirmation_hashes: &[[u8; 32]],
+) -> Result<(), VerifyError> {
+    let merkle_root =
+        commitment_merkle_root(soft_confirmation_hashes).ok_or(VerifyError::EmptyCommitment)?;
+    if merkle_root != commitment.merkle_root {
+        return Err(VerifyError::MerkleRootMismatch);
+    }
+    Ok(())
+}
+
+/// Returns the initial and final state roots of the state transition committed by a proof,
+/// read from the journal of its receipt.
+///
+/// The journal is the RISC Zero serialization of the state transition, where each byte of
+/// the state roots takes a little endian 32 bit word. The state roots come first, so they
+/// are read the same whatever the DA layer.
+pub fn state_transition_roots(journal: &[u8]) -> Result<([u8; 32], [u8; 32]), VerifyError> {
+    const WORD: usize = 4;
+    if journal.len() < 2 * 32 * WORD {
+        return Err(VerifyError::InvalidProofOutput("journal is too short"));
+    }
+
+    let mut roots = [[0; 32]; 2];
+    for (i, word) in journal[..2 * 32 * WORD].chunks_exact(WORD).enumerate() {
+        let word = u32::from_le_bytes(word.try_into().expect("Chunks are words"));
+        roots[i / 32][i % 32] = u8::try_from(word)
+            .map_err(|_| VerifyError::InvalidProofOutput("state roots are not bytes"))?;
+    }
+    Ok((roots[0], roots[1]))
+}
+
+/// Verifies that the journal of a proof commits to the transition from `initial_state_root`
+/// to `final_state_root`.
+///
+/// Only the output of the proof is checked, the receipt itself has to be verified against
+/// the image ID of the prover.
+pub fn verify_state_transition(
+    journal: &[u8],
+    initial_state_root: &[u8; 32],
+    final_state_root: &[u8; 32],
+) -> Result<(), VerifyError> {
+    let (initial, final_) = state_transition_roots(journal)?;
+    if &initial != initial_state_root || &final_ != final_state_root {
+        return Err(VerifyError::StateRootMismatch);
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshDeserialize;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct TestVectors {
+        soft_confirmation: SoftConfirmationVector,
+        sequencer_commitment: SequencerCommitmentVector,
+    }
+
+    #[derive(Deserialize)]
+    struct SoftConfirmationVector {
+        hash: String,
+        public_key: String,
+        signed_borsh: String,
+    }
+
+    #[derive(Deserialize)]
+    struct SequencerCommitmentVector {
+        soft_confirmation_hashes: Vec<String>,
+        borsh: String,
+    }
+
+    fn test_vectors() -> TestVectors {
+        serde_json::from_str(include_str!("../../../bin/test-data/test-vectors.json")).unwrap()
+    }
+
+    fn hash(hex_hash: &str) -> [u8; 32] {
+        hex::decode(hex_hash).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_verify_soft_confirmation() {
+        let vector = test_vectors().soft_confirmation;
+        let soft_confirmation = SignedSoftConfirmationBatch::try_from_slice(
+            &hex::decode(&vector.signed_borsh).unwrap(),
+        )
+        .unwrap();
+        let public_key = hex::decode(&vector.public_key).unwrap();
+
+        assert_eq!(
+            soft_confirmation_hash(&soft_confirmation),
+            hash(&vector.hash)
+        );
+        assert_eq!(
+            verify_soft_confirmation(&soft_confirmation, &public_key, SignatureScheme::Ed25519),
+            Ok(())
+        );
+        assert_eq!(
+            verify_soft_confirmation(&soft_confirmation, &[0; 32], SignatureScheme::Ed25519),
+            Err(VerifyError::PublicKeyMismatch)
+        );
+        // Ed25519 signatures don't verify as Schnorr signatures
+        assert!(verify_soft_confirmation(
+            &soft_confirmation,
+            &public_key,
+            SignatureScheme::Secp256k1Schnorr
+        )
+        .is_err());
+
+        let mut tampered = soft_confirmation.clone();
+        tampered.set_l1_fee_rate(soft_confirmation.l1_fee_rate() + 1);
+        assert!(matches!(
+            verify_soft_confirmation(&tampered, &public_key, SignatureScheme::Ed25519),
+            Err(VerifyError::HashMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_commitment() {
+        let vector = test_vectors().sequencer_commitment;
+        let commitment =
+            SequencerCommitment::try_from_slice(&hex::decode(&vector.borsh).unwrap()).unwrap();
+        let mut hashes: Vec<[u8; 32]> = vector
+            .soft_confirmation_hashes
+            .iter()
+            .map(|h| hash(h))
+            .collect();
+
+        assert_eq!(verify_commitment(&commitment, &hashes), Ok(()));
+        assert_eq!(
+            verify_commitment(&commitment, &[]),
+            Err(VerifyError::EmptyCommitment)
+        );
+
+        hashes.swap(0, 1);
+        assert_eq!(
+            verify_commitment(&commitment, &hashes),
+            Err(VerifyError::MerkleRootMismatch)
+        );
+        assert_eq!(
+            verify_commitment(&commitment, &hashes[..3]),
+            Err(VerifyError::MerkleRootMismatch)
+        );
+    }
+
+    #[test]
+    fn test_verify_state_transition() {
+        let initial = [1; 32];
+        let final_ = [2; 32];
+        let mut journal: Vec<u8> = initial
+            .iter()
+            .chain(final_.iter())
+            .flat_map(|byte| u32::from(*byte).to_le_bytes())
+            .collect();
+        // The rest of the state transition is not read
+        journal.extend_from_slice(&[7; 16]);
+
+        assert_eq!(state_transition_roots(&journal), Ok((initial, final_)));
+        assert_eq!(verify_state_transition(&journal, &initial, &final_), Ok(()));
+        assert_eq!(
+            verify_state_transition(&journal, &final_, &initial),
+            Err(VerifyError::StateRootMismatch)
+        );
+        assert!(state_transition_roots(&journal[..255]).is_err());
+
+        journal[1] = 1;
+        assert_eq!(
+            state_transition_roots(&journal),
+            Err(VerifyError::InvalidProofOutput("state roots are not bytes"))
+        );
+    }
+}
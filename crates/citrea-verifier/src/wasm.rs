@@ -0,0 +1,80 @@
+//! Bindings for JavaScript. Byte strings are taken as `Uint8Array`s, and the functions throw
+//! an `Error` with the reason if the verification fails.
+
+use borsh::BorshDeserialize;
+use sov_rollup_interface::da::{DaData, DaDataEnvelope};
+use sov_rollup_interface::soft_confirmation::{SignatureScheme, SignedSoftConfirmationBatch};
+use wasm_bindgen::prelude::*;
+
+/// Verifies a borsh serialized `SignedSoftConfirmationBatch` against the public key of the
+/// sequencer. `schnorr` selects the Schnorr signature scheme instead of Ed25519.
+#[wasm_bindgen(js_name = verifySoftConfirmation)]
+pub fn verify_soft_confirmation(
+    soft_confirmation: &[u8],
+    sequencer_public_key: &[u8],
+    schnorr: bool,
+) -> Result<(), JsError> {
+    let soft_confirmation = SignedSoftConfirmationBatch::try_from_slice(soft_confirmation)?;
+    let scheme = if schnorr {
+        SignatureScheme::Secp256k1Schnorr
+    } else {
+        SignatureScheme::Ed25519
+    };
+    crate::verify_soft_confirmation(&soft_confirmation, sequencer_public_key, scheme)?;
+    Ok(())
+}
+
+/// Returns the hash of a borsh serialized `SignedSoftConfirmationBatch`.
+#[wasm_bindgen(js_name = softConfirmationHash)]
+pub fn soft_confirmation_hash(soft_confirmation: &[u8]) -> Result<Vec<u8>, JsError> {
+    let soft_confirmation = SignedSoftConfirmationBatch::try_from_slice(soft_confirmation)?;
+    Ok(crate::soft_confirmation_hash(&soft_confirmation).to_vec())
+}
+
+/// Verifies a sequencer commitment, as written to DA, against the concatenated hashes of the
+/// soft confirmations it covers, in L2 height order.
+#[wasm_bindgen(js_name = verifyCommitment)]
+pub fn verify_commitment(da_data: &[u8], soft_confirmation_hashes: &[u8]) -> Result<(), JsError> {
+    let commitment = match DaData::from_envelope(da_data)? {
+        DaDataEnvelope::Known(DaData::SequencerCommitment(commitment)) => commitment,
+        DaDataEnvelope::Known(DaData::ZKProof(_)) => {
+            return Err(JsError::new(
+                "DA data is a proof, not a sequencer commitment",
+            ))
+        }
+        DaDataEnvelope::UnknownVersion(version) => {
+            return Err(JsError::new(&format!(
+                "DA data of unknown version {}",
+                version
+            )))
+        }
+    };
+    if soft_confirmation_hashes.len() % 32 != 0 {
+        return Err(JsError::new("Soft confirmation hashes are 32 bytes each"));
+    }
+    let hashes: Vec<[u8; 32]> = soft_confirmation_hashes
+        .chunks_exact(32)
+        .map(|hash| hash.try_into().expect("Chunks are 32 bytes"))
+        .collect();
+
+    crate::verify_commitment(&commitment, &hashes)?;
+    Ok(())
+}
+
+/// Verifies that the journal of a proof receipt commits to the transition between the given
+/// state roots.
+#[wasm_bindgen(js_name = verifyStateTransition)]
+pub fn verify_state_transition(
+    journal: &[u8],
+    initial_state_root: &[u8],
+    final_state_root: &[u8],
+) -> Result<(), JsError> {
+    let (Ok(initial_state_root), Ok(final_state_root)) = (
+        <&[u8; 32]>::try_from(initial_state_root),
+        <&[u8; 32]>::try_from(final_state_root),
+    ) else {
+        return Err(JsError::new("State roots are 32 bytes"));
+    };
+    crate::verify_state_transition(journal, initial_state_root, final_state_root)?;
+    Ok(())
+}
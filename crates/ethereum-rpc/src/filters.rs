@@ -0,0 +1,389 @@
+//! Filters installed with `eth_newFilter`, `eth_newBlockFilter` and
+//! `eth_newPendingTransactionFilter`, for the clients which poll `eth_getFilterChanges`
+//! instead of subscribing over WebSocket.
+//!
+//! Filters which are not polled for [`FILTER_TIMEOUT`] are uninstalled.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use citrea_evm::{Evm, Filter, FilterBlockOption, LogResponse};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use reth_primitives::{BlockNumberOrTag, B256};
+use serde::Serialize;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::services::da::DaService;
+use sov_stf_runner::{subscribe_pending_transactions, PendingTransaction};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::TryRecvError;
+use tracing::info;
+
+use crate::{Ethereum, ETH_RPC_ERROR};
+
+/// Time after which a filter which is not polled is uninstalled
+pub(crate) const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// What an installed filter tracks
+pub(crate) enum FilterKind {
+    /// Logs matching the filter in the new blocks
+    Logs(Box<Filter>),
+    /// Hashes of the new blocks
+    Blocks,
+    /// Hashes of the transactions entering the mempool of the sequencer
+    PendingTransactions(broadcast::Receiver<PendingTransaction>),
+}
+
+struct InstalledFilter {
+    kind: FilterKind,
+    /// Last block whose changes were returned
+    last_block: u64,
+    last_poll: Instant,
+}
+
+/// Changes of a filter since it was last polled, to be read from the state
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum FilterChanges {
+    /// Logs matching `filter` in the blocks `from_block..=to_block`
+    Logs {
+        filter: Box<Filter>,
+        from_block: u64,
+        to_block: u64,
+    },
+    /// Hashes of the blocks `from_block..=to_block`
+    Blocks { from_block: u64, to_block: u64 },
+    /// Hashes of the transactions which entered the mempool
+    PendingTransactions(Vec<B256>),
+}
+
+/// Response of `eth_getFilterChanges`
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum FilterChangesResponse {
+    Logs(Vec<LogResponse>),
+    Hashes(Vec<B256>),
+}
+
+/// Filters installed by the clients, by ID
+#[derive(Default)]
+pub(crate) struct Filters {
+    filters: HashMap<String, InstalledFilter>,
+}
+
+impl Filters {
+    /// Installs a filter whose changes start after block `head`, and returns its ID.
+    pub(crate) fn install(&mut self, kind: FilterKind, head: u64, now: Instant) -> String {
+        self.uninstall_expired(now);
+
+        let id = format!("0x{:x}", rand::random::<u128>());
+        self.filters.insert(
+            id.clone(),
+            InstalledFilter {
+                kind,
+                last_block: head,
+                last_poll: now,
+            },
+        );
+        id
+    }
+
+    /// Uninstalls the filter, returns whether it was installed.
+    pub(crate) fn uninstall(&mut self, id: &str) -> bool {
+        self.filters.remove(id).is_some()
+    }
+
+    /// Returns the changes of the filter since it was last polled, up to block `head`, and
+    /// marks them as returned.
+    pub(crate) fn poll(
+        &mut self,
+        id: &str,
+        head: u64,
+        now: Instant,
+    ) -> Result<FilterChanges, ErrorObjectOwned> {
+        let filter = self.get_mut(id, now)?;
+        let from_block = filter.last_block + 1;
+        filter.last_block = filter.last_block.max(head);
+
+        Ok(match &mut filter.kind {
+            FilterKind::Logs(filter) => {
+                // Changes stay within the range of the filter
+                let (from_block, to_block) = match &filter.block_option {
+                    FilterBlockOption::Range {
+                        from_block: filter_from,
+                        to_block: filter_to,
+                    } => (
+                        match filter_from {
+                            Some(BlockNumberOrTag::Number(number)) => from_block.max(*number),
+                            _ => from_block,
+                        },
+                        match filter_to {
+                            Some(BlockNumberOrTag::Number(number)) => head.min(*number),
+                            _ => head,
+                        },
+                    ),
+                    FilterBlockOption::AtBlockHash(_) => unreachable!("Rejected at install"),
+                };
+                FilterChanges::Logs {
+                    filter: filter.clone(),
+                    from_block,
+                    to_block,
+                }
+            }
+            FilterKind::Blocks => FilterChanges::Blocks {
+                from_block,
+                to_block: head,
+            },
+            FilterKind::PendingTransactions(transactions) => {
+                let mut hashes = vec![];
+                loop {
+                    match transactions.try_recv() {
+                        Ok(transaction) => hashes.push(B256::from(transaction.hash)),
+                        // Transactions missed by a slow client are skipped
+                        Err(TryRecvError::Lagged(_)) => continue,
+                        Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+                    }
+                }
+                FilterChanges::PendingTransactions(hashes)
+            }
+        })
+    }
+
+    /// Returns the log filter, to query all its logs.
+    pub(crate) fn logs_filter(
+        &mut self,
+        id: &str,
+        now: Instant,
+    ) -> Result<Filter, ErrorObjectOwned> {
+        match &self.get_mut(id, now)?.kind {
+            FilterKind::Logs(filter) => Ok(*filter.clone()),
+            _ => Err(to_jsonrpsee_error_object(
+                format!("Filter {} is not a log filter", id),
+                ETH_RPC_ERROR,
+            )),
+        }
+    }
+
+    fn get_mut(
+        &mut self,
+        id: &str,
+        now: Instant,
+    ) -> Result<&mut InstalledFilter, ErrorObjectOwned> {
+        self.uninstall_expired(now);
+
+        let filter = self
+            .filters
+            .get_mut(id)
+            .ok_or_else(|| to_jsonrpsee_error_object("filter not found", ETH_RPC_ERROR))?;
+        filter.last_poll = now;
+        Ok(filter)
+    }
+
+    fn uninstall_expired(&mut self, now: Instant) {
+        self.filters
+            .retain(|_, filter| now.duration_since(filter.last_poll) < FILTER_TIMEOUT);
+    }
+}
+
+pub(crate) fn register_filter_methods<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_async_method("eth_newFilter", |params, ethereum| async move {
+        info!("eth module: eth_newFilter");
+        let filter: Filter = params.one()?;
+        if let FilterBlockOption::AtBlockHash(_) = filter.block_option {
+            return Err(to_jsonrpsee_error_object(
+                "blockHash is not supported by installed filters, use eth_getLogs",
+                ETH_RPC_ERROR,
+            ));
+        }
+        install(&ethereum, FilterKind::Logs(Box::new(filter)))
+    })?;
+
+    rpc.register_async_method("eth_newBlockFilter", |_, ethereum| async move {
+        info!("eth module: eth_newBlockFilter");
+        install(&ethereum, FilterKind::Blocks)
+    })?;
+
+    rpc.register_async_method(
+        "eth_newPendingTransactionFilter",
+        |_, ethereum| async move {
+            info!("eth module: eth_newPendingTransactionFilter");
+            if ethereum.sequencer_client.is_some() {
+                return Err(to_jsonrpsee_error_object(
+                    "Pending transactions are only served by the sequencer",
+                    ETH_RPC_ERROR,
+                ));
+            }
+            install(
+                &ethereum,
+                FilterKind::PendingTransactions(subscribe_pending_transactions()),
+            )
+        },
+    )?;
+
+    rpc.register_async_method("eth_uninstallFilter", |params, ethereum| async move {
+        info!("eth module: eth_uninstallFilter");
+        let id: String = params.one()?;
+        Ok::<bool, ErrorObjectOwned>(ethereum.filters.lock().unwrap().uninstall(&id))
+    })?;
+
+    rpc.register_async_method("eth_getFilterChanges", |params, ethereum| async move {
+        info!("eth module: eth_getFilterChanges");
+        let id: String = params.one()?;
+
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        let head = evm.block_number(&mut working_set)?.to::<u64>();
+        let changes = ethereum
+            .filters
+            .lock()
+            .unwrap()
+            .poll(&id, head, Instant::now())?;
+
+        // The state is read outside of the lock
+        let response = match changes {
+            FilterChanges::Logs {
+                filter,
+                from_block,
+                to_block,
+            } => {
+                if from_block > to_block {
+                    FilterChangesResponse::Logs(vec![])
+                } else {
+                    let filter = Filter {
+                        block_option: FilterBlockOption::Range {
+                            from_block: Some(BlockNumberOrTag::Number(from_block)),
+                            to_block: Some(BlockNumberOrTag::Number(to_block)),
+                        },
+                        ..*filter
+                    };
+                    FilterChangesResponse::Logs(evm.eth_get_logs(filter, &mut working_set)?)
+                }
+            }
+            FilterChanges::Blocks {
+                from_block,
+                to_block,
+            } => {
+                let mut hashes = vec![];
+                for number in from_block..=to_block {
+                    let block = evm.get_block_by_number(
+                        Some(BlockNumberOrTag::Number(number)),
+                        Some(false),
+                        &mut working_set,
+                    )?;
+                    if let Some(hash) = block.and_then(|block| block.inner.header.hash) {
+                        hashes.push(hash);
+                    }
+                }
+                FilterChangesResponse::Hashes(hashes)
+            }
+            FilterChanges::PendingTransactions(hashes) => FilterChangesResponse::Hashes(hashes),
+        };
+        Ok::<FilterChangesResponse, ErrorObjectOwned>(response)
+    })?;
+
+    rpc.register_async_method("eth_getFilterLogs", |params, ethereum| async move {
+        info!("eth module: eth_getFilterLogs");
+        let id: String = params.one()?;
+        let filter = ethereum
+            .filters
+            .lock()
+            .unwrap()
+            .logs_filter(&id, Instant::now())?;
+
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        Ok::<Vec<LogResponse>, ErrorObjectOwned>(evm.eth_get_logs(filter, &mut working_set)?)
+    })?;
+
+    Ok(())
+}
+
+fn install<C: sov_modules_api::Context, Da: DaService>(
+    ethereum: &Ethereum<C, Da>,
+    kind: FilterKind,
+) -> Result<String, ErrorObjectOwned> {
+    let evm = Evm::<C>::default();
+    let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+    let head = evm.block_number(&mut working_set)?.to::<u64>();
+    Ok(ethereum
+        .filters
+        .lock()
+        .unwrap()
+        .install(kind, head, Instant::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range_filter(from_block: Option<u64>, to_block: Option<u64>) -> Filter {
+        Filter {
+            block_option: FilterBlockOption::Range {
+                from_block: from_block.map(BlockNumberOrTag::Number),
+                to_block: to_block.map(BlockNumberOrTag::Number),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_block_filter_changes() {
+        let now = Instant::now();
+        let mut filters = Filters::default();
+        let id = filters.install(FilterKind::Blocks, 10, now);
+
+        assert_eq!(
+            filters.poll(&id, 12, now).unwrap(),
+            FilterChanges::Blocks {
+                from_block: 11,
+                to_block: 12
+            }
+        );
+        // Nothing new
+        assert_eq!(
+            filters.poll(&id, 12, now).unwrap(),
+            FilterChanges::Blocks {
+                from_block: 13,
+                to_block: 12
+            }
+        );
+        assert!(filters.logs_filter(&id, now).is_err());
+    }
+
+    #[test]
+    fn test_logs_filter_changes_stay_in_range() {
+        let now = Instant::now();
+        let mut filters = Filters::default();
+        let filter = range_filter(Some(15), Some(20));
+        let id = filters.install(FilterKind::Logs(Box::new(filter.clone())), 10, now);
+
+        assert_eq!(
+            filters.poll(&id, 30, now).unwrap(),
+            FilterChanges::Logs {
+                filter: Box::new(filter.clone()),
+                from_block: 15,
+                to_block: 20,
+            }
+        );
+        assert_eq!(filters.logs_filter(&id, now).unwrap(), filter);
+    }
+
+    #[test]
+    fn test_filters_expire() {
+        let now = Instant::now();
+        let mut filters = Filters::default();
+        let polled = filters.install(FilterKind::Blocks, 0, now);
+        let idle = filters.install(FilterKind::Blocks, 0, now);
+
+        let later = now + FILTER_TIMEOUT / 2;
+        assert!(filters.poll(&polled, 1, later).is_ok());
+
+        let expired = now + FILTER_TIMEOUT;
+        assert!(filters.poll(&idle, 1, expired).is_err());
+        assert!(filters.poll(&polled, 1, expired).is_ok());
+        assert!(filters.uninstall(&polled));
+        assert!(!filters.uninstall(&polled));
+    }
+}
@@ -150,6 +150,36 @@ impl<C: sov_modules_api::Context> FeeHistoryCache<C> {
         result
     }
 
+    /// Calculates the rewards of the block for arbitrary percentiles, from its transactions.
+    /// Empty blocks have zero rewards, and are not read again.
+    pub fn rewards_for_percentiles(
+        &self,
+        block_number: u64,
+        entry: &FeeHistoryEntry,
+        percentiles: &[f64],
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Vec<U256>, EthApiError> {
+        if entry.gas_used == 0 {
+            return Ok(vec![U256::ZERO; percentiles.len()]);
+        }
+
+        let (block, receipts) = self
+            .block_cache
+            .get_block_with_receipts(block_number, working_set)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let transactions = match &block.transactions {
+            BlockTransactions::Full(transactions) => transactions,
+            _ => unreachable!(),
+        };
+        calculate_reward_percentiles_for_block(
+            percentiles,
+            entry.gas_used,
+            entry.base_fee_per_gas,
+            transactions,
+            &receipts,
+        )
+    }
+
     /// Generates predefined set of percentiles
     ///
     /// This returns 100 * resolution points
@@ -13,6 +13,7 @@ use reth_primitives::{BlockNumberOrTag, B256, U256, U64};
 use reth_rpc_types::{BlockTransactions, FeeHistory};
 use serde::{Deserialize, Serialize};
 use sov_modules_api::WorkingSet;
+use sov_stf_runner::GasOracleConfig;
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -41,6 +42,9 @@ pub struct GasPriceOracleConfig {
     /// The percentile of gas prices to use for the estimate
     pub percentile: u32,
 
+    /// The number of transactions sampled in a block
+    pub sample_number: u32,
+
     /// The maximum number of headers to keep in the cache
     pub max_header_history: u64,
 
@@ -53,6 +57,9 @@ pub struct GasPriceOracleConfig {
     /// The maximum gas price to use for the estimate
     pub max_price: Option<U256>,
 
+    /// The minimum gas price to use for the estimate, taking precedence over `max_price`
+    pub min_price: Option<U256>,
+
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
 }
@@ -62,10 +69,12 @@ impl Default for GasPriceOracleConfig {
         GasPriceOracleConfig {
             blocks: 20,
             percentile: 60,
+            sample_number: SAMPLE_NUMBER,
             max_header_history: MAX_HEADER_HISTORY,
             max_block_history: MAX_HEADER_HISTORY,
             default: None,
             max_price: Some(DEFAULT_MAX_PRICE),
+            min_price: None,
             ignore_price: Some(DEFAULT_IGNORE_PRICE),
         }
     }
@@ -82,13 +91,55 @@ impl GasPriceOracleConfig {
         Self {
             blocks: blocks.unwrap_or(20),
             percentile: percentile.unwrap_or(60),
+            sample_number: SAMPLE_NUMBER,
             max_header_history: 1024,
             max_block_history: 1024,
             default: None,
             max_price: max_price.map(U256::from).or(Some(DEFAULT_MAX_PRICE)),
+            min_price: None,
             ignore_price: ignore_price.map(U256::from).or(Some(DEFAULT_IGNORE_PRICE)),
         }
     }
+
+    /// Raises the minimum price to `floor`, the lowest tip the sequencer accepts, so that the
+    /// suggested prices are never rejected by it
+    pub fn with_acceptance_floor(mut self, floor: u64) -> Self {
+        if floor > 0 {
+            let floor = U256::from(floor);
+            self.min_price = Some(
+                self.min_price
+                    .map_or(floor, |min_price| min_price.max(floor)),
+            );
+        }
+        self
+    }
+
+    /// Bounds `price` by the maximum and minimum prices, the minimum taking precedence
+    fn clamp_price(&self, mut price: U256) -> U256 {
+        if let Some(max_price) = self.max_price {
+            price = price.min(max_price);
+        }
+        if let Some(min_price) = self.min_price {
+            price = price.max(min_price);
+        }
+        price
+    }
+}
+
+impl From<GasOracleConfig> for GasPriceOracleConfig {
+    fn from(config: GasOracleConfig) -> Self {
+        Self {
+            blocks: config.blocks,
+            percentile: config.percentile,
+            sample_number: config.samples_per_block,
+            max_header_history: config.max_history,
+            max_block_history: config.max_history,
+            default: Some(U256::from(config.default_tip)),
+            max_price: Some(U256::from(config.max_price)),
+            min_price: config.min_price.map(U256::from),
+            ignore_price: Some(U256::from(config.ignore_price)),
+        }
+    }
 }
 
 /// Calculates a gas price depending on recent blocks.
@@ -126,10 +177,20 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
 
         let fee_history_cache = FeeHistoryCache::new(fee_history_config, arc_cache.clone());
 
+        // Suggested until a block with transactions is seen
+        let last_price = GasPriceOracleResult {
+            price: oracle_config.clamp_price(
+                oracle_config
+                    .default
+                    .unwrap_or(GasPriceOracleResult::default().price),
+            ),
+            ..Default::default()
+        };
+
         Self {
             provider: provider.clone(),
             oracle_config,
-            last_price: Default::default(),
+            last_price: Mutex::new(last_price),
             fee_history_cache: Mutex::new(fee_history_cache),
             cache: arc_cache,
         }
@@ -152,6 +213,17 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             return Ok(FeeHistory::default());
         }
 
+        // There is no pending block, the next block is built on the latest one
+        let newest_block = match newest_block {
+            BlockNumberOrTag::Pending => BlockNumberOrTag::Latest,
+            BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized => {
+                return Err(EthApiError::InvalidParams(
+                    "unsupported block tag".to_string(),
+                ));
+            }
+            newest_block => newest_block,
+        };
+
         // See https://github.com/ethereum/go-ethereum/blob/2754b197c935ee63101cbbca2752338246384fec/eth/gasprice/feehistory.go#L218C8-L225
         let max_fee_history = if reward_percentiles.is_none() {
             self.config().max_header_history
@@ -177,14 +249,8 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             block_count = end_block_plus;
         }
 
-        // If reward percentiles were specified, we
-        // need to validate that they are monotonically
-        // increasing and 0 <= p <= 100
-        // Note: The types used ensure that the percentiles are never < 0
         if let Some(percentiles) = &reward_percentiles {
-            if percentiles.windows(2).any(|w| w[0] > w[1] || w[0] > 100.) {
-                return Err(EthApiError::InvalidRewardPercentiles);
-            }
+            validate_reward_percentiles(percentiles)?;
         }
 
         // Fetch the headers and ensure we got all of them
@@ -209,15 +275,34 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             return Err(EthApiError::InvalidBlockRange);
         }
 
-        for entry in &fee_entries {
+        // The rewards of the percentiles of the predefined set are cached, the other ones are
+        // calculated from the transactions of the blocks
+        let cached_percentiles = reward_percentiles.as_ref().map_or(true, |percentiles| {
+            percentiles
+                .iter()
+                .all(|percentile| (percentile * resolution as f64).fract() == 0.0)
+        });
+
+        for (block_number, entry) in (start_block..).zip(&fee_entries) {
             base_fee_per_gas.push(U256::from(entry.base_fee_per_gas));
             gas_used_ratio.push(entry.gas_used_ratio);
 
             if let Some(percentiles) = &reward_percentiles {
-                let mut block_rewards = Vec::with_capacity(percentiles.len());
-                for &percentile in percentiles.iter() {
-                    block_rewards.push(self.approximate_percentile(entry, percentile, resolution));
-                }
+                let block_rewards = if cached_percentiles {
+                    percentiles
+                        .iter()
+                        .map(|&percentile| {
+                            self.approximate_percentile(entry, percentile, resolution)
+                        })
+                        .collect()
+                } else {
+                    fee_history_cache.rewards_for_percentiles(
+                        block_number,
+                        entry,
+                        percentiles,
+                        working_set,
+                    )?
+                };
                 rewards.push(block_rewards);
             }
         }
@@ -229,13 +314,18 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             self.provider.get_chain_config(working_set).base_fee_params,
         )));
 
+        // Blob transactions are not supported, so blocks use no blob gas and the blob base
+        // fee stays at zero. The fields are still filled for clients expecting Cancun blocks.
+        let base_fee_per_blob_gas = vec![U256::ZERO; base_fee_per_gas.len()];
+        let blob_gas_used_ratio = vec![0.0; gas_used_ratio.len()];
+
         Ok(FeeHistory {
             base_fee_per_gas,
             gas_used_ratio,
             oldest_block: U256::from(start_block),
             reward: reward_percentiles.map(|_| rewards),
-            base_fee_per_blob_gas: Default::default(),
-            blob_gas_used_ratio: Default::default(),
+            base_fee_per_blob_gas,
+            blob_gas_used_ratio,
         })
     }
 
@@ -275,7 +365,11 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
 
         for _ in 0..max_blocks {
             let (parent_hash, block_values) = self
-                .get_block_values(current_hash, SAMPLE_NUMBER as usize, working_set)
+                .get_block_values(
+                    current_hash,
+                    self.oracle_config.sample_number as usize,
+                    working_set,
+                )
                 .await?
                 .ok_or(EthApiError::UnknownBlockNumber)?;
 
@@ -303,12 +397,8 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
                 .expect("gas price index is a percent of nonzero array length, so a value always exists; qed");
         }
 
-        // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
-            if price > max_price {
-                price = max_price;
-            }
-        }
+        // constrain to the max and min prices
+        let price = self.oracle_config.clamp_price(price);
 
         *last_price = GasPriceOracleResult {
             block_hash: header.hash.unwrap(),
@@ -399,6 +489,31 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
     }
 }
 
+/// Parses the block count of `eth_feeHistory`, either a hex quantity or a decimal number
+pub(crate) fn parse_block_count(block_count: &serde_json::Value) -> EthResult<u64> {
+    let invalid = || EthApiError::InvalidParams(format!("invalid block count {}", block_count));
+    match block_count {
+        serde_json::Value::Number(number) => number.as_u64().ok_or_else(invalid),
+        serde_json::Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).map_err(|_| invalid()),
+            None => string.parse().map_err(|_| invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Checks that the reward percentiles of `eth_feeHistory` are monotonically increasing and
+/// between 0 and 100
+pub(crate) fn validate_reward_percentiles(percentiles: &[f64]) -> EthResult<()> {
+    let out_of_range = percentiles
+        .iter()
+        .any(|percentile| !(0.0..=100.0).contains(percentile));
+    if out_of_range || percentiles.windows(2).any(|w| w[0] > w[1]) {
+        return Err(EthApiError::InvalidRewardPercentiles);
+    }
+    Ok(())
+}
+
 /// Stores the last result that the oracle returned
 #[derive(Debug, Clone)]
 pub struct GasPriceOracleResult {
@@ -498,6 +613,49 @@ mod tests {
         assert_eq!(DEFAULT_IGNORE_PRICE, U256::from(2u64));
     }
 
+    #[test]
+    fn clamps_price_to_acceptance_floor() {
+        let config = GasPriceOracleConfig::from(GasOracleConfig {
+            min_price: Some(10),
+            max_price: 1000,
+            ..Default::default()
+        });
+        assert_eq!(config.clamp_price(U256::from(5)), U256::from(10));
+        assert_eq!(config.clamp_price(U256::from(100)), U256::from(100));
+        assert_eq!(config.clamp_price(U256::from(5000)), U256::from(1000));
+
+        // The floor of the sequencer wins over the configured prices
+        let config = config.with_acceptance_floor(2000);
+        assert_eq!(config.min_price, Some(U256::from(2000)));
+        assert_eq!(config.clamp_price(U256::from(100)), U256::from(2000));
+        assert_eq!(
+            GasPriceOracleConfig::default()
+                .with_acceptance_floor(0)
+                .min_price,
+            None
+        );
+    }
+
+    #[test]
+    fn parses_block_count() {
+        assert_eq!(parse_block_count(&serde_json::json!("0x100")).unwrap(), 256);
+        assert_eq!(parse_block_count(&serde_json::json!("10")).unwrap(), 10);
+        assert_eq!(parse_block_count(&serde_json::json!(10)).unwrap(), 10);
+        assert!(parse_block_count(&serde_json::json!("0xzz")).is_err());
+        assert!(parse_block_count(&serde_json::json!(-1)).is_err());
+        assert!(parse_block_count(&serde_json::json!(null)).is_err());
+    }
+
+    #[test]
+    fn validates_reward_percentiles() {
+        assert!(validate_reward_percentiles(&[]).is_ok());
+        assert!(validate_reward_percentiles(&[0.0, 12.34, 12.34, 100.0]).is_ok());
+        assert!(validate_reward_percentiles(&[50.0, 10.0]).is_err());
+        assert!(validate_reward_percentiles(&[10.0, 100.5]).is_err());
+        assert!(validate_reward_percentiles(&[-1.0]).is_err());
+        assert!(validate_reward_percentiles(&[f64::NAN]).is_err());
+    }
+
     proptest! {
 
         #[test]
@@ -0,0 +1,423 @@
+//! Opt-in mirror of the EVM blocks, transactions and logs into a SQLite or Postgres
+//! database, served by `citrea_getIndexedBlocks`, `citrea_getAddressTransactions` and
+//! `citrea_getIndexedLogs`, for the lightweight explorers of test networks.
+//!
+//! Blocks are indexed in order as the node produces or syncs them, and indexing resumes
+//! from the last indexed block after a restart. Hashes, addresses and data are stored as
+//! `0x` prefixed lowercase hex, and values in wei as decimal strings.
+
+use std::time::Duration;
+
+use citrea_evm::Evm;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, B256};
+use reth_rpc_types::{BlockTransactions, RichBlock, TransactionReceipt};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::WorkingSet;
+use sov_stf_runner::IndexerConfig;
+use sqlx::any::{AnyPool, AnyPoolOptions};
+use sqlx::Row;
+use tracing::{info, warn};
+
+/// Largest number of rows returned by a query
+pub(crate) const MAX_INDEXER_PAGE_SIZE: u64 = 100;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS blocks (
+        number BIGINT PRIMARY KEY,
+        hash TEXT NOT NULL,
+        parent_hash TEXT NOT NULL,
+        timestamp BIGINT NOT NULL,
+        gas_used BIGINT NOT NULL,
+        base_fee_per_gas BIGINT,
+        transaction_count BIGINT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS transactions (
+        hash TEXT PRIMARY KEY,
+        block_number BIGINT NOT NULL,
+        transaction_index BIGINT NOT NULL,
+        from_address TEXT NOT NULL,
+        to_address TEXT,
+        contract_address TEXT,
+        value TEXT NOT NULL,
+        gas_used BIGINT NOT NULL,
+        status BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS transactions_from ON transactions (from_address, block_number)",
+    "CREATE INDEX IF NOT EXISTS transactions_to ON transactions (to_address, block_number)",
+    "CREATE TABLE IF NOT EXISTS logs (
+        block_number BIGINT NOT NULL,
+        log_index BIGINT NOT NULL,
+        transaction_hash TEXT NOT NULL,
+        address TEXT NOT NULL,
+        topic0 TEXT,
+        topic1 TEXT,
+        topic2 TEXT,
+        topic3 TEXT,
+        data TEXT NOT NULL,
+        PRIMARY KEY (block_number, log_index)
+    )",
+    "CREATE INDEX IF NOT EXISTS logs_address ON logs (address, block_number)",
+    "CREATE INDEX IF NOT EXISTS logs_topic0 ON logs (topic0, block_number)",
+];
+
+/// Indexed block, as returned by `citrea_getIndexedBlocks`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedBlock {
+    number: u64,
+    hash: String,
+    parent_hash: String,
+    timestamp: u64,
+    gas_used: u64,
+    base_fee_per_gas: Option<u64>,
+    transaction_count: u64,
+}
+
+/// Indexed transaction, as returned by `citrea_getAddressTransactions`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedTransaction {
+    hash: String,
+    block_number: u64,
+    transaction_index: u64,
+    from: String,
+    to: Option<String>,
+    contract_address: Option<String>,
+    /// Value in wei, as a decimal string
+    value: String,
+    gas_used: u64,
+    /// Whether the transaction succeeded
+    status: bool,
+}
+
+/// Indexed log, as returned by `citrea_getIndexedLogs`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedLog {
+    block_number: u64,
+    log_index: u64,
+    transaction_hash: String,
+    address: String,
+    topics: Vec<String>,
+    data: String,
+}
+
+/// Page of the rows of a query, newest first
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexerPage {
+    /// Number of rows, [`MAX_INDEXER_PAGE_SIZE`] at most and by default
+    pub(crate) limit: Option<u64>,
+    /// Number of rows skipped
+    #[serde(default)]
+    pub(crate) offset: u64,
+}
+
+impl IndexerPage {
+    fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(MAX_INDEXER_PAGE_SIZE)
+            .min(MAX_INDEXER_PAGE_SIZE) as i64
+    }
+}
+
+/// Filter of `citrea_getIndexedLogs`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedLogsFilter {
+    pub(crate) address: Option<Address>,
+    pub(crate) topic0: Option<B256>,
+    pub(crate) from_block: Option<u64>,
+    pub(crate) to_block: Option<u64>,
+}
+
+fn to_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Mirror of the chain data in the database of [`IndexerConfig`]
+#[derive(Clone)]
+pub(crate) struct Indexer {
+    pool: AnyPool,
+}
+
+impl Indexer {
+    /// Connects to the database and spawns the task indexing the blocks of `storage`.
+    pub(crate) fn spawn<C: sov_modules_api::Context>(
+        config: IndexerConfig,
+        storage: C::Storage,
+    ) -> anyhow::Result<Self> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new().connect_lazy(&config.database_url)?;
+        let indexer = Self { pool };
+
+        info!("Indexing the chain into {}", config.database_url);
+        let poll_interval = Duration::from_millis(config.poll_interval_ms);
+        tokio::spawn(indexer.clone().index_blocks::<C>(storage, poll_interval));
+
+        Ok(indexer)
+    }
+
+    async fn index_blocks<C: sov_modules_api::Context>(
+        self,
+        storage: C::Storage,
+        poll_interval: Duration,
+    ) {
+        for statement in SCHEMA {
+            if let Err(e) = sqlx::query(*statement).execute(&self.pool).await {
+                warn!("Indexer: failed to create the schema: {}", e);
+                return;
+            }
+        }
+
+        let evm = Evm::<C>::default();
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+
+            let mut next = match sqlx::query_scalar::<_, i64>(
+                "SELECT COALESCE(MAX(number) + 1, 0) FROM blocks",
+            )
+            .fetch_one(&self.pool)
+            .await
+            {
+                Ok(next) => next as u64,
+                Err(e) => {
+                    warn!("Indexer: failed to read the last indexed block: {}", e);
+                    continue;
+                }
+            };
+
+            // Index every block the node has until its head
+            loop {
+                let (block, receipts) = {
+                    let mut working_set = WorkingSet::<C>::new(storage.clone());
+                    let number = BlockNumberOrTag::Number(next);
+                    let Ok(Some(block)) =
+                        evm.get_block_by_number(Some(number), Some(true), &mut working_set)
+                    else {
+                        break;
+                    };
+                    let receipts = evm
+                        .get_block_receipts(BlockId::Number(number), &mut working_set)
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default();
+                    (block, receipts)
+                };
+
+                if let Err(e) = self.insert_block(&block, &receipts).await {
+                    warn!("Indexer: failed to index block {}: {}", next, e);
+                    break;
+                }
+                next += 1;
+            }
+        }
+    }
+
+    async fn insert_block(
+        &self,
+        block: &RichBlock,
+        receipts: &[TransactionReceipt],
+    ) -> Result<(), sqlx::Error> {
+        let header = &block.header;
+        let number = header.number.unwrap_or_default().to::<u64>() as i64;
+        let values = match &block.transactions {
+            BlockTransactions::Full(transactions) => {
+                transactions.iter().map(|tx| tx.value).collect::<Vec<_>>()
+            }
+            _ => unreachable!("Blocks are indexed with full transactions"),
+        };
+
+        let mut db_tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO blocks
+                (number, hash, parent_hash, timestamp, gas_used, base_fee_per_gas, transaction_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(number)
+        .bind(to_hex(header.hash.unwrap_or_default()))
+        .bind(to_hex(header.parent_hash))
+        .bind(header.timestamp.to::<u64>() as i64)
+        .bind(header.gas_used.to::<u64>() as i64)
+        .bind(header.base_fee_per_gas.map(|fee| fee.to::<u64>() as i64))
+        .bind(receipts.len() as i64)
+        .execute(&mut *db_tx)
+        .await?;
+
+        for (receipt, value) in receipts.iter().zip(values) {
+            let tx_hash = to_hex(receipt.transaction_hash.unwrap_or_default());
+            sqlx::query(
+                "INSERT INTO transactions
+                    (hash, block_number, transaction_index, from_address, to_address,
+                    contract_address, value, gas_used, status)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&tx_hash)
+            .bind(number)
+            .bind(receipt.transaction_index.to::<u64>() as i64)
+            .bind(to_hex(receipt.from))
+            .bind(receipt.to.map(to_hex))
+            .bind(receipt.contract_address.map(to_hex))
+            .bind(value.to_string())
+            .bind(receipt.gas_used.unwrap_or_default().to::<u64>() as i64)
+            .bind(receipt.status_code.unwrap_or_default().to::<u64>() as i64)
+            .execute(&mut *db_tx)
+            .await?;
+
+            for log in &receipt.logs {
+                let topic = |index: usize| log.topics.get(index).map(to_hex);
+                sqlx::query(
+                    "INSERT INTO logs
+                        (block_number, log_index, transaction_hash, address,
+                        topic0, topic1, topic2, topic3, data)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    ON CONFLICT DO NOTHING",
+                )
+                .bind(number)
+                .bind(log.log_index.unwrap_or_default().to::<u64>() as i64)
+                .bind(&tx_hash)
+                .bind(to_hex(log.address))
+                .bind(topic(0))
+                .bind(topic(1))
+                .bind(topic(2))
+                .bind(topic(3))
+                .bind(to_hex(&log.data))
+                .execute(&mut *db_tx)
+                .await?;
+            }
+        }
+
+        db_tx.commit().await
+    }
+
+    /// Returns the indexed blocks, newest first.
+    pub(crate) async fn blocks(&self, page: IndexerPage) -> Result<Vec<IndexedBlock>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT number, hash, parent_hash, timestamp, gas_used, base_fee_per_gas,
+                transaction_count
+            FROM blocks ORDER BY number DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(page.limit())
+        .bind(page.offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(IndexedBlock {
+                    number: row.try_get::<i64, _>("number")? as u64,
+                    hash: row.try_get("hash")?,
+                    parent_hash: row.try_get("parent_hash")?,
+                    timestamp: row.try_get::<i64, _>("timestamp")? as u64,
+                    gas_used: row.try_get::<i64, _>("gas_used")? as u64,
+                    base_fee_per_gas: row
+                        .try_get::<Option<i64>, _>("base_fee_per_gas")?
+                        .map(|fee| fee as u64),
+                    transaction_count: row.try_get::<i64, _>("transaction_count")? as u64,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the transactions sent by or to `address`, newest first.
+    pub(crate) async fn address_transactions(
+        &self,
+        address: Address,
+        page: IndexerPage,
+    ) -> Result<Vec<IndexedTransaction>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT hash, block_number, transaction_index, from_address, to_address,
+                contract_address, value, gas_used, status
+            FROM transactions WHERE from_address = $1 OR to_address = $1
+            ORDER BY block_number DESC, transaction_index DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(to_hex(address))
+        .bind(page.limit())
+        .bind(page.offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(IndexedTransaction {
+                    hash: row.try_get("hash")?,
+                    block_number: row.try_get::<i64, _>("block_number")? as u64,
+                    transaction_index: row.try_get::<i64, _>("transaction_index")? as u64,
+                    from: row.try_get("from_address")?,
+                    to: row.try_get("to_address")?,
+                    contract_address: row.try_get("contract_address")?,
+                    value: row.try_get("value")?,
+                    gas_used: row.try_get::<i64, _>("gas_used")? as u64,
+                    status: row.try_get::<i64, _>("status")? == 1,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the logs matching the filter, newest first.
+    pub(crate) async fn logs(
+        &self,
+        filter: IndexedLogsFilter,
+        page: IndexerPage,
+    ) -> Result<Vec<IndexedLog>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT block_number, log_index, transaction_hash, address,
+                topic0, topic1, topic2, topic3, data
+            FROM logs
+            WHERE ($1 IS NULL OR address = $1)
+                AND ($2 IS NULL OR topic0 = $2)
+                AND block_number >= $3 AND block_number <= $4
+            ORDER BY block_number DESC, log_index DESC LIMIT $5 OFFSET $6",
+        )
+        .bind(filter.address.map(to_hex))
+        .bind(filter.topic0.map(to_hex))
+        .bind(filter.from_block.unwrap_or(0) as i64)
+        .bind(filter.to_block.map_or(i64::MAX, |to_block| to_block as i64))
+        .bind(page.limit())
+        .bind(page.offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let mut topics = vec![];
+                for column in ["topic0", "topic1", "topic2", "topic3"] {
+                    if let Some(topic) = row.try_get::<Option<String>, _>(column)? {
+                        topics.push(topic);
+                    }
+                }
+                Ok(IndexedLog {
+                    block_number: row.try_get::<i64, _>("block_number")? as u64,
+                    log_index: row.try_get::<i64, _>("log_index")? as u64,
+                    transaction_hash: row.try_get("transaction_hash")?,
+                    address: row.try_get("address")?,
+                    topics,
+                    data: row.try_get("data")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_limit() {
+        assert_eq!(IndexerPage::default().limit(), MAX_INDEXER_PAGE_SIZE as i64);
+        let page = IndexerPage {
+            limit: Some(10),
+            offset: 0,
+        };
+        assert_eq!(page.limit(), 10);
+        let page = IndexerPage {
+            limit: Some(1000),
+            offset: 0,
+        };
+        assert_eq!(page.limit(), MAX_INDEXER_PAGE_SIZE as i64);
+    }
+}
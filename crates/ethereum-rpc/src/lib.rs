@@ -1,4 +1,13 @@
+mod filters;
 mod gas_price;
+mod indexer;
+#[cfg(feature = "local")]
+mod signing;
+mod streaming;
+mod subscription;
+mod trace;
+mod trace_stream;
+mod wallet_chain;
 
 use std::collections::BTreeMap;
 use std::process::Command;
@@ -8,17 +17,21 @@ use std::sync::Mutex;
 pub use citrea_evm::DevSigner;
 use citrea_evm::{EthApiError, Evm};
 use ethers::types::Bytes;
+use filters::{register_filter_methods, Filters};
 pub use gas_price::fee_history::FeeHistoryCacheConfig;
 use gas_price::gas_oracle::GasPriceOracle;
 pub use gas_price::gas_oracle::GasPriceOracleConfig;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
-use reth_primitives::{keccak256, BlockNumberOrTag, B256, U256};
+use reth_primitives::{keccak256, Address, BlockNumberOrTag, B256, U256};
+use reth_rpc_types::trace::filter::TraceFilter;
 use reth_rpc_types::trace::geth::{
     CallConfig, CallFrame, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerConfig,
     GethDebugTracerType, GethDebugTracingOptions, GethTrace, NoopFrame,
 };
-use reth_rpc_types::FeeHistory;
+use reth_rpc_types::trace::parity::LocalizedTransactionTrace;
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::{BlockTransactions, FeeHistory};
 use rustc_version_runtime::version;
 use schnellru::{ByLength, LruMap};
 use sequencer_client::SequencerClient;
@@ -26,9 +39,17 @@ use serde_json::json;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_modules_api::WorkingSet;
 use sov_rollup_interface::services::da::DaService;
-use tracing::info;
-
-use crate::gas_price::gas_oracle::convert_u256_to_u64;
+use sov_state::storage::NativeStorage;
+use sov_stf_runner::{IndexerConfig, StreamingConfig, WalletChainConfig};
+use tracing::{info, warn};
+
+use crate::gas_price::gas_oracle::{convert_u256_to_u64, parse_block_count};
+use crate::indexer::{IndexedLogsFilter, Indexer, IndexerPage};
+use crate::streaming::spawn_streaming_sink;
+use crate::subscription::register_subscriptions;
+use crate::trace::{filtered_parity_traces, paginate, TxLocation, MAX_TRACE_FILTER_BLOCK_RANGE};
+use crate::trace_stream::register_trace_stream;
+use crate::wallet_chain::AddEthereumChainParameters;
 
 const ETH_RPC_ERROR: &str = "ETH_RPC_ERROR";
 
@@ -40,6 +61,15 @@ pub struct EthRpcConfig {
     pub fee_history_cache_config: FeeHistoryCacheConfig,
     #[cfg(feature = "local")]
     pub eth_signer: DevSigner,
+    /// Serve `eth_sign` and `eth_signTransaction` with the accounts of `eth_signer`
+    #[cfg(feature = "local")]
+    pub enable_dev_signing: bool,
+    /// Network metadata served by `citrea_getAddEthereumChainParameters`
+    pub wallet_chain: Option<WalletChainConfig>,
+    /// NATS server the chain data is streamed to, only used by full nodes
+    pub streaming: Option<StreamingConfig>,
+    /// Database the chain data is mirrored into, for `citrea_getIndexedBlocks` and friends
+    pub indexer: Option<IndexerConfig>,
 }
 
 pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
@@ -47,18 +77,34 @@ pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
     eth_rpc_config: EthRpcConfig,
     storage: C::Storage,
     sequencer_client: Option<SequencerClient>,
-) -> RpcModule<Ethereum<C, Da>> {
+) -> RpcModule<Ethereum<C, Da>>
+where
+    C::Storage: NativeStorage,
+{
     // Unpack config
     let EthRpcConfig {
         #[cfg(feature = "local")]
         eth_signer,
         gas_price_oracle_config,
         fee_history_cache_config,
+        #[cfg(feature = "local")]
+        enable_dev_signing,
+        wallet_chain,
+        streaming,
+        indexer,
     } = eth_rpc_config;
 
     // If the node does not have a sequencer client, then it is the sequencer.
     let is_sequencer = sequencer_client.is_none();
 
+    // Chain events are only seen by full nodes
+    if let (Some(streaming), false) = (streaming, is_sequencer) {
+        spawn_streaming_sink::<C>(streaming, storage.clone());
+    }
+    let indexer = indexer.map(|config| {
+        Indexer::spawn::<C>(config, storage.clone()).expect("Invalid indexer database URL")
+    });
+
     // If the running node is a full node rpc context should also have sequencer client so that it can send txs to sequencer
     let mut rpc = RpcModule::new(Ethereum::new(
         da_service,
@@ -68,9 +114,20 @@ pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
         eth_signer,
         storage,
         sequencer_client,
+        wallet_chain,
+        indexer,
     ));
 
     register_rpc_methods(&mut rpc, is_sequencer).expect("Failed to register ethereum RPC methods");
+    register_proof_methods(&mut rpc).expect("Failed to register ethereum RPC proof methods");
+    register_subscriptions(&mut rpc).expect("Failed to register ethereum RPC subscriptions");
+    register_trace_stream(&mut rpc).expect("Failed to register ethereum RPC trace stream");
+    register_filter_methods(&mut rpc).expect("Failed to register ethereum RPC filter methods");
+    #[cfg(feature = "local")]
+    if enable_dev_signing {
+        signing::register_signing_methods(&mut rpc)
+            .expect("Failed to register ethereum signing RPC methods");
+    }
     rpc
 }
 
@@ -82,11 +139,15 @@ pub struct Ethereum<C: sov_modules_api::Context, Da: DaService> {
     eth_signer: DevSigner,
     storage: C::Storage,
     sequencer_client: Option<SequencerClient>,
+    wallet_chain: Option<WalletChainConfig>,
+    indexer: Option<Indexer>,
     web3_client_version: String,
     trace_cache: Mutex<LruMap<u64, Vec<GethTrace>, ByLength>>,
+    filters: Mutex<Filters>,
 }
 
 impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         da_service: Da,
         gas_price_oracle_config: GasPriceOracleConfig,
@@ -94,6 +155,8 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
         #[cfg(feature = "local")] eth_signer: DevSigner,
         storage: C::Storage,
         sequencer_client: Option<SequencerClient>,
+        wallet_chain: Option<WalletChainConfig>,
+        indexer: Option<Indexer>,
     ) -> Self {
         let evm = Evm::<C>::default();
         let gas_price_oracle =
@@ -122,12 +185,91 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
             eth_signer,
             storage,
             sequencer_client,
+            wallet_chain,
+            indexer,
             web3_client_version: current_version,
             trace_cache,
+            filters: Mutex::default(),
         }
     }
 }
 
+impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
+    /// Returns the indexer, fails if the node doesn't index the chain.
+    fn indexer(&self) -> Result<&Indexer, ErrorObjectOwned> {
+        self.indexer
+            .as_ref()
+            .ok_or_else(|| to_jsonrpsee_error_object("Indexer is not enabled", ETH_RPC_ERROR))
+    }
+
+    /// Returns the call traces of all the transactions in the block, from the cache or by
+    /// re-executing the block.
+    fn get_cached_block_traces(
+        &self,
+        evm: &Evm<C>,
+        block_number: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Vec<GethTrace>, ErrorObjectOwned> {
+        if let Some(traces) = self.trace_cache.lock().unwrap().get(&block_number) {
+            return Ok(traces.clone());
+        }
+        let traces = evm
+            .trace_block_transactions_by_number(
+                block_number,
+                Some(create_trace_cache_opts()),
+                None,
+                working_set,
+            )
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+        self.trace_cache
+            .lock()
+            .unwrap()
+            .insert(block_number, traces.clone());
+        Ok(traces)
+    }
+
+    /// Returns the Parity style traces of the transactions of a block matching the address
+    /// filters of `filter`, `None` if the block does not exist.
+    fn get_block_parity_traces(
+        &self,
+        evm: &Evm<C>,
+        block_number: u64,
+        filter: &TraceFilter,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Option<Vec<LocalizedTransactionTrace>>, ErrorObjectOwned> {
+        let Some(block) = evm.get_block_by_number(
+            Some(BlockNumberOrTag::Number(block_number)),
+            Some(false),
+            working_set,
+        )?
+        else {
+            return Ok(None);
+        };
+        let tx_hashes = match &block.transactions {
+            BlockTransactions::Hashes(hashes) => hashes.clone(),
+            _ => unreachable!("Block is requested without full transactions"),
+        };
+        if tx_hashes.is_empty() {
+            return Ok(Some(vec![]));
+        }
+
+        let block_traces = self.get_cached_block_traces(evm, block_number, working_set)?;
+        let mut traces = vec![];
+        for (tx_index, (trace, tx_hash)) in block_traces.iter().zip(tx_hashes).enumerate() {
+            if let GethTrace::CallTracer(frame) = trace {
+                let location = TxLocation {
+                    block_hash: block.header.hash.unwrap_or_default(),
+                    block_number,
+                    tx_hash,
+                    tx_index: tx_index as u64,
+                };
+                traces.extend(filtered_parity_traces(frame, &location, filter));
+            }
+        }
+        Ok(Some(traces))
+    }
+}
+
 // impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
 //     fn make_raw_tx(
 //         &self,
@@ -144,6 +286,28 @@ impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
 //     }
 // }
 
+/// Registers the methods which prove state against the state root, only available on
+/// storages that can generate proofs.
+fn register_proof_methods<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::Error>
+where
+    C::Storage: NativeStorage,
+{
+    rpc.register_async_method("eth_getProof", |params, ethereum| async move {
+        info!("eth module: eth_getProof");
+        let mut params = params.sequence();
+        let address: Address = params.next()?;
+        let keys: Vec<B256> = params.next()?;
+        let block_number: Option<BlockNumberOrTag> = params.optional_next()?;
+
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        Evm::<C>::default().get_proof(address, keys, block_number, &mut working_set)
+    })?;
+
+    Ok(())
+}
+
 fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
     rpc: &mut RpcModule<Ethereum<C, Da>>,
     // Checks wether the running node is a sequencer or not, if it is not a sequencer it should also have methods like eth_sendRawTransaction here.
@@ -164,6 +328,66 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         Ok::<_, ErrorObjectOwned>(hash)
     })?;
 
+    rpc.register_async_method(
+        "citrea_getAddEthereumChainParameters",
+        |_, ethereum| async move {
+            info!("eth module: citrea_getAddEthereumChainParameters");
+            let Some(wallet_chain) = &ethereum.wallet_chain else {
+                return Ok::<_, ErrorObjectOwned>(None);
+            };
+
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+            let chain_id = Evm::<C>::default()
+                .get_chain_config(&mut working_set)
+                .chain_id;
+
+            Ok(Some(AddEthereumChainParameters::new(
+                chain_id,
+                wallet_chain,
+            )))
+        },
+    )?;
+
+    rpc.register_async_method("citrea_getIndexedBlocks", |params, ethereum| async move {
+        info!("eth module: citrea_getIndexedBlocks");
+        let page: Option<IndexerPage> = params.sequence().optional_next()?;
+
+        ethereum
+            .indexer()?
+            .blocks(page.unwrap_or_default())
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+    })?;
+
+    rpc.register_async_method(
+        "citrea_getAddressTransactions",
+        |params, ethereum| async move {
+            info!("eth module: citrea_getAddressTransactions");
+            let mut params = params.sequence();
+            let address: Address = params.next()?;
+            let page: Option<IndexerPage> = params.optional_next()?;
+
+            ethereum
+                .indexer()?
+                .address_transactions(address, page.unwrap_or_default())
+                .await
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+        },
+    )?;
+
+    rpc.register_async_method("citrea_getIndexedLogs", |params, ethereum| async move {
+        info!("eth module: citrea_getIndexedLogs");
+        let mut params = params.sequence();
+        let filter: IndexedLogsFilter = params.next()?;
+        let page: Option<IndexerPage> = params.optional_next()?;
+
+        ethereum
+            .indexer()?
+            .logs(filter, page.unwrap_or_default())
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+    })?;
+
     rpc.register_async_method("eth_gasPrice", |_, ethereum| async move {
         info!("eth module: eth_gasPrice");
         let price = {
@@ -208,13 +432,11 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         info!("eth module: eth_feeHistory");
         let mut params = params.sequence();
 
-        let block_count: String = params.next().unwrap();
-        let newest_block: BlockNumberOrTag = params.next().unwrap();
+        let block_count: serde_json::Value = params.next()?;
+        let newest_block: BlockNumberOrTag = params.next()?;
         let reward_percentiles: Option<Vec<f64>> = params.optional_next()?;
 
-        // convert block count to u64 from hex
-        let block_count = u64::from_str_radix(&block_count[2..], 16)
-            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+        let block_count = parse_block_count(&block_count)?;
 
         let fee_history = {
             let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
@@ -227,8 +449,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                     reward_percentiles,
                     &mut working_set,
                 )
-                .await
-                .unwrap()
+                .await?
         };
 
         Ok::<FeeHistory, ErrorObjectOwned>(fee_history)
@@ -472,10 +693,10 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
 
             let mut params = parmaeters.sequence();
 
-            let block_hash: B256 = params.next().unwrap();
+            let block_hash: B256 = params.next()?;
             let evm = Evm::<C>::default();
             let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
-            let opts: Option<GethDebugTracingOptions> = params.optional_next().unwrap();
+            let opts: Option<GethDebugTracingOptions> = params.optional_next()?;
 
             let block_number =
                 match evm.get_block_number_by_block_hash(block_hash, &mut working_set) {
@@ -500,37 +721,13 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                     .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR));
             }
 
-            if let Some(traces) = ethereum.trace_cache.lock().unwrap().get(&block_number) {
-                // If traces are found in cache convert them to specified opts and then return
-                let requested_opts = opts.clone().unwrap();
-                let traces = get_traces_with_reuqested_tracer_and_config(
-                    traces.clone(),
-                    requested_opts.tracer.unwrap(),
-                    requested_opts.tracer_config,
-                )?;
-                return Ok::<Vec<GethTrace>, ErrorObjectOwned>(traces);
-            }
-            let cache_options = create_trace_cache_opts();
-            let traces = evm
-                .trace_block_transactions_by_number(
-                    block_number,
-                    Some(cache_options),
-                    None,
-                    &mut working_set,
-                )
-                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
-            ethereum
-                .trace_cache
-                .lock()
-                .unwrap()
-                .insert(block_number, traces.clone());
-            // Convert the traces to the requested tracer and config
-            let requested_opts = opts.clone().unwrap();
-            let tracer_config = requested_opts.tracer_config;
+            // Convert the cached traces to the requested tracer and config
+            let traces = ethereum.get_cached_block_traces(&evm, block_number, &mut working_set)?;
+            let requested_opts = opts.unwrap();
             let traces = get_traces_with_reuqested_tracer_and_config(
-                traces.clone(),
+                traces,
                 requested_opts.tracer.unwrap(),
-                tracer_config,
+                requested_opts.tracer_config,
             )?;
 
             Ok::<Vec<GethTrace>, ErrorObjectOwned>(traces)
@@ -544,17 +741,24 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
 
             let mut params = parameters.sequence();
 
-            let block_number: BlockNumberOrTag = params.next().unwrap();
-            let opts: Option<GethDebugTracingOptions> = params.optional_next().unwrap();
+            let block_number: BlockNumberOrTag = params.next()?;
+            let opts: Option<GethDebugTracingOptions> = params.optional_next()?;
 
             let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
             let evm = Evm::<C>::default();
             let block_number = match block_number {
                 BlockNumberOrTag::Number(block_number) => block_number,
-                BlockNumberOrTag::Latest => convert_u256_to_u64(evm.block_number(&mut working_set)?),
+                BlockNumberOrTag::Earliest => 0,
+                // Transactions of the pending block are not known before it is produced, the
+                // head block is traced as in the other methods
+                BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+                    convert_u256_to_u64(evm.block_number(&mut working_set)?)
+                }
                 _ => {
                     return Err(to_jsonrpsee_error_object(
-                        EthApiError::Unsupported("Earliest, pending, safe and finalized are not supported for debug_traceBlockByNumber"),
+                        EthApiError::Unsupported(
+                            "Safe and finalized are not supported for debug_traceBlockByNumber",
+                        ),
                         ETH_RPC_ERROR,
                     ));
                 }
@@ -562,37 +766,24 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
 
             // If opts is None or if opts.tracer is None, then do not check cache or insert cache, just perform the operation
             if opts.as_ref().map_or(true, |o| o.tracer.is_none()) {
-                return evm.trace_block_transactions_by_number(block_number, opts.clone(), None, &mut working_set)
-                        .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR));
-            }
-
-            if let Some(traces) = ethereum.trace_cache.lock().unwrap().get(&block_number) {
-                // If traces are found in cache convert them to specified opts and then return
-                let requested_opts = opts.clone().unwrap();
-                let tracer_config = requested_opts.tracer_config;
-                let traces = get_traces_with_reuqested_tracer_and_config(traces.clone(), requested_opts.tracer.unwrap(), tracer_config)?;
-                return Ok::<Vec<GethTrace>, ErrorObjectOwned>(traces);
+                return evm
+                    .trace_block_transactions_by_number(
+                        block_number,
+                        opts.clone(),
+                        None,
+                        &mut working_set,
+                    )
+                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR));
             }
 
-            let cache_options = create_trace_cache_opts();
-            let traces = evm
-                .trace_block_transactions_by_number(
-                    block_number,
-                    Some(cache_options),
-                    None,
-                    &mut working_set,
-                )
-                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
-                ethereum
-                    .trace_cache
-                    .lock()
-                    .unwrap()
-                    .insert(block_number, traces.clone());
-
-            // Convert the traces to the requested tracer and config
-            let requested_opts = opts.clone().unwrap();
-            let tracer_config = requested_opts.tracer_config;
-            let traces = get_traces_with_reuqested_tracer_and_config(traces.clone(), requested_opts.tracer.unwrap(), tracer_config)?;
+            // Convert the cached traces to the requested tracer and config
+            let traces = ethereum.get_cached_block_traces(&evm, block_number, &mut working_set)?;
+            let requested_opts = opts.unwrap();
+            let traces = get_traces_with_reuqested_tracer_and_config(
+                traces,
+                requested_opts.tracer.unwrap(),
+                requested_opts.tracer_config,
+            )?;
 
             Ok::<Vec<GethTrace>, ErrorObjectOwned>(traces)
         },
@@ -630,7 +821,7 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                     .expect("Block number must be set for tx inside block"),
             );
 
-            let opts: Option<GethDebugTracingOptions> = params.optional_next().unwrap();
+            let opts: Option<GethDebugTracingOptions> = params.optional_next()?;
 
             // If opts is None or if opts.tracer is None, then do not check cache or insert cache, just perform the operation
             // also since this is not cached we need to stop at somewhere, so we add param stop_at
@@ -687,16 +878,104 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         },
     )?;
 
-    rpc.register_async_method("txpool_content", |_, _| async move {
-        info!("eth module: txpool_content");
+    rpc.register_async_method("trace_filter", |parameters, ethereum| async move {
+        info!("eth module: trace_filter");
+
+        let filter: TraceFilter = parameters.one()?;
 
-        // This is a simple mock for serde.
-        let json = json!({
-            "pending": {},
-            "queued": {}
-        });
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        let evm = Evm::<C>::default();
 
-        Ok::<_, ErrorObjectOwned>(json)
+        let latest = convert_u256_to_u64(evm.block_number(&mut working_set)?);
+        let from_block = filter.from_block.unwrap_or(latest);
+        let to_block = filter.to_block.unwrap_or(latest).min(latest);
+        if from_block > to_block {
+            return Ok::<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>(vec![]);
+        }
+        if to_block - from_block >= MAX_TRACE_FILTER_BLOCK_RANGE {
+            return Err(to_jsonrpsee_error_object(
+                format!(
+                    "block range is too large, the maximum is {} blocks",
+                    MAX_TRACE_FILTER_BLOCK_RANGE
+                ),
+                ETH_RPC_ERROR,
+            ));
+        }
+
+        let mut traces = vec![];
+        for block_number in from_block..=to_block {
+            let block_traces = ethereum
+                .get_block_parity_traces(&evm, block_number, &filter, &mut working_set)?
+                .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+            traces.extend(block_traces);
+        }
+
+        Ok::<Vec<LocalizedTransactionTrace>, ErrorObjectOwned>(paginate(traces, &filter))
+    })?;
+
+    rpc.register_async_method("trace_block", |parameters, ethereum| async move {
+        info!("eth module: trace_block");
+
+        let block_number: BlockNumberOrTag = parameters.one()?;
+
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        let evm = Evm::<C>::default();
+        let block_number = match block_number {
+            BlockNumberOrTag::Number(block_number) => block_number,
+            BlockNumberOrTag::Earliest => 0,
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => {
+                convert_u256_to_u64(evm.block_number(&mut working_set)?)
+            }
+            _ => {
+                return Err(to_jsonrpsee_error_object(
+                    EthApiError::Unsupported(
+                        "Safe and finalized are not supported for trace_block",
+                    ),
+                    ETH_RPC_ERROR,
+                ));
+            }
+        };
+
+        ethereum.get_block_parity_traces(
+            &evm,
+            block_number,
+            &TraceFilter::default(),
+            &mut working_set,
+        )
+    })?;
+
+    rpc.register_async_method("trace_transaction", |parameters, ethereum| async move {
+        info!("eth module: trace_transaction");
+
+        let tx_hash: B256 = parameters.one()?;
+
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        let evm = Evm::<C>::default();
+
+        let Some(tx) = evm.get_transaction_by_hash(tx_hash, &mut working_set)? else {
+            return Ok::<Option<Vec<LocalizedTransactionTrace>>, ErrorObjectOwned>(None);
+        };
+        let block_number = convert_u256_to_u64(
+            tx.block_number
+                .expect("Block number must be set for tx inside block"),
+        );
+        let tx_index = convert_u256_to_u64(
+            tx.transaction_index
+                .expect("Tx index must be set for tx inside block"),
+        );
+
+        let traces = ethereum
+            .get_block_parity_traces(
+                &evm,
+                block_number,
+                &TraceFilter::default(),
+                &mut working_set,
+            )?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|trace| trace.transaction_position == Some(tx_index))
+            .collect();
+        Ok(Some(traces))
     })?;
 
     rpc.register_async_method(
@@ -715,6 +994,90 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         },
     )?;
 
+    rpc.register_async_method(
+        "eth_getUncleByBlockNumberAndIndex",
+        |parameters, _| async move {
+            info!("eth module: eth_getUncleByBlockNumberAndIndex");
+
+            let mut params = parameters.sequence();
+
+            let _block_number: BlockNumberOrTag = params.next()?;
+            let _uncle_index_position: U256 = params.next()?;
+
+            // There are no uncles on Citrea
+            Ok::<Option<reth_rpc_types::Block>, ErrorObjectOwned>(None)
+        },
+    )?;
+
+    rpc.register_async_method(
+        "eth_getUncleCountByBlockHash",
+        |parameters, ethereum| async move {
+            info!("eth module: eth_getUncleCountByBlockHash");
+
+            let block_hash: B256 = parameters.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+            let block = evm.get_block_by_hash(block_hash, Some(false), &mut working_set)?;
+
+            // Unknown blocks have no uncle count, like in geth
+            Ok::<Option<U256>, ErrorObjectOwned>(block.map(|_| U256::ZERO))
+        },
+    )?;
+
+    rpc.register_async_method(
+        "eth_getUncleCountByBlockNumber",
+        |parameters, ethereum| async move {
+            info!("eth module: eth_getUncleCountByBlockNumber");
+
+            let block_number: BlockNumberOrTag = parameters.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+            let block =
+                evm.get_block_by_number(Some(block_number), Some(false), &mut working_set)?;
+
+            Ok::<Option<U256>, ErrorObjectOwned>(block.map(|_| U256::ZERO))
+        },
+    )?;
+
+    // Citrea blocks are produced by the sequencer, so there is no mining to report
+    rpc.register_async_method("eth_mining", |_, _| async move {
+        info!("eth module: eth_mining");
+
+        Ok::<bool, ErrorObjectOwned>(false)
+    })?;
+
+    rpc.register_async_method("eth_hashrate", |_, _| async move {
+        info!("eth module: eth_hashrate");
+
+        Ok::<U256, ErrorObjectOwned>(U256::ZERO)
+    })?;
+
+    rpc.register_async_method("eth_submitWork", |_, _| async move {
+        info!("eth module: eth_submitWork");
+
+        Ok::<bool, ErrorObjectOwned>(false)
+    })?;
+
+    rpc.register_async_method("eth_submitHashrate", |_, _| async move {
+        info!("eth module: eth_submitHashrate");
+
+        Ok::<bool, ErrorObjectOwned>(false)
+    })?;
+
+    rpc.register_async_method("net_listening", |_, _| async move {
+        info!("eth module: net_listening");
+
+        Ok::<bool, ErrorObjectOwned>(true)
+    })?;
+
+    rpc.register_async_method("net_peerCount", |_, _| async move {
+        info!("eth module: net_peerCount");
+
+        Ok::<U256, ErrorObjectOwned>(U256::ZERO)
+    })?;
+
     if !is_sequencer {
         rpc.register_async_method(
             "eth_sendRawTransaction",
@@ -734,6 +1097,34 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
             },
         )?;
 
+        rpc.register_async_method(
+            "eth_getTransactionCount",
+            |parameters, ethereum| async move {
+                let mut params = parameters.sequence();
+                let address: Address = params.next()?;
+                let block_number: Option<BlockNumberOrTag> = params.optional_next()?;
+                info!(
+                    "Full Node: eth_getTransactionCount({}, {:?})",
+                    address, block_number
+                );
+
+                // Only the sequencer knows the transactions waiting in its mempool
+                if block_number == Some(BlockNumberOrTag::Pending) {
+                    return ethereum
+                        .sequencer_client
+                        .as_ref()
+                        .unwrap()
+                        .get_transaction_count(address, block_number)
+                        .await
+                        .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR));
+                }
+
+                let evm = Evm::<C>::default();
+                let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+                evm.get_transaction_count(address, block_number, &mut working_set)
+            },
+        )?;
+
         rpc.register_async_method(
             "eth_getTransactionByHash",
             |parameters, ethereum| async move {
@@ -789,6 +1180,53 @@ fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
                 }
             },
         )?;
+
+        // The mempool is only known by the sequencer. Empty results are returned if it can't
+        // be reached, for explorers polling the txpool not to fail.
+        rpc.register_async_method("txpool_content", |_, ethereum| async move {
+            info!("Full Node: txpool_content");
+            let content = ethereum
+                .sequencer_client
+                .as_ref()
+                .unwrap()
+                .txpool_content()
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to get the txpool content of the sequencer: {}", e);
+                    TxpoolContent::default()
+                });
+            Ok::<TxpoolContent, ErrorObjectOwned>(content)
+        })?;
+
+        rpc.register_async_method("txpool_inspect", |_, ethereum| async move {
+            info!("Full Node: txpool_inspect");
+            let inspect = ethereum
+                .sequencer_client
+                .as_ref()
+                .unwrap()
+                .txpool_inspect()
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to inspect the txpool of the sequencer: {}", e);
+                    TxpoolInspect::default()
+                });
+            Ok::<TxpoolInspect, ErrorObjectOwned>(inspect)
+        })?;
+
+        rpc.register_async_method("txpool_status", |_, ethereum| async move {
+            info!("Full Node: txpool_status");
+            let status = ethereum
+                .sequencer_client
+                .as_ref()
+                .unwrap()
+                .txpool_status()
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to get the txpool status of the sequencer: {}", e);
+                    TxpoolStatus::default()
+                });
+            Ok::<TxpoolStatus, ErrorObjectOwned>(status)
+        })?;
     }
 
     Ok(())
@@ -862,19 +1300,17 @@ pub fn get_latest_git_tag() -> Result<String, ErrorObjectOwned> {
         .to_string())
 }
 
-fn apply_call_config(call_frame: CallFrame, call_config: CallConfig) -> CallFrame {
-    // let only_top_call = call_config.only_top_call.unwrap_or();
-    let mut new_call_frame = call_frame.clone();
+fn apply_call_config(mut call_frame: CallFrame, call_config: CallConfig) -> CallFrame {
     if let Some(true) = call_config.only_top_call {
-        new_call_frame.calls = vec![];
+        call_frame.calls = vec![];
     }
     if !call_config.with_log.unwrap_or(false) {
-        remove_logs_from_call_frame(&mut vec![new_call_frame.clone()]);
+        remove_logs_from_call_frame(std::slice::from_mut(&mut call_frame));
     }
-    new_call_frame
+    call_frame
 }
 
-fn remove_logs_from_call_frame(call_frame: &mut Vec<CallFrame>) {
+fn remove_logs_from_call_frame(call_frame: &mut [CallFrame]) {
     for frame in call_frame {
         frame.logs = vec![];
         remove_logs_from_call_frame(&mut frame.calls);
@@ -976,15 +1412,74 @@ fn convert_call_trace_into_4byte_map(
 
 fn create_trace_cache_opts() -> GethDebugTracingOptions {
     // Get the traces with call tracer onlytopcall false and withlog true and always cache this way
-    let mut call_config_map = serde_json::Map::new();
-    call_config_map.insert("only_top_call".to_string(), serde_json::Value::Bool(false));
-    call_config_map.insert("with_log".to_string(), serde_json::Value::Bool(true));
-    let call_config = serde_json::Value::Object(call_config_map);
-    GethDebugTracingOptions {
-        tracer: Some(GethDebugTracerType::BuiltInTracer(
+    GethDebugTracingOptions::default()
+        .with_tracer(GethDebugTracerType::BuiltInTracer(
             GethDebugBuiltInTracerType::CallTracer,
-        )),
-        tracer_config: GethDebugTracerConfig(call_config),
-        ..Default::default()
+        ))
+        .call_config(CallConfig {
+            only_top_call: Some(false),
+            with_log: Some(true),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_frame_with_logs() -> CallFrame {
+        serde_json::from_value(json!({
+            "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "gas": "0x10000",
+            "gasUsed": "0x5000",
+            "to": "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512",
+            "input": "0x35c152bd",
+            "value": "0x0",
+            "type": "CALL",
+            "logs": [{ "address": "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512", "topics": [], "data": "0x01" }],
+            "calls": [{
+                "from": "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512",
+                "gas": "0x8000",
+                "gasUsed": "0x1000",
+                "to": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                "input": "0x6d4ce63c",
+                "type": "STATICCALL",
+                "logs": [{ "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3", "topics": [], "data": "0x02" }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_trace_cache_records_logs_of_all_calls() {
+        let call_config = create_trace_cache_opts()
+            .tracer_config
+            .into_call_config()
+            .unwrap();
+        assert_eq!(call_config.only_top_call, Some(false));
+        assert_eq!(call_config.with_log, Some(true));
+    }
+
+    #[test]
+    fn test_apply_call_config() {
+        let frame = apply_call_config(
+            call_frame_with_logs(),
+            CallConfig {
+                only_top_call: None,
+                with_log: Some(false),
+            },
+        );
+        assert!(frame.logs.is_empty());
+        assert_eq!(frame.calls.len(), 1);
+        assert!(frame.calls[0].logs.is_empty());
+
+        let frame = apply_call_config(
+            call_frame_with_logs(),
+            CallConfig {
+                only_top_call: Some(true),
+                with_log: Some(true),
+            },
+        );
+        assert_eq!(frame.logs.len(), 1);
+        assert!(frame.calls.is_empty());
     }
 }
@@ -0,0 +1,167 @@
+use citrea_evm::{Evm, SignError};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use reth_primitives::{
+    AccessList, AccessListItem, Address, BlockNumberOrTag, Bytes, Transaction, TransactionKind,
+    TxEip1559, TxLegacy, U256,
+};
+use reth_rpc_types::TransactionRequest;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::services::da::DaService;
+use tracing::info;
+
+use crate::{Ethereum, ETH_RPC_ERROR};
+
+/// Registers `eth_sign` and `eth_signTransaction`, which sign with the dev accounts
+/// held by the node.
+pub(crate) fn register_signing_methods<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_async_method("eth_sign", |parameters, ethereum| async move {
+        info!("eth module: eth_sign");
+        let mut params = parameters.sequence();
+        let address: Address = params.next()?;
+        let message: Bytes = params.next()?;
+
+        ethereum
+            .eth_signer
+            .sign_message(address, &message)
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+    })?;
+
+    rpc.register_async_method("eth_signTransaction", |parameters, ethereum| async move {
+        info!("eth module: eth_signTransaction");
+        let request: TransactionRequest = parameters.one()?;
+        let from = request
+            .from
+            .ok_or_else(|| to_jsonrpsee_error_object("No from address", ETH_RPC_ERROR))?;
+
+        let transaction = fill_transaction(&ethereum, from, request).await?;
+        let signed = ethereum
+            .eth_signer
+            .sign_transaction(transaction, from)
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+
+        Ok::<Bytes, ErrorObjectOwned>(signed.envelope_encoded())
+    })?;
+
+    Ok(())
+}
+
+/// Builds the transaction of `request`, filling the missing fields the way wallets do.
+/// The transaction is a legacy one if a gas price is given, an EIP-1559 one otherwise.
+async fn fill_transaction<C: sov_modules_api::Context, Da: DaService>(
+    ethereum: &Ethereum<C, Da>,
+    from: Address,
+    request: TransactionRequest,
+) -> Result<Transaction, ErrorObjectOwned> {
+    if !ethereum.eth_signer.signers().contains(&from) {
+        return Err(to_jsonrpsee_error_object(
+            SignError::NoAccount,
+            ETH_RPC_ERROR,
+        ));
+    }
+
+    let evm = Evm::<C>::default();
+    let nonce = match request.nonce {
+        Some(nonce) => nonce.to::<u64>(),
+        // Only the sequencer knows the transactions waiting in its mempool
+        None => match &ethereum.sequencer_client {
+            Some(sequencer_client) => sequencer_client
+                .get_transaction_count(from, Some(BlockNumberOrTag::Pending))
+                .await
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?
+                .to::<u64>(),
+            None => {
+                let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+                evm.get_transaction_count(from, None, &mut working_set)?
+                    .to::<u64>()
+            }
+        },
+    };
+
+    let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+    let chain_id = evm
+        .chain_id(&mut working_set)?
+        .map(|id| id.to::<u64>())
+        .unwrap_or_default();
+    if request
+        .chain_id
+        .is_some_and(|id| id.to::<u64>() != chain_id)
+    {
+        return Err(to_jsonrpsee_error_object(
+            SignError::InvalidTransactionRequest,
+            ETH_RPC_ERROR,
+        ));
+    }
+
+    let gas_limit = match request.gas {
+        Some(gas) => gas.to::<u64>(),
+        None => evm
+            .eth_estimate_gas(request.clone(), None, None, &mut working_set)?
+            .to::<u64>(),
+    };
+    let to = match request.to {
+        Some(to) => TransactionKind::Call(to),
+        None => TransactionKind::Create,
+    };
+    let value = request.value.unwrap_or_default();
+    let input = request.input.into_input().unwrap_or_default();
+
+    if let Some(gas_price) = request.gas_price {
+        return Ok(Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: gas_price.to::<u128>(),
+            gas_limit,
+            to,
+            value,
+            input,
+        }));
+    }
+
+    let max_priority_fee_per_gas = match request.max_priority_fee_per_gas {
+        Some(fee) => fee,
+        None => ethereum
+            .gas_price_oracle
+            .suggest_tip_cap(&mut working_set)
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?,
+    };
+    let max_fee_per_gas = match request.max_fee_per_gas {
+        Some(fee) => fee,
+        None => {
+            let base_fee = evm
+                .get_block_by_number(None, None, &mut working_set)?
+                .and_then(|block| block.header.base_fee_per_gas)
+                .unwrap_or_default();
+            // Leaves room for the base fee to double before the transaction is included
+            base_fee * U256::from(2) + max_priority_fee_per_gas
+        }
+    };
+    let access_list = AccessList(
+        request
+            .access_list
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .map(|item| AccessListItem {
+                address: item.address,
+                storage_keys: item.storage_keys,
+            })
+            .collect(),
+    );
+
+    Ok(Transaction::Eip1559(TxEip1559 {
+        chain_id,
+        nonce,
+        gas_limit,
+        max_fee_per_gas: max_fee_per_gas.to::<u128>(),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.to::<u128>(),
+        to,
+        value,
+        access_list,
+        input,
+    }))
+}
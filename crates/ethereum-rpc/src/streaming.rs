@@ -0,0 +1,127 @@
+//! Streaming of the chain data a full node sees to NATS, so that data pipelines get it in
+//! real time instead of polling the RPC.
+//!
+//! Each message is a JSON object published on a subject under the configured prefix:
+//! - `<prefix>.blocks`: a [`StreamedBlock`], for every soft batch applied by the node
+//! - `<prefix>.receipts`: a receipt of the block, as returned by `eth_getTransactionReceipt`
+//! - `<prefix>.logs`: a log of the block, as returned by `eth_getLogs`
+//! - `<prefix>.commitments`: a [`sov_stf_runner::NewCommitmentEvent`], for every sequencer
+//!   commitment found on the DA layer
+//! - `<prefix>.proofs`: a [`sov_stf_runner::NewProofEvent`], for every proof found on the DA
+//!   layer
+//!
+//! Messages are published at most once, in the order the node sees the data. Data is
+//! skipped if the server can't be reached or the sink falls too far behind the node.
+
+use citrea_evm::Evm;
+use reth_primitives::{BlockId, BlockNumberOrTag};
+use reth_rpc_types::RichBlock;
+use serde::Serialize;
+use sov_modules_api::WorkingSet;
+use sov_stf_runner::{subscribe_chain_events, ChainEvent, NewBlockEvent, StreamingConfig};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+/// Block published on `<prefix>.blocks`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamedBlock {
+    /// The soft batch the block was built in
+    #[serde(flatten)]
+    pub soft_batch: NewBlockEvent,
+    /// The block, as returned by `eth_getBlockByNumber` with transaction hashes
+    pub block: Option<RichBlock>,
+}
+
+/// Spawns the task publishing the chain data to the NATS server of `config`.
+pub fn spawn_streaming_sink<C: sov_modules_api::Context>(
+    config: StreamingConfig,
+    storage: C::Storage,
+) {
+    info!(
+        "Streaming chain data to {} under {}",
+        config.nats_url, config.subject_prefix
+    );
+
+    // Subscribe before spawning, so that no event is missed while connecting
+    let mut events = subscribe_chain_events();
+    tokio::spawn(async move {
+        let client = match async_nats::connect(&config.nats_url).await {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Streaming: failed to connect to {}: {}", config.nats_url, e);
+                return;
+            }
+        };
+        let evm = Evm::<C>::default();
+        let subject = |topic: &str| format!("{}.{}", config.subject_prefix, topic);
+
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Streaming: fell behind, skipped {} chain events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            let messages = match event {
+                ChainEvent::NewBlock(soft_batch) => {
+                    let mut working_set = WorkingSet::<C>::new(storage.clone());
+                    block_messages(&evm, soft_batch, &mut working_set)
+                }
+                ChainEvent::NewCommitment(commitment) => {
+                    vec![("commitments", to_payload(&commitment))]
+                }
+                ChainEvent::NewProof(proof) => vec![("proofs", to_payload(&proof))],
+            };
+
+            for (topic, payload) in messages {
+                if let Err(e) = client.publish(subject(topic), payload.into()).await {
+                    warn!("Streaming: failed to publish on {}: {}", subject(topic), e);
+                }
+            }
+        }
+    });
+}
+
+fn to_payload<T: Serialize>(message: &T) -> Vec<u8> {
+    serde_json::to_vec(message).expect("Streamed messages are serializable")
+}
+
+/// Returns the block, receipts and logs messages of the EVM block of the soft batch.
+fn block_messages<C: sov_modules_api::Context>(
+    evm: &Evm<C>,
+    soft_batch: NewBlockEvent,
+    working_set: &mut WorkingSet<C>,
+) -> Vec<(&'static str, Vec<u8>)> {
+    // The EVM block of a soft batch has its L2 height as number
+    let height = soft_batch.l2_height;
+    let number = BlockNumberOrTag::Number(height);
+    let block = evm
+        .get_block_by_number(Some(number), Some(false), working_set)
+        .unwrap_or_else(|e| {
+            warn!("Streaming: failed to read block {}: {}", height, e);
+            None
+        });
+    let receipts = evm
+        .get_block_receipts(BlockId::Number(number), working_set)
+        .unwrap_or_else(|e| {
+            warn!(
+                "Streaming: failed to read receipts of block {}: {}",
+                height, e
+            );
+            None
+        })
+        .unwrap_or_default();
+
+    let mut messages = vec![("blocks", to_payload(&StreamedBlock { soft_batch, block }))];
+    for receipt in &receipts {
+        messages.push(("receipts", to_payload(receipt)));
+    }
+    for log in receipts.iter().flat_map(|receipt| &receipt.logs) {
+        messages.push(("logs", to_payload(log)));
+    }
+    messages
+}
@@ -0,0 +1,250 @@
+//! `eth_subscribe` and `eth_unsubscribe`, served to the clients connected over WebSocket.
+//!
+//! Subscriptions are fed by the chain events of the node, so that clients are notified of
+//! every soft confirmation the node applies or, for the sequencer, produces. Pending
+//! transactions are only served by the sequencer, from its mempool.
+
+use citrea_evm::{Evm, Filter, FilterBlockOption};
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use reth_primitives::{BlockNumberOrTag, Bytes, TransactionSigned, B256};
+use reth_rpc_types_compat::transaction::from_recovered;
+use serde::Deserialize;
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::services::da::DaService;
+use sov_stf_runner::{
+    subscribe_chain_events, subscribe_pending_transactions, ChainEvent, PendingTransaction,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::{Ethereum, ETH_RPC_ERROR};
+
+/// Kind of the notifications of a subscription, the first parameter of `eth_subscribe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum SubscriptionKind {
+    /// The header of every new block
+    NewHeads,
+    /// The logs of every new block matching the filter given as second parameter
+    Logs,
+    /// The hash of every transaction entering the mempool of the sequencer, or the full
+    /// transaction if the second parameter is `true`
+    NewPendingTransactions,
+}
+
+pub(crate) fn register_subscriptions<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_subscription(
+        "eth_subscribe",
+        "eth_subscription",
+        "eth_unsubscribe",
+        |parameters, pending, ethereum| async move {
+            let mut params = parameters.sequence();
+            let kind: SubscriptionKind = match params.next() {
+                Ok(kind) => kind,
+                Err(e) => {
+                    pending.reject(e).await;
+                    return Ok(());
+                }
+            };
+            info!("eth module: eth_subscribe({:?})", kind);
+
+            match kind {
+                SubscriptionKind::NewHeads => new_heads(pending, &ethereum).await,
+                SubscriptionKind::Logs => {
+                    // Only the address and topics of the filter are used
+                    let filter: Filter = match params.optional_next() {
+                        Ok(filter) => filter.unwrap_or_default(),
+                        Err(e) => {
+                            pending.reject(e).await;
+                            return Ok(());
+                        }
+                    };
+                    logs(pending, &ethereum, filter).await
+                }
+                SubscriptionKind::NewPendingTransactions => {
+                    if ethereum.sequencer_client.is_some() {
+                        pending
+                            .reject(to_jsonrpsee_error_object(
+                                "Pending transactions are only served by the sequencer",
+                                ETH_RPC_ERROR,
+                            ))
+                            .await;
+                        return Ok(());
+                    }
+                    let full: bool = match params.optional_next() {
+                        Ok(full) => full.unwrap_or(false),
+                        Err(e) => {
+                            pending.reject(e).await;
+                            return Ok(());
+                        }
+                    };
+                    new_pending_transactions(pending, full).await
+                }
+            }
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Notifies the subscriber of the header of every block the node applies, until it
+/// unsubscribes or disconnects.
+async fn new_heads<C: sov_modules_api::Context, Da: DaService>(
+    pending: PendingSubscriptionSink,
+    ethereum: &Ethereum<C, Da>,
+) -> jsonrpsee::core::SubscriptionResult {
+    notify_new_blocks(pending, ethereum, |evm, height, working_set| {
+        let block = evm.get_block_by_number(
+            Some(BlockNumberOrTag::Number(height)),
+            Some(false),
+            working_set,
+        )?;
+        let Some(block) = block else {
+            warn!("eth_subscribe: block {} is not in the state", height);
+            return Ok(vec![]);
+        };
+        Ok(vec![SubscriptionMessage::from_json(&block.inner.header)?])
+    })
+    .await
+}
+
+/// Notifies the subscriber of every log matching the address and topics of `filter` in the
+/// blocks the node applies, one log per notification, until it unsubscribes or disconnects.
+async fn logs<C: sov_modules_api::Context, Da: DaService>(
+    pending: PendingSubscriptionSink,
+    ethereum: &Ethereum<C, Da>,
+    filter: Filter,
+) -> jsonrpsee::core::SubscriptionResult {
+    notify_new_blocks(pending, ethereum, |evm, height, working_set| {
+        let block_filter = Filter {
+            block_option: FilterBlockOption::Range {
+                from_block: Some(BlockNumberOrTag::Number(height)),
+                to_block: Some(BlockNumberOrTag::Number(height)),
+            },
+            ..filter.clone()
+        };
+        evm.eth_get_logs(block_filter, working_set)?
+            .iter()
+            .map(|log| SubscriptionMessage::from_json(log).map_err(anyhow::Error::from))
+            .collect()
+    })
+    .await
+}
+
+/// Notifies the subscriber of the hash, or the full transaction if `full`, of every
+/// transaction which becomes executable in the mempool of the sequencer, until it
+/// unsubscribes or disconnects.
+async fn new_pending_transactions(
+    pending: PendingSubscriptionSink,
+    full: bool,
+) -> jsonrpsee::core::SubscriptionResult {
+    let mut transactions = subscribe_pending_transactions();
+    let sink = pending.accept().await?;
+
+    loop {
+        let transaction = tokio::select! {
+            _ = sink.closed() => return Ok(()),
+            transaction = transactions.recv() => transaction,
+        };
+        let transaction = match transaction {
+            Ok(transaction) => transaction,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    "eth_subscribe: fell behind, skipped {} pending transactions",
+                    skipped
+                );
+                continue;
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        };
+
+        let message = if full {
+            let Some(transaction) = recover_pending_transaction(transaction) else {
+                continue;
+            };
+            SubscriptionMessage::from_json(&transaction)?
+        } else {
+            SubscriptionMessage::from_json(&B256::from(transaction.hash))?
+        };
+        if sink.send(message).await.is_err() {
+            // The subscriber is gone
+            return Ok(());
+        }
+    }
+}
+
+/// Decodes the transaction for its RPC representation, `None` if it is malformed, which the
+/// mempool should have prevented.
+fn recover_pending_transaction(
+    transaction: PendingTransaction,
+) -> Option<reth_rpc_types::Transaction> {
+    let recovered = TransactionSigned::decode_enveloped(Bytes::from(transaction.raw))
+        .ok()
+        .and_then(|signed| signed.into_ecrecovered());
+    if recovered.is_none() {
+        warn!(
+            "eth_subscribe: failed to decode pending transaction {}",
+            B256::from(transaction.hash)
+        );
+    }
+    recovered.map(from_recovered)
+}
+
+/// Accepts the subscription and sends the messages built by `messages` for every block the
+/// node applies, until the subscriber unsubscribes or disconnects.
+async fn notify_new_blocks<C, Da, F>(
+    pending: PendingSubscriptionSink,
+    ethereum: &Ethereum<C, Da>,
+    mut messages: F,
+) -> jsonrpsee::core::SubscriptionResult
+where
+    C: sov_modules_api::Context,
+    Da: DaService,
+    F: FnMut(&Evm<C>, u64, &mut WorkingSet<C>) -> anyhow::Result<Vec<SubscriptionMessage>>,
+{
+    // Subscribe before accepting, so that no block is missed in between
+    let mut events = subscribe_chain_events();
+    let sink = pending.accept().await?;
+    let evm = Evm::<C>::default();
+
+    loop {
+        let event = tokio::select! {
+            _ = sink.closed() => return Ok(()),
+            event = events.recv() => event,
+        };
+        let new_block = match event {
+            Ok(ChainEvent::NewBlock(new_block)) => new_block,
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    "eth_subscribe: fell behind, skipped {} chain events",
+                    skipped
+                );
+                continue;
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        };
+
+        // The EVM block of a soft batch has its L2 height as number
+        let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+        let messages = match messages(&evm, new_block.l2_height, &mut working_set) {
+            Ok(messages) => messages,
+            Err(e) => {
+                warn!(
+                    "eth_subscribe: failed to read block {}: {}",
+                    new_block.l2_height, e
+                );
+                continue;
+            }
+        };
+        for message in messages {
+            if sink.send(message).await.is_err() {
+                // The subscriber is gone
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use reth_primitives::{Address, B256, U256};
+use reth_rpc_types::trace::filter::TraceFilter;
+use reth_rpc_types::trace::geth::CallFrame;
+use reth_rpc_types::trace::parity::{
+    Action, CallAction, CallOutput, CallType, CreateAction, CreateOutput,
+    LocalizedTransactionTrace, SelfdestructAction, TraceOutput, TransactionTrace,
+};
+
+/// Maximum number of blocks `trace_filter` re-executes in a single request
+pub(crate) const MAX_TRACE_FILTER_BLOCK_RANGE: u64 = 100;
+
+/// Position of a transaction in the chain, to localize its traces
+pub(crate) struct TxLocation {
+    pub(crate) block_hash: B256,
+    pub(crate) block_number: u64,
+    pub(crate) tx_hash: B256,
+    pub(crate) tx_index: u64,
+}
+
+/// Converts the call tracer frame of a transaction into Parity style traces, in depth first
+/// order, keeping only the ones matching the address filters of `filter`.
+pub(crate) fn filtered_parity_traces(
+    frame: &CallFrame,
+    location: &TxLocation,
+    filter: &TraceFilter,
+) -> Vec<LocalizedTransactionTrace> {
+    let mut traces = vec![];
+    collect_traces(frame, vec![], location, filter, &mut traces);
+    traces
+}
+
+fn collect_traces(
+    frame: &CallFrame,
+    trace_address: Vec<usize>,
+    location: &TxLocation,
+    filter: &TraceFilter,
+    traces: &mut Vec<LocalizedTransactionTrace>,
+) {
+    let trace = parity_trace(frame, trace_address.clone());
+    let (from, to) = match &trace.action {
+        Action::Call(call) => (call.from, Some(call.to)),
+        Action::Create(create) => (create.from, frame.to),
+        Action::Selfdestruct(selfdestruct) => {
+            (selfdestruct.address, Some(selfdestruct.refund_address))
+        }
+        Action::Reward(reward) => (reward.author, None),
+    };
+    if matches_addresses(filter, from, to) {
+        traces.push(LocalizedTransactionTrace {
+            trace,
+            block_hash: Some(location.block_hash),
+            block_number: Some(location.block_number),
+            transaction_hash: Some(location.tx_hash),
+            transaction_position: Some(location.tx_index),
+        });
+    }
+
+    for (index, call) in frame.calls.iter().enumerate() {
+        let mut call_address = trace_address.clone();
+        call_address.push(index);
+        collect_traces(call, call_address, location, filter, traces);
+    }
+}
+
+/// A trace matches if its sender is in `from_address` and its recipient is in `to_address`,
+/// an empty list matching any address.
+fn matches_addresses(filter: &TraceFilter, from: Address, to: Option<Address>) -> bool {
+    let from_matches = filter.from_address.is_empty() || filter.from_address.contains(&from);
+    let to_matches =
+        filter.to_address.is_empty() || to.map_or(false, |to| filter.to_address.contains(&to));
+    from_matches && to_matches
+}
+
+fn parity_trace(frame: &CallFrame, trace_address: Vec<usize>) -> TransactionTrace {
+    let value = frame.value.unwrap_or_default();
+    let (action, result) = match frame.typ.as_str() {
+        "CREATE" | "CREATE2" => (
+            Action::Create(CreateAction {
+                from: frame.from,
+                gas: frame.gas,
+                init: frame.input.clone(),
+                value,
+            }),
+            TraceOutput::Create(CreateOutput {
+                address: frame.to.unwrap_or_default(),
+                code: frame.output.clone().unwrap_or_default(),
+                gas_used: frame.gas_used,
+            }),
+        ),
+        "SELFDESTRUCT" => (
+            Action::Selfdestruct(SelfdestructAction {
+                address: frame.from,
+                balance: value,
+                refund_address: frame.to.unwrap_or_default(),
+            }),
+            TraceOutput::Call(CallOutput {
+                gas_used: U256::ZERO,
+                output: Default::default(),
+            }),
+        ),
+        typ => (
+            Action::Call(CallAction {
+                from: frame.from,
+                call_type: match typ {
+                    "DELEGATECALL" => CallType::DelegateCall,
+                    "STATICCALL" => CallType::StaticCall,
+                    "CALLCODE" => CallType::CallCode,
+                    _ => CallType::Call,
+                },
+                gas: frame.gas,
+                input: frame.input.clone(),
+                to: frame.to.unwrap_or_default(),
+                value,
+            }),
+            TraceOutput::Call(CallOutput {
+                gas_used: frame.gas_used,
+                output: frame.output.clone().unwrap_or_default(),
+            }),
+        ),
+    };
+
+    TransactionTrace {
+        // Failed calls have no result, like in Parity
+        result: frame.error.is_none().then_some(result),
+        error: frame.error.clone(),
+        action,
+        subtraces: frame.calls.len(),
+        trace_address,
+    }
+}
+
+/// Applies the `after` and `count` pagination of `filter` to the matching traces.
+pub(crate) fn paginate(
+    traces: Vec<LocalizedTransactionTrace>,
+    filter: &TraceFilter,
+) -> Vec<LocalizedTransactionTrace> {
+    let after = filter.after.unwrap_or_default() as usize;
+    let count = filter.count.map_or(usize::MAX, |count| count as usize);
+    traces.into_iter().skip(after).take(count).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(typ: &str, from: u8, to: u8, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            from: Address::with_last_byte(from),
+            to: Some(Address::with_last_byte(to)),
+            typ: typ.to_string(),
+            calls,
+            ..Default::default()
+        }
+    }
+
+    fn location() -> TxLocation {
+        TxLocation {
+            block_hash: B256::with_last_byte(1),
+            block_number: 1,
+            tx_hash: B256::with_last_byte(2),
+            tx_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_filtered_parity_traces() {
+        let tx = frame(
+            "CALL",
+            1,
+            2,
+            vec![
+                frame("STATICCALL", 2, 3, vec![]),
+                frame("CALL", 2, 4, vec![frame("DELEGATECALL", 4, 3, vec![])]),
+            ],
+        );
+
+        let traces = filtered_parity_traces(&tx, &location(), &TraceFilter::default());
+        let addresses: Vec<_> = traces
+            .iter()
+            .map(|t| t.trace.trace_address.clone())
+            .collect();
+        assert_eq!(addresses, vec![vec![], vec![0], vec![1], vec![1, 0]]);
+        assert_eq!(traces[0].trace.subtraces, 2);
+
+        let filter = TraceFilter {
+            to_address: vec![Address::with_last_byte(3)],
+            ..Default::default()
+        };
+        let traces = filtered_parity_traces(&tx, &location(), &filter);
+        let addresses: Vec<_> = traces
+            .iter()
+            .map(|t| t.trace.trace_address.clone())
+            .collect();
+        assert_eq!(addresses, vec![vec![0], vec![1, 0]]);
+
+        let filter = TraceFilter {
+            from_address: vec![Address::with_last_byte(2)],
+            to_address: vec![Address::with_last_byte(4)],
+            after: Some(0),
+            count: Some(1),
+            ..Default::default()
+        };
+        let traces = paginate(filtered_parity_traces(&tx, &location(), &filter), &filter);
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].trace.trace_address, vec![1]);
+    }
+}
@@ -0,0 +1,199 @@
+//! `debug_subscribeTrace`, streaming the trace of a transaction in chunks to the clients
+//! connected over WebSocket.
+//!
+//! Traces too large to be returned by `debug_traceTransaction` can be streamed, as they are
+//! never serialized into a single response.
+
+use citrea_evm::{EthApiError, Evm};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{RpcModule, SubscriptionMessage};
+use reth_primitives::{Bytes, B256};
+use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace, StructLog};
+use serde::Serialize;
+use sov_modules_api::WorkingSet;
+use sov_rollup_interface::services::da::DaService;
+use tracing::info;
+
+use crate::gas_price::gas_oracle::convert_u256_to_u64;
+use crate::Ethereum;
+
+/// Maximum number of struct logs sent in a notification
+const STRUCT_LOGS_PER_CHUNK: usize = 1000;
+
+/// A notification of `debug_subscribeTrace`
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum TraceChunk {
+    /// Struct logs of the default tracer, in execution order
+    #[serde(rename_all = "camelCase")]
+    StructLogs { struct_logs: Vec<StructLog> },
+    /// Result of the default tracer, sent after all its struct logs
+    #[serde(rename_all = "camelCase")]
+    Result {
+        gas: u64,
+        failed: bool,
+        return_value: Bytes,
+    },
+    /// Whole trace of the other tracers
+    Trace { trace: GethTrace },
+}
+
+pub(crate) fn register_trace_stream<C: sov_modules_api::Context, Da: DaService>(
+    rpc: &mut RpcModule<Ethereum<C, Da>>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_subscription(
+        "debug_subscribeTrace",
+        "debug_traceChunk",
+        "debug_unsubscribeTrace",
+        |parameters, pending, ethereum| async move {
+            let mut params = parameters.sequence();
+            let tx_hash: B256 = match params.next() {
+                Ok(tx_hash) => tx_hash,
+                Err(e) => {
+                    pending.reject(e).await;
+                    return Ok(());
+                }
+            };
+            let opts: Option<GethDebugTracingOptions> = match params.optional_next() {
+                Ok(opts) => opts,
+                Err(e) => {
+                    pending.reject(e).await;
+                    return Ok(());
+                }
+            };
+            info!("eth module: debug_subscribeTrace({})", tx_hash);
+
+            let trace = match trace_transaction(&ethereum, tx_hash, opts) {
+                Ok(trace) => trace,
+                Err(e) => {
+                    pending.reject(e).await;
+                    return Ok(());
+                }
+            };
+
+            let sink = pending.accept().await?;
+            for chunk in trace_chunks(trace) {
+                if sink
+                    .send(SubscriptionMessage::from_json(&chunk)?)
+                    .await
+                    .is_err()
+                {
+                    // The subscriber is gone
+                    return Ok(());
+                }
+            }
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Traces the transaction like `debug_traceTransaction`, without limiting the size of the
+/// trace.
+fn trace_transaction<C: sov_modules_api::Context, Da: DaService>(
+    ethereum: &Ethereum<C, Da>,
+    tx_hash: B256,
+    opts: Option<GethDebugTracingOptions>,
+) -> Result<GethTrace, ErrorObjectOwned> {
+    let evm = Evm::<C>::default();
+    let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+
+    let tx = evm
+        .get_transaction_by_hash(tx_hash, &mut working_set)?
+        .ok_or(EthApiError::TransactionNotFound)?;
+    let block_number = convert_u256_to_u64(
+        tx.block_number
+            .expect("Block number must be set for tx inside block"),
+    );
+    let tx_index = convert_u256_to_u64(
+        tx.transaction_index
+            .expect("Tx index must be set for tx inside block"),
+    ) as usize;
+
+    let mut traces = evm.trace_block_transactions_by_number_with_max_size(
+        block_number,
+        opts,
+        Some(tx_index),
+        0,
+        &mut working_set,
+    )?;
+    Ok(traces.swap_remove(tx_index))
+}
+
+/// Splits the struct logs of the default tracer into chunks, followed by its result. The
+/// traces of the other tracers are sent whole.
+fn trace_chunks(trace: GethTrace) -> Vec<TraceChunk> {
+    let GethTrace::Default(frame) = trace else {
+        return vec![TraceChunk::Trace { trace }];
+    };
+
+    let mut chunks = vec![];
+    let mut struct_logs = frame.struct_logs.into_iter().peekable();
+    while struct_logs.peek().is_some() {
+        chunks.push(TraceChunk::StructLogs {
+            struct_logs: struct_logs.by_ref().take(STRUCT_LOGS_PER_CHUNK).collect(),
+        });
+    }
+    chunks.push(TraceChunk::Result {
+        gas: frame.gas,
+        failed: frame.failed,
+        return_value: frame.return_value,
+    });
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_rpc_types::trace::geth::{DefaultFrame, NoopFrame};
+
+    use super::*;
+
+    fn struct_log(pc: u64) -> StructLog {
+        serde_json::from_value(serde_json::json!({
+            "pc": pc,
+            "op": "PUSH1",
+            "gas": 0,
+            "gasCost": 3,
+            "depth": 1,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_trace_chunks() {
+        let struct_logs: Vec<_> = (0..STRUCT_LOGS_PER_CHUNK as u64 + 1)
+            .map(struct_log)
+            .collect();
+        let frame = DefaultFrame {
+            failed: false,
+            gas: 21000,
+            return_value: Bytes::from_static(&[1]),
+            struct_logs: struct_logs.clone(),
+        };
+
+        let chunks = trace_chunks(GethTrace::Default(frame));
+        assert_eq!(
+            chunks,
+            vec![
+                TraceChunk::StructLogs {
+                    struct_logs: struct_logs[..STRUCT_LOGS_PER_CHUNK].to_vec()
+                },
+                TraceChunk::StructLogs {
+                    struct_logs: vec![struct_log(STRUCT_LOGS_PER_CHUNK as u64)]
+                },
+                TraceChunk::Result {
+                    gas: 21000,
+                    failed: false,
+                    return_value: Bytes::from_static(&[1]),
+                },
+            ]
+        );
+
+        let noop = GethTrace::NoopTracer(NoopFrame::default());
+        assert_eq!(
+            trace_chunks(noop.clone()),
+            vec![TraceChunk::Trace { trace: noop }]
+        );
+    }
+}
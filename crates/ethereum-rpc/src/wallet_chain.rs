@@ -0,0 +1,64 @@
+use reth_primitives::U64;
+use serde::Serialize;
+use sov_stf_runner::{NativeCurrencyConfig, WalletChainConfig};
+
+/// Parameters of `wallet_addEthereumChain` (EIP-3085), returned by
+/// `citrea_getAddEthereumChainParameters`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddEthereumChainParameters {
+    chain_id: U64,
+    chain_name: String,
+    native_currency: NativeCurrencyConfig,
+    rpc_urls: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    block_explorer_urls: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    icon_urls: Vec<String>,
+}
+
+impl AddEthereumChainParameters {
+    pub(crate) fn new(chain_id: u64, config: &WalletChainConfig) -> Self {
+        Self {
+            chain_id: U64::from(chain_id),
+            chain_name: config.chain_name.clone(),
+            native_currency: config.native_currency.clone(),
+            rpc_urls: config.rpc_urls.clone(),
+            block_explorer_urls: config.block_explorer_urls.clone(),
+            icon_urls: config.icon_urls.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_add_ethereum_chain_parameters() {
+        let config = WalletChainConfig {
+            chain_name: "Citrea Testnet".to_string(),
+            rpc_urls: vec!["https://rpc.testnet.citrea.xyz".to_string()],
+            block_explorer_urls: vec!["https://explorer.testnet.citrea.xyz".to_string()],
+            icon_urls: vec![],
+            native_currency: NativeCurrencyConfig::default(),
+        };
+
+        assert_eq!(
+            serde_json::to_value(AddEthereumChainParameters::new(5115, &config)).unwrap(),
+            json!({
+                "chainId": "0x13fb",
+                "chainName": "Citrea Testnet",
+                "nativeCurrency": {
+                    "name": "Citrea Bitcoin",
+                    "symbol": "cBTC",
+                    "decimals": 18
+                },
+                "rpcUrls": ["https://rpc.testnet.citrea.xyz"],
+                "blockExplorerUrls": ["https://explorer.testnet.citrea.xyz"]
+            })
+        );
+    }
+}
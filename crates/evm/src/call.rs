@@ -1,4 +1,5 @@
 use core::panic;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 use reth_primitives::TransactionSignedEcRecovered;
@@ -6,10 +7,13 @@ use revm::primitives::{CfgEnvWithHandlerCfg, EVMError, SpecId};
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
+use crate::evm::access_hints::{access_hints_enabled, AccessRecorder};
 use crate::evm::db::EvmDb;
 use crate::evm::executor::{self};
 use crate::evm::handler::CitreaHandlerExt;
+use crate::evm::internal_txs::{internal_tx_index_enabled, InternalTxRecorder};
 use crate::evm::primitive_types::{BlockEnv, Receipt, TransactionSignedAndRecovered};
+use crate::evm::supply::{SupplyRecorder, SupplyReference};
 use crate::evm::{EvmChainConfig, RlpEvmTransaction};
 use crate::handler::CitreaHandlerContext;
 use crate::{Evm, PendingTransaction};
@@ -35,37 +39,84 @@ impl<C: sov_modules_api::Context> Evm<C> {
         _context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
-        let evm_txs_recovered: Vec<TransactionSignedEcRecovered> = txs
-            .into_iter()
-            .filter_map(|tx| match tx.try_into() {
-                Ok(tx) => Some(tx),
-                Err(_) => None,
-            })
-            .collect();
-
         let block_env = self
             .block_env
             .get(working_set)
             .expect("Pending block must be set");
 
+        // A transaction is executed at most once, so transactions of the pending block and of
+        // the recent blocks are skipped, as are the repeated ones of this call
+        let mut call_tx_hashes = HashSet::with_capacity(txs.len());
+        let evm_txs_recovered: Vec<TransactionSignedEcRecovered> = txs
+            .into_iter()
+            .filter_map(|tx| tx.try_into().ok())
+            .filter(|tx: &TransactionSignedEcRecovered| {
+                let tx_hash = tx.hash();
+                let included = self.recent_tx_hashes.get(&tx_hash, working_set).is_some();
+                if included || !call_tx_hashes.insert(tx_hash) {
+                    tracing::debug!("evm: Skipping already included transaction {}", tx_hash);
+                    return false;
+                }
+                true
+            })
+            .collect();
+
         let cfg = self.cfg.get(working_set).expect("Evm config must be set");
+        let eip6780_selfdestruct = cfg.eip6780_selfdestruct;
+        let calldata_l1_fee = cfg.calldata_l1_fee;
         let cfg_env: CfgEnvWithHandlerCfg = get_cfg_env(&block_env, cfg, None);
 
         let l1_fee_rate = self
             .l1_fee_rate
             .get(working_set)
             .expect("L1 fee rate must be set");
-        let mut citrea_handler_ext = CitreaHandlerExt::new(l1_fee_rate);
+        let mut citrea_handler_ext =
+            CitreaHandlerExt::new(l1_fee_rate, eip6780_selfdestruct, calldata_l1_fee);
+
+        // The transactions of a soft confirmation can be applied in several calls, which
+        // share the block gas limit
+        let block_gas_used = self
+            .pending_transactions
+            .last(working_set)
+            .map_or(0u64, |tx| tx.receipt.receipt.cumulative_gas_used);
 
         let block_number = block_env.number;
         let evm_db: EvmDb<'_, C> = self.get_db(working_set);
-        let results = executor::execute_multiple_tx(
-            evm_db,
-            block_env,
-            &evm_txs_recovered,
-            cfg_env,
-            &mut citrea_handler_ext,
-        );
+        // The state read is only recorded by native nodes serving access hints
+        let evm_db =
+            AccessRecorder::new(evm_db, cfg!(feature = "native") && access_hints_enabled());
+        // The changes of the total supply are only recorded by native nodes
+        let mut evm_db = SupplyRecorder::new(evm_db, cfg!(feature = "native"));
+        // Internal transactions are only recorded by native nodes serving explorers
+        let mut internal_txs = HashMap::new();
+        let results = if cfg!(feature = "native") && internal_tx_index_enabled() {
+            let mut recorder = InternalTxRecorder::new(&mut citrea_handler_ext);
+            let results = executor::inspect_multiple_tx(
+                &mut evm_db,
+                block_env,
+                block_gas_used,
+                &evm_txs_recovered,
+                cfg_env,
+                &mut recorder,
+            );
+            internal_txs = recorder.into_recorded();
+            results
+        } else {
+            executor::execute_multiple_tx(
+                &mut evm_db,
+                block_env,
+                block_gas_used,
+                &evm_txs_recovered,
+                cfg_env,
+                &mut citrea_handler_ext,
+            )
+        };
+        let mut supply_deltas = evm_db.take_deltas().unwrap_or_default().into_iter();
+        if let Some(accessed) = evm_db.into_inner().into_accessed() {
+            self.record_access_hints(block_number, accessed, &mut working_set.accessory_state());
+        }
+
+        let mut supply_events = vec![];
 
         // Iterate each evm_txs_recovered and results pair
         // Create a PendingTransaction for each pair
@@ -115,6 +166,32 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
                     self.pending_transactions
                         .push(&pending_transaction, working_set);
+                    self.recent_tx_hashes
+                        .set(&tx_hash, &block_number, working_set);
+
+                    let mut accessory_state = working_set.accessory_state();
+                    for contract in &tx_info.created_contracts {
+                        self.contract_creations
+                            .set(contract, &tx_hash, &mut accessory_state);
+                    }
+
+                    // Every executed transaction is committed, in order
+                    if let Some(event) = supply_deltas
+                        .next()
+                        .and_then(|delta| delta.into_event(SupplyReference::L2Tx(tx_hash)))
+                    {
+                        supply_events.push(event);
+                    }
+
+                    if let Some(internal_txs) = internal_txs.remove(&tx_hash) {
+                        if !internal_txs.is_empty() {
+                            self.internal_transactions.set(
+                                &tx_hash,
+                                &internal_txs,
+                                &mut accessory_state,
+                            );
+                        }
+                    }
                 }
                 // Adopted from https://github.com/paradigmxyz/reth/blob/main/crates/payload/basic/src/lib.rs#L884
                 Err(err) => match err {
@@ -131,6 +208,11 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 },
             }
         }
+        self.record_supply_events(
+            block_number,
+            supply_events,
+            &mut working_set.accessory_state(),
+        );
         Ok(CallResponse::default())
     }
 }
@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reth_primitives::{Address, U256};
+use revm::primitives::{Account, AccountInfo, Bytecode, HashMap, B256};
+use revm::{Database, DatabaseCommit};
+use sov_modules_api::prelude::*;
+use sov_modules_api::AccessoryWorkingSet;
+use sov_state::storage::StorageKey;
+
+use super::DbAccount;
+use crate::Evm;
+
+static ACCESS_HINTS: AtomicBool = AtomicBool::new(false);
+
+/// Makes the node record the state keys read by the transactions of every block it
+/// executes, so that they can be served by `citrea_getAccessHints` to full nodes
+/// prefetching state. Meant to be called once at startup, by sequencers.
+pub fn enable_access_hints() {
+    ACCESS_HINTS.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn access_hints_enabled() -> bool {
+    ACCESS_HINTS.load(Ordering::Relaxed)
+}
+
+/// Accounts, storage slots, code and block hashes read by the EVM
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct AccessedState {
+    pub(crate) accounts: BTreeSet<Address>,
+    pub(crate) storage: BTreeSet<(Address, U256)>,
+    pub(crate) code: BTreeSet<B256>,
+    pub(crate) block_hashes: BTreeSet<U256>,
+}
+
+/// Database wrapper recording the state read through it, if enabled.
+pub(crate) struct AccessRecorder<DB> {
+    db: DB,
+    accessed: Option<AccessedState>,
+}
+
+impl<DB> AccessRecorder<DB> {
+    pub(crate) fn new(db: DB, enabled: bool) -> Self {
+        Self {
+            db,
+            accessed: enabled.then(AccessedState::default),
+        }
+    }
+
+    /// Returns the state read through the recorder, `None` if it was not enabled.
+    pub(crate) fn into_accessed(self) -> Option<AccessedState> {
+        self.accessed
+    }
+}
+
+impl<DB: Database> Database for AccessRecorder<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(accessed) = &mut self.accessed {
+            accessed.accounts.insert(address);
+        }
+        self.db.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(accessed) = &mut self.accessed {
+            accessed.code.insert(code_hash);
+        }
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(accessed) = &mut self.accessed {
+            accessed.accounts.insert(address);
+            accessed.storage.insert((address, index));
+        }
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        if let Some(accessed) = &mut self.accessed {
+            accessed.block_hashes.insert(number);
+        }
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for AccessRecorder<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes);
+    }
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Adds the state keys of `accessed` to the access hints of the block. The transactions
+    /// of a block can be executed in several calls, whose hints are merged.
+    pub(crate) fn record_access_hints(
+        &self,
+        block_number: u64,
+        accessed: AccessedState,
+        accessory_state: &mut AccessoryWorkingSet<C>,
+    ) {
+        let mut keys: BTreeSet<reth_primitives::Bytes> = self
+            .access_hints
+            .get(&block_number, accessory_state)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let mut insert = |key: StorageKey| {
+            keys.insert(reth_primitives::Bytes::copy_from_slice(key.as_ref()));
+        };
+        for address in &accessed.accounts {
+            insert(StorageKey::new(
+                self.accounts.prefix(),
+                address,
+                self.accounts.codec(),
+            ));
+        }
+        for (address, slot) in &accessed.storage {
+            let account = DbAccount::new(self.accounts.prefix(), *address);
+            insert(StorageKey::new(
+                account.storage.prefix(),
+                slot,
+                account.storage.codec(),
+            ));
+        }
+        for code_hash in &accessed.code {
+            insert(StorageKey::new(
+                self.code.prefix(),
+                code_hash,
+                self.code.codec(),
+            ));
+        }
+        for number in &accessed.block_hashes {
+            insert(StorageKey::new(
+                self.latest_block_hashes.prefix(),
+                number,
+                self.latest_block_hashes.codec(),
+            ));
+        }
+
+        let keys: Vec<_> = keys.into_iter().collect();
+        self.access_hints.set(&block_number, &keys, accessory_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::InMemoryDB;
+
+    use super::*;
+
+    #[test]
+    fn test_access_recorder_records_reads_only_if_enabled() {
+        let address = Address::with_last_byte(1);
+
+        let mut recorder = AccessRecorder::new(InMemoryDB::default(), false);
+        recorder.basic(address).unwrap();
+        recorder.storage(address, U256::from(1)).unwrap();
+        assert_eq!(recorder.into_accessed(), None);
+
+        let mut recorder = AccessRecorder::new(InMemoryDB::default(), true);
+        recorder.storage(address, U256::from(1)).unwrap();
+        recorder.storage(address, U256::from(1)).unwrap();
+        recorder.block_hash(U256::from(7)).unwrap();
+        let accessed = recorder.into_accessed().unwrap();
+        assert_eq!(accessed.accounts, BTreeSet::from([address]));
+        assert_eq!(accessed.storage, BTreeSet::from([(address, U256::from(1))]));
+        assert_eq!(accessed.block_hashes, BTreeSet::from([U256::from(7)]));
+        assert!(accessed.code.is_empty());
+    }
+}
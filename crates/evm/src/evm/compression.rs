@@ -0,0 +1,122 @@
+/// Scaled intercept of the linear regression from FastLZ to Brotli compressed sizes
+const COMPRESSED_SIZE_INTERCEPT: i64 = -42_585_600;
+/// Scaled FastLZ coefficient of the linear regression from FastLZ to Brotli compressed sizes
+const COMPRESSED_SIZE_FASTLZ_COEF: i64 = 836_500;
+/// Scaled min. size charged for a transaction, the envelope of a transaction can't be compressed away
+const MIN_TRANSACTION_SIZE: i64 = 100_000_000;
+/// Scale of the constants above
+const COMPRESSED_SIZE_SCALE: i64 = 1_000_000;
+
+/// Estimates the size in bytes of `data` once compressed on the DA layer,
+/// used to charge the L1 fee of calldata.
+///
+/// The FastLZ compressed length is cheap to compute and is mapped to the size Brotli would
+/// compress `data` to with a linear regression, the same way as the Fjord upgrade of Optimism.
+pub fn estimated_compressed_size(data: &[u8]) -> u64 {
+    let estimate =
+        COMPRESSED_SIZE_INTERCEPT + COMPRESSED_SIZE_FASTLZ_COEF * flz_compress_len(data) as i64;
+    (estimate.max(MIN_TRANSACTION_SIZE) / COMPRESSED_SIZE_SCALE) as u64
+}
+
+/// Returns the length of `input` compressed with FastLZ level 1, without compressing it.
+/// Port of `FlzCompressLen` of the Optimism node, itself derived from solady's `LibZip`.
+pub(crate) fn flz_compress_len(input: &[u8]) -> u32 {
+    let mut len = 0u32;
+    let mut hash_table = [0u32; 8192];
+
+    let u24 = |i: u32| -> u32 {
+        let i = i as usize;
+        input[i] as u32 | (input[i + 1] as u32) << 8 | (input[i + 2] as u32) << 16
+    };
+    let hash = |v: u32| -> usize { ((2654435769u32.wrapping_mul(v) >> 19) & 0x1fff) as usize };
+    // Length of the match between `p` and `q`, one past the first mismatch
+    let match_len = |p: u32, q: u32, end: u32| -> u32 {
+        let mut end = end - q;
+        let mut l = 0;
+        while l < end {
+            if input[(p + l) as usize] != input[(q + l) as usize] {
+                end = 0;
+            }
+            l += 1;
+        }
+        l
+    };
+    let literals = |len: &mut u32, r: u32| {
+        *len += 0x21 * (r / 0x20);
+        let r = r % 0x20;
+        if r != 0 {
+            *len += r + 1;
+        }
+    };
+    let copy = |len: &mut u32, l: u32| {
+        let l = l - 1;
+        *len += 3 * (l / 262);
+        *len += if l % 262 >= 6 { 3 } else { 2 };
+    };
+
+    let mut anchor = 0u32;
+    let ip_limit = (input.len() as u32).saturating_sub(13);
+    let mut ip = anchor + 2;
+    while ip < ip_limit {
+        let mut r;
+        loop {
+            let seq = u24(ip);
+            let h = hash(seq);
+            r = hash_table[h];
+            hash_table[h] = ip;
+            let distance = ip - r;
+            if ip >= ip_limit {
+                break;
+            }
+            ip += 1;
+            if distance <= 0x1fff && seq == u24(r) {
+                break;
+            }
+        }
+        if ip >= ip_limit {
+            break;
+        }
+        ip -= 1;
+        if ip > anchor {
+            literals(&mut len, ip - anchor);
+        }
+        let l = match_len(r + 3, ip + 3, ip_limit + 9);
+        copy(&mut len, l);
+
+        ip += l;
+        for _ in 0..2 {
+            hash_table[hash(u24(ip))] = ip;
+            ip += 1;
+        }
+        anchor = ip;
+    }
+    literals(&mut len, input.len() as u32 - anchor);
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flz_compress_len() {
+        assert_eq!(flz_compress_len(&[]), 0);
+        // Too short to search for matches
+        assert_eq!(flz_compress_len(&[1; 5]), 6);
+        assert_eq!(flz_compress_len(&[0; 1000]), 21);
+
+        let repeated: Vec<u8> = (0..=255u8).cycle().take(1024).collect();
+        assert_eq!(flz_compress_len(&repeated), 279);
+    }
+
+    #[test]
+    fn test_estimated_compressed_size() {
+        // Compressible calldata only pays for the min. transaction size
+        assert_eq!(estimated_compressed_size(&[]), 100);
+        assert_eq!(estimated_compressed_size(&[0; 1000]), 100);
+
+        let repeated: Vec<u8> = (0..=255u8).cycle().take(1024).collect();
+        // (836_500 * 279 - 42_585_600) / 1_000_000
+        assert_eq!(estimated_compressed_size(&repeated), 190);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use reth_primitives::B256;
+use revm::interpreter::{InstructionResult, Interpreter};
+use revm::{Database, EvmContext, Inspector};
+
+use super::handler::{CitreaHandlerContext, TxInfo};
+
+/// Instructions executed between two reads of the clock
+const INSTRUCTIONS_PER_CHECK: u32 = 1024;
+
+/// Inspector halting the transactions which execute for longer than `budget`, delegating
+/// the Citrea handler context to `ext`. A halted transaction runs out of gas.
+///
+/// Execution time is not deterministic, so the budget is only meant for simulations of the
+/// block builder, never for the execution of blocks.
+pub(crate) struct ExecutionDeadline<EXT> {
+    ext: EXT,
+    budget: Duration,
+    current_tx_hash: Option<B256>,
+    deadline: Option<Instant>,
+    expired: bool,
+    steps: u32,
+    timed_out: HashSet<B256>,
+}
+
+impl<EXT: CitreaHandlerContext> ExecutionDeadline<EXT> {
+    pub(crate) fn new(ext: EXT, budget: Duration) -> Self {
+        Self {
+            ext,
+            budget,
+            current_tx_hash: None,
+            deadline: None,
+            expired: false,
+            steps: 0,
+            timed_out: HashSet::new(),
+        }
+    }
+
+    /// Hashes of the transactions halted for exceeding the budget.
+    pub(crate) fn into_timed_out(self) -> HashSet<B256> {
+        self.timed_out
+    }
+}
+
+impl<EXT: CitreaHandlerContext> CitreaHandlerContext for ExecutionDeadline<EXT> {
+    fn l1_fee_rate(&self) -> u64 {
+        self.ext.l1_fee_rate()
+    }
+    fn eip6780_selfdestruct(&self) -> bool {
+        self.ext.eip6780_selfdestruct()
+    }
+    fn calldata_l1_fee(&self) -> bool {
+        self.ext.calldata_l1_fee()
+    }
+    fn set_current_tx_hash(&mut self, hash: B256) {
+        self.current_tx_hash.replace(hash);
+        self.deadline = Some(Instant::now() + self.budget);
+        self.expired = false;
+        self.steps = 0;
+        self.ext.set_current_tx_hash(hash);
+    }
+    fn set_tx_info(&mut self, info: TxInfo) {
+        self.ext.set_tx_info(info)
+    }
+    fn get_tx_info(&self, tx_hash: B256) -> Option<TxInfo> {
+        self.ext.get_tx_info(tx_hash)
+    }
+}
+
+impl<EXT: CitreaHandlerContext, DB: Database> Inspector<DB> for ExecutionDeadline<EXT> {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.expired {
+            // The clock is read on the first instruction and every `INSTRUCTIONS_PER_CHECK`
+            // after it
+            let check = self.steps % INSTRUCTIONS_PER_CHECK == 0;
+            self.steps = self.steps.wrapping_add(1);
+            if !check {
+                return;
+            }
+            match self.deadline {
+                Some(deadline) if Instant::now() >= deadline => self.expired = true,
+                _ => return,
+            }
+            if let Some(hash) = self.current_tx_hash {
+                self.timed_out.insert(hash);
+            }
+        }
+        // Halts every frame of the transaction, down to the outermost one
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+}
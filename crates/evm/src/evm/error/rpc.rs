@@ -88,6 +88,9 @@ pub enum EthApiError {
     /// When the tracer config does not match the tracer
     #[error("invalid tracer config")]
     InvalidTracerConfig,
+    /// When the serialized traces of a request are larger than the configured maximum size
+    #[error("trace result exceeds the maximum size of {0} bytes, stream it over WebSocket with debug_subscribeTrace")]
+    TraceResultTooLarge(usize),
     /// When the percentile array is invalid
     #[error("invalid reward percentiles")]
     InvalidRewardPercentiles,
@@ -164,6 +167,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
             EthApiError::InvalidRewardPercentiles => internal_rpc_err(error.to_string()),
+            err @ EthApiError::TraceResultTooLarge(_) => internal_rpc_err(err.to_string()),
             err @ EthApiError::ExecutionTimedOut(_) => {
                 rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
             }
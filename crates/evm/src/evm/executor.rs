@@ -1,11 +1,18 @@
 use std::convert::Infallible;
 
 use reth_primitives::TransactionSignedEcRecovered;
-use revm::primitives::{CfgEnvWithHandlerCfg, EVMError, Env, ExecutionResult, InvalidTransaction};
-use revm::{self, Context, Database, DatabaseCommit, EvmContext};
+use revm::handler::register::HandleRegisters;
+use revm::primitives::{
+    CfgEnvWithHandlerCfg, EVMError, Env, ExecutionResult, InvalidTransaction, ResultAndState,
+    TxEnv, B256,
+};
+use revm::{
+    self, inspector_handle_register, Context, Database, DatabaseCommit, EvmContext, GetInspector,
+    Inspector,
+};
 
 use super::conversions::create_tx_env;
-use super::handler::{citrea_handler, CitreaHandlerContext};
+use super::handler::{citrea_handler, CitreaHandlerContext, CitreaHandlerExt, TxInfo};
 use super::primitive_types::BlockEnv;
 
 struct CitreaEvm<'a, EXT, DB: Database> {
@@ -27,6 +34,25 @@ where
         Self { evm }
     }
 
+    /// Creates a new Citrea EVM which also runs `ext` as an inspector.
+    fn new_with_inspector(
+        db: DB,
+        block_env: BlockEnv,
+        config_env: CfgEnvWithHandlerCfg,
+        ext: EXT,
+    ) -> Self
+    where
+        EXT: GetInspector<DB>,
+    {
+        let evm_env = Env::boxed(config_env.cfg_env, block_env.into(), Default::default());
+        let evm_context = EvmContext::new_with_env(db, evm_env);
+        let context = Context::new(evm_context, ext);
+        let mut handler = citrea_handler(config_env.handler_cfg);
+        handler.append_handler_register(HandleRegisters::Plain(inspector_handle_register));
+        let evm = revm::Evm::new(context, handler);
+        Self { evm }
+    }
+
     /// Sets all required parameters and executes a transaction.
     fn transact_commit(
         &mut self,
@@ -59,6 +85,7 @@ pub(crate) fn execute_multiple_tx<
 >(
     db: DB,
     block_env: BlockEnv,
+    block_gas_used: u64,
     txs: &[TransactionSignedEcRecovered],
     config_env: CfgEnvWithHandlerCfg,
     ext: &mut EXT,
@@ -68,13 +95,70 @@ pub(crate) fn execute_multiple_tx<
     }
 
     let block_gas_limit = block_env.gas_limit;
-    let mut cumulative_gas_used = 0u64;
-
     let mut evm = CitreaEvm::new(db, block_env, config_env, ext);
+    transact_multiple(&mut evm, block_gas_limit, block_gas_used, txs)
+}
+
+/// Executes `tx_env` without committing it, returning the result along with
+/// the info collected by the Citrea handler. No L1 fee is charged.
+pub(crate) fn simulate_tx<DB: Database<Error = Infallible> + DatabaseCommit>(
+    db: DB,
+    block_env: BlockEnv,
+    tx_env: TxEnv,
+    config_env: CfgEnvWithHandlerCfg,
+    eip6780_selfdestruct: bool,
+) -> Result<(ResultAndState, TxInfo), EVMError<Infallible>> {
+    let mut ext = CitreaHandlerExt::new(0, eip6780_selfdestruct, false);
+    let result = {
+        let mut evm = CitreaEvm::new(db, block_env, config_env, &mut ext);
+        evm.evm.context.external.set_current_tx_hash(B256::ZERO);
+        *evm.evm.tx_mut() = tx_env;
+        evm.evm.transact()?
+    };
+    let tx_info = ext
+        .get_tx_info(B256::ZERO)
+        .expect("Tx info is set by the Citrea handler");
+    Ok((result, tx_info))
+}
+
+/// Same as [`execute_multiple_tx`], running `ext` as an inspector of every transaction.
+pub(crate) fn inspect_multiple_tx<
+    DB: Database<Error = Infallible> + DatabaseCommit,
+    EXT: CitreaHandlerContext + Inspector<DB>,
+>(
+    db: DB,
+    block_env: BlockEnv,
+    block_gas_used: u64,
+    txs: &[TransactionSignedEcRecovered],
+    config_env: CfgEnvWithHandlerCfg,
+    ext: &mut EXT,
+) -> Vec<Result<ExecutionResult, EVMError<Infallible>>> {
+    if txs.is_empty() {
+        return vec![];
+    }
+
+    let block_gas_limit = block_env.gas_limit;
+    let mut evm = CitreaEvm::new_with_inspector(db, block_env, config_env, ext);
+    transact_multiple(&mut evm, block_gas_limit, block_gas_used, txs)
+}
+
+/// Executes `txs` after transactions of the same block which used `block_gas_used` gas,
+/// rejecting those which don't fit in the rest of the block gas limit.
+fn transact_multiple<EXT, DB>(
+    evm: &mut CitreaEvm<'_, EXT, DB>,
+    block_gas_limit: u64,
+    block_gas_used: u64,
+    txs: &[TransactionSignedEcRecovered],
+) -> Vec<Result<ExecutionResult, EVMError<Infallible>>>
+where
+    DB: Database<Error = Infallible> + DatabaseCommit,
+    EXT: CitreaHandlerContext,
+{
+    let mut cumulative_gas_used = block_gas_used;
 
     let mut tx_results = Vec::with_capacity(txs.len());
     for tx in txs {
-        let block_available_gas = block_gas_limit - cumulative_gas_used;
+        let block_available_gas = block_gas_limit.saturating_sub(cumulative_gas_used);
         let result = if tx.transaction.gas_limit() > block_available_gas {
             Err(EVMError::Transaction(
                 InvalidTransaction::CallerGasLimitMoreThanBlock,
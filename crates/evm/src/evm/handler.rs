@@ -3,15 +3,20 @@ use std::mem::size_of;
 use std::sync::Arc;
 
 use revm::handler::register::{EvmHandler, HandleRegisters};
-use revm::interpreter::{Gas, InstructionResult};
+use revm::interpreter::opcode::{self, InstructionTables};
+use revm::interpreter::{instructions, Gas, InstructionResult, Interpreter};
 use revm::primitives::{
     spec_to_generic, Address, EVMError, HandlerCfg, ResultAndState, Spec, SpecId, B256, U256,
 };
-use revm::{Context, Database, FrameResult, InnerEvmContext, JournalEntry};
+use revm::{Context, Database, Evm, FrameResult, InnerEvmContext, JournalEntry};
 
-#[derive(Copy, Clone)]
+use super::compression::estimated_compressed_size;
+
+#[derive(Clone)]
 pub struct TxInfo {
     pub diff_size: u64,
+    /// Contracts created by the tx, including nested creations
+    pub created_contracts: Vec<Address>,
 }
 
 pub(crate) trait CitreaHandlerContext {
@@ -23,6 +28,10 @@ pub(crate) trait CitreaHandlerContext {
     fn set_tx_info(&mut self, info: TxInfo);
     /// Get tx info for the given tx by its hash.
     fn get_tx_info(&self, tx_hash: B256) -> Option<TxInfo>;
+    /// Whether `SELFDESTRUCT` follows EIP-6780.
+    fn eip6780_selfdestruct(&self) -> bool;
+    /// Whether the L1 fee charges the compressed size of the calldata.
+    fn calldata_l1_fee(&self) -> bool;
 }
 
 // Blanked impl for &mut T: CitreaExternal
@@ -39,19 +48,29 @@ impl<T: CitreaHandlerContext> CitreaHandlerContext for &mut T {
     fn get_tx_info(&self, tx_hash: B256) -> Option<TxInfo> {
         (**self).get_tx_info(tx_hash)
     }
+    fn eip6780_selfdestruct(&self) -> bool {
+        (**self).eip6780_selfdestruct()
+    }
+    fn calldata_l1_fee(&self) -> bool {
+        (**self).calldata_l1_fee()
+    }
 }
 
 #[derive(Default)]
 pub(crate) struct CitreaHandlerExt {
     l1_fee_rate: u64,
+    eip6780_selfdestruct: bool,
+    calldata_l1_fee: bool,
     current_tx_hash: Option<B256>,
     tx_infos: HashMap<B256, TxInfo>,
 }
 
 impl CitreaHandlerExt {
-    pub(crate) fn new(l1_fee_rate: u64) -> Self {
+    pub(crate) fn new(l1_fee_rate: u64, eip6780_selfdestruct: bool, calldata_l1_fee: bool) -> Self {
         Self {
             l1_fee_rate,
+            eip6780_selfdestruct,
+            calldata_l1_fee,
             ..Default::default()
         }
     }
@@ -73,7 +92,13 @@ impl CitreaHandlerContext for CitreaHandlerExt {
         }
     }
     fn get_tx_info(&self, tx_hash: B256) -> Option<TxInfo> {
-        self.tx_infos.get(&tx_hash).copied()
+        self.tx_infos.get(&tx_hash).cloned()
+    }
+    fn eip6780_selfdestruct(&self) -> bool {
+        self.eip6780_selfdestruct
+    }
+    fn calldata_l1_fee(&self) -> bool {
+        self.calldata_l1_fee
     }
 }
 
@@ -97,9 +122,37 @@ where
         post_execution.reward_beneficiary =
             Arc::new(CitreaHandler::<SPEC, EXT, DB>::reward_beneficiary);
         post_execution.output = Arc::new(CitreaHandler::<SPEC, EXT, DB>::post_execution_output);
+
+        match handler.instruction_table.as_mut() {
+            Some(InstructionTables::Plain(table)) => {
+                table[opcode::SELFDESTRUCT as usize] = selfdestruct::<SPEC, EXT, DB>;
+            }
+            Some(InstructionTables::Boxed(table)) => {
+                table[opcode::SELFDESTRUCT as usize] = Box::new(selfdestruct::<SPEC, EXT, DB>);
+            }
+            None => {}
+        }
     })
 }
 
+/// `SELFDESTRUCT` instruction following EIP-6780 if the chain config enables it, whatever
+/// the spec is.
+fn selfdestruct<SPEC: Spec, EXT: CitreaHandlerContext, DB: Database>(
+    interpreter: &mut Interpreter,
+    evm: &mut Evm<'_, EXT, DB>,
+) {
+    if !evm.context.external.eip6780_selfdestruct() {
+        instructions::host::selfdestruct::<Evm<'_, EXT, DB>, SPEC>(interpreter, evm);
+        return;
+    }
+
+    // The journal applies EIP-6780 from Cancun on, the gas and refunds stay the ones of `SPEC`
+    let spec_id = evm.context.evm.inner.journaled_state.spec;
+    evm.context.evm.inner.journaled_state.spec = SpecId::CANCUN;
+    instructions::host::selfdestruct::<Evm<'_, EXT, DB>, SPEC>(interpreter, evm);
+    evm.context.evm.inner.journaled_state.spec = spec_id;
+}
+
 struct CitreaHandler<SPEC, EXT, DB> {
     _phantom: std::marker::PhantomData<(SPEC, EXT, DB)>,
 }
@@ -136,9 +189,19 @@ impl<SPEC: Spec, EXT: CitreaHandlerContext, DB: Database> CitreaHandler<SPEC, EX
         result: FrameResult,
     ) -> Result<ResultAndState, EVMError<<DB as Database>::Error>> {
         let diff_size = calc_diff_size(context).map_err(EVMError::Database)? as u64;
+        // Calldata is posted compressed, so if charged, it is by its estimated compressed size
+        let calldata_size = if context.external.calldata_l1_fee() {
+            estimated_compressed_size(&context.evm.env.tx.data)
+        } else {
+            0
+        };
         let l1_fee_rate = U256::from(context.external.l1_fee_rate());
-        let l1_fee = U256::from(diff_size) * l1_fee_rate;
-        context.external.set_tx_info(TxInfo { diff_size });
+        let l1_fee = U256::from(diff_size + calldata_size) * l1_fee_rate;
+        let created_contracts = created_contracts(context);
+        context.external.set_tx_info(TxInfo {
+            diff_size,
+            created_contracts,
+        });
         if result.interpreter_result().is_ok() {
             // Deduct L1 fee only if tx is successful.
             if let Some(_out_of_funds) = decrease_caller_balance(context, l1_fee)? {
@@ -154,6 +217,22 @@ impl<SPEC: Spec, EXT: CitreaHandlerContext, DB: Database> CitreaHandler<SPEC, EX
     }
 }
 
+/// Returns the sorted addresses of the contracts created by the tx which still exist.
+/// Creations reverted by a failing frame are unmarked by the journal.
+fn created_contracts<EXT, DB: Database>(context: &Context<EXT, DB>) -> Vec<Address> {
+    let mut created: Vec<Address> = context
+        .evm
+        .inner
+        .journaled_state
+        .state
+        .iter()
+        .filter(|(_, account)| account.is_created() && !account.is_selfdestructed())
+        .map(|(address, _)| *address)
+        .collect();
+    created.sort();
+    created
+}
+
 /// Calculates the diff of the modified state.
 fn calc_diff_size<EXT, DB: Database>(
     context: &mut Context<EXT, DB>,
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reth_primitives::{Address, B256, U256};
+use revm::interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome};
+use revm::{Database, EvmContext, Inspector};
+
+use super::handler::{CitreaHandlerContext, TxInfo};
+use super::primitive_types::{InternalTransaction, InternalTransactionKind};
+
+static INTERNAL_TX_INDEX: AtomicBool = AtomicBool::new(false);
+
+/// Makes the node record the internal transactions of every transaction it executes,
+/// so that they can be served by `citrea_getInternalTransactions` without re-execution.
+/// Meant to be called once at startup, by nodes serving block explorers.
+pub fn enable_internal_tx_index() {
+    INTERNAL_TX_INDEX.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn internal_tx_index_enabled() -> bool {
+    INTERNAL_TX_INDEX.load(Ordering::Relaxed)
+}
+
+/// Inspector recording value transfers, contract creations and self destructs made by
+/// the calls of a transaction, delegating the Citrea handler context to `ext`.
+///
+/// Internal transactions of calls which are reverted are dropped.
+pub(crate) struct InternalTxRecorder<EXT> {
+    ext: EXT,
+    current_tx_hash: Option<B256>,
+    /// Index in `current` of the first internal transaction of each open frame
+    frame_starts: Vec<usize>,
+    current: Vec<InternalTransaction>,
+    recorded: HashMap<B256, Vec<InternalTransaction>>,
+}
+
+impl<EXT: CitreaHandlerContext> InternalTxRecorder<EXT> {
+    pub(crate) fn new(ext: EXT) -> Self {
+        Self {
+            ext,
+            current_tx_hash: None,
+            frame_starts: vec![],
+            current: vec![],
+            recorded: HashMap::new(),
+        }
+    }
+
+    /// Internal transactions of the executed transactions, by transaction hash.
+    pub(crate) fn into_recorded(self) -> HashMap<B256, Vec<InternalTransaction>> {
+        self.recorded
+    }
+
+    fn depth(&self) -> u64 {
+        self.frame_starts.len() as u64
+    }
+
+    fn record(
+        &mut self,
+        kind: InternalTransactionKind,
+        from: Address,
+        to: Address,
+        value: U256,
+        depth: u64,
+    ) {
+        self.current.push(InternalTransaction {
+            kind,
+            from,
+            to,
+            value,
+            depth,
+        });
+    }
+
+    fn enter_frame(&mut self) {
+        self.frame_starts.push(self.current.len());
+    }
+
+    fn exit_frame(&mut self, success: bool) {
+        let start = self
+            .frame_starts
+            .pop()
+            .expect("Frame must be entered before exiting");
+        if !success {
+            self.current.truncate(start);
+        }
+        if self.frame_starts.is_empty() {
+            let internal_txs = std::mem::take(&mut self.current);
+            if let Some(hash) = self.current_tx_hash {
+                self.recorded.insert(hash, internal_txs);
+            }
+        }
+    }
+}
+
+impl<EXT: CitreaHandlerContext> CitreaHandlerContext for InternalTxRecorder<EXT> {
+    fn l1_fee_rate(&self) -> u64 {
+        self.ext.l1_fee_rate()
+    }
+    fn eip6780_selfdestruct(&self) -> bool {
+        self.ext.eip6780_selfdestruct()
+    }
+    fn calldata_l1_fee(&self) -> bool {
+        self.ext.calldata_l1_fee()
+    }
+    fn set_current_tx_hash(&mut self, hash: B256) {
+        self.current_tx_hash.replace(hash);
+        self.frame_starts.clear();
+        self.current.clear();
+        self.ext.set_current_tx_hash(hash);
+    }
+    fn set_tx_info(&mut self, info: TxInfo) {
+        self.ext.set_tx_info(info)
+    }
+    fn get_tx_info(&self, tx_hash: B256) -> Option<TxInfo> {
+        self.ext.get_tx_info(tx_hash)
+    }
+}
+
+impl<EXT: CitreaHandlerContext, DB: Database> Inspector<DB> for InternalTxRecorder<EXT> {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        // The outermost call is the transaction itself
+        let depth = self.depth();
+        let transfers_value = matches!(
+            inputs.context.scheme,
+            CallScheme::Call | CallScheme::CallCode
+        ) && inputs.transfer.value > U256::ZERO;
+        if depth > 0 && transfers_value {
+            self.record(
+                InternalTransactionKind::Call,
+                inputs.transfer.source,
+                inputs.transfer.target,
+                inputs.transfer.value,
+                depth,
+            );
+        }
+        self.enter_frame();
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit_frame(outcome.result.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let success = outcome.result.result.is_ok();
+        // The created contract is only known once its frame ends
+        if success && self.depth() > 1 {
+            if let Some(address) = outcome.address {
+                let start = *self.frame_starts.last().expect("Create frame is open");
+                self.current.insert(
+                    start,
+                    InternalTransaction {
+                        kind: InternalTransactionKind::Create,
+                        from: inputs.caller,
+                        to: address,
+                        value: inputs.value,
+                        depth: self.depth() - 1,
+                    },
+                );
+            }
+        }
+        self.exit_frame(success);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        // Made by the contract of the innermost open frame
+        let depth = self.depth().saturating_sub(1);
+        self.record(
+            InternalTransactionKind::SelfDestruct,
+            contract,
+            target,
+            value,
+            depth,
+        );
+    }
+}
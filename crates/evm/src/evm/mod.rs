@@ -4,21 +4,32 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::{StateMap, StateVec};
 use sov_state::Prefix;
 
+pub(crate) mod access_hints;
 pub(crate) mod call;
+mod compression;
 pub(crate) mod conversions;
 pub(crate) mod db;
 mod db_commit;
 pub(crate) mod db_init;
+pub(crate) mod deadline;
 pub(crate) mod error;
 pub(crate) mod executor;
 pub(crate) mod handler;
+pub(crate) mod internal_txs;
 pub(crate) mod primitive_types;
+pub(crate) mod state_diff;
+pub(crate) mod supply;
 #[cfg(test)]
 mod tests;
 
+pub use access_hints::enable_access_hints;
 pub(crate) use call::prepare_call_env;
-pub use primitive_types::RlpEvmTransaction;
+pub use compression::estimated_compressed_size;
+pub use internal_txs::enable_internal_tx_index;
+pub use primitive_types::{InternalTransaction, InternalTransactionKind, RlpEvmTransaction};
 use sov_state::codec::BcsCodec;
+pub use state_diff::enable_state_diff_rpc;
+pub use supply::{SupplyEvent, SupplyEventKind, SupplyReference};
 
 #[cfg(test)]
 use crate::tests::DEFAULT_CHAIN_ID;
@@ -99,11 +110,56 @@ pub struct EvmChainConfig {
     /// Gas limit for single block
     pub block_gas_limit: u64,
 
+    /// Gas limits for single blocks by block number from which they apply, sorted by block
+    /// number. Blocks before the first entry use `block_gas_limit`.
+    pub block_gas_limit_schedule: Vec<(u64, u64)>,
+
     /// Delta to add to parent block timestamp
     pub block_timestamp_delta: u64,
 
     /// Base fee params.
     pub base_fee_params: BaseFeeParams,
+
+    /// If set, `SELFDESTRUCT` follows EIP-6780: it only deletes accounts created in the same
+    /// transaction, other accounts only send their balance. Otherwise it always deletes the
+    /// account, as before Cancun.
+    pub eip6780_selfdestruct: bool,
+
+    /// If set, `PREVRANDAO` of a block is `keccak256(l1_block_hash || block_number)`, see
+    /// [`l1_mixed_prevrandao`](crate::l1_mixed_prevrandao). Otherwise it is the hash of the
+    /// L1 block the block is anchored to, the same for every block on that L1 block.
+    pub l1_mixed_prevrandao: bool,
+
+    /// If set, the L1 fee of a transaction also charges the estimated compressed size of its
+    /// calldata, see [`estimated_compressed_size`]. Otherwise only its state diff is charged.
+    pub calldata_l1_fee: bool,
+
+    /// Max. number of transactions in a single block, checked by the kernel so that no
+    /// block takes too long to prove. Unlimited if not set.
+    pub max_txs_per_block: Option<u64>,
+}
+
+impl EvmChainConfig {
+    /// Returns the gas limit of the block with the given number, following the schedule.
+    pub fn block_gas_limit_at(&self, block_number: u64) -> u64 {
+        match self
+            .block_gas_limit_schedule
+            .binary_search_by(|&(number, _)| number.cmp(&block_number))
+        {
+            Ok(index) => self.block_gas_limit_schedule[index].1,
+            Err(0) => self.block_gas_limit,
+            Err(index) => self.block_gas_limit_schedule[index - 1].1,
+        }
+    }
+
+    /// Returns the calldata bytes charged in the L1 fee of a transaction with the given input.
+    pub fn l1_calldata_size(&self, data: &[u8]) -> u64 {
+        if self.calldata_l1_fee {
+            estimated_compressed_size(data)
+        } else {
+            0
+        }
+    }
 }
 
 #[cfg(test)]
@@ -115,8 +171,13 @@ impl Default for EvmChainConfig {
             spec: vec![(0, SpecId::SHANGHAI)],
             coinbase: Address::ZERO,
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
+            block_gas_limit_schedule: vec![],
             block_timestamp_delta: 2,
             base_fee_params: BaseFeeParams::ethereum(),
+            eip6780_selfdestruct: false,
+            l1_mixed_prevrandao: false,
+            calldata_l1_fee: false,
+            max_txs_per_block: None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use reth_primitives::{Address, Header, SealedHeader, TransactionSigned, B256};
+use reth_primitives::{Address, Header, SealedHeader, TransactionSigned, B256, U256};
 use revm::primitives::EVMError;
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
@@ -126,3 +126,31 @@ pub(crate) struct Receipt {
     pub(crate) diff_size: u64,
     pub(crate) error: Option<EVMError<u8>>,
 }
+
+/// Kind of an [`InternalTransaction`].
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum InternalTransactionKind {
+    /// Value transfer with a `CALL` or `CALLCODE`.
+    Call,
+    /// Contract creation with a `CREATE` or `CREATE2`.
+    Create,
+    /// Balance transfer of a `SELFDESTRUCT`.
+    SelfDestruct,
+}
+
+/// Value transfer or contract creation made by a contract during the execution of a transaction.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InternalTransaction {
+    /// Kind of the internal transaction.
+    pub kind: InternalTransactionKind,
+    /// Sender, or creator of the contract.
+    pub from: Address,
+    /// Recipient, or created contract.
+    pub to: Address,
+    /// Transferred value.
+    pub value: U256,
+    /// Call depth the internal transaction was made at, 0 being the transaction itself.
+    pub depth: u64,
+}
@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reth_primitives::{Address, U256};
+use revm::primitives::{Account, AccountInfo, Bytecode, B256};
+use revm::{Database, DatabaseCommit};
+
+static STATE_DIFF_RPC: AtomicBool = AtomicBool::new(false);
+
+/// Makes the node serve `citrea_getStateDiff`, which re-executes historical blocks.
+/// Meant to be called once at startup, by archive nodes serving accounting systems
+/// and debuggers.
+pub fn enable_state_diff_rpc() {
+    STATE_DIFF_RPC.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn state_diff_rpc_enabled() -> bool {
+    STATE_DIFF_RPC.load(Ordering::Relaxed)
+}
+
+/// Database wrapper recording the accounts and storage slots changed by the
+/// transactions committed to it, along with their values before the first change.
+pub(crate) struct StateDiffRecorder<DB> {
+    db: DB,
+    /// Account info as first read from `db`, `None` if the account did not exist
+    accounts_before: HashMap<Address, Option<AccountInfo>>,
+    /// Account info after the last commit, `None` if the account self destructed
+    accounts_after: BTreeMap<Address, Option<AccountInfo>>,
+    /// Values of the storage slots before the first and after the last commit
+    storage: BTreeMap<(Address, U256), (U256, U256)>,
+}
+
+/// Changes recorded by a [`StateDiffRecorder`], in address and slot order.
+pub(crate) struct RecordedStateDiff {
+    pub(crate) accounts: Vec<(Address, Option<AccountInfo>, Option<AccountInfo>)>,
+    pub(crate) storage: Vec<(Address, U256, U256, U256)>,
+}
+
+impl<DB> StateDiffRecorder<DB> {
+    pub(crate) fn new(db: DB) -> Self {
+        Self {
+            db,
+            accounts_before: HashMap::new(),
+            accounts_after: BTreeMap::new(),
+            storage: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the accounts and storage slots whose values differ from before the
+    /// first commit.
+    pub(crate) fn into_diff(self) -> RecordedStateDiff {
+        let accounts = self
+            .accounts_after
+            .into_iter()
+            .filter_map(|(address, after)| {
+                let before = self.accounts_before.get(&address).cloned().flatten();
+                let unchanged = match (&before, &after) {
+                    (Some(before), Some(after)) => {
+                        before.balance == after.balance
+                            && before.nonce == after.nonce
+                            && before.code_hash == after.code_hash
+                    }
+                    (None, None) => true,
+                    _ => false,
+                };
+                (!unchanged).then_some((address, before, after))
+            })
+            .collect();
+        let storage = self
+            .storage
+            .into_iter()
+            .filter(|(_, (before, after))| before != after)
+            .map(|((address, slot), (before, after))| (address, slot, before, after))
+            .collect();
+        RecordedStateDiff { accounts, storage }
+    }
+}
+
+impl<DB: Database> Database for StateDiffRecorder<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        self.accounts_before
+            .entry(address)
+            .or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for StateDiffRecorder<DB> {
+    fn commit(&mut self, changes: revm::primitives::HashMap<Address, Account>) {
+        for (address, account) in changes.iter() {
+            if !account.is_touched() {
+                continue;
+            }
+            let destroyed = account.is_selfdestructed();
+            self.accounts_after
+                .insert(*address, (!destroyed).then(|| account.info.clone()));
+            for (slot, value) in account.storage.iter() {
+                let after = if destroyed {
+                    U256::ZERO
+                } else {
+                    value.present_value()
+                };
+                self.storage
+                    .entry((*address, *slot))
+                    .and_modify(|(_, present)| *present = after)
+                    .or_insert((value.original_value(), after));
+            }
+        }
+        self.db.commit(changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::primitives::{AccountStatus, StorageSlot};
+    use revm::InMemoryDB;
+
+    use super::*;
+
+    #[test]
+    fn test_state_diff_recorder_keeps_first_and_last_values() {
+        let address = Address::with_last_byte(1);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                balance: U256::from(100),
+                ..Default::default()
+            },
+        );
+        let mut recorder = StateDiffRecorder::new(db);
+
+        let mut info = recorder.basic(address).unwrap().unwrap();
+        for (balance, value) in [(50, 1), (100, 2)] {
+            info.balance = U256::from(balance);
+            let original = recorder.storage(address, U256::ZERO).unwrap();
+            let account = Account {
+                info: info.clone(),
+                storage: [(
+                    U256::ZERO,
+                    StorageSlot::new_changed(original, U256::from(value)),
+                )]
+                .into_iter()
+                .collect(),
+                status: AccountStatus::Touched,
+            };
+            recorder.commit([(address, account)].into_iter().collect());
+        }
+
+        let diff = recorder.into_diff();
+        // The balance went back to its value before the first commit
+        assert!(diff.accounts.is_empty());
+        assert_eq!(
+            diff.storage,
+            vec![(address, U256::ZERO, U256::ZERO, U256::from(2))]
+        );
+    }
+}
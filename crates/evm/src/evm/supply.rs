@@ -0,0 +1,243 @@
+use std::convert::Infallible;
+
+use reth_primitives::{Address, B256, U256};
+use revm::primitives::{Account, AccountInfo, Bytecode, HashMap};
+use revm::{Database, DatabaseCommit};
+use sov_modules_api::prelude::*;
+use sov_modules_api::AccessoryWorkingSet;
+
+use crate::Evm;
+
+/// Whether native tokens were created or destroyed.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SupplyEventKind {
+    /// Native tokens were created.
+    Mint,
+    /// Native tokens were destroyed.
+    Burn,
+}
+
+/// What triggered a [`SupplyEvent`].
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SupplyReference {
+    /// Balance of the account in the genesis config.
+    Genesis(Address),
+    /// Bitcoin transaction of a bridge deposit or withdrawal.
+    BitcoinTx(B256),
+    /// L2 transaction, e.g. one sending the balance of a contract to itself when it self destructs.
+    L2Tx(B256),
+}
+
+/// Change of the total supply of the native token.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyEvent {
+    /// Whether tokens were minted or burnt.
+    pub kind: SupplyEventKind,
+    /// Amount of tokens minted or burnt.
+    pub amount: U256,
+    /// What triggered the change.
+    pub reference: SupplyReference,
+}
+
+/// Balances credited and debited by the state changes of a transaction.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct SupplyDelta {
+    credited: U256,
+    debited: U256,
+}
+
+impl SupplyDelta {
+    /// Returns the event recording the net change of supply, `None` if the balances
+    /// credited and debited are equal.
+    pub(crate) fn into_event(self, reference: SupplyReference) -> Option<SupplyEvent> {
+        let (kind, amount) = if self.credited > self.debited {
+            (SupplyEventKind::Mint, self.credited - self.debited)
+        } else {
+            (SupplyEventKind::Burn, self.debited - self.credited)
+        };
+        (amount > U256::ZERO).then_some(SupplyEvent {
+            kind,
+            amount,
+            reference,
+        })
+    }
+}
+
+/// Database wrapper recording the balance changes of every commit made through it, if enabled.
+pub(crate) struct SupplyRecorder<DB> {
+    db: DB,
+    deltas: Option<Vec<SupplyDelta>>,
+}
+
+impl<DB> SupplyRecorder<DB> {
+    pub(crate) fn new(db: DB, enabled: bool) -> Self {
+        Self {
+            db,
+            deltas: enabled.then(Vec::new),
+        }
+    }
+
+    pub(crate) fn into_inner(self) -> DB {
+        self.db
+    }
+
+    /// Returns the balance changes of each commit, in order, `None` if the recorder was
+    /// not enabled.
+    pub(crate) fn take_deltas(&mut self) -> Option<Vec<SupplyDelta>> {
+        self.deltas.as_mut().map(std::mem::take)
+    }
+}
+
+impl<DB: Database> Database for SupplyRecorder<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
+impl<DB: Database<Error = Infallible> + DatabaseCommit> DatabaseCommit for SupplyRecorder<DB> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        if let Some(deltas) = &mut self.deltas {
+            let mut delta = SupplyDelta::default();
+            for (address, account) in &changes {
+                // Untouched accounts are not written by the commit
+                if !account.is_touched() {
+                    continue;
+                }
+                let previous = match self.db.basic(*address) {
+                    Ok(info) => info.map(|info| info.balance).unwrap_or_default(),
+                    Err(e) => match e {},
+                };
+                // The balance of a self destructed account is cleared, which burns it when the
+                // account was its own beneficiary
+                let current = if account.is_selfdestructed() {
+                    U256::ZERO
+                } else {
+                    account.info.balance
+                };
+                delta.credited += current.saturating_sub(previous);
+                delta.debited += previous.saturating_sub(current);
+            }
+            deltas.push(delta);
+        }
+        self.db.commit(changes);
+    }
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Adds `events` to the supply events of the block and applies them to the total supply.
+    pub(crate) fn record_supply_events(
+        &self,
+        block_number: u64,
+        events: Vec<SupplyEvent>,
+        accessory_state: &mut AccessoryWorkingSet<C>,
+    ) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut total_supply = self.total_supply.get(accessory_state).unwrap_or_default();
+        for event in &events {
+            match event.kind {
+                SupplyEventKind::Mint => total_supply += event.amount,
+                SupplyEventKind::Burn => total_supply -= event.amount,
+            }
+        }
+        self.total_supply.set(&total_supply, accessory_state);
+
+        let mut block_events = self
+            .supply_events
+            .get(&block_number, accessory_state)
+            .unwrap_or_default();
+        block_events.extend(events);
+        self.supply_events
+            .set(&block_number, &block_events, accessory_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::primitives::AccountStatus;
+    use revm::InMemoryDB;
+
+    use super::*;
+
+    fn touched(balance: u64, status: AccountStatus) -> Account {
+        Account {
+            info: AccountInfo {
+                balance: U256::from(balance),
+                ..Default::default()
+            },
+            storage: Default::default(),
+            status: status | AccountStatus::Touched,
+        }
+    }
+
+    #[test]
+    fn test_supply_recorder_records_net_balance_changes() {
+        let sender = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            sender,
+            AccountInfo {
+                balance: U256::from(100),
+                ..Default::default()
+            },
+        );
+
+        let mut recorder = SupplyRecorder::new(db, true);
+        // Transfer
+        recorder.commit(
+            [
+                (sender, touched(60, AccountStatus::empty())),
+                (recipient, touched(40, AccountStatus::empty())),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        // Self destruct of an account which is its own beneficiary
+        recorder.commit(
+            [(recipient, touched(40, AccountStatus::SelfDestructed))]
+                .into_iter()
+                .collect(),
+        );
+
+        let deltas = recorder.take_deltas().unwrap();
+        let reference = SupplyReference::L2Tx(B256::ZERO);
+        assert_eq!(deltas[0].into_event(reference), None);
+        assert_eq!(
+            deltas[1].into_event(reference),
+            Some(SupplyEvent {
+                kind: SupplyEventKind::Burn,
+                amount: U256::from(40),
+                reference,
+            })
+        );
+        assert_eq!(recorder.take_deltas(), Some(vec![]));
+
+        let mut recorder = SupplyRecorder::new(InMemoryDB::default(), false);
+        recorder.commit(
+            [(sender, touched(1, AccountStatus::empty()))]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(recorder.take_deltas(), None);
+    }
+}
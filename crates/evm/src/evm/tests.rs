@@ -54,7 +54,7 @@ fn simple_contract_execution<DB: Database<Error = Infallible> + DatabaseCommit +
     let mut cfg_env = CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::SHANGHAI);
     cfg_env.chain_id = DEFAULT_CHAIN_ID;
 
-    let mut citrea_ext = CitreaHandlerExt::new(0);
+    let mut citrea_ext = CitreaHandlerExt::new(0, false, false);
 
     let contract_address: Address = {
         let tx = dev_signer
@@ -9,7 +9,7 @@ use sov_modules_api::WorkingSet;
 
 use crate::evm::db_init::InitEvmDb;
 use crate::evm::primitive_types::Block;
-use crate::evm::{AccountInfo, EvmChainConfig};
+use crate::evm::{AccountInfo, EvmChainConfig, SupplyEvent, SupplyEventKind, SupplyReference};
 #[cfg(test)]
 use crate::tests::DEFAULT_CHAIN_ID;
 use crate::Evm;
@@ -58,12 +58,27 @@ pub struct EvmConfig {
     pub starting_base_fee: u64,
     /// Gas limit for single block
     pub block_gas_limit: u64,
+    /// Gas limits for single blocks by block number from which they apply
+    #[serde(default)]
+    pub block_gas_limit_schedule: HashMap<u64, u64>,
     /// Genesis timestamp.
     pub genesis_timestamp: u64,
     /// Delta to add to parent block timestamp,
     pub block_timestamp_delta: u64,
     /// Base fee params.
     pub base_fee_params: reth_primitives::BaseFeeParams,
+    /// Whether `SELFDESTRUCT` only deletes accounts created in the same transaction (EIP-6780).
+    #[serde(default)]
+    pub eip6780_selfdestruct: bool,
+    /// Whether `PREVRANDAO` mixes the L1 block hash with the block number.
+    #[serde(default)]
+    pub l1_mixed_prevrandao: bool,
+    /// Whether the L1 fee also charges the estimated compressed size of the calldata.
+    #[serde(default)]
+    pub calldata_l1_fee: bool,
+    /// Max. number of transactions in a single block, unlimited if not set.
+    #[serde(default)]
+    pub max_txs_per_block: Option<u64>,
 }
 
 #[cfg(test)]
@@ -77,9 +92,14 @@ impl Default for EvmConfig {
             coinbase: Address::ZERO,
             starting_base_fee: reth_primitives::constants::EIP1559_INITIAL_BASE_FEE,
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
+            block_gas_limit_schedule: HashMap::new(),
             block_timestamp_delta: reth_primitives::constants::SLOT_DURATION.as_secs(),
             genesis_timestamp: 0,
             base_fee_params: reth_primitives::BaseFeeParams::ethereum(),
+            eip6780_selfdestruct: false,
+            l1_mixed_prevrandao: false,
+            calldata_l1_fee: false,
+            max_txs_per_block: None,
         }
     }
 }
@@ -128,16 +148,38 @@ impl<C: sov_modules_api::Context> Evm<C> {
             panic!("EVM spec must start from block 0");
         }
 
+        let mut block_gas_limit_schedule = config
+            .block_gas_limit_schedule
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect::<Vec<_>>();
+
+        block_gas_limit_schedule.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut previous_gas_limit = config.block_gas_limit;
+        for (_, gas_limit) in &block_gas_limit_schedule {
+            if *gas_limit < previous_gas_limit {
+                panic!("Block gas limit schedule must not decrease the gas limit");
+            }
+            previous_gas_limit = *gas_limit;
+        }
+
         let chain_cfg = EvmChainConfig {
             chain_id: config.chain_id,
             limit_contract_code_size: config.limit_contract_code_size,
             spec,
             coinbase: config.coinbase,
             block_gas_limit: config.block_gas_limit,
+            block_gas_limit_schedule,
             block_timestamp_delta: config.block_timestamp_delta,
             base_fee_params: config.base_fee_params,
+            eip6780_selfdestruct: config.eip6780_selfdestruct,
+            l1_mixed_prevrandao: config.l1_mixed_prevrandao,
+            calldata_l1_fee: config.calldata_l1_fee,
+            max_txs_per_block: config.max_txs_per_block,
         };
 
+        let genesis_gas_limit = chain_cfg.block_gas_limit_at(0);
         self.cfg.set(&chain_cfg, working_set);
 
         let header = reth_primitives::Header {
@@ -152,7 +194,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             logs_bloom: Bloom::default(),
             difficulty: U256::ZERO,
             number: 0,
-            gas_limit: config.block_gas_limit,
+            gas_limit: genesis_gas_limit,
             gas_used: 0,
             timestamp: config.genesis_timestamp,
             mix_hash: B256::default(),
@@ -178,6 +220,18 @@ impl<C: sov_modules_api::Context> Evm<C> {
         self.pending_head
             .set(&block, &mut working_set.accessory_state());
 
+        let genesis_mints = config
+            .data
+            .iter()
+            .filter(|acc| acc.balance > U256::ZERO)
+            .map(|acc| SupplyEvent {
+                kind: SupplyEventKind::Mint,
+                amount: acc.balance,
+                reference: SupplyReference::Genesis(acc.address),
+            })
+            .collect();
+        self.record_supply_events(0, genesis_mints, &mut working_set.accessory_state());
+
         Ok(())
     }
 }
@@ -1,22 +1,42 @@
 use alloy_primitives::B256;
-use reth_primitives::{Bloom, Bytes, U256};
+use reth_primitives::{keccak256, Bloom, Bytes, U256};
+use sov_modules_api::hooks::ApplySoftConfirmationError;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{AccessoryWorkingSet, Spec, WorkingSet};
+use sov_modules_api::{AccessoryWorkingSet, SoftConfirmationOutcome, Spec, WorkingSet};
 use sov_state::Storage;
 
 use crate::evm::primitive_types::{Block, BlockEnv};
-use crate::{Evm, PendingTransaction};
+use crate::{Evm, PendingTransaction, RECENT_TX_WINDOW};
+
+/// `PREVRANDAO` of the L2 block `block_number` anchored to the L1 block `l1_block_hash`,
+/// when [`EvmChainConfig::l1_mixed_prevrandao`](crate::EvmChainConfig) is set:
+/// `keccak256(l1_block_hash || block_number)`, with the number as 8 big endian bytes.
+///
+/// Every input is part of the soft confirmation and of the DA block it is proven against,
+/// so the value is deterministic and checked by the prover. Unlike on Ethereum it is known
+/// to the sequencer in advance and must not be used as a source of randomness.
+pub fn l1_mixed_prevrandao(l1_block_hash: [u8; 32], block_number: u64) -> B256 {
+    let mut preimage = [0u8; 40];
+    preimage[..32].copy_from_slice(&l1_block_hash);
+    preimage[32..].copy_from_slice(&block_number.to_be_bytes());
+    keccak256(preimage)
+}
 
 impl<C: sov_modules_api::Context> Evm<C>
 where
     <C::Storage as Storage>::Root: Into<[u8; 32]>,
 {
     /// Logic executed at the beginning of the slot. Here we set the root hash of the previous head.
+    ///
+    /// The block gets the `timestamp` of its soft confirmation, as validated by the kernel,
+    /// or the one of its parent plus the block timestamp delta if the soft confirmation
+    /// carries none.
     pub fn begin_soft_confirmation_hook(
         &self,
         da_root_hash: [u8; 32],
         pre_state_root: &[u8],
         l1_fee_rate: u64,
+        timestamp: Option<u64>,
         working_set: &mut WorkingSet<C>,
     ) {
         let mut parent_block = self
@@ -41,16 +61,28 @@ where
             .cfg
             .get(working_set)
             .expect("EVM chain config should be set");
+        let number = parent_block.header.number + 1;
+        let prevrandao = if cfg.l1_mixed_prevrandao {
+            l1_mixed_prevrandao(da_root_hash, number)
+        } else {
+            da_root_hash.into()
+        };
+        // Blocks derived from the parent timestamp before the activation may be ahead of the
+        // soft confirmation timestamps, which must not take the block time back
+        let timestamp = match timestamp {
+            Some(timestamp) => timestamp.max(parent_block.header.timestamp),
+            None => parent_block.header.timestamp + cfg.block_timestamp_delta,
+        };
         let new_pending_env = BlockEnv {
-            number: parent_block.header.number + 1,
+            number,
             coinbase: cfg.coinbase,
-            timestamp: parent_block.header.timestamp + cfg.block_timestamp_delta,
-            prevrandao: da_root_hash.into(),
+            timestamp,
+            prevrandao,
             basefee: parent_block
                 .header
                 .next_block_base_fee(cfg.base_fee_params)
                 .unwrap(),
-            gas_limit: cfg.block_gas_limit,
+            gas_limit: cfg.block_gas_limit_at(number),
         };
         self.block_env.set(&new_pending_env, working_set);
         self.l1_fee_rate.set(&l1_fee_rate, working_set);
@@ -67,9 +99,40 @@ where
         }
     }
 
+    /// Checks that the pending block doesn't have more transactions than
+    /// [`EvmChainConfig::max_txs_per_block`](crate::EvmChainConfig) allows.
+    /// Must run before [`Self::end_soft_confirmation_hook`], which seals the pending block.
+    pub fn check_block_tx_count(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ApplySoftConfirmationError> {
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let Some(max_tx_count) = cfg.max_txs_per_block else {
+            return Ok(());
+        };
+
+        let tx_count = self.pending_transactions.len(working_set) as u64;
+        if tx_count > max_tx_count {
+            return Err(ApplySoftConfirmationError::TooManyTransactions {
+                tx_count,
+                max_tx_count,
+            });
+        }
+        Ok(())
+    }
+
     /// Logic executed at the end of the slot. Here, we generate an authenticated block and set it as the new head of the chain.
     /// It's important to note that the state root hash is not known at this moment, so we postpone setting this field until the begin_slot_hook of the next slot.
-    pub fn end_soft_confirmation_hook(&self, working_set: &mut WorkingSet<C>) {
+    ///
+    /// Returns the gas used by the block and the fees its transactions paid, of which the
+    /// sequencer gets everything but the burnt base fee. Enforcement results are left empty.
+    pub fn end_soft_confirmation_hook(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> SoftConfirmationOutcome {
         let cfg = self
             .cfg
             .get(working_set)
@@ -103,6 +166,26 @@ where
 
         self.pending_transactions.clear(working_set);
 
+        // The hashes of the executed transactions were recorded by the calls, and are
+        // forgotten once their block leaves the window
+        let tx_hashes: Vec<B256> = pending_transactions
+            .iter()
+            .map(|tx| tx.transaction.signed_transaction.hash)
+            .collect();
+        if !tx_hashes.is_empty() {
+            self.block_tx_hashes
+                .set(&block_env.number, &tx_hashes, working_set);
+        }
+        if let Some(expired) = block_env.number.checked_sub(RECENT_TX_WINDOW) {
+            for tx_hash in self
+                .block_tx_hashes
+                .remove(&expired, working_set)
+                .unwrap_or_default()
+            {
+                self.recent_tx_hashes.delete(&tx_hash, working_set);
+            }
+        }
+
         let start_tx_index = parent_block.transactions.end;
 
         let gas_used = pending_transactions
@@ -119,6 +202,23 @@ where
             .map(|tx| tx.receipt.receipt.clone().with_bloom())
             .collect();
 
+        let mut fees_collected = 0u128;
+        let mut sequencer_reward = 0u128;
+        for PendingTransaction {
+            transaction,
+            receipt,
+        } in &pending_transactions
+        {
+            let tx = &transaction.signed_transaction;
+            let gas_price = tx.effective_gas_price(Some(block_env.basefee));
+            // Same DA bytes as charged by the handler and reported by `citrea_getChainStats`
+            let da_bytes = receipt.diff_size + cfg.l1_calldata_size(tx.input());
+            let l1_fee = da_bytes as u128 * l1_fee_rate as u128;
+            let priority_fee = gas_price.saturating_sub(block_env.basefee as u128);
+            fees_collected += gas_price * receipt.gas_used as u128 + l1_fee;
+            sequencer_reward += priority_fee * receipt.gas_used as u128 + l1_fee;
+        }
+
         let header = reth_primitives::Header {
             parent_hash: parent_block.header.hash(),
             timestamp: block_env.timestamp,
@@ -181,6 +281,13 @@ where
         }
 
         self.pending_transactions.clear(working_set);
+
+        SoftConfirmationOutcome {
+            gas_used,
+            fees_collected,
+            sequencer_reward,
+            ..Default::default()
+        }
     }
 
     /// This logic is executed after calculating the root hash.
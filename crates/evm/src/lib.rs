@@ -10,6 +10,8 @@ pub use call::*;
 pub use error::rpc::*;
 pub use evm::*;
 pub use genesis::*;
+pub use hooks::l1_mixed_prevrandao;
+pub use provider_functions::SimulationOutcome;
 pub use rpc_helpers::*;
 #[cfg(feature = "native")]
 mod query;
@@ -36,6 +38,14 @@ use crate::evm::primitive_types::{
 };
 pub use crate::EvmConfig;
 
+/// Version of the Citrea protocol, bumped on changes of the state transition observable by
+/// clients. Reported by `citrea_getChainCapabilities`.
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// Number of blocks a transaction is remembered for. A transaction included in one of the
+/// last `RECENT_TX_WINDOW` blocks is skipped instead of being executed again.
+pub const RECENT_TX_WINDOW: u64 = 1024;
+
 // Gas per transaction not creating a contract.
 pub(crate) const MIN_TRANSACTION_GAS: u64 = 21_000u64;
 
@@ -97,6 +107,16 @@ pub struct Evm<C: sov_modules_api::Context> {
     #[state]
     pub(crate) l1_fee_rate: sov_modules_api::StateValue<u64, BcsCodec>,
 
+    /// Hashes of the transactions of the pending block and of the last [`RECENT_TX_WINDOW`]
+    /// blocks, with the number of the block which included them.
+    #[state]
+    pub(crate) recent_tx_hashes: sov_modules_api::StateMap<B256, u64, BcsCodec>,
+
+    /// Transaction hashes of each of the last [`RECENT_TX_WINDOW`] blocks, by block number,
+    /// to forget them once they leave the window.
+    #[state]
+    pub(crate) block_tx_hashes: sov_modules_api::StateMap<u64, Vec<B256>, BcsCodec>,
+
     /// Used only by the RPC: This represents the head of the chain and is set in two distinct stages:
     /// 1. `end_slot_hook`: the pending head is populated with data from pending_transactions.
     /// 2. `finalize_hook` the `root_hash` is populated.
@@ -126,6 +146,37 @@ pub struct Evm<C: sov_modules_api::Context> {
     /// Used only by the RPC: Receipts.
     #[state]
     pub(crate) receipts: sov_modules_api::AccessoryStateVec<Receipt, BcsCodec>,
+
+    /// Used only by the RPC: contract address => hash of the transaction which created it.
+    #[state]
+    pub(crate) contract_creations:
+        sov_modules_api::AccessoryStateMap<Address, reth_primitives::B256, BcsCodec>,
+
+    /// Used only by the RPC: transaction_hash => internal transactions mapping.
+    /// Only filled by nodes which enabled the index with [`enable_internal_tx_index`].
+    #[state]
+    pub(crate) internal_transactions: sov_modules_api::AccessoryStateMap<
+        reth_primitives::B256,
+        Vec<evm::InternalTransaction>,
+        BcsCodec,
+    >,
+
+    /// Used only by the RPC: block number => keys of the state read by its transactions.
+    /// Only filled by nodes which enabled the hints with [`enable_access_hints`].
+    #[state]
+    pub(crate) access_hints:
+        sov_modules_api::AccessoryStateMap<u64, Vec<reth_primitives::Bytes>, BcsCodec>,
+
+    /// Used only by the RPC: total supply of the native token, the balances minted at genesis
+    /// and later minus the ones burnt.
+    #[state]
+    pub(crate) total_supply: sov_modules_api::AccessoryStateValue<U256, BcsCodec>,
+
+    /// Used only by the RPC: block number => mints and burns of the native token in the block,
+    /// genesis balances being minted at block 0.
+    #[state]
+    pub(crate) supply_events:
+        sov_modules_api::AccessoryStateMap<u64, Vec<evm::SupplyEvent>, BcsCodec>,
 }
 
 impl<C: sov_modules_api::Context> sov_modules_api::Module for Evm<C> {
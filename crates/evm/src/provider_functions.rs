@@ -1,8 +1,28 @@
-use reth_primitives::{Account, Address, SealedHeader};
-use sov_modules_api::{StateMapAccessor, StateVecAccessor, WorkingSet};
+use std::collections::HashSet;
+use std::time::Duration;
 
+use reth_primitives::{Account, Address, SealedHeader, TransactionSignedEcRecovered};
+use revm::primitives::ExecutionResult;
+use sov_modules_api::{StateMapAccessor, StateValueAccessor, StateVecAccessor, WorkingSet};
+
+use crate::call::get_cfg_env;
+use crate::evm::deadline::ExecutionDeadline;
+use crate::evm::executor;
+use crate::evm::handler::CitreaHandlerExt;
+use crate::evm::primitive_types::BlockEnv;
 use crate::{DbAccount, Evm};
 
+/// Outcome of a transaction simulated by [`Evm::simulate_next_block_txs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationOutcome {
+    /// The transaction succeeded.
+    Succeeded,
+    /// The transaction reverted, halted or was invalid.
+    Failed,
+    /// The transaction executed for longer than the budget and was halted.
+    TimedOut,
+}
+
 impl<C: sov_modules_api::Context> Evm<C> {
     /// Returns the account at the given address.
     pub fn basic_account(
@@ -30,4 +50,78 @@ impl<C: sov_modules_api::Context> Evm<C> {
             .unwrap()
             .header
     }
+
+    /// Executes `txs` in order on top of the head block, as the next block would, and returns
+    /// the outcome of each. Transactions executing for longer than `budget`, if set, are
+    /// halted.
+    ///
+    /// The state changes are written to `working_set`, which is meant to be discarded. The
+    /// next block is simulated with the `PREVRANDAO` of the head block.
+    pub fn simulate_next_block_txs(
+        &self,
+        txs: &[TransactionSignedEcRecovered],
+        l1_fee_rate: u64,
+        budget: Option<Duration>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<SimulationOutcome> {
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let head = self.last_sealed_header(working_set);
+        let number = head.number + 1;
+        let block_env = BlockEnv {
+            number,
+            coinbase: cfg.coinbase,
+            timestamp: head.timestamp + cfg.block_timestamp_delta,
+            prevrandao: head.mix_hash,
+            basefee: head
+                .next_block_base_fee(cfg.base_fee_params)
+                .unwrap_or_default(),
+            gas_limit: cfg.block_gas_limit_at(number),
+        };
+
+        let mut citrea_handler_ext =
+            CitreaHandlerExt::new(l1_fee_rate, cfg.eip6780_selfdestruct, cfg.calldata_l1_fee);
+        let cfg_env = get_cfg_env(&block_env, cfg, None);
+        let evm_db = self.get_db(working_set);
+        let (results, timed_out) = match budget {
+            Some(budget) => {
+                let mut deadline = ExecutionDeadline::new(&mut citrea_handler_ext, budget);
+                let results = executor::inspect_multiple_tx(
+                    evm_db,
+                    block_env,
+                    0,
+                    txs,
+                    cfg_env,
+                    &mut deadline,
+                );
+                (results, deadline.into_timed_out())
+            }
+            None => (
+                executor::execute_multiple_tx(
+                    evm_db,
+                    block_env,
+                    0,
+                    txs,
+                    cfg_env,
+                    &mut citrea_handler_ext,
+                ),
+                HashSet::new(),
+            ),
+        };
+        results
+            .into_iter()
+            .zip(txs)
+            .map(|(result, tx)| {
+                if timed_out.contains(&tx.hash()) {
+                    SimulationOutcome::TimedOut
+                } else if matches!(result, Ok(ExecutionResult::Success { .. })) {
+                    SimulationOutcome::Succeeded
+                } else {
+                    SimulationOutcome::Failed
+                }
+            })
+            .collect()
+    }
 }
@@ -3,6 +3,7 @@ use std::ops::{Range, RangeInclusive};
 
 use alloy_primitives::Uint;
 use alloy_rlp::Encodable;
+use borsh::BorshSerialize;
 use jsonrpsee::core::RpcResult;
 use reth_interfaces::provider::ProviderError;
 use reth_primitives::revm::env::tx_env_with_recovered;
@@ -12,31 +13,48 @@ use reth_primitives::{
 };
 use reth_revm::tracing::{TracingInspector, TracingInspectorConfig};
 use reth_rpc_types::other::OtherFields;
-use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace};
+use reth_rpc_types::trace::geth::{
+    GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
+};
 use reth_rpc_types::AccessListWithGasUsed;
 use reth_rpc_types_compat::block::from_primitive_with_hash;
 use revm::primitives::{
-    CfgEnvWithHandlerCfg, EVMError, ExecutionResult, HaltReason, InvalidTransaction, TransactTo,
-    TxEnv, KECCAK_EMPTY,
+    CfgEnvWithHandlerCfg, EVMError, ExecutionResult, HaltReason, InvalidTransaction, SpecId,
+    TransactTo, TxEnv, KECCAK_EMPTY, MAX_CODE_SIZE,
 };
 use revm::{Database, DatabaseCommit};
 use revm_inspectors::access_list::AccessListInspector;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
+use sov_state::storage::{NativeStorage, Storage, StorageKey};
 use tracing::info;
 
-use crate::call::get_cfg_env;
+use crate::call::{get_cfg_env, get_spec_id};
 use crate::error::rpc::{ensure_success, EthApiError, RevertError, RpcInvalidTransactionError};
 use crate::evm::db::EvmDb;
-use crate::evm::prepare_call_env;
+use crate::evm::executor::{self, simulate_tx};
+use crate::evm::handler::CitreaHandlerExt;
 use crate::evm::primitive_types::{BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered};
+use crate::evm::state_diff::{state_diff_rpc_enabled, StateDiffRecorder};
+use crate::evm::{prepare_call_env, DbAccount};
 use crate::rpc_helpers::*;
 use crate::{
     BloomFilter, EthResult, Evm, EvmChainConfig, FilterBlockOption, FilterError,
-    ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS,
+    InternalTransaction, SupplyEvent, ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS,
+    PROTOCOL_VERSION,
 };
 
+/// Max. number of blocks aggregated by a single `citrea_getChainStats` query
+const MAX_CHAIN_STATS_BLOCKS: u64 = 10_000;
+
+/// Max. number of calls simulated by a single `eth_callMany` or `eth_simulateV1` query, over
+/// all bundles or blocks
+const MAX_CALL_MANY_CALLS: usize = 1000;
+
+/// Max. number of blocks simulated by a single `eth_simulateV1` query
+const MAX_SIMULATED_BLOCKS: usize = 256;
+
 #[rpc_gen(client, server)]
 impl<C: sov_modules_api::Context> Evm<C> {
     /// Handler for `net_version`
@@ -165,13 +183,23 @@ impl<C: sov_modules_api::Context> Evm<C> {
             }),
         };
 
+        // Blocks have no withdrawals, but strict clients expect an empty list from Shanghai on
+        let spec_id = get_spec_id(
+            self.get_chain_config(working_set).spec,
+            header
+                .number
+                .expect("Block must be already sealed")
+                .to::<u64>(),
+        );
+        let withdrawals = SpecId::enabled(spec_id, SpecId::SHANGHAI).then(Vec::new);
+
         // Build rpc block response
         let block = reth_rpc_types::Block {
             header,
             size: Some(U256::from(size)),
             uncles: Default::default(),
             transactions,
-            withdrawals: Default::default(),
+            withdrawals,
             other: Default::default(),
         };
 
@@ -179,6 +207,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 
     /// Handler for: `eth_getBlockReceipts`
+    /// Returns the receipts of all the transactions of the block, in order, or `None` if the
+    /// block is unknown. The pending block is the latest one.
     #[rpc_method(name = "eth_getBlockReceipts")]
     pub fn get_block_receipts(
         &self,
@@ -202,7 +232,14 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     .get(block_number as usize, &mut working_set.accessory_state())
                     .expect("Block must be set")
             }
+            BlockId::Number(BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized) => {
+                return Err(EthApiError::InvalidParams("unsupported block tag".into()).into());
+            }
             BlockId::Number(block_number) => {
+                let block_number = match block_number {
+                    BlockNumberOrTag::Pending => BlockNumberOrTag::Latest,
+                    block_number => block_number,
+                };
                 match self.get_sealed_block_by_number(Some(block_number), working_set) {
                     Some(block) => block,
                     None => return Ok(None), // if block doesn't exist return null
@@ -210,7 +247,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             }
         };
 
-        let receipts = &block
+        let receipts = block
             .transactions
             .clone()
             .map(|id| {
@@ -228,7 +265,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             })
             .collect::<Vec<_>>();
 
-        Ok(Some(receipts.clone()))
+        Ok(Some(receipts))
     }
 
     /// Handler for: `eth_getBalance`
@@ -327,7 +364,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 
     /// Handler for: `eth_getTransactionCount`
-    #[rpc_method(name = "eth_getTransactionCount")]
+    /// RPC method is moved to sequencer and ethereum-rpc modules, which account for the
+    /// transactions in the mempool under the `pending` tag
     pub fn get_transaction_count(
         &self,
         address: reth_primitives::Address,
@@ -553,6 +591,250 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(receipt)
     }
 
+    /// Handler for: `citrea_getReceiptProof`
+    /// Returns the Merkle proof of the receipt of the transaction against the receipts root
+    /// of its block, or `None` if the transaction is unknown.
+    #[rpc_method(name = "citrea_getReceiptProof")]
+    pub fn get_receipt_proof(
+        &self,
+        hash: reth_primitives::B256,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<ReceiptProof>> {
+        info!("evm module: citrea_getReceiptProof");
+        let mut accessory_state = working_set.accessory_state();
+
+        let Some(tx_number) = self.transaction_hashes.get(&hash, &mut accessory_state) else {
+            return Ok(None);
+        };
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Transaction with known hash must be set");
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .expect("Block number for known transaction must be set");
+
+        let receipts = block
+            .transactions
+            .clone()
+            .map(|number| {
+                self.receipts
+                    .get(number as usize, &mut accessory_state)
+                    .expect("Receipt for known transaction must be set")
+                    .receipt
+                    .with_bloom()
+            })
+            .collect::<Vec<_>>();
+        let index = tx_number - block.transactions.start;
+        let proof = receipt_trie_proof(&receipts, index as usize);
+
+        Ok(Some(ReceiptProof {
+            transaction_hash: hash,
+            block_hash: block.header.hash(),
+            block_number: U64::from(block.header.number),
+            transaction_index: U64::from(index),
+            receipts_root: proof.root,
+            key: proof.key,
+            receipt: proof.value,
+            proof: proof.nodes,
+        }))
+    }
+
+    /// Handler for: `citrea_getContractCreationTx`
+    /// Returns the transaction which created the contract at `address`, including creations
+    /// through factories, or `None` if no contract was created there.
+    #[rpc_method(name = "citrea_getContractCreationTx")]
+    pub fn get_contract_creation_tx(
+        &self,
+        address: reth_primitives::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<ContractCreationResponse>> {
+        info!("evm module: citrea_getContractCreationTx");
+        let mut accessory_state = working_set.accessory_state();
+
+        let Some(tx_hash) = self.contract_creations.get(&address, &mut accessory_state) else {
+            return Ok(None);
+        };
+        // The creating transaction is not in a sealed block yet
+        let Some(tx_number) = self.transaction_hashes.get(&tx_hash, &mut accessory_state) else {
+            return Ok(None);
+        };
+
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Transaction with known hash must be set");
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .expect("Block number for known transaction must be set");
+
+        Ok(Some(ContractCreationResponse {
+            contract_address: address,
+            transaction_hash: tx_hash,
+            from: tx.signer,
+            block_hash: block.header.hash(),
+            block_number: U256::from(block.header.number),
+        }))
+    }
+
+    /// Handler for: `citrea_getContractVerificationData`
+    /// Returns what source verification services need to match the contract at `address`:
+    /// its deployed bytecode and code hash, and, if it was created by a transaction sent
+    /// to no address, the creation input and the constructor arguments following the init code.
+    #[rpc_method(name = "citrea_getContractVerificationData")]
+    pub fn get_contract_verification_data(
+        &self,
+        address: reth_primitives::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<ContractVerificationResponse>> {
+        info!("evm module: citrea_getContractVerificationData");
+
+        let Some(account) = self.accounts.get(&address, working_set) else {
+            return Ok(None);
+        };
+        let deployed_bytecode = self
+            .code
+            .get(&account.info.code_hash, working_set)
+            .unwrap_or_default();
+        if deployed_bytecode.is_empty() {
+            return Ok(None);
+        }
+
+        let creation = self.get_contract_creation_tx(address, working_set)?;
+        // Contracts created by factories don't have their init code in the transaction input
+        let creation_bytecode = creation.as_ref().and_then(|creation| {
+            let mut accessory_state = working_set.accessory_state();
+            let tx_number = self
+                .transaction_hashes
+                .get(&creation.transaction_hash, &mut accessory_state)?;
+            let tx = self
+                .transactions
+                .get(tx_number as usize, &mut accessory_state)
+                .expect("Transaction with known hash must be set");
+            tx.signed_transaction
+                .kind()
+                .is_create()
+                .then(|| tx.signed_transaction.input().clone())
+        });
+        let constructor_arguments = creation_bytecode.as_ref().and_then(|creation_bytecode| {
+            constructor_arguments(creation_bytecode, &deployed_bytecode)
+        });
+
+        Ok(Some(ContractVerificationResponse {
+            contract_address: address,
+            deployed_code_hash: account.info.code_hash,
+            deployed_bytecode,
+            creation_transaction_hash: creation.map(|creation| creation.transaction_hash),
+            creation_bytecode,
+            constructor_arguments,
+        }))
+    }
+
+    /// Handler for: `citrea_getInternalTransactions`
+    /// Returns the internal transactions recorded while executing the transaction, or `None`
+    /// if the transaction is unknown. Nodes which did not enable the internal transactions index
+    /// return an empty list.
+    #[rpc_method(name = "citrea_getInternalTransactions")]
+    pub fn get_internal_transactions(
+        &self,
+        hash: reth_primitives::B256,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Vec<InternalTransaction>>> {
+        info!("evm module: citrea_getInternalTransactions");
+        let mut accessory_state = working_set.accessory_state();
+
+        if self
+            .transaction_hashes
+            .get(&hash, &mut accessory_state)
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.internal_transactions
+                .get(&hash, &mut accessory_state)
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Handler for: `citrea_getAccessHints`
+    /// Returns the keys of the state read by the transactions of the block, which full nodes
+    /// prefetch before executing it, or `None` if the block is unknown. Nodes which did not
+    /// enable the hints with [`enable_access_hints`](crate::enable_access_hints) return an
+    /// empty list.
+    #[rpc_method(name = "citrea_getAccessHints")]
+    pub fn get_access_hints(
+        &self,
+        block_number: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Vec<reth_primitives::Bytes>>> {
+        info!("evm module: citrea_getAccessHints");
+        let mut accessory_state = working_set.accessory_state();
+
+        if block_number >= self.blocks.len(&mut accessory_state) as u64 {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.access_hints
+                .get(&block_number, &mut accessory_state)
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Handler for: `citrea_getTotalSupply`
+    /// Returns the total supply of the native token, the balances minted at genesis and later
+    /// minus the ones burnt.
+    #[rpc_method(name = "citrea_getTotalSupply")]
+    pub fn get_total_supply(&self, working_set: &mut WorkingSet<C>) -> RpcResult<U256> {
+        info!("evm module: citrea_getTotalSupply");
+        Ok(self
+            .total_supply
+            .get(&mut working_set.accessory_state())
+            .unwrap_or_default())
+    }
+
+    /// Handler for: `citrea_getSupplyEvents`
+    /// Returns the mints and burns of the native token in the block, with what triggered them,
+    /// or `None` if the block is unknown. The genesis balances are minted in block 0.
+    #[rpc_method(name = "citrea_getSupplyEvents")]
+    pub fn get_supply_events(
+        &self,
+        block_number: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Vec<SupplyEvent>>> {
+        info!("evm module: citrea_getSupplyEvents");
+        let mut accessory_state = working_set.accessory_state();
+
+        if block_number >= self.blocks.len(&mut accessory_state) as u64 {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.supply_events
+                .get(&block_number, &mut accessory_state)
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// Handler for: `citrea_estimateCompressedSize`
+    /// Returns the size in bytes the L1 fee of a transaction with the given calldata is
+    /// charged for, which is an estimate of its compressed size, on top of its state diff size.
+    /// Zero if the chain does not charge calldata in the L1 fee.
+    #[rpc_method(name = "citrea_estimateCompressedSize")]
+    pub fn estimate_compressed_size(
+        &self,
+        data: reth_primitives::Bytes,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<U64> {
+        info!("evm module: citrea_estimateCompressedSize");
+        let cfg = self.get_chain_config(working_set);
+        Ok(U64::from(cfg.l1_calldata_size(&data)))
+    }
+
     /// Handler for: `eth_call`
     //https://github.com/paradigmxyz/reth/blob/f577e147807a783438a3f16aad968b4396274483/crates/rpc/rpc/src/eth/api/transactions.rs#L502
     //https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-types/src/eth/call.rs#L7
@@ -561,12 +843,26 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         request: reth_rpc_types::TransactionRequest,
         block_number: Option<BlockNumberOrTag>,
-        _state_overrides: Option<reth_rpc_types::state::StateOverride>,
-        _block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         info!("evm module: eth_call");
-        let block_env = match block_number {
+        // Calls on the latest state read through the per block cache. Archival state is not
+        // cached, and neither is the genesis state as the genesis header doesn't commit to it.
+        // Calls overriding the state or the block bypass the cache, which must only hold the
+        // real state.
+        let cached_block_hash = match block_number {
+            _ if state_overrides.is_some() || block_overrides.is_some() => None,
+            None | Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) => self
+                .blocks
+                .last(&mut working_set.accessory_state())
+                .filter(|block| block.header.number > 0)
+                .map(|block| block.header.hash()),
+            _ => None,
+        };
+
+        let mut block_env = match block_number {
             Some(BlockNumberOrTag::Pending) => {
                 self.block_env.get(working_set).unwrap_or_default().clone()
             }
@@ -585,33 +881,495 @@ impl<C: sov_modules_api::Context> Evm<C> {
             }
         };
 
-        let mut tx_env = prepare_call_env(&block_env, request.clone())?;
+        // Identical calls on the latest state within a short window share their result
+        let result_cache_key = cached_block_hash.map(|block_hash| {
+            let request_key = serde_json::to_vec(&(
+                &request,
+                block_env.number,
+                block_env.timestamp,
+                block_env.basefee,
+            ))
+            .expect("Call request must serialize");
+            (block_hash, request_key)
+        });
+        if let Some((block_hash, request_key)) = &result_cache_key {
+            if let Some(result) = cached_call_result(*block_hash, request_key) {
+                return Ok(result);
+            }
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+
+        let mut evm_db: EvmDb<'_, C> = self.get_db(working_set);
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(*block_overrides, &mut block_env, &mut evm_db)?;
+        }
+        if let Some(state_overrides) = state_overrides {
+            apply_state_overrides(state_overrides, &mut evm_db)?;
+        }
+
+        let mut tx_env = prepare_call_env(&block_env, request)?;
 
         // https://github.com/paradigmxyz/reth/issues/6574
         tx_env.nonce = None;
 
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        let inspector = TracingInspector::new(TracingInspectorConfig::all());
+        let result = match cached_block_hash {
+            Some(block_hash) => inspect(
+                CachedDb::new(evm_db, block_hash),
+                cfg_env,
+                block_env.into(),
+                tx_env,
+                inspector,
+            ),
+            None => inspect(evm_db, cfg_env, block_env.into(), tx_env, inspector),
+        };
+        let result = match result {
+            Ok(result) => result.result,
+            Err(err) => {
+                return Err(EthApiError::from(err).into());
+            }
+        };
+
+        let output = ensure_success(result)?;
+        if let Some((block_hash, request_key)) = result_cache_key {
+            cache_call_result(block_hash, request_key, output.clone());
+        }
+        Ok(output)
+    }
+
+    /// Handler for: `debug_traceCall`
+    /// Traces the call, as executed by `eth_call` on the state of the block, with the tracer
+    /// of the options. The state changes of the call are not committed.
+    #[rpc_method(name = "debug_traceCall")]
+    pub fn debug_trace_call(
+        &self,
+        request: reth_rpc_types::TransactionRequest,
+        block_number: Option<BlockNumberOrTag>,
+        opts: Option<GethDebugTracingCallOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GethTrace> {
+        info!("evm module: debug_traceCall");
+        let opts = opts.unwrap_or_default();
+
+        let mut block_env = match block_number {
+            None | Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) => {
+                self.block_env.get(working_set).unwrap_or_default().clone()
+            }
+            Some(BlockNumberOrTag::Safe) | Some(BlockNumberOrTag::Finalized) => {
+                return Err(EthApiError::InvalidParams("unsupported block tag".into()).into());
+            }
+            _ => {
+                let block = match self.get_sealed_block_by_number(block_number, working_set) {
+                    Some(block) => block,
+                    None => return Err(EthApiError::UnknownBlockNumber.into()),
+                };
+
+                working_set.set_archival_version(block.header.number);
+                BlockEnv::from(&block)
+            }
+        };
+
         let cfg = self
             .cfg
             .get(working_set)
             .expect("EVM chain config should be set");
-        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
 
-        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
+        let mut evm_db = self.get_db(working_set);
+        if let Some(block_overrides) = opts.block_overrides {
+            apply_block_overrides(block_overrides, &mut block_env, &mut evm_db)?;
+        }
+        if let Some(state_overrides) = opts.state_overrides {
+            apply_state_overrides(state_overrides, &mut evm_db)?;
+        }
+
+        let mut tx_env = prepare_call_env(&block_env, request)?;
 
-        let result = match inspect(
-            evm_db,
+        // https://github.com/paradigmxyz/reth/issues/6574
+        tx_env.nonce = None;
+
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+        let (trace, _) = trace_transaction(
+            opts.tracing_options,
             cfg_env,
             block_env.into(),
             tx_env,
-            TracingInspector::new(TracingInspectorConfig::all()),
+            &mut evm_db,
+        )?;
+        TraceSizeGuard::new(max_trace_result_size()).check(&trace)?;
+        Ok(trace)
+    }
+
+    /// Handler for: `eth_callMany`
+    /// Simulates the calls of the bundles in order, each on the state left by the previous
+    /// ones, as `eth_call` does. The block override of a bundle only applies to its calls,
+    /// except for the block hashes it overrides. The state changes are not committed.
+    #[rpc_method(name = "eth_callMany")]
+    pub fn call_many(
+        &self,
+        bundles: Vec<CallBundle>,
+        state_context: Option<CallManyContext>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<Vec<CallManyResponse>>> {
+        info!("evm module: eth_callMany");
+        let CallManyContext {
+            block_number,
+            transaction_index,
+        } = state_context.unwrap_or_default();
+
+        let call_count: usize = bundles.iter().map(|bundle| bundle.transactions.len()).sum();
+        if call_count > MAX_CALL_MANY_CALLS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many calls, max. {}",
+                MAX_CALL_MANY_CALLS
+            ))
+            .into());
+        }
+        if matches!(
+            block_number,
+            Some(BlockNumberOrTag::Safe) | Some(BlockNumberOrTag::Finalized)
         ) {
-            Ok(result) => result.result,
-            Err(err) => {
-                return Err(EthApiError::from(err).into());
+            return Err(EthApiError::InvalidParams("unsupported block tag".into()).into());
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+
+        // Transactions of the block executed before the bundles
+        let mut block_txs = None;
+        let block_env = match (block_number, transaction_index) {
+            (None | Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending), None) => {
+                self.block_env.get(working_set).unwrap_or_default().clone()
+            }
+            (_, transaction_index) => {
+                let block_number = match block_number {
+                    Some(BlockNumberOrTag::Pending) => Some(BlockNumberOrTag::Latest),
+                    block_number => block_number,
+                };
+                let block = match self.get_sealed_block_by_number(block_number, working_set) {
+                    Some(block) => block,
+                    None => return Err(EthApiError::UnknownBlockNumber.into()),
+                };
+
+                if let Some(transaction_index) = transaction_index {
+                    let tx_count = block.transactions.end - block.transactions.start;
+                    if transaction_index > tx_count {
+                        return Err(EthApiError::InvalidParams(
+                            "transaction index out of range".into(),
+                        )
+                        .into());
+                    }
+                    let txs: Vec<TransactionSignedEcRecovered> = (block.transactions.start
+                        ..block.transactions.start + transaction_index)
+                        .map(|id| {
+                            self.transactions
+                                .get(id as usize, &mut working_set.accessory_state())
+                                .expect("Transaction must be set")
+                                .into()
+                        })
+                        .collect();
+                    block_txs = Some((txs, block.l1_fee_rate));
+                }
+
+                working_set.set_archival_version(block.header.number);
+                BlockEnv::from(&block)
+            }
+        };
+
+        let mut evm_db = self.get_db(working_set);
+
+        if let Some((txs, l1_fee_rate)) = block_txs {
+            let mut citrea_handler_ext =
+                CitreaHandlerExt::new(l1_fee_rate, cfg.eip6780_selfdestruct, cfg.calldata_l1_fee);
+            executor::execute_multiple_tx(
+                &mut evm_db,
+                block_env.clone(),
+                0,
+                &txs,
+                get_cfg_env(&block_env, cfg.clone(), None),
+                &mut citrea_handler_ext,
+            );
+        }
+
+        let mut results = Vec::with_capacity(bundles.len());
+        for bundle in bundles {
+            let mut block_env = block_env.clone();
+            if let Some(block_override) = bundle.block_override {
+                apply_block_overrides(block_override, &mut block_env, &mut evm_db)?;
+            }
+            let cfg_env = get_cfg_env(&block_env, cfg.clone(), Some(get_cfg_env_template()));
+
+            let mut bundle_results = Vec::with_capacity(bundle.transactions.len());
+            for request in bundle.transactions {
+                let result = prepare_call_env(&block_env, request).and_then(|mut tx_env| {
+                    // https://github.com/paradigmxyz/reth/issues/6574
+                    tx_env.nonce = None;
+
+                    let res = inspect(
+                        &mut evm_db,
+                        cfg_env.clone(),
+                        block_env.clone().into(),
+                        tx_env,
+                        revm::inspectors::NoOpInspector,
+                    )
+                    .map_err(EthApiError::from)?;
+                    // The next calls see the changes of the call, even if it reverted
+                    evm_db.commit(res.state);
+                    ensure_success(res.result)
+                });
+
+                bundle_results.push(match result {
+                    Ok(value) => CallManyResponse {
+                        value: Some(value),
+                        error: None,
+                    },
+                    Err(err) => CallManyResponse {
+                        value: None,
+                        error: Some(err.to_string()),
+                    },
+                });
+            }
+            results.push(bundle_results);
+        }
+        Ok(results)
+    }
+
+    /// Handler for: `eth_simulateV1`
+    /// Simulates blocks of calls on top of the given block, each on the state left by the
+    /// previous ones, after applying their block and state overrides. The state changes are
+    /// not committed.
+    ///
+    /// Simulated blocks follow the chain config: their timestamps are
+    /// `block_timestamp_delta` apart, and their base fee follows EIP-1559 if `validation` is
+    /// set, else it is 0. Their transactions are not returned.
+    #[rpc_method(name = "eth_simulateV1")]
+    pub fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        info!("evm module: eth_simulateV1");
+        let SimulatePayload {
+            block_state_calls,
+            trace_transfers,
+            validation,
+        } = payload;
+
+        if block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many blocks, max. {}",
+                MAX_SIMULATED_BLOCKS
+            ))
+            .into());
+        }
+        let call_count: usize = block_state_calls
+            .iter()
+            .map(|block| block.calls.len())
+            .sum();
+        if call_count > MAX_CALL_MANY_CALLS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many calls, max. {}",
+                MAX_CALL_MANY_CALLS
+            ))
+            .into());
+        }
+
+        let block_number = match block_number {
+            Some(BlockNumberOrTag::Safe) | Some(BlockNumberOrTag::Finalized) => {
+                return Err(EthApiError::InvalidParams("unsupported block tag".into()).into());
             }
+            Some(BlockNumberOrTag::Pending) => Some(BlockNumberOrTag::Latest),
+            block_number => block_number,
         };
+        let base_block = match self.get_sealed_block_by_number(block_number, working_set) {
+            Some(block) => block,
+            None => return Err(EthApiError::UnknownBlockNumber.into()),
+        };
+        let head_number = self
+            .blocks
+            .len(&mut working_set.accessory_state())
+            .saturating_sub(1) as u64;
+        if base_block.header.number < head_number {
+            // State after the base block
+            working_set.set_archival_version(base_block.header.number + 1);
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let mut evm_db = self.get_db(working_set);
+
+        let mut parent = base_block.header;
+        let mut simulated_blocks = Vec::with_capacity(block_state_calls.len());
+        for simulate_block in block_state_calls {
+            let number = parent.number + 1;
+            let mut block_env = BlockEnv {
+                number,
+                coinbase: cfg.coinbase,
+                timestamp: parent.timestamp + cfg.block_timestamp_delta,
+                prevrandao: parent.mix_hash,
+                basefee: if validation {
+                    parent
+                        .next_block_base_fee(cfg.base_fee_params)
+                        .unwrap_or_default()
+                } else {
+                    0
+                },
+                gas_limit: cfg.block_gas_limit_at(number),
+            };
+            if let Some(block_overrides) = simulate_block.block_overrides {
+                apply_block_overrides(block_overrides, &mut block_env, &mut evm_db)?;
+            }
+            if block_env.number <= parent.number {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block number {} is not above {}",
+                    block_env.number, parent.number
+                ))
+                .into());
+            }
+            if block_env.timestamp <= parent.timestamp {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block timestamp {} is not above {}",
+                    block_env.timestamp, parent.timestamp
+                ))
+                .into());
+            }
+            if let Some(state_overrides) = simulate_block.state_overrides {
+                apply_state_overrides(state_overrides, &mut evm_db)?;
+            }
+
+            let template = (!validation).then(get_cfg_env_template);
+            let cfg_env = get_cfg_env(&block_env, cfg.clone(), template);
+
+            let mut gas_used = 0;
+            let mut logs_count = 0;
+            let mut calls = Vec::with_capacity(simulate_block.calls.len());
+            for (index, request) in simulate_block.calls.into_iter().enumerate() {
+                let gas_limit_set = request.gas.is_some();
+                let mut tx_env = prepare_call_env(&block_env, request)?;
+                if !gas_limit_set {
+                    // Calls without a gas limit can use the rest of the block
+                    tx_env.gas_limit = block_env.gas_limit.saturating_sub(gas_used);
+                }
+                if !validation {
+                    // https://github.com/paradigmxyz/reth/issues/6574
+                    tx_env.nonce = None;
+                }
+
+                let mut recorder = SimulationLogRecorder::new(trace_transfers);
+                let res = inspect(
+                    &mut evm_db,
+                    cfg_env.clone(),
+                    block_env.clone().into(),
+                    tx_env,
+                    &mut recorder,
+                )
+                .map_err(EthApiError::from)?;
+                evm_db.commit(res.state);
+
+                let call_gas_used = res.result.gas_used();
+                gas_used += call_gas_used;
+                if gas_used > block_env.gas_limit {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block {} exceeds its gas limit {}",
+                        number, block_env.gas_limit
+                    ))
+                    .into());
+                }
+
+                let logs: Vec<_> = recorder
+                    .into_logs()
+                    .into_iter()
+                    .map(|log| {
+                        logs_count += 1;
+                        LogResponse {
+                            address: log.address,
+                            topics: log.topics,
+                            data: log.data,
+                            block_hash: None,
+                            block_number: Some(U256::from(block_env.number)),
+                            transaction_hash: None,
+                            transaction_index: Some(U256::from(index)),
+                            log_index: Some(U256::from(logs_count - 1)),
+                            removed: false,
+                        }
+                    })
+                    .collect();
+                let (status, return_data, error) = match res.result {
+                    ExecutionResult::Success { output, .. } => (1, output.into_data(), None),
+                    ExecutionResult::Revert { output, .. } => (
+                        0,
+                        output.clone(),
+                        Some(SimulatedCallError {
+                            code: 3,
+                            message: RevertError::new(output).to_string(),
+                        }),
+                    ),
+                    ExecutionResult::Halt { reason, gas_used } => (
+                        0,
+                        Default::default(),
+                        Some(SimulatedCallError {
+                            code: -32015,
+                            message: RpcInvalidTransactionError::halt(reason, gas_used).to_string(),
+                        }),
+                    ),
+                };
+                calls.push(SimulatedCall {
+                    status: U64::from(status),
+                    return_data,
+                    gas_used: U64::from(call_gas_used),
+                    logs,
+                    error,
+                });
+            }
 
-        Ok(ensure_success(result)?)
+            let header = reth_primitives::Header {
+                parent_hash: parent.hash(),
+                ommers_hash: reth_primitives::constants::EMPTY_OMMER_ROOT_HASH,
+                beneficiary: block_env.coinbase,
+                number: block_env.number,
+                timestamp: block_env.timestamp,
+                gas_limit: block_env.gas_limit,
+                gas_used,
+                mix_hash: block_env.prevrandao,
+                base_fee_per_gas: Some(block_env.basefee),
+                ..Default::default()
+            }
+            .seal_slow();
+            // Later blocks can read the hash of the simulated block
+            evm_db.last_block_hashes.set(
+                &U256::from(header.number),
+                &header.hash(),
+                evm_db.working_set,
+            );
+
+            for log in calls.iter_mut().flat_map(|call| call.logs.iter_mut()) {
+                log.block_hash = Some(header.hash());
+            }
+            simulated_blocks.push(SimulatedBlock {
+                number: U64::from(header.number),
+                hash: header.hash(),
+                parent_hash: header.parent_hash,
+                timestamp: U64::from(header.timestamp),
+                gas_limit: U64::from(header.gas_limit),
+                gas_used: U64::from(header.gas_used),
+                miner: header.beneficiary,
+                base_fee_per_gas: U256::from(block_env.basefee),
+                mix_hash: header.mix_hash,
+                calls,
+            });
+            parent = header;
+        }
+        Ok(simulated_blocks)
     }
 
     /// Handler for: `eth_blockNumber`
@@ -716,6 +1474,8 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         let access_list = inspector.into_access_list();
 
+        // The gas is estimated for the transaction carrying the generated access list
+        tx_env.access_list = access_list.clone().into_flattened();
         request.access_list = Some(access_list.clone());
 
         let gas_used = self.estimate_gas_with_env(
@@ -739,6 +1499,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         request: reth_rpc_types::TransactionRequest,
         block_number: Option<BlockNumberOrTag>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U64> {
         info!("evm module: eth_estimateGas");
@@ -769,9 +1530,308 @@ impl<C: sov_modules_api::Context> Evm<C> {
             .expect("EVM chain config should be set");
         let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
 
+        // The overrides are written to the working set, so that every execution of the
+        // estimation reads them
+        if let Some(state_overrides) = state_overrides {
+            apply_state_overrides(state_overrides, &mut self.get_db(working_set))?;
+        }
+
         self.estimate_gas_with_env(request, block_env, cfg_env, &mut tx_env, working_set)
     }
 
+    /// Handler for: `citrea_estimateL1Fee`
+    /// Returns the execution fee, the L1 fee and the total fee the transaction would pay
+    /// if it was included on top of the given block.
+    #[rpc_method(name = "citrea_estimateL1Fee")]
+    pub fn estimate_l1_fee(
+        &self,
+        request: reth_rpc_types::TransactionRequest,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<FeeEstimateResponse> {
+        info!("evm module: citrea_estimateL1Fee");
+        let gas = self.eth_estimate_gas(request.clone(), block_number, None, working_set)?;
+
+        let (block_env, l1_fee_rate) = match block_number {
+            None | Some(BlockNumberOrTag::Pending) | Some(BlockNumberOrTag::Latest) => {
+                let l1_fee_rate = self
+                    .blocks
+                    .last(&mut working_set.accessory_state())
+                    .map(|block| block.l1_fee_rate)
+                    .unwrap_or_default();
+                (
+                    self.block_env.get(working_set).unwrap_or_default(),
+                    l1_fee_rate,
+                )
+            }
+            _ => {
+                let block = match self.get_sealed_block_by_number(block_number, working_set) {
+                    Some(block) => block,
+                    None => return Err(EthApiError::UnknownBlockNumber.into()),
+                };
+
+                working_set.set_archival_version(block.header.number);
+                (BlockEnv::from(&block), block.l1_fee_rate)
+            }
+        };
+
+        let base_fee = U256::from(block_env.basefee);
+        let gas_price = match (request.gas_price, request.max_fee_per_gas) {
+            (Some(gas_price), _) => gas_price,
+            (None, Some(max_fee_per_gas)) => {
+                max_fee_per_gas.min(base_fee + request.max_priority_fee_per_gas.unwrap_or_default())
+            }
+            (None, None) => base_fee,
+        };
+
+        let gas_limit: u64 = gas.to();
+        let mut tx_env = prepare_call_env(&block_env, request)?;
+        tx_env.gas_limit = gas_limit;
+        // https://github.com/paradigmxyz/reth/issues/6574
+        tx_env.nonce = None;
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let eip6780_selfdestruct = cfg.eip6780_selfdestruct;
+        let calldata_size = cfg.l1_calldata_size(&tx_env.data);
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        let evm_db = self.get_db(working_set);
+        let (_, tx_info) = simulate_tx(evm_db, block_env, tx_env, cfg_env, eip6780_selfdestruct)
+            .map_err(EthApiError::from)?;
+
+        let execution_fee = U256::from(gas_limit) * gas_price;
+        let l1_fee = U256::from(tx_info.diff_size + calldata_size) * U256::from(l1_fee_rate);
+        Ok(FeeEstimateResponse {
+            gas,
+            gas_price,
+            execution_fee,
+            l1_fee_rate: U256::from(l1_fee_rate),
+            l1_diff_size: U64::from(tx_info.diff_size),
+            l1_calldata_size: U64::from(calldata_size),
+            l1_fee,
+            total_fee: execution_fee + l1_fee,
+        })
+    }
+
+    /// Handler for: `citrea_getChainStats`
+    /// Returns the activity of the blocks from `from_block` to `to_block`, aggregated into
+    /// consecutive buckets of `granularity` blocks.
+    #[rpc_method(name = "citrea_getChainStats")]
+    pub fn get_chain_stats(
+        &self,
+        from_block: U64,
+        to_block: U64,
+        granularity: U64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<ChainStats>> {
+        info!("evm module: citrea_getChainStats");
+        let from_block: u64 = from_block.to();
+        let granularity: u64 = granularity.to();
+        if granularity == 0 {
+            return Err(EthApiError::InvalidParams("granularity must be positive".into()).into());
+        }
+        let head = self
+            .blocks
+            .len(&mut working_set.accessory_state())
+            .saturating_sub(1) as u64;
+        if from_block > head {
+            return Err(EthApiError::UnknownBlockNumber.into());
+        }
+        let to_block = to_block.to::<u64>().min(head);
+        if to_block < from_block {
+            return Err(EthApiError::InvalidBlockRange.into());
+        }
+        if to_block - from_block >= MAX_CHAIN_STATS_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "query exceeds max block range {}",
+                MAX_CHAIN_STATS_BLOCKS
+            ))
+            .into());
+        }
+
+        let cfg = self.get_chain_config(working_set);
+        let mut stats = vec![];
+        let mut bucket_start = from_block;
+        while bucket_start <= to_block {
+            let bucket_end = to_block.min(bucket_start.saturating_add(granularity - 1));
+            let mut gas_used = 0u64;
+            let mut base_fee_sum = 0u128;
+            let mut tx_count = 0u64;
+            let mut da_bytes = 0u64;
+            let mut da_fees = U256::ZERO;
+            let mut timestamps = (0, 0);
+
+            for number in bucket_start..=bucket_end {
+                let block = self
+                    .blocks
+                    .get(number as usize, &mut working_set.accessory_state())
+                    .expect("Block must be set");
+                if number == bucket_start {
+                    timestamps.0 = block.header.timestamp;
+                }
+                timestamps.1 = block.header.timestamp;
+                gas_used += block.header.gas_used;
+                base_fee_sum += block.header.base_fee_per_gas.unwrap_or_default() as u128;
+                tx_count += block.transactions.end - block.transactions.start;
+
+                for id in block.transactions.clone() {
+                    let tx = self
+                        .transactions
+                        .get(id as usize, &mut working_set.accessory_state())
+                        .expect("Transaction must be set");
+                    let receipt = self
+                        .receipts
+                        .get(id as usize, &mut working_set.accessory_state())
+                        .expect("Receipt for known transaction must be set");
+                    let tx_da_bytes =
+                        receipt.diff_size + cfg.l1_calldata_size(tx.signed_transaction.input());
+                    da_bytes += tx_da_bytes;
+                    da_fees += U256::from(tx_da_bytes) * U256::from(block.l1_fee_rate);
+                }
+            }
+
+            let block_count = (bucket_end - bucket_start + 1) as u128;
+            stats.push(ChainStats {
+                from_block: U64::from(bucket_start),
+                to_block: U64::from(bucket_end),
+                from_timestamp: U64::from(timestamps.0),
+                to_timestamp: U64::from(timestamps.1),
+                gas_used: U64::from(gas_used),
+                avg_base_fee_per_gas: U64::from((base_fee_sum / block_count) as u64),
+                tx_count: U64::from(tx_count),
+                da_bytes: U64::from(da_bytes),
+                da_fees,
+            });
+            bucket_start = bucket_end + 1;
+        }
+
+        Ok(stats)
+    }
+
+    /// Handler for: `citrea_getChainCapabilities`
+    /// Returns the EVM features active in the latest block, so that clients can detect them
+    /// instead of assuming them.
+    #[rpc_method(name = "citrea_getChainCapabilities")]
+    pub fn get_chain_capabilities(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ChainCapabilities> {
+        info!("evm module: citrea_getChainCapabilities");
+        let cfg = self.get_chain_config(working_set);
+        let block_number = self.block_env.get(working_set).unwrap_or_default().number;
+        let spec_id = get_spec_id(cfg.spec.clone(), block_number);
+        let mut precompiles = get_precompiles(spec_id).into_iter().collect::<Vec<_>>();
+        precompiles.sort();
+
+        Ok(ChainCapabilities {
+            protocol_version: U64::from(PROTOCOL_VERSION),
+            spec_id,
+            precompiles,
+            max_code_size: U64::from(cfg.limit_contract_code_size.unwrap_or(MAX_CODE_SIZE)),
+            // Cancun is rejected in genesis, blob transactions are never accepted
+            blob_transactions: SpecId::enabled(spec_id, SpecId::CANCUN),
+            eip6780_selfdestruct: cfg.eip6780_selfdestruct,
+            block_gas_limit: U64::from(cfg.block_gas_limit_at(block_number + 1)),
+            max_txs_per_block: cfg.max_txs_per_block.map(U64::from),
+        })
+    }
+
+    /// Handler for: `citrea_getStateDiff`
+    /// Returns the accounts and storage slots changed by the transactions of the block, with
+    /// their values before and after the block, computed by re-executing them. Only served by
+    /// nodes which enabled it with [`enable_state_diff_rpc`](crate::enable_state_diff_rpc).
+    ///
+    /// Storage slots cleared by a self destruct are not listed unless the transaction also
+    /// accessed them.
+    #[rpc_method(name = "citrea_getStateDiff")]
+    pub fn get_state_diff(
+        &self,
+        block_number: BlockNumberOrTag,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<StateDiff> {
+        info!("evm module: citrea_getStateDiff");
+        if !state_diff_rpc_enabled() {
+            return Err(EthApiError::Unsupported("citrea_getStateDiff is not enabled").into());
+        }
+        if matches!(
+            block_number,
+            BlockNumberOrTag::Pending | BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized
+        ) {
+            return Err(EthApiError::InvalidParams("unsupported block tag".into()).into());
+        }
+
+        let sealed_block = self
+            .get_sealed_block_by_number(Some(block_number), working_set)
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let block_txs: Vec<TransactionSignedEcRecovered> = sealed_block
+            .transactions
+            .clone()
+            .map(|id| {
+                self.transactions
+                    .get(id as usize, &mut working_set.accessory_state())
+                    .expect("Transaction must be set")
+                    .into()
+            })
+            .collect();
+
+        working_set.set_archival_version(sealed_block.header.number);
+        let block_env = BlockEnv::from(&sealed_block);
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM chain config should be set");
+        let eip6780_selfdestruct = cfg.eip6780_selfdestruct;
+        let calldata_l1_fee = cfg.calldata_l1_fee;
+        let cfg_env = get_cfg_env(&block_env, cfg, None);
+        let mut citrea_handler_ext = CitreaHandlerExt::new(
+            sealed_block.l1_fee_rate,
+            eip6780_selfdestruct,
+            calldata_l1_fee,
+        );
+
+        let mut recorder = StateDiffRecorder::new(self.get_db(working_set));
+        executor::execute_multiple_tx(
+            &mut recorder,
+            block_env,
+            0,
+            &block_txs,
+            cfg_env,
+            &mut citrea_handler_ext,
+        );
+        let diff = recorder.into_diff();
+
+        let account_state = |info: revm::primitives::AccountInfo| AccountState {
+            balance: info.balance,
+            nonce: U64::from(info.nonce),
+            code_hash: info.code_hash,
+        };
+        Ok(StateDiff {
+            block_number: U64::from(sealed_block.header.number),
+            accounts: diff
+                .accounts
+                .into_iter()
+                .map(|(address, before, after)| AccountDiff {
+                    address,
+                    before: before.map(account_state),
+                    after: after.map(account_state),
+                })
+                .collect(),
+            storage: diff
+                .storage
+                .into_iter()
+                .map(|(address, slot, before, after)| StorageDiff {
+                    address,
+                    slot,
+                    before,
+                    after,
+                })
+                .collect(),
+        })
+    }
+
     /// Inner gas estimator
     pub(crate) fn estimate_gas_with_env(
         &self,
@@ -983,6 +2043,28 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(self.logs_for_filter(filter, working_set)?)
     }
 
+    /// Returns a page of the logs matching the filter, starting at `cursor`, or at the start of
+    /// the block range of the filter if unset. A page scans at most as many blocks and returns
+    /// at most as many logs as `eth_getLogs` does, the next page starts at its `nextCursor`.
+    ///
+    /// Handler for `citrea_getLogsPage`
+    #[rpc_method(name = "citrea_getLogsPage")]
+    pub fn get_logs_page(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<LogsPage> {
+        info!("evm module: citrea_getLogsPage");
+        Ok(self.logs_page_for_filter(
+            &filter,
+            cursor,
+            max_blocks_per_filter(),
+            max_logs_per_response(),
+            working_set,
+        )?)
+    }
+
     /// Handler for: `eth_getTransactionByHash`
     /// RPC method is moved to sequencer and ethereum-rpc modules
     pub fn get_transaction_by_hash(
@@ -1030,6 +2112,26 @@ impl<C: sov_modules_api::Context> Evm<C> {
         opts: Option<GethDebugTracingOptions>,
         stop_at: Option<usize>,
         working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<GethTrace>> {
+        self.trace_block_transactions_by_number_with_max_size(
+            block_number,
+            opts,
+            stop_at,
+            max_trace_result_size(),
+            working_set,
+        )
+    }
+
+    /// Traces the block txs like [`Self::trace_block_transactions_by_number`], failing if the
+    /// serialized traces to return are larger than `max_size` bytes, 0 for no limit.
+    /// If `stop_at` is set, only the size of the trace of that transaction is counted.
+    pub fn trace_block_transactions_by_number_with_max_size(
+        &self,
+        block_number: u64,
+        opts: Option<GethDebugTracingOptions>,
+        stop_at: Option<usize>,
+        max_size: usize,
+        working_set: &mut WorkingSet<C>,
     ) -> RpcResult<Vec<GethTrace>> {
         let sealed_block = self
             .get_sealed_block_by_number(Some(BlockNumberOrTag::Number(block_number)), working_set)
@@ -1060,6 +2162,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         // TODO: Convert below steps to blocking task like in reth after implementing the semaphores
         let mut traces = Vec::new();
+        let mut size_guard = TraceSizeGuard::new(max_size);
         let mut transactions = block_txs.into_iter().enumerate().peekable();
         let limit = stop_at.unwrap_or(usize::MAX);
         while let Some((index, tx)) = transactions.next() {
@@ -1070,6 +2173,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 tx_env_with_recovered(&tx),
                 &mut evm_db,
             )?;
+            if stop_at.map_or(true, |stop_at| stop_at == index) {
+                size_guard.check(&trace)?;
+            }
             traces.push(trace);
 
             if limit == index {
@@ -1114,10 +2220,48 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 // all of the logs we have in the block
                 let mut all_logs: Vec<LogResponse> = Vec::new();
 
-                self.append_matching_block_logs(working_set, &mut all_logs, &filter, block);
+                self.append_matching_block_logs(
+                    working_set,
+                    &mut all_logs,
+                    &filter,
+                    block,
+                    0,
+                    usize::MAX,
+                );
 
                 Ok(all_logs)
             }
+            FilterBlockOption::Range { .. } => {
+                let (from_block_number, to_block_number) =
+                    self.filter_block_range(&filter, working_set)?;
+                self.get_logs_in_block_range(
+                    working_set,
+                    &filter,
+                    from_block_number,
+                    to_block_number,
+                )
+            }
+        }
+    }
+
+    /// Returns the _inclusive_ block range of the filter, checked against the head block
+    fn filter_block_range(
+        &self,
+        filter: &Filter,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(u64, u64), FilterError> {
+        match filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                let block_number = self
+                    .block_hashes
+                    .get(&block_hash, &mut working_set.accessory_state())
+                    .ok_or_else(|| {
+                        FilterError::EthAPIError(
+                            ProviderError::BlockHashNotFound(block_hash).into(),
+                        )
+                    })?;
+                Ok((block_number, block_number))
+            }
             FilterBlockOption::Range {
                 from_block,
                 to_block,
@@ -1137,18 +2281,88 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     .map(|num| convert_block_number(num, start_block))
                     .transpose()?
                     .flatten();
-                let (from_block_number, to_block_number) =
-                    get_filter_block_range(from, to, start_block);
-                self.get_logs_in_block_range(
-                    working_set,
-                    &filter,
-                    from_block_number,
-                    to_block_number,
-                )
+                Ok(get_filter_block_range(from, to, start_block))
             }
         }
     }
 
+    /// Returns the logs matching the filter from `cursor` on, scanning at most `max_blocks`
+    /// blocks and returning at most `max_logs` logs. The block range of the filter is not
+    /// limited, it is split into pages.
+    pub(crate) fn logs_page_for_filter(
+        &self,
+        filter: &Filter,
+        cursor: Option<LogCursor>,
+        max_blocks: u64,
+        max_logs: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<LogsPage, FilterError> {
+        let (from_block_number, to_block_number) = self.filter_block_range(filter, working_set)?;
+        let (start_block, start_log_index) = match cursor {
+            Some(cursor) => (
+                cursor.block_number.to::<u64>(),
+                cursor.log_index.to::<u64>(),
+            ),
+            None => (from_block_number, 0),
+        };
+        if start_block < from_block_number || start_block > to_block_number {
+            return Err(FilterError::InvalidCursor);
+        }
+        let end_block = to_block_number.min(start_block.saturating_add(max_blocks.max(1) - 1));
+        let max_logs = max_logs.max(1);
+
+        let address_filter: BloomFilter = filter.address.to_bloom_filter();
+        let topics_filter: Vec<BloomFilter> =
+            filter.topics.iter().map(|t| t.to_bloom_filter()).collect();
+
+        let mut logs = Vec::new();
+        for block_number in start_block..=end_block {
+            let block = self
+                .blocks
+                .get(block_number as usize, &mut working_set.accessory_state())
+                .ok_or_else(|| {
+                    FilterError::EthAPIError(
+                        ProviderError::BlockBodyIndicesNotFound(block_number).into(),
+                    )
+                })?;
+
+            let logs_bloom = alloy_primitives::Bloom::from(block.header.logs_bloom.data());
+            if !(matches_address(logs_bloom, &address_filter)
+                && matches_topics(logs_bloom, &topics_filter))
+            {
+                continue;
+            }
+
+            let skip_before = if block_number == start_block {
+                start_log_index
+            } else {
+                0
+            };
+            if let Some(log_index) = self.append_matching_block_logs(
+                working_set,
+                &mut logs,
+                filter,
+                block,
+                skip_before,
+                max_logs,
+            ) {
+                return Ok(LogsPage {
+                    logs,
+                    next_cursor: Some(LogCursor {
+                        block_number: U64::from(block_number),
+                        log_index: U64::from(log_index),
+                    }),
+                });
+            }
+        }
+
+        let next_cursor = (end_block < to_block_number).then(|| LogCursor {
+            block_number: U64::from(end_block + 1),
+            log_index: U64::ZERO,
+        });
+        Ok(LogsPage { logs, next_cursor })
+    }
+
     // https://github.com/paradigmxyz/reth/blob/8892d04a88365ba507f28c3314d99a6b54735d3f/crates/rpc/rpc/src/eth/filter.rs#L423
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
@@ -1162,7 +2376,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         from_block_number: u64,
         to_block_number: u64,
     ) -> Result<Vec<LogResponse>, FilterError> {
-        let max_blocks_per_filter: u64 = DEFAULT_MAX_BLOCKS_PER_FILTER;
+        let max_blocks_per_filter = max_blocks_per_filter();
         if to_block_number - from_block_number >= max_blocks_per_filter {
             return Err(FilterError::QueryExceedsMaxBlocks(max_blocks_per_filter));
         }
@@ -1202,8 +2416,15 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 if matches_address(alloy_logs_bloom, &address_filter)
                     && matches_topics(alloy_logs_bloom, &topics_filter)
                 {
-                    self.append_matching_block_logs(working_set, &mut all_logs, filter, block);
-                    let max_logs_per_response = DEFAULT_MAX_LOGS_PER_RESPONSE;
+                    self.append_matching_block_logs(
+                        working_set,
+                        &mut all_logs,
+                        filter,
+                        block,
+                        0,
+                        usize::MAX,
+                    );
+                    let max_logs_per_response = max_logs_per_response();
                     // size check but only if range is multiple blocks, so we always return all
                     // logs of a single block
                     let is_multi_block_range = from_block_number != to_block_number;
@@ -1217,58 +2438,74 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 
     // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/logs_utils.rs#L21
+    /// Appends the logs of the block matching the filter, skipping the ones with an index
+    /// below `skip_before`. Stops before the first matching log which would make `all_logs`
+    /// longer than `max_logs`, returning its index.
+    ///
+    /// Only the receipts of the block are read, and the transactions of the receipts with
+    /// matching logs.
     fn append_matching_block_logs(
         &self,
         working_set: &mut WorkingSet<C>,
         all_logs: &mut Vec<LogResponse>,
         filter: &Filter,
         block: SealedBlock,
-    ) {
-        // tracks the index of a log in the entire block
-        let mut log_index: u32 = 0;
-
+        skip_before: u64,
+        max_logs: usize,
+    ) -> Option<u64> {
         // TODO: Understand how to handle this
         // TAG - true when the log was removed, due to a chain reorganization. false if its a valid log.
         let removed = false;
 
         let topics = filter.topics.clone();
+        let block_hash = block.header.hash();
         let tx_range = block.transactions;
+        let first_tx_number = tx_range.start;
 
         for i in tx_range {
             let receipt = self
                 .receipts
                 .get(i as usize, &mut working_set.accessory_state())
                 .expect("Transaction must be set");
-            let tx = self
-                .transactions
-                .get(i as usize, &mut working_set.accessory_state())
-                .unwrap();
+            // tracks the index of a log in the entire block
+            let first_log_index = receipt.log_index_start;
             let logs = receipt.receipt.logs;
+            if first_log_index + (logs.len() as u64) <= skip_before {
+                continue;
+            }
 
-            for log in logs.into_iter() {
-                if log_matches_filter(
-                    &log,
-                    filter,
-                    &topics,
-                    &block.header.hash(),
-                    &block.header.number,
-                ) {
-                    let log = LogResponse {
-                        address: log.address,
-                        topics: log.topics,
-                        data: log.data.to_vec().into(),
-                        block_hash: Some(block.header.hash()),
-                        block_number: Some(U256::from(block.header.number)),
-                        transaction_hash: Some(tx.signed_transaction.hash),
-                        transaction_index: Some(U256::from(i)),
-                        log_index: Some(U256::from(log_index)),
-                        removed,
-                    };
-                    all_logs.push(log);
+            let mut tx_hash = None;
+            for (log_index, log) in (first_log_index..).zip(logs) {
+                if log_index < skip_before
+                    || !log_matches_filter(&log, filter, &topics, &block_hash, &block.header.number)
+                {
+                    continue;
                 }
-                log_index += 1;
+                if all_logs.len() >= max_logs {
+                    return Some(log_index);
+                }
+
+                let transaction_hash = *tx_hash.get_or_insert_with(|| {
+                    self.transactions
+                        .get(i as usize, &mut working_set.accessory_state())
+                        .expect("Transaction must be set")
+                        .signed_transaction
+                        .hash
+                });
+                all_logs.push(LogResponse {
+                    address: log.address,
+                    topics: log.topics,
+                    data: log.data.to_vec().into(),
+                    block_hash: Some(block_hash),
+                    block_number: Some(U256::from(block.header.number)),
+                    transaction_hash: Some(transaction_hash),
+                    transaction_index: Some(U256::from(i - first_tx_number)),
+                    log_index: Some(U256::from(log_index)),
+                    removed,
+                });
             }
         }
+        None
     }
 
     /// Helper function to get chain config
@@ -1388,7 +2625,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
     ) -> Option<u64> {
         match block_id {
             BlockNumberOrTag::Earliest => Some(0),
-            BlockNumberOrTag::Latest => self
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => self
                 .blocks
                 .last(&mut working_set.accessory_state())
                 .map(|block| block.header.number),
@@ -1450,6 +2687,95 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 }
 
+impl<C: sov_modules_api::Context> Evm<C>
+where
+    C::Storage: NativeStorage,
+{
+    /// Handler for: `eth_getProof`
+    /// Returns the account and the given storage slots of `address` with their proofs against
+    /// the state root. Proofs can only be generated for the latest block.
+    pub fn get_proof(
+        &self,
+        address: reth_primitives::Address,
+        keys: Vec<reth_primitives::B256>,
+        block_number: Option<BlockNumberOrTag>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AccountProofResponse> {
+        info!("evm module: eth_getProof");
+
+        let curr_block_number = self
+            .blocks
+            .last(&mut working_set.accessory_state())
+            .expect("Head block must be set")
+            .header
+            .number;
+
+        match block_number {
+            Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) | None => {}
+            Some(BlockNumberOrTag::Number(num)) if num == curr_block_number => {}
+            Some(BlockNumberOrTag::Number(num)) if num > curr_block_number => {
+                return Err(EthApiError::UnknownBlockNumber.into());
+            }
+            _ => {
+                return Err(EthApiError::InvalidParams(
+                    "Proofs are only available for the latest block".to_string(),
+                )
+                .into())
+            }
+        }
+
+        // The storage map of the account has the same prefix whether the account exists or not
+        let account = self.accounts.get(&address, working_set).unwrap_or_else(|| {
+            DbAccount::new_with_info(self.accounts.prefix(), address, Default::default())
+        });
+
+        let account_key = StorageKey::new(self.accounts.prefix(), &address, self.accounts.codec());
+        let account_proof = working_set.get_with_proof(account_key);
+
+        let storage_proof = keys
+            .into_iter()
+            .map(|key| {
+                let slot = U256::from_be_bytes(key.0);
+                let slot_key =
+                    StorageKey::new(account.storage.prefix(), &slot, account.storage.codec());
+                let slot_proof = working_set.get_with_proof(slot_key);
+                StorageProofResponse {
+                    key,
+                    value: account.storage.get(&slot, working_set).unwrap_or_default(),
+                    proof: vec![encode_state_proof::<C>(&slot_proof.proof)],
+                    state_key: reth_primitives::Bytes::copy_from_slice(slot_proof.key.as_ref()),
+                    state_value: slot_proof
+                        .value
+                        .map(|value| reth_primitives::Bytes::copy_from_slice(value.value())),
+                }
+            })
+            .collect();
+
+        Ok(AccountProofResponse {
+            address,
+            balance: account.info.balance,
+            code_hash: account.info.code_hash,
+            nonce: U64::from(account.info.nonce),
+            storage_hash: reth_primitives::B256::ZERO,
+            account_proof: vec![encode_state_proof::<C>(&account_proof.proof)],
+            state_key: reth_primitives::Bytes::copy_from_slice(account_proof.key.as_ref()),
+            state_value: account_proof
+                .value
+                .map(|value| reth_primitives::Bytes::copy_from_slice(value.value())),
+            storage_proof,
+        })
+    }
+}
+
+fn encode_state_proof<C: sov_modules_api::Context>(
+    proof: &<C::Storage as Storage>::Proof,
+) -> reth_primitives::Bytes {
+    proof
+        .try_to_vec()
+        .expect("Serialization to vec is infallible")
+        .into()
+}
+
 fn get_cfg_env_template() -> revm::primitives::CfgEnvWithHandlerCfg {
     // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
     let mut cfg_env = revm::primitives::CfgEnvWithHandlerCfg::new_with_spec_id(
@@ -1593,6 +2919,21 @@ fn map_out_of_gas_err<C: sov_modules_api::Context>(
     }
 }
 
+/// Returns the tail of `creation_bytecode` after the last copy of `deployed_bytecode`, which is
+/// where Solidity and Vyper put the ABI encoded constructor arguments.
+/// Returns `None` if the init code doesn't embed the deployed bytecode as is, like when
+/// the contract has immutables.
+fn constructor_arguments(
+    creation_bytecode: &reth_primitives::Bytes,
+    deployed_bytecode: &reth_primitives::Bytes,
+) -> Option<reth_primitives::Bytes> {
+    let end = creation_bytecode
+        .windows(deployed_bytecode.len())
+        .rposition(|window| window == deployed_bytecode.as_ref())?
+        + deployed_bytecode.len();
+    Some(creation_bytecode[end..].to_vec().into())
+}
+
 fn convert_u256_to_u64(u256: reth_primitives::U256) -> Result<u64, TryFromSliceError> {
     let bytes: [u8; 32] = u256.to_be_bytes();
     let bytes: [u8; 8] = bytes[24..].try_into()?;
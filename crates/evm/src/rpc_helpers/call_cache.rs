@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use reth_primitives::{Address, Bytes, B256};
+use revm::primitives::{AccountInfo, Bytecode, U256};
+use revm::Database;
+
+/// Number of entries after which the cache is cleared, to bound its memory usage
+const MAX_CACHE_ENTRIES: usize = 100_000;
+
+/// Number of call results after which the result cache is cleared
+const MAX_CALL_RESULTS: usize = 10_000;
+
+/// How long a call result is served from the cache.
+/// The pending block environment of the sequencer can change without a new block, so
+/// results are not kept for the whole lifetime of the block.
+const CALL_RESULT_TTL: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    static ref CALL_STATE_CACHE: Mutex<CallStateCache> = Mutex::new(CallStateCache::default());
+    static ref CALL_RESULT_CACHE: Mutex<CallResultCache> = Mutex::new(CallResultCache::default());
+}
+
+/// Identifies the state the cache was filled from.
+/// Unit tests run in parallel on top of identical chains with different uncommitted
+/// changes, so there the cache is also scoped to the test thread.
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
+struct CacheKey {
+    block_hash: B256,
+    #[cfg(test)]
+    thread: Option<std::thread::ThreadId>,
+}
+
+impl CacheKey {
+    fn new(block_hash: B256) -> Self {
+        Self {
+            block_hash,
+            #[cfg(test)]
+            thread: Some(std::thread::current().id()),
+        }
+    }
+}
+
+/// EVM state read by RPC simulations on top of the block with `key.block_hash`.
+#[derive(Default)]
+struct CallStateCache {
+    key: CacheKey,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+    code: HashMap<B256, Bytecode>,
+    block_hashes: HashMap<U256, B256>,
+}
+
+impl CallStateCache {
+    fn len(&self) -> usize {
+        self.accounts.len() + self.storage.len() + self.code.len() + self.block_hashes.len()
+    }
+}
+
+/// A [`Database`] which serves reads of the state at `block_hash` from a process wide cache,
+/// and falls back to the wrapped database on misses.
+/// Bursts of `eth_call`s against the latest block then don't hit the versioned store
+/// repeatedly. The cache is invalidated as soon as a call is made on top of another block.
+pub(crate) struct CachedDb<DB> {
+    db: DB,
+    key: CacheKey,
+}
+
+impl<DB: Database> CachedDb<DB> {
+    /// Wraps `db`, which must read the state after the block with `block_hash`.
+    pub(crate) fn new(db: DB, block_hash: B256) -> Self {
+        let key = CacheKey::new(block_hash);
+        let mut cache = CALL_STATE_CACHE.lock().unwrap();
+        if cache.key != key {
+            *cache = CallStateCache {
+                key,
+                ..Default::default()
+            };
+        }
+        Self { db, key }
+    }
+
+    /// Runs `f` on the cache, unless it was invalidated by a call on top of another block.
+    fn with_cache<R>(&self, f: impl FnOnce(&mut CallStateCache) -> Option<R>) -> Option<R> {
+        let mut cache = CALL_STATE_CACHE.lock().unwrap();
+        if cache.key != self.key {
+            return None;
+        }
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            *cache = CallStateCache {
+                key: self.key,
+                ..Default::default()
+            };
+        }
+        f(&mut cache)
+    }
+}
+
+impl<DB: Database> Database for CachedDb<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.with_cache(|cache| cache.accounts.get(&address).cloned()) {
+            return Ok(info);
+        }
+        let info = self.db.basic(address)?;
+        self.with_cache(|cache| cache.accounts.insert(address, info.clone()));
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.with_cache(|cache| cache.code.get(&code_hash).cloned()) {
+            return Ok(code);
+        }
+        let code = self.db.code_by_hash(code_hash)?;
+        self.with_cache(|cache| cache.code.insert(code_hash, code.clone()));
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.with_cache(|cache| cache.storage.get(&(address, index)).copied())
+        {
+            return Ok(value);
+        }
+        let value = self.db.storage(address, index)?;
+        self.with_cache(|cache| cache.storage.insert((address, index), value));
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        if let Some(hash) = self.with_cache(|cache| cache.block_hashes.get(&number).copied()) {
+            return Ok(hash);
+        }
+        let hash = self.db.block_hash(number)?;
+        self.with_cache(|cache| cache.block_hashes.insert(number, hash));
+        Ok(hash)
+    }
+}
+
+/// Results of `eth_call`s made on top of the block with `key.block_hash`, by request.
+#[derive(Default)]
+struct CallResultCache {
+    key: CacheKey,
+    results: HashMap<Vec<u8>, (Instant, Bytes)>,
+}
+
+/// Returns the result of an identical call made on top of the block with `block_hash`
+/// in the last [`CALL_RESULT_TTL`], if any.
+pub(crate) fn cached_call_result(block_hash: B256, request: &[u8]) -> Option<Bytes> {
+    let cache = CALL_RESULT_CACHE.lock().unwrap();
+    if cache.key != CacheKey::new(block_hash) {
+        return None;
+    }
+    cache
+        .results
+        .get(request)
+        .filter(|(inserted_at, _)| inserted_at.elapsed() < CALL_RESULT_TTL)
+        .map(|(_, result)| result.clone())
+}
+
+/// Stores the result of a call made on top of the block with `block_hash`, dropping the
+/// results of calls made on top of other blocks.
+pub(crate) fn cache_call_result(block_hash: B256, request: Vec<u8>, result: Bytes) {
+    let key = CacheKey::new(block_hash);
+    let mut cache = CALL_RESULT_CACHE.lock().unwrap();
+    if cache.key != key || cache.results.len() >= MAX_CALL_RESULTS {
+        *cache = CallResultCache {
+            key,
+            ..Default::default()
+        };
+    }
+    cache.results.insert(request, (Instant::now(), result));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_results_are_scoped_to_block_and_request() {
+        let block_hash = B256::repeat_byte(1);
+        let result = Bytes::from_static(&[1, 2, 3]);
+        cache_call_result(block_hash, b"request".to_vec(), result.clone());
+
+        assert_eq!(cached_call_result(block_hash, b"request"), Some(result));
+        assert_eq!(cached_call_result(block_hash, b"other request"), None);
+        assert_eq!(cached_call_result(B256::repeat_byte(2), b"request"), None);
+
+        // A call on top of a new block invalidates the results of the previous one
+        cache_call_result(B256::repeat_byte(2), b"request".to_vec(), Bytes::new());
+        assert_eq!(cached_call_result(block_hash, b"request"), None);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use std::hash::Hash;
 use std::iter::StepBy;
 use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use alloy_primitives::{Bloom, BloomInput, U64};
 use itertools::EitherOrBoth::*;
@@ -22,6 +23,25 @@ pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 /// The maximum number of headers we read at once when handling a range filter.
 pub const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes? per header this is ~500kb?
 
+static MAX_BLOCKS_PER_FILTER: AtomicU64 = AtomicU64::new(DEFAULT_MAX_BLOCKS_PER_FILTER);
+static MAX_LOGS_PER_RESPONSE: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_LOGS_PER_RESPONSE);
+
+/// Sets the maximum number of blocks scanned and of logs returned by an `eth_getLogs`
+/// request, which are also the size of the pages of `citrea_getLogsPage`. Meant to be called
+/// once at startup.
+pub fn set_log_query_limits(max_blocks: u64, max_logs: usize) {
+    MAX_BLOCKS_PER_FILTER.store(max_blocks.max(1), Ordering::Relaxed);
+    MAX_LOGS_PER_RESPONSE.store(max_logs.max(1), Ordering::Relaxed);
+}
+
+pub(crate) fn max_blocks_per_filter() -> u64 {
+    MAX_BLOCKS_PER_FILTER.load(Ordering::Relaxed)
+}
+
+pub(crate) fn max_logs_per_response() -> usize {
+    MAX_LOGS_PER_RESPONSE.load(Ordering::Relaxed)
+}
+
 /// Helper type to represent a bloom filter used for matching logs.
 #[derive(Default, Debug)]
 pub struct BloomFilter(Vec<Bloom>);
@@ -731,6 +751,9 @@ pub enum FilterError {
     /// There is a maximum number of logs that can be returned in a single eth_getLogs response.
     #[error("query exceeds max results {0}")]
     QueryExceedsMaxResults(usize),
+    /// The cursor of a page of logs is outside of the block range of the filter.
+    #[error("cursor is outside of the block range of the filter")]
+    InvalidCursor,
     /// Error thrown when the eth api returns an error
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
@@ -760,6 +783,10 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 jsonrpsee::types::error::INVALID_PARAMS_CODE,
                 err.to_string(),
             ),
+            err @ FilterError::InvalidCursor => rpc_error_with_code(
+                jsonrpsee::types::error::INVALID_PARAMS_CODE,
+                err.to_string(),
+            ),
         }
     }
 }
@@ -1,9 +1,18 @@
+mod call_cache;
 mod filter;
 mod log_utils;
+mod overrides;
+mod receipt_proof;
 mod responses;
+mod simulate;
 mod tracing_utils;
 
+pub(crate) use call_cache::*;
 pub use filter::*;
 pub use log_utils::*;
+pub(crate) use overrides::*;
+pub(crate) use receipt_proof::*;
 pub use responses::*;
+pub use simulate::*;
 pub(crate) use tracing_utils::*;
+pub use tracing_utils::{set_max_trace_result_size, DEFAULT_MAX_TRACE_RESULT_SIZE};
@@ -0,0 +1,111 @@
+use reth_primitives::U256;
+use reth_rpc_types::state::StateOverride;
+use reth_rpc_types::BlockOverrides;
+use revm::primitives::{Account, AccountInfo, Bytecode, StorageSlot};
+use revm::{Database, DatabaseCommit};
+use sov_modules_api::StateMapAccessor;
+
+use crate::evm::db::EvmDb;
+use crate::evm::primitive_types::BlockEnv;
+use crate::{EthApiError, EthResult};
+
+/// Applies the account overrides of a simulation to the state read through `db`.
+///
+/// The overrides are written to the working set of `db`, which must not be committed.
+pub(crate) fn apply_state_overrides<C: sov_modules_api::Context>(
+    overrides: StateOverride,
+    db: &mut EvmDb<'_, C>,
+) -> EthResult<()> {
+    for (address, account_override) in overrides {
+        let mut info = db.basic(address)?.unwrap_or_default();
+        if let Some(balance) = account_override.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account_override.nonce {
+            info.nonce = nonce.to();
+        }
+        if let Some(code) = account_override.code {
+            let bytecode = Bytecode::new_raw(code);
+            info.code_hash = bytecode.hash_slow();
+            info.code = Some(bytecode);
+        }
+
+        let storage = match (account_override.state, account_override.state_diff) {
+            (Some(_), Some(_)) => {
+                return Err(EthApiError::BothStateAndStateDiffInOverride(address));
+            }
+            (Some(state), None) => {
+                // The storage of the account is replaced, clear it first
+                let mut cleared = Account::from(AccountInfo::default());
+                cleared.mark_touch();
+                cleared.mark_selfdestruct();
+                db.commit([(address, cleared)].into_iter().collect());
+                state
+            }
+            (None, Some(state_diff)) => state_diff,
+            (None, None) => Default::default(),
+        };
+
+        let mut account = Account::from(info);
+        account.mark_touch();
+        account.storage = storage
+            .into_iter()
+            .map(|(slot, value)| {
+                (
+                    U256::from_be_bytes(slot.0),
+                    StorageSlot::new(U256::from_be_bytes(value.0)),
+                )
+            })
+            .collect();
+        db.commit([(address, account)].into_iter().collect());
+    }
+    Ok(())
+}
+
+/// Applies the block overrides of a simulation to `block_env`. Overridden block hashes are
+/// written to the working set of `db`, which must not be committed.
+pub(crate) fn apply_block_overrides<C: sov_modules_api::Context>(
+    overrides: BlockOverrides,
+    block_env: &mut BlockEnv,
+    db: &mut EvmDb<'_, C>,
+) -> EthResult<()> {
+    let BlockOverrides {
+        number,
+        // Replaced by PREVRANDAO since the merge
+        difficulty: _,
+        time,
+        gas_limit,
+        coinbase,
+        random,
+        base_fee,
+        block_hash,
+    } = overrides;
+
+    if let Some(number) = number {
+        block_env.number = number
+            .try_into()
+            .map_err(|_| EthApiError::InvalidParams("block number override too large".into()))?;
+    }
+    if let Some(time) = time {
+        block_env.timestamp = time.to();
+    }
+    if let Some(gas_limit) = gas_limit {
+        block_env.gas_limit = gas_limit.to();
+    }
+    if let Some(coinbase) = coinbase {
+        block_env.coinbase = coinbase;
+    }
+    if let Some(random) = random {
+        block_env.prevrandao = random;
+    }
+    if let Some(base_fee) = base_fee {
+        block_env.basefee = base_fee
+            .try_into()
+            .map_err(|_| EthApiError::InvalidParams("base fee override too large".into()))?;
+    }
+    for (number, hash) in block_hash.unwrap_or_default() {
+        db.last_block_hashes
+            .set(&U256::from(number), &hash, db.working_set);
+    }
+    Ok(())
+}
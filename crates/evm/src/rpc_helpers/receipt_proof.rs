@@ -0,0 +1,107 @@
+//! Merkle proofs of receipts against the receipts root of their block
+
+use alloy_rlp::{Encodable, Header, EMPTY_LIST_CODE};
+use reth_primitives::trie::{HashBuilder, Nibbles};
+use reth_primitives::{Bytes, ReceiptWithBloom, B256};
+
+/// Proof of the receipt at `index` in the receipts trie of a block with `receipts`
+pub(crate) struct ReceiptTrieProof {
+    /// Root of the receipts trie
+    pub(crate) root: B256,
+    /// Key of the receipt, its RLP encoded index
+    pub(crate) key: Bytes,
+    /// Value of the receipt in the trie
+    pub(crate) value: Bytes,
+    /// RLP encoded trie nodes from the root to the receipt
+    pub(crate) nodes: Vec<Bytes>,
+}
+
+/// Builds the receipts trie of a block, the same way its `receiptsRoot` is computed, and
+/// returns the proof of the receipt at `index`.
+pub(crate) fn receipt_trie_proof(receipts: &[ReceiptWithBloom], index: usize) -> ReceiptTrieProof {
+    let key = alloy_rlp::encode(index);
+    let mut hash_builder = HashBuilder::default().with_proof_retainer(vec![Nibbles::unpack(&key)]);
+
+    // Leaves are added in the order of their keys, where the RLP encoding of 0 comes after
+    // the one of 1 to 127
+    let len = receipts.len();
+    for i in 0..len {
+        let leaf_index = adjust_index_for_rlp(i, len);
+        hash_builder.add_leaf(
+            Nibbles::unpack(alloy_rlp::encode(leaf_index)),
+            &trie_encoded_receipt(&receipts[leaf_index]),
+        );
+    }
+
+    let root = hash_builder.root();
+    // Only the nodes on the path to the receipt are retained, ordered from the root down
+    let nodes = hash_builder.take_proofs().into_values().collect();
+    ReceiptTrieProof {
+        root,
+        key: key.into(),
+        value: trie_encoded_receipt(&receipts[index]).into(),
+        nodes,
+    }
+}
+
+const fn adjust_index_for_rlp(i: usize, len: usize) -> usize {
+    if i > 0x7f {
+        i
+    } else if i == 0x7f || i + 1 == len {
+        0
+    } else {
+        i + 1
+    }
+}
+
+/// Encodes the receipt as stored in the trie: its EIP-2718 envelope, without the RLP string
+/// header typed receipts get in their network encoding.
+fn trie_encoded_receipt(receipt: &ReceiptWithBloom) -> Vec<u8> {
+    let mut encoded = vec![];
+    receipt.encode(&mut encoded);
+    if encoded[0] >= EMPTY_LIST_CODE {
+        return encoded;
+    }
+    let mut payload = encoded.as_slice();
+    Header::decode(&mut payload).expect("Receipt encoding must have a valid header");
+    payload.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::proofs::calculate_receipt_root;
+    use reth_primitives::{keccak256, Receipt, TxType};
+
+    use super::*;
+
+    #[test]
+    fn test_receipt_trie_proof_matches_receipts_root() {
+        // More than 128 receipts, so that keys of one and two bytes are in the trie
+        let receipts = (0..130u64)
+            .map(|i| {
+                Receipt {
+                    tx_type: if i % 2 == 0 {
+                        TxType::Legacy
+                    } else {
+                        TxType::Eip1559
+                    },
+                    success: true,
+                    cumulative_gas_used: 21000 * (i + 1),
+                    logs: vec![],
+                }
+                .with_bloom()
+            })
+            .collect::<Vec<_>>();
+        let receipts_root = calculate_receipt_root(&receipts);
+
+        for index in [0, 1, 2, 127, 128, 129] {
+            let proof = receipt_trie_proof(&receipts, index);
+            assert_eq!(proof.root, receipts_root);
+            assert_eq!(keccak256(&proof.nodes[0]), receipts_root);
+            let leaf = proof.nodes.last().unwrap();
+            assert!(leaf
+                .windows(proof.value.len())
+                .any(|window| window == &proof.value[..]));
+        }
+    }
+}
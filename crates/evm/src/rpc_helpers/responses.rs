@@ -1,8 +1,8 @@
 use std::hash::Hash;
 
 use alloy_primitives::Bytes;
-use reth_primitives::{Address, U256};
-use revm::primitives::B256;
+use reth_primitives::{Address, U256, U64};
+use revm::primitives::{SpecId, B256};
 
 /// Ethereum Log emitted by a transaction
 #[derive(Debug, Clone, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
@@ -28,3 +28,277 @@ pub struct LogResponse {
     #[serde(default)]
     pub removed: bool,
 }
+
+/// Position of a log, where a page of `citrea_getLogsPage` resumes
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCursor {
+    /// Number of the block of the log
+    pub block_number: U64,
+    /// Index of the log in the block
+    pub log_index: U64,
+}
+
+/// Page of the logs matching a filter, returned by `citrea_getLogsPage`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    /// Matching logs, in order
+    pub logs: Vec<LogResponse>,
+    /// Where the next page starts, `None` if there are no more logs in the range
+    pub next_cursor: Option<LogCursor>,
+}
+
+/// Transaction which created a contract, returned by `citrea_getContractCreationTx`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreationResponse {
+    /// Address of the created contract
+    pub contract_address: Address,
+    /// Hash of the creating transaction, which may have created the contract through a factory
+    pub transaction_hash: B256,
+    /// Sender of the creating transaction
+    pub from: Address,
+    /// Hash of the block the creating transaction was included in
+    pub block_hash: B256,
+    /// Number of the block the creating transaction was included in
+    pub block_number: U256,
+}
+
+/// Data used to verify the source of a contract, returned by `citrea_getContractVerificationData`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractVerificationResponse {
+    /// Address of the contract
+    pub contract_address: Address,
+    /// Deployed bytecode of the contract
+    pub deployed_bytecode: Bytes,
+    /// Hash of the deployed bytecode
+    pub deployed_code_hash: B256,
+    /// Hash of the transaction which created the contract, unknown for genesis contracts
+    pub creation_transaction_hash: Option<B256>,
+    /// Input of the creating transaction, only known if it directly created the contract
+    pub creation_bytecode: Option<Bytes>,
+    /// Tail of the creation bytecode after the init code, likely the constructor arguments
+    pub constructor_arguments: Option<Bytes>,
+}
+
+/// Fee breakdown of a transaction, returned by `citrea_estimateL1Fee`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimateResponse {
+    /// Estimated gas limit of the transaction
+    pub gas: U64,
+    /// Gas price the execution fee is computed with
+    pub gas_price: U256,
+    /// Max. fee paid for executing the transaction, `gas * gasPrice`
+    pub execution_fee: U256,
+    /// L1 fee rate of the block the estimate is made on
+    pub l1_fee_rate: U256,
+    /// Size of the state diff of the transaction in bytes
+    pub l1_diff_size: U64,
+    /// Estimated compressed size of the calldata of the transaction in bytes, 0 if the chain
+    /// does not charge calldata in the L1 fee
+    pub l1_calldata_size: U64,
+    /// Fee paid for the data posted to the DA layer, `(l1DiffSize + l1CalldataSize) * l1FeeRate`
+    pub l1_fee: U256,
+    /// Sum of the execution and L1 fees
+    pub total_fee: U256,
+}
+
+/// Aggregated activity of a range of blocks, returned by `citrea_getChainStats`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStats {
+    /// First block of the range
+    pub from_block: U64,
+    /// Last block of the range
+    pub to_block: U64,
+    /// Timestamp of the first block
+    pub from_timestamp: U64,
+    /// Timestamp of the last block
+    pub to_timestamp: U64,
+    /// Gas used by the blocks
+    pub gas_used: U64,
+    /// Average base fee per gas of the blocks
+    pub avg_base_fee_per_gas: U64,
+    /// Number of transactions in the blocks
+    pub tx_count: U64,
+    /// Bytes the transactions posted to the DA layer, their state diffs and their estimated
+    /// compressed calldata
+    pub da_bytes: U64,
+    /// L1 fees paid by the transactions
+    pub da_fees: U256,
+}
+
+/// Merkle proof of a receipt against the receipts root of its block, returned by
+/// `citrea_getReceiptProof`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptProof {
+    /// Hash of the transaction
+    pub transaction_hash: B256,
+    /// Hash of the block
+    pub block_hash: B256,
+    /// Number of the block
+    pub block_number: U64,
+    /// Index of the transaction in the block
+    pub transaction_index: U64,
+    /// Receipts root of the block
+    pub receipts_root: B256,
+    /// Key of the receipt in the receipts trie, the RLP encoded transaction index
+    pub key: Bytes,
+    /// Receipt as stored in the receipts trie, its EIP-2718 encoding
+    pub receipt: Bytes,
+    /// RLP encoded trie nodes from the root to the receipt
+    pub proof: Vec<Bytes>,
+}
+
+/// Proof of an account and some of its storage slots, returned by `eth_getProof`
+///
+/// Unlike Ethereum, the state is a single Jellyfish Merkle Tree: the account and each slot
+/// are proven against the state root of the rollup. A proof is the borsh serialization of a
+/// `jmt::proof::SparseMerkleProof` of the `stateKey` and `stateValue` of the entry.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProofResponse {
+    /// Address of the account
+    pub address: Address,
+    /// Balance of the account
+    pub balance: U256,
+    /// Hash of the code of the account
+    pub code_hash: B256,
+    /// Nonce of the account
+    pub nonce: U64,
+    /// Always zero, accounts have no storage trie of their own
+    pub storage_hash: B256,
+    /// Proof of the account, a single element
+    pub account_proof: Vec<Bytes>,
+    /// Key of the account in the state
+    pub state_key: Bytes,
+    /// Value of the account in the state, `None` if the account doesn't exist
+    pub state_value: Option<Bytes>,
+    /// Proofs of the requested storage slots
+    pub storage_proof: Vec<StorageProofResponse>,
+}
+
+/// Proof of a storage slot, part of [`AccountProofResponse`]
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProofResponse {
+    /// Storage slot
+    pub key: B256,
+    /// Value of the slot
+    pub value: U256,
+    /// Proof of the slot, a single element
+    pub proof: Vec<Bytes>,
+    /// Key of the slot in the state
+    pub state_key: Bytes,
+    /// Value of the slot in the state, `None` if the slot is not set
+    pub state_value: Option<Bytes>,
+}
+
+/// EVM features of the chain, returned by `citrea_getChainCapabilities`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainCapabilities {
+    /// Version of the Citrea protocol the node follows
+    pub protocol_version: U64,
+    /// EVM hardfork active in the latest block
+    pub spec_id: SpecId,
+    /// Addresses of the precompiles enabled in the latest block, ascending
+    pub precompiles: Vec<Address>,
+    /// Max. size of contract code in bytes
+    pub max_code_size: U64,
+    /// Whether EIP-4844 blob transactions are supported
+    pub blob_transactions: bool,
+    /// Whether `SELFDESTRUCT` follows EIP-6780
+    pub eip6780_selfdestruct: bool,
+    /// Gas limit of the next block
+    pub block_gas_limit: U64,
+    /// Max. number of transactions in a block, unlimited if not set
+    pub max_txs_per_block: Option<U64>,
+}
+
+/// Accounts and storage slots changed by a block, returned by `citrea_getStateDiff`
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiff {
+    /// Number of the block
+    pub block_number: U64,
+    /// Accounts whose balance, nonce or code changed, by address
+    pub accounts: Vec<AccountDiff>,
+    /// Storage slots whose value changed, by address and slot
+    pub storage: Vec<StorageDiff>,
+}
+
+/// Account before and after a block
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    /// Address of the account
+    pub address: Address,
+    /// Account before the block, `None` if it did not exist
+    pub before: Option<AccountState>,
+    /// Account after the block, `None` if it self destructed
+    pub after: Option<AccountState>,
+}
+
+/// Balance, nonce and code of an account
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// Balance of the account
+    pub balance: U256,
+    /// Nonce of the account
+    pub nonce: U64,
+    /// Hash of the code of the account
+    pub code_hash: B256,
+}
+
+/// Storage slot before and after a block
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDiff {
+    /// Address of the account owning the slot
+    pub address: Address,
+    /// Storage slot
+    pub slot: U256,
+    /// Value before the block
+    pub before: U256,
+    /// Value after the block
+    pub after: U256,
+}
+
+/// Calls simulated in order by `eth_callMany`, each on the state left by the previous ones
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundle {
+    /// Calls of the bundle
+    pub transactions: Vec<reth_rpc_types::TransactionRequest>,
+    /// Overrides of the block environment of the calls
+    pub block_override: Option<reth_rpc_types::BlockOverrides>,
+}
+
+/// State the bundles of `eth_callMany` are simulated on
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyContext {
+    /// Block whose state and environment are used, the latest one if not set
+    pub block_number: Option<reth_primitives::BlockNumberOrTag>,
+    /// Number of transactions of the block executed before the bundles. If not set, the
+    /// bundles are simulated on the state `eth_call` uses for the block.
+    pub transaction_index: Option<u64>,
+}
+
+/// Result of a call of `eth_callMany`
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallManyResponse {
+    /// Output of the call, if it succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Error of the call, if it failed or reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
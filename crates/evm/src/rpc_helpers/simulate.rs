@@ -0,0 +1,241 @@
+use alloy_primitives::b256;
+use reth_primitives::{Address, Bytes, Log, B256, U256, U64};
+use reth_rpc_types::state::StateOverride;
+use reth_rpc_types::{BlockOverrides, TransactionRequest};
+use revm::interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome};
+use revm::{Database, EvmContext, Inspector};
+
+use crate::rpc_helpers::LogResponse;
+
+/// Address the ETH transfers are logged from when `traceTransfers` is set, as specified by
+/// `eth_simulateV1`
+pub const TRANSFER_LOG_ADDRESS: Address = Address::new([0xee; 20]);
+
+/// `keccak256("Transfer(address,address,uint256)")`, the ERC-20 transfer event
+const TRANSFER_EVENT_SIGNATURE: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Blocks simulated by `eth_simulateV1`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Blocks to simulate, in order, each on the state left by the previous ones
+    pub block_state_calls: Vec<SimulateBlock>,
+    /// If set, ETH transfers are returned as ERC-20 transfer logs of [`TRANSFER_LOG_ADDRESS`]
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// If set, the calls are validated like transactions: nonces, balances and base fees
+    /// are checked
+    #[serde(default)]
+    pub validation: bool,
+}
+
+/// A block of `eth_simulateV1`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    /// Overrides of the block environment, applied before the calls
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides of accounts, applied before the calls
+    pub state_overrides: Option<StateOverride>,
+    /// Calls of the block
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// A block simulated by `eth_simulateV1`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// Number of the block
+    pub number: U64,
+    /// Hash of the header of the block
+    pub hash: B256,
+    /// Hash of the parent block
+    pub parent_hash: B256,
+    /// Timestamp of the block
+    pub timestamp: U64,
+    /// Gas limit of the block
+    pub gas_limit: U64,
+    /// Gas used by the calls of the block
+    pub gas_used: U64,
+    /// Fee recipient of the block
+    pub miner: Address,
+    /// Base fee of the block
+    pub base_fee_per_gas: U256,
+    /// PREVRANDAO of the block
+    pub mix_hash: B256,
+    /// Results of the calls of the block
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Result of a call of `eth_simulateV1`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// 1 if the call succeeded, 0 if it reverted or halted
+    pub status: U64,
+    /// Output of the call, or its revert data
+    pub return_data: Bytes,
+    /// Gas used by the call
+    pub gas_used: U64,
+    /// Logs emitted by the call, along with its ETH transfers if `traceTransfers` is set
+    pub logs: Vec<LogResponse>,
+    /// Why the call reverted or halted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a call of `eth_simulateV1`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SimulatedCallError {
+    /// JSON-RPC error code, 3 for reverts and -32015 for halts
+    pub code: i32,
+    /// Error message
+    pub message: String,
+}
+
+/// Inspector recording the logs emitted by a call, along with its ETH transfers as ERC-20
+/// transfer logs if enabled, in execution order.
+///
+/// Logs of calls which are reverted are dropped.
+pub(crate) struct SimulationLogRecorder {
+    trace_transfers: bool,
+    /// Index in `logs` of the first log of each open frame
+    frame_starts: Vec<usize>,
+    logs: Vec<Log>,
+}
+
+impl SimulationLogRecorder {
+    pub(crate) fn new(trace_transfers: bool) -> Self {
+        Self {
+            trace_transfers,
+            frame_starts: vec![],
+            logs: vec![],
+        }
+    }
+
+    /// Logs of the call, empty if it reverted.
+    pub(crate) fn into_logs(self) -> Vec<Log> {
+        self.logs
+    }
+
+    fn transfer_log(from: Address, to: Address, value: U256) -> Log {
+        Log {
+            address: TRANSFER_LOG_ADDRESS,
+            topics: vec![TRANSFER_EVENT_SIGNATURE, from.into_word(), to.into_word()],
+            data: Bytes::from(value.to_be_bytes::<32>().to_vec()),
+        }
+    }
+
+    fn exit_frame(&mut self, success: bool) {
+        let start = self
+            .frame_starts
+            .pop()
+            .expect("Frame must be entered before exiting");
+        if !success {
+            self.logs.truncate(start);
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for SimulationLogRecorder {
+    fn log(&mut self, _context: &mut EvmContext<DB>, log: &revm::primitives::Log) {
+        self.logs.push(log.clone().into());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.frame_starts.push(self.logs.len());
+        let transfers_value = matches!(
+            inputs.context.scheme,
+            CallScheme::Call | CallScheme::CallCode
+        ) && inputs.transfer.value > U256::ZERO;
+        if self.trace_transfers && transfers_value {
+            self.logs.push(Self::transfer_log(
+                inputs.transfer.source,
+                inputs.transfer.target,
+                inputs.transfer.value,
+            ));
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit_frame(outcome.result.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frame_starts.push(self.logs.len());
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let success = outcome.result.result.is_ok();
+        // The created contract is only known once its frame ends
+        if success && self.trace_transfers && inputs.value > U256::ZERO {
+            if let Some(address) = outcome.address {
+                let start = *self.frame_starts.last().expect("Create frame is open");
+                self.logs.insert(
+                    start,
+                    Self::transfer_log(inputs.caller, address, inputs.value),
+                );
+            }
+        }
+        self.exit_frame(success);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if self.trace_transfers && value > U256::ZERO {
+            self.logs.push(Self::transfer_log(contract, target, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_log() {
+        let log = SimulationLogRecorder::transfer_log(
+            Address::with_last_byte(1),
+            Address::with_last_byte(2),
+            U256::from(3),
+        );
+        assert_eq!(
+            log.address,
+            "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                .parse::<Address>()
+                .unwrap()
+        );
+        assert_eq!(
+            log.topics[0],
+            reth_primitives::keccak256("Transfer(address,address,uint256)")
+        );
+        assert_eq!(log.topics[2], B256::with_last_byte(2));
+        assert_eq!(
+            log.data,
+            Bytes::from(U256::from(3).to_be_bytes::<32>().to_vec())
+        );
+    }
+}
@@ -1,9 +1,12 @@
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use reth_primitives::revm::env::{fill_tx_env, fill_tx_env_with_recovered};
 use reth_primitives::revm_primitives::TxEnv;
 use reth_primitives::{TransactionSigned, TransactionSignedEcRecovered, TxHash, U256};
 use reth_rpc_types::trace::geth::{
     FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
-    GethTrace, NoopFrame,
+    GethDefaultTracingOptions, GethTrace, NoopFrame,
 };
 use revm::precompile::{PrecompileSpecId, Precompiles};
 use revm::primitives::db::Database;
@@ -15,6 +18,84 @@ use crate::error::rpc::{EthApiError, EthResult};
 use crate::evm::db::EvmDb;
 use crate::RpcInvalidTransactionError;
 
+/// Default maximum size of the serialized traces returned by a `debug_trace*` request
+pub const DEFAULT_MAX_TRACE_RESULT_SIZE: usize = 64 * 1024 * 1024;
+
+static MAX_TRACE_RESULT_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_TRACE_RESULT_SIZE);
+
+/// Sets the maximum size in bytes of the serialized traces returned by a `debug_trace*`
+/// request, 0 for no limit. Larger traces are rejected instead of being built into a
+/// response, they can still be streamed over WebSocket. Meant to be called once at startup.
+pub fn set_max_trace_result_size(max_size: usize) {
+    MAX_TRACE_RESULT_SIZE.store(max_size, Ordering::Relaxed);
+}
+
+pub(crate) fn max_trace_result_size() -> usize {
+    MAX_TRACE_RESULT_SIZE.load(Ordering::Relaxed)
+}
+
+/// Tracks the serialized size of the traces of a request against a maximum size.
+pub(crate) struct TraceSizeGuard {
+    max_size: usize,
+    remaining: usize,
+}
+
+impl TraceSizeGuard {
+    /// Creates a guard allowing `max_size` bytes of traces, 0 for no limit.
+    pub(crate) fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            remaining: max_size,
+        }
+    }
+
+    /// Adds the serialized size of `trace` to the size of the traces of the request, failing
+    /// if it goes over the maximum size. The trace is not serialized into memory.
+    pub(crate) fn check(&mut self, trace: &GethTrace) -> EthResult<()> {
+        if self.max_size == 0 {
+            return Ok(());
+        }
+        let mut counter = SizeCounter {
+            remaining: self.remaining,
+        };
+        serde_json::to_writer(&mut counter, trace)
+            .map_err(|_| EthApiError::TraceResultTooLarge(self.max_size))?;
+        self.remaining = counter.remaining;
+        Ok(())
+    }
+}
+
+/// Writer discarding its input, failing once more than `remaining` bytes are written
+struct SizeCounter {
+    remaining: usize,
+}
+
+impl io::Write for SizeCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.remaining = self
+            .remaining
+            .checked_sub(buf.len())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "size limit exceeded"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Resolves the deprecated `disableMemory` and `disableReturnData` options of the struct
+/// logger into their `enable*` counterparts, memory and return data being only recorded on
+/// explicit request as in geth.
+fn normalize_struct_log_options(config: &mut GethDefaultTracingOptions) {
+    if config.enable_memory.is_none() {
+        config.enable_memory = Some(config.disable_memory == Some(false));
+    }
+    if config.enable_return_data.is_none() {
+        config.enable_return_data = Some(config.disable_return_data == Some(false));
+    }
+}
+
 pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     opts: GethDebugTracingOptions,
     config_env: CfgEnvWithHandlerCfg,
@@ -23,7 +104,7 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     db: &mut EvmDb<'_, C>,
 ) -> EthResult<(GethTrace, revm::primitives::State)> {
     let GethDebugTracingOptions {
-        config,
+        mut config,
         tracer,
         tracer_config,
         ..
@@ -71,6 +152,7 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     }
 
     // default structlog tracer
+    normalize_struct_log_options(&mut config);
     let inspector_config = TracingInspectorConfig::from_geth_config(&config);
 
     let mut inspector = TracingInspector::new(inspector_config);
@@ -78,9 +160,14 @@ pub(crate) fn trace_transaction<C: sov_modules_api::Context>(
     let res = inspect(db, config_env, block_env, tx_env, &mut inspector)?;
     let gas_used = res.result.gas_used();
     let return_value = res.result.into_output().unwrap_or_default();
-    let frame = inspector
+    let limit = config.limit.unwrap_or_default() as usize;
+    let mut frame = inspector
         .into_geth_builder()
         .geth_traces(gas_used, return_value, config);
+    // Like in geth, a limit of 0 means no limit
+    if limit > 0 {
+        frame.struct_logs.truncate(limit);
+    }
 
     Ok((frame.into(), res.state))
 }
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use reth_primitives::{sign_message, Address, Transaction, TransactionSigned, B256};
+use reth_primitives::{
+    keccak256, sign_message, Address, Bytes, Transaction, TransactionSigned, B256,
+};
 use secp256k1::{PublicKey, SecretKey};
 
 use crate::error::rpc::SignError;
@@ -44,8 +46,65 @@ impl DevSigner {
         ))
     }
 
+    /// Signs `message` prefixed as defined by EIP-191 for `eth_sign`. Returns the signature
+    /// encoded as `r || s || v`, with `v` being 27 or 28.
+    pub fn sign_message(&self, address: Address, message: &[u8]) -> Result<Bytes, SignError> {
+        let signer = self.signers.get(&address).ok_or(SignError::NoAccount)?;
+
+        let signature = sign_message(B256::from_slice(signer.as_ref()), hash_message(message))
+            .map_err(|_| SignError::CouldNotSign)?;
+
+        let mut bytes = Vec::with_capacity(65);
+        bytes.extend_from_slice(&signature.r.to_be_bytes::<32>());
+        bytes.extend_from_slice(&signature.s.to_be_bytes::<32>());
+        bytes.push(27 + signature.odd_y_parity as u8);
+        Ok(bytes.into())
+    }
+
     /// List of signers.
     pub fn signers(&self) -> Vec<Address> {
         self.signers.keys().copied().collect()
     }
 }
+
+/// Hash of `message` signed by `eth_sign`, as defined by EIP-191.
+fn hash_message(message: &[u8]) -> B256 {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(prefixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use reth_primitives::{Signature, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_sign_message_recovers_signer() {
+        let secret_key =
+            SecretKey::from_str("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+                .unwrap();
+        let signer = DevSigner::new(vec![secret_key]);
+        let address = signer.signers()[0];
+
+        let signature = signer.sign_message(address, b"hello").unwrap();
+        assert_eq!(signature.len(), 65);
+        let signature = Signature {
+            r: U256::from_be_slice(&signature[..32]),
+            s: U256::from_be_slice(&signature[32..64]),
+            odd_y_parity: signature[64] == 28,
+        };
+        assert_eq!(
+            signature.recover_signer(hash_message(b"hello")),
+            Some(address)
+        );
+
+        assert!(matches!(
+            signer.sign_message(Address::ZERO, b"hello"),
+            Err(SignError::NoAccount)
+        ));
+    }
+}
@@ -7,6 +7,7 @@ mod caller_contract;
 mod coinbase_contract;
 mod logs_contract;
 mod payable_contract;
+mod self_destructing_constructor_contract;
 mod self_destructor_contract;
 mod simple_storage_contract;
 
@@ -18,6 +19,7 @@ use ethers_core::abi::Abi;
 use ethers_core::types::Bytes;
 pub use logs_contract::LogsContract;
 pub use payable_contract::SimplePayableContract;
+pub use self_destructing_constructor_contract::SelfDestructingConstructorContract;
 pub use self_destructor_contract::SelfDestructorContract;
 pub use simple_storage_contract::SimpleStorageContract;
 
@@ -0,0 +1,49 @@
+use std::any::Any;
+
+use ethers_core::types::Bytes;
+
+use super::{test_data_path, TestContract};
+
+/// Contract whose creation code sets its storage slot 0 to 1, then self destructs
+/// sending its balance to the caller. Its bytecode is assembled by hand:
+///
+/// ```text
+/// PUSH1 0x01 PUSH1 0x00 SSTORE CALLER SELFDESTRUCT
+/// ```
+pub struct SelfDestructingConstructorContract {
+    bytecode: Bytes,
+}
+
+impl Default for SelfDestructingConstructorContract {
+    fn default() -> Self {
+        let contract_data = {
+            let mut path = test_data_path();
+            path.push("SelfDestructingConstructor.bin");
+
+            let contract_data = std::fs::read_to_string(path).unwrap();
+            hex::decode(contract_data).unwrap()
+        };
+
+        Self {
+            bytecode: Bytes::from(contract_data),
+        }
+    }
+}
+
+impl TestContract for SelfDestructingConstructorContract {
+    /// SelfDestructingConstructor bytecode.
+    fn byte_code(&self) -> Bytes {
+        self.bytecode.clone()
+    }
+    /// Dynamically dispatch from trait. Downcast to SelfDestructingConstructorContract.
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    /// Create the default instance of the smart contract.
+    fn default_(&self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
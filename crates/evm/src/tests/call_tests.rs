@@ -1,22 +1,31 @@
 use std::str::FromStr;
+use std::time::Duration;
 
 use alloy_rpc_types::request::{TransactionInput, TransactionRequest};
 use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
-use reth_primitives::{Address, BlockNumberOrTag, Bytes, TransactionKind, U64};
+use reth_primitives::{
+    Address, BlockNumberOrTag, Bytes, TransactionKind, TransactionSignedEcRecovered, B256, U64,
+};
 use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address;
-use sov_modules_api::{Context, Module, StateMapAccessor, StateVecAccessor};
+use sov_modules_api::{Context, Module, StateMapAccessor, StateVecAccessor, WorkingSet};
+use sov_state::storage::StorageKey;
 
 use crate::call::CallMessage;
 use crate::evm::primitive_types::Receipt;
 use crate::smart_contracts::{
-    BlockHashContract, LogsContract, SelfDestructorContract, SimpleStorageContract, TestContract,
+    BlockHashContract, LogsContract, SelfDestructingConstructorContract, SelfDestructorContract,
+    SimpleStorageContract, TestContract,
 };
 use crate::tests::genesis_tests::get_evm;
 use crate::tests::test_signer::TestSigner;
 use crate::tests::DEFAULT_CHAIN_ID;
-use crate::{AccountData, EvmConfig, RlpEvmTransaction};
+use crate::{
+    estimated_compressed_size, AccountData, EvmConfig, InternalTransaction,
+    InternalTransactionKind, RlpEvmTransaction, SimulationOutcome, SupplyEvent, SupplyEventKind,
+    SupplyReference, RECENT_TX_WINDOW,
+};
 
 type C = DefaultContext;
 
@@ -48,7 +57,7 @@ fn call_multiple_test() {
 
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
 
     let set_arg = 999;
     {
@@ -148,7 +157,7 @@ fn call_test() {
     let (evm, mut working_set) = get_evm(&config);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
 
     let set_arg = 999;
     {
@@ -217,7 +226,7 @@ fn failed_transaction_test() {
     let working_set = &mut working_set;
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -266,7 +275,7 @@ fn self_destruct_test() {
     let (evm, mut working_set) = get_evm(&config);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -314,7 +323,7 @@ fn self_destruct_test() {
     assert_eq!(db_contract.keys.len(&mut working_set), 1);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], l1_fee_rate, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -375,6 +384,345 @@ fn self_destruct_test() {
     assert_eq!(db_contract.keys.len(&mut working_set), 0);
 }
 
+#[test]
+fn self_destruct_eip6780_test() {
+    let contract_balance: u64 = 1000000000000000;
+    let die_to_address = Address::with_last_byte(0x42);
+
+    for eip6780_selfdestruct in [false, true] {
+        let (mut config, dev_signer, contract_addr) =
+            get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+        config.eip6780_selfdestruct = eip6780_selfdestruct;
+        let (evm, mut working_set) = get_evm(&config);
+        // Created and destroyed by the same transaction
+        let ephemeral_addr = dev_signer.address().create(4);
+
+        evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 0, None, &mut working_set);
+        {
+            let sender_address = generate_address::<C>("sender");
+            let sequencer_address = generate_address::<C>("sequencer");
+            let context = C::new(sender_address, sequencer_address, 1);
+
+            let rlp_transactions = vec![
+                create_contract_message(&dev_signer, 0, SelfDestructorContract::default()),
+                send_money_to_contract_message(
+                    contract_addr,
+                    &dev_signer,
+                    1,
+                    contract_balance as u128,
+                ),
+                set_selfdestruct_arg_message(contract_addr, &dev_signer, 2, 123),
+                selfdestruct_message(contract_addr, &dev_signer, 3, die_to_address),
+                dev_signer
+                    .sign_default_transaction(
+                        TransactionKind::Create,
+                        SelfDestructingConstructorContract::default()
+                            .byte_code()
+                            .to_vec(),
+                        4,
+                        contract_balance as u128,
+                    )
+                    .unwrap(),
+            ];
+
+            evm.call(
+                CallMessage {
+                    txs: rlp_transactions,
+                },
+                &context,
+                &mut working_set,
+            )
+            .unwrap();
+        }
+        evm.end_soft_confirmation_hook(&mut working_set);
+        evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+        let receipts = evm
+            .receipts
+            .iter(&mut working_set.accessory_state())
+            .collect::<Vec<_>>();
+        assert_eq!(receipts.len(), 5);
+        assert!(receipts.iter().all(|receipt| receipt.receipt.success));
+
+        // The balance is sent in both cases
+        let db_account = evm
+            .accounts
+            .get(&die_to_address, &mut working_set)
+            .expect("die to address should exist");
+        assert_eq!(db_account.info.balance, U256::from(contract_balance));
+
+        let db_contract = evm
+            .accounts
+            .get(&contract_addr, &mut working_set)
+            .expect("contract address should exist");
+        assert_eq!(db_contract.info.balance, U256::from(0));
+        if eip6780_selfdestruct {
+            // The contract was created by an earlier transaction so it is kept
+            assert_ne!(db_contract.info.code_hash, KECCAK_EMPTY);
+            assert_eq!(db_contract.info.nonce, 1);
+            assert_eq!(
+                db_contract.storage.get(&U256::from(0), &mut working_set),
+                Some(U256::from(123))
+            );
+            assert_eq!(db_contract.keys.len(&mut working_set), 1);
+        } else {
+            assert_eq!(db_contract.info.code_hash, KECCAK_EMPTY);
+            assert_eq!(db_contract.info.nonce, 0);
+            assert_eq!(
+                db_contract.storage.get(&U256::from(0), &mut working_set),
+                None
+            );
+            assert_eq!(db_contract.keys.len(&mut working_set), 0);
+        }
+
+        // Accounts created in the same transaction are always deleted
+        let db_ephemeral = evm
+            .accounts
+            .get(&ephemeral_addr, &mut working_set)
+            .expect("ephemeral address should exist");
+        assert_eq!(db_ephemeral.info.balance, U256::from(0));
+        assert_eq!(db_ephemeral.info.code_hash, KECCAK_EMPTY);
+        assert_eq!(db_ephemeral.info.nonce, 0);
+        assert_eq!(
+            db_ephemeral.storage.get(&U256::from(0), &mut working_set),
+            None
+        );
+    }
+}
+
+#[test]
+fn internal_transactions_test() {
+    let contract_balance: u64 = 1000000000000000;
+    let die_to_address = Address::with_last_byte(0x42);
+
+    let (config, dev_signer, contract_addr) =
+        get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+    let (evm, mut working_set) = get_evm(&config);
+    crate::enable_internal_tx_index();
+
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 0, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        let rlp_transactions = vec![
+            create_contract_message(&dev_signer, 0, SelfDestructorContract::default()),
+            send_money_to_contract_message(contract_addr, &dev_signer, 1, contract_balance as u128),
+            selfdestruct_message(contract_addr, &dev_signer, 2, die_to_address),
+        ];
+
+        evm.call(
+            CallMessage {
+                txs: rlp_transactions,
+            },
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let tx_hashes = evm
+        .transactions
+        .iter(&mut working_set.accessory_state())
+        .map(|tx| tx.signed_transaction.hash)
+        .collect::<Vec<_>>();
+
+    // A plain value transfer has no internal transactions
+    assert_eq!(
+        evm.get_internal_transactions(tx_hashes[1], &mut working_set)
+            .unwrap(),
+        Some(vec![])
+    );
+
+    assert_eq!(
+        evm.get_internal_transactions(tx_hashes[2], &mut working_set)
+            .unwrap(),
+        Some(vec![InternalTransaction {
+            kind: InternalTransactionKind::SelfDestruct,
+            from: contract_addr,
+            to: die_to_address,
+            value: U256::from(contract_balance),
+            depth: 0,
+        }])
+    );
+
+    assert_eq!(
+        evm.get_internal_transactions(B256::with_last_byte(1), &mut working_set)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn supply_conservation_test() {
+    let contract_balance: u64 = 1000000000000000;
+    let signer_balance = U256::from_str("100000000000000000000").unwrap();
+
+    let (config, dev_signer, contract_addr) = get_evm_config(signer_balance, None);
+    let (evm, mut working_set) = get_evm(&config);
+
+    assert_eq!(
+        evm.get_supply_events(0, &mut working_set).unwrap(),
+        Some(vec![SupplyEvent {
+            kind: SupplyEventKind::Mint,
+            amount: signer_balance,
+            reference: SupplyReference::Genesis(dev_signer.address()),
+        }])
+    );
+
+    let l1_fee_rate = 1;
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        // Gas and L1 fees are paid to the coinbase, the balance of the contract is burnt
+        // when it self destructs to itself
+        let rlp_transactions = vec![
+            create_contract_message(&dev_signer, 0, SelfDestructorContract::default()),
+            send_money_to_contract_message(contract_addr, &dev_signer, 1, contract_balance as u128),
+            selfdestruct_message(contract_addr, &dev_signer, 2, contract_addr),
+        ];
+
+        evm.call(
+            CallMessage {
+                txs: rlp_transactions,
+            },
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let selfdestruct_hash = evm
+        .transactions
+        .iter(&mut working_set.accessory_state())
+        .map(|tx| tx.signed_transaction.hash)
+        .last()
+        .unwrap();
+    assert_eq!(
+        evm.get_supply_events(1, &mut working_set).unwrap(),
+        Some(vec![SupplyEvent {
+            kind: SupplyEventKind::Burn,
+            amount: U256::from(contract_balance),
+            reference: SupplyReference::L2Tx(selfdestruct_hash),
+        }])
+    );
+    assert_eq!(evm.get_supply_events(2, &mut working_set).unwrap(), None);
+
+    let total_supply = evm.get_total_supply(&mut working_set).unwrap();
+    assert_eq!(total_supply, signer_balance - U256::from(contract_balance));
+
+    let balances = [dev_signer.address(), config.coinbase, contract_addr]
+        .iter()
+        .map(|address| {
+            evm.accounts
+                .get(address, &mut working_set)
+                .map(|account| account.info.balance)
+                .unwrap_or_default()
+        })
+        .fold(U256::ZERO, |sum, balance| sum + balance);
+    assert_eq!(balances, total_supply);
+}
+
+#[test]
+fn access_hints_test() {
+    let (config, dev_signer, contract_addr) =
+        get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+    let (evm, mut working_set) = get_evm(&config);
+    crate::enable_access_hints();
+
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 0, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        // The hints of the calls applying the transactions of a block are merged
+        for txs in [
+            vec![create_contract_message(
+                &dev_signer,
+                0,
+                SimpleStorageContract::default(),
+            )],
+            vec![set_arg_message(contract_addr, &dev_signer, 1, 42)],
+        ] {
+            evm.call(CallMessage { txs }, &context, &mut working_set)
+                .unwrap();
+        }
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let hints = evm.get_access_hints(1, &mut working_set).unwrap().unwrap();
+    let account_key = |address: Address| {
+        let key = StorageKey::new(evm.accounts.prefix(), &address, evm.accounts.codec());
+        Bytes::copy_from_slice(key.as_ref())
+    };
+    assert!(hints.contains(&account_key(dev_signer.address())));
+    assert!(hints.contains(&account_key(contract_addr)));
+
+    let contract = evm.accounts.get(&contract_addr, &mut working_set).unwrap();
+    let slot_key = StorageKey::new(
+        contract.storage.prefix(),
+        &U256::ZERO,
+        contract.storage.codec(),
+    );
+    assert!(hints.contains(&Bytes::copy_from_slice(slot_key.as_ref())));
+
+    assert_eq!(evm.get_access_hints(2, &mut working_set).unwrap(), None);
+}
+
+#[test]
+fn simulation_budget_test() {
+    let (config, dev_signer, contract_addr) =
+        get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+    let (evm, mut working_set) = get_evm(&config);
+
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 0, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        evm.call(
+            CallMessage {
+                txs: vec![create_contract_message(
+                    &dev_signer,
+                    0,
+                    SimpleStorageContract::default(),
+                )],
+            },
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let txs: Vec<TransactionSignedEcRecovered> = [
+        // A transfer to an account without code executes no instruction
+        send_money_to_contract_message(Address::with_last_byte(1), &dev_signer, 1, 1),
+        set_arg_message(contract_addr, &dev_signer, 2, 42),
+    ]
+    .into_iter()
+    .map(|tx| tx.try_into().unwrap())
+    .collect();
+
+    let outcomes = evm.simulate_next_block_txs(&txs, 0, Some(Duration::ZERO), &mut working_set);
+    assert_eq!(
+        outcomes,
+        vec![SimulationOutcome::Succeeded, SimulationOutcome::TimedOut]
+    );
+}
+
 #[test]
 fn test_block_hash_in_evm() {
     let (config, dev_signer, contract_addr) =
@@ -383,7 +731,7 @@ fn test_block_hash_in_evm() {
     let (evm, mut working_set) = get_evm(&config);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -406,7 +754,13 @@ fn test_block_hash_in_evm() {
     for _i in 0..514 {
         // generate 514 more blocks
         let l1_fee_rate = 0;
-        evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], l1_fee_rate, &mut working_set);
+        evm.begin_soft_confirmation_hook(
+            [5u8; 32],
+            &[99u8; 32],
+            l1_fee_rate,
+            None,
+            &mut working_set,
+        );
         evm.end_soft_confirmation_hook(&mut working_set);
         evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
     }
@@ -481,7 +835,7 @@ fn test_block_gas_limit() {
     let (evm, mut working_set) = get_evm(&config);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -528,6 +882,117 @@ fn test_block_gas_limit() {
     );
 }
 
+#[test]
+fn test_block_gas_limit_is_shared_by_calls() {
+    let (config, dev_signer, contract_addr) = get_evm_config(
+        U256::from_str("100000000000000000000").unwrap(),
+        Some(ETHEREUM_BLOCK_GAS_LIMIT),
+    );
+
+    let (evm, mut working_set) = get_evm(&config);
+    let l1_fee_rate = 0;
+
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        // deploy logs contract
+        let mut rlp_transactions = vec![create_contract_message(
+            &dev_signer,
+            0,
+            LogsContract::default(),
+        )];
+
+        for i in 0..10_000 {
+            rlp_transactions.push(publish_event_message(
+                contract_addr,
+                &dev_signer,
+                i + 1,
+                "hello".to_string(),
+            ));
+        }
+
+        // Applied in chunks, like the sequencer does with large blocks
+        for chunk in rlp_transactions.chunks(1000) {
+            evm.call(
+                CallMessage {
+                    txs: chunk.to_vec(),
+                },
+                &context,
+                &mut working_set,
+            )
+            .unwrap();
+        }
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let block = evm
+        .get_block_by_number(Some(BlockNumberOrTag::Latest), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(block.header.gas_limit, U256::from(ETHEREUM_BLOCK_GAS_LIMIT));
+    assert!(block.header.gas_used <= block.header.gas_limit);
+    assert!(
+        block.transactions.hashes().len() < 10_000,
+        "Some transactions should be dropped because of gas limit"
+    );
+}
+
+#[test]
+fn test_included_transactions_are_not_executed_again() {
+    let (config, dev_signer, _) =
+        get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+    let (evm, mut working_set) = get_evm(&config);
+
+    let sender_address = generate_address::<C>("sender");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let context = C::new(sender_address, sequencer_address, 1);
+
+    // Applies a block with the given transactions and returns how many it included
+    let apply_block = |txs: Vec<RlpEvmTransaction>, working_set: &mut WorkingSet<C>| {
+        evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 0, None, working_set);
+        evm.call(CallMessage { txs }, &context, working_set)
+            .unwrap();
+        evm.end_soft_confirmation_hook(working_set);
+        evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+        evm.get_block_by_number(Some(BlockNumberOrTag::Latest), None, working_set)
+            .unwrap()
+            .unwrap()
+            .transactions
+            .hashes()
+            .len()
+    };
+
+    let tx = create_contract_message(&dev_signer, 0, SimpleStorageContract::default());
+    let tx_hash = TransactionSignedEcRecovered::try_from(tx.clone())
+        .unwrap()
+        .hash();
+
+    // A transaction repeated in a block is executed once
+    assert_eq!(
+        apply_block(vec![tx.clone(), tx.clone()], &mut working_set),
+        1
+    );
+    // and is skipped by the later blocks
+    assert_eq!(apply_block(vec![tx], &mut working_set), 0);
+
+    // until its block leaves the window
+    for _ in 2..RECENT_TX_WINDOW {
+        apply_block(vec![], &mut working_set);
+    }
+    assert_eq!(
+        evm.recent_tx_hashes.get(&tx_hash, &mut working_set),
+        Some(1)
+    );
+    apply_block(vec![], &mut working_set);
+    assert_eq!(evm.recent_tx_hashes.get(&tx_hash, &mut working_set), None);
+    assert_eq!(evm.block_tx_hashes.get(&1, &mut working_set), None);
+}
+
 pub fn create_contract_message<T: TestContract>(
     dev_signer: &TestSigner,
     nonce: u64,
@@ -732,13 +1197,25 @@ fn get_evm_config_starting_base_fee(
 
 #[test]
 fn test_l1_fee_success() {
-    fn run_tx(l1_fee_rate: u64, expected_balance: U256, expected_coinbase_balance: U256) {
-        let (config, dev_signer, _) =
+    fn run_tx(
+        l1_fee_rate: u64,
+        calldata_l1_fee: bool,
+        expected_balance: U256,
+        expected_coinbase_balance: U256,
+    ) {
+        let (mut config, dev_signer, _) =
             get_evm_config_starting_base_fee(U256::from_str("1000000").unwrap(), None, 1);
+        config.calldata_l1_fee = calldata_l1_fee;
 
         let (evm, mut working_set) = get_evm(&config);
 
-        evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+        evm.begin_soft_confirmation_hook(
+            [5u8; 32],
+            &[10u8; 32],
+            l1_fee_rate,
+            None,
+            &mut working_set,
+        );
         {
             let sender_address = generate_address::<C>("sender");
             let sequencer_address = generate_address::<C>("sequencer");
@@ -793,8 +1270,19 @@ fn test_l1_fee_success() {
 
     let gas_fee_paid = 114235;
 
-    run_tx(0, U256::from(885765), U256::from(gas_fee_paid));
-    run_tx(1, U256::from(885288), U256::from(gas_fee_paid + 477));
+    run_tx(0, false, U256::from(885765), U256::from(gas_fee_paid));
+    run_tx(1, false, U256::from(885288), U256::from(gas_fee_paid + 477));
+
+    // The compressed calldata is charged on top of the state diff
+    let calldata_size = estimated_compressed_size(&BlockHashContract::default().byte_code());
+    assert!(calldata_size >= 100);
+    run_tx(0, true, U256::from(885765), U256::from(gas_fee_paid));
+    run_tx(
+        1,
+        true,
+        U256::from(885288 - calldata_size),
+        U256::from(gas_fee_paid + 477 + calldata_size),
+    );
 }
 
 #[test]
@@ -805,7 +1293,7 @@ fn test_l1_fee_not_enough_funds() {
     let l1_fee_rate = 10000;
     let (evm, mut working_set) = get_evm(&config);
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], l1_fee_rate, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
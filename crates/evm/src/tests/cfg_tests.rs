@@ -49,3 +49,19 @@ fn spec_id_lookup() {
     assert_eq!(get_spec_id(spec.clone(), 20), SpecId::LONDON);
     assert_eq!(get_spec_id(spec, 25), SpecId::LONDON);
 }
+
+#[test]
+fn block_gas_limit_lookup() {
+    let cfg = EvmChainConfig {
+        block_gas_limit: 100,
+        block_gas_limit_schedule: vec![(10, 200), (20, 300)],
+        ..Default::default()
+    };
+
+    assert_eq!(cfg.block_gas_limit_at(0), 100);
+    assert_eq!(cfg.block_gas_limit_at(9), 100);
+    assert_eq!(cfg.block_gas_limit_at(10), 200);
+    assert_eq!(cfg.block_gas_limit_at(15), 200);
+    assert_eq!(cfg.block_gas_limit_at(20), 300);
+    assert_eq!(cfg.block_gas_limit_at(25), 300);
+}
@@ -30,12 +30,17 @@ lazy_static! {
             .collect(),
         chain_id: 1000,
         block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
+        block_gas_limit_schedule: Default::default(),
         block_timestamp_delta: 2,
         genesis_timestamp: 50,
         coinbase: Address::from([3u8; 20]),
         limit_contract_code_size: Some(5000),
         starting_base_fee: 1000000000,
         base_fee_params: BaseFeeParams::ethereum(),
+        eip6780_selfdestruct: false,
+        l1_mixed_prevrandao: false,
+        calldata_l1_fee: false,
+        max_txs_per_block: Some(1000),
     };
 
     pub(crate) static ref GENESIS_HASH: B256 = B256::from(hex!(
@@ -85,10 +90,15 @@ fn genesis_cfg() {
             spec: vec![(0, SpecId::BERLIN), (1, SpecId::SHANGHAI)],
             chain_id: 1000,
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
+            block_gas_limit_schedule: vec![],
             block_timestamp_delta: 2,
             coinbase: Address::from([3u8; 20]),
             limit_contract_code_size: Some(5000),
             base_fee_params: BaseFeeParams::ethereum(),
+            eip6780_selfdestruct: false,
+            l1_mixed_prevrandao: false,
+            calldata_l1_fee: false,
+            max_txs_per_block: Some(1000),
         }
     );
 }
@@ -112,6 +122,17 @@ fn genesis_empty_spec_defaults_to_shanghai() {
     assert_eq!(cfg.spec, vec![(0, SpecId::SHANGHAI)]);
 }
 
+#[test]
+#[should_panic(expected = "Block gas limit schedule must not decrease the gas limit")]
+fn genesis_cfg_decreasing_block_gas_limit() {
+    get_evm(&EvmConfig {
+        block_gas_limit_schedule: vec![(10, ETHEREUM_BLOCK_GAS_LIMIT / 2)]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    });
+}
+
 #[test]
 #[should_panic(expected = "Cancun is not supported")]
 fn genesis_cfg_cancun() {
@@ -2,9 +2,10 @@ use lazy_static::lazy_static;
 use rand::Rng;
 use reth_primitives::hex_literal::hex;
 use reth_primitives::{
-    Address, Bloom, Bytes, Header, SealedHeader, Signature, TransactionSigned, B256,
+    keccak256, Address, Bloom, Bytes, Header, SealedHeader, Signature, TransactionSigned, B256,
     EMPTY_OMMER_ROOT_HASH, KECCAK_EMPTY, U256,
 };
+use sov_modules_api::hooks::ApplySoftConfirmationError;
 use sov_modules_api::{StateMapAccessor, StateValueAccessor, StateVecAccessor};
 
 use super::genesis_tests::{get_evm, TEST_CONFIG};
@@ -13,7 +14,7 @@ use crate::evm::primitive_types::{
 };
 use crate::tests::genesis_tests::{BENEFICIARY, GENESIS_HASH, GENESIS_STATE_ROOT};
 use crate::tests::DEFAULT_CHAIN_ID;
-use crate::PendingTransaction;
+use crate::{estimated_compressed_size, l1_mixed_prevrandao, EvmConfig, PendingTransaction};
 
 lazy_static! {
     pub(crate) static ref DA_ROOT_HASH: B256 = B256::from([5u8; 32]);
@@ -23,7 +24,13 @@ lazy_static! {
 fn begin_soft_confirmation_hook_creates_pending_block() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
     let l1_fee_rate = 0;
-    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &[10u8; 32],
+        l1_fee_rate,
+        None,
+        &mut working_set,
+    );
     let pending_block = evm.block_env.get(&mut working_set).unwrap();
     assert_eq!(
         pending_block,
@@ -38,6 +45,97 @@ fn begin_soft_confirmation_hook_creates_pending_block() {
     );
 }
 
+#[test]
+fn begin_soft_confirmation_hook_uses_soft_confirmation_timestamp() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let timestamp = TEST_CONFIG.genesis_timestamp + 1000;
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &[10u8; 32],
+        0,
+        Some(timestamp),
+        &mut working_set,
+    );
+    let pending_block = evm.block_env.get(&mut working_set).unwrap();
+    assert_eq!(pending_block.timestamp, timestamp);
+
+    // Never before the parent block
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &[10u8; 32],
+        0,
+        Some(TEST_CONFIG.genesis_timestamp - 1),
+        &mut working_set,
+    );
+    let pending_block = evm.block_env.get(&mut working_set).unwrap();
+    assert_eq!(pending_block.timestamp, TEST_CONFIG.genesis_timestamp);
+}
+
+#[test]
+fn begin_soft_confirmation_hook_mixes_l1_hash_into_prevrandao() {
+    let config = EvmConfig {
+        l1_mixed_prevrandao: true,
+        ..TEST_CONFIG.clone()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &[10u8; 32], 0, None, &mut working_set);
+
+    let mut preimage = DA_ROOT_HASH.to_vec();
+    preimage.extend_from_slice(&1u64.to_be_bytes());
+    let pending_block = evm.block_env.get(&mut working_set).unwrap();
+    assert_eq!(pending_block.prevrandao, keccak256(preimage));
+
+    // Blocks anchored to the same L1 block get different values
+    assert_ne!(
+        l1_mixed_prevrandao(DA_ROOT_HASH.0, 1),
+        l1_mixed_prevrandao(DA_ROOT_HASH.0, 2)
+    );
+}
+
+#[test]
+fn begin_soft_confirmation_hook_follows_block_gas_limit_schedule() {
+    let config = EvmConfig {
+        block_gas_limit_schedule: vec![(1, TEST_CONFIG.block_gas_limit * 2)]
+            .into_iter()
+            .collect(),
+        ..TEST_CONFIG.clone()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &[10u8; 32], 0, None, &mut working_set);
+
+    let pending_block = evm.block_env.get(&mut working_set).unwrap();
+    assert_eq!(pending_block.gas_limit, TEST_CONFIG.block_gas_limit * 2);
+}
+
+#[test]
+fn check_block_tx_count_enforces_max_txs_per_block() {
+    let config = EvmConfig {
+        max_txs_per_block: Some(1),
+        ..TEST_CONFIG.clone()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &[10u8; 32], 0, None, &mut working_set);
+
+    evm.pending_transactions.push(
+        &create_pending_transaction(B256::from([1u8; 32]), 1),
+        &mut working_set,
+    );
+    assert!(evm.check_block_tx_count(&mut working_set).is_ok());
+
+    evm.pending_transactions.push(
+        &create_pending_transaction(B256::from([2u8; 32]), 2),
+        &mut working_set,
+    );
+    assert!(matches!(
+        evm.check_block_tx_count(&mut working_set),
+        Err(ApplySoftConfirmationError::TooManyTransactions {
+            tx_count: 2,
+            max_tx_count: 1
+        })
+    ));
+}
+
 #[test]
 fn end_soft_confirmation_hook_sets_head() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
@@ -49,6 +147,7 @@ fn end_soft_confirmation_hook_sets_head() {
         DA_ROOT_HASH.0,
         GENESIS_STATE_ROOT.as_ref(),
         l1_fee_rate,
+        None,
         &mut working_set,
     );
 
@@ -111,7 +210,13 @@ fn end_soft_confirmation_hook_sets_head() {
 fn end_soft_confirmation_hook_moves_transactions_and_receipts() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
     let l1_fee_rate = 0;
-    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &[10u8; 32], l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &[10u8; 32],
+        l1_fee_rate,
+        None,
+        &mut working_set,
+    );
 
     let tx1 = create_pending_transaction(B256::from([1u8; 32]), 1);
     evm.pending_transactions.push(&tx1, &mut working_set);
@@ -155,6 +260,47 @@ fn end_soft_confirmation_hook_moves_transactions_and_receipts() {
     assert_eq!(evm.pending_transactions.len(&mut working_set), 0);
 }
 
+#[test]
+fn end_soft_confirmation_hook_returns_gas_and_fees() {
+    let config = EvmConfig {
+        calldata_l1_fee: true,
+        ..TEST_CONFIG.clone()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    let l1_fee_rate = 10;
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &[10u8; 32],
+        l1_fee_rate,
+        None,
+        &mut working_set,
+    );
+    let basefee = evm.block_env.get(&mut working_set).unwrap().basefee as u128;
+
+    for index in 1..=2 {
+        let mut tx = create_pending_transaction(B256::from([index as u8; 32]), index);
+        if let reth_primitives::Transaction::Eip1559(tx) =
+            &mut tx.transaction.signed_transaction.transaction
+        {
+            tx.max_fee_per_gas = 2 * basefee;
+        }
+        evm.pending_transactions.push(&tx, &mut working_set);
+    }
+
+    let outcome = evm.end_soft_confirmation_hook(&mut working_set);
+
+    // Both transactions pay the base fee and their priority fee of 3000 for 100 gas, plus
+    // the L1 fee of their calldata. The base fee is burnt.
+    let l1_fee = estimated_compressed_size(&[4u8; 20]) as u128 * 10;
+    assert_eq!(outcome.gas_used, 200);
+    assert_eq!(
+        outcome.fees_collected,
+        2 * ((basefee + 3000) * 100 + l1_fee)
+    );
+    assert_eq!(outcome.sequencer_reward, 2 * (3000 * 100 + l1_fee));
+    assert_eq!(outcome.sequencer_penalty, 0);
+}
+
 fn create_pending_transaction(hash: B256, index: u64) -> PendingTransaction {
     PendingTransaction {
         transaction: TransactionSignedAndRecovered {
@@ -202,6 +348,7 @@ fn finalize_hook_creates_final_block() {
         DA_ROOT_HASH.0,
         GENESIS_STATE_ROOT.as_ref(),
         l1_fee_rate,
+        None,
         &mut working_set,
     );
     evm.pending_transactions.push(
@@ -221,7 +368,13 @@ fn finalize_hook_creates_final_block() {
     assert_eq!(evm.blocks.len(&mut accessory_state), 2);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &root_hash, l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &root_hash,
+        l1_fee_rate,
+        None,
+        &mut working_set,
+    );
 
     let mut accessory_state = working_set.accessory_state();
 
@@ -288,7 +441,13 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
     state_root.copy_from_slice(&GENESIS_STATE_ROOT.0);
     let l1_fee_rate = 0;
 
-    evm.begin_soft_confirmation_hook(DA_ROOT_HASH.0, &state_root, l1_fee_rate, &mut working_set);
+    evm.begin_soft_confirmation_hook(
+        DA_ROOT_HASH.0,
+        &state_root,
+        l1_fee_rate,
+        None,
+        &mut working_set,
+    );
 
     // on block 1, only block 0 exists, so the last block hash should be the genesis hash
     // the others should not exist
@@ -320,6 +479,7 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
             DA_ROOT_HASH.0,
             &random_32_bytes,
             l1_fee_rate,
+            None,
             &mut working_set,
         );
 
@@ -335,6 +495,7 @@ fn begin_soft_confirmation_hook_appends_last_block_hashes() {
         DA_ROOT_HASH.0,
         &random_32_bytes,
         l1_fee_rate,
+        None,
         &mut working_set,
     );
 
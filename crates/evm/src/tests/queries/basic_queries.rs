@@ -3,15 +3,21 @@ use std::str::FromStr;
 
 use alloy_primitives::FixedBytes;
 use alloy_rpc_types::request::{TransactionInput, TransactionRequest};
+use borsh::BorshDeserialize;
 use hex::FromHex;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, U64};
-use reth_rpc_types::{Block, Rich, TransactionReceipt};
-use revm::primitives::{B256, U256};
+use reth_primitives::{keccak256, Address, BlockId, BlockNumberOrTag, Bytes, U64};
+use reth_rpc_types::{Block, BlockTransactions, Rich, TransactionReceipt};
+use revm::primitives::{SpecId, B256, U256};
 use serde_json::json;
+use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_prover_storage_manager::SnapshotManager;
+use sov_state::storage::{StorageKey, StorageProof, StorageValue};
+use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
 
 use crate::smart_contracts::SimpleStorageContract;
-use crate::tests::queries::init_evm;
-use crate::EthApiError;
+use crate::tests::queries::{commit, get_evm_with_storage, init_evm};
+use crate::tests::test_signer::TestSigner;
+use crate::{AccountData, EthApiError, EvmConfig};
 
 #[test]
 fn get_block_by_hash_test() {
@@ -88,6 +94,19 @@ fn get_block_receipts_test() {
         .unwrap();
 
     check_against_third_block_receipts(third_block_receipts);
+
+    // The third block is the head, which is also the pending block
+    for tag in [BlockNumberOrTag::Latest, BlockNumberOrTag::Pending] {
+        let receipts = evm
+            .get_block_receipts(BlockId::Number(tag), &mut working_set)
+            .unwrap()
+            .unwrap();
+        check_against_third_block_receipts(receipts);
+    }
+
+    assert!(evm
+        .get_block_receipts(BlockId::Number(BlockNumberOrTag::Safe), &mut working_set)
+        .is_err());
 }
 
 #[test]
@@ -189,6 +208,71 @@ fn get_transaction_by_block_number_and_index_test() {
     }
 }
 
+#[test]
+fn get_contract_creation_tx_test() {
+    let (evm, mut working_set, dev_signer) = init_evm();
+
+    // The simple storage contract is created by the first transaction of the third block
+    let contract_addr = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let creation_tx = evm
+        .get_transaction_by_block_number_and_index(
+            BlockNumberOrTag::Number(3),
+            U64::from(0),
+            &mut working_set,
+        )
+        .unwrap()
+        .unwrap();
+    let third_block = evm
+        .get_block_by_number(Some(BlockNumberOrTag::Number(3)), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+
+    let result = evm
+        .get_contract_creation_tx(contract_addr, &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.contract_address, contract_addr);
+    assert_eq!(result.transaction_hash, creation_tx.hash);
+    assert_eq!(result.from, dev_signer.address());
+    assert_eq!(result.block_hash, third_block.header.hash.unwrap());
+    assert_eq!(result.block_number, U256::from(3));
+
+    // Not a contract
+    let result = evm.get_contract_creation_tx(dev_signer.address(), &mut working_set);
+    assert_eq!(result, Ok(None));
+}
+
+#[test]
+fn get_contract_verification_data_test() {
+    let (evm, mut working_set, dev_signer) = init_evm();
+
+    let contract_addr = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let deployed_bytecode = evm.get_code(contract_addr, None, &mut working_set).unwrap();
+    let creation_tx = evm
+        .get_transaction_by_block_number_and_index(
+            BlockNumberOrTag::Number(3),
+            U64::from(0),
+            &mut working_set,
+        )
+        .unwrap()
+        .unwrap();
+
+    let result = evm
+        .get_contract_verification_data(contract_addr, &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.deployed_bytecode, deployed_bytecode);
+    assert_eq!(result.deployed_code_hash, keccak256(&deployed_bytecode));
+    assert_eq!(result.creation_transaction_hash, Some(creation_tx.hash));
+    assert_eq!(result.creation_bytecode, Some(creation_tx.input));
+    // The simple storage contract has no constructor arguments
+    assert_eq!(result.constructor_arguments, Some(Bytes::new()));
+
+    // Not a contract
+    let result = evm.get_contract_verification_data(dev_signer.address(), &mut working_set);
+    assert_eq!(result, Ok(None));
+}
+
 #[test]
 fn call_test() {
     let (evm, mut working_set, signer) = init_evm();
@@ -356,7 +440,8 @@ fn check_against_third_block(block: &Rich<Block>) {
             "0x17fa953338b32b30795ccb62f050f1c9bcdd48f4793fb2d6d34290b444841271",
             "0xd7e5b2bce65678b5e1a4430b1320b18a258fd5412e20bd5734f446124a9894e6"
         ],
-        "size": "0x54b"
+        "size": "0x54b",
+        "withdrawals": []
     })).unwrap();
 
     let rich_block: Rich<Block> = Rich {
@@ -568,3 +653,270 @@ fn check_against_third_block_receipts(receipts: Vec<TransactionReceipt>) {
 
     assert_eq!(receipts, test_receipts)
 }
+
+#[test]
+fn get_chain_stats_test() {
+    let (evm, mut working_set, _) = init_evm();
+
+    let head: u64 = evm.block_number(&mut working_set).unwrap().to();
+    let per_block = evm
+        .get_chain_stats(
+            U64::from(0),
+            U64::from(head),
+            U64::from(1),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(per_block.len() as u64, head + 1);
+
+    for stats in &per_block {
+        let block = evm
+            .get_block_by_number(
+                Some(BlockNumberOrTag::Number(stats.from_block.to())),
+                None,
+                &mut working_set,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.from_block, stats.to_block);
+        assert_eq!(U256::from(stats.gas_used), block.header.gas_used);
+        let BlockTransactions::Hashes(hashes) = &block.transactions else {
+            panic!("Block must be returned with transaction hashes");
+        };
+        assert_eq!(stats.tx_count.to::<usize>(), hashes.len());
+    }
+    assert!(per_block.iter().any(|stats| stats.da_bytes > U64::ZERO));
+
+    // A single bucket holds the totals of the per block series
+    let total = evm
+        .get_chain_stats(
+            U64::from(0),
+            U64::from(head + 10),
+            U64::from(100),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(total.len(), 1);
+    assert_eq!(total[0].to_block, U64::from(head));
+    assert_eq!(
+        total[0].tx_count,
+        per_block.iter().map(|stats| stats.tx_count).sum::<U64>()
+    );
+    assert_eq!(
+        total[0].da_fees,
+        per_block.iter().map(|stats| stats.da_fees).sum::<U256>()
+    );
+
+    assert_eq!(
+        evm.get_chain_stats(
+            U64::from(0),
+            U64::from(head),
+            U64::from(0),
+            &mut working_set
+        ),
+        Err(EthApiError::InvalidParams("granularity must be positive".into()).into())
+    );
+    assert_eq!(
+        evm.get_chain_stats(
+            U64::from(head + 1),
+            U64::from(head + 1),
+            U64::from(1),
+            &mut working_set
+        ),
+        Err(EthApiError::UnknownBlockNumber.into())
+    );
+}
+
+#[test]
+fn get_receipt_proof_test() {
+    let (evm, mut working_set, _) = init_evm();
+
+    // Block 2 has 4 transactions
+    let block = evm
+        .get_block_by_number(Some(BlockNumberOrTag::Number(2)), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+    let BlockTransactions::Hashes(hashes) = &block.transactions else {
+        panic!("Block must be returned with transaction hashes");
+    };
+    assert_eq!(hashes.len(), 4);
+
+    for (index, hash) in hashes.iter().enumerate() {
+        let proof = evm
+            .get_receipt_proof(*hash, &mut working_set)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.block_number, U64::from(2));
+        assert_eq!(proof.transaction_index, U64::from(index));
+        assert_eq!(proof.receipts_root, block.header.receipts_root);
+        assert_eq!(keccak256(&proof.proof[0]), block.header.receipts_root);
+    }
+
+    assert_eq!(
+        evm.get_receipt_proof(B256::ZERO, &mut working_set),
+        Ok(None)
+    );
+}
+
+#[test]
+fn get_chain_capabilities_test() {
+    let (evm, mut working_set, _) = init_evm();
+
+    let capabilities = evm.get_chain_capabilities(&mut working_set).unwrap();
+    assert_eq!(
+        capabilities.protocol_version,
+        U64::from(crate::PROTOCOL_VERSION)
+    );
+    assert_eq!(capabilities.spec_id, SpecId::SHANGHAI);
+    // Shanghai has the precompiles at addresses 0x01 to 0x09
+    assert_eq!(
+        capabilities.precompiles,
+        (1..=9u8).map(Address::with_last_byte).collect::<Vec<_>>()
+    );
+    assert_eq!(capabilities.max_code_size, U64::from(0x6000));
+    assert!(!capabilities.blob_transactions);
+    assert_eq!(
+        capabilities.block_gas_limit,
+        U64::from(reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT)
+    );
+    assert_eq!(capabilities.max_txs_per_block, None);
+}
+
+#[test]
+fn get_state_diff_test() {
+    crate::enable_state_diff_rpc();
+    let (evm, mut working_set, signer) = init_evm();
+
+    // The first block deploys the logs contract and calls it twice
+    let diff = evm
+        .get_state_diff(BlockNumberOrTag::Number(1), &mut working_set)
+        .unwrap();
+    working_set.unset_archival_version();
+    assert_eq!(diff.block_number, U64::from(1));
+
+    let sender = diff
+        .accounts
+        .iter()
+        .find(|account| account.address == signer.address())
+        .unwrap();
+    let (before, after) = (
+        sender.before.as_ref().unwrap(),
+        sender.after.as_ref().unwrap(),
+    );
+    assert_eq!(before.nonce, U64::from(0));
+    assert_eq!(after.nonce, U64::from(3));
+    assert!(after.balance < before.balance);
+
+    let contract = diff
+        .accounts
+        .iter()
+        .find(|account| {
+            account.address
+                == Address::from_str("0x819c5497b157177315e1204f52e588b393771719").unwrap()
+        })
+        .unwrap();
+    assert_eq!(contract.before, None);
+    assert_ne!(
+        contract.after.as_ref().unwrap().code_hash,
+        revm::primitives::KECCAK_EMPTY
+    );
+
+    assert_eq!(
+        evm.get_state_diff(BlockNumberOrTag::Pending, &mut working_set),
+        Err(EthApiError::InvalidParams("unsupported block tag".into()).into())
+    );
+}
+
+#[test]
+fn get_proof_test() {
+    type TestStorage = ProverStorage<DefaultStorageSpec, SnapshotManager>;
+
+    let signer = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: signer.address(),
+            balance: U256::from(1000),
+            code_hash: AccountData::empty_code(),
+            code: Bytes::default(),
+            nonce: 3,
+        }],
+        ..Default::default()
+    };
+    let (evm, mut working_set, prover_storage) = get_evm_with_storage(&config);
+    let account = evm
+        .accounts
+        .get(&signer.address(), &mut working_set)
+        .unwrap();
+    account
+        .storage
+        .set(&U256::from(1), &U256::from(42), &mut working_set);
+    let state_root = commit(working_set, prover_storage.clone());
+    let mut working_set = WorkingSet::new(prover_storage);
+
+    let verify = |key: StorageKey, proof: &Bytes, state_value: &Option<Bytes>| {
+        let proof = StorageProof {
+            key,
+            value: state_value
+                .as_ref()
+                .map(|value| StorageValue::from(value.to_vec())),
+            proof: <TestStorage as Storage>::Proof::try_from_slice(proof).unwrap(),
+        };
+        TestStorage::open_proof(state_root, proof).unwrap();
+    };
+
+    let proof = evm
+        .get_proof(
+            signer.address(),
+            vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(proof.balance, U256::from(1000));
+    assert_eq!(proof.nonce, U64::from(3));
+    assert_eq!(proof.code_hash, AccountData::empty_code());
+
+    let account_key = StorageKey::new(
+        evm.accounts.prefix(),
+        &signer.address(),
+        evm.accounts.codec(),
+    );
+    assert_eq!(proof.state_key.as_ref(), account_key.as_ref().as_slice());
+    assert!(proof.state_value.is_some());
+    verify(account_key, &proof.account_proof[0], &proof.state_value);
+
+    let [set_slot, unset_slot] = &proof.storage_proof[..] else {
+        panic!("Both slots must be proven");
+    };
+    assert_eq!(set_slot.value, U256::from(42));
+    assert!(set_slot.state_value.is_some());
+    assert_eq!(unset_slot.value, U256::ZERO);
+    assert!(unset_slot.state_value.is_none());
+    for slot in [set_slot, unset_slot] {
+        let slot_key = StorageKey::new(
+            account.storage.prefix(),
+            &U256::from_be_bytes(slot.key.0),
+            account.storage.codec(),
+        );
+        verify(slot_key, &slot.proof[0], &slot.state_value);
+    }
+
+    // Accounts which don't exist are proven absent
+    let proof = evm
+        .get_proof(Address::ZERO, vec![], None, &mut working_set)
+        .unwrap();
+    assert!(proof.state_value.is_none());
+    assert_eq!(proof.balance, U256::ZERO);
+    let account_key = StorageKey::new(evm.accounts.prefix(), &Address::ZERO, evm.accounts.codec());
+    verify(account_key, &proof.account_proof[0], &proof.state_value);
+
+    assert_eq!(
+        evm.get_proof(
+            signer.address(),
+            vec![],
+            Some(BlockNumberOrTag::Number(1)),
+            &mut working_set
+        ),
+        Err(EthApiError::UnknownBlockNumber.into())
+    );
+}
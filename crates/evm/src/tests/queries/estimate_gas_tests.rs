@@ -7,6 +7,7 @@ use jsonrpsee::core::RpcResult;
 use reth_primitives::hex::ToHexExt;
 use reth_primitives::{AccessList, AccessListItem, Address, BlockNumberOrTag, Bytes, U64};
 use reth_rpc::eth::error::RpcInvalidTransactionError;
+use reth_rpc_types::state::AccountOverride;
 use reth_rpc_types::AccessListWithGasUsed;
 use revm::primitives::U256;
 use sov_modules_api::default_context::DefaultContext;
@@ -45,10 +46,63 @@ fn payable_contract_value_test() {
         other: Default::default(),
     };
 
-    let result = evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), &mut working_set);
+    let result = evm.eth_estimate_gas(
+        tx_req,
+        Some(BlockNumberOrTag::Latest),
+        None,
+        &mut working_set,
+    );
     assert_eq!(result.unwrap(), Uint::from_str("0xab12").unwrap());
 }
 
+#[test]
+fn estimate_l1_fee_test() {
+    let (evm, mut working_set, signer) = init_evm_single_block();
+
+    let tx_req = TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(Address::from_str("0x819c5497b157177315e1204f52e588b393771719").unwrap()), // Address of the payable contract.
+        gas: Some(U256::from(100000)),
+        gas_price: Some(U256::from(100000000)),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        value: Some(U256::from(3100000)),
+        input: TransactionInput {
+            input: None,
+            data: None,
+        },
+        nonce: Some(U64::from(1)),
+        chain_id: Some(U64::from(1u64)),
+        access_list: None,
+        max_fee_per_blob_gas: None,
+        blob_versioned_hashes: None,
+        transaction_type: None,
+        sidecar: None,
+        other: Default::default(),
+    };
+
+    let estimate = evm
+        .estimate_l1_fee(tx_req, Some(BlockNumberOrTag::Latest), &mut working_set)
+        .unwrap();
+
+    assert_eq!(estimate.gas, Uint::from_str("0xab12").unwrap());
+    assert_eq!(estimate.gas_price, U256::from(100000000));
+    assert_eq!(
+        estimate.execution_fee,
+        U256::from(0xab12) * U256::from(100000000)
+    );
+    assert_eq!(estimate.l1_fee_rate, U256::from(1));
+    // Calldata is not charged unless the chain enables it
+    assert_eq!(estimate.l1_calldata_size, U64::ZERO);
+    // The value transfer changes the balances of the sender and the contract
+    assert!(estimate.l1_diff_size > U64::ZERO);
+    assert_eq!(
+        estimate.l1_fee,
+        U256::from(estimate.l1_diff_size.to::<u64>())
+    );
+    assert_eq!(estimate.total_fee, estimate.execution_fee + estimate.l1_fee);
+}
+
 #[test]
 fn test_tx_request_fields_gas() {
     let (evm, mut working_set, signer) = init_evm_single_block();
@@ -78,6 +132,7 @@ fn test_tx_request_fields_gas() {
     let result_contract_call = evm.eth_estimate_gas(
         tx_req_contract_call.clone(),
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -94,6 +149,7 @@ fn test_tx_request_fields_gas() {
     let result_no_sender = evm.eth_estimate_gas(
         tx_req_no_sender,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(result_no_sender.unwrap(), Uint::from_str("0x6601").unwrap());
@@ -107,6 +163,7 @@ fn test_tx_request_fields_gas() {
     let result_no_recipient = evm.eth_estimate_gas(
         tx_req_no_recipient,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -123,6 +180,7 @@ fn test_tx_request_fields_gas() {
     let result_no_gas = evm.eth_estimate_gas(
         tx_req_no_gas,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(result_no_gas.unwrap(), Uint::from_str("0x6601").unwrap());
@@ -136,6 +194,7 @@ fn test_tx_request_fields_gas() {
     let result_no_gas_price = evm.eth_estimate_gas(
         tx_req_no_gas_price,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -152,6 +211,7 @@ fn test_tx_request_fields_gas() {
     let result_no_chain_id = evm.eth_estimate_gas(
         tx_req_no_chain_id,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -168,6 +228,7 @@ fn test_tx_request_fields_gas() {
     let result_invalid_chain_id = evm.eth_estimate_gas(
         tx_req_invalid_chain_id,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -185,6 +246,7 @@ fn test_tx_request_fields_gas() {
     let result_no_blob_versioned_hashes = evm.eth_estimate_gas(
         tx_req_no_blob_versioned_hashes,
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
     assert_eq!(
@@ -198,7 +260,7 @@ fn test_tx_request_fields_gas() {
         ..tx_req_contract_call.clone()
     };
 
-    // TODO: Fix create_access_list returning wrong gas (i.e. returns as if access list is none, rather than the provided argument).
+    // The gas is estimated with the generated access list
     let create_no_access_list_test = evm.create_access_list(
         no_access_list_req,
         Some(BlockNumberOrTag::Latest),
@@ -216,9 +278,7 @@ fn test_tx_request_fields_gas() {
                 .unwrap()]
             }])
             .into(),
-            // This should actually be 0x6e66
-            // See: https://github.com/chainwayxyz/citrea/issues/272
-            gas_used: Uint::from_str("0x6601").unwrap()
+            gas_used: Uint::from_str("0x6e66").unwrap()
         }
     );
 
@@ -239,6 +299,7 @@ fn test_tx_request_fields_gas() {
     let access_list_gas_test = evm.eth_estimate_gas(
         access_list_req.clone(),
         Some(BlockNumberOrTag::Latest),
+        None,
         &mut working_set,
     );
 
@@ -306,7 +367,8 @@ fn test_access_list() {
         other: Default::default(),
     };
 
-    let no_access_list = evm.eth_estimate_gas(tx_req_contract_call.clone(), None, &mut working_set);
+    let no_access_list =
+        evm.eth_estimate_gas(tx_req_contract_call.clone(), None, None, &mut working_set);
     assert_eq!(no_access_list.unwrap(), Uint::from_str("0x788b").unwrap());
 
     let form_access_list =
@@ -323,7 +385,7 @@ fn test_access_list() {
                 .unwrap()]
             }])
             .into(),
-            gas_used: Uint::from_str("0x788b").unwrap()
+            gas_used: Uint::from_str("0x775d").unwrap()
         }
     );
 
@@ -341,7 +403,8 @@ fn test_access_list() {
         ..tx_req_contract_call.clone()
     };
 
-    let with_access_list = evm.eth_estimate_gas(tx_req_with_access_list, None, &mut working_set);
+    let with_access_list =
+        evm.eth_estimate_gas(tx_req_with_access_list, None, None, &mut working_set);
     assert_eq!(with_access_list.unwrap(), Uint::from_str("0x775d").unwrap());
 }
 
@@ -388,7 +451,7 @@ fn test_estimate_gas_with_input(
         ..Default::default()
     };
 
-    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), working_set)
+    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), None, working_set)
 }
 
 fn test_estimate_gas_with_value(
@@ -404,5 +467,33 @@ fn test_estimate_gas_with_value(
         ..Default::default()
     };
 
-    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), working_set)
+    evm.eth_estimate_gas(tx_req, Some(BlockNumberOrTag::Latest), None, working_set)
+}
+
+#[test]
+fn estimate_gas_with_state_overrides_test() {
+    let (evm, mut working_set, _) = init_evm();
+
+    let poor = Address::with_last_byte(0xaa);
+    let tx_req = TransactionRequest {
+        from: Some(poor),
+        to: Some(Address::with_last_byte(0xbb)),
+        value: Some(U256::from(1000)),
+        ..Default::default()
+    };
+
+    let result = evm.eth_estimate_gas(tx_req.clone(), None, None, &mut working_set);
+    assert!(result.is_err());
+
+    let overrides = [(
+        poor,
+        AccountOverride {
+            balance: Some(U256::from(1000)),
+            ..Default::default()
+        },
+    )]
+    .into_iter()
+    .collect();
+    let result = evm.eth_estimate_gas(tx_req, None, Some(overrides), &mut working_set);
+    assert_eq!(result.unwrap(), U64::from(21000));
 }
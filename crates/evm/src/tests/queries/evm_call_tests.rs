@@ -3,13 +3,26 @@ use std::str::FromStr;
 use alloy_rpc_types::request::{TransactionInput, TransactionRequest};
 use hex::FromHex;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockNumberOrTag, Bytes, U64};
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, B256, U64};
 use reth_rpc::eth::error::RpcInvalidTransactionError;
+use reth_rpc_types::state::AccountOverride;
+use reth_rpc_types::trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace,
+};
+use reth_rpc_types::BlockOverrides;
 use revm::primitives::U256;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, WorkingSet};
 
 use super::C;
+use crate::call::CallMessage;
+use crate::rpc_helpers::{
+    CallBundle, CallManyContext, SimulateBlock, SimulatePayload, TraceSizeGuard,
+    TRANSFER_LOG_ADDRESS,
+};
 use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::call_tests::set_arg_message;
 use crate::tests::queries::{init_evm, init_evm_single_block};
 use crate::tests::test_signer::TestSigner;
 use crate::Evm;
@@ -72,7 +85,7 @@ fn test_state_change() {
 
     let random_address = Address::from_str("0x000000000000000000000000000000000000dead").unwrap();
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
 
     let call_result = evm.get_call(
         TransactionRequest {
@@ -98,6 +111,69 @@ fn test_state_change() {
     assert_eq!(balance_1, balance_2);
 }
 
+#[test]
+fn call_after_new_block_reads_new_state() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+
+    let get_request = TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(contract_address),
+        gas: Some(U256::from(100000)),
+        gas_price: Some(U256::from(100000000)),
+        value: None,
+        input: TransactionInput::new(contract.get_call_data().to_vec().into()),
+        ..Default::default()
+    };
+
+    // Fills the call cache for the latest block
+    let call_result = evm.get_call(
+        get_request.clone(),
+        Some(BlockNumberOrTag::Latest),
+        None,
+        None,
+        &mut working_set,
+    );
+    assert_eq!(
+        call_result.unwrap(),
+        Bytes::from_str("0x00000000000000000000000000000000000000000000000000000000000001de")
+            .unwrap()
+    );
+
+    evm.begin_soft_confirmation_hook([12u8; 32], &[101u8; 32], 1, None, &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        evm.call(
+            CallMessage {
+                txs: vec![set_arg_message(contract_address, &signer, 9, 5)],
+            },
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_soft_confirmation_hook(&mut working_set);
+    evm.finalize_hook(&[102u8; 32].into(), &mut working_set.accessory_state());
+
+    let call_result = evm.get_call(
+        get_request,
+        Some(BlockNumberOrTag::Latest),
+        None,
+        None,
+        &mut working_set,
+    );
+    assert_eq!(
+        call_result.unwrap(),
+        Bytes::from_str("0x0000000000000000000000000000000000000000000000000000000000000005")
+            .unwrap()
+    );
+}
+
 #[test]
 fn call_contract_with_value_transfer() {
     let (evm, mut working_set, signer) = init_evm();
@@ -492,3 +568,564 @@ fn gas_price_call_test() {
     assert!(result_high_fees.is_ok());
     working_set.unset_archival_version();
 }
+
+#[test]
+fn debug_trace_call_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let request = |input: Vec<u8>| TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(contract_address),
+        gas: Some(U256::from(100000)),
+        gas_price: Some(U256::from(100000000)),
+        input: TransactionInput::new(input.into()),
+        ..Default::default()
+    };
+    let call_tracer = GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions::default().with_tracer(
+            GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer),
+        ),
+        ..Default::default()
+    };
+
+    let trace = evm
+        .debug_trace_call(
+            request(contract.set_call_data(5).to_vec()),
+            Some(BlockNumberOrTag::Latest),
+            Some(call_tracer.clone()),
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::CallTracer(frame) = trace else {
+        panic!("Expected a call trace, got {:?}", trace);
+    };
+    assert_eq!(frame.from, signer.address());
+    assert_eq!(frame.to, Some(contract_address));
+    assert!(frame.error.is_none());
+
+    // The traced call is not committed
+    let trace = evm
+        .debug_trace_call(
+            request(contract.get_call_data().to_vec()),
+            None,
+            Some(call_tracer),
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::CallTracer(frame) = trace else {
+        panic!("Expected a call trace, got {:?}", trace);
+    };
+    assert_eq!(
+        frame.output,
+        Some(
+            Bytes::from_str("0x00000000000000000000000000000000000000000000000000000000000001de")
+                .unwrap()
+        )
+    );
+
+    // The default tracer logs the opcodes
+    let trace = evm
+        .debug_trace_call(
+            request(contract.get_call_data().to_vec()),
+            Some(BlockNumberOrTag::Latest),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::Default(frame) = trace else {
+        panic!("Expected a struct log trace, got {:?}", trace);
+    };
+    assert!(!frame.failed);
+    assert!(!frame.struct_logs.is_empty());
+    // Memory is only recorded on request
+    assert!(frame.struct_logs.iter().all(|log| log.memory.is_none()));
+
+    // The struct logs are truncated to the limit
+    let limited = GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions {
+            config: GethDefaultTracingOptions {
+                limit: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let trace = evm
+        .debug_trace_call(
+            request(contract.get_call_data().to_vec()),
+            Some(BlockNumberOrTag::Latest),
+            Some(limited),
+            &mut working_set,
+        )
+        .unwrap();
+    let size = serde_json::to_vec(&trace).unwrap().len();
+    let GethTrace::Default(frame) = &trace else {
+        panic!("Expected a struct log trace, got {:?}", trace);
+    };
+    assert_eq!(frame.struct_logs.len(), 2);
+
+    // Traces are checked against the size limit without being serialized
+    assert!(TraceSizeGuard::new(0).check(&trace).is_ok());
+    assert!(TraceSizeGuard::new(size).check(&trace).is_ok());
+    assert!(TraceSizeGuard::new(size - 1).check(&trace).is_err());
+    let mut guard = TraceSizeGuard::new(size * 2);
+    assert!(guard.check(&trace).is_ok());
+    assert!(guard.check(&trace).is_ok());
+    assert!(guard.check(&trace).is_err());
+
+    assert!(evm
+        .debug_trace_call(
+            request(contract.get_call_data().to_vec()),
+            Some(BlockNumberOrTag::Number(100)),
+            None,
+            &mut working_set,
+        )
+        .is_err());
+}
+
+#[test]
+fn call_with_state_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let request = TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(contract_address),
+        gas: Some(U256::from(100000)),
+        input: TransactionInput::new(contract.get_call_data().to_vec().into()),
+        ..Default::default()
+    };
+    let overrides = || {
+        Some(
+            [(
+                contract_address,
+                AccountOverride {
+                    state_diff: Some(
+                        [(B256::ZERO, B256::with_last_byte(7))]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        )
+    };
+
+    let stored = evm
+        .get_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(stored, Bytes::from(U256::from(478).to_be_bytes_vec()));
+
+    // The overridden storage is read instead of the cached result of the same call
+    let overridden = evm
+        .get_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            overrides(),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(overridden, Bytes::from(U256::from(7).to_be_bytes_vec()));
+
+    let trace = evm
+        .debug_trace_call(
+            request,
+            Some(BlockNumberOrTag::Latest),
+            Some(GethDebugTracingCallOptions {
+                tracing_options: GethDebugTracingOptions::default().with_tracer(
+                    GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer),
+                ),
+                state_overrides: overrides(),
+                ..Default::default()
+            }),
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::CallTracer(frame) = trace else {
+        panic!("Expected a call trace, got {:?}", trace);
+    };
+    assert_eq!(
+        frame.output,
+        Some(Bytes::from(U256::from(7).to_be_bytes_vec()))
+    );
+}
+
+#[test]
+fn call_with_block_overrides_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    // Returns the number, timestamp, coinbase, prevrandao and base fee of the block, in 32
+    // bytes words
+    let block_fields = Address::with_last_byte(0xbf);
+    let mut code = vec![];
+    for (i, opcode) in [0x43u8, 0x42, 0x41, 0x44, 0x48].into_iter().enumerate() {
+        // OPCODE PUSH1 offset MSTORE
+        code.extend([opcode, 0x60, i as u8 * 32, 0x52]);
+    }
+    // PUSH1 160 PUSH1 0 RETURN
+    code.extend([0x60, 0xa0, 0x60, 0x00, 0xf3]);
+    let state_overrides = || {
+        Some(
+            [(
+                block_fields,
+                AccountOverride {
+                    code: Some(Bytes::from(code.clone())),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        )
+    };
+    let request = TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(block_fields),
+        gas: Some(U256::from(100000)),
+        ..Default::default()
+    };
+    let coinbase = Address::with_last_byte(0xcb);
+    let prevrandao = B256::with_last_byte(0x44);
+    let block_overrides = || BlockOverrides {
+        number: Some(U256::from(1000)),
+        time: Some(U64::from(1_700_000_000)),
+        coinbase: Some(coinbase),
+        random: Some(prevrandao),
+        base_fee: Some(U256::from(7)),
+        ..Default::default()
+    };
+    let word = |bytes: &[u8]| B256::left_padding_from(bytes);
+    let expected = [
+        word(&1000u64.to_be_bytes()),
+        word(&1_700_000_000u64.to_be_bytes()),
+        word(coinbase.as_slice()),
+        prevrandao,
+        word(&[7]),
+    ]
+    .into_iter()
+    .flat_map(|field| field.0)
+    .collect::<Vec<u8>>();
+
+    let real = evm
+        .get_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            state_overrides(),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_ne!(real, Bytes::from(expected.clone()));
+
+    let overridden = evm
+        .get_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            state_overrides(),
+            Some(Box::new(block_overrides())),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(overridden, Bytes::from(expected.clone()));
+
+    let trace = evm
+        .debug_trace_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            Some(GethDebugTracingCallOptions {
+                tracing_options: GethDebugTracingOptions::default().with_tracer(
+                    GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer),
+                ),
+                state_overrides: state_overrides(),
+                block_overrides: Some(block_overrides()),
+            }),
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::CallTracer(frame) = trace else {
+        panic!("Expected a call trace, got {:?}", trace);
+    };
+    assert_eq!(frame.output, Some(Bytes::from(expected)));
+
+    // Overridden block hashes are read by BLOCKHASH
+    let block_hash = Address::with_last_byte(0xbb);
+    let hash = B256::with_last_byte(0xaa);
+    let overridden_hash = evm
+        .get_call(
+            TransactionRequest {
+                to: Some(block_hash),
+                ..request.clone()
+            },
+            Some(BlockNumberOrTag::Latest),
+            Some(
+                [(
+                    block_hash,
+                    AccountOverride {
+                        // PUSH2 999 BLOCKHASH PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+                        code: Some(Bytes::from(vec![
+                            0x61, 0x03, 0xe7, 0x40, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+                        ])),
+                        ..Default::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            ),
+            Some(Box::new(BlockOverrides {
+                number: Some(U256::from(1000)),
+                block_hash: Some([(999, hash)].into_iter().collect()),
+                ..Default::default()
+            })),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(overridden_hash, Bytes::from(hash.to_vec()));
+
+    // The overridden block is used instead of the cached result of the same call
+    let contract = SimpleStorageContract::default();
+    let request = TransactionRequest {
+        to: Some(Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap()),
+        input: TransactionInput::new(contract.get_call_data().to_vec().into()),
+        ..request
+    };
+    assert!(evm
+        .get_call(
+            request.clone(),
+            Some(BlockNumberOrTag::Latest),
+            None,
+            None,
+            &mut working_set,
+        )
+        .is_ok());
+    // The gas of the call is above the overridden block gas limit
+    assert!(evm
+        .get_call(
+            request,
+            Some(BlockNumberOrTag::Latest),
+            None,
+            Some(Box::new(BlockOverrides {
+                gas_limit: Some(U64::from(50000)),
+                ..Default::default()
+            })),
+            &mut working_set,
+        )
+        .is_err());
+}
+
+#[test]
+fn call_many_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let request = |input: Vec<u8>| TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(contract_address),
+        gas: Some(U256::from(100000)),
+        gas_price: Some(U256::from(100000000)),
+        input: TransactionInput::new(input.into()),
+        ..Default::default()
+    };
+    let stored = |value: u64| Some(Bytes::from(U256::from(value).to_be_bytes::<32>().to_vec()));
+
+    let results = evm
+        .call_many(
+            vec![
+                CallBundle {
+                    transactions: vec![
+                        request(contract.set_call_data(5).to_vec()),
+                        request(contract.get_call_data().to_vec()),
+                    ],
+                    block_override: None,
+                },
+                CallBundle {
+                    transactions: vec![
+                        request(contract.get_call_data().to_vec()),
+                        // Calls to a contract without code succeed with no output
+                        TransactionRequest {
+                            to: Some(Address::with_last_byte(0xde)),
+                            ..request(vec![])
+                        },
+                    ],
+                    block_override: None,
+                },
+            ],
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0][0].value, Some(Bytes::new()));
+    // The calls see the changes of the previous calls, of the same bundle or not
+    assert_eq!(results[0][1].value, stored(5));
+    assert_eq!(results[1][0].value, stored(5));
+    assert_eq!(results[1][1].value, Some(Bytes::new()));
+    assert!(results
+        .iter()
+        .flatten()
+        .all(|result| result.error.is_none()));
+
+    // Failed calls are reported in their result
+    let results = evm
+        .call_many(
+            vec![CallBundle {
+                transactions: vec![TransactionRequest {
+                    gas: Some(U256::from(100)),
+                    ..request(contract.get_call_data().to_vec())
+                }],
+                block_override: None,
+            }],
+            Some(CallManyContext {
+                block_number: Some(BlockNumberOrTag::Latest),
+                transaction_index: None,
+            }),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(results[0][0].value, None);
+    assert!(results[0][0].error.is_some());
+
+    // Block overrides only apply to the calls of their bundle
+    let results = evm
+        .call_many(
+            vec![
+                CallBundle {
+                    transactions: vec![request(contract.get_call_data().to_vec())],
+                    block_override: Some(BlockOverrides {
+                        gas_limit: Some(U64::from(50000)),
+                        ..Default::default()
+                    }),
+                },
+                CallBundle {
+                    transactions: vec![request(contract.get_call_data().to_vec())],
+                    block_override: None,
+                },
+            ],
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    // The gas of the call is above the overridden block gas limit
+    assert!(results[0][0].error.is_some());
+    assert_eq!(results[1][0].value, stored(478));
+}
+
+#[test]
+fn simulate_v1_test() {
+    let (evm, mut working_set, signer) = init_evm();
+
+    let contract = SimpleStorageContract::default();
+    let contract_address = Address::from_str("0xeeb03d20dae810f52111b853b31c8be6f30f4cd3").unwrap();
+    let request = |input: Vec<u8>| TransactionRequest {
+        from: Some(signer.address()),
+        to: Some(contract_address),
+        gas: Some(U256::from(100000)),
+        input: TransactionInput::new(input.into()),
+        ..Default::default()
+    };
+    let rich = Address::with_last_byte(0xaa);
+    let receiver = Address::with_last_byte(0xbb);
+
+    let head = evm
+        .get_block_by_number(Some(BlockNumberOrTag::Latest), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+    let blocks = evm
+        .simulate_v1(
+            SimulatePayload {
+                block_state_calls: vec![
+                    SimulateBlock {
+                        state_overrides: Some(
+                            [(
+                                rich,
+                                AccountOverride {
+                                    balance: Some(U256::from(1000)),
+                                    ..Default::default()
+                                },
+                            )]
+                            .into_iter()
+                            .collect(),
+                        ),
+                        calls: vec![
+                            request(contract.set_call_data(5).to_vec()),
+                            TransactionRequest {
+                                from: Some(rich),
+                                to: Some(receiver),
+                                value: Some(U256::from(300)),
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                    SimulateBlock {
+                        calls: vec![request(contract.get_call_data().to_vec())],
+                        ..Default::default()
+                    },
+                ],
+                trace_transfers: true,
+                validation: false,
+            },
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].parent_hash, head.header.hash.unwrap());
+    assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+    assert_eq!(blocks[0].number + U64::from(1), blocks[1].number);
+    assert!(blocks[0].timestamp < blocks[1].timestamp);
+
+    let set_call = &blocks[0].calls[0];
+    assert_eq!(set_call.status, U64::from(1));
+    assert!(set_call.error.is_none());
+    // The overridden balance pays for the transfer, logged as an ERC-20 transfer
+    let transfer = &blocks[0].calls[1];
+    assert_eq!(transfer.status, U64::from(1));
+    assert_eq!(transfer.logs.len(), 1);
+    assert_eq!(transfer.logs[0].address, TRANSFER_LOG_ADDRESS);
+    assert_eq!(transfer.logs[0].block_hash, Some(blocks[0].hash));
+    assert_eq!(
+        transfer.logs[0].data,
+        Bytes::from(U256::from(300).to_be_bytes::<32>().to_vec())
+    );
+    // Later blocks see the changes of the previous ones
+    assert_eq!(
+        blocks[1].calls[0].return_data,
+        Bytes::from(U256::from(5).to_be_bytes::<32>().to_vec())
+    );
+    assert_eq!(
+        blocks[0].gas_used,
+        blocks[0].calls[0].gas_used + blocks[0].calls[1].gas_used
+    );
+
+    // Block numbers must increase
+    assert!(evm
+        .simulate_v1(
+            SimulatePayload {
+                block_state_calls: vec![SimulateBlock {
+                    block_overrides: Some(BlockOverrides {
+                        number: Some(U256::from(1)),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            None,
+            &mut working_set,
+        )
+        .is_err());
+}
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use alloy_primitives::FixedBytes;
 use hex::FromHex;
 use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
-use reth_primitives::BlockNumberOrTag;
+use reth_primitives::{BlockNumberOrTag, U64};
 use revm::primitives::{B256, U256};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address;
@@ -14,7 +14,7 @@ use crate::smart_contracts::LogsContract;
 use crate::tests::call_tests::{create_contract_message, get_evm_config, publish_event_message};
 use crate::tests::genesis_tests::get_evm;
 use crate::tests::queries::init_evm;
-use crate::{EthApiError, Filter, FilterBlockOption, FilterSet};
+use crate::{EthApiError, Filter, FilterBlockOption, FilterError, FilterSet, LogCursor};
 
 type C = DefaultContext;
 
@@ -68,7 +68,7 @@ fn log_filter_test_at_block_hash() {
 
     let (evm, mut working_set) = get_evm(&config);
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -262,7 +262,7 @@ fn log_filter_test_with_range() {
 
     let (evm, mut working_set) = get_evm(&config);
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -311,7 +311,7 @@ fn log_filter_test_with_range() {
 
     assert_eq!(rpc_logs.len(), 4);
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], 1, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -346,6 +346,10 @@ fn log_filter_test_with_range() {
     let rpc_logs = evm.eth_get_logs(filter, &mut working_set).unwrap();
     // In the last block we have 2 logs
     assert_eq!(rpc_logs.len(), 2);
+    // of its first transaction
+    assert!(rpc_logs
+        .iter()
+        .all(|log| log.transaction_index == Some(U256::ZERO)));
 }
 
 #[test]
@@ -360,7 +364,7 @@ fn test_log_limits() {
 
     let (evm, mut working_set) = get_evm(&config);
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -448,7 +452,7 @@ fn test_log_limits() {
 
     for _ in 1..100_001 {
         // generate 100_000 blocks to test the max block range limit
-        evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], 1, &mut working_set);
+        evm.begin_soft_confirmation_hook([5u8; 32], &[99u8; 32], 1, None, &mut working_set);
         evm.end_soft_confirmation_hook(&mut working_set);
         evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
     }
@@ -470,3 +474,93 @@ fn test_log_limits() {
         "query exceeds max block range 100000".to_string()
     );
 }
+
+#[test]
+fn test_logs_pages() {
+    let (config, dev_signer, contract_addr) =
+        get_evm_config(U256::from_str("100000000000000000000").unwrap(), None);
+    let (evm, mut working_set) = get_evm(&config);
+
+    let sender_address = generate_address::<C>("sender");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let context = C::new(sender_address, sequencer_address, 1);
+
+    // Block 1 has 4 logs, block 2 none and block 3 has 2 logs
+    let blocks = [
+        vec![
+            create_contract_message(&dev_signer, 0, LogsContract::default()),
+            publish_event_message(contract_addr, &dev_signer, 1, "hello".to_string()),
+            publish_event_message(contract_addr, &dev_signer, 2, "hi".to_string()),
+        ],
+        vec![],
+        vec![publish_event_message(
+            contract_addr,
+            &dev_signer,
+            3,
+            "hey".to_string(),
+        )],
+    ];
+    for txs in blocks {
+        evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
+        if !txs.is_empty() {
+            evm.call(CallMessage { txs }, &context, &mut working_set)
+                .unwrap();
+        }
+        evm.end_soft_confirmation_hook(&mut working_set);
+        evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+    }
+
+    let filter = Filter {
+        block_option: FilterBlockOption::Range {
+            from_block: Some(BlockNumberOrTag::Number(1)),
+            to_block: Some(BlockNumberOrTag::Number(3)),
+        },
+        address: FilterSet::default(),
+        topics: Default::default(),
+    };
+    let cursor = |block_number: u64, log_index: u64| LogCursor {
+        block_number: U64::from(block_number),
+        log_index: U64::from(log_index),
+    };
+    let positions = |logs: &[crate::LogResponse]| -> Vec<(u64, u64)> {
+        logs.iter()
+            .map(|log| {
+                (
+                    log.block_number.unwrap().to::<u64>(),
+                    log.log_index.unwrap().to::<u64>(),
+                )
+            })
+            .collect()
+    };
+
+    // Pages of at most 2 blocks and 3 logs
+    let page = evm
+        .logs_page_for_filter(&filter, None, 2, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(positions(&page.logs), vec![(1, 0), (1, 1), (1, 2)]);
+    assert_eq!(page.next_cursor, Some(cursor(1, 3)));
+
+    let page = evm
+        .logs_page_for_filter(&filter, page.next_cursor, 2, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(positions(&page.logs), vec![(1, 3)]);
+    assert_eq!(page.next_cursor, Some(cursor(3, 0)));
+
+    let page = evm
+        .logs_page_for_filter(&filter, page.next_cursor, 2, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(positions(&page.logs), vec![(3, 0), (3, 1)]);
+    assert_eq!(page.next_cursor, None);
+
+    // The pages have the logs of eth_getLogs
+    let all_logs = evm.eth_get_logs(filter.clone(), &mut working_set).unwrap();
+    assert_eq!(
+        positions(&all_logs),
+        vec![(1, 0), (1, 1), (1, 2), (1, 3), (3, 0), (3, 1)]
+    );
+
+    assert!(matches!(
+        evm.logs_page_for_filter(&filter, Some(cursor(4, 0)), 2, 3, &mut working_set),
+        Err(FilterError::InvalidCursor)
+    ));
+}
@@ -60,7 +60,7 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
             .as_slice(),
     );
 
-    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([5u8; 32], &[10u8; 32], 1, None, &mut working_set);
 
     {
         let sender_address = generate_address::<C>("sender");
@@ -88,7 +88,7 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
 
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
 
-    evm.begin_soft_confirmation_hook([8u8; 32], &[99u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([8u8; 32], &[99u8; 32], 1, None, &mut working_set);
 
     {
         let sender_address = generate_address::<C>("sender");
@@ -117,7 +117,7 @@ fn init_evm() -> (Evm<C>, WorkingSet<C>, TestSigner) {
 
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
 
-    evm.begin_soft_confirmation_hook([10u8; 32], &[100u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([10u8; 32], &[100u8; 32], 1, None, &mut working_set);
 
     {
         let sender_address = generate_address::<C>("sender");
@@ -170,7 +170,7 @@ pub fn init_evm_single_block() -> (Evm<C>, WorkingSet<C>, TestSigner) {
     //         .as_slice(),
     // );
 
-    evm.begin_soft_confirmation_hook([1u8; 32], &[0u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([1u8; 32], &[0u8; 32], 1, None, &mut working_set);
 
     let simple_payable_contract_tx =
         create_contract_transaction(&dev_signer, 0, SimplePayableContract::default());
@@ -228,7 +228,7 @@ pub fn init_evm_with_caller_contract() -> (Evm<C>, WorkingSet<C>, TestSigner) {
     //         .as_slice(),
     // );
 
-    evm.begin_soft_confirmation_hook([1u8; 32], &[0u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([1u8; 32], &[0u8; 32], 1, None, &mut working_set);
 
     {
         let sender_address = generate_address::<C>("sender");
@@ -255,7 +255,7 @@ pub fn init_evm_with_caller_contract() -> (Evm<C>, WorkingSet<C>, TestSigner) {
 
     let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
 
-    evm.begin_soft_confirmation_hook([2u8; 32], &[2u8; 32], 1, &mut working_set);
+    evm.begin_soft_confirmation_hook([2u8; 32], &[2u8; 32], 1, None, &mut working_set);
 
     {
         let sender_address = generate_address::<C>("sender");
@@ -309,16 +309,17 @@ fn get_evm_with_storage(
     (evm, working_set, prover_storage)
 }
 
+/// Commits the working set to the storage and returns the new state root
 fn commit(
     working_set: WorkingSet<DefaultContext>,
     storage: ProverStorage<DefaultStorageSpec, SnapshotManager>,
-) {
+) -> <ProverStorage<DefaultStorageSpec, SnapshotManager> as Storage>::Root {
     // Save checkpoint
     let mut checkpoint = working_set.checkpoint();
 
     let (cache_log, witness) = checkpoint.freeze();
 
-    let (_, authenticated_node_batch) = storage
+    let (state_root, authenticated_node_batch) = storage
         .compute_state_update(cache_log, &witness)
         .expect("jellyfish merkle tree update must succeed");
 
@@ -327,4 +328,5 @@ fn commit(
     let accessory_log = working_set.checkpoint().freeze_non_provable();
 
     storage.commit(&authenticated_node_batch, &accessory_log);
+    state_root
 }
@@ -1,11 +1,12 @@
+pub use citrea_client::{GetSoftBatchResponse, ProvingClaim};
 use ethers::types::{Bytes, H256};
 use jsonrpsee::core::client::ClientT;
 use jsonrpsee::core::Error;
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
-use reth_primitives::B256;
-use serde::Deserialize;
-use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use reth_primitives::{Address, BlockNumberOrTag, B256, U64};
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolInspect, TxpoolStatus};
+use sov_rollup_interface::rpc::SignedCheckpoint;
 
 /// Configuration for SequencerClient.
 #[derive(Debug, Clone)]
@@ -28,6 +29,11 @@ impl SequencerClient {
         &self,
         num: u64,
     ) -> anyhow::Result<Option<GetSoftBatchResponse>> {
+        #[cfg(feature = "chaos")]
+        if sov_mock_da::chaos::should_fail(sov_mock_da::chaos::Fault::RpcConnectionDrop) {
+            return Err(Error::Transport(anyhow::anyhow!("Injected connection drop")).into());
+        }
+
         let res: Result<Option<GetSoftBatchResponse>, jsonrpsee::core::Error> = self
             .client
             .request("ledger_getSoftBatchByNumber", rpc_params![num])
@@ -42,6 +48,16 @@ impl SequencerClient {
         }
     }
 
+    /// Gets the keys of the state read by the transactions of the l2 block at the given height,
+    /// empty if the sequencer doesn't record them
+    pub async fn get_access_hints(&self, num: u64) -> anyhow::Result<Vec<reth_primitives::Bytes>> {
+        let hints: Option<Vec<reth_primitives::Bytes>> = self
+            .client
+            .request("citrea_getAccessHints", rpc_params![num])
+            .await?;
+        Ok(hints.unwrap_or_default())
+    }
+
     /// Sends raw tx to sequencer
     pub async fn send_raw_tx(&self, tx: Bytes) -> anyhow::Result<H256> {
         let tx_hash: H256 = self
@@ -65,39 +81,92 @@ impl SequencerClient {
             .await?;
         Ok(tx)
     }
-}
 
-#[derive(Deserialize, Debug, Clone)]
-pub struct GetSoftBatchResponse {
-    #[serde(with = "hex::serde")]
-    pub hash: [u8; 32],
-    pub da_slot_height: u64,
-    #[serde(with = "hex::serde")]
-    pub da_slot_hash: [u8; 32],
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub txs: Option<Vec<Vec<u8>>>,
-    #[serde(with = "hex::serde")]
-    pub pre_state_root: Vec<u8>,
-    #[serde(with = "hex::serde")]
-    pub post_state_root: Vec<u8>,
-    #[serde(with = "hex::serde")]
-    pub soft_confirmation_signature: Vec<u8>,
-    #[serde(with = "hex::serde")]
-    pub pub_key: Vec<u8>,
-    pub l1_fee_rate: u64,
-}
+    /// Gets the nonce of the address, including the transactions in the sequencer's mempool
+    /// for the `pending` tag
+    pub async fn get_transaction_count(
+        &self,
+        address: Address,
+        block_number: Option<BlockNumberOrTag>,
+    ) -> anyhow::Result<U64> {
+        let nonce: U64 = self
+            .client
+            .request(
+                "eth_getTransactionCount",
+                rpc_params![address, block_number],
+            )
+            .await?;
+        Ok(nonce)
+    }
+
+    /// Claims the commitment range between the given L2 heights for the prover `prover_id`.
+    /// Returns the claim on the range, which may be held by another prover.
+    pub async fn claim_proving_range(
+        &self,
+        prover_id: &str,
+        start_l2_height: u64,
+        end_l2_height: u64,
+    ) -> anyhow::Result<ProvingClaim> {
+        let claim: ProvingClaim = self
+            .client
+            .request(
+                "citrea_claimProvingRange",
+                rpc_params![prover_id, start_l2_height, end_l2_height],
+            )
+            .await?;
+        Ok(claim)
+    }
+
+    /// Reports the commitment range between the given L2 heights as proven by `prover_id`
+    pub async fn finish_proving_range(
+        &self,
+        prover_id: &str,
+        start_l2_height: u64,
+        end_l2_height: u64,
+    ) -> anyhow::Result<ProvingClaim> {
+        let claim: ProvingClaim = self
+            .client
+            .request(
+                "citrea_finishProvingRange",
+                rpc_params![prover_id, start_l2_height, end_l2_height],
+            )
+            .await?;
+        Ok(claim)
+    }
+
+    /// Gets the last checkpoint of the state signed by the sequencer
+    pub async fn get_latest_checkpoint(&self) -> anyhow::Result<Option<SignedCheckpoint>> {
+        let checkpoint: Option<SignedCheckpoint> = self
+            .client
+            .request("citrea_getLatestCheckpoint", rpc_params![])
+            .await?;
+        Ok(checkpoint)
+    }
+
+    /// Gets the raw transactions in the sequencer's mempool
+    pub async fn get_mempool_transactions(&self) -> anyhow::Result<Vec<reth_primitives::Bytes>> {
+        let txs: Vec<reth_primitives::Bytes> = self
+            .client
+            .request("citrea_getMempoolTransactions", rpc_params![])
+            .await?;
+        Ok(txs)
+    }
+
+    /// Gets the transactions in the sequencer's mempool, grouped by sender and nonce
+    pub async fn txpool_content(&self) -> anyhow::Result<TxpoolContent> {
+        let content: TxpoolContent = self.client.request("txpool_content", rpc_params![]).await?;
+        Ok(content)
+    }
+
+    /// Gets a summary of the transactions in the sequencer's mempool, grouped by sender and nonce
+    pub async fn txpool_inspect(&self) -> anyhow::Result<TxpoolInspect> {
+        let inspect: TxpoolInspect = self.client.request("txpool_inspect", rpc_params![]).await?;
+        Ok(inspect)
+    }
 
-impl From<GetSoftBatchResponse> for SignedSoftConfirmationBatch {
-    fn from(val: GetSoftBatchResponse) -> Self {
-        SignedSoftConfirmationBatch::new(
-            val.hash,
-            val.da_slot_height,
-            val.da_slot_hash,
-            val.pre_state_root,
-            val.l1_fee_rate,
-            val.txs.unwrap_or_default(),
-            val.soft_confirmation_signature,
-            val.pub_key,
-        )
+    /// Gets the number of pending and queued transactions in the sequencer's mempool
+    pub async fn txpool_status(&self) -> anyhow::Result<TxpoolStatus> {
+        let status: TxpoolStatus = self.client.request("txpool_status", rpc_params![]).await?;
+        Ok(status)
     }
 }
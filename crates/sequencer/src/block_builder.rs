@@ -0,0 +1,393 @@
+//! Selection of the mempool transactions included in a soft confirmation
+
+use std::collections::{HashSet, VecDeque};
+
+use reth_primitives::{Address, TxHash};
+
+use crate::config::{PriorityLaneConfig, RevertingTxPolicy};
+
+/// A transaction the block builder can include
+pub(crate) struct Candidate<T> {
+    pub(crate) tx: T,
+    pub(crate) sender: Address,
+    pub(crate) gas_limit: u64,
+    /// Encoded size of the transaction in bytes
+    pub(crate) size: u64,
+}
+
+/// Space available in a soft confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockLimits {
+    pub(crate) gas: u64,
+    /// Unbounded if not set
+    pub(crate) bytes: Option<u64>,
+    /// Number of transactions, unbounded if not set
+    pub(crate) txs: Option<u64>,
+}
+
+impl BlockLimits {
+    fn fits<T>(&self, candidate: &Candidate<T>) -> bool {
+        candidate.gas_limit <= self.gas
+            && self.bytes.map_or(true, |bytes| candidate.size <= bytes)
+            && self.txs.map_or(true, |txs| txs > 0)
+    }
+
+    fn take<T>(&mut self, candidate: &Candidate<T>) {
+        self.gas -= candidate.gas_limit;
+        if let Some(bytes) = self.bytes.as_mut() {
+            *bytes -= candidate.size;
+        }
+        if let Some(txs) = self.txs.as_mut() {
+            *txs -= 1;
+        }
+    }
+}
+
+/// Transactions picked for a soft confirmation
+pub(crate) struct BlockSelection<T> {
+    /// Priority lane transactions first, then the others, in mempool order
+    pub(crate) txs: Vec<T>,
+    /// Priority lane transactions which didn't fit and are left for the next block
+    pub(crate) spilled_priority: usize,
+}
+
+/// Picks the transactions of a soft confirmation from `candidates`, in mempool order.
+///
+/// Transactions of the priority senders are taken first and may use the whole block.
+/// The others only get what is left after the reserve of the priority lane, or after
+/// the priority transactions if they used more than the reserve. A transaction which
+/// doesn't fit is left in the mempool along with the later transactions of its sender,
+/// which could not be executed without it.
+pub(crate) fn select_transactions<T>(
+    candidates: impl IntoIterator<Item = Candidate<T>>,
+    config: &PriorityLaneConfig,
+    limits: BlockLimits,
+) -> BlockSelection<T> {
+    let (priority, others): (Vec<_>, Vec<_>) = candidates
+        .into_iter()
+        .partition(|candidate| config.senders.contains(&candidate.sender));
+
+    let mut remaining = limits;
+    let mut txs = Vec::new();
+    let spilled_priority = take_fitting(priority, &mut remaining, &mut txs);
+
+    // The reserve is kept even when the priority lane doesn't use it
+    let priority_gas = limits.gas - remaining.gas;
+    let mut others_limits = BlockLimits {
+        gas: limits
+            .gas
+            .saturating_sub(priority_gas.max(config.reserved_gas)),
+        bytes: limits.bytes.map(|bytes| {
+            let priority_bytes = bytes - remaining.bytes.unwrap_or_default();
+            bytes.saturating_sub(priority_bytes.max(config.reserved_bytes))
+        }),
+        // No transactions are reserved for the priority lane
+        txs: remaining.txs,
+    };
+    take_fitting(others, &mut others_limits, &mut txs);
+
+    BlockSelection {
+        txs,
+        spilled_priority,
+    }
+}
+
+/// Picks the transactions of a soft confirmation from `candidates` in mempool order, like
+/// [`select_transactions`] without a priority lane. The candidates are only read as the
+/// returned iterator is, so that the first transactions can be executed while the mempool
+/// is still being drained.
+pub(crate) fn stream_transactions<T>(
+    candidates: impl IntoIterator<Item = Candidate<T>>,
+    mut limits: BlockLimits,
+) -> impl Iterator<Item = T> {
+    let mut skipped_senders = HashSet::new();
+    candidates.into_iter().filter_map(move |candidate| {
+        if skipped_senders.contains(&candidate.sender) || !limits.fits(&candidate) {
+            skipped_senders.insert(candidate.sender);
+            return None;
+        }
+        limits.take(&candidate);
+        Some(candidate.tx)
+    })
+}
+
+/// Moves the candidates fitting in `limits` to `txs`, returning how many were left out
+fn take_fitting<T>(
+    candidates: Vec<Candidate<T>>,
+    limits: &mut BlockLimits,
+    txs: &mut Vec<T>,
+) -> usize {
+    let mut skipped_senders = HashSet::new();
+    let mut skipped = 0;
+    for candidate in candidates {
+        if skipped_senders.contains(&candidate.sender) || !limits.fits(&candidate) {
+            skipped_senders.insert(candidate.sender);
+            skipped += 1;
+            continue;
+        }
+        limits.take(&candidate);
+        txs.push(candidate.tx);
+    }
+    skipped
+}
+
+/// Selected transactions after applying the [`RevertingTxPolicy`]
+pub(crate) struct SimulatedSelection<T> {
+    /// Transactions to include, in order
+    pub(crate) txs: Vec<T>,
+    /// Transactions left out because they revert, to drop from the mempool
+    pub(crate) skipped: Vec<T>,
+    /// Number of transactions which reverted in the simulation
+    pub(crate) reverted: usize,
+}
+
+/// Applies `policy` to the selected transactions, given their sender and whether they
+/// succeeded when simulated in order.
+///
+/// The later transactions of the sender of a skipped transaction are left in the mempool,
+/// as they can't be executed without it, and those of a deprioritized transaction are
+/// moved along with it.
+pub(crate) fn apply_reverting_tx_policy<T>(
+    txs: impl IntoIterator<Item = (T, Address, bool)>,
+    policy: RevertingTxPolicy,
+) -> SimulatedSelection<T> {
+    let mut selection = SimulatedSelection {
+        txs: vec![],
+        skipped: vec![],
+        reverted: 0,
+    };
+    let mut moved_senders = HashSet::new();
+    let mut deprioritized = vec![];
+    for (tx, sender, succeeded) in txs {
+        if !succeeded {
+            selection.reverted += 1;
+        }
+        let follows_moved = moved_senders.contains(&sender);
+        if policy == RevertingTxPolicy::Include || (succeeded && !follows_moved) {
+            selection.txs.push(tx);
+        } else if policy == RevertingTxPolicy::Deprioritize {
+            moved_senders.insert(sender);
+            deprioritized.push(tx);
+        } else if !follows_moved {
+            moved_senders.insert(sender);
+            selection.skipped.push(tx);
+        }
+    }
+    selection.txs.extend(deprioritized);
+    selection
+}
+
+/// Number of soft confirmations whose transactions are remembered by [`RecentInclusions`],
+/// the same as the EVM which skips them
+pub(crate) const RECENT_INCLUSION_WINDOW: usize = citrea_evm::RECENT_TX_WINDOW as usize;
+
+/// Hashes of the transactions of the last soft confirmations, so that transactions which
+/// reach the mempool again after being included, such as when mirrored from the primary
+/// sequencer during a failover, are not included a second time.
+pub(crate) struct RecentInclusions {
+    window: usize,
+    blocks: VecDeque<Vec<TxHash>>,
+    hashes: HashSet<TxHash>,
+}
+
+impl RecentInclusions {
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            window,
+            blocks: VecDeque::with_capacity(window),
+            hashes: HashSet::new(),
+        }
+    }
+
+    /// Records the transactions of a soft confirmation, forgetting the ones of the soft
+    /// confirmation leaving the window.
+    pub(crate) fn record_block(&mut self, tx_hashes: Vec<TxHash>) {
+        self.hashes.extend(tx_hashes.iter().copied());
+        self.blocks.push_back(tx_hashes);
+        if self.blocks.len() > self.window {
+            for hash in self.blocks.pop_front().unwrap_or_default() {
+                self.hashes.remove(&hash);
+            }
+        }
+    }
+
+    /// Whether the transaction is included in one of the soft confirmations of the window
+    pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
+        self.hashes.contains(tx_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEM: Address = Address::with_last_byte(1);
+    const ALICE: Address = Address::with_last_byte(2);
+    const BOB: Address = Address::with_last_byte(3);
+
+    fn candidate(tx: u32, sender: Address, gas_limit: u64) -> Candidate<u32> {
+        Candidate {
+            tx,
+            sender,
+            gas_limit,
+            size: 100,
+        }
+    }
+
+    fn config(reserved_gas: u64) -> PriorityLaneConfig {
+        PriorityLaneConfig {
+            senders: vec![SYSTEM],
+            reserved_gas,
+            reserved_bytes: 0,
+        }
+    }
+
+    const LIMITS: BlockLimits = BlockLimits {
+        gas: 100,
+        bytes: None,
+        txs: None,
+    };
+
+    #[test]
+    fn test_reserve_is_kept_for_priority_lane() {
+        let selection = select_transactions(
+            vec![
+                candidate(1, ALICE, 40),
+                candidate(2, BOB, 40),
+                candidate(3, SYSTEM, 10),
+            ],
+            &config(30),
+            LIMITS,
+        );
+        // Priority transactions go first, and the others only get 70 gas
+        assert_eq!(selection.txs, vec![3, 1]);
+        assert_eq!(selection.spilled_priority, 0);
+    }
+
+    #[test]
+    fn test_priority_lane_spills_over() {
+        let selection = select_transactions(
+            vec![
+                candidate(1, ALICE, 20),
+                candidate(2, SYSTEM, 50),
+                candidate(3, SYSTEM, 40),
+                candidate(4, SYSTEM, 20),
+            ],
+            &config(30),
+            LIMITS,
+        );
+        // The priority lane takes space from the other transactions, and its last
+        // transaction waits for the next block
+        assert_eq!(selection.txs, vec![2, 3]);
+        assert_eq!(selection.spilled_priority, 1);
+    }
+
+    #[test]
+    fn test_later_transactions_of_skipped_sender_are_left_out() {
+        let selection = select_transactions(
+            vec![
+                candidate(1, ALICE, 80),
+                candidate(2, BOB, 10),
+                candidate(3, ALICE, 10),
+            ],
+            &config(30),
+            LIMITS,
+        );
+        assert_eq!(selection.txs, vec![2]);
+    }
+
+    #[test]
+    fn test_reserved_bytes() {
+        let config = PriorityLaneConfig {
+            reserved_bytes: 150,
+            ..config(0)
+        };
+        let limits = BlockLimits {
+            gas: 100,
+            bytes: Some(300),
+            txs: None,
+        };
+        let selection = select_transactions(
+            vec![candidate(1, ALICE, 1), candidate(2, BOB, 1)],
+            &config,
+            limits,
+        );
+        assert_eq!(selection.txs, vec![1]);
+    }
+
+    #[test]
+    fn test_max_txs() {
+        let limits = BlockLimits {
+            txs: Some(2),
+            ..LIMITS
+        };
+        let selection = select_transactions(
+            vec![
+                candidate(1, ALICE, 10),
+                candidate(2, SYSTEM, 10),
+                candidate(3, BOB, 10),
+            ],
+            &config(0),
+            limits,
+        );
+        assert_eq!(selection.txs, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_streamed_transactions_match_selection() {
+        let candidates = || {
+            vec![
+                candidate(1, ALICE, 40),
+                candidate(2, BOB, 50),
+                candidate(3, ALICE, 30),
+                candidate(4, BOB, 10),
+                candidate(5, SYSTEM, 10),
+            ]
+        };
+        let limits = BlockLimits {
+            txs: Some(3),
+            ..LIMITS
+        };
+        let selection = select_transactions(candidates(), &Default::default(), limits);
+        let streamed: Vec<_> = stream_transactions(candidates(), limits).collect();
+        assert_eq!(streamed, selection.txs);
+        assert_eq!(streamed, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_reverting_tx_policy() {
+        let simulated = || {
+            vec![
+                (1, ALICE, false),
+                (2, BOB, true),
+                (3, ALICE, true),
+                (4, BOB, true),
+            ]
+        };
+
+        let selection = apply_reverting_tx_policy(simulated(), RevertingTxPolicy::Include);
+        assert_eq!(selection.txs, vec![1, 2, 3, 4]);
+        assert_eq!(selection.reverted, 1);
+
+        // The later transaction of Alice stays in the mempool
+        let selection = apply_reverting_tx_policy(simulated(), RevertingTxPolicy::Skip);
+        assert_eq!(selection.txs, vec![2, 4]);
+        assert_eq!(selection.skipped, vec![1]);
+
+        let selection = apply_reverting_tx_policy(simulated(), RevertingTxPolicy::Deprioritize);
+        assert_eq!(selection.txs, vec![2, 4, 1, 3]);
+        assert!(selection.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_recent_inclusions_window() {
+        let mut recent = RecentInclusions::new(2);
+        recent.record_block(vec![TxHash::with_last_byte(1)]);
+        recent.record_block(vec![TxHash::with_last_byte(2)]);
+        assert!(recent.contains(&TxHash::with_last_byte(1)));
+
+        recent.record_block(vec![]);
+        assert!(!recent.contains(&TxHash::with_last_byte(1)));
+        assert!(recent.contains(&TxHash::with_last_byte(2)));
+    }
+}
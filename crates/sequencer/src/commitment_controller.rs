@@ -108,6 +108,25 @@ pub fn get_commitment_info(
     })
 }
 
+/// Returns the DA heights to scan at startup for commitments the sequencer sent before it
+/// stopped but did not record: from the DA height after the last recorded commitment, or
+/// from the DA height of the first soft confirmation if none was recorded, up to
+/// `last_finalized_height`. Returns none if there is nothing to scan.
+pub fn get_commitment_recovery_range(
+    ledger_db: &LedgerDB,
+    last_finalized_height: u64,
+) -> anyhow::Result<Option<RangeInclusive<u64>>> {
+    let start = match ledger_db.get_last_sequencer_commitment_l1_height()? {
+        Some(last_commitment_l1_height) => last_commitment_l1_height.0 + 1,
+        None => match ledger_db.get_soft_batch_by_number::<()>(1)? {
+            Some(first_soft_confirmation) => first_soft_confirmation.da_slot_height,
+            None => return Ok(None),
+        },
+    };
+
+    Ok((start <= last_finalized_height).then_some(start..=last_finalized_height))
+}
+
 pub fn get_commitment(
     commitment_info: CommitmentInfo,
     soft_confirmation_hashes: Vec<[u8; 32]>,
@@ -129,3 +148,95 @@ pub fn get_commitment(
         l1_end_block_hash: commitment_info.l1_end_hash,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use sov_mock_da::{MockDaSpec, MockHash};
+    use sov_rollup_interface::stf::SoftBatchReceipt;
+
+    use super::*;
+
+    fn commit_soft_confirmation(ledger_db: &LedgerDB, da_slot_height: u64) {
+        let soft_batch = SoftBatchReceipt::<(), (), MockDaSpec> {
+            da_slot_height,
+            da_slot_hash: MockHash([da_slot_height as u8; 32]),
+            batch_hash: [ledger_db.get_next_items_numbers().soft_batch_number as u8; 32],
+            tx_receipts: vec![],
+            phantom_data: PhantomData,
+            pre_state_root: vec![],
+            post_state_root: vec![],
+            soft_confirmation_signature: vec![],
+            pub_key: vec![],
+            l1_fee_rate: 0,
+            timestamp: None,
+            ordering_commitment: None,
+            outcome: None,
+        };
+        ledger_db.commit_soft_batch(soft_batch, true).unwrap();
+    }
+
+    fn l2_range(info: &CommitmentInfo) -> (u64, u64) {
+        (info.l2_height_range.start().0, info.l2_height_range.end().0)
+    }
+
+    // The ledger is reopened from disk at each phase boundary, as after a restart
+    #[test]
+    fn test_commitment_schedule_survives_restarts() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        // Before the first soft confirmation
+        {
+            let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+            assert!(get_commitment_info(&ledger_db, 1, 1).is_none());
+            assert_eq!(get_commitment_recovery_range(&ledger_db, 1).unwrap(), None);
+
+            // L2 heights 1 to 4 on DA heights 1 and 2
+            for da_slot_height in [1, 1, 2, 2] {
+                commit_soft_confirmation(&ledger_db, da_slot_height);
+            }
+        }
+
+        // Soft confirmations produced, no commitment sent
+        {
+            let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+            assert_eq!(
+                get_commitment_recovery_range(&ledger_db, 3).unwrap(),
+                Some(1..=3)
+            );
+            assert!(get_commitment_info(&ledger_db, 5, 2).is_none());
+            let info = get_commitment_info(&ledger_db, 4, 2).unwrap();
+            assert_eq!(l2_range(&info), (1, 4));
+            assert_eq!(info.l1_height_range, BatchNumber(1)..=BatchNumber(2));
+        }
+
+        // Commitment sent but not recorded: the commitment found on DA is recorded at startup,
+        // and the next commitment starts after it
+        {
+            let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+            let recovery_range = get_commitment_recovery_range(&ledger_db, 3).unwrap();
+            assert!(recovery_range.unwrap().contains(&3));
+            ledger_db
+                .set_last_sequencer_commitment_l1_height(SlotNumber(2))
+                .unwrap();
+
+            // L2 heights 5 and 6 on DA height 3
+            commit_soft_confirmation(&ledger_db, 3);
+            commit_soft_confirmation(&ledger_db, 3);
+        }
+
+        // Commitment recorded
+        {
+            let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+            assert_eq!(
+                get_commitment_recovery_range(&ledger_db, 3).unwrap(),
+                Some(3..=3)
+            );
+            assert_eq!(get_commitment_recovery_range(&ledger_db, 2).unwrap(), None);
+            let info = get_commitment_info(&ledger_db, 2, 3).unwrap();
+            assert_eq!(l2_range(&info), (5, 6));
+            assert_eq!(info.l1_height_range, BatchNumber(3)..=BatchNumber(3));
+        }
+    }
+}
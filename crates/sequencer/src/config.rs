@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+
+use reth_primitives::Address;
 use serde::Deserialize;
 
 /// Rollup Configuration
@@ -5,6 +8,199 @@ use serde::Deserialize;
 pub struct SequencerConfig {
     /// Min. soft confirmaitons for sequencer to commit
     pub min_soft_confirmations_per_commitment: u64,
+    /// Max. estimated size of the batch working set in bytes. When a soft confirmation
+    /// exceeds it, the sequencer retries with fewer transactions, and evicts a transaction
+    /// which exceeds it on its own. Unbounded if not set.
+    ///
+    /// Only soft confirmations built by this sequencer are bounded. Full nodes still apply
+    /// the soft confirmations they receive in memory, so they rely on the sequencer keeping
+    /// them small; streaming the commit of larger ones is out of scope.
+    #[serde(default)]
+    pub max_working_set_bytes: Option<u64>,
+    /// Target DA cost of a soft confirmation, priced as its compressed size in bytes times
+    /// the L1 fee rate. Soft confirmations over it are retried with fewer transactions,
+    /// so that blocks stay economical to post with calldata heavy transactions.
+    /// Unbounded if not set.
+    #[serde(default)]
+    pub target_da_cost_per_block: Option<u64>,
+    /// Lease lock that prevents two sequencer instances from producing blocks at the same time
+    #[serde(default)]
+    pub lease: SequencerLeaseConfig,
+    /// Hot standby mode. When set, the sequencer follows the primary sequencer
+    /// and only starts producing blocks once it acquires the lease.
+    #[serde(default)]
+    pub standby: Option<StandbyConfig>,
+    /// Worker threads of the runtime serving the RPC, which is kept separate from block
+    /// production so that RPC load can't delay soft confirmations.
+    /// Defaults to the number of available CPUs.
+    #[serde(default)]
+    pub rpc_worker_threads: Option<usize>,
+    /// Share of each soft confirmation kept for system transactions
+    #[serde(default)]
+    pub priority_lane: PriorityLaneConfig,
+    /// Hex encoded secp256k1 secret key soft confirmations are signed with once Schnorr
+    /// signatures are activated. Needed from the activation on.
+    #[serde(default)]
+    pub schnorr_private_key: Option<String>,
+    /// Seconds a prover's claim on a commitment range lasts before the range is handed
+    /// out to other provers, unless the prover reports it as proven
+    #[serde(default = "default_proving_claim_ttl_secs")]
+    pub proving_claim_ttl_secs: u64,
+    /// Soft confirmations between two checkpoints of the state signed by the sequencer,
+    /// which nodes bootstrapping from a snapshot can trust until they are proven.
+    /// No checkpoints are signed if not set.
+    #[serde(default)]
+    pub checkpoint_interval: Option<u64>,
+    /// What the block builder does with transactions which revert when simulated on the
+    /// pending state. Simulating them costs an extra execution of every block.
+    #[serde(default)]
+    pub reverting_txs: RevertingTxPolicy,
+    /// Max. milliseconds a transaction may execute for when the block builder simulates the
+    /// block. Slower transactions are left out of the block and stay in the mempool. Full
+    /// nodes execute blocks without limit. Simulating costs an extra execution of every
+    /// block. Unbounded if not set.
+    #[serde(default)]
+    pub tx_execution_budget_ms: Option<u64>,
+    /// Max. number of transactions executed at once when building a soft confirmation.
+    /// Larger blocks are split into chunks, each its own sequencer transaction, so that an
+    /// oversized working set is caught before the rest of the block is executed.
+    /// Unless a priority lane, an execution budget or a reverting transaction policy other than
+    /// `include` is set, chunks are executed while the rest of the block is still being drained
+    /// from the mempool.
+    /// Executed at once if not set.
+    #[serde(default)]
+    pub tx_chunk_size: Option<usize>,
+    /// Executes each block on a throwaway copy of the state before signing it, as full nodes
+    /// will, and refuses to publish it if the result differs from the sequencer's execution.
+    /// The transactions of the differing chunks are evicted from the mempool.
+    /// Doubles the execution cost of blocks.
+    #[serde(default)]
+    pub dry_run_blocks: bool,
+    /// Alerts and forces blocks when no soft confirmation is produced in time.
+    /// Disabled if not set.
+    #[serde(default)]
+    pub watchdog: Option<WatchdogConfig>,
+    /// Lowest max. priority fee per gas, or gas price for legacy transactions, of the
+    /// transactions accepted into the mempool. Published through the gas price oracle as
+    /// the lowest tip it suggests.
+    #[serde(default)]
+    pub min_priority_fee_per_gas: u64,
+}
+
+impl SequencerConfig {
+    /// Max. compressed size of a soft confirmation in bytes at the given L1 fee rate,
+    /// derived from [`Self::target_da_cost_per_block`].
+    pub fn max_soft_confirmation_bytes(&self, l1_fee_rate: u64) -> Option<u64> {
+        self.target_da_cost_per_block
+            .map(|cost| cost / l1_fee_rate.max(1))
+    }
+}
+
+/// Priority lane configuration.
+///
+/// Transactions of the priority senders, like the deposits of the bridge operator, are
+/// included before any other transaction, in a part of the block the other transactions
+/// can't use. When they need more than the reserve they take space from the other
+/// transactions, and those which don't fit in the block at all are included first in
+/// the next one.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct PriorityLaneConfig {
+    /// Senders whose transactions go through the priority lane
+    #[serde(default)]
+    pub senders: Vec<Address>,
+    /// Gas of each block only available to the priority lane
+    #[serde(default)]
+    pub reserved_gas: u64,
+    /// Bytes of each block only available to the priority lane. Only applies when the size
+    /// of blocks is bounded by `target_da_cost_per_block`.
+    #[serde(default)]
+    pub reserved_bytes: u64,
+}
+
+/// Handling of the transactions which revert when the block builder simulates them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevertingTxPolicy {
+    /// Include them, without simulating the block
+    #[default]
+    Include,
+    /// Leave them out and drop them from the mempool, saving their DA bytes
+    Skip,
+    /// Include them after the transactions which don't revert
+    Deprioritize,
+}
+
+/// Block production watchdog configuration.
+///
+/// When no soft confirmation is produced for `stall_factor` block times, the watchdog logs
+/// the state of the sequencer, counts a stall in `sequencer_block_production_stalls_total`
+/// and forces blocks to recover.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WatchdogConfig {
+    /// Milliseconds between two soft confirmations in normal operation
+    pub block_time_ms: u64,
+    /// Block times without a soft confirmation after which block production is stalled
+    #[serde(default = "default_stall_factor")]
+    pub stall_factor: u32,
+    /// Blocks forced during a stall before the watchdog only alerts
+    #[serde(default = "default_max_recovery_attempts")]
+    pub max_recovery_attempts: u32,
+}
+
+fn default_stall_factor() -> u32 {
+    3
+}
+
+fn default_max_recovery_attempts() -> u32 {
+    3
+}
+
+/// Sequencer lease lock configuration
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SequencerLeaseConfig {
+    /// Path of the lease lock file. Defaults to `sequencer.lock` in the rollup storage directory
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Seconds after which the lease expires if it is not renewed. A running sequencer
+    /// renews it every third of this, whether it produces blocks or not
+    #[serde(default = "default_lease_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Take over the lease even if it is held by another instance.
+    /// Only set this if the other instance is known to be stopped.
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl Default for SequencerLeaseConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            ttl_secs: default_lease_ttl_secs(),
+            force: false,
+        }
+    }
+}
+
+fn default_lease_ttl_secs() -> u64 {
+    30
+}
+
+fn default_proving_claim_ttl_secs() -> u64 {
+    3600
+}
+
+/// Hot standby configuration
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StandbyConfig {
+    /// RPC url of the primary sequencer to follow
+    pub primary_url: String,
+    /// Milliseconds between polls of the primary for new soft confirmations and transactions
+    #[serde(default = "default_standby_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+fn default_standby_poll_interval_ms() -> u64 {
+    500
 }
 
 #[cfg(test)]
@@ -26,6 +222,32 @@ mod tests {
     fn test_correct_config_sequencer() {
         let config = r#"
             min_soft_confirmations_per_commitment = 123
+            max_working_set_bytes = 1048576
+            target_da_cost_per_block = 200000
+            rpc_worker_threads = 4
+            schnorr_private_key = "0101010101010101010101010101010101010101010101010101010101010101"
+            proving_claim_ttl_secs = 600
+            checkpoint_interval = 1000
+            reverting_txs = "skip"
+            tx_execution_budget_ms = 50
+            tx_chunk_size = 500
+            dry_run_blocks = true
+            min_priority_fee_per_gas = 1000
+
+            [lease]
+            path = "/tmp/sequencer.lock"
+            force = true
+
+            [standby]
+            primary_url = "http://localhost:12345"
+
+            [watchdog]
+            block_time_ms = 2000
+
+            [priority_lane]
+            senders = ["0x0000000000000000000000000000000000000001"]
+            reserved_gas = 1000000
+            reserved_bytes = 2048
         "#;
 
         let config_file = create_config_from(config);
@@ -34,7 +256,71 @@ mod tests {
 
         let expected = SequencerConfig {
             min_soft_confirmations_per_commitment: 123,
+            max_working_set_bytes: Some(1048576),
+            target_da_cost_per_block: Some(200000),
+            lease: SequencerLeaseConfig {
+                path: Some(PathBuf::from("/tmp/sequencer.lock")),
+                ttl_secs: 30,
+                force: true,
+            },
+            standby: Some(StandbyConfig {
+                primary_url: "http://localhost:12345".to_string(),
+                poll_interval_ms: 500,
+            }),
+            rpc_worker_threads: Some(4),
+            priority_lane: PriorityLaneConfig {
+                senders: vec![Address::with_last_byte(1)],
+                reserved_gas: 1000000,
+                reserved_bytes: 2048,
+            },
+            schnorr_private_key: Some(
+                "0101010101010101010101010101010101010101010101010101010101010101".to_string(),
+            ),
+            proving_claim_ttl_secs: 600,
+            checkpoint_interval: Some(1000),
+            reverting_txs: RevertingTxPolicy::Skip,
+            tx_execution_budget_ms: Some(50),
+            tx_chunk_size: Some(500),
+            dry_run_blocks: true,
+            watchdog: Some(WatchdogConfig {
+                block_time_ms: 2000,
+                stall_factor: 3,
+                max_recovery_attempts: 3,
+            }),
+            min_priority_fee_per_gas: 1000,
         };
         assert_eq!(config, expected);
     }
+
+    #[test]
+    fn test_max_soft_confirmation_bytes() {
+        let config = SequencerConfig {
+            min_soft_confirmations_per_commitment: 1,
+            max_working_set_bytes: None,
+            target_da_cost_per_block: Some(200000),
+            lease: Default::default(),
+            standby: None,
+            rpc_worker_threads: None,
+            priority_lane: Default::default(),
+            schnorr_private_key: None,
+            proving_claim_ttl_secs: 3600,
+            checkpoint_interval: None,
+            reverting_txs: RevertingTxPolicy::Include,
+            tx_execution_budget_ms: None,
+            tx_chunk_size: None,
+            dry_run_blocks: false,
+            watchdog: None,
+            min_priority_fee_per_gas: 0,
+        };
+
+        assert_eq!(config.max_soft_confirmation_bytes(10), Some(20000));
+        // A zero fee rate is priced as the lowest possible one
+        assert_eq!(config.max_soft_confirmation_bytes(0), Some(200000));
+
+        let config = SequencerConfig {
+            target_da_cost_per_block: None,
+            ..config
+        };
+        assert_eq!(config.max_soft_confirmation_bytes(10), None);
+    }
 }
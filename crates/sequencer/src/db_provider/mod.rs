@@ -1,9 +1,11 @@
-use citrea_evm::{Evm, EvmChainConfig};
+use std::time::Duration;
+
+use citrea_evm::{Evm, EvmChainConfig, SimulationOutcome};
 use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::provider::ProviderResult;
 use reth_primitives::{
-    Account, Address, BlockNumberOrTag, Bytecode, SealedHeader, StorageKey, StorageValue, B256,
-    U256,
+    Account, Address, BlockNumberOrTag, Bytecode, SealedHeader, StorageKey, StorageValue,
+    TransactionSignedEcRecovered, B256, U256,
 };
 use reth_provider::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
@@ -32,6 +34,20 @@ impl<C: sov_modules_api::Context> DbProvider<C> {
         self.evm.get_chain_config(&mut working_set)
     }
 
+    /// Returns the outcome of each of `txs` when executed in order in the next block, halting
+    /// those executing for longer than `budget`. The state changes of the simulation are
+    /// discarded.
+    pub fn simulate_txs(
+        &self,
+        txs: &[TransactionSignedEcRecovered],
+        l1_fee_rate: u64,
+        budget: Option<Duration>,
+    ) -> Vec<SimulationOutcome> {
+        let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+        self.evm
+            .simulate_next_block_txs(txs, l1_fee_rate, budget, &mut working_set)
+    }
+
     pub fn last_block_tx_hashes(&self) -> Vec<B256> {
         let mut working_set = WorkingSet::<C>::new(self.storage.clone());
         let rich_block = self
@@ -0,0 +1,268 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use tracing::{debug, warn};
+
+/// A lease that must be held by a sequencer instance to produce blocks.
+/// Prevents two instances running with the same keys from producing conflicting
+/// soft confirmations.
+///
+/// The default implementation is [`FileLease`]. External lock backends (e.g. etcd or a
+/// database row) can be plugged in by implementing this trait.
+pub trait SequencerLease: Send + Sync {
+    /// Acquires the lease for `ttl`. Fails if the lease is held by another instance and has
+    /// not expired yet, unless `force` is set.
+    fn acquire(&mut self, ttl: Duration, force: bool) -> anyhow::Result<()>;
+
+    /// Extends a lease held by this instance for another `ttl`. Fails if the lease was
+    /// taken over by another instance in the meantime.
+    fn renew(&mut self, ttl: Duration) -> anyhow::Result<()>;
+
+    /// Releases the lease, if it is held by this instance.
+    fn release(&mut self) -> anyhow::Result<()>;
+}
+
+/// A [`SequencerLease`] backed by a lock file on the local file system.
+/// The file contains the id of the holder and the unix timestamp at which the lease expires.
+///
+/// Every read-check-write of the lease file happens under an exclusive `flock` on a guard
+/// file next to it, so two instances can never both take the lease. The OS releases the
+/// `flock` if an instance dies.
+pub struct FileLease {
+    path: PathBuf,
+    holder: String,
+    held: bool,
+}
+
+impl FileLease {
+    /// Creates a lease backed by the file at `path`. The lease is not acquired yet.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        static INSTANCES: AtomicU64 = AtomicU64::new(0);
+        let holder = format!(
+            "{}-{}-{}",
+            std::process::id(),
+            unix_time().as_nanos(),
+            INSTANCES.fetch_add(1, Ordering::Relaxed)
+        );
+        Self {
+            path: path.into(),
+            holder,
+            held: false,
+        }
+    }
+
+    /// Takes the exclusive lock on the guard file, which is held until the returned file
+    /// is dropped.
+    fn lock_guard(&self) -> anyhow::Result<File> {
+        let guard = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(self.path.with_extension("guard"))?;
+        guard.lock_exclusive()?;
+        Ok(guard)
+    }
+
+    /// Returns the current holder and expiry of the lease, if the lock file exists.
+    fn read(&self) -> anyhow::Result<Option<(String, u64)>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let (holder, expiry) = content
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| anyhow::anyhow!("Malformed lease file {}", self.path.display()))?;
+        Ok(Some((holder.to_string(), expiry.parse()?)))
+    }
+
+    fn write(&self, ttl: Duration) -> anyhow::Result<()> {
+        let expiry = (unix_time() + ttl).as_secs();
+        // Write to a temporary file first so that readers never see a partial lease
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, format!("{} {}", self.holder, expiry))?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl SequencerLease for FileLease {
+    fn acquire(&mut self, ttl: Duration, force: bool) -> anyhow::Result<()> {
+        let _guard = self.lock_guard()?;
+        if let Some((holder, expiry)) = self.read()? {
+            if holder != self.holder && expiry > unix_time().as_secs() {
+                if !force {
+                    anyhow::bail!(
+                        "Sequencer lease {} is held by {} until {}. Another sequencer instance might be running. \
+                        Set `lease.force = true` in the sequencer config to take it over",
+                        self.path.display(),
+                        holder,
+                        expiry
+                    );
+                }
+                warn!(
+                    "Forcefully taking over sequencer lease {} held by {}",
+                    self.path.display(),
+                    holder
+                );
+            }
+        }
+        self.write(ttl)?;
+        self.held = true;
+        debug!("Acquired sequencer lease {}", self.path.display());
+        Ok(())
+    }
+
+    fn renew(&mut self, ttl: Duration) -> anyhow::Result<()> {
+        let _guard = self.lock_guard()?;
+        match self.read()? {
+            Some((holder, _)) if holder == self.holder => self.write(ttl),
+            Some((holder, _)) => {
+                self.held = false;
+                anyhow::bail!("Sequencer lease was taken over by {}", holder)
+            }
+            None => {
+                self.held = false;
+                anyhow::bail!("Sequencer lease file {} was removed", self.path.display())
+            }
+        }
+    }
+
+    fn release(&mut self) -> anyhow::Result<()> {
+        if !self.held {
+            return Ok(());
+        }
+        self.held = false;
+        let _guard = self.lock_guard()?;
+        match self.read()? {
+            Some((holder, _)) if holder == self.holder => Ok(fs::remove_file(&self.path)?),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for FileLease {
+    fn drop(&mut self) {
+        if let Err(e) = self.release() {
+            warn!("Failed to release sequencer lease: {:?}", e);
+        }
+    }
+}
+
+pub(crate) fn unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_acquire_renew_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.lock");
+        let mut lease = FileLease::new(&path);
+
+        lease.acquire(TTL, false).unwrap();
+        let (holder, expiry) = lease.read().unwrap().unwrap();
+        assert_eq!(holder, lease.holder);
+        assert!(expiry >= (unix_time() + TTL).as_secs() - 1);
+
+        // Acquiring a lease held by the same instance succeeds
+        lease.acquire(TTL, false).unwrap();
+        lease.renew(TTL * 2).unwrap();
+        assert!(lease.read().unwrap().unwrap().1 > expiry);
+
+        lease.release().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_held_lease_is_not_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.lock");
+        let mut primary = FileLease::new(&path);
+        let mut standby = FileLease::new(&path);
+
+        primary.acquire(TTL, false).unwrap();
+        assert!(standby.acquire(TTL, false).is_err());
+        assert!(standby.renew(TTL).is_err());
+
+        // Releasing a lease held by another instance leaves it in place
+        standby.release().unwrap();
+        assert_eq!(primary.read().unwrap().unwrap().0, primary.holder);
+        primary.renew(TTL).unwrap();
+    }
+
+    #[test]
+    fn test_expired_lease_is_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.lock");
+        let mut primary = FileLease::new(&path);
+        let mut standby = FileLease::new(&path);
+
+        primary.acquire(Duration::ZERO, false).unwrap();
+        standby.acquire(TTL, false).unwrap();
+        assert_eq!(standby.read().unwrap().unwrap().0, standby.holder);
+
+        // The previous holder notices the takeover on renewal and keeps its hands off
+        assert!(primary.renew(TTL).is_err());
+        primary.release().unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_forced_takeover() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.lock");
+        let mut primary = FileLease::new(&path);
+        let mut standby = FileLease::new(&path);
+
+        primary.acquire(TTL, false).unwrap();
+        standby.acquire(TTL, true).unwrap();
+        assert_eq!(standby.read().unwrap().unwrap().0, standby.holder);
+        assert!(primary.renew(TTL).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_acquire_has_a_single_winner() {
+        const INSTANCES: usize = 16;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sequencer.lock");
+        let barrier = Arc::new(Barrier::new(INSTANCES));
+
+        let handles = (0..INSTANCES)
+            .map(|_| {
+                let path = path.clone();
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    let mut lease = FileLease::new(path);
+                    barrier.wait();
+                    let acquired = lease.acquire(TTL, false).is_ok();
+                    // Keep the lease alive until all instances tried to acquire it
+                    barrier.wait();
+                    acquired
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let winners = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|acquired| *acquired)
+            .count();
+        assert_eq!(winners, 1);
+    }
+}
@@ -1,10 +1,23 @@
+mod block_builder;
 mod commitment_controller;
 mod config;
 mod db_provider;
+mod lease;
 mod mempool;
+mod metrics;
+mod proving_claims;
 mod rpc;
 mod sequencer;
+mod tx_status;
 mod utils;
+mod watchdog;
 
-pub use config::SequencerConfig;
+pub use config::{
+    PriorityLaneConfig, RevertingTxPolicy, SequencerConfig, SequencerLeaseConfig, StandbyConfig,
+    WatchdogConfig,
+};
+pub use lease::{FileLease, SequencerLease};
+pub use metrics::{LatencyPercentiles, LatencySummary};
+pub use proving_claims::ProvingClaim;
 pub use sequencer::CitreaSequencer;
+pub use tx_status::{Finality, TransactionStatus};
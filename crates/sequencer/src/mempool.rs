@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
-use reth_primitives::{BaseFeeParamsKind, Chain, ChainSpec};
+use futures::StreamExt;
+use reth_primitives::{BaseFeeParamsKind, Chain, ChainSpec, IntoRecoveredTransaction};
 use reth_tasks::TokioTaskExecutor;
 use reth_transaction_pool::blobstore::NoopBlobStore;
 use reth_transaction_pool::{
-    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, Pool,
-    TransactionValidationTaskExecutor,
+    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, FullTransactionEvent, Pool,
+    TransactionPool, TransactionValidationTaskExecutor,
 };
+use sov_stf_runner::{publish_pending_transaction, PendingTransaction};
 
 pub use crate::db_provider::DbProvider;
 
@@ -39,3 +41,30 @@ pub(crate) fn create_mempool<C: sov_modules_api::Context>(
         Default::default(),
     )
 }
+
+/// Spawns a task publishing the transactions which become executable in the mempool, for the
+/// `newPendingTransactions` subscriptions.
+pub(crate) fn spawn_pending_tx_publisher<C: sov_modules_api::Context>(
+    mempool: Arc<CitreaMempool<C>>,
+) {
+    let mut events = mempool.all_transactions_event_listener();
+    tokio::spawn(async move {
+        while let Some(event) = events.next().await {
+            let FullTransactionEvent::Pending(hash) = event else {
+                continue;
+            };
+            // The transaction may have been included or replaced in between
+            let Some(tx) = mempool.get(&hash) else {
+                continue;
+            };
+            publish_pending_transaction(PendingTransaction {
+                hash: hash.0,
+                raw: tx
+                    .to_recovered_transaction()
+                    .into_signed()
+                    .envelope_encoded()
+                    .to_vec(),
+            });
+        }
+    });
+}
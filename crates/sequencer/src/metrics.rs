@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge_vec, Histogram, HistogramVec, IntCounter,
+    IntCounterVec, IntGaugeVec,
+};
+use serde::Serialize;
+
+/// Number of most recent samples kept per stage to compute the latency summary
+const MAX_SAMPLES: usize = 1024;
+
+/// Maximum number of committed soft confirmations awaiting a proof, in case no prover
+/// reports its proofs to the sequencer
+const MAX_UNPROVEN: usize = 1 << 16;
+
+/// Interval at which the runtime probes measure how late their timers fire
+const RUNTIME_PROBE_INTERVAL: Duration = Duration::from_millis(250);
+
+pub static SEQUENCER_TX_INCLUSION_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sequencer_tx_inclusion_latency_seconds",
+        // metric description
+        "Time from a transaction entering the mempool to its inclusion in a soft confirmation",
+        exponential_buckets(/*start=*/ 1e-2, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_COMMITMENT_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sequencer_commitment_latency_seconds",
+        // metric description
+        "Time from producing a soft confirmation to submitting a sequencer commitment including it",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_PROOF_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sequencer_proof_latency_seconds",
+        // metric description
+        "Time from producing a soft confirmation to a prover reporting a proof of the commitment including it",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_REVERTING_TXS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "sequencer_reverting_txs_total",
+        // metric description
+        "Transactions which reverted when the block builder simulated them, by what was done with them",
+        // labels
+        &["action"]
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_SLOW_TXS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        // metric name
+        "sequencer_slow_txs_total",
+        // metric description
+        "Transactions left out of a block for executing longer than the budget when the block builder simulated them"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_BLOCK_PRODUCTION_STALLS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        // metric name
+        "sequencer_block_production_stalls_total",
+        // metric description
+        "Times the watchdog found no soft confirmation produced within the stall threshold"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_WATCHDOG_RECOVERIES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        // metric name
+        "sequencer_watchdog_recoveries_total",
+        // metric description
+        "Blocks forced by the watchdog to recover from a stall"
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_RUNTIME_WORKERS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        // metric name
+        "sequencer_runtime_workers",
+        // metric description
+        "Worker threads of a sequencer runtime",
+        // labels
+        &["pool"]
+    )
+    .unwrap()
+});
+
+pub static SEQUENCER_RUNTIME_SCHEDULE_DELAY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "sequencer_runtime_schedule_delay_seconds",
+        // metric description
+        "How late the timers of a sequencer runtime fire, growing when its workers are saturated",
+        // labels
+        &["pool"],
+        exponential_buckets(/*start=*/ 1e-4, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Spawns a task on the current runtime which records the schedule delay of the runtime,
+/// labelled with `pool`.
+pub(crate) fn spawn_runtime_probe(pool: &'static str) {
+    let schedule_delay = SEQUENCER_RUNTIME_SCHEDULE_DELAY_SECONDS.with_label_values(&[pool]);
+    tokio::spawn(async move {
+        loop {
+            let start = Instant::now();
+            tokio::time::sleep(RUNTIME_PROBE_INTERVAL).await;
+            let delay = start.elapsed().saturating_sub(RUNTIME_PROBE_INTERVAL);
+            schedule_delay.observe(delay.as_secs_f64());
+        }
+    });
+}
+
+/// Latency percentiles of a single stage, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPercentiles {
+    /// Number of samples the percentiles are computed from
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Latency summary returned by `citrea_getLatencySummary`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    /// Mempool arrival to soft confirmation inclusion, per transaction
+    pub inclusion: LatencyPercentiles,
+    /// Soft confirmation production to sequencer commitment submission, per soft confirmation
+    pub commitment: LatencyPercentiles,
+    /// Soft confirmation production to a prover reporting the proof of its commitment,
+    /// per soft confirmation
+    pub proof: LatencyPercentiles,
+}
+
+/// Tracks the latencies of transactions and soft confirmations through the sequencer.
+/// Every sample is recorded in the prometheus histograms, and the most recent ones are
+/// kept to compute exact percentiles for the RPC summary.
+#[derive(Debug, Default)]
+pub(crate) struct LatencyTracker {
+    inclusion: VecDeque<Duration>,
+    commitment: VecDeque<Duration>,
+    proof: VecDeque<Duration>,
+    /// Soft confirmations which are not in a commitment yet, with the time they were produced
+    uncommitted: VecDeque<(u64, Instant)>,
+    /// Soft confirmations which are in a commitment but not proven yet, with the time they
+    /// were produced
+    unproven: VecDeque<(u64, Instant)>,
+}
+
+impl LatencyTracker {
+    /// Records the inclusion of a transaction which arrived in the mempool at `arrival`.
+    pub fn record_inclusion(&mut self, arrival: Instant) {
+        let latency = arrival.elapsed();
+        SEQUENCER_TX_INCLUSION_LATENCY_SECONDS.observe(latency.as_secs_f64());
+        push_sample(&mut self.inclusion, latency);
+    }
+
+    /// Records that the soft confirmation at `height` was produced.
+    pub fn record_soft_batch(&mut self, height: u64) {
+        self.uncommitted.push_back((height, Instant::now()));
+    }
+
+    /// Records the submission of a commitment for all soft confirmations up to and
+    /// including `end_height`.
+    pub fn record_commitment(&mut self, end_height: u64) {
+        while let Some((height, produced)) = self.uncommitted.front().copied() {
+            if height > end_height {
+                break;
+            }
+            self.uncommitted.pop_front();
+            let latency = produced.elapsed();
+            SEQUENCER_COMMITMENT_LATENCY_SECONDS.observe(latency.as_secs_f64());
+            push_sample(&mut self.commitment, latency);
+
+            if self.unproven.len() == MAX_UNPROVEN {
+                self.unproven.pop_front();
+            }
+            self.unproven.push_back((height, produced));
+        }
+    }
+
+    /// Records that a prover reported the proof of a commitment ending at `end_height`,
+    /// proving all committed soft confirmations up to and including it.
+    pub fn record_proof(&mut self, end_height: u64) {
+        while let Some((height, produced)) = self.unproven.front().copied() {
+            if height > end_height {
+                break;
+            }
+            self.unproven.pop_front();
+            let latency = produced.elapsed();
+            SEQUENCER_PROOF_LATENCY_SECONDS.observe(latency.as_secs_f64());
+            push_sample(&mut self.proof, latency);
+        }
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        LatencySummary {
+            inclusion: percentiles(&self.inclusion),
+            commitment: percentiles(&self.commitment),
+            proof: percentiles(&self.proof),
+        }
+    }
+}
+
+fn push_sample(samples: &mut VecDeque<Duration>, latency: Duration) {
+    if samples.len() == MAX_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(latency);
+}
+
+fn percentiles(samples: &VecDeque<Duration>) -> LatencyPercentiles {
+    if samples.is_empty() {
+        return LatencyPercentiles::default();
+    }
+    let mut sorted: Vec<u64> = samples.iter().map(|d| d.as_millis() as u64).collect();
+    sorted.sort_unstable();
+    let at = |p: usize| sorted[(sorted.len() * p / 100).min(sorted.len() - 1)];
+    LatencyPercentiles {
+        samples: sorted.len(),
+        p50_ms: at(50),
+        p90_ms: at(90),
+        p99_ms: at(99),
+        max_ms: sorted[sorted.len() - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_latency_is_recorded_up_to_end_height() {
+        let mut tracker = LatencyTracker::default();
+        for height in 1..=3 {
+            tracker.record_soft_batch(height);
+        }
+
+        tracker.record_commitment(2);
+        assert_eq!(tracker.summary().commitment.samples, 2);
+        assert_eq!(tracker.uncommitted.len(), 1);
+
+        tracker.record_commitment(3);
+        assert_eq!(tracker.summary().commitment.samples, 3);
+        assert!(tracker.uncommitted.is_empty());
+    }
+
+    #[test]
+    fn test_proof_latency_is_recorded_for_committed_soft_batches() {
+        let mut tracker = LatencyTracker::default();
+        for height in 1..=4 {
+            tracker.record_soft_batch(height);
+        }
+
+        // Soft confirmations which are not committed yet can not be proven
+        tracker.record_commitment(2);
+        tracker.record_proof(4);
+        assert_eq!(tracker.summary().proof.samples, 2);
+
+        tracker.record_commitment(4);
+        tracker.record_proof(3);
+        assert_eq!(tracker.summary().proof.samples, 3);
+        assert_eq!(tracker.unproven.len(), 1);
+
+        // Reporting the same proof again records nothing
+        tracker.record_proof(3);
+        assert_eq!(tracker.summary().proof.samples, 3);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let samples = (1..=100).map(Duration::from_millis).collect();
+        let percentiles = percentiles(&samples);
+        assert_eq!(
+            percentiles,
+            LatencyPercentiles {
+                samples: 100,
+                p50_ms: 51,
+                p90_ms: 91,
+                p99_ms: 100,
+                max_ms: 100,
+            }
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Number of most recent commitment ranges whose claims are remembered
+const MAX_CLAIMS: usize = 1024;
+
+/// Claim of a prover on a commitment range, as returned by `citrea_claimProvingRange`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvingClaim {
+    /// Id of the prover holding the claim
+    pub prover_id: String,
+    /// First L2 height of the commitment range
+    pub start_l2_height: u64,
+    /// Last L2 height of the commitment range
+    pub end_l2_height: u64,
+    /// Unix timestamp in seconds after which the range is handed out to other provers,
+    /// unless it is proven
+    pub expires_at: u64,
+    /// Whether the prover reported the range as proven
+    pub proven: bool,
+}
+
+/// Claims provers running against the chain hold on commitment ranges, so that they
+/// partition the ranges instead of all proving the same ones.
+///
+/// A claim which is not proven before it expires is handed out to the next prover
+/// claiming the range.
+#[derive(Debug, Default)]
+pub(crate) struct ProvingClaims {
+    claims: BTreeMap<(u64, u64), ProvingClaim>,
+}
+
+impl ProvingClaims {
+    /// Claims the range for `prover_id` for `ttl_secs`, or renews its claim, unless another
+    /// prover holds a live claim on it or it is proven. Returns the claim on the range.
+    pub(crate) fn claim(
+        &mut self,
+        prover_id: &str,
+        range: (u64, u64),
+        now: u64,
+        ttl_secs: u64,
+    ) -> ProvingClaim {
+        if let Some(claim) = self.claims.get(&range) {
+            let held_by_other = claim.prover_id != prover_id && claim.expires_at > now;
+            if claim.proven || held_by_other {
+                return claim.clone();
+            }
+        }
+
+        let claim = ProvingClaim {
+            prover_id: prover_id.to_string(),
+            start_l2_height: range.0,
+            end_l2_height: range.1,
+            expires_at: now + ttl_secs,
+            proven: false,
+        };
+        self.insert(range, claim.clone());
+        claim
+    }
+
+    /// Records the range as proven by `prover_id`, whoever claimed it.
+    pub(crate) fn finish(&mut self, prover_id: &str, range: (u64, u64), now: u64) -> ProvingClaim {
+        let claim = ProvingClaim {
+            prover_id: prover_id.to_string(),
+            start_l2_height: range.0,
+            end_l2_height: range.1,
+            expires_at: now,
+            proven: true,
+        };
+        self.insert(range, claim.clone());
+        claim
+    }
+
+    /// Returns the remembered claims, by ascending range.
+    pub(crate) fn claims(&self) -> Vec<ProvingClaim> {
+        self.claims.values().cloned().collect()
+    }
+
+    fn insert(&mut self, range: (u64, u64), claim: ProvingClaim) {
+        self.claims.insert(range, claim);
+        while self.claims.len() > MAX_CLAIMS {
+            self.claims.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proving_claims() {
+        let mut claims = ProvingClaims::default();
+
+        let claim = claims.claim("a", (1, 10), 100, 60);
+        assert_eq!(claim.prover_id, "a");
+        assert_eq!(claim.expires_at, 160);
+
+        // Live claims are kept by their holder, and renewed by it
+        assert_eq!(claims.claim("b", (1, 10), 150, 60).prover_id, "a");
+        assert_eq!(claims.claim("a", (1, 10), 150, 60).expires_at, 210);
+        assert_eq!(claims.claim("b", (11, 20), 150, 60).prover_id, "b");
+
+        // Expired claims are handed out to the next prover
+        assert_eq!(claims.claim("b", (1, 10), 210, 60).prover_id, "b");
+
+        // Proven ranges are not handed out anymore
+        claims.finish("b", (1, 10), 220);
+        let claim = claims.claim("a", (1, 10), 1000, 60);
+        assert_eq!(claim.prover_id, "b");
+        assert!(claim.proven);
+
+        assert_eq!(claims.claims().len(), 2);
+        for start in 0..MAX_CLAIMS as u64 {
+            claims.claim("a", (1000 + start, 1000 + start), 1000, 60);
+        }
+        assert_eq!(claims.claims().len(), MAX_CLAIMS);
+        assert_eq!(claims.claims()[0].start_l2_height, 1000);
+    }
+}
@@ -1,19 +1,33 @@
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
 
 use citrea_evm::Evm;
 use futures::channel::mpsc::UnboundedSender;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
-use reth_primitives::{Bytes, FromRecoveredPooledTransaction, IntoRecoveredTransaction, B256};
+use reth_primitives::{
+    Address, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, IntoRecoveredTransaction,
+    TransactionSignedEcRecovered, B256, U256, U64,
+};
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus};
 use reth_rpc_types_compat::transaction::from_recovered;
-use reth_transaction_pool::{EthPooledTransaction, TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{
+    AllPoolTransactions, EthPooledTransaction, PoolTransaction, TransactionOrigin, TransactionPool,
+    ValidPoolTransaction,
+};
+use sov_db::ledger_db::LedgerDB;
 use sov_mock_da::{MockAddress, MockDaService};
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_modules_api::WorkingSet;
+use sov_rollup_interface::rpc::SignedCheckpoint;
 use tracing::info;
 
+use crate::lease::unix_time;
 use crate::mempool::CitreaMempool;
-use crate::utils::recover_raw_transaction;
+use crate::metrics::{LatencySummary, LatencyTracker};
+use crate::proving_claims::{ProvingClaim, ProvingClaims};
+use crate::tx_status::{DroppedTxs, Finality, TransactionStatus};
+use crate::utils::{next_pending_nonce, recover_raw_transaction};
 
 const ETH_RPC_ERROR: &str = "ETH_RPC_ERROR";
 
@@ -21,6 +35,13 @@ pub(crate) struct RpcContext<C: sov_modules_api::Context> {
     pub mempool: Arc<CitreaMempool<C>>,
     pub l2_force_block_tx: UnboundedSender<()>,
     pub storage: C::Storage,
+    pub latency: Arc<Mutex<LatencyTracker>>,
+    pub ledger_db: LedgerDB,
+    pub dropped_txs: Arc<Mutex<DroppedTxs>>,
+    pub proving_claims: Arc<Mutex<ProvingClaims>>,
+    pub proving_claim_ttl_secs: u64,
+    pub latest_checkpoint: Arc<Mutex<Option<SignedCheckpoint>>>,
+    pub min_priority_fee_per_gas: u64,
 }
 
 pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
@@ -37,6 +58,16 @@ pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
 
         let pool_transaction = EthPooledTransaction::from_recovered_pooled_transaction(recovered);
 
+        if pool_transaction.priority_fee_or_price() < ctx.min_priority_fee_per_gas as u128 {
+            return Err(to_jsonrpsee_error_object(
+                format!(
+                    "max priority fee per gas less than the minimum of {}",
+                    ctx.min_priority_fee_per_gas
+                ),
+                ETH_RPC_ERROR,
+            ));
+        }
+
         // submit the transaction to the pool with a `Local` origin
         let hash: B256 = ctx
             .mempool
@@ -87,5 +118,218 @@ pub(crate) fn create_rpc_module<C: sov_modules_api::Context>(
             },
         }
     })?;
+    rpc.register_async_method("eth_getTransactionCount", |parameters, ctx| async move {
+        let mut params = parameters.sequence();
+        let address: Address = params.next()?;
+        let block_number: Option<BlockNumberOrTag> = params.optional_next()?;
+        info!(
+            "Sequencer: eth_getTransactionCount({}, {:?})",
+            address, block_number
+        );
+
+        let evm = Evm::<C>::default();
+        let mut working_set = WorkingSet::<C>::new(ctx.storage.clone());
+        let nonce = evm.get_transaction_count(address, block_number, &mut working_set)?;
+        if block_number != Some(BlockNumberOrTag::Pending) {
+            return Ok::<U64, ErrorObjectOwned>(nonce);
+        }
+
+        // Bursts of transactions from the same sender wait in the mempool
+        let pool_nonces = ctx
+            .mempool
+            .get_transactions_by_sender(address)
+            .iter()
+            .map(|tx| tx.nonce())
+            .collect::<Vec<_>>();
+        Ok::<U64, ErrorObjectOwned>(U64::from(next_pending_nonce(nonce.to(), pool_nonces)))
+    })?;
+    rpc.register_async_method("citrea_getMempoolTransactions", |_, ctx| async move {
+        info!("Sequencer: citrea_getMempoolTransactions");
+        let txs = ctx
+            .mempool
+            .pooled_transactions()
+            .iter()
+            .map(|tx| {
+                tx.to_recovered_transaction()
+                    .into_signed()
+                    .envelope_encoded()
+            })
+            .collect::<Vec<Bytes>>();
+        Ok::<Vec<Bytes>, ErrorObjectOwned>(txs)
+    })?;
+    rpc.register_async_method("txpool_content", |_, ctx| async move {
+        info!("Sequencer: txpool_content");
+        let AllPoolTransactions { pending, queued } = ctx.mempool.all_transactions();
+        let content = TxpoolContent {
+            pending: group_by_sender(&pending, from_recovered),
+            queued: group_by_sender(&queued, from_recovered),
+        };
+        Ok::<TxpoolContent, ErrorObjectOwned>(content)
+    })?;
+    rpc.register_async_method("txpool_inspect", |_, ctx| async move {
+        info!("Sequencer: txpool_inspect");
+        let AllPoolTransactions { pending, queued } = ctx.mempool.all_transactions();
+        let inspect = TxpoolInspect {
+            pending: group_by_sender(&pending, inspect_summary),
+            queued: group_by_sender(&queued, inspect_summary),
+        };
+        Ok::<TxpoolInspect, ErrorObjectOwned>(inspect)
+    })?;
+    rpc.register_async_method("txpool_status", |_, ctx| async move {
+        info!("Sequencer: txpool_status");
+        let AllPoolTransactions { pending, queued } = ctx.mempool.all_transactions();
+        let status = TxpoolStatus {
+            pending: U64::from(pending.len()),
+            queued: U64::from(queued.len()),
+        };
+        Ok::<TxpoolStatus, ErrorObjectOwned>(status)
+    })?;
+    rpc.register_async_method("citrea_getLatencySummary", |_, ctx| async move {
+        info!("Sequencer: citrea_getLatencySummary");
+        let summary = ctx.latency.lock().unwrap().summary();
+        Ok::<LatencySummary, ErrorObjectOwned>(summary)
+    })?;
+    rpc.register_async_method(
+        "citrea_getTransactionStatus",
+        |parameters, ctx| async move {
+            let hash: B256 = parameters.one()?;
+            info!("Sequencer: citrea_getTransactionStatus({})", hash);
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ctx.storage.clone());
+
+            if let Some(tx) = ctx.mempool.get(&hash) {
+                let state_nonce = evm
+                    .get_transaction_count(*tx.sender(), None, &mut working_set)?
+                    .to::<u64>();
+                let pool_nonces = ctx
+                    .mempool
+                    .get_transactions_by_sender(*tx.sender())
+                    .iter()
+                    .map(|tx| tx.nonce())
+                    .collect::<Vec<_>>();
+                let expected_nonce = next_pending_nonce(state_nonce, pool_nonces);
+                let status = if tx.nonce() < expected_nonce {
+                    TransactionStatus::Pending
+                } else {
+                    TransactionStatus::Queued {
+                        expected_nonce: U64::from(expected_nonce),
+                    }
+                };
+                return Ok::<TransactionStatus, ErrorObjectOwned>(status);
+            }
+
+            let included_in = evm
+                .get_transaction_receipt(hash, &mut working_set)?
+                .and_then(|receipt| receipt.block_number)
+                .map(|block_number| block_number.to::<u64>());
+            if let Some(block_number) = included_in {
+                // L2 blocks up to the last one covered by a sequencer commitment are committed
+                let last_committed_l2_height = ctx
+                    .ledger_db
+                    .get_last_sequencer_commitment_l1_height()
+                    .and_then(|l1_height| match l1_height {
+                        Some(l1_height) => ctx.ledger_db.get_l2_range_by_l1_height(l1_height),
+                        None => Ok(None),
+                    })
+                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?
+                    .map(|(_, end)| end.0);
+                let finality = match last_committed_l2_height {
+                    Some(height) if block_number <= height => Finality::Committed,
+                    _ => Finality::SoftConfirmed,
+                };
+                return Ok::<TransactionStatus, ErrorObjectOwned>(TransactionStatus::Included {
+                    block_number: U64::from(block_number),
+                    finality,
+                });
+            }
+
+            let status = match ctx.dropped_txs.lock().unwrap().reason(&hash) {
+                Some(reason) => TransactionStatus::Dropped { reason },
+                None => TransactionStatus::Unknown,
+            };
+            Ok::<TransactionStatus, ErrorObjectOwned>(status)
+        },
+    )?;
+    rpc.register_async_method("citrea_claimProvingRange", |parameters, ctx| async move {
+        let (prover_id, range) = proving_range_params(parameters)?;
+        info!(
+            "Sequencer: citrea_claimProvingRange({}, {}..={})",
+            prover_id, range.0, range.1
+        );
+        let claim = ctx.proving_claims.lock().unwrap().claim(
+            &prover_id,
+            range,
+            unix_time().as_secs(),
+            ctx.proving_claim_ttl_secs,
+        );
+        Ok::<ProvingClaim, ErrorObjectOwned>(claim)
+    })?;
+    rpc.register_async_method("citrea_finishProvingRange", |parameters, ctx| async move {
+        let (prover_id, range) = proving_range_params(parameters)?;
+        info!(
+            "Sequencer: citrea_finishProvingRange({}, {}..={})",
+            prover_id, range.0, range.1
+        );
+        let claim =
+            ctx.proving_claims
+                .lock()
+                .unwrap()
+                .finish(&prover_id, range, unix_time().as_secs());
+        ctx.latency.lock().unwrap().record_proof(range.1);
+        Ok::<ProvingClaim, ErrorObjectOwned>(claim)
+    })?;
+    rpc.register_async_method("citrea_getLatestCheckpoint", |_, ctx| async move {
+        info!("Sequencer: citrea_getLatestCheckpoint");
+        let checkpoint = ctx.latest_checkpoint.lock().unwrap().clone();
+        Ok::<Option<SignedCheckpoint>, ErrorObjectOwned>(checkpoint)
+    })?;
+    rpc.register_async_method("citrea_getProvingClaims", |_, ctx| async move {
+        info!("Sequencer: citrea_getProvingClaims");
+        let claims = ctx.proving_claims.lock().unwrap().claims();
+        Ok::<Vec<ProvingClaim>, ErrorObjectOwned>(claims)
+    })?;
     Ok(rpc)
 }
+
+/// Groups the mempool transactions by sender and nonce, as served by the `txpool` namespace.
+fn group_by_sender<R>(
+    txs: &[Arc<ValidPoolTransaction<EthPooledTransaction>>],
+    f: impl Fn(TransactionSignedEcRecovered) -> R,
+) -> BTreeMap<Address, BTreeMap<String, R>> {
+    let mut grouped: BTreeMap<Address, BTreeMap<String, R>> = BTreeMap::new();
+    for tx in txs {
+        grouped
+            .entry(*tx.sender())
+            .or_default()
+            .insert(tx.nonce().to_string(), f(tx.to_recovered_transaction()));
+    }
+    grouped
+}
+
+fn inspect_summary(tx: TransactionSignedEcRecovered) -> TxpoolInspectSummary {
+    TxpoolInspectSummary {
+        to: tx.to(),
+        value: U256::from(tx.value()),
+        gas: U256::from(tx.gas_limit()),
+        gas_price: U256::from(tx.max_fee_per_gas()),
+    }
+}
+
+/// Parses the prover id and the L2 height range of the proving claim methods.
+fn proving_range_params(
+    parameters: jsonrpsee::types::Params<'_>,
+) -> Result<(String, (u64, u64)), ErrorObjectOwned> {
+    let mut params = parameters.sequence();
+    let prover_id: String = params.next()?;
+    let start_l2_height: u64 = params.next()?;
+    let end_l2_height: u64 = params.next()?;
+    if start_l2_height > end_l2_height {
+        return Err(ErrorObjectOwned::owned(
+            jsonrpsee::types::error::INVALID_PARAMS_CODE,
+            "Start L2 height is after the end L2 height",
+            None::<()>,
+        ));
+    }
+    Ok((prover_id, (start_l2_height, end_l2_height)))
+}
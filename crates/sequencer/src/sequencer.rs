@@ -1,46 +1,145 @@
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::net::SocketAddr;
-use std::sync::Arc;
-use std::vec;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{thread, vec};
 
+use bitcoin_da::compress_blob;
+use borsh::de::BorshDeserialize;
 use borsh::ser::BorshSerialize;
-use citrea_evm::{CallMessage, RlpEvmTransaction};
-use citrea_stf::runtime::Runtime;
+use citrea_evm::{CallMessage, RlpEvmTransaction, SimulationOutcome};
+use citrea_stf::runtime::{Runtime, RuntimeCall};
 use digest::Digest;
 use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use futures::StreamExt;
 use jsonrpsee::RpcModule;
-use reth_primitives::IntoRecoveredTransaction;
+use reth_primitives::{
+    keccak256, FromRecoveredPooledTransaction, IntoRecoveredTransaction,
+    TransactionSignedEcRecovered, TxHash,
+};
 use reth_provider::BlockReaderIdExt;
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{
+    BestTransactionsAttributes, EthPooledTransaction, TransactionOrigin, TransactionPool,
+    ValidPoolTransaction,
+};
+use secp256k1::{KeyPair, SECP256K1};
+use sequencer_client::{GetSoftBatchResponse, SequencerClient};
 use sov_accounts::Accounts;
 use sov_accounts::Response::{AccountEmpty, AccountExists};
 use sov_db::ledger_db::{LedgerDB, SlotCommit};
-use sov_db::schema::types::{BatchNumber, SlotNumber};
+use sov_db::schema::types::{BatchNumber, SlotNumber, StoredSoftBatch};
 use sov_modules_api::hooks::HookSoftConfirmationInfo;
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    Context, EncodeCall, PrivateKey, SignedSoftConfirmationBatch, SlotData,
-    UnsignedSoftConfirmationBatch, WorkingSet,
+    Context, DispatchCall, EncodeCall, PrivateKey, SignatureScheme, SignedSoftConfirmationBatch,
+    SlotData, UnsignedSoftConfirmationBatch, WorkingSet,
 };
-use sov_modules_stf_blueprint::StfBlueprintTrait;
-use sov_rollup_interface::da::{BlockHeaderTrait, DaData, DaSpec};
+use sov_modules_stf_blueprint::{StfBlueprintTrait, TransactionReceipt, TxEffect};
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaData, DaDataEnvelope, DaSpec};
+use sov_rollup_interface::rpc::{Checkpoint, SignedCheckpoint};
 use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::soft_confirmation::ordering_commitment;
 use sov_rollup_interface::stf::{SoftBatchReceipt, StateTransitionFunction};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_rollup_interface::zk::ZkvmHost;
-use sov_stf_runner::{InitVariant, RunnerConfig};
+use sov_stf_runner::{
+    publish_chain_event, rpc_middleware, ChainEvent, InitVariant, NewBlockEvent, RunnerConfig,
+};
+use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
+use crate::block_builder::{
+    apply_reverting_tx_policy, select_transactions, stream_transactions, BlockLimits, Candidate,
+    RecentInclusions, RECENT_INCLUSION_WINDOW,
+};
 use crate::commitment_controller;
-use crate::config::SequencerConfig;
+use crate::config::{RevertingTxPolicy, SequencerConfig, StandbyConfig};
 use crate::db_provider::DbProvider;
-use crate::mempool::{create_mempool, CitreaMempool};
+use crate::lease::SequencerLease;
+use crate::mempool::{create_mempool, spawn_pending_tx_publisher, CitreaMempool};
+use crate::metrics::{
+    spawn_runtime_probe, LatencyTracker, SEQUENCER_REVERTING_TXS, SEQUENCER_RUNTIME_WORKERS,
+    SEQUENCER_SLOW_TXS,
+};
+use crate::proving_claims::ProvingClaims;
 use crate::rpc::{create_rpc_module, RpcContext};
+use crate::tx_status::{spawn_dropped_tx_tracker, DroppedTxs};
+use crate::utils::{
+    recover_raw_transaction, sign_checkpoint, sign_schnorr, soft_confirmation_timestamp,
+};
+use crate::watchdog::{spawn_watchdog, ProductionHealth};
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 
+/// Returned by `produce_l2_block` when applying the given transactions grew the batch
+/// working set over [`SequencerConfig::max_working_set_bytes`]. Nothing is committed in
+/// that case, so the block can be produced again with fewer transactions, or without the
+/// transaction if a single one exceeds the limit.
+#[derive(Debug)]
+struct WorkingSetLimitExceeded {
+    tx_count: usize,
+    estimated_size: usize,
+    limit: u64,
+}
+
+impl std::fmt::Display for WorkingSetLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Working set of {} bytes exceeds the limit of {} bytes with {} transactions",
+            self.estimated_size, self.limit, self.tx_count
+        )
+    }
+}
+
+impl std::error::Error for WorkingSetLimitExceeded {}
+
+/// Returned by `produce_l2_block` when the compressed soft confirmation is over the size
+/// derived from [`SequencerConfig::target_da_cost_per_block`]. Checked before execution,
+/// so the block can be produced again with fewer transactions.
+#[derive(Debug)]
+struct SizeTargetExceeded {
+    tx_count: usize,
+    compressed_size: usize,
+    limit: u64,
+}
+
+impl std::fmt::Display for SizeTargetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Soft confirmation of {} compressed bytes exceeds the size target of {} bytes with {} transactions",
+            self.compressed_size, self.limit, self.tx_count
+        )
+    }
+}
+
+impl std::error::Error for SizeTargetExceeded {}
+
+/// Returned by `produce_l2_block` when the dry run of a soft confirmation failed. Nothing
+/// is committed in that case, and the `offending` transactions are evicted so that the same
+/// block is not built again.
+#[derive(Debug)]
+struct DryRunFailed {
+    reason: String,
+    offending: Vec<TxHash>,
+}
+
+impl std::fmt::Display for DryRunFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, with {} offending transactions",
+            self.reason,
+            self.offending.len()
+        )
+    }
+}
+
+impl std::error::Error for DryRunFailed {}
+
 pub struct CitreaSequencer<C, Da, Sm, Vm, Stf>
 where
     C: Context,
@@ -63,13 +162,25 @@ where
     storage_manager: Sm,
     state_root: StateRoot<Stf, Vm, Da::Spec>,
     sequencer_pub_key: Vec<u8>,
+    /// Signs soft confirmations once Schnorr signatures are activated
+    schnorr_key_pair: Option<KeyPair>,
     listen_address: SocketAddr,
+    /// Immutable RPC responses cached by the RPC server
+    response_cache_entries: u32,
+    lease: Box<dyn SequencerLease>,
+    latency: Arc<Mutex<LatencyTracker>>,
+    dropped_txs: Arc<Mutex<DroppedTxs>>,
+    proving_claims: Arc<Mutex<ProvingClaims>>,
+    /// Last checkpoint signed every `checkpoint_interval` soft confirmations
+    latest_checkpoint: Arc<Mutex<Option<SignedCheckpoint>>>,
+    recent_inclusions: Arc<Mutex<RecentInclusions>>,
+    health: Arc<Mutex<ProductionHealth>>,
 }
 
 impl<C, Da, Sm, Vm, Stf> CitreaSequencer<C, Da, Sm, Vm, Stf>
 where
     C: Context,
-    Da: DaService,
+    Da: DaService<Error = anyhow::Error>,
     Sm: HierarchicalStorageManager<Da::Spec>,
     Vm: ZkvmHost,
     Stf: StateTransitionFunction<
@@ -92,7 +203,24 @@ where
         sequencer_pub_key: Vec<u8>,
         ledger_db: LedgerDB,
         runner_config: RunnerConfig,
+        mut lease: Box<dyn SequencerLease>,
     ) -> Result<Self, anyhow::Error> {
+        // Refuse to start if another instance is producing blocks.
+        // A standby acquires the lease once it takes over.
+        if config.standby.is_none() {
+            lease.acquire(
+                Duration::from_secs(config.lease.ttl_secs),
+                config.lease.force,
+            )?;
+        }
+
+        let schnorr_key_pair = config
+            .schnorr_private_key
+            .as_deref()
+            .map(|key| KeyPair::from_seckey_str(SECP256K1, key))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Sequencer: invalid schnorr_private_key: {}", e))?;
+
         let (l2_force_block_tx, l2_force_block_rx) = unbounded();
 
         let prev_state_root = match init_variant {
@@ -143,7 +271,16 @@ where
             storage_manager,
             state_root: prev_state_root,
             sequencer_pub_key,
+            schnorr_key_pair,
             listen_address,
+            response_cache_entries: rpc_config.response_cache_entries,
+            lease,
+            latency: Default::default(),
+            dropped_txs: Default::default(),
+            proving_claims: Default::default(),
+            latest_checkpoint: Default::default(),
+            recent_inclusions: Arc::new(Mutex::new(RecentInclusions::new(RECENT_INCLUSION_WINDOW))),
+            health: Default::default(),
         })
     }
 
@@ -155,66 +292,121 @@ where
         let methods = self.register_rpc_methods(methods)?;
 
         let listen_address = self.listen_address;
-        let _handle = tokio::spawn(async move {
-            let server = jsonrpsee::server::ServerBuilder::default()
-                .build([listen_address].as_ref())
-                .await
-                .unwrap();
-
-            let bound_address = server.local_addr().unwrap();
-            if let Some(channel) = channel {
-                channel.send(bound_address).unwrap();
-            }
-            info!("Starting RPC server at {} ", &bound_address);
-
-            let _server_handle = server.start(methods);
-            futures::future::pending::<()>().await;
+        let response_cache_entries = self.response_cache_entries;
+        // The RPC runs on its own runtime so that load spikes can't delay block production
+        let worker_threads = self.config.rpc_worker_threads.unwrap_or_else(|| {
+            std::thread::available_parallelism().map_or(1, |threads| threads.get())
         });
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name("sequencer-rpc")
+            .enable_all()
+            .build()?;
+        SEQUENCER_RUNTIME_WORKERS
+            .with_label_values(&["rpc"])
+            .set(worker_threads as i64);
+
+        std::thread::Builder::new()
+            .name("sequencer-rpc".to_string())
+            .spawn(move || {
+                runtime.block_on(async move {
+                    spawn_runtime_probe("rpc");
+
+                    // Serves HTTP and WebSocket on the same port, subscriptions need WebSocket
+                    let server = jsonrpsee::server::ServerBuilder::default()
+                        .set_middleware(rpc_middleware(response_cache_entries))
+                        .build([listen_address].as_ref())
+                        .await
+                        .unwrap();
+
+                    let bound_address = server.local_addr().unwrap();
+                    if let Some(channel) = channel {
+                        channel.send(bound_address).unwrap();
+                    }
+                    info!("Starting RPC server at {} ", &bound_address);
+
+                    let _server_handle = server.start(methods);
+                    futures::future::pending::<()>().await;
+                })
+            })?;
         Ok(())
     }
 
+    /// Produces a soft confirmation with `txs`, executed in chunks of `tx_chunk_size` as they
+    /// are read. The transactions read are pushed to `included`, so that they can be retried
+    /// when the block grows too large.
     async fn produce_l2_block(
         &mut self,
         da_block: <Da as DaService>::FilteredBlock,
         l1_fee_rate: u64,
-        rlp_txs: Vec<RlpEvmTransaction>,
+        mut txs: impl Iterator<Item = (RlpEvmTransaction, Instant)>,
+        included: &mut Vec<(RlpEvmTransaction, Instant)>,
     ) -> Result<(), anyhow::Error> {
-        debug!(
-            "Sequencer: publishing block with {} transactions",
-            rlp_txs.len()
-        );
+        // Stop producing blocks if another instance took over the lease
+        self.lease
+            .renew(Duration::from_secs(self.config.lease.ttl_secs))?;
+
         let da_height = da_block.header().height();
-        let (l2_height, l1_height) = match self
+        let (l2_height, l1_height, parent_timestamp) = match self
             .ledger_db
             .get_head_soft_batch()
             .expect("Sequencer: Failed to get head soft batch")
         {
-            Some((l2_height, sb)) => (l2_height.0 + 1, sb.da_slot_height),
-            None => (0, da_height),
+            Some((l2_height, sb)) => (l2_height.0 + 1, sb.da_slot_height, sb.timestamp),
+            None => (0, da_height, None),
         };
         anyhow::ensure!(
             l1_height == da_height || l1_height + 1 == da_height,
             "Sequencer: L1 height mismatch, expected {da_height} (or {da_height}-1), got {l1_height}",
         );
+
+        let prestate = self
+            .storage_manager
+            .create_storage_on_l2_height(l2_height)
+            .unwrap();
+
+        // Soft confirmations carry a timestamp from the activation height on
+        let timestamp = if self.stf.soft_batch_timestamped(prestate.clone(), da_height) {
+            let da_block_time = da_block.header().time().secs().max(0) as u64;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_secs();
+            // Only fails if the DA block time went back by more than the window since the parent
+            let timestamp = soft_confirmation_timestamp(now, parent_timestamp, da_block_time)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Sequencer: parent timestamp {:?} is too far from the time {} of DA block {}",
+                        parent_timestamp,
+                        da_block_time,
+                        da_height
+                    )
+                })?;
+            Some(timestamp)
+        } else {
+            None
+        };
+
+        // Soft confirmations carry an ordering commitment from the activation height on
+        let ordering_committed = self
+            .stf
+            .soft_batch_ordering_committed(prestate.clone(), da_height);
+
+        // Soft confirmations are signed with the scheme active at their DA height
+        let scheme = self
+            .stf
+            .soft_batch_signature_scheme(prestate.clone(), da_height);
+        let signer_pub_key = self.signer_pub_key(scheme)?;
+
         let batch_info = HookSoftConfirmationInfo {
             da_slot_height: da_block.header().height(),
             da_slot_hash: da_block.header().hash().into(),
             pre_state_root: self.state_root.clone().as_ref().to_vec(),
-            pub_key: self.sov_tx_signer_priv_key.pub_key().try_to_vec().unwrap(),
+            pub_key: signer_pub_key.clone(),
             l1_fee_rate,
+            timestamp,
         };
         let mut signed_batch: SignedSoftConfirmationBatch = batch_info.clone().into();
-        // initially create sc info and call begin soft confirmation hook with it
-        let call_txs = CallMessage { txs: rlp_txs };
-        let raw_message =
-            <Runtime<C, Da::Spec> as EncodeCall<citrea_evm::Evm<C>>>::encode_call(call_txs);
-        let signed_blob = self.make_blob(raw_message);
-        let txs = vec![signed_blob.clone()];
-
-        let prestate = self
-            .storage_manager
-            .create_storage_on_l2_height(l2_height)
-            .unwrap();
 
         info!(
             "Applying soft batch on DA block: {}",
@@ -231,23 +423,143 @@ where
             da_block.header(),
             &mut signed_batch,
         ) {
-            (Ok(()), batch_workspace) => {
-                let (batch_workspace, tx_receipts) =
-                    self.stf.apply_soft_batch_txs(txs.clone(), batch_workspace);
+            (Ok(()), mut batch_workspace) => {
+                // Large blocks are executed in chunks, each its own sov transaction with the
+                // next nonce, as soon as they are read. If a batch failed the nonce must be
+                // refetched, so it is always fetched from state.
+                let chunk_size = self.config.tx_chunk_size.unwrap_or(usize::MAX).max(1);
+                let mut nonce = self.get_nonce();
+                // Repeated transactions would be skipped by the EVM
+                let mut tx_hashes = HashSet::new();
+                let mut received = vec![];
+                let mut blobs = vec![];
+                let mut chunk_tx_hashes = vec![];
+                let mut tx_receipts = vec![];
+                let mut compressed_size = 0;
+                loop {
+                    let mut chunk_hashes = vec![];
+                    let chunk: Vec<RlpEvmTransaction> = txs
+                        .by_ref()
+                        .filter_map(|(tx, arrival)| {
+                            let hash = keccak256(&tx.rlp);
+                            tx_hashes.insert(hash).then(|| {
+                                received.push((arrival, hash));
+                                chunk_hashes.push(hash);
+                                included.push((tx.clone(), arrival));
+                                tx
+                            })
+                        })
+                        .take(chunk_size)
+                        .collect();
+                    let last_chunk = chunk.len() < chunk_size;
+                    // An empty block still has a sov transaction
+                    if chunk.is_empty() && !blobs.is_empty() {
+                        break;
+                    }
+                    let tx_count = included.len();
+
+                    let call_txs = CallMessage { txs: chunk };
+                    let raw_message =
+                        <Runtime<C, Da::Spec> as EncodeCall<citrea_evm::Evm<C>>>::encode_call(
+                            call_txs,
+                        );
+                    let blob = self.make_blob(raw_message, nonce);
+                    nonce += 1;
+
+                    // Checked before each chunk is executed, and after it for the working set,
+                    // so that an oversized block is given up on without executing the rest of it.
+                    // The size target is only economic, so a single transaction is let through.
+                    // A single transaction over the working set limit is evicted instead, and an
+                    // empty block can not be made any smaller, so it is always let through
+                    if let Some(limit) = self.config.max_soft_confirmation_bytes(l1_fee_rate) {
+                        compressed_size += compress_blob(&blob).len();
+                        if compressed_size as u64 > limit && tx_count > 1 {
+                            batch_workspace.revert();
+                            return Err(SizeTargetExceeded {
+                                tx_count,
+                                compressed_size,
+                                limit,
+                            }
+                            .into());
+                        }
+                    }
+
+                    let (workspace, receipts) = self.stf.apply_soft_batch_txs(
+                        &pub_key,
+                        vec![blob.clone()],
+                        batch_workspace,
+                    );
+                    batch_workspace = workspace;
+                    tx_receipts.extend(receipts);
+                    blobs.push(blob);
+                    chunk_tx_hashes.push(chunk_hashes);
+
+                    if let Some(limit) = self.config.max_working_set_bytes {
+                        let estimated_size = batch_workspace.estimated_size();
+                        if estimated_size as u64 > limit && tx_count > 0 {
+                            warn!(
+                                "Sequencer: working set of {} bytes exceeds the limit of {} bytes after {} transactions, reverting batch workspace",
+                                estimated_size, limit, tx_count
+                            );
+                            batch_workspace.revert();
+                            return Err(WorkingSetLimitExceeded {
+                                tx_count,
+                                estimated_size,
+                                limit,
+                            }
+                            .into());
+                        }
+                    }
+
+                    if last_chunk {
+                        break;
+                    }
+                }
+                debug!(
+                    "Sequencer: publishing block with {} transactions",
+                    included.len()
+                );
+
+                // Commit to the order the transactions were received in, which the block order
+                // may differ from. Transactions received at the same instant keep their block order.
+                let ordering_commitment = ordering_committed.then(|| {
+                    received.sort_by_key(|(arrival, _)| *arrival);
+                    ordering_commitment::<<C as sov_modules_api::Spec>::Hasher>(
+                        received.into_iter().map(|(_, hash)| hash.0),
+                    )
+                });
 
                 // create the unsigned batch with the txs then sign th sc
                 let unsigned_batch = UnsignedSoftConfirmationBatch::new(
                     da_block.header().height(),
                     da_block.header().hash().into(),
                     self.state_root.clone().as_ref().to_vec(),
-                    txs,
+                    blobs,
                     l1_fee_rate,
+                    timestamp,
+                    ordering_commitment,
                 );
 
-                let mut signed_soft_batch = self.sign_soft_confirmation_batch(unsigned_batch);
+                if self.config.dry_run_blocks {
+                    let mut candidate = unsigned_candidate::<C>(&unsigned_batch, pub_key.clone());
+                    if let Err(e) = self.dry_run_soft_batch(
+                        prestate.clone(),
+                        da_block.header(),
+                        &mut candidate,
+                        &tx_receipts,
+                        &chunk_tx_hashes,
+                    ) {
+                        warn!("{}, not publishing it \n reverting batch workspace", e);
+                        batch_workspace.revert();
+                        return Err(e.into());
+                    }
+                }
+
+                let mut signed_soft_batch =
+                    self.sign_soft_confirmation_batch(unsigned_batch, scheme)?;
 
                 let (batch_receipt, checkpoint) = self.stf.end_soft_batch(
-                    self.sequencer_pub_key.as_ref(),
+                    &signer_pub_key,
                     &mut signed_soft_batch,
                     tx_receipts,
                     batch_workspace,
@@ -298,8 +610,17 @@ where
                     soft_confirmation_signature: signed_soft_batch.signature().to_vec(),
                     pub_key: signed_soft_batch.pub_key().to_vec(),
                     l1_fee_rate: signed_soft_batch.l1_fee_rate(),
+                    timestamp: signed_soft_batch.timestamp(),
+                    ordering_commitment: signed_soft_batch.ordering_commitment(),
+                    outcome: batch_receipt.soft_confirmation_outcome,
                 };
 
+                // Record the commit before touching the state, so that a crash between
+                // finalizing the state and committing the ledger entry is detected at startup
+                let soft_batch_number = self.ledger_db.get_next_items_numbers().soft_batch_number;
+                self.ledger_db
+                    .put_pending_soft_batch_commit(BatchNumber(soft_batch_number))?;
+
                 // TODO: this will only work for mock da
                 // when https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218
                 // is merged, rpc will access up to date storage then we won't need to finalize rigth away.
@@ -310,26 +631,49 @@ where
                 tracing::debug!("Finalizing l2 height: {:?}", l2_height);
                 self.storage_manager.finalize_l2(l2_height)?;
 
+                let new_block = NewBlockEvent {
+                    l2_height,
+                    hash: signed_soft_batch.hash(),
+                    da_slot_height: da_block.header().height(),
+                    state_root: next_state_root.as_ref().to_vec(),
+                };
                 self.state_root = next_state_root;
 
+                #[cfg(feature = "chaos")]
+                if sov_mock_da::chaos::should_fail(sov_mock_da::chaos::Fault::LedgerWriteError) {
+                    anyhow::bail!("Sequencer: injected ledger write failure");
+                }
+
                 self.ledger_db.commit_soft_batch(soft_batch_receipt, true)?;
+                publish_chain_event(ChainEvent::NewBlock(new_block));
 
-                self.mempool
-                    .remove_transactions(self.db_provider.last_block_tx_hashes());
+                // Inclusions are only recorded once the soft confirmation is committed
+                {
+                    let mut latency = self.latency.lock().unwrap();
+                    for (_, arrival) in included.iter() {
+                        latency.record_inclusion(*arrival);
+                    }
+                    latency.record_soft_batch(soft_batch_number);
+                }
+                self.sign_checkpoint_if_due(soft_batch_number, signed_soft_batch.hash());
+                self.health.lock().unwrap().record_block(l2_height);
 
-                // connect L1 and L2 height
-                self.ledger_db
-                    .extend_l2_range_of_l1_slot(
-                        SlotNumber(da_block.header().height()),
-                        BatchNumber(l2_height),
-                    )
-                    .expect("Sequencer: Failed to set L1 L2 connection");
+                let included = self.db_provider.last_block_tx_hashes();
+                self.mempool.remove_transactions(included.clone());
+                self.recent_inclusions
+                    .lock()
+                    .unwrap()
+                    .record_block(included);
             }
             (Err(err), batch_workspace) => {
                 warn!(
                     "Failed to apply soft confirmation hook: {:?} \n reverting batch workspace",
                     err
                 );
+                self.health
+                    .lock()
+                    .unwrap()
+                    .record_error(format!("Failed to apply soft confirmation hook: {:?}", err));
                 batch_workspace.revert();
             }
         }
@@ -340,19 +684,55 @@ where
         // TODO: hotfix for mock da
         self.da_service.get_block_at(1).await.unwrap();
 
+        spawn_runtime_probe("block_production");
+        spawn_dropped_tx_tracker(&self.mempool, self.dropped_txs.clone());
+        spawn_pending_tx_publisher(self.mempool.clone());
+
+        if let Some(standby) = self.config.standby.clone() {
+            self.follow_primary(standby).await?;
+        }
+        self.recover_commitment_schedule().await?;
+        if let Some(watchdog) = self.config.watchdog.clone() {
+            spawn_watchdog(
+                watchdog,
+                self.health.clone(),
+                self.mempool.clone(),
+                self.l2_force_block_tx.clone(),
+            );
+        }
+
+        // The lease is renewed on a timer as well as before each block, so that an idle
+        // primary keeps it. The interval panics on a zero period.
+        let lease_ttl = Duration::from_secs(self.config.lease.ttl_secs);
+        let mut lease_renewal =
+            tokio::time::interval((lease_ttl / 3).max(Duration::from_millis(100)));
+
         loop {
-            if (self.l2_force_block_rx.next().await).is_some() {
+            let force_block = tokio::select! {
+                _ = lease_renewal.tick() => {
+                    // Stop producing blocks if another instance took over the lease
+                    self.lease.renew(lease_ttl)?;
+                    continue;
+                }
+                force_block = self.l2_force_block_rx.next() => force_block,
+            };
+            if force_block.is_some() {
                 // best txs with base fee
                 // get base fee from last blocks => header => next base fee() function
                 let cfg: citrea_evm::EvmChainConfig = self.db_provider.cfg();
 
-                let base_fee = self
+                let latest_header = self
                     .db_provider
                     .latest_header()
                     .expect("Failed to get latest header")
-                    .map(|header| header.unseal().next_block_base_fee(cfg.base_fee_params))
-                    .expect("Failed to get next block base fee")
-                    .unwrap();
+                    .expect("Latest header should be set")
+                    .unseal();
+                let base_fee = latest_header
+                    .next_block_base_fee(cfg.base_fee_params)
+                    .expect("Failed to get next block base fee");
+                // The gas limit can grow on a schedule, the block is built against the limit the
+                // kernel enforces on it
+                let block_gas_limit = cfg.block_gas_limit_at(latest_header.number + 1);
 
                 let best_txs_with_base_fee = self.mempool.best_transactions_with_attributes(
                     BestTransactionsAttributes::base_fee(base_fee),
@@ -388,6 +768,10 @@ where
                     "Sequencer: last finalized height: {:?}",
                     last_finalized_height
                 );
+                self.health
+                    .lock()
+                    .unwrap()
+                    .record_da_height(last_finalized_height);
 
                 let l1_fee_rate = self.da_service.get_fee_rate().await.unwrap();
 
@@ -407,7 +791,19 @@ where
                                 );
                                 let da_block =
                                     self.da_service.get_block_at(skipped_height).await.unwrap();
-                                self.produce_l2_block(da_block, l1_fee_rate, vec![]).await?;
+                                match self
+                                    .produce_l2_block(
+                                        da_block,
+                                        l1_fee_rate,
+                                        std::iter::empty(),
+                                        &mut vec![],
+                                    )
+                                    .await
+                                {
+                                    // An empty block has no transactions to evict
+                                    Err(e) if e.is::<DryRunFailed>() => warn!("Sequencer: {}", e),
+                                    res => res?,
+                                }
                             }
                         }
                         let prev_l1_height = last_finalized_height - 1;
@@ -447,38 +843,57 @@ where
 
                         info!("Sequencer: submitting commitment: {:?}", commitment);
 
-                        // submit commitment
-                        self.da_service
+                        // submit commitment, if it fails it is retried with the next L1 block
+                        match self
+                            .da_service
                             .send_transaction(
                                 DaData::SequencerCommitment(commitment)
-                                    .try_to_vec()
-                                    .unwrap()
+                                    .to_envelope()
                                     .as_slice(),
                             )
                             .await
-                            .expect("Sequencer: Failed to send commitment");
-
-                        self.ledger_db
-                            .set_last_sequencer_commitment_l1_height(SlotNumber(
-                                commitment_info.l1_height_range.end().0,
-                            ))
-                            .expect("Sequencer: Failed to set last sequencer commitment L1 height");
+                        {
+                            Ok(()) => {
+                                self.ledger_db
+                                    .set_last_sequencer_commitment_l1_height(SlotNumber(
+                                        commitment_info.l1_height_range.end().0,
+                                    ))
+                                    .expect(
+                                        "Sequencer: Failed to set last sequencer commitment L1 height",
+                                    );
+
+                                self.latency
+                                    .lock()
+                                    .unwrap()
+                                    .record_commitment(l2_range_to_submit.end().0);
+                            }
+                            Err(e) => {
+                                warn!("Sequencer: failed to send commitment: {:?}", e);
+                                self.health
+                                    .lock()
+                                    .unwrap()
+                                    .record_error(format!("failed to send commitment: {:?}", e));
+                            }
+                        }
                     }
 
                     // TODO: this is where we would include forced transactions from the new L1 block
                 }
 
-                // TODO: implement block builder instead of just including every transaction in order
-                let rlp_txs: Vec<RlpEvmTransaction> = best_txs_with_base_fee
-                    .into_iter()
-                    .map(|tx| {
-                        tx.to_recovered_transaction()
-                            .into_signed()
-                            .envelope_encoded()
-                            .to_vec()
-                    })
-                    .map(|rlp| RlpEvmTransaction { rlp })
-                    .collect();
+                let priority_lane = &self.config.priority_lane;
+                let limits = BlockLimits {
+                    gas: block_gas_limit,
+                    // Sizes are only tracked to keep the reserved bytes free
+                    bytes: (priority_lane.reserved_bytes > 0)
+                        .then(|| self.config.max_soft_confirmation_bytes(l1_fee_rate))
+                        .flatten(),
+                    // Blocks with more transactions are rejected by the kernel
+                    txs: cfg.max_txs_per_block,
+                };
+                let budget = self
+                    .config
+                    .tx_execution_budget_ms
+                    .map(Duration::from_millis);
 
                 let last_finalized_block = self
                     .da_service
@@ -486,19 +901,511 @@ where
                     .await
                     .unwrap();
 
-                self.produce_l2_block(last_finalized_block, l1_fee_rate, rlp_txs)
-                    .await?;
+                let mut included = vec![];
+                let mut result = match self.config.tx_chunk_size {
+                    // Without a priority lane or simulation, the block is picked in mempool order
+                    // while the mempool is drained on another thread, so that its first chunks
+                    // are executed while the rest is still being drained
+                    Some(chunk_size)
+                        if priority_lane.senders.is_empty()
+                            && self.config.reverting_txs == RevertingTxPolicy::Include
+                            && budget.is_none() =>
+                    {
+                        let (tx_sender, tx_receiver) = mpsc::sync_channel(chunk_size.max(1));
+                        let recent_inclusions = self.recent_inclusions.clone();
+                        let drainer = thread::spawn(move || {
+                            let mut already_included = vec![];
+                            let candidates = mempool_candidates(
+                                best_txs_with_base_fee,
+                                &recent_inclusions,
+                                &mut already_included,
+                            );
+                            for tx in stream_transactions(candidates, limits) {
+                                // The block was given up on
+                                if tx_sender.send(tx).is_err() {
+                                    break;
+                                }
+                            }
+                            already_included
+                        });
+                        let result = self
+                            .produce_l2_block(
+                                last_finalized_block.clone(),
+                                l1_fee_rate,
+                                tx_receiver.into_iter(),
+                                &mut included,
+                            )
+                            .await;
+                        let already_included =
+                            drainer.join().expect("Sequencer: mempool drainer panicked");
+                        self.drop_already_included(already_included);
+                        result
+                    }
+                    _ => {
+                        let mut already_included = vec![];
+                        let candidates = mempool_candidates(
+                            best_txs_with_base_fee,
+                            &self.recent_inclusions,
+                            &mut already_included,
+                        );
+                        let selection = select_transactions(candidates, priority_lane, limits);
+                        self.drop_already_included(already_included);
+                        if selection.spilled_priority > 0 {
+                            warn!(
+                                "Sequencer: {} priority lane transactions don't fit in the block, leaving them for the next one",
+                                selection.spilled_priority
+                            );
+                        }
+                        let txs = if self.config.reverting_txs == RevertingTxPolicy::Include
+                            && budget.is_none()
+                        {
+                            selection.txs
+                        } else {
+                            self.simulate_selected_txs(selection.txs, budget, l1_fee_rate)
+                        };
+                        self.produce_l2_block(
+                            last_finalized_block.clone(),
+                            l1_fee_rate,
+                            txs.into_iter(),
+                            &mut included,
+                        )
+                        .await
+                    }
+                };
+
+                // If the batch grows too large, retry with half of the transactions.
+                // The rest stay in the mempool and are picked up by the next block.
+                // A single transaction over the working set limit on its own is evicted instead,
+                // otherwise every following block would be given up on for it.
+                loop {
+                    match result {
+                        Err(e)
+                            if e.is::<WorkingSetLimitExceeded>()
+                                || e.is::<SizeTargetExceeded>() =>
+                        {
+                            let mut txs = std::mem::take(&mut included);
+                            if e.is::<WorkingSetLimitExceeded>() && txs.len() == 1 {
+                                let (tx, _) = txs.pop().unwrap();
+                                warn!("Sequencer: {}, evicting the transaction", e);
+                                self.mempool.remove_transactions(vec![keccak256(&tx.rlp)]);
+                            } else {
+                                warn!("Sequencer: {}, retrying with fewer transactions", e);
+                                txs.truncate(txs.len() / 2);
+                            }
+                            result = self
+                                .produce_l2_block(
+                                    last_finalized_block.clone(),
+                                    l1_fee_rate,
+                                    txs.into_iter(),
+                                    &mut included,
+                                )
+                                .await;
+                        }
+                        Err(e) if e.is::<DryRunFailed>() => {
+                            let e = e.downcast::<DryRunFailed>().unwrap();
+                            warn!("Sequencer: {}, evicting them from the mempool", e);
+                            self.mempool.remove_transactions(e.offending);
+                            break;
+                        }
+                        res => break res?,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drops from the mempool the transactions found there again after being included in a
+    /// recent soft confirmation
+    fn drop_already_included(&self, already_included: Vec<TxHash>) {
+        if !already_included.is_empty() {
+            warn!(
+                "Sequencer: dropping {} mempool transactions already included in a recent soft confirmation",
+                already_included.len()
+            );
+            self.mempool.remove_transactions(already_included);
+        }
+    }
+
+    /// Simulates the selected transactions on the pending state. Those executing for longer
+    /// than `budget` are left out of the block and stay in the mempool, and the reverting
+    /// transaction policy is applied to the rest. Skipped reverting transactions are dropped
+    /// from the mempool.
+    fn simulate_selected_txs(
+        &self,
+        txs: Vec<(RlpEvmTransaction, Instant)>,
+        budget: Option<Duration>,
+        l1_fee_rate: u64,
+    ) -> Vec<(RlpEvmTransaction, Instant)> {
+        let recovered = txs
+            .iter()
+            .map(|(tx, _)| {
+                TransactionSignedEcRecovered::try_from(tx.clone())
+                    .expect("Mempool transactions are valid")
+            })
+            .collect::<Vec<_>>();
+        let outcomes = self
+            .db_provider
+            .simulate_txs(&recovered, l1_fee_rate, budget);
+
+        let mut simulated = Vec::with_capacity(txs.len());
+        let mut timed_out = 0;
+        for ((tx, recovered), outcome) in txs.into_iter().zip(&recovered).zip(outcomes) {
+            if outcome == SimulationOutcome::TimedOut {
+                timed_out += 1;
+                continue;
+            }
+            let succeeded = outcome == SimulationOutcome::Succeeded;
+            simulated.push(((tx, recovered.hash()), recovered.signer(), succeeded));
+        }
+        if timed_out > 0 {
+            warn!(
+                "Sequencer: leaving {} transactions over the execution budget out of the block",
+                timed_out
+            );
+            SEQUENCER_SLOW_TXS.inc_by(timed_out);
+        }
+
+        let policy = self.config.reverting_txs;
+        if policy == RevertingTxPolicy::Include {
+            return simulated.into_iter().map(|((tx, _), _, _)| tx).collect();
+        }
+        let selection = apply_reverting_tx_policy(simulated, policy);
+        if selection.reverted == 0 {
+            return selection.txs.into_iter().map(|(tx, _)| tx).collect();
+        }
+
+        match policy {
+            RevertingTxPolicy::Skip => {
+                warn!(
+                    "Sequencer: dropping {} mempool transactions which revert",
+                    selection.skipped.len()
+                );
+                SEQUENCER_REVERTING_TXS
+                    .with_label_values(&["skipped"])
+                    .inc_by(selection.skipped.len() as u64);
+                self.mempool
+                    .remove_transactions(selection.skipped.iter().map(|(_, hash)| *hash).collect());
+            }
+            _ => {
+                SEQUENCER_REVERTING_TXS
+                    .with_label_values(&["deprioritized"])
+                    .inc_by(selection.reverted as u64);
+            }
+        }
+        selection.txs.into_iter().map(|(tx, _)| tx).collect()
+    }
+
+    /// Follows the primary sequencer as a hot standby until the lease can be acquired.
+    ///
+    /// Soft confirmations of the primary are applied as they are published and its mempool
+    /// is mirrored into the local one, so that block production can continue from the
+    /// primary's head as soon as its lease expires.
+    async fn follow_primary(&mut self, standby: StandbyConfig) -> Result<(), anyhow::Error> {
+        info!(
+            "Sequencer: running as hot standby of {}",
+            standby.primary_url
+        );
+        let client = SequencerClient::new(standby.primary_url);
+        let ttl = Duration::from_secs(self.config.lease.ttl_secs);
+        let poll_interval = Duration::from_millis(standby.poll_interval_ms);
+        let mut last_scanned_da_height = None;
+
+        loop {
+            // Blocks can not be produced before taking over, drop requests to publish one
+            while let Ok(Some(())) = self.l2_force_block_rx.try_next() {}
+
+            match self
+                .sync_from_primary(&client, &mut last_scanned_da_height)
+                .await
+            {
+                Err(e) if is_connection_error(&e) => {
+                    debug!("Sequencer: primary is not reachable: {:?}", e)
+                }
+                res => res?,
             }
+
+            if let Err(e) = self.mirror_primary_mempool(&client).await {
+                debug!("Sequencer: failed to mirror primary mempool: {:?}", e);
+            }
+
+            match self.lease.acquire(ttl, false) {
+                Ok(()) => break,
+                Err(e) => debug!("Sequencer: lease is not available yet: {}", e),
+            }
+
+            sleep(poll_interval).await;
+        }
+
+        info!("Sequencer: acquired the lease, taking over block production");
+
+        // The primary might have published soft confirmations right before its lease expired
+        match self
+            .sync_from_primary(&client, &mut last_scanned_da_height)
+            .await
+        {
+            Err(e) if is_connection_error(&e) => {
+                debug!("Sequencer: primary is not reachable: {:?}", e)
+            }
+            res => res?,
+        }
+
+        // Commitments might have been sent in DA blocks no soft confirmation is built on yet
+        let last_finalized_height = self
+            .da_service
+            .get_last_finalized_block_header()
+            .await?
+            .height();
+        let scan_from = last_scanned_da_height.map_or(last_finalized_height, |h| h + 1);
+        for height in scan_from..=last_finalized_height {
+            let da_block = self.da_service.get_block_at(height).await?;
+            self.track_sequencer_commitments(&da_block).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the soft confirmations the primary published on top of the local head.
+    async fn sync_from_primary(
+        &mut self,
+        client: &SequencerClient,
+        last_scanned_da_height: &mut Option<u64>,
+    ) -> Result<(), anyhow::Error> {
+        loop {
+            let next_height = self.ledger_db.get_next_items_numbers().soft_batch_number;
+            let Some(soft_batch) = client.get_soft_batch::<Da::Spec>(next_height).await? else {
+                return Ok(());
+            };
+
+            let da_block = self
+                .da_service
+                .get_block_at(soft_batch.da_slot_height)
+                .await?;
+            if *last_scanned_da_height < Some(soft_batch.da_slot_height) {
+                self.track_sequencer_commitments(&da_block).await?;
+                *last_scanned_da_height = Some(soft_batch.da_slot_height);
+            }
+
+            self.apply_primary_soft_batch(da_block, soft_batch)?;
+        }
+    }
+
+    /// Applies and commits a soft confirmation produced by the primary sequencer.
+    fn apply_primary_soft_batch(
+        &mut self,
+        da_block: <Da as DaService>::FilteredBlock,
+        soft_batch: GetSoftBatchResponse,
+    ) -> Result<(), anyhow::Error> {
+        anyhow::ensure!(
+            soft_batch.pre_state_root.as_slice() == self.state_root.as_ref(),
+            "Sequencer: pre state root of primary soft batch 0x{} does not match the local state root",
+            hex::encode(soft_batch.hash)
+        );
+
+        let l2_height = match self.ledger_db.get_head_soft_batch()? {
+            Some((l2_height, _)) => l2_height.0 + 1,
+            None => 0,
+        };
+        let pre_state = self
+            .storage_manager
+            .create_storage_on_l2_height(l2_height)?;
+
+        anyhow::ensure!(
+            self.is_sequencer_pub_key(&soft_batch.pub_key),
+            "Sequencer: primary soft batch 0x{} is not signed with a key of the sequencer",
+            hex::encode(soft_batch.hash)
+        );
+
+        let slot_result = self.stf.apply_soft_batch(
+            soft_batch.pub_key.as_slice(),
+            &self.state_root,
+            pre_state,
+            Default::default(),
+            da_block.header(),
+            &da_block.validity_condition(),
+            &mut soft_batch.clone().into(),
+        );
+
+        anyhow::ensure!(
+            slot_result.state_root.as_ref() == soft_batch.post_state_root.as_slice(),
+            "Sequencer: post state root mismatch for primary soft batch 0x{}",
+            hex::encode(soft_batch.hash)
+        );
+
+        let batch_receipt = slot_result
+            .batch_receipts
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Sequencer: soft batch produced no receipt"))?;
+
+        let soft_batch_receipt = SoftBatchReceipt::<_, _, Da::Spec> {
+            pre_state_root: self.state_root.as_ref().to_vec(),
+            post_state_root: slot_result.state_root.as_ref().to_vec(),
+            phantom_data: PhantomData::<u64>,
+            batch_hash: batch_receipt.batch_hash,
+            da_slot_hash: da_block.header().hash(),
+            da_slot_height: da_block.header().height(),
+            tx_receipts: batch_receipt.tx_receipts,
+            soft_confirmation_signature: soft_batch.soft_confirmation_signature,
+            pub_key: soft_batch.pub_key,
+            l1_fee_rate: soft_batch.l1_fee_rate,
+            timestamp: soft_batch.timestamp,
+            ordering_commitment: soft_batch.ordering_commitment,
+            outcome: batch_receipt.soft_confirmation_outcome,
+        };
+
+        self.ledger_db.put_pending_soft_batch_commit(BatchNumber(
+            self.ledger_db.get_next_items_numbers().soft_batch_number,
+        ))?;
+        self.storage_manager
+            .save_change_set_l2(l2_height, slot_result.change_set)?;
+        self.storage_manager.finalize_l2(l2_height)?;
+
+        self.state_root = slot_result.state_root;
+
+        self.ledger_db.commit_soft_batch(soft_batch_receipt, true)?;
+        publish_chain_event(ChainEvent::NewBlock(NewBlockEvent {
+            l2_height,
+            hash: soft_batch.hash,
+            da_slot_height: da_block.header().height(),
+            state_root: soft_batch.post_state_root,
+        }));
+
+        let included = self.db_provider.last_block_tx_hashes();
+        self.mempool.remove_transactions(included.clone());
+        self.recent_inclusions
+            .lock()
+            .unwrap()
+            .record_block(included);
+
+        debug!(
+            "Sequencer: applied primary soft batch 0x{}",
+            hex::encode(soft_batch.hash)
+        );
+        Ok(())
+    }
+
+    /// Records the commitments the sequencer sent before it stopped but did not record, so
+    /// that the next commitment starts after them instead of covering their range again.
+    async fn recover_commitment_schedule(&self) -> Result<(), anyhow::Error> {
+        let last_finalized_height = self
+            .da_service
+            .get_last_finalized_block_header()
+            .await?
+            .height();
+        let Some(heights) = commitment_controller::get_commitment_recovery_range(
+            &self.ledger_db,
+            last_finalized_height,
+        )?
+        else {
+            return Ok(());
+        };
+
+        debug!(
+            "Sequencer: scanning DA heights {:?} for unrecorded commitments",
+            heights
+        );
+        for height in heights {
+            let da_block = self.da_service.get_block_at(height).await?;
+            self.track_sequencer_commitments(&da_block).await?;
+        }
+        info!(
+            "Sequencer: last commitment covers DA height {:?}",
+            self.ledger_db.get_last_sequencer_commitment_l1_height()?
+        );
+        Ok(())
+    }
+
+    /// Records sequencer commitments found in the given DA block, so that they are not
+    /// submitted again after taking over.
+    async fn track_sequencer_commitments(
+        &self,
+        da_block: &<Da as DaService>::FilteredBlock,
+    ) -> Result<(), anyhow::Error> {
+        for mut blob in self.da_service.extract_relevant_blobs(da_block) {
+            let Ok(DaDataEnvelope::Known(DaData::SequencerCommitment(commitment))) =
+                DaData::from_envelope(blob.full_data())
+            else {
+                continue;
+            };
+
+            let l1_end_height = self
+                .da_service
+                .get_block_by_hash(commitment.l1_end_block_hash)
+                .await?
+                .header()
+                .height();
+            let last_commitment_l1_height =
+                self.ledger_db.get_last_sequencer_commitment_l1_height()?;
+            if last_commitment_l1_height < Some(SlotNumber(l1_end_height)) {
+                self.ledger_db
+                    .set_last_sequencer_commitment_l1_height(SlotNumber(l1_end_height))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the transactions in the primary's mempool to the local one.
+    async fn mirror_primary_mempool(&self, client: &SequencerClient) -> Result<(), anyhow::Error> {
+        for tx in client.get_mempool_transactions().await? {
+            let Ok(recovered) = recover_raw_transaction(tx) else {
+                continue;
+            };
+            let pool_transaction =
+                EthPooledTransaction::from_recovered_pooled_transaction(recovered);
+            // Transactions which are already known are rejected by the pool
+            let _ = self
+                .mempool
+                .add_transaction(TransactionOrigin::External, pool_transaction)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Puts the transactions of soft batches which were rolled back, such as by the
+    /// startup recovery, back into the mempool. They go through the same admission checks
+    /// as new transactions, so the ones which are no longer valid are dropped.
+    pub async fn resubmit_unwound_soft_batches(&self, soft_batches: Vec<StoredSoftBatch>) {
+        let mut resubmitted = 0;
+        for body in soft_batches
+            .into_iter()
+            .flat_map(|soft_batch| soft_batch.txs)
+            .filter_map(|tx| tx.body)
+        {
+            let Ok(tx) = Transaction::<C>::try_from_slice(&body) else {
+                continue;
+            };
+            let Ok(RuntimeCall::evm(CallMessage { txs })) =
+                Runtime::<C, Da::Spec>::decode_call(tx.runtime_msg())
+            else {
+                continue;
+            };
+            for rlp_tx in txs {
+                let Ok(recovered) = recover_raw_transaction(rlp_tx.rlp.into()) else {
+                    continue;
+                };
+                let pool_transaction =
+                    EthPooledTransaction::from_recovered_pooled_transaction(recovered);
+                match self
+                    .mempool
+                    .add_transaction(TransactionOrigin::External, pool_transaction)
+                    .await
+                {
+                    Ok(_) => resubmitted += 1,
+                    Err(e) => debug!("Unwound transaction was not resubmitted: {}", e),
+                }
+            }
+        }
+        if resubmitted > 0 {
+            info!(
+                "Resubmitted {} transactions of unwound soft batches",
+                resubmitted
+            );
         }
     }
 
     /// Signs batch of messages with sovereign priv key turns them into a sov blob
     /// Returns a single sovereign transaction made up of multiple ethereum transactions
-    fn make_blob(&mut self, raw_message: Vec<u8>) -> Vec<u8> {
-        // if a batch failed need to refetch nonce
-        // so sticking to fetching from state makes sense
-        let nonce = self.get_nonce();
-
+    fn make_blob(&self, raw_message: Vec<u8>, nonce: u64) -> Vec<u8> {
         // TODO: figure out what to do with sov-tx fields
         // chain id gas tip and gas limit
 
@@ -507,27 +1414,155 @@ where
             .unwrap()
     }
 
-    /// Signs necessary info and returns a BlockTemplate
+    /// Returns the public key soft confirmations are signed with under `scheme`
+    fn signer_pub_key(&self, scheme: SignatureScheme) -> Result<Vec<u8>, anyhow::Error> {
+        match scheme {
+            SignatureScheme::Ed25519 => {
+                Ok(self.sov_tx_signer_priv_key.pub_key().try_to_vec().unwrap())
+            }
+            SignatureScheme::Secp256k1Schnorr => {
+                let (public_key, _parity) = self.schnorr_key_pair()?.x_only_public_key();
+                Ok(public_key.serialize().to_vec())
+            }
+        }
+    }
+
+    fn schnorr_key_pair(&self) -> Result<&KeyPair, anyhow::Error> {
+        self.schnorr_key_pair.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Sequencer: Schnorr signatures are active but no schnorr_private_key is configured"
+            )
+        })
+    }
+
+    /// Whether `pub_key` is one of the keys the sequencer signs soft confirmations with
+    fn is_sequencer_pub_key(&self, pub_key: &[u8]) -> bool {
+        pub_key == self.sequencer_pub_key.as_slice()
+            || self
+                .signer_pub_key(SignatureScheme::Secp256k1Schnorr)
+                .is_ok_and(|schnorr_pub_key| pub_key == schnorr_pub_key.as_slice())
+    }
+
+    /// Applies the candidate soft batch on a throwaway copy of `prestate` in one go, as full
+    /// nodes will, and checks that its transactions have the same effects as when the block
+    /// builder applied them. Nothing is persisted.
+    ///
+    /// `chunk_tx_hashes` holds the EVM transaction hashes of each chunk, in the order of
+    /// `tx_receipts`. The transactions of the chunks whose effects differ are reported as
+    /// offending, and all of them if the soft batch is rejected outright.
+    fn dry_run_soft_batch(
+        &self,
+        prestate: Sm::NativeStorage,
+        da_block_header: &<Da::Spec as DaSpec>::BlockHeader,
+        candidate: &mut SignedSoftConfirmationBatch,
+        tx_receipts: &[TransactionReceipt<TxEffect>],
+        chunk_tx_hashes: &[Vec<TxHash>],
+    ) -> Result<(), DryRunFailed> {
+        let pub_key = candidate.pub_key().clone();
+        let receipt = self
+            .stf
+            .dry_run_soft_batch(
+                &pub_key,
+                &self.state_root,
+                prestate,
+                da_block_header,
+                candidate,
+            )
+            .map_err(|e| DryRunFailed {
+                reason: format!("Sequencer: dry run rejected the soft batch: {:?}", e),
+                offending: chunk_tx_hashes.concat(),
+            })?;
+
+        let effect = |receipts: &[TransactionReceipt<TxEffect>], i: usize| {
+            receipts
+                .get(i)
+                .map(|receipt| (receipt.tx_hash, receipt.receipt))
+        };
+        #[cfg(feature = "chaos")]
+        let injected = sov_mock_da::chaos::should_fail(sov_mock_da::chaos::Fault::DryRunMismatch);
+        #[cfg(not(feature = "chaos"))]
+        let injected = false;
+        let chunk_count = receipt.tx_receipts.len().max(tx_receipts.len());
+        let mismatched: Vec<usize> = (0..chunk_count)
+            .filter(|&i| injected || effect(&receipt.tx_receipts, i) != effect(tx_receipts, i))
+            .collect();
+        if !mismatched.is_empty() {
+            return Err(DryRunFailed {
+                reason: format!(
+                    "Sequencer: dry run of the soft batch gave different transaction effects for {} of its {} chunks",
+                    mismatched.len(),
+                    chunk_count
+                ),
+                offending: mismatched
+                    .into_iter()
+                    .filter_map(|i| chunk_tx_hashes.get(i))
+                    .flatten()
+                    .copied()
+                    .collect(),
+            });
+        }
+        if let Some(outcome) = receipt.soft_confirmation_outcome {
+            debug!(
+                "Sequencer: dry run of the soft batch used {} gas and collected {} in fees",
+                outcome.gas_used, outcome.fees_collected
+            );
+        }
+        Ok(())
+    }
+
+    /// Signs necessary info with `scheme` and returns a BlockTemplate
     fn sign_soft_confirmation_batch(
         &mut self,
         soft_confirmation: UnsignedSoftConfirmationBatch,
-    ) -> SignedSoftConfirmationBatch {
+        scheme: SignatureScheme,
+    ) -> Result<SignedSoftConfirmationBatch, anyhow::Error> {
         let raw = soft_confirmation.try_to_vec().unwrap();
 
         let hash = <C as sov_modules_api::Spec>::Hasher::digest(raw.as_slice()).into();
 
-        let signature = self.sov_tx_signer_priv_key.sign(&raw);
+        let (signature, pub_key) = match scheme {
+            SignatureScheme::Ed25519 => (
+                self.sov_tx_signer_priv_key.sign(&raw).try_to_vec().unwrap(),
+                self.sov_tx_signer_priv_key.pub_key().try_to_vec().unwrap(),
+            ),
+            SignatureScheme::Secp256k1Schnorr => sign_schnorr(self.schnorr_key_pair()?, &raw),
+        };
 
-        SignedSoftConfirmationBatch::new(
+        Ok(SignedSoftConfirmationBatch::new(
             hash,
             soft_confirmation.da_slot_height(),
             soft_confirmation.da_slot_hash(),
             soft_confirmation.pre_state_root(),
             soft_confirmation.l1_fee_rate(),
             soft_confirmation.txs(),
-            signature.try_to_vec().unwrap(),
-            self.sov_tx_signer_priv_key.pub_key().try_to_vec().unwrap(),
-        )
+            signature,
+            pub_key,
+            soft_confirmation.timestamp(),
+            soft_confirmation.ordering_commitment(),
+        ))
+    }
+
+    /// Signs a checkpoint of the state after the soft batch with the given number, if one is
+    /// due by the checkpoint interval.
+    fn sign_checkpoint_if_due(&self, soft_batch_number: u64, soft_batch_hash: [u8; 32]) {
+        let Some(interval) = self.config.checkpoint_interval else {
+            return;
+        };
+        if interval == 0 || soft_batch_number % interval != 0 {
+            return;
+        }
+
+        let checkpoint = Checkpoint {
+            l2_height: soft_batch_number,
+            soft_batch_hash,
+            state_root: self.state_root.as_ref().to_vec(),
+        };
+        let signed = sign_checkpoint::<C>(&self.sov_tx_signer_priv_key, checkpoint);
+        info!(
+            "Sequencer: signed checkpoint at L2 height {}",
+            soft_batch_number
+        );
+        *self.latest_checkpoint.lock().unwrap() = Some(signed);
     }
 
     /// Fetches nonce from state
@@ -551,6 +1586,13 @@ where
             mempool: self.mempool.clone(),
             l2_force_block_tx,
             storage: self.storage.clone(),
+            latency: self.latency.clone(),
+            ledger_db: self.ledger_db.clone(),
+            dropped_txs: self.dropped_txs.clone(),
+            proving_claims: self.proving_claims.clone(),
+            proving_claim_ttl_secs: self.config.proving_claim_ttl_secs,
+            latest_checkpoint: self.latest_checkpoint.clone(),
+            min_priority_fee_per_gas: self.config.min_priority_fee_per_gas,
         }
     }
 
@@ -565,3 +1607,66 @@ where
         Ok(rpc_methods)
     }
 }
+
+/// Turns the best transactions of the mempool into candidates of a block.
+/// Transactions can reach the mempool again after being included, such as when mirrored from
+/// the primary, and must not be included twice. Those included in a recent soft confirmation
+/// are left out and pushed to `already_included`.
+fn mempool_candidates<'a>(
+    best_txs: impl Iterator<Item = Arc<ValidPoolTransaction<EthPooledTransaction>>> + 'a,
+    recent_inclusions: &'a Mutex<RecentInclusions>,
+    already_included: &'a mut Vec<TxHash>,
+) -> impl Iterator<Item = Candidate<(RlpEvmTransaction, Instant)>> + 'a {
+    best_txs
+        .filter(move |tx| {
+            let included = recent_inclusions.lock().unwrap().contains(tx.hash());
+            if included {
+                already_included.push(*tx.hash());
+            }
+            !included
+        })
+        .map(|tx| {
+            let rlp = tx
+                .to_recovered_transaction()
+                .into_signed()
+                .envelope_encoded()
+                .to_vec();
+            Candidate {
+                sender: tx.sender(),
+                gas_limit: tx.gas_limit(),
+                size: rlp.len() as u64,
+                tx: (RlpEvmTransaction { rlp }, tx.timestamp),
+            }
+        })
+}
+
+/// Returns `soft_confirmation` with its hash and `pub_key` but without a signature, to be
+/// dry run before it is signed.
+fn unsigned_candidate<C: Context>(
+    soft_confirmation: &UnsignedSoftConfirmationBatch,
+    pub_key: Vec<u8>,
+) -> SignedSoftConfirmationBatch {
+    let raw = soft_confirmation.try_to_vec().unwrap();
+    let hash = <C as sov_modules_api::Spec>::Hasher::digest(raw.as_slice()).into();
+
+    SignedSoftConfirmationBatch::new(
+        hash,
+        soft_confirmation.da_slot_height(),
+        soft_confirmation.da_slot_hash(),
+        soft_confirmation.pre_state_root(),
+        soft_confirmation.l1_fee_rate(),
+        soft_confirmation.txs(),
+        vec![],
+        pub_key,
+        soft_confirmation.timestamp(),
+        soft_confirmation.ordering_commitment(),
+    )
+}
+
+/// Returns true if the error was caused by a failed connection to the primary sequencer.
+fn is_connection_error(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<jsonrpsee::core::Error>(),
+        Some(jsonrpsee::core::Error::Transport(_))
+    )
+}
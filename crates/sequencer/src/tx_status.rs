@@ -0,0 +1,129 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use reth_primitives::{B256, U64};
+use reth_transaction_pool::{FullTransactionEvent, TransactionPool};
+use serde::Serialize;
+
+use crate::mempool::CitreaMempool;
+
+/// Number of most recently dropped transactions whose drop reason is remembered
+const MAX_DROPPED_TXS: usize = 10_000;
+
+/// Where a transaction is in its lifecycle, as returned by `citrea_getTransactionStatus`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TransactionStatus {
+    /// The transaction was never seen, or was dropped too long ago to be remembered
+    Unknown,
+    /// The transaction is in the mempool and executable in the next blocks
+    Pending,
+    /// The transaction is in the mempool but waits for a transaction with a lower nonce
+    #[serde(rename_all = "camelCase")]
+    Queued {
+        /// Nonce the sender has to fill before this transaction can be included
+        expected_nonce: U64,
+    },
+    /// The transaction was removed from the mempool without being included
+    Dropped {
+        /// Why the mempool removed the transaction
+        reason: String,
+    },
+    /// The transaction is part of an L2 block
+    #[serde(rename_all = "camelCase")]
+    Included {
+        /// Number of the block containing the transaction
+        block_number: U64,
+        /// How final the block containing the transaction is
+        finality: Finality,
+    },
+}
+
+/// Finality level of an L2 block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Finality {
+    /// The block was produced by the sequencer but not yet committed to the DA layer
+    SoftConfirmed,
+    /// The block is covered by a sequencer commitment on the DA layer
+    Committed,
+}
+
+/// Remembers why the most recent transactions were dropped from the mempool
+#[derive(Debug, Default)]
+pub(crate) struct DroppedTxs {
+    reasons: HashMap<B256, String>,
+    order: VecDeque<B256>,
+}
+
+impl DroppedTxs {
+    pub(crate) fn record(&mut self, hash: B256, reason: String) {
+        if self.reasons.insert(hash, reason).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > MAX_DROPPED_TXS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.reasons.remove(&oldest);
+            }
+        }
+    }
+
+    pub(crate) fn reason(&self, hash: &B256) -> Option<String> {
+        self.reasons.get(hash).cloned()
+    }
+}
+
+/// Spawns a task recording the transactions the mempool drops into `dropped_txs`.
+pub(crate) fn spawn_dropped_tx_tracker<C: sov_modules_api::Context>(
+    mempool: &CitreaMempool<C>,
+    dropped_txs: Arc<Mutex<DroppedTxs>>,
+) {
+    let mut events = mempool.all_transactions_event_listener();
+    tokio::spawn(async move {
+        while let Some(event) = events.next().await {
+            let (hash, reason) = match event {
+                FullTransactionEvent::Replaced {
+                    transaction,
+                    replaced_by,
+                } => (
+                    *transaction.hash(),
+                    format!("replaced by transaction {}", replaced_by),
+                ),
+                FullTransactionEvent::Discarded(hash) => {
+                    (hash, "discarded by the mempool".to_string())
+                }
+                FullTransactionEvent::Invalid(hash) => (hash, "invalid".to_string()),
+                _ => continue,
+            };
+            dropped_txs.lock().unwrap().record(hash, reason);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(i: u64) -> B256 {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&i.to_be_bytes());
+        B256::from(bytes)
+    }
+
+    #[test]
+    fn test_dropped_txs_is_bounded() {
+        let mut dropped = DroppedTxs::default();
+        for i in 0..=MAX_DROPPED_TXS as u64 {
+            dropped.record(hash(i), "invalid".into());
+        }
+
+        // The oldest transaction is forgotten first
+        assert_eq!(dropped.reason(&hash(0)), None);
+        assert_eq!(
+            dropped.reason(&hash(MAX_DROPPED_TXS as u64)),
+            Some("invalid".to_string())
+        );
+        assert_eq!(dropped.order.len(), MAX_DROPPED_TXS);
+    }
+}
@@ -1,7 +1,14 @@
 //! Commonly used code snippets
 
+use std::collections::BTreeSet;
+
+use borsh::BorshSerialize;
 use citrea_evm::{EthApiError, EthResult};
 use reth_primitives::{Bytes, PooledTransactionsElement, PooledTransactionsElementEcRecovered};
+use secp256k1::{KeyPair, SECP256K1};
+use sov_modules_api::{Context, PrivateKey, MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS};
+use sov_modules_stf_blueprint::schnorr_message_digest;
+use sov_rollup_interface::rpc::{Checkpoint, SignedCheckpoint};
 
 /// Recovers a [PooledTransactionsElementEcRecovered] from an enveloped encoded byte stream.
 ///
@@ -20,3 +27,171 @@ pub(crate) fn recover_raw_transaction(
         .try_into_ecrecovered()
         .or(Err(EthApiError::InvalidTransactionSignature))
 }
+
+/// Returns the nonce following the transactions of a sender which can be executed in order,
+/// starting from its nonce in the state. Transactions after a nonce gap are not counted.
+pub(crate) fn next_pending_nonce(
+    state_nonce: u64,
+    pool_nonces: impl IntoIterator<Item = u64>,
+) -> u64 {
+    let pool_nonces: BTreeSet<u64> = pool_nonces.into_iter().collect();
+    let mut nonce = state_nonce;
+    while pool_nonces.contains(&nonce) {
+        nonce += 1;
+    }
+    nonce
+}
+
+/// Returns the timestamp of the next soft confirmation: the current time, moved within the
+/// window the kernel accepts around the DA block time, and not before the parent timestamp.
+/// Returns `None` if the parent timestamp is already past the window.
+pub(crate) fn soft_confirmation_timestamp(
+    now: u64,
+    parent_timestamp: Option<u64>,
+    da_block_time: u64,
+) -> Option<u64> {
+    let earliest = da_block_time.saturating_sub(MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS);
+    let latest = da_block_time + MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS;
+    let timestamp = now
+        .clamp(earliest, latest)
+        .max(parent_timestamp.unwrap_or_default());
+    (timestamp <= latest).then_some(timestamp)
+}
+
+/// Signs the borsh serialized unsigned soft confirmation `raw` with a Schnorr signature,
+/// returning the signature and the x-only public key it verifies with.
+pub(crate) fn sign_schnorr(key_pair: &KeyPair, raw: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let signature = SECP256K1.sign_schnorr(&schnorr_message_digest(raw), key_pair);
+    let (public_key, _parity) = key_pair.x_only_public_key();
+    (signature[..].to_vec(), public_key.serialize().to_vec())
+}
+
+/// Signs the borsh serialized checkpoint with the ed25519 key of the sequencer.
+pub(crate) fn sign_checkpoint<C: Context>(
+    priv_key: &C::PrivateKey,
+    checkpoint: Checkpoint,
+) -> SignedCheckpoint {
+    let raw = checkpoint.try_to_vec().unwrap();
+    SignedCheckpoint {
+        checkpoint,
+        signature: priv_key.sign(&raw).try_to_vec().unwrap(),
+        pub_key: priv_key.pub_key().try_to_vec().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::{
+        SignatureScheme, SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+    };
+    use sov_modules_stf_blueprint::{verify_checkpoint_signature, verify_soft_batch_signature};
+
+    use super::*;
+
+    #[test]
+    fn test_next_pending_nonce() {
+        assert_eq!(next_pending_nonce(3, []), 3);
+        assert_eq!(next_pending_nonce(3, [4, 3, 5]), 6);
+        // Queued transactions after a gap don't advance the nonce
+        assert_eq!(next_pending_nonce(3, [3, 5, 6]), 4);
+        assert_eq!(next_pending_nonce(3, [1, 2]), 3);
+    }
+
+    #[test]
+    fn test_soft_confirmation_timestamp() {
+        let drift = MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS;
+        let da_block_time = 1_000_000;
+
+        assert_eq!(
+            soft_confirmation_timestamp(da_block_time + 10, None, da_block_time),
+            Some(da_block_time + 10)
+        );
+        // The DA block is too old or too recent for the current time
+        assert_eq!(
+            soft_confirmation_timestamp(da_block_time + 2 * drift, None, da_block_time),
+            Some(da_block_time + drift)
+        );
+        assert_eq!(
+            soft_confirmation_timestamp(0, None, da_block_time),
+            Some(da_block_time - drift)
+        );
+        // Timestamps never go back
+        assert_eq!(
+            soft_confirmation_timestamp(da_block_time, Some(da_block_time + 5), da_block_time),
+            Some(da_block_time + 5)
+        );
+        assert_eq!(
+            soft_confirmation_timestamp(
+                da_block_time,
+                Some(da_block_time + drift + 1),
+                da_block_time
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sign_schnorr() {
+        let key_pair = KeyPair::from_seckey_slice(SECP256K1, &[1; 32]).unwrap();
+        let unsigned = UnsignedSoftConfirmationBatch::new(
+            3,
+            [2; 32],
+            vec![4; 32],
+            vec![],
+            10,
+            Some(5),
+            Some([6; 32]),
+        );
+        let (signature, pub_key) = sign_schnorr(&key_pair, &unsigned.try_to_vec().unwrap());
+
+        let signed = SignedSoftConfirmationBatch::new(
+            [0; 32],
+            unsigned.da_slot_height(),
+            unsigned.da_slot_hash(),
+            unsigned.pre_state_root(),
+            unsigned.l1_fee_rate(),
+            unsigned.txs(),
+            signature,
+            pub_key.clone(),
+            unsigned.timestamp(),
+            unsigned.ordering_commitment(),
+        );
+        assert!(verify_soft_batch_signature::<DefaultContext>(
+            &signed,
+            &pub_key,
+            SignatureScheme::Secp256k1Schnorr
+        )
+        .is_ok());
+        // Schnorr signatures are rejected before the activation
+        assert!(verify_soft_batch_signature::<DefaultContext>(
+            &signed,
+            &pub_key,
+            SignatureScheme::Ed25519
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sign_checkpoint() {
+        let priv_key = DefaultPrivateKey::generate();
+        let pub_key = priv_key.pub_key().try_to_vec().unwrap();
+        let checkpoint = Checkpoint {
+            l2_height: 100,
+            soft_batch_hash: [1; 32],
+            state_root: vec![2; 32],
+        };
+        let mut signed = sign_checkpoint::<DefaultContext>(&priv_key, checkpoint);
+        assert!(verify_checkpoint_signature::<DefaultContext>(&signed, &pub_key).is_ok());
+
+        let other_key = DefaultPrivateKey::generate()
+            .pub_key()
+            .try_to_vec()
+            .unwrap();
+        assert!(verify_checkpoint_signature::<DefaultContext>(&signed, &other_key).is_err());
+
+        signed.checkpoint.state_root = vec![3; 32];
+        assert!(verify_checkpoint_signature::<DefaultContext>(&signed, &pub_key).is_err());
+    }
+}
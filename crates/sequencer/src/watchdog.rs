@@ -0,0 +1,223 @@
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc::UnboundedSender;
+use reth_transaction_pool::TransactionPool;
+use tracing::{error, info};
+
+use crate::config::WatchdogConfig;
+use crate::mempool::CitreaMempool;
+use crate::metrics::{SEQUENCER_BLOCK_PRODUCTION_STALLS, SEQUENCER_WATCHDOG_RECOVERIES};
+
+/// What the block production loop last did, reported by the watchdog when it stalls
+#[derive(Debug)]
+pub(crate) struct ProductionHealth {
+    /// Height and production time of the last soft confirmation
+    last_block: Option<(u64, Instant)>,
+    /// Last finalized DA height seen, and when it was seen
+    last_da_height: Option<(u64, Instant)>,
+    last_error: Option<String>,
+    /// When the watchdog started watching, the reference until the first block
+    started: Instant,
+}
+
+impl Default for ProductionHealth {
+    fn default() -> Self {
+        Self {
+            last_block: None,
+            last_da_height: None,
+            last_error: None,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl ProductionHealth {
+    pub fn record_block(&mut self, height: u64) {
+        self.last_block = Some((height, Instant::now()));
+    }
+
+    pub fn record_da_height(&mut self, height: u64) {
+        self.last_da_height = Some((height, Instant::now()));
+    }
+
+    pub fn record_error(&mut self, error: impl Display) {
+        self.last_error = Some(error.to_string());
+    }
+
+    fn last_progress(&self) -> Instant {
+        self.last_block
+            .map(|(_, produced)| produced)
+            .unwrap_or(self.started)
+    }
+}
+
+/// What the watchdog does after a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    /// Soft confirmations are produced in time
+    Healthy,
+    /// No soft confirmation was produced in time
+    Stalled {
+        /// The stall was just detected
+        new: bool,
+        /// Force a block to recover
+        recover: bool,
+    },
+}
+
+/// Detects stalls of block production and bounds the recovery attempts of each stall
+#[derive(Debug)]
+struct Watchdog {
+    stall_after: Duration,
+    max_recovery_attempts: u32,
+    stalled: bool,
+    recovery_attempts: u32,
+}
+
+impl Watchdog {
+    fn new(config: &WatchdogConfig) -> Self {
+        Self {
+            stall_after: Duration::from_millis(config.block_time_ms)
+                .saturating_mul(config.stall_factor),
+            max_recovery_attempts: config.max_recovery_attempts,
+            stalled: false,
+            recovery_attempts: 0,
+        }
+    }
+
+    fn check(&mut self, last_progress: Instant, now: Instant) -> Verdict {
+        if now.saturating_duration_since(last_progress) <= self.stall_after {
+            self.stalled = false;
+            return Verdict::Healthy;
+        }
+
+        let new = !self.stalled;
+        if new {
+            self.stalled = true;
+            self.recovery_attempts = 0;
+        }
+        let recover = self.recovery_attempts < self.max_recovery_attempts;
+        if recover {
+            self.recovery_attempts += 1;
+        }
+        Verdict::Stalled { new, recover }
+    }
+}
+
+/// Spawns a task checking every block time that a soft confirmation was produced within
+/// `stall_factor` block times. On a stall it logs the state of the sequencer, counts the
+/// stall and forces a block, at most `max_recovery_attempts` times per stall.
+pub(crate) fn spawn_watchdog<C: sov_modules_api::Context>(
+    config: WatchdogConfig,
+    health: Arc<Mutex<ProductionHealth>>,
+    mempool: Arc<CitreaMempool<C>>,
+    l2_force_block_tx: UnboundedSender<()>,
+) {
+    let mut watchdog = Watchdog::new(&config);
+    let check_interval = Duration::from_millis(config.block_time_ms.max(1));
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(check_interval).await;
+
+            let now = Instant::now();
+            let health = health.lock().unwrap();
+            let Verdict::Stalled { new, recover } = watchdog.check(health.last_progress(), now)
+            else {
+                continue;
+            };
+
+            if new {
+                SEQUENCER_BLOCK_PRODUCTION_STALLS.inc();
+                let pool_size = mempool.pool_size();
+                error!(
+                    "Sequencer: no soft confirmation produced for {:?}. Last block: {:?}, last DA height: {:?}, mempool: {} pending, {} queued, last error: {:?}",
+                    now.duration_since(health.last_progress()),
+                    health
+                        .last_block
+                        .map(|(height, produced)| (height, now.duration_since(produced))),
+                    health
+                        .last_da_height
+                        .map(|(height, seen)| (height, now.duration_since(seen))),
+                    pool_size.pending,
+                    pool_size.queued,
+                    health.last_error,
+                );
+            }
+            if recover {
+                SEQUENCER_WATCHDOG_RECOVERIES.inc();
+                info!(
+                    "Sequencer: watchdog forcing a block, attempt {} of {}",
+                    watchdog.recovery_attempts, watchdog.max_recovery_attempts
+                );
+                if l2_force_block_tx.unbounded_send(()).is_err() {
+                    // The sequencer stopped
+                    return;
+                }
+            } else if watchdog.recovery_attempts == watchdog.max_recovery_attempts {
+                // Logged once per stall
+                watchdog.recovery_attempts += 1;
+                error!("Sequencer: block production is still stalled, the watchdog gave up recovering it");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog() -> Watchdog {
+        Watchdog::new(&WatchdogConfig {
+            block_time_ms: 1000,
+            stall_factor: 3,
+            max_recovery_attempts: 2,
+        })
+    }
+
+    #[test]
+    fn test_recovery_attempts_are_bounded_per_stall() {
+        let mut watchdog = watchdog();
+        let last_block = Instant::now();
+
+        assert_eq!(
+            watchdog.check(last_block, last_block + Duration::from_secs(3)),
+            Verdict::Healthy
+        );
+
+        let stalled = last_block + Duration::from_secs(4);
+        assert_eq!(
+            watchdog.check(last_block, stalled),
+            Verdict::Stalled {
+                new: true,
+                recover: true
+            }
+        );
+        assert_eq!(
+            watchdog.check(last_block, stalled),
+            Verdict::Stalled {
+                new: false,
+                recover: true
+            }
+        );
+        assert_eq!(
+            watchdog.check(last_block, stalled),
+            Verdict::Stalled {
+                new: false,
+                recover: false
+            }
+        );
+
+        // A new block ends the stall, the next one gets its own attempts
+        let last_block = stalled;
+        assert_eq!(watchdog.check(last_block, last_block), Verdict::Healthy);
+        assert_eq!(
+            watchdog.check(last_block, last_block + Duration::from_secs(4)),
+            Verdict::Stalled {
+                new: true,
+                recover: true
+            }
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{Context, DaSpec, StateValueAccessor, WorkingSet};
+
+use crate::SoftConfirmationRuleEnforcer;
+
+/// Budget of the calls to the modules other than the EVM, whose calls are already priced in EVM gas.
+/// Keeps the sequencer from filling soft confirmations with cheap module calls.
+/// Nothing is charged to the sender of a call: the costs only count against the budget of the
+/// soft confirmation the call is in.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize, Eq, PartialEq,
+)]
+pub struct ModuleCallBudget {
+    /// Cost of a call, by name of the called module.
+    /// Calls to modules that are not listed cost nothing.
+    pub call_costs: BTreeMap<String, u64>,
+    /// Cost per byte of the call message, for calls to the listed modules.
+    pub cost_per_byte: u64,
+    /// Total cost the module calls of a soft confirmation can have at most.
+    /// Unlimited if not set.
+    pub soft_confirmation_limit: Option<u64>,
+}
+
+impl ModuleCallBudget {
+    /// Returns the cost of a call to `module` with a message of `message_len` bytes,
+    /// `None` if calls to `module` cost nothing.
+    pub fn call_cost(&self, module: &str, message_len: usize) -> Option<u64> {
+        let call_cost = self.call_costs.get(module)?;
+        Some(
+            self.cost_per_byte
+                .saturating_mul(message_len as u64)
+                .saturating_add(*call_cost),
+        )
+    }
+}
+
+impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
+    /// Spends the cost of a call to `module` with a message of `message_len` bytes from the
+    /// module call budget of the current soft confirmation, and returns it.
+    /// Fails if the call would take the soft confirmation over its limit.
+    pub fn spend_module_call_budget(
+        &self,
+        module: &str,
+        message_len: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<u64> {
+        let budget = self.module_call_budget.get(working_set).unwrap_or_default();
+        let Some(cost) = budget.call_cost(module, message_len) else {
+            return Ok(0);
+        };
+
+        let used = self
+            .module_call_budget_used
+            .get(working_set)
+            .unwrap_or(0)
+            .saturating_add(cost);
+        if let Some(limit) = budget.soft_confirmation_limit {
+            anyhow::ensure!(
+                used <= limit,
+                "Call to {} costs {}, exceeding the module call budget of {} per soft confirmation",
+                module,
+                cost,
+                limit
+            );
+        }
+        self.module_call_budget_used.set(&used, working_set);
+
+        Ok(cost)
+    }
+}
@@ -1,7 +1,7 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use sov_modules_api::{CallResponse, Context, DaSpec, StateValueAccessor, WorkingSet};
 
-use crate::SoftConfirmationRuleEnforcer;
+use crate::{ModuleCallBudget, SoftConfirmationRuleEnforcer};
 
 #[cfg_attr(
     feature = "serde",
@@ -20,6 +20,11 @@ pub enum CallMessage<C: Context> {
         /// The new limiting number representing max number of L2 blocks published per L1 block.
         limiting_number: u64,
     },
+    /// Replace the budget of the calls to the modules other than the EVM.
+    ModifyModuleCallBudget {
+        /// The new module call budget.
+        budget: ModuleCallBudget,
+    },
 }
 
 impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
@@ -57,4 +62,18 @@ impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
         self.limiting_number.set(&limiting_number, working_set);
         Ok(CallResponse::default())
     }
+
+    pub(crate) fn modify_module_call_budget(
+        &self,
+        budget: ModuleCallBudget,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        anyhow::ensure!(
+            *context.sender() == self.get_authority(working_set),
+            "Only authority can change the module call budget"
+        );
+        self.module_call_budget.set(&budget, working_set);
+        Ok(CallResponse::default())
+    }
 }
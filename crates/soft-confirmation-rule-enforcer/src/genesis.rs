@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use sov_modules_api::{Context, DaSpec, StateValueAccessor, WorkingSet};
 
-use crate::SoftConfirmationRuleEnforcer;
+use crate::{ModuleCallBudget, SoftConfirmationRuleEnforcer};
+
+/// Maximum number of L2 blocks per L1 slot a genesis can set.
+/// One L2 block per second over a day of L1 slots.
+pub const MAX_LIMITING_NUMBER: u64 = 86400;
 
 /// Config for the SoftConfirmationRuleEnforcer module.
 /// Sets limiting number and authority.
@@ -15,6 +19,153 @@ pub struct SoftConfirmationRuleEnforcerConfig<C: Context> {
     /// L1 fee rate change percentage
     /// Out of 100.
     pub(crate) l1_fee_rate_change_percentage: u64,
+    /// Budget of the calls to the modules other than the EVM.
+    /// Module calls are unlimited if not set.
+    #[serde(default)]
+    pub(crate) module_call_budget: ModuleCallBudget,
+}
+
+/// Invalid field of a [`SoftConfirmationRuleEnforcerConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GenesisConfigError {
+    /// The authority is the zero address, whose key nobody holds.
+    #[error("authority: the zero address cannot be the authority")]
+    ZeroAuthority,
+    /// The limiting number is out of `1..=MAX_LIMITING_NUMBER`.
+    #[error("limiting_number: {0} is not between 1 and {max}", max = MAX_LIMITING_NUMBER)]
+    LimitingNumberOutOfBounds(u64),
+    /// The L1 fee rate change percentage is over 100.
+    #[error("l1_fee_rate_change_percentage: {0} is over 100")]
+    L1FeeRateChangePercentageOutOfBounds(u64),
+    /// The soft confirmation limit is lower than the cost of a call to the module.
+    #[error("module_call_budget.call_costs.{module}: {call_cost} is over the soft confirmation limit {limit}")]
+    CallCostOverLimit {
+        /// Module whose calls cannot fit in a soft confirmation
+        module: String,
+        /// Cost of a call to the module
+        call_cost: u64,
+        /// Soft confirmation limit of the budget
+        limit: u64,
+    },
+}
+
+impl GenesisConfigError {
+    /// Returns the path of the offending field in the genesis JSON.
+    pub fn field(&self) -> String {
+        match self {
+            Self::ZeroAuthority => "authority".to_string(),
+            Self::LimitingNumberOutOfBounds(_) => "limiting_number".to_string(),
+            Self::L1FeeRateChangePercentageOutOfBounds(_) => {
+                "l1_fee_rate_change_percentage".to_string()
+            }
+            Self::CallCostOverLimit { module, .. } => {
+                format!("module_call_budget.call_costs.{}", module)
+            }
+        }
+    }
+}
+
+impl<C: Context> SoftConfirmationRuleEnforcerConfig<C> {
+    /// Returns a builder of the config with the given authority.
+    pub fn builder(authority: C::Address) -> SoftConfirmationRuleEnforcerConfigBuilder<C> {
+        SoftConfirmationRuleEnforcerConfigBuilder::new(authority)
+    }
+
+    /// Authority address.
+    pub fn authority(&self) -> &C::Address {
+        &self.authority
+    }
+
+    /// Maximum number of L2 blocks per L1 slot.
+    pub fn limiting_number(&self) -> u64 {
+        self.limiting_number
+    }
+
+    /// L1 fee rate change percentage, out of 100.
+    pub fn l1_fee_rate_change_percentage(&self) -> u64 {
+        self.l1_fee_rate_change_percentage
+    }
+
+    /// Budget of the calls to the modules other than the EVM.
+    pub fn module_call_budget(&self) -> &ModuleCallBudget {
+        &self.module_call_budget
+    }
+
+    /// Checks the fields of the config, as genesis does.
+    /// Fails with the first invalid field.
+    pub fn validate(&self) -> Result<(), GenesisConfigError> {
+        if self.authority.as_ref().iter().all(|byte| *byte == 0) {
+            return Err(GenesisConfigError::ZeroAuthority);
+        }
+        if !(1..=MAX_LIMITING_NUMBER).contains(&self.limiting_number) {
+            return Err(GenesisConfigError::LimitingNumberOutOfBounds(
+                self.limiting_number,
+            ));
+        }
+        if self.l1_fee_rate_change_percentage > 100 {
+            return Err(GenesisConfigError::L1FeeRateChangePercentageOutOfBounds(
+                self.l1_fee_rate_change_percentage,
+            ));
+        }
+        if let Some(limit) = self.module_call_budget.soft_confirmation_limit {
+            for (module, call_cost) in &self.module_call_budget.call_costs {
+                if *call_cost > limit {
+                    return Err(GenesisConfigError::CallCostOverLimit {
+                        module: module.clone(),
+                        call_cost: *call_cost,
+                        limit,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds a validated [`SoftConfirmationRuleEnforcerConfig`], as written by `citrea genesis`.
+/// Defaults to the limiting number and L1 fee rate change percentage of the test networks,
+/// and to unlimited module calls.
+#[derive(Debug, Clone)]
+pub struct SoftConfirmationRuleEnforcerConfigBuilder<C: Context> {
+    config: SoftConfirmationRuleEnforcerConfig<C>,
+}
+
+impl<C: Context> SoftConfirmationRuleEnforcerConfigBuilder<C> {
+    /// Creates a builder with the given authority.
+    pub fn new(authority: C::Address) -> Self {
+        Self {
+            config: SoftConfirmationRuleEnforcerConfig {
+                authority,
+                limiting_number: MAX_LIMITING_NUMBER,
+                l1_fee_rate_change_percentage: 10,
+                module_call_budget: ModuleCallBudget::default(),
+            },
+        }
+    }
+
+    /// Sets the maximum number of L2 blocks per L1 slot.
+    pub fn limiting_number(mut self, limiting_number: u64) -> Self {
+        self.config.limiting_number = limiting_number;
+        self
+    }
+
+    /// Sets the L1 fee rate change percentage, out of 100.
+    pub fn l1_fee_rate_change_percentage(mut self, percentage: u64) -> Self {
+        self.config.l1_fee_rate_change_percentage = percentage;
+        self
+    }
+
+    /// Sets the budget of the calls to the modules other than the EVM.
+    pub fn module_call_budget(mut self, budget: ModuleCallBudget) -> Self {
+        self.config.module_call_budget = budget;
+        self
+    }
+
+    /// Returns the config, if valid.
+    pub fn build(self) -> Result<SoftConfirmationRuleEnforcerConfig<C>, GenesisConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
 }
 
 impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
@@ -23,11 +174,15 @@ impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
         config: &<Self as sov_modules_api::Module>::Config,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
+        config.validate()?;
+
         self.authority.set(&config.authority, working_set);
         self.limiting_number
             .set(&config.limiting_number, working_set);
         self.l1_fee_rate_change_percentage
             .set(&config.l1_fee_rate_change_percentage, working_set);
+        self.module_call_budget
+            .set(&config.module_call_budget, working_set);
         Ok(())
     }
 }
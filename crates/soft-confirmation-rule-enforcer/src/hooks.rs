@@ -1,5 +1,7 @@
 use sov_modules_api::hooks::{ApplySoftConfirmationError, HookSoftConfirmationInfo};
-use sov_modules_api::{Context, DaSpec, StateMapAccessor, StateValueAccessor, WorkingSet};
+use sov_modules_api::{
+    Context, DaSpec, SoftConfirmationEnforcement, StateMapAccessor, StateValueAccessor, WorkingSet,
+};
 use sov_state::Storage;
 
 use crate::SoftConfirmationRuleEnforcer;
@@ -93,6 +95,7 @@ where
 
     /// Logic executed at the beginning of the soft confirmation.
     /// Checks two rules: block count rule and fee rate rule.
+    /// Resets the module call budget spent, which is per soft confirmation.
     pub fn begin_soft_confirmation_hook(
         &self,
         soft_batch: &mut HookSoftConfirmationInfo,
@@ -102,6 +105,30 @@ where
 
         self.apply_fee_rate_rule(soft_batch, working_set)?;
 
+        self.module_call_budget_used.set(&0, working_set);
+
         Ok(())
     }
+
+    /// Logic executed at the end of the soft confirmation.
+    /// Returns where the soft confirmation stands against the rules checked at its beginning.
+    pub fn end_soft_confirmation_hook(
+        &self,
+        soft_batch: &HookSoftConfirmationInfo,
+        working_set: &mut WorkingSet<C>,
+    ) -> SoftConfirmationEnforcement {
+        SoftConfirmationEnforcement {
+            l2_blocks_on_da_slot: self
+                .get_block_count_by_da_root_hash(soft_batch.da_slot_hash(), working_set)
+                .unwrap_or_default(),
+            max_l2_blocks_on_da_slot: self
+                .get_limiting_number(working_set)
+                .expect("Limiting number must be set"),
+            l1_fee_rate: self.last_l1_fee_rate.get(working_set).unwrap_or_default(),
+            max_l1_fee_rate_change_percentage: self
+                .l1_fee_rate_change_percentage
+                .get(working_set)
+                .expect("L1 fee rate change should be set"),
+        }
+    }
 }
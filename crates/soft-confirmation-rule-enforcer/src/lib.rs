@@ -1,8 +1,10 @@
+mod budget;
 mod call;
 mod genesis;
 mod hooks;
 mod query;
 mod tests;
+pub use budget::*;
 pub use call::*;
 pub use genesis::*;
 pub use query::*;
@@ -34,6 +36,12 @@ pub struct SoftConfirmationRuleEnforcer<C: Context, Da: DaSpec> {
     /// 0 at genesis
     #[state]
     pub(crate) last_l1_fee_rate: StateValue<u64, BcsCodec>,
+    /// Budget of the calls to the modules other than the EVM.
+    #[state]
+    pub(crate) module_call_budget: StateValue<ModuleCallBudget, BcsCodec>,
+    /// Module call budget spent by the calls of the current soft confirmation.
+    #[state]
+    pub(crate) module_call_budget_used: StateValue<u64, BcsCodec>,
     /// Phantom state using the da type.
     /// This is used to make sure that the state is generic over the DA type.
     #[allow(dead_code)]
@@ -63,6 +71,9 @@ impl<C: Context, Da: DaSpec> sov_modules_api::Module for SoftConfirmationRuleEnf
             CallMessage::ModifyLimitingNumber { limiting_number } => {
                 Ok(self.modify_limiting_number(limiting_number, context, working_set)?)
             }
+            CallMessage::ModifyModuleCallBudget { budget } => {
+                Ok(self.modify_module_call_budget(budget, context, working_set)?)
+            }
         }
     }
 
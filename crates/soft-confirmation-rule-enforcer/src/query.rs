@@ -2,7 +2,7 @@ use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::{Context, DaSpec, StateMapAccessor, StateValueAccessor, WorkingSet};
 
-use crate::SoftConfirmationRuleEnforcer;
+use crate::{ModuleCallBudget, SoftConfirmationRuleEnforcer};
 
 #[rpc_gen(client, server, namespace = "softConfirmationRuleEnforcer")]
 impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
@@ -46,4 +46,13 @@ impl<C: Context, Da: DaSpec> SoftConfirmationRuleEnforcer<C, Da> {
     pub fn get_last_l1_fee_rate(&self, working_set: &mut WorkingSet<C>) -> RpcResult<u64> {
         Ok(self.last_l1_fee_rate.get(working_set).unwrap_or(0))
     }
+
+    #[rpc_method(name = "getModuleCallBudget")]
+    /// Get the budget of the calls to the modules other than the EVM.
+    pub fn get_module_call_budget(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ModuleCallBudget> {
+        Ok(self.module_call_budget.get(working_set).unwrap_or_default())
+    }
 }
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, Spec, StateValueAccessor};
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+
+use crate::call::CallMessage;
+use crate::tests::genesis_tests::{get_soft_confirmation_rule_enforcer, TEST_CONFIG};
+use crate::ModuleCallBudget;
+
+type C = DefaultContext;
+
+#[test]
+fn module_call_budget_is_spent_up_to_the_limit() {
+    let (soft_confirmation_rule_enforcer, mut working_set) =
+        get_soft_confirmation_rule_enforcer::<MockDaSpec>(&TEST_CONFIG);
+
+    // 1000 + 10 * 50 per call, with a limit of 4000
+    for _ in 0..2 {
+        assert_eq!(
+            soft_confirmation_rule_enforcer
+                .spend_module_call_budget("accounts", 50, &mut working_set)
+                .unwrap(),
+            1500
+        );
+    }
+    assert!(soft_confirmation_rule_enforcer
+        .spend_module_call_budget("accounts", 50, &mut working_set)
+        .is_err());
+
+    // Calls to modules missing from the budget cost nothing
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .spend_module_call_budget("evm", 50_000, &mut working_set)
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .module_call_budget_used
+            .get(&mut working_set)
+            .unwrap(),
+        3000
+    );
+
+    // The budget spent is reset by the next soft confirmation
+    let signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        1,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
+    soft_confirmation_rule_enforcer
+        .begin_soft_confirmation_hook(&mut signed_soft_confirmation_batch.into(), &mut working_set)
+        .unwrap();
+    assert!(soft_confirmation_rule_enforcer
+        .spend_module_call_budget("accounts", 50, &mut working_set)
+        .is_ok());
+}
+
+#[test]
+fn only_authority_can_modify_module_call_budget() {
+    let (soft_confirmation_rule_enforcer, mut working_set) =
+        get_soft_confirmation_rule_enforcer::<MockDaSpec>(&TEST_CONFIG);
+
+    let budget = ModuleCallBudget {
+        call_costs: BTreeMap::from([("soft_confirmation_rule_enforcer".to_string(), 7)]),
+        cost_per_byte: 0,
+        soft_confirmation_limit: None,
+    };
+    let sequencer_address = generate_address::<C>("sequencer");
+
+    let outsider = C::new(generate_address::<C>("outsider"), sequencer_address, 1);
+    assert!(soft_confirmation_rule_enforcer
+        .call(
+            CallMessage::ModifyModuleCallBudget {
+                budget: budget.clone(),
+            },
+            &outsider,
+            &mut working_set,
+        )
+        .is_err());
+
+    let authority = <DefaultContext as Spec>::Address::from_str(
+        "sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94",
+    )
+    .unwrap();
+    let authority = C::new(authority, sequencer_address, 1);
+    soft_confirmation_rule_enforcer
+        .call(
+            CallMessage::ModifyModuleCallBudget {
+                budget: budget.clone(),
+            },
+            &authority,
+            &mut working_set,
+        )
+        .unwrap();
+
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .get_module_call_budget(&mut working_set)
+            .unwrap(),
+        budget
+    );
+}
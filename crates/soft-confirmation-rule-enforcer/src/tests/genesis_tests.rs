@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use lazy_static::lazy_static;
@@ -6,7 +7,10 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::{DaSpec, Module, Spec, StateValueAccessor, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
 
-use crate::{SoftConfirmationRuleEnforcer, SoftConfirmationRuleEnforcerConfig};
+use crate::{
+    GenesisConfigError, ModuleCallBudget, SoftConfirmationRuleEnforcer,
+    SoftConfirmationRuleEnforcerConfig, MAX_LIMITING_NUMBER,
+};
 
 type C = DefaultContext;
 
@@ -19,6 +23,11 @@ lazy_static! {
             )
             .unwrap(),
             l1_fee_rate_change_percentage: 10,
+            module_call_budget: ModuleCallBudget {
+                call_costs: BTreeMap::from([("accounts".to_string(), 1000)]),
+                cost_per_byte: 10,
+                soft_confirmation_limit: Some(4000),
+            },
         };
 }
 
@@ -52,6 +61,78 @@ fn genesis_data() {
             .unwrap(),
         l1_fee_rate_change_percentage
     );
+
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .module_call_budget
+            .get(&mut working_set)
+            .unwrap(),
+        TEST_CONFIG.module_call_budget
+    );
+}
+
+#[test]
+fn genesis_validation() {
+    let config = |f: fn(&mut SoftConfirmationRuleEnforcerConfig<C>)| {
+        let mut config = TEST_CONFIG.clone();
+        f(&mut config);
+        config.validate()
+    };
+
+    assert_eq!(config(|_| {}), Ok(()));
+    assert_eq!(
+        config(|c| c.authority = <C as Spec>::Address::from([0; 32])),
+        Err(GenesisConfigError::ZeroAuthority)
+    );
+    assert_eq!(
+        config(|c| c.limiting_number = 0),
+        Err(GenesisConfigError::LimitingNumberOutOfBounds(0))
+    );
+    assert_eq!(
+        config(|c| c.limiting_number = MAX_LIMITING_NUMBER + 1),
+        Err(GenesisConfigError::LimitingNumberOutOfBounds(
+            MAX_LIMITING_NUMBER + 1
+        ))
+    );
+    assert_eq!(
+        config(|c| c.l1_fee_rate_change_percentage = 101),
+        Err(GenesisConfigError::L1FeeRateChangePercentageOutOfBounds(
+            101
+        ))
+    );
+
+    let err = config(|c| c.module_call_budget.soft_confirmation_limit = Some(999)).unwrap_err();
+    assert_eq!(err.field(), "module_call_budget.call_costs.accounts");
+    assert_eq!(
+        err.to_string(),
+        "module_call_budget.call_costs.accounts: 1000 is over the soft confirmation limit 999"
+    );
+
+    // Genesis fails on invalid configs
+    let mut invalid_config = TEST_CONFIG.clone();
+    invalid_config.limiting_number = 0;
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    assert!(SoftConfirmationRuleEnforcer::<C, MockDaSpec>::default()
+        .genesis(&invalid_config, &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn genesis_config_builder() {
+    let config = SoftConfirmationRuleEnforcerConfig::<C>::builder(TEST_CONFIG.authority)
+        .limiting_number(TEST_CONFIG.limiting_number)
+        .l1_fee_rate_change_percentage(TEST_CONFIG.l1_fee_rate_change_percentage)
+        .module_call_budget(TEST_CONFIG.module_call_budget.clone())
+        .build()
+        .unwrap();
+    assert_eq!(config, *TEST_CONFIG);
+
+    let err = SoftConfirmationRuleEnforcerConfig::<C>::builder(TEST_CONFIG.authority)
+        .l1_fee_rate_change_percentage(200)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.field(), "l1_fee_rate_change_percentage");
 }
 
 pub(crate) fn get_soft_confirmation_rule_enforcer<Da: DaSpec>(
@@ -1,14 +1,16 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use sov_hook_test_kit::{HookTestKit, SoftConfirmationBuilder};
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address;
-use sov_modules_api::{Context, Module, Spec, StateValueAccessor};
+use sov_modules_api::{Context, Module, SoftConfirmationEnforcement, Spec, StateValueAccessor};
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 
 use crate::call::CallMessage;
 use crate::tests::genesis_tests::{get_soft_confirmation_rule_enforcer, TEST_CONFIG};
+use crate::SoftConfirmationRuleEnforcer;
 
 type C = DefaultContext;
 
@@ -33,8 +35,18 @@ fn begin_soft_confirmation_hook_checks_limiting_number() {
         .call(call_message, &context, &mut working_set)
         .unwrap();
 
-    let signed_soft_confirmation_batch =
-        SignedSoftConfirmationBatch::new([0; 32], 0, [0; 32], vec![], 1, vec![], vec![], vec![]);
+    let signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        1,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
 
     // call begin_slot_hook 11 times
     for i in 0..11 {
@@ -56,8 +68,18 @@ fn begin_soft_confirmation_hook_checks_l1_fee_rate() {
     let (soft_confirmation_rule_enforcer, mut working_set) =
         get_soft_confirmation_rule_enforcer::<MockDaSpec>(&TEST_CONFIG);
 
-    let mut signed_soft_confirmation_batch =
-        SignedSoftConfirmationBatch::new([0; 32], 0, [0; 32], vec![], 100, vec![], vec![], vec![]);
+    let mut signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        100,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
 
     // call first with 100 fee rate to set last_l1_fee_rate
     let res = soft_confirmation_rule_enforcer.begin_soft_confirmation_hook(
@@ -185,3 +207,61 @@ fn begin_soft_confirmation_hook_checks_l1_fee_rate() {
 
     assert!(res.is_ok());
 }
+
+#[test]
+fn rejected_soft_confirmation_does_not_count_towards_limiting_number() {
+    let soft_confirmation_rule_enforcer = SoftConfirmationRuleEnforcer::<C, MockDaSpec>::default();
+    let mut kit = HookTestKit::new().with_genesis(&soft_confirmation_rule_enforcer, &TEST_CONFIG);
+
+    let batch = SoftConfirmationBuilder::default().l1_fee_rate(100).build();
+    kit.run_hook(|working_set| {
+        soft_confirmation_rule_enforcer
+            .begin_soft_confirmation_hook(&mut batch.clone().into(), working_set)
+    })
+    .unwrap();
+
+    // The block count rule passes but the fee rate rule rejects the soft confirmation
+    let batch = SoftConfirmationBuilder::default().l1_fee_rate(111).build();
+    assert!(kit
+        .run_hook(|working_set| {
+            soft_confirmation_rule_enforcer
+                .begin_soft_confirmation_hook(&mut batch.clone().into(), working_set)
+        })
+        .is_err());
+
+    assert_eq!(
+        soft_confirmation_rule_enforcer
+            .get_block_count_by_da_root_hash([0; 32], kit.working_set())
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn end_soft_confirmation_hook_returns_enforcement() {
+    let soft_confirmation_rule_enforcer = SoftConfirmationRuleEnforcer::<C, MockDaSpec>::default();
+    let mut kit = HookTestKit::new().with_genesis(&soft_confirmation_rule_enforcer, &TEST_CONFIG);
+
+    for l1_fee_rate in [100, 105] {
+        let batch = SoftConfirmationBuilder::default()
+            .l1_fee_rate(l1_fee_rate)
+            .build();
+        kit.run_hook(|working_set| {
+            soft_confirmation_rule_enforcer
+                .begin_soft_confirmation_hook(&mut batch.clone().into(), working_set)
+        })
+        .unwrap();
+
+        let enforcement = soft_confirmation_rule_enforcer
+            .end_soft_confirmation_hook(&batch.into(), kit.working_set());
+        assert_eq!(
+            enforcement,
+            SoftConfirmationEnforcement {
+                l2_blocks_on_da_slot: if l1_fee_rate == 100 { 1 } else { 2 },
+                max_l2_blocks_on_da_slot: TEST_CONFIG.limiting_number,
+                l1_fee_rate,
+                max_l1_fee_rate_change_percentage: TEST_CONFIG.l1_fee_rate_change_percentage,
+            }
+        );
+    }
+}
@@ -1,4 +1,6 @@
 #[cfg(test)]
+mod budget_tests;
+#[cfg(test)]
 mod call_tests;
 #[cfg(test)]
 mod genesis_tests;
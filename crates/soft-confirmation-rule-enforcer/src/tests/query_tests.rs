@@ -9,8 +9,18 @@ fn block_count_per_da_hash_must_be_correct() {
     let (soft_confirmation_rule_enforcer, mut working_set) =
         get_soft_confirmation_rule_enforcer::<MockDaSpec>(&TEST_CONFIG);
 
-    let mut signed_soft_confirmation_batch =
-        SignedSoftConfirmationBatch::new([0; 32], 0, [0; 32], vec![], 1, vec![], vec![], vec![]);
+    let mut signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        1,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
     // call begin_slot_hook a couple times for da hash 0
     for _ in 0..3 {
         soft_confirmation_rule_enforcer
@@ -60,8 +70,18 @@ fn get_max_l1_fee_rate_change_percentage_must_be_correct() {
         10
     );
 
-    let signed_soft_confirmation_batch =
-        SignedSoftConfirmationBatch::new([0; 32], 0, [0; 32], vec![], 1, vec![], vec![], vec![]);
+    let signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        1,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
 
     soft_confirmation_rule_enforcer
         .begin_soft_confirmation_hook(
@@ -91,8 +111,18 @@ fn get_last_l1_fee_rate_must_be_correct() {
         0
     );
 
-    let signed_soft_confirmation_batch =
-        SignedSoftConfirmationBatch::new([0; 32], 0, [0; 32], vec![], 1, vec![], vec![], vec![]);
+    let signed_soft_confirmation_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        0,
+        [0; 32],
+        vec![],
+        1,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        None,
+    );
     soft_confirmation_rule_enforcer
         .begin_soft_confirmation_hook(
             &mut signed_soft_confirmation_batch.clone().into(),
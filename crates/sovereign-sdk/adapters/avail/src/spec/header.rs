@@ -2,7 +2,9 @@
 use avail_subxt::primitives::Header as SubxtHeader;
 use primitive_types::H256;
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::da::{
+    BlockHeaderTrait, LightClientError, LightClientHeader, LinkedChainTip,
+};
 
 const KATE_START_TIME: i64 = 1686066440;
 const KATE_SECONDS_PER_BLOCK: i64 = 20;
@@ -64,6 +66,18 @@ impl BlockHeaderTrait for AvailHeader {
     }
 }
 
+impl LightClientHeader for AvailHeader {
+    type ChainTip = LinkedChainTip;
+
+    fn tip_hash(tip: &Self::ChainTip) -> [u8; 32] {
+        tip.hash
+    }
+
+    fn extend_tip(&self, tip: &Self::ChainTip) -> Result<Self::ChainTip, LightClientError> {
+        tip.extend(self)
+    }
+}
+
 #[cfg(feature = "native")]
 impl
     From<
@@ -9,7 +9,10 @@ use celestia_types::{DataAvailabilityHeader, ExtendedHeader};
 use prost::bytes::Buf;
 use prost::Message;
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::da::{BlockHeaderTrait as BlockHeader, CountedBufReader, Time};
+use sov_rollup_interface::da::{
+    BlockHeaderTrait as BlockHeader, CountedBufReader, LightClientError, LightClientHeader,
+    LinkedChainTip, Time,
+};
 use sov_rollup_interface::services::da::SlotData;
 pub use tendermint::block::Header as TendermintHeader;
 use tendermint::block::Height;
@@ -254,6 +257,18 @@ impl BlockHeader for CelestiaHeader {
     }
 }
 
+impl LightClientHeader for CelestiaHeader {
+    type ChainTip = LinkedChainTip;
+
+    fn tip_hash(tip: &Self::ChainTip) -> [u8; 32] {
+        tip.hash
+    }
+
+    fn extend_tip(&self, tip: &Self::ChainTip) -> Result<Self::ChainTip, LightClientError> {
+        tip.extend(self)
+    }
+}
+
 /// We implement [`SlotData`] for [`CelestiaHeader`] in a similar fashion as for
 /// [`FilteredCelestiaBlock`](crate::types::FilteredCelestiaBlock).
 impl SlotData for CelestiaHeader {
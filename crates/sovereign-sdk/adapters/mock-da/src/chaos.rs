@@ -0,0 +1,77 @@
+//! Failure points which can be switched on from tests, so that the recovery paths of the
+//! rollup are covered by the integration tests and not only the happy path.
+//!
+//! Faults are scoped to the current thread in the same way as the [`MockDaService`](crate::MockDaService)
+//! database, so tests running in parallel do not affect each other.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::db_connector::thread_scope;
+
+lazy_static! {
+    static ref FAULTS: Mutex<HashMap<(String, Fault), usize>> = Mutex::new(HashMap::new());
+}
+
+/// A failure point which can be injected with [`inject`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// `MockDaService::send_transaction` returns an error without publishing the blob.
+    DaSubmissionError,
+    /// `MockDaService::send_transaction` succeeds, but the published blob is corrupted.
+    DaCorruptBlob,
+    /// The sequencer fails to write a soft confirmation to the ledger.
+    LedgerWriteError,
+    /// Requests of the sequencer client fail with a connection error.
+    RpcConnectionDrop,
+    /// The dry run of a block by the sequencer gives different transaction effects than its
+    /// execution.
+    DryRunMismatch,
+}
+
+/// Makes the next `times` hits of `fault` fail in the current thread.
+pub fn inject(fault: Fault, times: usize) {
+    FAULTS
+        .lock()
+        .unwrap()
+        .insert((thread_scope(), fault), times);
+}
+
+/// Removes all faults injected in the current thread.
+pub fn clear() {
+    let scope = thread_scope();
+    FAULTS
+        .lock()
+        .unwrap()
+        .retain(|(fault_scope, _), _| fault_scope != &scope);
+}
+
+/// Returns how many hits of `fault` injected in the current thread are left, so that tests
+/// can check the failure point was actually hit.
+pub fn remaining(fault: Fault) -> usize {
+    FAULTS
+        .lock()
+        .unwrap()
+        .get(&(thread_scope(), fault))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Returns true if `fault` is injected in the current thread and consumes one of its hits.
+pub fn should_fail(fault: Fault) -> bool {
+    let mut faults = FAULTS.lock().unwrap();
+    let key = (thread_scope(), fault);
+    match faults.get_mut(&key) {
+        Some(remaining) if *remaining > 0 => {
+            *remaining -= 1;
+            if *remaining == 0 {
+                faults.remove(&key);
+            }
+            tracing::warn!("Injecting fault {:?}", fault);
+            true
+        }
+        _ => false,
+    }
+}
@@ -19,14 +19,10 @@ pub(crate) struct DbConnector {
 
 impl DbConnector {
     pub fn new() -> Self {
-        let thread = std::thread::current();
-        let mut thread_name = thread.name().unwrap_or("unnamed");
-        if thread_name == "tokio-runtime-worker" {
-            thread_name = "main"
-        }
+        let thread_name = thread_scope();
         let dir = workspace_dir()
             .join("test-da-dbs")
-            .join(thread_name.to_string() + ".db");
+            .join(thread_name.clone() + ".db");
         let db_name = dir.to_str().unwrap().to_string();
 
         debug!("Using test db: {}", db_name);
@@ -50,11 +46,11 @@ impl DbConnector {
         // keep main thread's data since main thread runs only when running demo or mocknet
         // we would like to keep da data in that case
         let mut set = USED_THREAD.lock().unwrap();
-        if !set.contains(&thread_name.to_string()) && thread_name != "main" {
+        if !set.contains(&thread_name) && thread_name != "main" {
             debug!("deleting db");
             conn.execute("DELETE FROM blocks", ())
                 .expect("DbConnector: failed to delete all rows");
-            set.insert(thread_name.to_string());
+            set.insert(thread_name);
         }
 
         Self { conn }
@@ -161,6 +157,17 @@ impl DbConnector {
     }
 }
 
+/// Name of the current thread, which scopes the test data to the test that is running.
+/// Tokio worker threads are shared by the demo and mocknet, so they use the `main` scope.
+pub(crate) fn thread_scope() -> String {
+    let thread = std::thread::current();
+    match thread.name() {
+        Some("tokio-runtime-worker") => "main".to_string(),
+        Some(name) => name.to_string(),
+        None => "unnamed".to_string(),
+    }
+}
+
 fn workspace_dir() -> PathBuf {
     let output = std::process::Command::new(env!("CARGO"))
         .arg("locate-project")
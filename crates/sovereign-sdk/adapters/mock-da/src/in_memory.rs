@@ -0,0 +1,178 @@
+//! Synchronous in-memory DA layer for unit tests of modules and state transition functions.
+//!
+//! Unlike [`MockDaService`](crate::MockDaService), it needs no async runtime and no database:
+//! blobs are queued and sealed into a block on demand, so tests can drive the DA layer block
+//! by block without waiting.
+
+use sov_rollup_interface::da::{DaSpec, Time};
+
+use crate::types::{block_hash, hash_to_array, GENESIS_HEADER};
+use crate::{MockAddress, MockBlob, MockBlock, MockBlockHeader, MockDaSpec};
+
+/// In-memory chain of [`MockBlock`]s, extended by [`InMemoryDaLayer::produce_block`].
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDaLayer {
+    blocks: Vec<MockBlock>,
+    pending_blobs: Vec<MockBlob>,
+}
+
+impl InMemoryDaLayer {
+    /// Creates a DA layer with only the genesis block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a sender address whose bytes are all `byte`.
+    pub fn sender(byte: u8) -> MockAddress {
+        MockAddress::new([byte; 32])
+    }
+
+    /// Crafts a blob with `data` sent by `sender`, hashed like the blobs of
+    /// [`MockDaService`](crate::MockDaService).
+    pub fn blob(data: &[u8], sender: MockAddress) -> MockBlob {
+        MockBlob::new(data.to_vec(), sender, hash_to_array(data))
+    }
+
+    /// Crafts a blob carrying an aggregated ZK proof sent by `sender`.
+    pub fn proof_blob(proof: &[u8], sender: MockAddress) -> MockBlob {
+        MockBlob::new_with_zkp_proof(vec![], proof.to_vec(), sender, hash_to_array(&[]))
+    }
+
+    /// Queues a blob with `data` sent by `sender` for the next block, returning its hash.
+    pub fn send_blob(&mut self, data: &[u8], sender: MockAddress) -> [u8; 32] {
+        let blob = Self::blob(data, sender);
+        let hash = blob.hash;
+        self.pending_blobs.push(blob);
+        hash
+    }
+
+    /// Queues an aggregated ZK proof sent by `sender` for the next block.
+    pub fn send_proof(&mut self, proof: &[u8], sender: MockAddress) {
+        self.pending_blobs.push(Self::proof_blob(proof, sender));
+    }
+
+    /// Seals the queued blobs, if any, into a new block on top of the chain.
+    pub fn produce_block(&mut self) -> &MockBlock {
+        let blobs = std::mem::take(&mut self.pending_blobs);
+        self.produce_block_with_blobs(blobs)
+    }
+
+    /// Adds a new block with `blobs` on top of the chain. The queued blobs are left for the
+    /// next block.
+    pub fn produce_block_with_blobs(&mut self, blobs: Vec<MockBlob>) -> &MockBlock {
+        let head = self.head();
+        let height = head.height + 1;
+
+        let data_hashes: Vec<u8> = blobs.iter().flat_map(|blob| blob.hash).collect();
+        let proofs: Vec<u8> = blobs
+            .iter()
+            .flat_map(|blob| blob.zk_proofs_data.iter().copied())
+            .collect();
+        let hash = block_hash(
+            height,
+            hash_to_array(&data_hashes),
+            hash_to_array(&proofs),
+            head.hash.into(),
+        );
+
+        self.blocks.push(MockBlock {
+            header: MockBlockHeader {
+                prev_hash: head.hash,
+                hash,
+                height,
+                // Deterministic, for the state roots of tests not to depend on the clock
+                time: Time::from_secs(GENESIS_HEADER.time.secs() + height as i64),
+            },
+            validity_cond: Default::default(),
+            blobs,
+        });
+        self.blocks.last().expect("A block was just added")
+    }
+
+    /// Returns the header of the last block, the genesis one if no block was produced.
+    pub fn head(&self) -> MockBlockHeader {
+        self.blocks
+            .last()
+            .map(|block| block.header.clone())
+            .unwrap_or(GENESIS_HEADER)
+    }
+
+    /// Returns the block at `height`, `None` if it was not produced. The genesis block at
+    /// height 0 has no blobs and is not queryable, like with
+    /// [`MockDaService`](crate::MockDaService).
+    pub fn block_at(&self, height: u64) -> Option<&MockBlock> {
+        let index = height.checked_sub(1)?;
+        self.blocks.get(index as usize)
+    }
+
+    /// Returns the block with `hash`, `None` if it was not produced.
+    pub fn block_by_hash(&self, hash: [u8; 32]) -> Option<&MockBlock> {
+        self.blocks.iter().find(|block| block.header.hash.0 == hash)
+    }
+
+    /// Returns the blobs of `block` with the inclusion and completeness proofs accepted by
+    /// [`MockDaVerifier`](crate::MockDaVerifier), as the STF receives them.
+    pub fn relevant_blobs_with_proof(
+        block: &MockBlock,
+    ) -> (
+        Vec<MockBlob>,
+        <MockDaSpec as DaSpec>::InclusionMultiProof,
+        <MockDaSpec as DaSpec>::CompletenessProof,
+    ) {
+        (block.blobs.clone(), [0u8; 32], ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_rollup_interface::da::{BlobReaderTrait, DaVerifier};
+
+    use super::*;
+    use crate::MockDaVerifier;
+
+    #[test]
+    fn test_blocks_are_chained_and_carry_queued_blobs() {
+        let mut da = InMemoryDaLayer::new();
+        assert_eq!(da.head(), GENESIS_HEADER);
+        assert!(da.block_at(0).is_none());
+
+        let sequencer = InMemoryDaLayer::sender(1);
+        let prover = InMemoryDaLayer::sender(2);
+        let blob_hash = da.send_blob(b"batch", sequencer);
+        da.send_proof(b"proof", prover);
+        let first = da.produce_block().clone();
+        assert_eq!(first.header.height, 1);
+        assert_eq!(first.header.prev_hash, GENESIS_HEADER.hash);
+        assert_eq!(first.blobs.len(), 2);
+        assert_eq!(first.blobs[0].hash(), blob_hash);
+        assert_eq!(first.blobs[0].sender(), sequencer);
+        assert_eq!(first.blobs[0].total_len(), 5);
+        assert_eq!(first.blobs[1].zk_proofs_data, b"proof".to_vec());
+
+        let second = da.produce_block().clone();
+        assert_eq!(second.header.prev_hash, first.header.hash);
+        assert_ne!(second.header.hash, first.header.hash);
+        assert!(second.blobs.is_empty());
+
+        assert_eq!(da.head(), second.header);
+        assert_eq!(da.block_at(1), Some(&first));
+        assert_eq!(da.block_by_hash(second.header.hash.0), Some(&second));
+        assert!(da.block_at(3).is_none());
+
+        let (blobs, inclusion_proof, completeness_proof) =
+            InMemoryDaLayer::relevant_blobs_with_proof(&first);
+        assert!(MockDaVerifier::new(())
+            .verify_relevant_tx_list(&first.header, &blobs, inclusion_proof, completeness_proof)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_block_hashes_are_deterministic() {
+        let produce = || {
+            let mut da = InMemoryDaLayer::new();
+            da.send_blob(b"batch", InMemoryDaLayer::sender(1));
+            da.produce_block().header.clone()
+        };
+        assert_eq!(produce(), produce());
+    }
+}
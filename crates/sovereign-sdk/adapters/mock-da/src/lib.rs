@@ -1,8 +1,11 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "native")]
+pub mod chaos;
 #[cfg(feature = "native")]
 mod db_connector;
+mod in_memory;
 #[cfg(feature = "native")]
 mod service;
 mod types;
@@ -10,6 +13,7 @@ mod validity_condition;
 /// Contains DaSpec and DaVerifier
 pub mod verifier;
 
+pub use in_memory::InMemoryDaLayer;
 #[cfg(feature = "native")]
 pub use service::*;
 pub use types::*;
@@ -5,25 +5,19 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use pin_project::pin_project;
-use sha2::Digest;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, Time};
 use sov_rollup_interface::maybestd::sync::Arc;
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use tokio::sync::{broadcast, Mutex as AsyncMutex};
 use tokio::time;
 
+use crate::chaos::{self, Fault};
 use crate::db_connector::DbConnector;
-use crate::types::{MockAddress, MockBlob, MockBlock, MockDaVerifier};
-use crate::verifier::MockDaSpec;
-use crate::{MockBlockHeader, MockHash};
-
-const GENESIS_HEADER: MockBlockHeader = MockBlockHeader {
-    prev_hash: MockHash([0; 32]),
-    hash: MockHash([1; 32]),
-    height: 0,
-    // 2023-01-01T00:00:00Z
-    time: Time::from_secs(1672531200),
+use crate::types::{
+    block_hash, hash_to_array, MockAddress, MockBlob, MockBlock, MockDaVerifier, GENESIS_HEADER,
 };
+use crate::verifier::MockDaSpec;
+use crate::MockBlockHeader;
 
 /// Definition of a fork that will be executed in `MockDaService` at specified height
 pub struct PlannedFork {
@@ -369,6 +363,14 @@ impl DaService for MockDaService {
     }
 
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
+        if chaos::should_fail(Fault::DaSubmissionError) {
+            anyhow::bail!("Injected DA submission failure");
+        }
+        if chaos::should_fail(Fault::DaCorruptBlob) {
+            let corrupted: Vec<u8> = blob.iter().map(|byte| !byte).chain([0xff]).collect();
+            let _ = self.add_blob(&corrupted, Default::default()).await?;
+            return Ok(());
+        }
         let _ = self.add_blob(blob, Default::default()).await?;
         Ok(())
     }
@@ -396,30 +398,6 @@ impl DaService for MockDaService {
     }
 }
 
-fn hash_to_array(bytes: &[u8]) -> [u8; 32] {
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(bytes);
-    let result = hasher.finalize();
-    result
-        .as_slice()
-        .try_into()
-        .expect("SHA256 should be 32 bytes")
-}
-
-fn block_hash(
-    height: u64,
-    data_hash: [u8; 32],
-    proof_hash: [u8; 32],
-    prev_hash: [u8; 32],
-) -> MockHash {
-    let mut block_to_hash = height.to_be_bytes().to_vec();
-    block_to_hash.extend_from_slice(&data_hash[..]);
-    block_to_hash.extend_from_slice(&proof_hash[..]);
-    block_to_hash.extend_from_slice(&prev_hash[..]);
-
-    MockHash::from(hash_to_array(&block_to_hash))
-}
-
 #[cfg(test)]
 mod tests {
     use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait};
@@ -818,6 +796,28 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn injected_faults() {
+        let mut da = MockDaService::new(MockAddress::new([1; 32]));
+        da.wait_attempts = 2;
+
+        chaos::inject(Fault::DaSubmissionError, 1);
+        assert_eq!(chaos::remaining(Fault::DaSubmissionError), 1);
+        assert!(da.send_transaction(&[1, 2, 3]).await.is_err());
+        assert_eq!(chaos::remaining(Fault::DaSubmissionError), 0);
+        assert_eq!(da.get_head_block_header().await.unwrap(), GENESIS_HEADER);
+
+        chaos::inject(Fault::DaCorruptBlob, 1);
+        da.send_transaction(&[1, 2, 3]).await.unwrap();
+        let mut block = da.get_block_at(1).await.unwrap();
+        assert_ne!(block.blobs[0].full_data(), &[1, 2, 3]);
+
+        // Faults are consumed, the next submission succeeds
+        da.send_transaction(&[1, 2, 3]).await.unwrap();
+        let mut block = da.get_block_at(2).await.unwrap();
+        assert_eq!(block.blobs[0].full_data(), &[1, 2, 3]);
+    }
+
     fn assert_consecutive_blocks(block1: &MockBlock, block2: &MockBlock) {
         assert_eq!(block2.header().prev_hash(), block1.header().hash())
     }
@@ -6,12 +6,24 @@ use std::hash::Hasher;
 pub use address::{MockAddress, MOCK_SEQUENCER_DA_ADDRESS};
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::da::{BlockHashTrait, BlockHeaderTrait, CountedBufReader, Time};
+use sha2::Digest;
+use sov_rollup_interface::da::{
+    BlockHashTrait, BlockHeaderTrait, CountedBufReader, LightClientError, LightClientHeader,
+    LinkedChainTip, Time,
+};
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::Bytes;
 
 use crate::validity_condition::MockValidityCond;
 
+pub(crate) const GENESIS_HEADER: MockBlockHeader = MockBlockHeader {
+    prev_hash: MockHash([0; 32]),
+    hash: MockHash([1; 32]),
+    height: 0,
+    // 2023-01-01T00:00:00Z
+    time: Time::from_secs(1672531200),
+};
+
 /// A mock hash digest.
 #[derive(
     Clone,
@@ -130,6 +142,18 @@ impl BlockHeaderTrait for MockBlockHeader {
     }
 }
 
+impl LightClientHeader for MockBlockHeader {
+    type ChainTip = LinkedChainTip;
+
+    fn tip_hash(tip: &Self::ChainTip) -> [u8; 32] {
+        tip.hash
+    }
+
+    fn extend_tip(&self, tip: &Self::ChainTip) -> Result<Self::ChainTip, LightClientError> {
+        tip.extend(self)
+    }
+}
+
 /// The configuration for mock da
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct MockDaConfig {
@@ -225,6 +249,30 @@ impl MockBlock {
     }
 }
 
+pub(crate) fn hash_to_array(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(bytes);
+    let result = hasher.finalize();
+    result
+        .as_slice()
+        .try_into()
+        .expect("SHA256 should be 32 bytes")
+}
+
+pub(crate) fn block_hash(
+    height: u64,
+    data_hash: [u8; 32],
+    proof_hash: [u8; 32],
+    prev_hash: [u8; 32],
+) -> MockHash {
+    let mut block_to_hash = height.to_be_bytes().to_vec();
+    block_to_hash.extend_from_slice(&data_hash[..]);
+    block_to_hash.extend_from_slice(&proof_hash[..]);
+    block_to_hash.extend_from_slice(&prev_hash[..]);
+
+    MockHash::from(hash_to_array(&block_to_hash))
+}
+
 fn u64_to_bytes(value: u64) -> [u8; 32] {
     let value = value.to_be_bytes();
     let mut result = [0u8; 32];
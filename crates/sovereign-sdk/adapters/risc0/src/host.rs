@@ -14,6 +14,8 @@ use crate::Risc0MethodId;
 pub struct Risc0Host<'a> {
     env: Vec<u32>,
     elf: &'a [u8],
+    /// Cycles of the last execution
+    cycles: Option<u64>,
 }
 
 #[cfg(not(feature = "bench"))]
@@ -43,6 +45,7 @@ impl<'a> Risc0Host<'a> {
         Self {
             env: Default::default(),
             elf,
+            cycles: None,
         }
     }
 
@@ -54,7 +57,9 @@ impl<'a> Risc0Host<'a> {
             .build()
             .unwrap();
         let mut executor = ExecutorImpl::from_elf(env, self.elf)?;
-        executor.run()
+        let session = executor.run()?;
+        self.cycles = session.get_cycles().ok();
+        Ok(session)
     }
     /// Run a computation in the zkvm and generate a receipt.
     pub fn run(&mut self) -> anyhow::Result<Receipt> {
@@ -97,6 +102,10 @@ impl<'a> ZkvmHost for Risc0Host<'a> {
         }
     }
 
+    fn cycle_count(&self) -> Option<u64> {
+        self.cycles
+    }
+
     fn extract_output<Da: sov_rollup_interface::da::DaSpec, Root: Serialize + DeserializeOwned>(
         proof: &Proof,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
@@ -96,6 +96,7 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
                 batch_hash: hash,
                 tx_receipts: vec![],
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: None,
             });
         }
 
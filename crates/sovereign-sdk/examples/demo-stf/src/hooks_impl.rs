@@ -5,7 +5,7 @@ use sov_modules_api::hooks::{
     HookSoftConfirmationInfo, SlotHooks, TxHooks,
 };
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{AccessoryWorkingSet, Context, Spec, WorkingSet};
+use sov_modules_api::{AccessoryWorkingSet, Context, SoftConfirmationOutcome, Spec, WorkingSet};
 use sov_modules_stf_blueprint::{RuntimeTxHook, SequencerOutcome};
 use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 use sov_state::Storage;
@@ -67,8 +67,7 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
 
 impl<C: Context, Da: DaSpec> ApplySoftConfirmationHooks<Da> for Runtime<C, Da> {
     type Context = C;
-    type SoftConfirmationResult =
-        SequencerOutcome<<<Da as DaSpec>::BlobTransaction as BlobReaderTrait>::Address>;
+    type SoftConfirmationResult = SoftConfirmationOutcome;
 
     fn begin_soft_confirmation_hook(
         &self,
@@ -81,9 +80,10 @@ impl<C: Context, Da: DaSpec> ApplySoftConfirmationHooks<Da> for Runtime<C, Da> {
 
     fn end_soft_confirmation_hook(
         &self,
+        _soft_batch: &HookSoftConfirmationInfo,
         _working_set: &mut WorkingSet<C>,
-    ) -> Result<(), ApplySoftConfirmationError> {
-        Ok(())
+    ) -> Result<SoftConfirmationOutcome, ApplySoftConfirmationError> {
+        Ok(SoftConfirmationOutcome::default())
     }
 }
 
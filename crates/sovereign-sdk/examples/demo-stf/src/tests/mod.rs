@@ -45,7 +45,10 @@ pub(crate) fn get_genesis_config_for_tests<Da: DaSpec>(
             .unwrap();
 
     let chain_state = read_json_file(integ_test_conf_dir.join("chain_state.json")).unwrap();
-    let kernel_params = BasicKernelGenesisConfig { chain_state };
+    let kernel_params = BasicKernelGenesisConfig {
+        chain_state,
+        da_light_client: None,
+    };
     GenesisParams {
         runtime: rt_params,
         kernel: kernel_params,
@@ -1,3 +1,4 @@
+use borsh::BorshSerialize;
 use sov_cli::wallet_state::PrivateKeyAndAddress;
 use sov_data_generators::bank_data::get_default_token_address;
 use sov_data_generators::new_test_blob_from_batch;
@@ -5,9 +6,13 @@ use sov_mock_da::{MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_modules_api::{Context, PrivateKey, WorkingSet};
-use sov_modules_stf_blueprint::{Batch, StfBlueprint};
+use sov_modules_stf_blueprint::{Batch, StfBlueprint, StfBlueprintTrait, TxEffect};
+use sov_rollup_interface::da::BlockHeaderTrait;
 use sov_rollup_interface::services::da::SlotData;
-use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::soft_confirmation::{
+    SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+};
+use sov_rollup_interface::stf::{Event, StateTransitionFunction};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 
 use crate::runtime::Runtime;
@@ -274,6 +279,112 @@ fn test_sequencer_unknown_sequencer() {
     // assert!(!has_tx_events(&apply_blob_outcome));
 }
 
+/// Applies a soft batch with its transactions given to the STF in chunks of `chunk_size`, and
+/// returns the state root and the contents of the transaction receipts
+fn apply_soft_batch_in_chunks(
+    chunk_size: usize,
+) -> (Vec<u8>, Vec<([u8; 32], Vec<Event>, TxEffect)>) {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path();
+    let mut storage_manager = create_storage_manager_for_tests(path);
+    let config = get_genesis_config_for_tests();
+
+    let genesis_block = MockBlock::default();
+    let block_1 = genesis_block.next_mock();
+
+    let stf: StfBlueprintTest = StfBlueprint::new();
+    let (genesis_root, storage) = stf.init_chain(
+        storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap(),
+        config,
+    );
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    let private_key = read_private_key::<DefaultContext>().private_key;
+    let txs: Vec<Vec<u8>> = simulate_da(private_key)
+        .into_iter()
+        .map(|tx| tx.data)
+        .collect();
+
+    let sequencer_key = read_private_key::<DefaultContext>().private_key;
+    let sequencer_pub_key = sequencer_key.pub_key().try_to_vec().unwrap();
+    let unsigned = UnsignedSoftConfirmationBatch::new(
+        block_1.header().height(),
+        block_1.header().hash().into(),
+        genesis_root.as_ref().to_vec(),
+        txs.clone(),
+        1,
+        None,
+        None,
+    );
+    let signature = sequencer_key.sign(&unsigned.try_to_vec().unwrap());
+    let mut soft_batch = SignedSoftConfirmationBatch::new(
+        [0; 32],
+        block_1.header().height(),
+        block_1.header().hash().into(),
+        genesis_root.as_ref().to_vec(),
+        1,
+        txs.clone(),
+        signature.try_to_vec().unwrap(),
+        sequencer_pub_key.clone(),
+        None,
+        None,
+    );
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let (result, mut batch_workspace) = stf.begin_soft_batch(
+        &sequencer_pub_key,
+        &genesis_root,
+        storage.clone(),
+        Default::default(),
+        block_1.header(),
+        &mut soft_batch,
+    );
+    result.unwrap();
+
+    let mut tx_receipts = vec![];
+    for chunk in txs.chunks(chunk_size) {
+        let (workspace, receipts) =
+            stf.apply_soft_batch_txs(&sequencer_pub_key, chunk.to_vec(), batch_workspace);
+        batch_workspace = workspace;
+        tx_receipts.extend(receipts);
+    }
+    let (batch_receipt, checkpoint) = stf.end_soft_batch(
+        &sequencer_pub_key,
+        &mut soft_batch,
+        tx_receipts,
+        batch_workspace,
+    );
+    let result = stf.finalize_soft_batch(batch_receipt, checkpoint, storage, &mut soft_batch);
+
+    let receipts = result.batch_receipts[0]
+        .tx_receipts
+        .iter()
+        .map(|receipt| (receipt.tx_hash, receipt.events.clone(), receipt.receipt))
+        .collect();
+    (result.state_root.as_ref().to_vec(), receipts)
+}
+
+#[test]
+fn test_soft_batch_applied_in_chunks() {
+    let (state_root, receipts) = apply_soft_batch_in_chunks(usize::MAX);
+    // 2 transactions from value setter
+    // 2 transactions from bank
+    assert_eq!(receipts.len(), 4);
+
+    for chunk_size in [1, 3] {
+        assert_eq!(
+            apply_soft_batch_in_chunks(chunk_size),
+            (state_root.clone(), receipts.clone()),
+            "chunks of {} transactions",
+            chunk_size
+        );
+    }
+}
+
 fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
     let token_deployer_data =
         std::fs::read_to_string("../test-data/keys/token_deployer_private_key.json")
@@ -0,0 +1,300 @@
+use std::fmt;
+
+use super::LedgerDB;
+use crate::schema::tables::{
+    BatchByHash, EventByNumber, L2RangeByL1Height, SoftBatchByHash, SoftBatchByNumber, TxByHash,
+    TxByNumber,
+};
+use crate::schema::types::{BatchNumber, EventNumber, SlotNumber, TxNumber};
+
+/// A single inconsistency found while verifying the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    /// The soft batch in which the inconsistency was found
+    pub height: BatchNumber,
+    /// Human readable description of the inconsistency
+    pub reason: String,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "soft batch {}: {}", self.height.0, self.reason)
+    }
+}
+
+impl LedgerDB {
+    /// Walks all soft batches from the first one up to and including `up_to` and cross-checks
+    /// them against the secondary indexes and against each other:
+    /// - soft batch numbers are contiguous
+    /// - each pre state root is equal to the previous post state root
+    /// - the hash index points back to the soft batch
+    /// - the stored transactions match the transaction range, the tx hash index and the events
+    /// - the soft batch is within the L2 range recorded for its DA slot
+    ///
+    /// If `tx_hasher` is provided, stored transaction bodies are re-hashed and compared
+    /// against the receipt hashes.
+    ///
+    /// Returns every issue found; an empty list means the ledger is consistent.
+    pub fn verify_soft_batches(
+        &self,
+        up_to: BatchNumber,
+        tx_hasher: Option<&dyn Fn(&[u8]) -> [u8; 32]>,
+    ) -> anyhow::Result<Vec<IntegrityIssue>> {
+        let mut issues = vec![];
+        let mut report = |height: u64, reason: String| {
+            issues.push(IntegrityIssue {
+                height: BatchNumber(height),
+                reason,
+            })
+        };
+
+        let mut prev_post_state_root: Option<Vec<u8>> = None;
+        let mut next_tx_number: Option<u64> = None;
+
+        for height in 1..=up_to.0 {
+            let Some(batch) = self.db.get::<SoftBatchByNumber>(&BatchNumber(height))? else {
+                report(height, "missing soft batch".to_string());
+                prev_post_state_root = None;
+                next_tx_number = None;
+                continue;
+            };
+
+            if let Some(prev_root) = prev_post_state_root.as_ref() {
+                if prev_root != &batch.pre_state_root {
+                    report(
+                        height,
+                        format!(
+                            "pre state root {} does not match previous post state root {}",
+                            hex::encode(&batch.pre_state_root),
+                            hex::encode(prev_root)
+                        ),
+                    );
+                }
+            }
+            prev_post_state_root = Some(batch.post_state_root.clone());
+
+            // Soft batches are currently indexed by hash in the `BatchByHash` table,
+            // so accept either of the indexes.
+            let indexed_number = match self.db.get::<SoftBatchByHash>(&batch.hash)? {
+                Some(number) => Some(number),
+                None => self.db.get::<BatchByHash>(&batch.hash)?,
+            };
+            if indexed_number != Some(BatchNumber(height)) {
+                report(
+                    height,
+                    format!(
+                        "hash {} is indexed to {:?}",
+                        hex::encode(batch.hash),
+                        indexed_number
+                    ),
+                );
+            }
+
+            let tx_range_len = batch.tx_range.end.0.saturating_sub(batch.tx_range.start.0);
+            if tx_range_len != batch.txs.len() as u64 {
+                report(
+                    height,
+                    format!(
+                        "tx range {:?} does not match the {} stored transactions",
+                        batch.tx_range,
+                        batch.txs.len()
+                    ),
+                );
+            }
+            if let Some(expected) = next_tx_number {
+                if batch.tx_range.start.0 != expected {
+                    report(
+                        height,
+                        format!(
+                            "tx range starts at {} but previous soft batch ended at {}",
+                            batch.tx_range.start.0, expected
+                        ),
+                    );
+                }
+            }
+            next_tx_number = Some(batch.tx_range.end.0);
+
+            for (tx_number, tx) in (batch.tx_range.start.0..).zip(batch.txs.iter()) {
+                match self.db.get::<TxByNumber>(&TxNumber(tx_number))? {
+                    Some(stored) if stored.hash == tx.hash => {}
+                    Some(_) => report(
+                        height,
+                        format!("tx {} does not match the soft batch receipt", tx_number),
+                    ),
+                    None => report(height, format!("missing tx {}", tx_number)),
+                }
+
+                if self.db.get::<TxByHash>(&tx.hash)? != Some(TxNumber(tx_number)) {
+                    report(
+                        height,
+                        format!(
+                            "tx hash {} is not indexed to {}",
+                            hex::encode(tx.hash),
+                            tx_number
+                        ),
+                    );
+                }
+
+                for event_number in tx.events.start.0..tx.events.end.0 {
+                    if self
+                        .db
+                        .get::<EventByNumber>(&EventNumber(event_number))?
+                        .is_none()
+                    {
+                        report(
+                            height,
+                            format!("missing event {} of tx {}", event_number, tx_number),
+                        );
+                    }
+                }
+
+                if let (Some(hasher), Some(body)) = (tx_hasher, tx.body.as_ref()) {
+                    if hasher(body) != tx.hash {
+                        report(
+                            height,
+                            format!("body of tx {} does not hash to its receipt hash", tx_number),
+                        );
+                    }
+                }
+            }
+
+            match self
+                .db
+                .get::<L2RangeByL1Height>(&SlotNumber(batch.da_slot_height))?
+            {
+                Some((start, end)) if start.0 <= height && height <= end.0 => {}
+                range => report(
+                    height,
+                    format!(
+                        "not within the L2 range {:?} of DA slot {}",
+                        range, batch.da_slot_height
+                    ),
+                ),
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use sov_mock_da::{MockDaSpec, MockHash};
+    use sov_rollup_interface::stf::{Event, SoftBatchReceipt, TransactionReceipt};
+
+    use super::*;
+
+    /// Hashes a transaction body by padding it to 32 bytes
+    fn tx_hash(body: &[u8]) -> [u8; 32] {
+        let mut hash = [0; 32];
+        hash[..body.len()].copy_from_slice(body);
+        hash
+    }
+
+    /// Commits `count` soft batches with a transaction and an event each, chaining the state
+    /// roots `[i]` to `[i + 1]`. Two soft batches are anchored to each DA slot.
+    fn ledger_with_soft_batches(count: u8) -> LedgerDB {
+        let ledger_db = LedgerDB::with_path(tempfile::tempdir().unwrap().into_path()).unwrap();
+        for i in 0..count {
+            let body = vec![i];
+            let receipt: SoftBatchReceipt<(), (), MockDaSpec> = SoftBatchReceipt {
+                da_slot_height: i as u64 / 2 + 1,
+                da_slot_hash: MockHash([i; 32]),
+                batch_hash: [i; 32],
+                tx_receipts: vec![TransactionReceipt {
+                    tx_hash: tx_hash(&body),
+                    body_to_save: Some(body),
+                    events: vec![Event::new("key", "value")],
+                    receipt: (),
+                }],
+                phantom_data: PhantomData,
+                pre_state_root: vec![i],
+                post_state_root: vec![i + 1],
+                soft_confirmation_signature: vec![],
+                pub_key: vec![],
+                l1_fee_rate: 0,
+                timestamp: None,
+                ordering_commitment: None,
+                outcome: None,
+            };
+            ledger_db.commit_soft_batch(receipt, true).unwrap();
+        }
+        ledger_db
+    }
+
+    fn issue_heights(issues: &[IntegrityIssue]) -> Vec<u64> {
+        issues.iter().map(|issue| issue.height.0).collect()
+    }
+
+    #[test]
+    fn test_consistent_ledger_has_no_issues() {
+        let ledger_db = ledger_with_soft_batches(4);
+        assert_eq!(
+            ledger_db
+                .verify_soft_batches(BatchNumber(4), Some(&tx_hash))
+                .unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_inconsistencies_are_reported() {
+        let ledger_db = ledger_with_soft_batches(4);
+
+        // Transaction bodies are checked against their hashes
+        let wrong_hasher = |_: &[u8]| [0xff; 32];
+        let issues = ledger_db
+            .verify_soft_batches(BatchNumber(4), Some(&wrong_hasher))
+            .unwrap();
+        assert_eq!(issue_heights(&issues), vec![1, 2, 3, 4]);
+
+        // Missing tx hash index
+        ledger_db.db.delete::<TxByHash>(&tx_hash(&[1])).unwrap();
+        // Broken state root chain
+        let mut batch = ledger_db
+            .db
+            .get::<SoftBatchByNumber>(&BatchNumber(3))
+            .unwrap()
+            .unwrap();
+        batch.pre_state_root = vec![0xff];
+        ledger_db
+            .db
+            .put::<SoftBatchByNumber>(&BatchNumber(3), &batch)
+            .unwrap();
+        // Missing L2 range of a DA slot
+        ledger_db
+            .db
+            .delete::<L2RangeByL1Height>(&SlotNumber(2))
+            .unwrap();
+
+        let issues = ledger_db
+            .verify_soft_batches(BatchNumber(4), Some(&tx_hash))
+            .unwrap();
+        assert_eq!(issue_heights(&issues), vec![2, 3, 3, 4]);
+        assert!(issues[0].reason.contains("is not indexed"));
+        assert!(issues[1].reason.contains("pre state root"));
+        assert!(issues[2].reason.contains("L2 range"));
+        assert!(issues[3].reason.contains("L2 range"));
+
+        // Soft batches above `up_to` are not checked
+        assert_eq!(
+            issue_heights(
+                &ledger_db
+                    .verify_soft_batches(BatchNumber(2), Some(&tx_hash))
+                    .unwrap()
+            ),
+            vec![2]
+        );
+
+        // Missing soft batch, after which the next one is not compared to it
+        ledger_db
+            .db
+            .delete::<SoftBatchByNumber>(&BatchNumber(1))
+            .unwrap();
+        let issues = ledger_db.verify_soft_batches(BatchNumber(2), None).unwrap();
+        assert_eq!(issue_heights(&issues), vec![1, 2]);
+        assert_eq!(issues[0].reason, "missing soft batch");
+    }
+}
@@ -9,17 +9,23 @@ use sov_schema_db::{Schema, SchemaBatch, SeekKeyEncoder, DB};
 
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByKey, EventByNumber, L2RangeByL1Height,
-    LastSequencerCommitmentSent, SlotByHash, SlotByNumber, SoftBatchByNumber,
-    SoftConfirmationStatus, TxByHash, TxByNumber, LEDGER_TABLES,
+    BatchByHash, BatchByNumber, ChainIdentity, CommitmentByL2End, EventByKey, EventByNumber,
+    L2RangeByL1Height, LastSequencerCommitmentSent, OrderingCommitmentByNumber,
+    PendingSoftBatchCommit, SlotByHash, SlotByNumber, SoftBatchByNumber,
+    SoftConfirmationOutcomeByNumber, SoftConfirmationStatus, TxByHash, TxByNumber,
+    UnknownDaDataByL1Height, LEDGER_TABLES,
 };
 use crate::schema::types::{
     split_tx_for_storage, BatchNumber, EventNumber, L2HeightRange, SlotNumber, StoredBatch,
-    StoredSlot, StoredSoftBatch, StoredTransaction, TxNumber,
+    StoredChainIdentity, StoredCommitmentLocation, StoredSlot, StoredSoftBatch, StoredTransaction,
+    TxNumber,
 };
 
+mod integrity;
 mod rpc;
 
+pub use integrity::IntegrityIssue;
+
 const LEDGER_DB_PATH_SUFFIX: &str = "ledger";
 
 #[derive(Clone, Debug)]
@@ -158,6 +164,14 @@ impl LedgerDB {
         self.get_data_range::<SoftBatchByNumber, _, _>(range)
     }
 
+    /// Gets the soft confirmation stored under `number`, if any.
+    pub fn get_stored_soft_batch(
+        &self,
+        number: BatchNumber,
+    ) -> Result<Option<StoredSoftBatch>, anyhow::Error> {
+        self.db.get::<SoftBatchByNumber>(&number)
+    }
+
     /// Gets all transactions with numbers `range.start` to `range.end`. If `range.end` is outside
     /// the range of the database, the result will smaller than the requested range.
     /// Note that this method blindly preallocates for the requested range, so it should not be exposed
@@ -241,7 +255,26 @@ impl LedgerDB {
         schema_batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
     }
 
+    fn put_l2_range_of_l1_slot(
+        &self,
+        l1_height: SlotNumber,
+        l2_height: BatchNumber,
+        schema_batch: &mut SchemaBatch,
+    ) -> Result<(), anyhow::Error> {
+        let current_range = self.db.get::<L2RangeByL1Height>(&l1_height)?;
+
+        let new_range = match current_range {
+            Some(existing) => (existing.0, l2_height),
+            None => (l2_height, l2_height),
+        };
+
+        schema_batch.put::<L2RangeByL1Height>(&l1_height, &new_range)
+    }
+
     /// Commits a soft batch to the database by inserting its transactions and batches before
+    /// inserting the soft batch itself. The L1 to L2 height index is updated and the pending
+    /// commit record is cleared in the same write, so the ledger entry and its indexes are
+    /// either all persisted or not at all.
     pub fn commit_soft_batch<B: Serialize, T: Serialize, DS: DaSpec>(
         &self,
         batch_receipt: SoftBatchReceipt<B, T, DS>,
@@ -297,9 +330,11 @@ impl LedgerDB {
             txs.push(tx_to_store);
         }
 
+        let da_slot_height = batch_receipt.da_slot_height;
+
         // Insert batch
         let batch_to_store = StoredSoftBatch {
-            da_slot_height: batch_receipt.da_slot_height,
+            da_slot_height,
             da_slot_hash: batch_receipt.da_slot_hash.into(),
             hash: batch_receipt.batch_hash,
             tx_range: TxNumber(first_tx_number)..TxNumber(last_tx_number),
@@ -309,12 +344,35 @@ impl LedgerDB {
             soft_confirmation_signature: batch_receipt.soft_confirmation_signature,
             pub_key: batch_receipt.pub_key,
             l1_fee_rate: batch_receipt.l1_fee_rate,
+            timestamp: batch_receipt.timestamp,
         };
         self.put_soft_batch(
             &batch_to_store,
             &BatchNumber(current_item_numbers.soft_batch_number),
             &mut schema_batch,
         )?;
+
+        if let Some(ordering_commitment) = &batch_receipt.ordering_commitment {
+            schema_batch.put::<OrderingCommitmentByNumber>(
+                &BatchNumber(current_item_numbers.soft_batch_number),
+                ordering_commitment,
+            )?;
+        }
+
+        if let Some(outcome) = &batch_receipt.outcome {
+            schema_batch.put::<SoftConfirmationOutcomeByNumber>(
+                &BatchNumber(current_item_numbers.soft_batch_number),
+                outcome,
+            )?;
+        }
+
+        // connect L1 and L2 height
+        self.put_l2_range_of_l1_slot(
+            SlotNumber(da_slot_height),
+            BatchNumber(current_item_numbers.soft_batch_number),
+            &mut schema_batch,
+        )?;
+        schema_batch.delete::<PendingSoftBatchCommit>(&())?;
         current_item_numbers.soft_batch_number += 1;
 
         self.db.write_schemas(schema_batch)?;
@@ -411,20 +469,129 @@ impl LedgerDB {
         l1_height: SlotNumber,
         l2_height: BatchNumber,
     ) -> Result<(), anyhow::Error> {
-        let current_range = self.db.get::<L2RangeByL1Height>(&l1_height)?;
+        let mut schema_batch = SchemaBatch::new();
 
-        let new_range = match current_range {
-            Some(existing) => (existing.0, l2_height),
-            None => (l2_height, l2_height),
+        self.put_l2_range_of_l1_slot(l1_height, l2_height, &mut schema_batch)?;
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
+    /// Records that the state for the soft batch at `l2_height` is about to be committed.
+    /// Must be called before the state update is persisted; the record is cleared by
+    /// [`LedgerDB::commit_soft_batch`]. If a record is found at startup, the node crashed
+    /// between persisting the state and the ledger entry of that soft batch.
+    pub fn put_pending_soft_batch_commit(&self, l2_height: BatchNumber) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+
+        schema_batch.put::<PendingSoftBatchCommit>(&(), &l2_height)?;
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
+    /// Removes the most recent soft batch together with its transactions, events and index
+    /// entries. Used to roll back a soft batch whose state was not persisted.
+    /// Returns the number and the contents of the removed soft batch, if any.
+    pub fn rollback_head_soft_batch(
+        &self,
+    ) -> anyhow::Result<Option<(BatchNumber, StoredSoftBatch)>> {
+        let Some((number, batch)) = self.get_head_soft_batch()? else {
+            return Ok(None);
         };
 
         let mut schema_batch = SchemaBatch::new();
 
-        schema_batch
-            .put::<L2RangeByL1Height>(&l1_height, &new_range)
-            .unwrap();
+        for (tx_number, tx) in (batch.tx_range.start.0..).zip(batch.txs.iter()) {
+            for event_number in tx.events.start.0..tx.events.end.0 {
+                let event_number = EventNumber(event_number);
+                if let Some(event) = self.db.get::<EventByNumber>(&event_number)? {
+                    schema_batch.delete::<EventByKey>(&(
+                        event.key().clone(),
+                        TxNumber(tx_number),
+                        event_number,
+                    ))?;
+                }
+                schema_batch.delete::<EventByNumber>(&event_number)?;
+            }
+            schema_batch.delete::<TxByHash>(&tx.hash)?;
+            schema_batch.delete::<TxByNumber>(&TxNumber(tx_number))?;
+        }
+
+        schema_batch.delete::<BatchByHash>(&batch.hash)?;
+        schema_batch.delete::<SoftBatchByNumber>(&number)?;
+        schema_batch.delete::<OrderingCommitmentByNumber>(&number)?;
+
+        let l1_height = SlotNumber(batch.da_slot_height);
+        match self.db.get::<L2RangeByL1Height>(&l1_height)? {
+            Some((start, _)) if start.0 < number.0 => schema_batch
+                .put::<L2RangeByL1Height>(&l1_height, &(start, BatchNumber(number.0 - 1)))?,
+            Some(_) => schema_batch.delete::<L2RangeByL1Height>(&l1_height)?,
+            None => {}
+        }
+
         self.db.write_schemas(schema_batch)?;
 
+        let mut next_item_numbers = self.next_item_numbers.lock().unwrap();
+        next_item_numbers.soft_batch_number = number.0;
+        next_item_numbers.tx_number = batch.tx_range.start.0;
+        if let Some(first_tx) = batch.txs.first() {
+            next_item_numbers.event_number = first_tx.events.start.0;
+        }
+
+        Ok(Some((number, batch)))
+    }
+
+    /// Get the soft batch whose commit was started but not completed, if any
+    pub fn get_pending_soft_batch_commit(&self) -> anyhow::Result<Option<BatchNumber>> {
+        self.db.get::<PendingSoftBatchCommit>(&())
+    }
+
+    /// Removes the pending commit record once the node reconciled the ledger with the state
+    pub fn clear_pending_soft_batch_commit(&self) -> anyhow::Result<()> {
+        self.db.delete::<PendingSoftBatchCommit>(&())
+    }
+
+    /// Records the chain the database is created for on its first start, and fails if it was
+    /// created for another chain on later starts. `genesis_root` is the state root of the
+    /// genesis block once genesis was applied, recorded on the first start it is known.
+    pub fn check_chain_identity(
+        &self,
+        chain_id: u64,
+        genesis_root: Option<&[u8]>,
+    ) -> anyhow::Result<()> {
+        let Some(mut identity) = self.db.get::<ChainIdentity>(&())? else {
+            return self.db.put::<ChainIdentity>(
+                &(),
+                &StoredChainIdentity {
+                    chain_id,
+                    genesis_root: genesis_root.map(<[u8]>::to_vec),
+                },
+            );
+        };
+
+        anyhow::ensure!(
+            identity.chain_id == chain_id,
+            "The database was created for chain id {}, but the genesis config is for chain id {}. \
+            Point the storage path to the database of chain {} or to an empty directory",
+            identity.chain_id,
+            chain_id,
+            chain_id,
+        );
+        match (&identity.genesis_root, genesis_root) {
+            (Some(recorded), Some(genesis_root)) => anyhow::ensure!(
+                recorded.as_slice() == genesis_root,
+                "The ledger database was created for genesis state root 0x{}, but the state \
+                database has genesis state root 0x{}",
+                hex::encode(recorded),
+                hex::encode(genesis_root),
+            ),
+            (None, Some(genesis_root)) => {
+                identity.genesis_root = Some(genesis_root.to_vec());
+                self.db.put::<ChainIdentity>(&(), &identity)?;
+            }
+            (_, None) => {}
+        }
         Ok(())
     }
 
@@ -443,6 +610,75 @@ impl LedgerDB {
         Ok(())
     }
 
+    /// Records where the sequencer commitment covering the L2 heights `l2_range` was found
+    /// on the DA layer
+    pub fn put_commitment_location(
+        &self,
+        l2_range: L2HeightRange,
+        l1_height: SlotNumber,
+        blob_hash: [u8; 32],
+    ) -> Result<(), anyhow::Error> {
+        let (l2_start_height, l2_end_height) = l2_range;
+        let mut schema_batch = SchemaBatch::new();
+
+        schema_batch.put::<CommitmentByL2End>(
+            &l2_end_height,
+            &StoredCommitmentLocation {
+                l2_start_height,
+                l1_height,
+                blob_hash,
+            },
+        )?;
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
+    /// Get the location on the DA layer of the sequencer commitment covering `l2_height`,
+    /// along with the last L2 height the commitment covers
+    pub fn get_commitment_location_by_l2_height(
+        &self,
+        l2_height: BatchNumber,
+    ) -> anyhow::Result<Option<(BatchNumber, StoredCommitmentLocation)>> {
+        let mut iter = self.db.iter::<CommitmentByL2End>()?;
+        // Commitments are keyed by the end of their range, so the first one ending at
+        // or after `l2_height` is the only candidate
+        iter.seek(&l2_height)?;
+
+        match iter.next() {
+            Some(Ok(item)) => {
+                let (l2_end_height, location) = item.into_tuple();
+                Ok((location.l2_start_height <= l2_height).then_some((l2_end_height, location)))
+            }
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+
+    /// Records the DA data of unknown versions found in the L1 block at `l1_height`, by blob
+    /// hash and envelope version, which the node skipped
+    pub fn put_unknown_da_data(
+        &self,
+        l1_height: SlotNumber,
+        unknown_da_data: Vec<([u8; 32], u8)>,
+    ) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+
+        schema_batch.put::<UnknownDaDataByL1Height>(&l1_height, &unknown_da_data)?;
+        self.db.write_schemas(schema_batch)?;
+
+        Ok(())
+    }
+
+    /// Get the blob hashes and envelope versions of the DA data of unknown versions the node
+    /// skipped in the L1 block at `l1_height`
+    pub fn get_unknown_da_data(
+        &self,
+        l1_height: SlotNumber,
+    ) -> anyhow::Result<Option<Vec<([u8; 32], u8)>>> {
+        self.db.get::<UnknownDaDataByL1Height>(&l1_height)
+    }
+
     /// Saves a soft confirmation status for a given L1 height
     pub fn put_soft_confirmation_status(
         &self,
@@ -459,6 +695,14 @@ impl LedgerDB {
         Ok(())
     }
 
+    /// Gets the soft confirmation status of a given L1 height, `None` if it is only trusted
+    pub fn get_soft_confirmation_status_by_l1_height(
+        &self,
+        height: SlotNumber,
+    ) -> anyhow::Result<Option<sov_rollup_interface::rpc::SoftConfirmationStatus>> {
+        self.db.get::<SoftConfirmationStatus>(&height)
+    }
+
     fn last_version_written<T: Schema<Key = U>, U: Into<u64>>(
         db: &DB,
         _schema: T,
@@ -514,3 +758,94 @@ impl LedgerDB {
         self.db.get::<L2RangeByL1Height>(&l1_height)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Opens the ledger database in `dir`, as a node does on start
+    fn start(dir: &tempfile::TempDir) -> LedgerDB {
+        LedgerDB::with_path(dir.path()).unwrap()
+    }
+
+    fn stored_identity(ledger_db: &LedgerDB) -> Option<StoredChainIdentity> {
+        ledger_db.db.get::<ChainIdentity>(&()).unwrap()
+    }
+
+    #[test]
+    fn test_first_start_records_chain_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = start(&dir);
+        ledger_db.check_chain_identity(5655, None).unwrap();
+        assert_eq!(
+            stored_identity(&ledger_db),
+            Some(StoredChainIdentity {
+                chain_id: 5655,
+                genesis_root: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_matching_restart_is_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        start(&dir).check_chain_identity(5655, None).unwrap();
+
+        // The genesis root is recorded on the first start it is known
+        start(&dir)
+            .check_chain_identity(5655, Some(&[1; 32]))
+            .unwrap();
+
+        let ledger_db = start(&dir);
+        ledger_db
+            .check_chain_identity(5655, Some(&[1; 32]))
+            .unwrap();
+        ledger_db.check_chain_identity(5655, None).unwrap();
+        assert_eq!(
+            stored_identity(&ledger_db),
+            Some(StoredChainIdentity {
+                chain_id: 5655,
+                genesis_root: Some(vec![1; 32]),
+            })
+        );
+    }
+
+    #[test]
+    fn test_chain_id_mismatch_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        start(&dir)
+            .check_chain_identity(5655, Some(&[1; 32]))
+            .unwrap();
+
+        let ledger_db = start(&dir);
+        let err = ledger_db
+            .check_chain_identity(5656, Some(&[1; 32]))
+            .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "The database was created for chain id 5655, but the genesis config is for chain id \
+            5656"
+        ));
+        assert_eq!(stored_identity(&ledger_db).unwrap().chain_id, 5655);
+    }
+
+    #[test]
+    fn test_genesis_root_mismatch_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        start(&dir)
+            .check_chain_identity(5655, Some(&[1; 32]))
+            .unwrap();
+
+        let ledger_db = start(&dir);
+        let err = ledger_db
+            .check_chain_identity(5655, Some(&[2; 32]))
+            .unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "created for genesis state root 0x{}",
+            hex::encode([1; 32])
+        )));
+        assert_eq!(
+            stored_identity(&ledger_db).unwrap().genesis_root,
+            Some(vec![1; 32])
+        );
+    }
+}
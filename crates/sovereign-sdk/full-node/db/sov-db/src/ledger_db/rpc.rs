@@ -1,15 +1,19 @@
 use serde::de::DeserializeOwned;
+use sha2::Sha256;
 use sov_rollup_interface::rpc::{
     BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
     LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse,
-    SoftBatchIdentifier, SoftBatchResponse, TxIdAndOffset, TxIdentifier, TxResponse,
+    SoftBatchIdentifier, SoftBatchResponse, SoftConfirmationRange, TxIdAndOffset, TxIdentifier,
+    TxResponse,
 };
-use sov_rollup_interface::stf::Event;
+use sov_rollup_interface::soft_confirmation::ordering_commitment;
+use sov_rollup_interface::stf::{Event, SoftConfirmationOutcome};
 use tokio::sync::broadcast::Receiver;
 
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, SoftBatchByHash,
-    SoftBatchByNumber, SoftConfirmationStatus, TxByHash, TxByNumber,
+    BatchByHash, BatchByNumber, EventByNumber, L2RangeByL1Height, OrderingCommitmentByNumber,
+    SlotByHash, SlotByNumber, SoftBatchByHash, SoftBatchByNumber, SoftConfirmationOutcomeByNumber,
+    SoftConfirmationStatus, TxByHash, TxByNumber,
 };
 use crate::schema::types::{
     BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
@@ -65,7 +69,8 @@ impl LedgerRpcProvider for LedgerDB {
         Ok(match batch_num {
             Some(num) => {
                 if let Some(stored_batch) = self.db.get::<SoftBatchByNumber>(&num)? {
-                    Some(stored_batch.try_into()?)
+                    let ordering_commitment = self.db.get::<OrderingCommitmentByNumber>(&num)?;
+                    Some(stored_batch.into_response(ordering_commitment))
                 } else {
                     None
                 }
@@ -326,6 +331,40 @@ impl LedgerRpcProvider for LedgerDB {
         }
     }
 
+    fn get_soft_confirmation_outcome(
+        &self,
+        l2_height: u64,
+    ) -> Result<Option<SoftConfirmationOutcome>, anyhow::Error> {
+        self.db
+            .get::<SoftConfirmationOutcomeByNumber>(&BatchNumber(l2_height))
+    }
+
+    fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Result<Option<SoftConfirmationRange>, anyhow::Error> {
+        let range = self.db.get::<L2RangeByL1Height>(&SlotNumber(l1_height))?;
+        Ok(range.map(|(start, end)| SoftConfirmationRange {
+            start: start.0,
+            end: end.0,
+        }))
+    }
+
+    fn verify_ordering_commitment(
+        &self,
+        l2_height: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<Option<bool>, anyhow::Error> {
+        let Some(stored) = self
+            .db
+            .get::<OrderingCommitmentByNumber>(&BatchNumber(l2_height))?
+        else {
+            return Ok(None);
+        };
+        let commitment = ordering_commitment::<Sha256>(tx_hashes.iter().copied());
+        Ok(Some(commitment == stored))
+    }
+
     fn subscribe_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
         Ok(self.slot_subscriptions.subscribe())
     }
@@ -494,8 +533,13 @@ impl LedgerDB {
 
 #[cfg(test)]
 mod tests {
-    use sov_mock_da::{MockBlob, MockBlock};
-    use sov_rollup_interface::rpc::LedgerRpcProvider;
+    use std::marker::PhantomData;
+
+    use sha2::Sha256;
+    use sov_mock_da::{MockBlob, MockBlock, MockDaSpec, MockHash};
+    use sov_rollup_interface::rpc::{LedgerRpcProvider, SoftConfirmationRange};
+    use sov_rollup_interface::soft_confirmation::ordering_commitment;
+    use sov_rollup_interface::stf::SoftBatchReceipt;
 
     use crate::ledger_db::{LedgerDB, SlotCommit};
     #[test]
@@ -510,4 +554,89 @@ mod tests {
 
         assert_eq!(rx.blocking_recv().unwrap(), 1);
     }
+    #[test]
+    fn test_verify_ordering_commitment() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let received = [[1; 32], [2; 32], [3; 32]];
+
+        let receipt: SoftBatchReceipt<(), (), MockDaSpec> = SoftBatchReceipt {
+            da_slot_height: 1,
+            da_slot_hash: MockHash([1; 32]),
+            batch_hash: [0; 32],
+            tx_receipts: vec![],
+            phantom_data: PhantomData,
+            pre_state_root: vec![],
+            post_state_root: vec![],
+            soft_confirmation_signature: vec![],
+            pub_key: vec![],
+            l1_fee_rate: 0,
+            timestamp: None,
+            ordering_commitment: Some(ordering_commitment::<Sha256>(received)),
+            outcome: None,
+        };
+        db.commit_soft_batch(receipt, false).unwrap();
+
+        assert_eq!(
+            db.get_soft_batch_by_number::<()>(1)
+                .unwrap()
+                .unwrap()
+                .ordering_commitment,
+            Some(ordering_commitment::<Sha256>(received))
+        );
+        assert_eq!(
+            db.verify_ordering_commitment(1, &received).unwrap(),
+            Some(true)
+        );
+        let reordered = [received[1], received[0], received[2]];
+        assert_eq!(
+            db.verify_ordering_commitment(1, &reordered).unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            db.verify_ordering_commitment(1, &received[..2]).unwrap(),
+            Some(false)
+        );
+        assert_eq!(db.verify_ordering_commitment(2, &received).unwrap(), None);
+
+        // The commitment is unwound with its soft batch
+        db.rollback_head_soft_batch().unwrap();
+        assert_eq!(db.verify_ordering_commitment(1, &received).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_soft_confirmations_by_l1_height() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        // Soft batches 1 and 2 are anchored to L1 block 5, soft batch 3 to L1 block 6
+        for (i, l1_height) in [5, 5, 6].into_iter().enumerate() {
+            let receipt: SoftBatchReceipt<(), (), MockDaSpec> = SoftBatchReceipt {
+                da_slot_height: l1_height,
+                da_slot_hash: MockHash([l1_height as u8; 32]),
+                batch_hash: [i as u8; 32],
+                tx_receipts: vec![],
+                phantom_data: PhantomData,
+                pre_state_root: vec![],
+                post_state_root: vec![],
+                soft_confirmation_signature: vec![],
+                pub_key: vec![],
+                l1_fee_rate: 0,
+                timestamp: None,
+                ordering_commitment: None,
+                outcome: None,
+            };
+            db.commit_soft_batch(receipt, false).unwrap();
+        }
+
+        assert_eq!(
+            db.get_soft_confirmations_by_l1_height(5).unwrap(),
+            Some(SoftConfirmationRange { start: 1, end: 2 })
+        );
+        assert_eq!(
+            db.get_soft_confirmations_by_l1_height(6).unwrap(),
+            Some(SoftConfirmationRange { start: 3, end: 3 })
+        );
+        assert_eq!(db.get_soft_confirmations_by_l1_height(4).unwrap(), None);
+    }
 }
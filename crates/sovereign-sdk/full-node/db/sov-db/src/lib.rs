@@ -23,3 +23,7 @@ pub mod state_db;
 /// outside of the zkVM execution environment, as this data is not included in
 /// the JMT and does not contribute to proofs of execution.
 pub mod native_db;
+
+/// Implements the startup reconciliation of the ledger and state databases of a node
+/// that might have shut down uncleanly.
+pub mod recovery;
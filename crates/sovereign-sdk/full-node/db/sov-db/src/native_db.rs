@@ -5,7 +5,7 @@ use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{ModuleAccessoryState, NATIVE_TABLES};
+use crate::schema::tables::{ModuleAccessoryState, ModuleAccessoryStateByVersion, NATIVE_TABLES};
 use crate::schema::types::AccessoryKey;
 
 /// Specifies a particular version of the Accessory state.
@@ -49,6 +49,25 @@ impl<Q> NativeDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Removes all values of the given [`Version`] from the [`sov_schema_db::DB`].
+    /// Used to roll back a partially committed state update. The write is atomic.
+    pub fn rollback_version(db: &sov_schema_db::DB, version: Version) -> anyhow::Result<()> {
+        let mut batch = SchemaBatch::new();
+
+        let mut values = db.iter::<ModuleAccessoryStateByVersion>()?;
+        values.seek(&version)?;
+        for item in values {
+            let (value_version, key) = item?.key;
+            if value_version != version {
+                break;
+            }
+            batch.delete::<ModuleAccessoryState>(&(key.clone(), version))?;
+            batch.delete::<ModuleAccessoryStateByVersion>(&(version, key))?;
+        }
+
+        db.write_schemas(batch)
+    }
 }
 
 impl<Q: QueryManager> NativeDB<Q> {
@@ -90,6 +109,7 @@ impl<Q: QueryManager> NativeDB<Q> {
     ) -> anyhow::Result<()> {
         let mut batch = SchemaBatch::default();
         for (key, value) in key_value_pairs {
+            batch.put::<ModuleAccessoryStateByVersion>(&(version, key.clone()), &())?;
             batch.put::<ModuleAccessoryState>(&(key, version), &value)?;
         }
         self.db.write_many(batch)?;
@@ -142,4 +162,38 @@ mod tests {
         let key = b"spam".to_vec();
         assert_eq!(db.get_value_option(&key, 0).unwrap(), None);
     }
+
+    #[test]
+    fn rollback_version() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let key = b"foo".to_vec();
+
+        for version in 1..=3 {
+            let db = setup_db();
+            db.set_values(vec![(key.clone(), Some(vec![version as u8]))], version)
+                .unwrap();
+            NativeDB::<NoopQueryManager>::setup_schema_db(tmpdir.path())
+                .unwrap()
+                .write_schemas(db.freeze().unwrap().into())
+                .unwrap();
+        }
+
+        let db = NativeDB::<NoopQueryManager>::setup_schema_db(tmpdir.path()).unwrap();
+        NativeDB::<NoopQueryManager>::rollback_version(&db, 2).unwrap();
+
+        for (version, exists) in [(1, true), (2, false), (3, true)] {
+            assert_eq!(
+                db.get::<ModuleAccessoryState>(&(key.clone(), version))
+                    .unwrap()
+                    .is_some(),
+                exists
+            );
+            assert_eq!(
+                db.get::<ModuleAccessoryStateByVersion>(&(version, key.clone()))
+                    .unwrap()
+                    .is_some(),
+                exists
+            );
+        }
+    }
 }
@@ -0,0 +1,258 @@
+use std::path::Path;
+
+use sov_schema_db::snapshot::NoopQueryManager;
+use tracing::warn;
+
+use crate::ledger_db::LedgerDB;
+use crate::native_db::NativeDB;
+use crate::schema::types::StoredSoftBatch;
+use crate::state_db::StateDB;
+
+/// Reconciles the ledger with the state databases under `storage_path`.
+///
+/// The state of a soft batch is persisted before its ledger entry, guarded by a pending
+/// commit record in the ledger. If the record is still present and the ledger entry is
+/// missing, the state of that soft batch is rolled back. If the ledger head has no state,
+/// the ledger entry is rolled back. In both cases the soft batch is applied again
+/// after startup.
+///
+/// Returns the soft batches removed from the ledger, so that their transactions can be
+/// resubmitted.
+///
+/// Must be called before the storage manager opens the state databases.
+pub fn recover_ledger_and_state(
+    ledger_db: &LedgerDB,
+    storage_path: &Path,
+) -> anyhow::Result<Vec<StoredSoftBatch>> {
+    let mut unwound = vec![];
+
+    let state_db = StateDB::<NoopQueryManager>::setup_schema_db(storage_path)?;
+    let native_db = NativeDB::<NoopQueryManager>::setup_schema_db(storage_path)?;
+
+    let head = ledger_db
+        .get_head_soft_batch()?
+        .map(|(number, _)| number.0)
+        .unwrap_or_default();
+
+    if let Some(pending) = ledger_db.get_pending_soft_batch_commit()? {
+        if pending.0 > head {
+            // The state of soft batch N is stored at version N + 1, its accessory state at N
+            warn!(
+                "Soft batch {} was not committed to the ledger, rolling back its state",
+                pending.0
+            );
+            StateDB::<NoopQueryManager>::rollback_version(&state_db, pending.0 + 1)?;
+            NativeDB::<NoopQueryManager>::rollback_version(&native_db, pending.0)?;
+        }
+        ledger_db.clear_pending_soft_batch_commit()?;
+    }
+
+    if head > 0 && !StateDB::<NoopQueryManager>::has_version(&state_db, head + 1)? {
+        warn!(
+            "State of soft batch {} was not committed, rolling back its ledger entry",
+            head
+        );
+        unwound.extend(
+            ledger_db
+                .rollback_head_soft_batch()?
+                .map(|(_, soft_batch)| soft_batch),
+        );
+    }
+
+    Ok(unwound)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+    use std::sync::{Arc, RwLock};
+
+    use jmt::storage::{Node, NodeBatch, NodeKey, TreeWriter};
+    use jmt::KeyHash;
+    use sov_mock_da::{MockDaSpec, MockHash};
+    use sov_rollup_interface::stf::SoftBatchReceipt;
+    use sov_schema_db::snapshot::{DbSnapshot, ReadOnlyLock};
+
+    use super::*;
+    use crate::schema::tables::{JmtValues, ModuleAccessoryState};
+    use crate::schema::types::BatchNumber;
+
+    const KEY: &[u8] = b"key";
+
+    /// Persists the state of soft batch `number` like the storage manager does, at JMT
+    /// version `number + 1` and accessory state version `number`
+    fn persist_state(path: &Path, number: u64) {
+        let manager = || ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let key = KEY.to_vec();
+
+        let state_db =
+            StateDB::with_db_snapshot(DbSnapshot::<NoopQueryManager>::new(0, manager())).unwrap();
+        let key_hash = KeyHash([1; 32]);
+        state_db.put_preimages(vec![(key_hash, &key)]).unwrap();
+        let mut batch = NodeBatch::default();
+        batch.extend(
+            vec![(NodeKey::new_empty_path(number + 1), Node::Null)],
+            vec![((number + 1, key_hash), Some(vec![number as u8]))],
+        );
+        state_db.write_node_batch(&batch).unwrap();
+        StateDB::<NoopQueryManager>::setup_schema_db(path)
+            .unwrap()
+            .write_schemas(state_db.freeze().unwrap().into())
+            .unwrap();
+
+        let native_db =
+            NativeDB::with_db_snapshot(DbSnapshot::<NoopQueryManager>::new(0, manager())).unwrap();
+        native_db
+            .set_values(vec![(key, Some(vec![number as u8]))], number)
+            .unwrap();
+        NativeDB::<NoopQueryManager>::setup_schema_db(path)
+            .unwrap()
+            .write_schemas(native_db.freeze().unwrap().into())
+            .unwrap();
+    }
+
+    /// Asserts whether the state of soft batch `number` is persisted, checking its JMT
+    /// nodes, its JMT values and its accessory state
+    fn assert_state_persisted(path: &Path, number: u64, persisted: bool) {
+        let state_db = StateDB::<NoopQueryManager>::setup_schema_db(path).unwrap();
+        let native_db = NativeDB::<NoopQueryManager>::setup_schema_db(path).unwrap();
+        assert_eq!(
+            StateDB::<NoopQueryManager>::has_version(&state_db, number + 1).unwrap(),
+            persisted
+        );
+        assert_eq!(
+            state_db
+                .get::<JmtValues>(&(KEY.to_vec(), number + 1))
+                .unwrap()
+                .is_some(),
+            persisted
+        );
+        assert_eq!(
+            native_db
+                .get::<ModuleAccessoryState>(&(KEY.to_vec(), number))
+                .unwrap()
+                .is_some(),
+            persisted
+        );
+    }
+
+    fn commit_ledger_entry(ledger_db: &LedgerDB, number: u64) {
+        let receipt: SoftBatchReceipt<(), (), MockDaSpec> = SoftBatchReceipt {
+            da_slot_height: 1,
+            da_slot_hash: MockHash([1; 32]),
+            batch_hash: [number as u8; 32],
+            tx_receipts: vec![],
+            phantom_data: PhantomData,
+            pre_state_root: vec![],
+            post_state_root: vec![],
+            soft_confirmation_signature: vec![],
+            pub_key: vec![],
+            l1_fee_rate: 0,
+            timestamp: None,
+            ordering_commitment: None,
+            outcome: None,
+        };
+        ledger_db.commit_soft_batch(receipt, false).unwrap();
+    }
+
+    /// Commits soft batch `number` in the order of the nodes: pending commit record, state,
+    /// then ledger entry
+    fn commit_soft_batch(ledger_db: &LedgerDB, path: &Path, number: u64) {
+        ledger_db
+            .put_pending_soft_batch_commit(BatchNumber(number))
+            .unwrap();
+        persist_state(path, number);
+        commit_ledger_entry(ledger_db, number);
+    }
+
+    fn head(ledger_db: &LedgerDB) -> Option<u64> {
+        ledger_db
+            .get_head_soft_batch()
+            .unwrap()
+            .map(|(number, _)| number.0)
+    }
+
+    #[test]
+    fn test_clean_shutdown_is_left_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(dir.path()).unwrap();
+        commit_soft_batch(&ledger_db, dir.path(), 1);
+        assert_eq!(ledger_db.get_pending_soft_batch_commit().unwrap(), None);
+
+        assert!(recover_ledger_and_state(&ledger_db, dir.path())
+            .unwrap()
+            .is_empty());
+        assert_eq!(head(&ledger_db), Some(1));
+        assert_state_persisted(dir.path(), 1, true);
+    }
+
+    #[test]
+    fn test_crash_before_state_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(dir.path()).unwrap();
+        commit_soft_batch(&ledger_db, dir.path(), 1);
+        // Interrupted after the pending commit record of soft batch 2 is put
+        ledger_db
+            .put_pending_soft_batch_commit(BatchNumber(2))
+            .unwrap();
+
+        assert!(recover_ledger_and_state(&ledger_db, dir.path())
+            .unwrap()
+            .is_empty());
+        assert_eq!(ledger_db.get_pending_soft_batch_commit().unwrap(), None);
+        assert_eq!(head(&ledger_db), Some(1));
+        assert_state_persisted(dir.path(), 1, true);
+        assert_state_persisted(dir.path(), 2, false);
+    }
+
+    #[test]
+    fn test_crash_after_state_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(dir.path()).unwrap();
+        commit_soft_batch(&ledger_db, dir.path(), 1);
+        // Interrupted after the state of soft batch 2 is persisted, before its ledger entry
+        ledger_db
+            .put_pending_soft_batch_commit(BatchNumber(2))
+            .unwrap();
+        persist_state(dir.path(), 2);
+
+        assert!(recover_ledger_and_state(&ledger_db, dir.path())
+            .unwrap()
+            .is_empty());
+        assert_eq!(ledger_db.get_pending_soft_batch_commit().unwrap(), None);
+        assert_eq!(head(&ledger_db), Some(1));
+        assert_state_persisted(dir.path(), 1, true);
+        assert_state_persisted(dir.path(), 2, false);
+
+        // Soft batch 2 can be committed again
+        commit_soft_batch(&ledger_db, dir.path(), 2);
+        assert!(recover_ledger_and_state(&ledger_db, dir.path())
+            .unwrap()
+            .is_empty());
+        assert_eq!(head(&ledger_db), Some(2));
+        assert_state_persisted(dir.path(), 2, true);
+    }
+
+    #[test]
+    fn test_ledger_entry_without_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(dir.path()).unwrap();
+        commit_soft_batch(&ledger_db, dir.path(), 1);
+        // The ledger entry of soft batch 2 was persisted but its state was lost
+        commit_ledger_entry(&ledger_db, 2);
+
+        let unwound = recover_ledger_and_state(&ledger_db, dir.path()).unwrap();
+        assert_eq!(unwound.len(), 1);
+        assert_eq!(unwound[0].hash, [2; 32]);
+        assert_eq!(head(&ledger_db), Some(1));
+        assert_state_persisted(dir.path(), 1, true);
+
+        // Soft batch 2 can be committed again
+        commit_soft_batch(&ledger_db, dir.path(), 2);
+        assert!(recover_ledger_and_state(&ledger_db, dir.path())
+            .unwrap()
+            .is_empty());
+        assert_eq!(head(&ledger_db), Some(2));
+        assert_state_persisted(dir.path(), 2, true);
+    }
+}
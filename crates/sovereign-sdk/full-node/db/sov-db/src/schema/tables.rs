@@ -20,10 +20,12 @@
 //! JMT Tables:
 //! - `KeyHash -> Key`
 //! - `(Key, Version) -> JmtValue`
+//! - `(Version, Key) -> ()`
 //! - `NodeKey -> Node`
 //!
 //! Module Accessory State Table:
 //! - `(ModuleAddress, Key) -> Value`
+//! - `(Version, Key) -> ()`
 
 use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -35,7 +37,8 @@ use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
     AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, EventNumber, JmtValue, L2HeightRange,
-    SlotNumber, StateKey, StoredBatch, StoredSlot, StoredSoftBatch, StoredTransaction, TxNumber,
+    SlotNumber, StateKey, StoredBatch, StoredChainIdentity, StoredCommitmentLocation, StoredSlot,
+    StoredSoftBatch, StoredTransaction, TxNumber,
 };
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
@@ -43,6 +46,7 @@ use super::types::{
 pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
+    JmtValuesByVersion::table_name(),
     JmtNodes::table_name(),
 ];
 
@@ -54,7 +58,13 @@ pub const LEDGER_TABLES: &[&str] = &[
     SoftBatchByNumber::table_name(),
     SoftBatchByHash::table_name(),
     L2RangeByL1Height::table_name(),
+    CommitmentByL2End::table_name(),
+    UnknownDaDataByL1Height::table_name(),
     LastSequencerCommitmentSent::table_name(),
+    PendingSoftBatchCommit::table_name(),
+    ChainIdentity::table_name(),
+    SoftConfirmationOutcomeByNumber::table_name(),
+    OrderingCommitmentByNumber::table_name(),
     BatchByHash::table_name(),
     BatchByNumber::table_name(),
     SoftConfirmationStatus::table_name(),
@@ -67,7 +77,10 @@ pub const LEDGER_TABLES: &[&str] = &[
 /// A list of all tables used by the NativeDB. These tables store
 /// "accessory" state only accessible from a native execution context, to be
 /// used for JSON-RPC and other tooling.
-pub const NATIVE_TABLES: &[&str] = &[ModuleAccessoryState::table_name()];
+pub const NATIVE_TABLES: &[&str] = &[
+    ModuleAccessoryState::table_name(),
+    ModuleAccessoryStateByVersion::table_name(),
+];
 
 /// Macro to define a table that implements [`sov_schema_db::Schema`].
 /// KeyCodec<Schema> and ValueCodec<Schema> must be implemented separately.
@@ -238,11 +251,45 @@ define_table_with_default_codec!(
     (L2RangeByL1Height) SlotNumber => L2HeightRange
 );
 
+define_table_with_seek_key_codec!(
+    /// Location on the DA layer of the sequencer commitments seen by the node,
+    /// keyed by the last L2 height they cover
+    (CommitmentByL2End) BatchNumber => StoredCommitmentLocation
+);
+
+define_table_with_default_codec!(
+    /// Blob hashes and envelope versions of the DA data written by newer software, which the
+    /// node skipped, by the L1 height they were found at
+    (UnknownDaDataByL1Height) SlotNumber => Vec<([u8; 32], u8)>
+);
+
 define_table_with_seek_key_codec!(
     /// Sequencer uses this table to store the last commitment it sent
     (LastSequencerCommitmentSent) () => SlotNumber
 );
 
+define_table_with_seek_key_codec!(
+    /// Write-ahead record of the soft batch whose state is being committed.
+    /// Cleared atomically with the ledger entry of that soft batch
+    (PendingSoftBatchCommit) () => BatchNumber
+);
+
+define_table_with_seek_key_codec!(
+    /// Identity of the chain the database was created for, checked at every start
+    (ChainIdentity) () => StoredChainIdentity
+);
+
+define_table_with_seek_key_codec!(
+    /// Outcome of applying each soft batch, written with the soft batch
+    (SoftConfirmationOutcomeByNumber) BatchNumber => sov_rollup_interface::stf::SoftConfirmationOutcome
+);
+
+define_table_with_seek_key_codec!(
+    /// Commitment to the order in which the sequencer received the transactions of each soft
+    /// batch carrying one, written with the soft batch
+    (OrderingCommitmentByNumber) BatchNumber => [u8; 32]
+);
+
 define_table_with_seek_key_codec!(
     /// The primary source for batch data
     (BatchByNumber) BatchNumber => StoredBatch
@@ -294,6 +341,13 @@ impl KeyEncoder<JmtNodes> for NodeKey {
         Ok(output)
     }
 }
+/// Seeks to the first node of the given version
+impl SeekKeyEncoder<JmtNodes> for Version {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
 impl KeyDecoder<JmtNodes> for NodeKey {
     fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
         if data.len() < 8 {
@@ -365,6 +419,18 @@ impl ValueCodec<JmtValues> for JmtValue {
     }
 }
 
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for JMT values by version, used to roll back a version
+    (JmtValuesByVersion) (Version, StateKey) => ()
+);
+
+/// Seeks to the first value of the given version
+impl SeekKeyEncoder<JmtValuesByVersion> for Version {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
 define_table_with_default_codec!(
     /// A mapping from key-hashes to their preimages and latest version. Since we store raw
     /// key-value pairs instead of keyHash->value pairs,
@@ -416,3 +482,15 @@ impl ValueCodec<ModuleAccessoryState> for AccessoryStateValue {
         Ok(Self::deserialize_reader(&mut &data[..])?)
     }
 }
+
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for accessory state by version, used to roll back a version
+    (ModuleAccessoryStateByVersion) (Version, AccessoryKey) => ()
+);
+
+/// Seeks to the first value of the given version
+impl SeekKeyEncoder<ModuleAccessoryStateByVersion> for Version {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
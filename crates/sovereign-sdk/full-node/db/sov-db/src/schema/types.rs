@@ -70,7 +70,11 @@ pub struct StoredSlot {
 
 /// The on-disk format for a batch. Stores the hash and identifies the range of transactions
 /// included in the batch.
-#[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+///
+/// The timestamp is encoded last, and only if the soft batch carries one, so that soft
+/// batches stored before it existed still decode. The ordering commitment is stored in
+/// its own table.
+#[derive(Debug, PartialEq)]
 pub struct StoredSoftBatch {
     /// The number of the batch
     pub da_slot_height: u64,
@@ -92,26 +96,107 @@ pub struct StoredSoftBatch {
     pub pub_key: Vec<u8>,
     /// L1 fee rate
     pub l1_fee_rate: u64,
+    /// Block timestamp, in seconds since the unix epoch, if the soft batch carries one
+    pub timestamp: Option<u64>,
+}
+
+impl BorshSerialize for StoredSoftBatch {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.da_slot_height.serialize(writer)?;
+        self.da_slot_hash.serialize(writer)?;
+        self.hash.serialize(writer)?;
+        self.tx_range.serialize(writer)?;
+        self.txs.serialize(writer)?;
+        self.pre_state_root.serialize(writer)?;
+        self.post_state_root.serialize(writer)?;
+        self.soft_confirmation_signature.serialize(writer)?;
+        self.pub_key.serialize(writer)?;
+        self.l1_fee_rate.serialize(writer)?;
+        match self.timestamp {
+            Some(timestamp) => timestamp.serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl BorshDeserialize for StoredSoftBatch {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let da_slot_height = u64::deserialize_reader(reader)?;
+        let da_slot_hash = <[u8; 32]>::deserialize_reader(reader)?;
+        let hash = DbHash::deserialize_reader(reader)?;
+        let tx_range = std::ops::Range::<TxNumber>::deserialize_reader(reader)?;
+        let txs = Vec::<StoredTransaction>::deserialize_reader(reader)?;
+        let pre_state_root = Vec::<u8>::deserialize_reader(reader)?;
+        let post_state_root = Vec::<u8>::deserialize_reader(reader)?;
+        let soft_confirmation_signature = Vec::<u8>::deserialize_reader(reader)?;
+        let pub_key = Vec::<u8>::deserialize_reader(reader)?;
+        let l1_fee_rate = u64::deserialize_reader(reader)?;
+
+        let mut rest = vec![];
+        reader.read_to_end(&mut rest)?;
+        let timestamp = if rest.is_empty() {
+            None
+        } else {
+            Some(u64::try_from_slice(&rest)?)
+        };
+
+        Ok(Self {
+            da_slot_height,
+            da_slot_hash,
+            hash,
+            tx_range,
+            txs,
+            pre_state_root,
+            post_state_root,
+            soft_confirmation_signature,
+            pub_key,
+            l1_fee_rate,
+            timestamp,
+        })
+    }
 }
 
 /// The range of L2 heights (soft confirmations) for a given L1 block
 /// (start, end) inclusive
 pub type L2HeightRange = (BatchNumber, BatchNumber);
 
-impl TryFrom<StoredSoftBatch> for SoftBatchResponse {
-    type Error = anyhow::Error;
-    fn try_from(value: StoredSoftBatch) -> Result<Self, Self::Error> {
-        Ok(Self {
-            da_slot_hash: value.da_slot_hash,
-            da_slot_height: value.da_slot_height,
-            hash: value.hash,
-            txs: Some(value.txs.into_iter().filter_map(|tx| tx.body).collect()), // Rollup full nodes don't store tx bodies
-            pre_state_root: value.pre_state_root,
-            post_state_root: value.post_state_root,
-            soft_confirmation_signature: value.soft_confirmation_signature,
-            pub_key: value.pub_key,
-            l1_fee_rate: value.l1_fee_rate,
-        })
+/// The on-disk format of the location of a sequencer commitment on the DA layer
+#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize)]
+pub struct StoredCommitmentLocation {
+    /// First L2 height covered by the commitment
+    pub l2_start_height: BatchNumber,
+    /// Height of the DA block containing the commitment
+    pub l1_height: SlotNumber,
+    /// Hash of the DA blob containing the commitment
+    pub blob_hash: DbHash,
+}
+
+/// The on-disk format of the identity of the chain a database was created for
+#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize)]
+pub struct StoredChainIdentity {
+    /// Chain id of the genesis config
+    pub chain_id: u64,
+    /// State root of the genesis block, unknown until genesis was applied
+    pub genesis_root: Option<Vec<u8>>,
+}
+
+impl StoredSoftBatch {
+    /// Converts the soft batch into its RPC response, with the ordering commitment stored
+    /// alongside it, if it carries one
+    pub fn into_response(self, ordering_commitment: Option<[u8; 32]>) -> SoftBatchResponse {
+        SoftBatchResponse {
+            da_slot_hash: self.da_slot_hash,
+            da_slot_height: self.da_slot_height,
+            hash: self.hash,
+            txs: Some(self.txs.into_iter().filter_map(|tx| tx.body).collect()), // Rollup full nodes don't store tx bodies
+            pre_state_root: self.pre_state_root,
+            post_state_root: self.post_state_root,
+            soft_confirmation_signature: self.soft_confirmation_signature,
+            pub_key: self.pub_key,
+            l1_fee_rate: self.l1_fee_rate,
+            timestamp: self.timestamp,
+            ordering_commitment,
+        }
     }
 }
 
@@ -280,3 +365,50 @@ pub mod arbitrary {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_soft_batch(timestamp: Option<u64>) -> StoredSoftBatch {
+        StoredSoftBatch {
+            da_slot_height: 1,
+            da_slot_hash: [2; 32],
+            hash: [3; 32],
+            tx_range: TxNumber(4)..TxNumber(5),
+            txs: vec![StoredTransaction {
+                hash: [6; 32],
+                events: EventNumber(7)..EventNumber(8),
+                body: Some(vec![9]),
+            }],
+            pre_state_root: vec![10],
+            post_state_root: vec![11],
+            soft_confirmation_signature: vec![12],
+            pub_key: vec![13],
+            l1_fee_rate: 14,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_stored_soft_batch_encoding() {
+        let untimestamped = stored_soft_batch(None).try_to_vec().unwrap();
+        let timestamped = stored_soft_batch(Some(15)).try_to_vec().unwrap();
+
+        // Soft batches without a timestamp keep the layout from before the field existed
+        assert!(untimestamped.ends_with(&14u64.to_le_bytes()));
+        assert_eq!(
+            timestamped,
+            [untimestamped.as_slice(), &15u64.to_le_bytes()].concat()
+        );
+
+        assert_eq!(
+            StoredSoftBatch::try_from_slice(&untimestamped).unwrap(),
+            stored_soft_batch(None)
+        );
+        assert_eq!(
+            StoredSoftBatch::try_from_slice(&timestamped).unwrap(),
+            stored_soft_batch(Some(15))
+        );
+    }
+}
@@ -7,7 +7,7 @@ use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
+use crate::schema::tables::{JmtNodes, JmtValues, JmtValuesByVersion, KeyHashToKey, STATE_TABLES};
 use crate::schema::types::StateKey;
 
 /// A typed wrapper around the db for storing rollup state. Internally,
@@ -57,6 +57,45 @@ impl<Q> StateDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Checks whether a tree of the given [`Version`] was written to the [`sov_schema_db::DB`].
+    pub fn has_version(db: &sov_schema_db::DB, version: Version) -> anyhow::Result<bool> {
+        let mut nodes = db.iter::<JmtNodes>()?;
+        nodes.seek(&version)?;
+        match nodes.next() {
+            Some(item) => Ok(item?.key.version() == version),
+            None => Ok(false),
+        }
+    }
+
+    /// Removes all nodes and values of the given [`Version`] from the [`sov_schema_db::DB`].
+    /// Used to roll back a partially committed state update. The write is atomic.
+    pub fn rollback_version(db: &sov_schema_db::DB, version: Version) -> anyhow::Result<()> {
+        let mut batch = SchemaBatch::new();
+
+        let mut nodes = db.iter::<JmtNodes>()?;
+        nodes.seek(&version)?;
+        for item in nodes {
+            let node_key = item?.key;
+            if node_key.version() != version {
+                break;
+            }
+            batch.delete::<JmtNodes>(&node_key)?;
+        }
+
+        let mut values = db.iter::<JmtValuesByVersion>()?;
+        values.seek(&version)?;
+        for item in values {
+            let (value_version, key) = item?.key;
+            if value_version != version {
+                break;
+            }
+            batch.delete::<JmtValues>(&(key.clone(), version))?;
+            batch.delete::<JmtValuesByVersion>(&(version, key))?;
+        }
+
+        db.write_schemas(batch)
+    }
 }
 
 impl<Q: QueryManager> StateDB<Q> {
@@ -173,6 +212,7 @@ impl<Q: QueryManager> TreeWriter for StateDB<Q> {
                     .ok_or(anyhow::format_err!(
                         "Could not find preimage for key hash {key_hash:?}. Has `StateDB::put_preimage` been called for this key?"
                     ))?;
+            batch.put::<JmtValuesByVersion>(&(*version, key_preimage.clone()), &())?;
             batch.put::<JmtValues>(&(key_preimage, *version), value)?;
         }
         self.db.write_many(batch)?;
@@ -190,11 +230,12 @@ impl<Q: QueryManager> HasPreimage for StateDB<Q> {
 mod state_db_tests {
     use std::sync::{Arc, RwLock};
 
-    use jmt::storage::{NodeBatch, TreeReader, TreeWriter};
+    use jmt::storage::{Node, NodeBatch, NodeKey, TreeReader, TreeWriter};
     use jmt::KeyHash;
     use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, ReadOnlyLock};
 
     use super::StateDB;
+    use crate::schema::tables::{JmtValues, JmtValuesByVersion};
 
     #[test]
     fn test_simple() {
@@ -216,4 +257,50 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+
+    #[test]
+    fn test_rollback_version() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let key_hash = KeyHash([1u8; 32]);
+        let key = vec![2u8; 100];
+
+        for version in 1..=3 {
+            let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+            let db_snapshot = DbSnapshot::<NoopQueryManager>::new(0, manager);
+            let db = StateDB::with_db_snapshot(db_snapshot).unwrap();
+            db.put_preimages(vec![(key_hash, &key)]).unwrap();
+            let mut batch = NodeBatch::default();
+            batch.extend(
+                vec![(NodeKey::new_empty_path(version), Node::Null)],
+                vec![((version, key_hash), Some(vec![version as u8]))],
+            );
+            db.write_node_batch(&batch).unwrap();
+            StateDB::<NoopQueryManager>::setup_schema_db(tmpdir.path())
+                .unwrap()
+                .write_schemas(db.freeze().unwrap().into())
+                .unwrap();
+        }
+
+        let db = StateDB::<NoopQueryManager>::setup_schema_db(tmpdir.path()).unwrap();
+        StateDB::<NoopQueryManager>::rollback_version(&db, 2).unwrap();
+
+        for (version, exists) in [(1, true), (2, false), (3, true)] {
+            assert_eq!(
+                StateDB::<NoopQueryManager>::has_version(&db, version).unwrap(),
+                exists
+            );
+            assert_eq!(
+                db.get::<JmtValues>(&(key.clone(), version))
+                    .unwrap()
+                    .is_some(),
+                exists
+            );
+            assert_eq!(
+                db.get::<JmtValuesByVersion>(&(version, key.clone()))
+                    .unwrap()
+                    .is_some(),
+                exists
+            );
+        }
+    }
 }
@@ -5,10 +5,10 @@
 
 use jsonrpsee::proc_macros::rpc;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, SoftConfirmationStatus,
-    TxIdentifier,
+    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, SoftConfirmationRange,
+    SoftConfirmationStatus, TxIdentifier,
 };
-use sov_rollup_interface::stf::Event;
+use sov_rollup_interface::stf::{Event, SoftConfirmationOutcome};
 
 use crate::HexHash;
 
@@ -146,6 +146,29 @@ where
         soft_batch_receipt: u64,
     ) -> RpcResult<SoftConfirmationStatus>;
 
+    /// Gets the outcome of applying the soft batch at the given L2 height.
+    #[method(name = "getSoftConfirmationOutcome")]
+    async fn get_soft_confirmation_outcome(
+        &self,
+        l2_height: u64,
+    ) -> RpcResult<Option<SoftConfirmationOutcome>>;
+
+    /// Gets the range of L2 heights of the soft confirmations anchored to the given L1 height.
+    #[method(name = "getSoftConfirmationsByL1Height")]
+    async fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> RpcResult<Option<SoftConfirmationRange>>;
+
+    /// Checks the transaction hashes, in the order the sequencer received them, against the
+    /// ordering commitment of the soft batch at the given L2 height.
+    #[method(name = "verifyOrderingCommitment")]
+    async fn verify_ordering_commitment(
+        &self,
+        l2_height: u64,
+        tx_hashes: Vec<HexHash>,
+    ) -> RpcResult<Option<bool>>;
+
     /// Subscription method to receive a notification each time a slot is
     /// processed.
     #[subscription(name = "subscribeSlots", item = u64)]
@@ -159,6 +159,31 @@ where
             .get_soft_confirmation_status(args.0)
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
+    rpc.register_method(
+        "ledger_getSoftConfirmationOutcome",
+        move |params, ledger| {
+            let args: QueryArgs<u64> = extract_query_args(params)?;
+            ledger
+                .get_soft_confirmation_outcome(args.0)
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+        },
+    )?;
+    rpc.register_method(
+        "ledger_getSoftConfirmationsByL1Height",
+        move |params, ledger| {
+            let args: QueryArgs<u64> = extract_query_args(params)?;
+            ledger
+                .get_soft_confirmations_by_l1_height(args.0)
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+        },
+    )?;
+    rpc.register_method("ledger_verifyOrderingCommitment", move |params, ledger| {
+        let args: OrderingArgs = params.parse()?;
+        let tx_hashes: Vec<[u8; 32]> = args.1.into_iter().map(|hash| hash.0).collect();
+        ledger
+            .verify_ordering_commitment(args.0, &tx_hashes)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
 
     rpc.register_subscription(
         "ledger_subscribeSlots",
@@ -202,6 +227,10 @@ where
 #[derive(serde::Deserialize)]
 struct RangeArgs(u64, u64, #[serde(default)] QueryMode);
 
+/// An L2 height and the hashes of its transactions in the order they were received.
+#[derive(serde::Deserialize)]
+struct OrderingArgs(u64, Vec<HexHash>);
+
 /// A structure containing serialized query arguments for RPC queries.
 #[derive(serde::Deserialize)]
 struct QueryArgs<T>(T, #[serde(default)] QueryMode);
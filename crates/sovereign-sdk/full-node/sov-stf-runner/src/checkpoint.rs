@@ -0,0 +1,81 @@
+//! Checkpoints of the state signed by the sequencer, which nodes bootstrapping from a
+//! snapshot can trust until they are proven.
+
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use sequencer_client::SequencerClient;
+use sov_db::ledger_db::LedgerDB;
+use sov_modules_api::Context;
+use sov_modules_stf_blueprint::verify_checkpoint_signature;
+use sov_rollup_interface::rpc::{
+    CheckpointStatus, CheckpointWithStatus, LedgerRpcProvider, SignedCheckpoint,
+    SoftConfirmationStatus,
+};
+
+const CHECKPOINT_RPC_ERROR: &str = "CHECKPOINT_RPC_ERROR";
+
+fn to_rpc_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        jsonrpsee::types::error::UNKNOWN_ERROR_CODE,
+        CHECKPOINT_RPC_ERROR,
+        Some(err.to_string()),
+    )
+}
+
+/// Returns how far the checkpoint can be trusted, given the soft batches the node applied.
+/// Fails if the checkpoint conflicts with the node's chain.
+fn checkpoint_status(
+    ledger_db: &LedgerDB,
+    checkpoint: &SignedCheckpoint,
+) -> anyhow::Result<CheckpointStatus> {
+    let l2_height = checkpoint.checkpoint.l2_height;
+    let Some(soft_batch) = ledger_db.get_soft_batch_by_number::<()>(l2_height)? else {
+        // Not synced up to the checkpoint yet
+        return Ok(CheckpointStatus::TrustedCheckpoint);
+    };
+
+    anyhow::ensure!(
+        soft_batch.hash == checkpoint.checkpoint.soft_batch_hash
+            && soft_batch.post_state_root == checkpoint.checkpoint.state_root,
+        "Checkpoint at L2 height {} conflicts with the local chain",
+        l2_height
+    );
+
+    match ledger_db.get_soft_confirmation_status(l2_height)? {
+        SoftConfirmationStatus::Proven => Ok(CheckpointStatus::Proven),
+        _ => Ok(CheckpointStatus::TrustedCheckpoint),
+    }
+}
+
+/// Creates the RPC module serving `citrea_getCheckpointStatus`, which returns the last
+/// checkpoint signed by the sequencer once its signature is verified with
+/// `sequencer_pub_key`, along with whether it is only trusted or proven.
+pub fn checkpoint_status_rpc<C: Context>(
+    ledger_db: LedgerDB,
+    sequencer_client: SequencerClient,
+    sequencer_pub_key: Vec<u8>,
+) -> RpcModule<()> {
+    let mut rpc = RpcModule::new(());
+    rpc.register_async_method("citrea_getCheckpointStatus", move |_, _| {
+        let ledger_db = ledger_db.clone();
+        let sequencer_client = sequencer_client.clone();
+        let sequencer_pub_key = sequencer_pub_key.clone();
+        async move {
+            let Some(checkpoint) = sequencer_client
+                .get_latest_checkpoint()
+                .await
+                .map_err(to_rpc_error)?
+            else {
+                return Ok::<Option<CheckpointWithStatus>, ErrorObjectOwned>(None);
+            };
+
+            verify_checkpoint_signature::<C>(&checkpoint, &sequencer_pub_key)
+                .map_err(to_rpc_error)?;
+            let status = checkpoint_status(&ledger_db, &checkpoint).map_err(to_rpc_error)?;
+
+            Ok(Some(CheckpointWithStatus { checkpoint, status }))
+        }
+    })
+    .expect("Method is registered once");
+    rpc
+}
@@ -0,0 +1,156 @@
+//! Chain events seen by a full node while it follows the sequencer and the DA layer, posted
+//! to the webhooks and handed to the in-process subscribers of [`subscribe_chain_events`].
+//! The sequencer publishes the blocks it produces as well, and the transactions entering its
+//! mempool to the subscribers of [`subscribe_pending_transactions`].
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::ChainEventKind;
+
+/// Number of events a lagging subscriber can fall behind before it misses events
+const CHAIN_EVENTS_CAPACITY: usize = 1024;
+
+static CHAIN_EVENTS: OnceLock<broadcast::Sender<ChainEvent>> = OnceLock::new();
+
+static PENDING_TRANSACTIONS: OnceLock<broadcast::Sender<PendingTransaction>> = OnceLock::new();
+
+fn chain_events() -> &'static broadcast::Sender<ChainEvent> {
+    CHAIN_EVENTS.get_or_init(|| broadcast::channel(CHAIN_EVENTS_CAPACITY).0)
+}
+
+/// Subscribes to the chain events of the node, from the next one on.
+pub fn subscribe_chain_events() -> broadcast::Receiver<ChainEvent> {
+    chain_events().subscribe()
+}
+
+/// Hands the event to the subscribers.
+pub fn publish_chain_event(event: ChainEvent) {
+    // Fails only if there are no subscribers
+    let _ = chain_events().send(event);
+}
+
+fn pending_transactions() -> &'static broadcast::Sender<PendingTransaction> {
+    PENDING_TRANSACTIONS.get_or_init(|| broadcast::channel(CHAIN_EVENTS_CAPACITY).0)
+}
+
+/// Subscribes to the transactions entering the mempool of the sequencer, from the next one on.
+/// Nothing is published on the other nodes.
+pub fn subscribe_pending_transactions() -> broadcast::Receiver<PendingTransaction> {
+    pending_transactions().subscribe()
+}
+
+/// Hands the transaction to the subscribers.
+pub fn publish_pending_transaction(transaction: PendingTransaction) {
+    // Fails only if there are no subscribers
+    let _ = pending_transactions().send(transaction);
+}
+
+/// A transaction which became executable in the mempool of the sequencer.
+/// Not a chain event, as it may never be included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTransaction {
+    /// Hash of the transaction
+    pub hash: [u8; 32],
+    /// EIP-2718 encoding of the signed transaction
+    pub raw: Vec<u8>,
+}
+
+/// A soft batch applied by the node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBlockEvent {
+    /// Height of the soft batch
+    pub l2_height: u64,
+    /// Hash of the soft batch
+    #[serde(with = "hex::serde")]
+    pub hash: [u8; 32],
+    /// Height of the DA block the soft batch is built on
+    pub da_slot_height: u64,
+    /// State root after the soft batch
+    #[serde(with = "hex::serde")]
+    pub state_root: Vec<u8>,
+}
+
+/// A sequencer commitment found on the DA layer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCommitmentEvent {
+    /// First L2 height of the commitment
+    pub start_l2_height: u64,
+    /// Last L2 height of the commitment
+    pub end_l2_height: u64,
+    /// Merkle root of the soft batch hashes of the commitment
+    #[serde(with = "hex::serde")]
+    pub merkle_root: [u8; 32],
+    /// Height of the DA block the commitment was found in
+    pub da_slot_height: u64,
+    /// Hash of the blob holding the commitment
+    #[serde(with = "hex::serde")]
+    pub blob_hash: [u8; 32],
+}
+
+/// A valid proof found on the DA layer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewProofEvent {
+    /// First L2 height proven
+    pub start_l2_height: u64,
+    /// Last L2 height proven, inclusive
+    pub end_l2_height: u64,
+    /// Height of the DA block the proof was found in
+    pub da_slot_height: u64,
+    /// Hash of the DA block the proof was found in
+    #[serde(with = "hex::serde")]
+    pub da_block_hash: [u8; 32],
+}
+
+/// Chain event seen by a full node, tagged with its kind in `event` once serialized
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ChainEvent {
+    /// A soft batch was applied
+    NewBlock(NewBlockEvent),
+    /// A sequencer commitment was found on the DA layer
+    NewCommitment(NewCommitmentEvent),
+    /// A valid proof was found on the DA layer
+    NewProof(NewProofEvent),
+}
+
+impl ChainEvent {
+    /// Returns the kind of the event.
+    pub fn kind(&self) -> ChainEventKind {
+        match self {
+            ChainEvent::NewBlock(_) => ChainEventKind::NewBlock,
+            ChainEvent::NewCommitment(_) => ChainEventKind::NewCommitment,
+            ChainEvent::NewProof(_) => ChainEventKind::NewProof,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_body() {
+        let event = ChainEvent::NewProof(NewProofEvent {
+            start_l2_height: 1,
+            end_l2_height: 5,
+            da_slot_height: 7,
+            da_block_hash: [1; 32],
+        });
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "event": "new_proof",
+                "startL2Height": 1,
+                "endL2Height": 5,
+                "daSlotHeight": 7,
+                "daBlockHash": hex::encode([1; 32]),
+            })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Golden files of the soft batches applied by a full node, replayed in tests to catch
+//! accidental changes to the state transition function.
+//!
+//! A full node started from genesis with `golden_batches_path` set in its [`RunnerConfig`]
+//! appends every soft batch it applies, along with the resulting state root and receipts,
+//! to `batches.jsonl` in that directory. [`replay_golden_batches`] applies them again from
+//! genesis and fails on the first batch whose results differ from the recorded ones.
+//!
+//! [`RunnerConfig`]: crate::RunnerConfig
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use anyhow::{ensure, Context as _};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use sov_rollup_interface::stf::{BatchReceipt, StateTransitionFunction};
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_rollup_interface::zk::Zkvm;
+
+/// Name of the file golden batches are recorded to
+pub const GOLDEN_BATCHES_FILE: &str = "batches.jsonl";
+
+/// A soft batch applied by a full node, with the inputs needed to apply it again
+/// and what applying it produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "Root: Serialize, B: Serialize, T: Serialize",
+    deserialize = "Root: DeserializeOwned, B: DeserializeOwned, T: DeserializeOwned"
+))]
+pub struct GoldenSoftBatch<Da: DaSpec, Root, B, T> {
+    /// L2 height of the soft batch
+    pub l2_height: u64,
+    /// Header of the DA block the soft batch was given for
+    pub slot_header: Da::BlockHeader,
+    /// Validity condition of the DA block
+    pub validity_condition: Da::ValidityCondition,
+    /// The soft batch, as signed by the sequencer
+    pub soft_batch: SignedSoftConfirmationBatch,
+    /// State root after applying the soft batch
+    pub state_root: Root,
+    /// Receipts of the soft batch
+    pub batch_receipts: Vec<BatchReceipt<B, T>>,
+}
+
+/// Appends the soft batches applied by a full node to the golden file of a directory.
+pub struct GoldenRecorder {
+    file: File,
+}
+
+impl GoldenRecorder {
+    /// Opens the golden file in `dir`, creating both if needed.
+    pub fn new(dir: &Path) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create golden directory {}", dir.display()))?;
+        let path = dir.join(GOLDEN_BATCHES_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open golden file {}", path.display()))?;
+
+        Ok(Self { file })
+    }
+
+    /// Appends `batch` to the golden file.
+    pub fn record<Da: DaSpec, Root: Serialize, B: Serialize, T: Serialize>(
+        &mut self,
+        batch: &GoldenSoftBatch<Da, Root, B, T>,
+    ) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(batch)?;
+        line.push(b'\n');
+        // One write per batch, so that a node stopped mid-recording leaves whole lines
+        self.file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// Reads the soft batches recorded in the golden file of `dir`.
+pub fn read_golden_batches<Da: DaSpec, Root, B, T>(
+    dir: &Path,
+) -> anyhow::Result<Vec<GoldenSoftBatch<Da, Root, B, T>>>
+where
+    Root: DeserializeOwned,
+    B: DeserializeOwned,
+    T: DeserializeOwned,
+{
+    let path = dir.join(GOLDEN_BATCHES_FILE);
+    let file = File::open(&path)
+        .with_context(|| format!("Failed to open golden file {}", path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(&line?).with_context(|| {
+                format!("Failed to parse line {} of {}", index + 1, path.display())
+            })
+        })
+        .collect()
+}
+
+type GoldenBatchOf<Stf, Vm, Da> = GoldenSoftBatch<
+    Da,
+    <Stf as StateTransitionFunction<Vm, Da>>::StateRoot,
+    <Stf as StateTransitionFunction<Vm, Da>>::BatchReceiptContents,
+    <Stf as StateTransitionFunction<Vm, Da>>::TxReceiptContents,
+>;
+
+/// Initializes the chain with `genesis_params` and applies `batches` on it, in order.
+/// Fails on the first batch whose state root or receipts differ from the recorded ones.
+pub fn replay_golden_batches<Stf, Sm, Vm, Da>(
+    stf: &Stf,
+    storage_manager: &mut Sm,
+    genesis_params: Stf::GenesisParams,
+    batches: Vec<GoldenBatchOf<Stf, Vm, Da>>,
+) -> anyhow::Result<()>
+where
+    Da: DaSpec,
+    Vm: Zkvm,
+    Sm: HierarchicalStorageManager<Da>,
+    Stf: StateTransitionFunction<
+        Vm,
+        Da,
+        Condition = Da::ValidityCondition,
+        PreState = Sm::NativeStorage,
+        ChangeSet = Sm::NativeChangeSet,
+    >,
+{
+    let storage = storage_manager.create_storage_on_l2_height(0)?;
+    let (mut state_root, storage) = stf.init_chain(storage, genesis_params);
+    storage_manager.save_change_set_l2(0, storage)?;
+    storage_manager.finalize_l2(0)?;
+
+    for golden in batches {
+        let height = golden.l2_height;
+        let sequencer_pub_key = golden.soft_batch.sequencer_pub_key().to_vec();
+        let pre_state = storage_manager.create_storage_on_l2_height(height)?;
+
+        let slot_result = stf.apply_soft_batch(
+            &sequencer_pub_key,
+            &state_root,
+            pre_state,
+            Default::default(),
+            &golden.slot_header,
+            &golden.validity_condition,
+            &mut golden.soft_batch.clone(),
+        );
+
+        ensure!(
+            slot_result.state_root.as_ref() == golden.state_root.as_ref(),
+            "State root after soft batch #{} is 0x{} but 0x{} was recorded",
+            height,
+            hex::encode(slot_result.state_root.as_ref()),
+            hex::encode(golden.state_root.as_ref()),
+        );
+        ensure!(
+            serde_json::to_value(&slot_result.batch_receipts)?
+                == serde_json::to_value(&golden.batch_receipts)?,
+            "Receipts of soft batch #{} differ from the recorded ones",
+            height,
+        );
+
+        storage_manager.save_change_set_l2(height, slot_result.change_set)?;
+        storage_manager.finalize_l2(height)?;
+        state_root = slot_result.state_root;
+    }
+
+    Ok(())
+}
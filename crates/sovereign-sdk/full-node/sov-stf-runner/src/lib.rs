@@ -1,14 +1,30 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "native")]
+mod checkpoint;
 #[cfg(feature = "native")]
 mod config;
+#[cfg(feature = "native")]
+mod events;
+#[cfg(feature = "native")]
+pub mod golden;
 #[cfg(feature = "mock")]
 /// Testing utilities.
 #[cfg(feature = "mock")]
 pub mod mock;
 #[cfg(feature = "native")]
+mod prefetch;
+#[cfg(feature = "native")]
+mod proof_acceptance;
+#[cfg(feature = "native")]
 mod prover_service;
+#[cfg(feature = "native")]
+mod prover_stats;
+#[cfg(feature = "native")]
+mod proving_coordinator;
+#[cfg(feature = "native")]
+mod rpc_cache;
 
 #[cfg(feature = "native")]
 use std::path::Path;
@@ -16,18 +32,39 @@ use std::path::Path;
 #[cfg(feature = "native")]
 use anyhow::Context;
 #[cfg(feature = "native")]
+pub use checkpoint::checkpoint_status_rpc;
+#[cfg(feature = "native")]
 pub use config::RpcConfig;
 #[cfg(feature = "native")]
 pub use prover_service::*;
 #[cfg(feature = "native")]
 mod runner;
 #[cfg(feature = "native")]
+mod telemetry;
+#[cfg(feature = "native")]
+mod webhooks;
+#[cfg(feature = "native")]
 pub use config::{
-    from_toml_path, ProverServiceConfig, RollupConfig, RunnerConfig, SequencerClientRpcConfig,
-    StorageConfig,
+    from_toml_path, ChainEventKind, GasOracleConfig, IndexerConfig, NativeCurrencyConfig,
+    ProverServiceConfig, RollupConfig, RunnerConfig, SequencerClientRpcConfig, StorageConfig,
+    StreamingConfig, TelemetryConfig, WalletChainConfig, WebhookConfig,
+};
+#[cfg(feature = "native")]
+pub use events::{
+    publish_chain_event, publish_pending_transaction, subscribe_chain_events,
+    subscribe_pending_transactions, ChainEvent, NewBlockEvent, NewCommitmentEvent, NewProofEvent,
+    PendingTransaction,
 };
 #[cfg(feature = "native")]
+pub use prover_stats::{
+    prover_stats, prover_stats_rpc, record_proof_da_fee, ProofRecord, ProverStats,
+};
+#[cfg(feature = "native")]
+pub use rpc_cache::{rpc_middleware, ResponseCache, ResponseCacheLayer};
+#[cfg(feature = "native")]
 pub use runner::*;
+#[cfg(feature = "native")]
+pub use telemetry::{spawn_telemetry, NodeMode};
 
 /// Implements the `StateTransitionVerifier` type for checking the validity of a state transition
 pub mod verifier;
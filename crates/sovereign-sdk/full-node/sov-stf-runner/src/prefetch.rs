@@ -0,0 +1,346 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::Error;
+use rayon::prelude::*;
+use sequencer_client::{GetSoftBatchResponse, SequencerClient};
+use sov_modules_api::{Context, SignatureScheme, SignedSoftConfirmationBatch};
+use sov_modules_stf_blueprint::verify_soft_batch_signature;
+use sov_rollup_interface::da::DaSpec;
+use sov_state::storage::{CacheKey, StorageKey};
+use sov_state::Storage;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{debug, error};
+
+/// Maximum number of soft batches requested from the sequencer at once while syncing
+pub(crate) const PREFETCH_WINDOW: u64 = 64;
+
+const CONNECTION_INTERVALS: &[u64] = &[0, 1, 2, 5, 10, 15, 30, 60];
+const RETRY_INTERVAL: &[u64] = &[1, 5];
+const RETRY_SLEEP: u64 = 2;
+
+/// A soft batch fetched from the sequencer, with its height, the keys of the state it reads
+/// and the signature scheme its signature was verified with
+pub(crate) type FetchedSoftBatch = (u64, GetSoftBatchResponse, Vec<Vec<u8>>, SignatureScheme);
+
+/// Fetches soft batches from the sequencer starting at `start_height`, verifies their
+/// signatures in parallel and sends them in order to `tx`, so that fetching and verifying
+/// overlap with applying the previous soft batches.
+///
+/// While the node is behind, up to [`PREFETCH_WINDOW`] soft batches are requested
+/// concurrently. Once it has caught up, only the next soft batch is polled.
+///
+/// Soft batches signed with `sequencer_schnorr_pub_key` are verified as Schnorr
+/// signatures, and those signed with `sequencer_pub_key` as ed25519 signatures. Which
+/// scheme is active at their DA height is checked by the runner.
+///
+/// With `fetch_access_hints`, the keys of the state read by each soft batch are fetched
+/// along with it. Soft batches whose hints can't be fetched are sent without hints.
+///
+/// Returns when the receiver is dropped or after sending an unrecoverable error.
+pub(crate) async fn fetch_soft_batches<C: Context, Da: DaSpec>(
+    client: SequencerClient,
+    sequencer_pub_key: Vec<u8>,
+    sequencer_schnorr_pub_key: Option<Vec<u8>>,
+    start_height: u64,
+    fetch_access_hints: bool,
+    tx: mpsc::Sender<anyhow::Result<FetchedSoftBatch>>,
+) {
+    let mut height = start_height;
+    let mut window = PREFETCH_WINDOW;
+
+    let mut last_connection_error = Instant::now();
+    let mut last_parse_error = Instant::now();
+
+    let mut connection_index = 0;
+    let mut retry_index = 0;
+
+    loop {
+        let responses = futures::future::join_all(
+            (height..height + window).map(|h| client.get_soft_batch::<Da>(h)),
+        )
+        .await;
+
+        // Only the soft batches up to the first missing one can be applied
+        let mut soft_batches = vec![];
+        let mut first_error = None;
+        for response in responses {
+            match response {
+                Ok(Some(soft_batch)) => soft_batches.push(soft_batch),
+                Ok(None) => break,
+                Err(e) => {
+                    first_error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if soft_batches.is_empty() {
+            match first_error {
+                Some(e) => match e.downcast_ref::<Error>() {
+                    Some(Error::Transport(e)) => {
+                        debug!("Soft Batch: connection error during RPC call: {:?}", e);
+                        log_error(
+                            &mut last_connection_error,
+                            CONNECTION_INTERVALS,
+                            &mut connection_index,
+                            format!("Soft Batch: connection error during RPC call: {:?}", e)
+                                .as_str(),
+                        );
+                    }
+                    _ => {
+                        let _ = tx
+                            .send(Err(anyhow::anyhow!(
+                                "Soft Batch: unknown error from RPC call: {:?}",
+                                e
+                            )))
+                            .await;
+                        return;
+                    }
+                },
+                None => {
+                    debug!(
+                        "Soft Batch: no batch at height {}, retrying in {} seconds",
+                        height, RETRY_SLEEP
+                    );
+                    log_error(
+                        &mut last_parse_error,
+                        RETRY_INTERVAL,
+                        &mut retry_index,
+                        "No soft batch published".to_string().as_str(),
+                    );
+                }
+            }
+            window = 1;
+            sleep(Duration::from_secs(RETRY_SLEEP)).await;
+            continue;
+        }
+
+        // Keep the full window only while the sequencer has more soft batches than requested
+        window = if soft_batches.len() as u64 == window {
+            PREFETCH_WINDOW
+        } else {
+            1
+        };
+
+        let access_hints: Vec<Vec<Vec<u8>>> = if fetch_access_hints {
+            futures::future::join_all(
+                (height..height + soft_batches.len() as u64).map(|h| client.get_access_hints(h)),
+            )
+            .await
+            .into_iter()
+            .map(|hints| match hints {
+                Ok(hints) => hints.iter().map(|key| key.to_vec()).collect(),
+                Err(e) => {
+                    debug!("Soft Batch: failed to fetch access hints: {:?}", e);
+                    vec![]
+                }
+            })
+            .collect()
+        } else {
+            vec![vec![]; soft_batches.len()]
+        };
+
+        let pub_key = sequencer_pub_key.clone();
+        let schnorr_pub_key = sequencer_schnorr_pub_key.clone();
+        let verified = tokio::task::spawn_blocking(move || {
+            soft_batches
+                .into_par_iter()
+                .map(|soft_batch| {
+                    let signed: SignedSoftConfirmationBatch = soft_batch.clone().into();
+                    let valid = match &schnorr_pub_key {
+                        Some(schnorr_pub_key) if signed.sequencer_pub_key() == schnorr_pub_key => {
+                            verify_soft_batch_signature::<C>(
+                                &signed,
+                                schnorr_pub_key,
+                                SignatureScheme::Secp256k1Schnorr,
+                            )
+                            .map(|()| SignatureScheme::Secp256k1Schnorr)
+                        }
+                        _ if signed.sequencer_pub_key() == pub_key => {
+                            verify_soft_batch_signature::<C>(
+                                &signed,
+                                &pub_key,
+                                SignatureScheme::Ed25519,
+                            )
+                            .map(|()| SignatureScheme::Ed25519)
+                        }
+                        _ => Err(anyhow::anyhow!(
+                            "not signed with a key of the sequencer: 0x{}",
+                            hex::encode(signed.sequencer_pub_key())
+                        )),
+                    };
+                    (soft_batch, valid)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .expect("Soft batch signature verification should not panic");
+
+        for ((soft_batch, valid), access_hints) in verified.into_iter().zip(access_hints) {
+            let item = match valid {
+                Ok(scheme) => Ok((height, soft_batch, access_hints, scheme)),
+                Err(e) => Err(anyhow::anyhow!(
+                    "Soft Batch: invalid signature at height {}: {:?}",
+                    height,
+                    e
+                )),
+            };
+            let is_err = item.is_err();
+            if tx.send(item).await.is_err() || is_err {
+                return;
+            }
+            height += 1;
+        }
+    }
+}
+
+/// Reads the state keys of the access hints of a soft batch from `storage` in parallel, so
+/// that its sequential execution finds them in the caches of the state database.
+pub(crate) fn prefetch_state<S: Storage + Sync>(storage: &S, access_hints: Vec<Vec<u8>>) {
+    access_hints.into_par_iter().for_each(|key| {
+        let key = StorageKey::from(CacheKey { key: Arc::new(key) });
+        storage.get(&key, None, &S::Witness::default());
+    });
+}
+
+/// A basic helper for exponential backoff for error logging.
+pub(crate) fn log_error(
+    last_error_log: &mut Instant,
+    error_log_intervals: &[u64],
+    error_interval_index: &mut usize,
+    error_msg: &str,
+) {
+    let now = Instant::now();
+    if now.duration_since(*last_error_log)
+        >= Duration::from_secs(error_log_intervals[*error_interval_index] * 60)
+    {
+        error!(
+            "{} : {} minutes",
+            error_msg, error_log_intervals[*error_interval_index]
+        );
+        *last_error_log = now; // Update the value pointed by the reference
+        *error_interval_index = (*error_interval_index + 1).min(error_log_intervals.len() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+    use jsonrpsee::server::ServerBuilder;
+    use jsonrpsee::RpcModule;
+    use sov_mock_da::MockDaSpec;
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::{PrivateKey, UnsignedSoftConfirmationBatch};
+
+    use super::*;
+
+    /// More soft batches than fit in a prefetch window
+    const SOFT_BATCHES: u64 = 2 * PREFETCH_WINDOW + 10;
+
+    /// Returns the `ledger_getSoftBatchByNumber` response for the soft batch at `height`,
+    /// carrying the public key of `key` but signed by `signer`.
+    fn soft_batch_response(
+        key: &DefaultPrivateKey,
+        signer: &DefaultPrivateKey,
+        height: u64,
+    ) -> serde_json::Value {
+        let txs = vec![height.to_le_bytes().to_vec()];
+        let unsigned = UnsignedSoftConfirmationBatch::new(
+            height,
+            [2; 32],
+            vec![3; 32],
+            txs.clone(),
+            10,
+            Some(height),
+            Some([4; 32]),
+        );
+        let signature = signer.sign(&unsigned.try_to_vec().unwrap());
+        serde_json::json!({
+            "hash": hex::encode([1; 32]),
+            "da_slot_height": height,
+            "da_slot_hash": hex::encode([2; 32]),
+            "txs": txs,
+            "pre_state_root": hex::encode([3; 32]),
+            "post_state_root": hex::encode([5; 32]),
+            "soft_confirmation_signature": hex::encode(signature.try_to_vec().unwrap()),
+            "pub_key": hex::encode(key.pub_key().try_to_vec().unwrap()),
+            "l1_fee_rate": 10,
+            "timestamp": height,
+            "ordering_commitment": hex::encode([4; 32]),
+        })
+    }
+
+    /// Serves the soft batches up to [`SOFT_BATCHES`], the one at `forged_height` being
+    /// signed by another key, and starts fetching them from height 1.
+    async fn fetch_from_sequencer(
+        forged_height: Option<u64>,
+    ) -> (
+        mpsc::Receiver<anyhow::Result<FetchedSoftBatch>>,
+        jsonrpsee::server::ServerHandle,
+    ) {
+        let key = DefaultPrivateKey::generate();
+        let sequencer_pub_key = key.pub_key().try_to_vec().unwrap();
+        let forger = DefaultPrivateKey::generate();
+
+        let mut module = RpcModule::new(());
+        module
+            .register_method("ledger_getSoftBatchByNumber", move |params, _| {
+                let height: u64 = params.one()?;
+                let signer = if Some(height) == forged_height {
+                    &forger
+                } else {
+                    &key
+                };
+                Ok::<_, jsonrpsee::types::ErrorObjectOwned>(
+                    (1..=SOFT_BATCHES)
+                        .contains(&height)
+                        .then(|| soft_batch_response(&key, signer, height)),
+                )
+            })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let address = server.local_addr().unwrap();
+        let handle = server.start(module);
+
+        let (tx, rx) = mpsc::channel(PREFETCH_WINDOW as usize);
+        tokio::spawn(fetch_soft_batches::<DefaultContext, MockDaSpec>(
+            SequencerClient::new(format!("http://{}", address)),
+            sequencer_pub_key,
+            None,
+            1,
+            false,
+            tx,
+        ));
+        (rx, handle)
+    }
+
+    #[tokio::test]
+    async fn test_fetched_soft_batches_are_verified_in_order() {
+        let (mut rx, _handle) = fetch_from_sequencer(None).await;
+
+        for height in 1..=SOFT_BATCHES {
+            let (fetched_height, soft_batch, access_hints, scheme) =
+                rx.recv().await.unwrap().unwrap();
+            assert_eq!(fetched_height, height);
+            assert_eq!(soft_batch.da_slot_height, height);
+            assert!(access_hints.is_empty());
+            assert_eq!(scheme, SignatureScheme::Ed25519);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetching_stops_at_invalid_signature() {
+        let forged_height = PREFETCH_WINDOW + 5;
+        let (mut rx, _handle) = fetch_from_sequencer(Some(forged_height)).await;
+
+        for height in 1..forged_height {
+            assert_eq!(rx.recv().await.unwrap().unwrap().0, height);
+        }
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("invalid signature"));
+        // The fetcher returns after sending the error
+        assert!(rx.recv().await.is_none());
+    }
+}
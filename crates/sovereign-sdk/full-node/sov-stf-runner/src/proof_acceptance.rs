@@ -0,0 +1,233 @@
+//! Proofs posted to DA by any prover. A full node accepts the proof of a range of soft
+//! batches once it verifies against the code commitment of its guest version and its state
+//! roots are the ones of the soft batches the node applied, so that third parties can prove
+//! the chain when the canonical prover stalls.
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::SlotNumber;
+use sov_rollup_interface::da::{BatchProof, DaSpec};
+use sov_rollup_interface::rpc::{LedgerRpcProvider, SoftConfirmationStatus};
+use sov_rollup_interface::zk::Zkvm;
+
+/// Why a proof posted to DA is not accepted
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ProofRejection {
+    #[error("no code commitment is known for guest version {0}")]
+    UnknownGuestVersion(u32),
+    #[error("proof does not verify: {0}")]
+    InvalidProof(String),
+    #[error("L2 range {start}..={end} is empty")]
+    EmptyRange { start: u64, end: u64 },
+    #[error("soft batch {0} is not applied yet")]
+    UnknownSoftBatch(u64),
+    #[error("state roots do not match the soft batches {start}..={end}")]
+    StateRootMismatch { start: u64, end: u64 },
+    #[error(transparent)]
+    Ledger(#[from] anyhow::Error),
+}
+
+/// Verifies `proof` against the code commitment of its guest version, checking that it
+/// proves the transition from the pre state root of its first soft batch to the post state
+/// root of its last one. The sender of the proof does not matter.
+pub(crate) fn verify_batch_proof<Vm, Da, Root>(
+    proof: &BatchProof,
+    code_commitments: &HashMap<u32, Vm::CodeCommitment>,
+    ledger_db: &LedgerDB,
+) -> Result<(), ProofRejection>
+where
+    Vm: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned + AsRef<[u8]>,
+{
+    let code_commitment = code_commitments
+        .get(&proof.guest_version)
+        .ok_or(ProofRejection::UnknownGuestVersion(proof.guest_version))?;
+
+    let (start, end) = (proof.l2_start_height, proof.l2_end_height);
+    if start > end {
+        return Err(ProofRejection::EmptyRange { start, end });
+    }
+
+    let transition = Vm::verify_and_extract_output::<Da, Root>(&proof.proof, code_commitment)
+        .map_err(|e| ProofRejection::InvalidProof(format!("{:?}", e)))?;
+
+    let soft_batch = |height| {
+        ledger_db
+            .get_soft_batch_by_number::<()>(height)?
+            .ok_or(ProofRejection::UnknownSoftBatch(height))
+    };
+    let first = soft_batch(start)?;
+    let last = soft_batch(end)?;
+    if transition.initial_state_root.as_ref() != first.pre_state_root.as_slice()
+        || transition.final_state_root.as_ref() != last.post_state_root.as_slice()
+    {
+        return Err(ProofRejection::StateRootMismatch { start, end });
+    }
+
+    Ok(())
+}
+
+/// Marks as proven the L1 heights whose soft batches are all in the L2 range
+/// `start..=end`, returning them. The status is kept by L1 height, so the L1 heights only
+/// partly covered by the range stay as they are.
+pub(crate) fn mark_range_proven(
+    ledger_db: &LedgerDB,
+    start: u64,
+    end: u64,
+) -> anyhow::Result<Vec<u64>> {
+    let l1_height = |l2_height| -> anyhow::Result<u64> {
+        ledger_db
+            .get_soft_batch_by_number::<()>(l2_height)?
+            .map(|soft_batch| soft_batch.da_slot_height)
+            .ok_or_else(|| anyhow::anyhow!("Soft batch {} is not applied", l2_height))
+    };
+
+    let mut proven = vec![];
+    for height in l1_height(start)?..=l1_height(end)? {
+        let Some((l2_start, l2_end)) = ledger_db.get_l2_range_by_l1_height(SlotNumber(height))?
+        else {
+            continue;
+        };
+        if l2_start.0 >= start && l2_end.0 <= end {
+            ledger_db
+                .put_soft_confirmation_status(SlotNumber(height), SoftConfirmationStatus::Proven)?;
+            proven.push(height);
+        }
+    }
+    Ok(proven)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use sov_mock_da::{MockDaSpec, MockHash, MockValidityCond};
+    use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+    use sov_rollup_interface::stf::SoftBatchReceipt;
+    use sov_rollup_interface::zk::StateTransition;
+
+    use super::*;
+
+    type Vm = MockZkvm<MockValidityCond>;
+
+    const CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([1; 32]);
+
+    /// Commits soft batches with the state roots `roots[i]..roots[i + 1]`, in the L1
+    /// blocks `l1_heights[i]`
+    fn ledger_with_soft_batches(roots: &[[u8; 32]], l1_heights: &[u64]) -> LedgerDB {
+        let ledger_db = LedgerDB::with_path(tempfile::tempdir().unwrap().into_path()).unwrap();
+        for (i, l1_height) in l1_heights.iter().enumerate() {
+            let receipt: SoftBatchReceipt<(), (), MockDaSpec> = SoftBatchReceipt {
+                da_slot_height: *l1_height,
+                da_slot_hash: MockHash([*l1_height as u8; 32]),
+                batch_hash: [i as u8; 32],
+                tx_receipts: vec![],
+                phantom_data: PhantomData,
+                pre_state_root: roots[i].to_vec(),
+                post_state_root: roots[i + 1].to_vec(),
+                soft_confirmation_signature: vec![],
+                pub_key: vec![],
+                l1_fee_rate: 0,
+                timestamp: None,
+                ordering_commitment: None,
+                outcome: None,
+            };
+            ledger_db.commit_soft_batch(receipt, false).unwrap();
+        }
+        ledger_db
+    }
+
+    fn batch_proof(
+        program_id: MockCodeCommitment,
+        (start, end): (u64, u64),
+        (initial_root, final_root): ([u8; 32], [u8; 32]),
+    ) -> BatchProof {
+        let transition: StateTransition<MockDaSpec, [u8; 32]> = StateTransition {
+            initial_state_root: initial_root,
+            final_state_root: final_root,
+            slot_hash: MockHash([0; 32]),
+            validity_condition: MockValidityCond::default(),
+        };
+        let log = bincode::serialize(&transition).unwrap();
+        BatchProof {
+            guest_version: 1,
+            l2_start_height: start,
+            l2_end_height: end,
+            proof: MockProof {
+                program_id,
+                is_valid: true,
+                log: &log,
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    fn verify(proof: &BatchProof, ledger_db: &LedgerDB) -> Result<(), ProofRejection> {
+        let code_commitments = HashMap::from([(1, CODE_COMMITMENT)]);
+        verify_batch_proof::<Vm, MockDaSpec, [u8; 32]>(proof, &code_commitments, ledger_db)
+    }
+
+    #[test]
+    fn test_proof_of_applied_range_is_accepted() {
+        let roots = [[10; 32], [11; 32], [12; 32], [13; 32]];
+        // Soft batches 1 and 2 are in L1 block 5, soft batch 3 in L1 block 6
+        let ledger_db = ledger_with_soft_batches(&roots, &[5, 5, 6]);
+
+        let proof = batch_proof(CODE_COMMITMENT, (1, 2), (roots[0], roots[2]));
+        verify(&proof, &ledger_db).unwrap();
+        assert_eq!(mark_range_proven(&ledger_db, 1, 2).unwrap(), vec![5]);
+        assert_eq!(
+            ledger_db
+                .get_soft_confirmation_status_by_l1_height(SlotNumber(5))
+                .unwrap(),
+            Some(SoftConfirmationStatus::Proven)
+        );
+        assert_eq!(
+            ledger_db
+                .get_soft_confirmation_status_by_l1_height(SlotNumber(6))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_proofs_are_rejected() {
+        let roots = [[10; 32], [11; 32], [12; 32]];
+        let ledger_db = ledger_with_soft_batches(&roots, &[5, 6]);
+
+        let mut proof = batch_proof(CODE_COMMITMENT, (1, 2), (roots[0], roots[2]));
+        proof.guest_version = 2;
+        assert!(matches!(
+            verify(&proof, &ledger_db),
+            Err(ProofRejection::UnknownGuestVersion(2))
+        ));
+
+        let proof = batch_proof(MockCodeCommitment([2; 32]), (1, 2), (roots[0], roots[2]));
+        assert!(matches!(
+            verify(&proof, &ledger_db),
+            Err(ProofRejection::InvalidProof(_))
+        ));
+
+        let proof = batch_proof(CODE_COMMITMENT, (2, 1), (roots[0], roots[2]));
+        assert!(matches!(
+            verify(&proof, &ledger_db),
+            Err(ProofRejection::EmptyRange { start: 2, end: 1 })
+        ));
+
+        let proof = batch_proof(CODE_COMMITMENT, (1, 3), (roots[0], roots[2]));
+        assert!(matches!(
+            verify(&proof, &ledger_db),
+            Err(ProofRejection::UnknownSoftBatch(3))
+        ));
+
+        let proof = batch_proof(CODE_COMMITMENT, (1, 2), (roots[1], roots[2]));
+        assert!(matches!(
+            verify(&proof, &ledger_db),
+            Err(ProofRejection::StateRootMismatch { start: 1, end: 2 })
+        ));
+    }
+}
@@ -68,10 +68,7 @@ where
         Self {
             vm,
             prover_config,
-            prover_state: Prover::new(
-                num_threads,
-                prover_service_config.aggregated_proof_block_jump,
-            ),
+            prover_state: Prover::new(num_threads, prover_service_config),
             zk_storage,
         }
     }
@@ -1,7 +1,9 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::io::Write;
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -11,8 +13,10 @@ use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::zk::{Proof, StateTransitionData, ZkvmHost};
 
 use super::ProverServiceError;
+use crate::prover_stats::{record_proof, ProofRecord};
 use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, WitnessSubmissionStatus,
+    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, ProverServiceConfig,
+    WitnessSubmissionStatus,
 };
 
 enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
@@ -26,6 +30,8 @@ enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
 struct ProverState<StateRoot, Witness, Da: DaSpec> {
     prover_status: HashMap<Da::SlotHash, ProverStatus<StateRoot, Witness, Da>>,
     pending_tasks_count: usize,
+    /// Estimated memory of the running jobs
+    reserved_memory: u64,
 }
 
 impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
@@ -47,7 +53,10 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         proof: Result<Proof, anyhow::Error>,
     ) -> Option<ProverStatus<StateRoot, Witness, Da>> {
         match proof {
-            Ok(p) => self.prover_status.insert(hash, ProverStatus::Proved(p)),
+            Ok(p) => {
+                crate::telemetry::record_proof();
+                self.prover_status.insert(hash, ProverStatus::Proved(p))
+            }
             Err(e) => self.prover_status.insert(hash, ProverStatus::Err(e)),
         }
     }
@@ -59,28 +68,47 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         self.prover_status.get(&hash)
     }
 
-    fn inc_task_count_if_not_busy(&mut self, num_threads: usize) -> bool {
+    /// Admits a job with the given estimated memory if a thread is free and the memory fits
+    /// in `memory_limit` next to the running jobs.
+    /// A job which doesn't fit in the limit on its own is only admitted when no other job
+    /// runs, so that it isn't refused forever.
+    fn try_start_task(
+        &mut self,
+        num_threads: usize,
+        memory_limit: Option<u64>,
+        memory: u64,
+    ) -> bool {
         if self.pending_tasks_count >= num_threads {
             return false;
         }
+        if let Some(limit) = memory_limit {
+            if self.pending_tasks_count > 0 && self.reserved_memory + memory > limit {
+                return false;
+            }
+        }
 
         self.pending_tasks_count += 1;
+        self.reserved_memory += memory;
         true
     }
 
-    fn dec_task_count(&mut self) {
+    fn finish_task(&mut self, memory: u64) {
         assert!(self.pending_tasks_count > 0);
         self.pending_tasks_count -= 1;
+        self.reserved_memory -= memory;
     }
 }
 
 // A prover that generates proofs in parallel using a thread pool. If the pool is saturated,
-// the prover will reject new jobs.
+// or the memory of a new job doesn't fit in the configured limit, the prover will reject
+// new jobs.
 pub(crate) struct Prover<StateRoot, Witness, Da: DaService> {
     prover_state: Arc<RwLock<ProverState<StateRoot, Witness, Da::Spec>>>,
     num_threads: usize,
     pool: rayon::ThreadPool,
     _aggregated_proof_block_jump: u64,
+    max_memory_bytes: Option<u64>,
+    memory_per_witness_byte: u64,
 }
 
 impl<StateRoot, Witness, Da> Prover<StateRoot, Witness, Da>
@@ -89,7 +117,7 @@ where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
 {
-    pub(crate) fn new(num_threads: usize, _aggregated_proof_block_jump: u64) -> Self {
+    pub(crate) fn new(num_threads: usize, config: ProverServiceConfig) -> Self {
         Self {
             num_threads,
             pool: rayon::ThreadPoolBuilder::new()
@@ -100,8 +128,11 @@ where
             prover_state: Arc::new(RwLock::new(ProverState {
                 prover_status: Default::default(),
                 pending_tasks_count: Default::default(),
+                reserved_memory: Default::default(),
             })),
-            _aggregated_proof_block_jump,
+            _aggregated_proof_block_jump: config.aggregated_proof_block_jump,
+            max_memory_bytes: config.max_memory_bytes,
+            memory_per_witness_byte: config.memory_per_witness_byte,
         }
     }
 
@@ -145,7 +176,10 @@ where
 
         match prover_status {
             ProverStatus::WitnessSubmitted(state_transition_data) => {
-                let start_prover = prover_state.inc_task_count_if_not_busy(self.num_threads);
+                let memory = serialized_size(&state_transition_data)
+                    .saturating_mul(self.memory_per_witness_byte);
+                let start_prover =
+                    prover_state.try_start_task(self.num_threads, self.max_memory_bytes, memory);
 
                 // Initiate a new proving job only if the prover is not busy.
                 if start_prover {
@@ -154,13 +188,22 @@ where
 
                     self.pool.spawn(move || {
                         tracing::info_span!("guest_execution").in_scope(|| {
-                            let proof = make_proof(vm, config, zk_storage);
+                            let started = Instant::now();
+                            let proof = make_proof(&mut vm, config, zk_storage);
+                            if proof.is_ok() {
+                                record_proof(ProofRecord {
+                                    da_block_hash: block_header_hash.clone().into(),
+                                    cycles: vm.cycle_count(),
+                                    wall_time_ms: started.elapsed().as_millis() as u64,
+                                    da_fee: None,
+                                });
+                            }
 
                             let mut prover_state =
                                 prover_state_clone.write().expect("Lock was poisoned");
 
                             prover_state.set_to_proved(block_header_hash, proof);
-                            prover_state.dec_task_count();
+                            prover_state.finish_task(memory);
                         })
                     });
 
@@ -211,8 +254,28 @@ where
     }
 }
 
+/// Size of `value` serialized as JSON, the format witnesses are handed to the guest in.
+fn serialized_size<T: Serialize>(value: &T) -> u64 {
+    struct ByteCounter(u64);
+
+    impl Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len() as u64;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, value).expect("Witness must serialize");
+    counter.0
+}
+
 fn make_proof<V, Vm, Da>(
-    mut vm: Vm,
+    vm: &mut Vm,
     config: Arc<ProofGenConfig<V, Da, Vm>>,
     zk_storage: V::PreState,
 ) -> Result<Proof, anyhow::Error>
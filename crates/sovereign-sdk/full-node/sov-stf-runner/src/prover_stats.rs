@@ -0,0 +1,149 @@
+//! Accounting of the proofs produced by this node, kept as the data a future fee and reward
+//! mechanism for decentralized provers needs, and served by `citrea_getProverStats`.
+//!
+//! Records only live in memory, for the proofs produced since the node started.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+
+/// Number of proofs whose records are kept, older ones only count in the totals
+const MAX_RECENT_PROOFS: usize = 256;
+
+static PROVER_STATS: Mutex<ProverStats> = Mutex::new(ProverStats::new());
+
+/// What producing a proof cost
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofRecord {
+    /// Hash of the DA block the proof is for
+    #[serde(with = "hex::serde")]
+    pub da_block_hash: [u8; 32],
+    /// Cycles of the guest execution, if reported by the zkVM
+    pub cycles: Option<u64>,
+    /// Time spent producing the proof, in milliseconds
+    pub wall_time_ms: u64,
+    /// Fee paid to post the proof on the DA layer, once it is posted
+    pub da_fee: Option<u64>,
+}
+
+/// Totals and recent records of the proofs produced by this node, as returned by
+/// `citrea_getProverStats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProverStats {
+    /// Number of proofs produced
+    pub proofs: u64,
+    /// Guest cycles of all proofs whose cycles are known
+    pub total_cycles: u64,
+    /// Time spent producing all proofs, in milliseconds
+    pub total_wall_time_ms: u64,
+    /// Fees paid to post proofs on the DA layer
+    pub total_da_fee: u64,
+    /// Records of the last proofs, oldest first
+    pub recent: VecDeque<ProofRecord>,
+}
+
+impl ProverStats {
+    const fn new() -> Self {
+        Self {
+            proofs: 0,
+            total_cycles: 0,
+            total_wall_time_ms: 0,
+            total_da_fee: 0,
+            recent: VecDeque::new(),
+        }
+    }
+
+    fn record_proof(&mut self, record: ProofRecord) {
+        self.proofs += 1;
+        self.total_cycles += record.cycles.unwrap_or_default();
+        self.total_wall_time_ms += record.wall_time_ms;
+        self.total_da_fee += record.da_fee.unwrap_or_default();
+
+        if self.recent.len() == MAX_RECENT_PROOFS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(record);
+    }
+
+    fn record_da_fee(&mut self, da_block_hash: [u8; 32], fee: u64) {
+        self.total_da_fee += fee;
+        if let Some(record) = self
+            .recent
+            .iter_mut()
+            .rev()
+            .find(|record| record.da_block_hash == da_block_hash)
+        {
+            *record.da_fee.get_or_insert(0) += fee;
+        }
+    }
+}
+
+/// Records a proof produced by the prover service.
+pub(crate) fn record_proof(record: ProofRecord) {
+    PROVER_STATS
+        .lock()
+        .expect("Lock was poisoned")
+        .record_proof(record);
+}
+
+/// Records the fee paid to post the proof of the DA block `da_block_hash` on the DA layer.
+pub fn record_proof_da_fee(da_block_hash: [u8; 32], fee: u64) {
+    PROVER_STATS
+        .lock()
+        .expect("Lock was poisoned")
+        .record_da_fee(da_block_hash, fee);
+}
+
+/// Returns the stats of the proofs produced by this node.
+pub fn prover_stats() -> ProverStats {
+    PROVER_STATS.lock().expect("Lock was poisoned").clone()
+}
+
+/// Creates the RPC module serving `citrea_getProverStats`.
+pub fn prover_stats_rpc() -> RpcModule<()> {
+    let mut rpc = RpcModule::new(());
+    rpc.register_method("citrea_getProverStats", |_, _| {
+        Ok::<_, jsonrpsee::types::ErrorObjectOwned>(prover_stats())
+    })
+    .expect("Method is registered once");
+    rpc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(hash: u8, cycles: Option<u64>) -> ProofRecord {
+        ProofRecord {
+            da_block_hash: [hash; 32],
+            cycles,
+            wall_time_ms: 10,
+            da_fee: None,
+        }
+    }
+
+    #[test]
+    fn test_prover_stats_totals() {
+        let mut stats = ProverStats::new();
+        stats.record_proof(record(1, Some(1000)));
+        stats.record_proof(record(2, None));
+        stats.record_da_fee([1; 32], 50);
+
+        assert_eq!(stats.proofs, 2);
+        assert_eq!(stats.total_cycles, 1000);
+        assert_eq!(stats.total_wall_time_ms, 20);
+        assert_eq!(stats.total_da_fee, 50);
+        assert_eq!(stats.recent[0].da_fee, Some(50));
+        assert_eq!(stats.recent[1].da_fee, None);
+
+        for hash in 0..MAX_RECENT_PROOFS {
+            stats.record_proof(record(hash as u8, None));
+        }
+        assert_eq!(stats.recent.len(), MAX_RECENT_PROOFS);
+        assert_eq!(stats.proofs, MAX_RECENT_PROOFS as u64 + 2);
+    }
+}
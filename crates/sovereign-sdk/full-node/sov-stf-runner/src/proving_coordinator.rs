@@ -0,0 +1,74 @@
+//! Coordination of the provers running against the same chain, through the claims on
+//! commitment ranges kept by the sequencer.
+
+use std::collections::BTreeSet;
+
+use sequencer_client::SequencerClient;
+use tracing::{info, warn};
+
+/// Claims commitment ranges on the sequencer before proving them, so that provers partition
+/// the ranges instead of all proving the same ones.
+///
+/// Ranges claimed by other provers are claimed again on every new DA block, and fall back
+/// to this prover once their claim expired without being proven.
+pub(crate) struct ProvingCoordinator {
+    client: SequencerClient,
+    prover_id: String,
+    /// Ranges other provers held a live claim on when last claimed
+    deferred: BTreeSet<(u64, u64)>,
+}
+
+impl ProvingCoordinator {
+    pub(crate) fn new(client: SequencerClient, prover_id: String) -> Self {
+        Self {
+            client,
+            prover_id,
+            deferred: BTreeSet::new(),
+        }
+    }
+
+    /// Claims the commitment range between the given L2 heights, returns whether this prover
+    /// has to prove it. Ranges are proven without a claim if the sequencer can't be reached.
+    pub(crate) async fn claim(&mut self, range: (u64, u64)) -> bool {
+        let (start_l2_height, end_l2_height) = range;
+        let claim = match self
+            .client
+            .claim_proving_range(&self.prover_id, start_l2_height, end_l2_height)
+            .await
+        {
+            Ok(claim) => claim,
+            Err(e) => {
+                warn!(
+                    "Failed to claim commitment range {}..={}, proving it unclaimed: {}",
+                    start_l2_height, end_l2_height, e
+                );
+                self.deferred.remove(&range);
+                return true;
+            }
+        };
+
+        if claim.proven || claim.prover_id == self.prover_id {
+            self.deferred.remove(&range);
+            return !claim.proven;
+        }
+
+        info!(
+            "Commitment range {}..={} is claimed by prover {} until {}",
+            start_l2_height, end_l2_height, claim.prover_id, claim.expires_at
+        );
+        self.deferred.insert(range);
+        false
+    }
+
+    /// Claims the ranges other provers held a claim on again, returns those this prover has
+    /// to prove now.
+    pub(crate) async fn claim_deferred(&mut self) -> Vec<(u64, u64)> {
+        let mut claimed = Vec::new();
+        for range in self.deferred.clone() {
+            if self.claim(range).await {
+                claimed.push(range);
+            }
+        }
+        claimed
+    }
+}
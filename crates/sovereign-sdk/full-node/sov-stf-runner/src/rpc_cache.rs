@@ -0,0 +1,411 @@
+//! HTTP caching of the JSON-RPC responses which can't change once they exist, the blocks
+//! and their receipts looked up by hash.
+//!
+//! Responses of the [`IMMUTABLE_METHODS`] called with a block hash are kept in memory and
+//! served with an `ETag` and a long lived `Cache-Control`, so that reverse proxies and CDNs
+//! can serve repeated calls. A request with an `If-None-Match` matching the `ETag` gets an
+//! empty `304 Not Modified`. The `ETag` only covers the result, which is the same whatever
+//! the request ID.
+//!
+//! Results looked up by height or by transaction hash are not cached: soft batches can be
+//! unwound, on startup or when a standby sequencer takes over, and other soft batches,
+//! including the unwound transactions, produced at the same heights.
+
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use hyper::header::{
+    HeaderValue, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use schnellru::{ByLength, LruMap};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tower::layer::util::{Identity, Stack};
+use tower::util::Either;
+use tower::{Layer, Service, ServiceBuilder};
+
+/// Methods whose non null result never changes when called with a block hash
+const IMMUTABLE_METHODS: &[&str] = &[
+    "eth_getBlockByHash",
+    "eth_getBlockReceipts",
+    "ledger_getSoftBatchByHash",
+];
+
+/// Largest request body buffered to look for a cacheable call, which is a single call with a
+/// hash. Larger requests and requests without a `Content-Length` are passed through as they
+/// are, to the server and its own body size limit.
+const MAX_CACHEABLE_REQUEST_SIZE: u64 = 4096;
+
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Single JSON-RPC call, batches are not cached
+#[derive(Debug, Deserialize)]
+struct RpcCall {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+impl RpcCall {
+    fn is_cacheable(&self) -> bool {
+        IMMUTABLE_METHODS.contains(&self.method.as_str()) && is_block_hash(&self.params[0])
+    }
+
+    fn key(&self) -> String {
+        format!("{}:{}", self.method, self.params)
+    }
+}
+
+/// Returns whether the parameter is a 32 bytes hash, and not a block number or tag
+fn is_block_hash(param: &Value) -> bool {
+    param.as_str().map_or(false, |param| {
+        param.len() == 66
+            && param.starts_with("0x")
+            && param[2..].bytes().all(|byte| byte.is_ascii_hexdigit())
+    })
+}
+
+/// Returns whether the length of the request body is known and small enough to be buffered
+fn is_small_request(request: &Request<Body>) -> bool {
+    request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok()?.parse::<u64>().ok())
+        .map_or(false, |length| length <= MAX_CACHEABLE_REQUEST_SIZE)
+}
+
+#[derive(Debug, Clone)]
+struct CachedResult {
+    result: Arc<Value>,
+    etag: HeaderValue,
+}
+
+impl CachedResult {
+    fn new(result: Value) -> Self {
+        let digest = Sha256::digest(result.to_string().as_bytes());
+        let etag = HeaderValue::from_str(&format!("\"{}\"", hex::encode(digest)))
+            .expect("Hex strings are valid header values");
+        Self {
+            result: Arc::new(result),
+            etag,
+        }
+    }
+
+    fn response(&self, id: &Value, if_none_match: Option<&HeaderValue>) -> Response<Body> {
+        let not_modified = if_none_match.map_or(false, |tags| {
+            tags.to_str().map_or(false, |tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .any(|tag| tag == "*" || tag.as_bytes() == self.etag.as_bytes())
+            })
+        });
+
+        let mut response = if not_modified {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::NOT_MODIFIED;
+            response
+        } else {
+            let body = serde_json::json!({
+                "jsonrpc": "2.0",
+                "result": self.result.as_ref(),
+                "id": id,
+            });
+            let mut response = Response::new(Body::from(body.to_string()));
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            response
+        };
+        let headers = response.headers_mut();
+        headers.insert(ETAG, self.etag.clone());
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+        );
+        response
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResult {
+    result: Value,
+}
+
+/// HTTP middleware of the RPC servers, which caches the results of at most
+/// `response_cache_entries` calls, and none if 0.
+pub fn rpc_middleware(
+    response_cache_entries: u32,
+) -> ServiceBuilder<Stack<Either<ResponseCacheLayer, Identity>, Identity>> {
+    ServiceBuilder::new().option_layer(
+        (response_cache_entries > 0).then(|| ResponseCacheLayer::new(response_cache_entries)),
+    )
+}
+
+/// Layer caching the immutable responses of the RPC server, see the [module](self) docs.
+#[derive(Clone)]
+pub struct ResponseCacheLayer {
+    cache: Arc<Mutex<LruMap<String, CachedResult, ByLength>>>,
+}
+
+impl ResponseCacheLayer {
+    /// Caches the results of at most `entries` calls, the least recently used are evicted.
+    pub fn new(entries: u32) -> Self {
+        Self {
+            cache: Arc::new(Mutex::new(LruMap::new(ByLength::new(entries)))),
+        }
+    }
+}
+
+impl<S> Layer<S> for ResponseCacheLayer {
+    type Service = ResponseCache<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCache {
+            inner,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// Service built by [`ResponseCacheLayer`]
+#[derive(Clone)]
+pub struct ResponseCache<S> {
+    inner: S,
+    cache: Arc<Mutex<LruMap<String, CachedResult, ByLength>>>,
+}
+
+impl<S> Service<Request<Body>> for ResponseCache<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Error: Into<BoxError> + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // The ready service is the one to call, the clone is left for the next call
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        // WebSocket upgrades, large requests and other requests are passed through
+        if request.method() != Method::POST || !is_small_request(&request) {
+            return Box::pin(async move { inner.call(request).await.map_err(Into::into) });
+        }
+
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            let call = serde_json::from_slice::<RpcCall>(&body)
+                .ok()
+                .filter(RpcCall::is_cacheable);
+
+            let Some(call) = call else {
+                return inner
+                    .call(Request::from_parts(parts, Body::from(body)))
+                    .await
+                    .map_err(Into::into);
+            };
+
+            let key = call.key();
+            let if_none_match = parts.headers.get(IF_NONE_MATCH).cloned();
+            let cached = cache.lock().unwrap().get(&key).cloned();
+            if let Some(cached) = cached {
+                return Ok(cached.response(&call.id, if_none_match.as_ref()));
+            }
+
+            let response = inner
+                .call(Request::from_parts(parts, Body::from(body)))
+                .await
+                .map_err(Into::into)?;
+            if response.status() != StatusCode::OK {
+                return Ok(response);
+            }
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            match serde_json::from_slice::<RpcResult>(&body) {
+                // Blocks which are not known yet may show up later
+                Ok(RpcResult { result }) if !result.is_null() => {
+                    let cached = CachedResult::new(result);
+                    cache.lock().unwrap().insert(key, cached.clone());
+                    Ok(cached.response(&call.id, if_none_match.as_ref()))
+                }
+                // Errors and missing results are returned as they are
+                _ => Ok(Response::from_parts(parts, Body::from(body))),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tower::util::BoxCloneService;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+
+    const BLOCK_HASH: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const UNKNOWN_BLOCK_HASH: &str =
+        "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn call(method: &str, params: Value, id: u64, if_none_match: Option<&str>) -> Request<Body> {
+        let body =
+            serde_json::json!({"jsonrpc": "2.0", "method": method, "params": params, "id": id})
+                .to_string();
+        let mut request = Request::post("/")
+            .header(CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .unwrap();
+        if let Some(tag) = if_none_match {
+            request
+                .headers_mut()
+                .insert(IF_NONE_MATCH, HeaderValue::from_str(tag).unwrap());
+        }
+        request
+    }
+
+    async fn body(response: Response<Body>) -> Value {
+        serde_json::from_slice(&hyper::body::to_bytes(response.into_body()).await.unwrap()).unwrap()
+    }
+
+    /// Returns a cache in front of a server counting its calls, which knows a single block
+    fn cached_server(
+        calls: Arc<AtomicUsize>,
+    ) -> BoxCloneService<Request<Body>, Response<Body>, BoxError> {
+        let server = service_fn(move |request: Request<Body>| {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let request: Value = serde_json::from_slice(
+                    &hyper::body::to_bytes(request.into_body()).await.unwrap(),
+                )
+                .unwrap();
+                let result = match request["params"][0].as_str() {
+                    Some(UNKNOWN_BLOCK_HASH) => Value::Null,
+                    _ => serde_json::json!({"number": "0x1"}),
+                };
+                let body =
+                    serde_json::json!({"jsonrpc": "2.0", "result": result, "id": request["id"]});
+                Ok::<_, Infallible>(Response::new(Body::from(body.to_string())))
+            }
+        });
+        BoxCloneService::new(ResponseCacheLayer::new(16).layer(server))
+    }
+
+    #[tokio::test]
+    async fn test_immutable_results_are_cached() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = cached_server(calls.clone());
+
+        let response = service
+            .clone()
+            .oneshot(call(
+                "eth_getBlockByHash",
+                serde_json::json!([BLOCK_HASH, false]),
+                1,
+                None,
+            ))
+            .await
+            .unwrap();
+        let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+        assert_eq!(
+            response.headers()[CACHE_CONTROL],
+            HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL)
+        );
+        assert_eq!(body(response).await["id"], 1);
+
+        // Served from the cache, with the ID of the request
+        let response = service
+            .clone()
+            .oneshot(call(
+                "eth_getBlockByHash",
+                serde_json::json!([BLOCK_HASH, false]),
+                2,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+        let response = body(response).await;
+        assert_eq!(response["id"], 2);
+        assert_eq!(response["result"]["number"], "0x1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let response = service
+            .clone()
+            .oneshot(call(
+                "eth_getBlockByHash",
+                serde_json::json!([BLOCK_HASH, false]),
+                3,
+                Some(&etag),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // Heights, tags and missing blocks are not cached
+        for (method, params) in [
+            ("eth_getBlockByNumber", serde_json::json!(["0x1", false])),
+            ("eth_getBlockReceipts", serde_json::json!(["0x1"])),
+            ("eth_getBlockReceipts", serde_json::json!(["latest"])),
+            (
+                "eth_getBlockByHash",
+                serde_json::json!([UNKNOWN_BLOCK_HASH, false]),
+            ),
+        ] {
+            for _ in 0..2 {
+                let response = service
+                    .clone()
+                    .oneshot(call(method, params.clone(), 4, None))
+                    .await
+                    .unwrap();
+                assert!(response.headers().get(ETAG).is_none());
+            }
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 9);
+    }
+
+    #[tokio::test]
+    async fn test_large_requests_are_passed_through() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = cached_server(calls.clone());
+
+        let padding = "0".repeat(MAX_CACHEABLE_REQUEST_SIZE as usize);
+        let large = call(
+            "eth_getBlockByHash",
+            serde_json::json!([BLOCK_HASH, false, padding]),
+            1,
+            None,
+        );
+        let mut unknown_length = call(
+            "eth_getBlockByHash",
+            serde_json::json!([BLOCK_HASH, false]),
+            2,
+            None,
+        );
+        unknown_length.headers_mut().remove(CONTENT_LENGTH);
+
+        for request in [large, unknown_length] {
+            let response = service.clone().oneshot(request).await.unwrap();
+            assert!(response.headers().get(ETAG).is_none());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
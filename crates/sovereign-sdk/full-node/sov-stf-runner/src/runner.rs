@@ -1,10 +1,8 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
 use std::net::SocketAddr;
 
 use anyhow::bail;
-use borsh::de::BorshDeserialize;
-use jsonrpsee::core::Error;
 use jsonrpsee::RpcModule;
 use rs_merkle::algorithms::Sha256;
 use rs_merkle::MerkleTree;
@@ -14,27 +12,45 @@ use sov_db::schema::types::{BatchNumber, SlotNumber, StoredSoftBatch};
 use sov_modules_api::Context;
 use sov_modules_stf_blueprint::StfBlueprintTrait;
 use sov_rollup_interface::da::{
-    BatchProof, BlobReaderTrait, BlockHeaderTrait, DaData, DaSpec, SequencerCommitment,
+    BatchProof, BlobReaderTrait, BlockHeaderTrait, DaData, DaDataEnvelope, DaSpec,
+    SequencerCommitment,
 };
 use sov_rollup_interface::rpc::SoftConfirmationStatus;
 use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::soft_confirmation::SignatureScheme;
 pub use sov_rollup_interface::stf::BatchReceipt;
 use sov_rollup_interface::stf::{SoftBatchReceipt, StateTransitionFunction};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
-use tokio::sync::oneshot;
-use tokio::time::{sleep, Duration, Instant};
-use tracing::{debug, error, info};
-
+use sov_state::Storage;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{debug, info};
+
+use crate::events::{
+    publish_chain_event, ChainEvent, NewBlockEvent, NewCommitmentEvent, NewProofEvent,
+};
+use crate::golden::{GoldenRecorder, GoldenSoftBatch};
+use crate::prefetch::{fetch_soft_batches, prefetch_state, PREFETCH_WINDOW};
+use crate::proof_acceptance::{mark_range_proven, verify_batch_proof};
+use crate::proving_coordinator::ProvingCoordinator;
+use crate::rpc_cache::rpc_middleware;
 use crate::verifier::StateTransitionVerifier;
+use crate::webhooks::WebhookDispatcher;
 use crate::{ProverService, RunnerConfig};
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 type GenesisParams<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::GenesisParams;
 
-const CONNECTION_INTERVALS: &[u64] = &[0, 1, 2, 5, 10, 15, 30, 60];
-const RETRY_INTERVAL: &[u64] = &[1, 5];
-const RETRY_SLEEP: u64 = 2;
+/// Aborts the task when dropped
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
 
 /// Combines `DaService` with `StateTransitionFunction` and "runs" the rollup.
 pub struct StateTransitionRunner<Stf, Sm, Da, Vm, Ps, C>
@@ -55,12 +71,23 @@ where
     pub ledger_db: LedgerDB,
     state_root: StateRoot<Stf, Vm, Da::Spec>,
     listen_address: SocketAddr,
+    response_cache_entries: u32,
     #[allow(dead_code)]
     prover_service: Option<Ps>,
     sequencer_client: Option<SequencerClient>,
     sequencer_pub_key: Vec<u8>,
+    sequencer_schnorr_pub_key: Option<Vec<u8>>,
     phantom: std::marker::PhantomData<C>,
     include_tx_body: bool,
+    golden_recorder: Option<GoldenRecorder>,
+    /// Claims commitment ranges on the sequencer, for provers with a prover id
+    proving_coordinator: Option<ProvingCoordinator>,
+    /// Posts the chain events to the configured webhooks
+    webhooks: Option<WebhookDispatcher>,
+    /// Prefetch the state read by the soft batches with the access hints of the sequencer
+    prefetch_state: bool,
+    /// Code commitments of the guests whose proofs are accepted, by guest version
+    proof_code_commitments: HashMap<u32, <Vm as Zkvm>::CodeCommitment>,
 }
 
 /// Represents the possible modes of execution for a zkVM program
@@ -123,9 +150,24 @@ where
         prover_service: Option<Ps>,
         sequencer_client: Option<SequencerClient>,
         sequencer_pub_key: Vec<u8>,
+        sequencer_schnorr_pub_key: Option<Vec<u8>>,
         include_tx_body: bool,
+        proof_code_commitments: HashMap<u32, <Vm as Zkvm>::CodeCommitment>,
     ) -> Result<Self, anyhow::Error> {
         let rpc_config = runner_config.rpc_config;
+        let golden_recorder = runner_config
+            .golden_batches_path
+            .as_deref()
+            .map(GoldenRecorder::new)
+            .transpose()?;
+        let proving_coordinator = match (&prover_service, &sequencer_client) {
+            (Some(_), Some(client)) => runner_config
+                .prover_id
+                .map(|prover_id| ProvingCoordinator::new(client.clone(), prover_id)),
+            _ => None,
+        };
+        let webhooks = (!runner_config.webhooks.is_empty())
+            .then(|| WebhookDispatcher::spawn(runner_config.webhooks));
 
         let prev_state_root = match init_variant {
             InitVariant::Initialized(state_root) => {
@@ -168,11 +210,18 @@ where
             ledger_db,
             state_root: prev_state_root,
             listen_address,
+            response_cache_entries: rpc_config.response_cache_entries,
             prover_service,
             sequencer_client,
             sequencer_pub_key,
+            sequencer_schnorr_pub_key,
             phantom: std::marker::PhantomData,
             include_tx_body,
+            golden_recorder,
+            proving_coordinator,
+            webhooks,
+            prefetch_state: runner_config.prefetch_state,
+            proof_code_commitments,
         })
     }
 
@@ -183,8 +232,11 @@ where
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) {
         let listen_address = self.listen_address;
+        let middleware = rpc_middleware(self.response_cache_entries);
         let _handle = tokio::spawn(async move {
+            // Serves HTTP and WebSocket on the same port, subscriptions need WebSocket
             let server = jsonrpsee::server::ServerBuilder::default()
+                .set_middleware(middleware)
                 .build([listen_address].as_ref())
                 .await
                 .unwrap();
@@ -205,8 +257,19 @@ where
         self.ledger_db.get_head_soft_batch()
     }
 
+    /// Hands the chain event to the webhooks and to the subscribers of the chain events.
+    fn notify(&self, event: ChainEvent) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify(event.clone());
+        }
+        publish_chain_event(event);
+    }
+
     /// Runs the rollup.
-    pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
+    pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error>
+    where
+        Sm::NativeStorage: Storage + Sync,
+    {
         let Some(client) = &self.sequencer_client else {
             return Err(anyhow::anyhow!("Sequencer Client is not initialized"));
         };
@@ -216,188 +279,293 @@ where
         let mut height = self.start_height;
         info!("Starting to sync from height {}", height);
 
-        let mut last_connection_error = Instant::now();
-        let mut last_parse_error = Instant::now();
-
-        let mut connection_index = 0;
-        let mut retry_index = 0;
+        // Soft batches are fetched and verified ahead in a separate task, and applied here in order
+        let (soft_batch_tx, mut soft_batch_rx) = mpsc::channel(PREFETCH_WINDOW as usize);
+        let fetcher = tokio::spawn(fetch_soft_batches::<C, Da::Spec>(
+            client.clone(),
+            self.sequencer_pub_key.clone(),
+            self.sequencer_schnorr_pub_key.clone(),
+            height,
+            self.prefetch_state,
+            soft_batch_tx,
+        ));
+        // Stop fetching when the runner returns
+        let _fetcher_guard = AbortOnDrop(fetcher);
+
+        // Consecutive soft batches are mostly built on the same DA block
+        let mut last_filtered_block: Option<Da::FilteredBlock> = None;
 
         loop {
-            let soft_batch = client.get_soft_batch::<Da::Spec>(height).await;
-
-            if soft_batch.is_err() {
-                let x = soft_batch.unwrap_err();
-                match x.downcast_ref::<jsonrpsee::core::Error>() {
-                    Some(Error::Transport(e)) => {
-                        debug!("Soft Batch: connection error during RPC call: {:?}", e);
-                        Self::log_error(
-                            &mut last_connection_error,
-                            CONNECTION_INTERVALS,
-                            &mut connection_index,
-                            format!("Soft Batch: connection error during RPC call: {:?}", e)
-                                .as_str(),
+            let Some(fetched) = soft_batch_rx.recv().await else {
+                bail!("Soft Batch: fetcher stopped unexpectedly");
+            };
+            let (fetched_height, soft_batch, access_hints, verified_scheme) = fetched?;
+            debug_assert_eq!(fetched_height, height);
+
+            // DA data of a block only has to be processed once
+            let is_new_da_block = last_filtered_block.as_ref().map_or(true, |block| {
+                block.header().height() != soft_batch.da_slot_height
+            });
+
+            if is_new_da_block {
+                // TODO: for a node, the da block at slot_height might not have been finalized yet
+                // should wait for it to be finalized
+                let filtered_block = self
+                    .da_service
+                    .get_block_at(soft_batch.da_slot_height)
+                    .await?;
+
+                if let Some(coordinator) = &mut self.proving_coordinator {
+                    for (start_l2_height, end_l2_height) in coordinator.claim_deferred().await {
+                        // TODO: prove the range once proving is enabled below
+                        info!(
+                            "Claimed commitment range {}..={} after the claim of another prover expired",
+                            start_l2_height, end_l2_height
                         );
-                        sleep(Duration::from_secs(RETRY_SLEEP)).await;
-                        continue;
-                    }
-                    _ => {
-                        anyhow::bail!("Soft Batch: unknown error from RPC call: {:?}", x);
                     }
                 }
-            }
 
-            let soft_batch = match soft_batch.unwrap() {
-                Some(soft_batch) => soft_batch,
-                None => {
-                    debug!(
-                        "Soft Batch: no batch at height {}, retrying in {} seconds",
-                        height, RETRY_SLEEP
-                    );
-                    Self::log_error(
-                        &mut last_parse_error,
-                        RETRY_INTERVAL,
-                        &mut retry_index,
-                        "No soft batch published".to_string().as_str(),
+                // TODO: when legit blocks are implemented use below to
+                // check for reorgs
+                // Checking if reorg happened or not.
+                // if let Some(prev_block_header) = seen_block_headers.back() {
+                //     if prev_block_header.hash() != filtered_block.header().prev_hash() {
+                //         tracing::warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
+                //         while let Some(seen_block_header) = seen_block_headers.pop_back() {
+                //             seen_receipts.pop_back();
+                //             let block = self
+                //                 .da_service
+                //                 .get_block_at(seen_block_header.height())
+                //                 .await?;
+                //             if block.header().prev_hash() == seen_block_header.prev_hash() {
+                //                 height = seen_block_header.height();
+                //                 filtered_block = block;
+                //                 break;
+                //             }
+                //         }
+                //         tracing::info!("Resuming execution on height={}", height);
+                //     }
+                // }
+
+                // Merkle root hash - L1 start height - L1 end height
+                // TODO: How to confirm this is what we submit - use?
+                // TODO: Add support for multiple commitments in a single block
+                // Proofs are accepted from anyone, the other blobs posted by anyone but the
+                // sequencer registered in the chain state are ignored
+                let sequencer_state = self.storage_manager.create_storage_on_l2_height(height)?;
+                let (da_data, da_errors): (Vec<_>, Vec<_>) = self
+                    .da_service
+                    .extract_relevant_blobs(&filtered_block)
+                    .into_iter()
+                    .map(|mut tx| {
+                        let blob_hash = tx.hash();
+                        let sender = tx.sender();
+                        DaData::from_envelope(tx.full_data()).map(|data| (blob_hash, sender, data))
+                    })
+                    .partition(Result::is_ok);
+
+                if !da_errors.is_empty() {
+                    tracing::warn!(
+                        "Found broken DA data in block 0x{}: {:?}",
+                        hex::encode(filtered_block.hash()),
+                        da_errors
                     );
-                    sleep(Duration::from_secs(RETRY_SLEEP)).await;
-                    continue;
                 }
-            };
-
-            // TODO: for a node, the da block at slot_height might not have been finalized yet
-            // should wait for it to be finalized
-            let filtered_block = self
-                .da_service
-                .get_block_at(soft_batch.da_slot_height)
-                .await?;
-
-            // TODO: when legit blocks are implemented use below to
-            // check for reorgs
-            // Checking if reorg happened or not.
-            // if let Some(prev_block_header) = seen_block_headers.back() {
-            //     if prev_block_header.hash() != filtered_block.header().prev_hash() {
-            //         tracing::warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
-            //         while let Some(seen_block_header) = seen_block_headers.pop_back() {
-            //             seen_receipts.pop_back();
-            //             let block = self
-            //                 .da_service
-            //                 .get_block_at(seen_block_header.height())
-            //                 .await?;
-            //             if block.header().prev_hash() == seen_block_header.prev_hash() {
-            //                 height = seen_block_header.height();
-            //                 filtered_block = block;
-            //                 break;
-            //             }
-            //         }
-            //         tracing::info!("Resuming execution on height={}", height);
-            //     }
-            // }
 
-            // Merkle root hash - L1 start height - L1 end height
-            // TODO: How to confirm this is what we submit - use?
-            // TODO: Add support for multiple commitments in a single block
-            let (da_data, da_errors): (Vec<_>, Vec<_>) = self
-                .da_service
-                .extract_relevant_blobs(&filtered_block)
-                .into_iter()
-                .map(|mut tx| DaData::try_from_slice(tx.full_data()))
-                .partition(Result::is_ok);
-
-            if !da_errors.is_empty() {
-                tracing::warn!(
-                    "Found broken DA data in block 0x{}: {:?}",
-                    hex::encode(filtered_block.hash()),
-                    da_errors
-                );
-            }
-
-            // seperate DaData into sequencer commitments and proofs
-            let mut sequencer_commitments = Vec::<SequencerCommitment>::new();
-            let mut zk_proofs = Vec::<BatchProof>::new();
-
-            da_data.into_iter().for_each(|da_data| match da_data {
-                Ok(DaData::SequencerCommitment(seq_com)) => sequencer_commitments.push(seq_com),
-                Ok(DaData::ZKProof(batch_proof)) => zk_proofs.push(batch_proof),
-                _ => {}
-            });
+                // seperate DaData into sequencer commitments and proofs
+                let mut sequencer_commitments = Vec::<([u8; 32], SequencerCommitment)>::new();
+                let mut zk_proofs = Vec::<(<Da::Spec as DaSpec>::Address, BatchProof)>::new();
+                let mut unknown_da_data = Vec::<([u8; 32], u8)>::new();
 
-            if !zk_proofs.is_empty() {
-                // TODO: Implement this
-            }
-
-            // TODO here we can support multiple commitments but for now let's take the last one.
-            let sequencer_commitment = sequencer_commitments.iter().last();
-
-            if sequencer_commitment.is_some() {
-                let sequencer_commitment = sequencer_commitment.unwrap();
+                da_data.into_iter().for_each(|da_data| match da_data {
+                    Ok((_, sender, DaDataEnvelope::Known(DaData::ZKProof(batch_proof)))) => {
+                        zk_proofs.push((sender, batch_proof))
+                    }
+                    Ok((blob_hash, sender, _))
+                        if !self
+                            .stf
+                            .is_sequencer_da_sender(sequencer_state.clone(), &sender) =>
+                    {
+                        tracing::warn!(
+                            "Ignoring blob 0x{} in block 0x{} sent by {}, which is not the sequencer",
+                            hex::encode(blob_hash),
+                            hex::encode(filtered_block.hash()),
+                            sender
+                        );
+                    }
+                    Ok((
+                        blob_hash,
+                        _,
+                        DaDataEnvelope::Known(DaData::SequencerCommitment(seq_com)),
+                    )) => sequencer_commitments.push((blob_hash, seq_com)),
+                    Ok((blob_hash, _, DaDataEnvelope::UnknownVersion(version))) => {
+                        unknown_da_data.push((blob_hash, version))
+                    }
+                    _ => {}
+                });
 
-                let start_l1_height = self
-                    .da_service
-                    .get_block_by_hash(sequencer_commitment.l1_start_block_hash)
-                    .await
-                    .unwrap()
-                    .header()
-                    .height();
+                // Written by a sequencer upgraded before this node, skipped so that the node
+                // keeps following the chain until it is upgraded too
+                if !unknown_da_data.is_empty() {
+                    tracing::warn!(
+                        "Skipping {} DA blobs of unknown versions in block 0x{}, the node needs to be upgraded",
+                        unknown_da_data.len(),
+                        hex::encode(filtered_block.hash()),
+                    );
+                    self.ledger_db.put_unknown_da_data(
+                        SlotNumber(filtered_block.header().height()),
+                        unknown_da_data,
+                    )?;
+                }
 
-                let end_l1_height = self
-                    .da_service
-                    .get_block_by_hash(sequencer_commitment.l1_end_block_hash)
-                    .await
-                    .unwrap()
-                    .header()
-                    .height();
-
-                let (start_l2_height, _) = self
-                    .ledger_db
-                    .get_l2_range_by_l1_height(SlotNumber(start_l1_height))
-                    .expect("Sequencer: Failed to get L1 L2 connection")
-                    .unwrap();
-
-                let (_, end_l2_height) = self
-                    .ledger_db
-                    .get_l2_range_by_l1_height(SlotNumber(start_l1_height))
-                    .expect("Sequencer: Failed to get L1 L2 connection")
-                    .unwrap();
-
-                let range_end = BatchNumber(end_l2_height.0 + 1);
-                // Traverse each item's field of vector of transactions, put them in merkle tree
-                // and compare the root with the one from the ledger
-                let stored_soft_batches: Vec<StoredSoftBatch> = self
-                    .ledger_db
-                    .get_soft_batch_range(&(start_l2_height..range_end))
-                    .unwrap();
-
-                let soft_batches_tree = MerkleTree::<Sha256>::from_leaves(
-                    stored_soft_batches
-                        .iter()
-                        .map(|x| x.hash)
-                        .collect::<Vec<_>>()
-                        .as_slice(),
-                );
+                for (sender, proof) in &zk_proofs {
+                    let (start, end) = (proof.l2_start_height, proof.l2_end_height);
+                    if let Err(e) = verify_batch_proof::<Vm, Da::Spec, StateRoot<Stf, Vm, Da::Spec>>(
+                        proof,
+                        &self.proof_code_commitments,
+                        &self.ledger_db,
+                    ) {
+                        tracing::warn!(
+                            "Rejected proof of L2 range {}..={} in block 0x{} sent by {}: {}",
+                            start,
+                            end,
+                            hex::encode(filtered_block.hash()),
+                            sender,
+                            e
+                        );
+                        continue;
+                    }
 
-                if soft_batches_tree.root() != Some(sequencer_commitment.merkle_root) {
-                    tracing::warn!(
-                        "Merkle root mismatch - expected 0x{} but got 0x{}",
-                        hex::encode(soft_batches_tree.root().unwrap()),
-                        hex::encode(sequencer_commitment.merkle_root)
+                    let proven_l1_heights = mark_range_proven(&self.ledger_db, start, end)?;
+                    info!(
+                        "Accepted proof of L2 range {}..={} sent by {}, L1 heights {:?} are proven",
+                        start, end, sender, proven_l1_heights
                     );
+                    self.notify(ChainEvent::NewProof(NewProofEvent {
+                        start_l2_height: start,
+                        end_l2_height: end,
+                        da_slot_height: filtered_block.header().height(),
+                        da_block_hash: filtered_block.hash(),
+                    }));
                 }
 
-                for i in start_l1_height..=end_l1_height {
+                // TODO here we can support multiple commitments but for now let's take the last one.
+                let sequencer_commitment = sequencer_commitments.iter().last();
+
+                if sequencer_commitment.is_some() {
+                    let (blob_hash, sequencer_commitment) = sequencer_commitment.unwrap();
+
+                    let start_l1_height = self
+                        .da_service
+                        .get_block_by_hash(sequencer_commitment.l1_start_block_hash)
+                        .await
+                        .unwrap()
+                        .header()
+                        .height();
+
+                    let end_l1_height = self
+                        .da_service
+                        .get_block_by_hash(sequencer_commitment.l1_end_block_hash)
+                        .await
+                        .unwrap()
+                        .header()
+                        .height();
+
+                    let (start_l2_height, _) = self
+                        .ledger_db
+                        .get_l2_range_by_l1_height(SlotNumber(start_l1_height))
+                        .expect("Sequencer: Failed to get L1 L2 connection")
+                        .unwrap();
+
+                    let (_, end_l2_height) = self
+                        .ledger_db
+                        .get_l2_range_by_l1_height(SlotNumber(end_l1_height))
+                        .expect("Sequencer: Failed to get L1 L2 connection")
+                        .unwrap();
+
+                    let range_end = BatchNumber(end_l2_height.0 + 1);
+                    // Traverse each item's field of vector of transactions, put them in merkle tree
+                    // and compare the root with the one from the ledger
+                    let stored_soft_batches: Vec<StoredSoftBatch> = self
+                        .ledger_db
+                        .get_soft_batch_range(&(start_l2_height..range_end))
+                        .unwrap();
+
+                    let soft_batches_tree = MerkleTree::<Sha256>::from_leaves(
+                        stored_soft_batches
+                            .iter()
+                            .map(|x| x.hash)
+                            .collect::<Vec<_>>()
+                            .as_slice(),
+                    );
+
+                    if soft_batches_tree.root() != Some(sequencer_commitment.merkle_root) {
+                        tracing::warn!(
+                            "Merkle root mismatch - expected 0x{} but got 0x{}",
+                            hex::encode(soft_batches_tree.root().unwrap()),
+                            hex::encode(sequencer_commitment.merkle_root)
+                        );
+                    }
+
                     self.ledger_db
-                        .put_soft_confirmation_status(
-                            SlotNumber(i),
-                            SoftConfirmationStatus::Finalized,
+                        .put_commitment_location(
+                            (start_l2_height, end_l2_height),
+                            SlotNumber(filtered_block.header().height()),
+                            *blob_hash,
                         )
-                        .unwrap_or_else(|_| {
-                            panic!(
-                                "Failed to put soft confirmation status in the ledger db {}",
-                                i
+                        .expect("Failed to put commitment location in the ledger db");
+
+                    self.notify(ChainEvent::NewCommitment(NewCommitmentEvent {
+                        start_l2_height: start_l2_height.0,
+                        end_l2_height: end_l2_height.0,
+                        merkle_root: sequencer_commitment.merkle_root,
+                        da_slot_height: filtered_block.header().height(),
+                        blob_hash: *blob_hash,
+                    }));
+
+                    if let Some(coordinator) = &mut self.proving_coordinator {
+                        if coordinator
+                            .claim((start_l2_height.0, end_l2_height.0))
+                            .await
+                        {
+                            // TODO: prove the range once proving is enabled below
+                            info!(
+                                "Claimed commitment range {}..={}",
+                                start_l2_height.0, end_l2_height.0
+                            );
+                        }
+                    }
+
+                    for i in start_l1_height..=end_l1_height {
+                        // A proof may have been accepted before the commitment
+                        if self
+                            .ledger_db
+                            .get_soft_confirmation_status_by_l1_height(SlotNumber(i))?
+                            == Some(SoftConfirmationStatus::Proven)
+                        {
+                            continue;
+                        }
+                        self.ledger_db
+                            .put_soft_confirmation_status(
+                                SlotNumber(i),
+                                SoftConfirmationStatus::Finalized,
                             )
-                        });
+                            .unwrap_or_else(|_| {
+                                panic!(
+                                    "Failed to put soft confirmation status in the ledger db {}",
+                                    i
+                                )
+                            });
+                    }
                 }
+
+                last_filtered_block = Some(filtered_block);
             }
 
+            let filtered_block = last_filtered_block
+                .clone()
+                .expect("DA block of the soft batch is fetched above");
+
             info!(
                 "Running soft confirmation batch #{} with hash: 0x{} on DA block #{}",
                 height,
@@ -408,9 +576,34 @@ where
             let mut data_to_commit = SlotCommit::new(filtered_block.clone());
 
             let pre_state = self.storage_manager.create_storage_on_l2_height(height)?;
+            if !access_hints.is_empty() {
+                prefetch_state(&pre_state, access_hints);
+            }
 
-            let slot_result = self.stf.apply_soft_batch(
-                self.sequencer_pub_key.as_slice(),
+            // The fetcher only lets through soft batches signed with one of the keys of
+            // the sequencer and verified their signature, which must be the key of the
+            // scheme active at their DA height
+            let scheme = self
+                .stf
+                .soft_batch_signature_scheme(pre_state.clone(), soft_batch.da_slot_height);
+            if verified_scheme != scheme {
+                bail!(
+                    "Soft Batch: #{} is signed with {:?} but {:?} is active at DA height {}",
+                    height,
+                    verified_scheme,
+                    scheme,
+                    soft_batch.da_slot_height
+                );
+            }
+            let sequencer_pub_key = match scheme {
+                SignatureScheme::Ed25519 => self.sequencer_pub_key.as_slice(),
+                SignatureScheme::Secp256k1Schnorr => self
+                    .sequencer_schnorr_pub_key
+                    .as_deref()
+                    .expect("Schnorr soft batches are only verified with the Schnorr key"),
+            };
+            let slot_result = self.stf.apply_verified_soft_batch(
+                sequencer_pub_key,
                 // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                 &self.state_root,
                 pre_state,
@@ -418,8 +611,20 @@ where
                 filtered_block.header(),
                 &filtered_block.validity_condition(),
                 &mut soft_batch.clone().into(),
+                verified_scheme,
             );
 
+            if let Some(recorder) = &mut self.golden_recorder {
+                recorder.record(&GoldenSoftBatch::<Da::Spec, _, _, _> {
+                    l2_height: height,
+                    slot_header: filtered_block.header().clone(),
+                    validity_condition: filtered_block.validity_condition(),
+                    soft_batch: soft_batch.clone().into(),
+                    state_root: slot_result.state_root.clone(),
+                    batch_receipts: slot_result.batch_receipts.clone(),
+                })?;
+            }
+
             for receipt in slot_result.batch_receipts {
                 data_to_commit.add_batch(receipt);
             }
@@ -496,16 +701,26 @@ where
                 soft_confirmation_signature: soft_batch.soft_confirmation_signature,
                 pub_key: soft_batch.pub_key,
                 l1_fee_rate: soft_batch.l1_fee_rate,
+                timestamp: soft_batch.timestamp,
+                ordering_commitment: soft_batch.ordering_commitment,
+                outcome: batch_receipt.soft_confirmation_outcome,
             };
 
+            // The state is persisted first, guarded by the pending commit record,
+            // and the ledger entry clears the record once it is written
             self.ledger_db
-                .commit_soft_batch(soft_batch_receipt, self.include_tx_body)?;
+                .put_pending_soft_batch_commit(BatchNumber(height))?;
+            self.storage_manager.finalize_l2(height)?;
+
             self.ledger_db
-                .extend_l2_range_of_l1_slot(
-                    SlotNumber(filtered_block.header().height()),
-                    BatchNumber(height),
-                )
-                .expect("Sequencer: Failed to set L1 L2 connection");
+                .commit_soft_batch(soft_batch_receipt, self.include_tx_body)?;
+
+            self.notify(ChainEvent::NewBlock(NewBlockEvent {
+                l2_height: height,
+                hash: soft_batch.hash,
+                da_slot_height: soft_batch.da_slot_height,
+                state_root: soft_batch.post_state_root,
+            }));
 
             self.state_root = next_state_root;
             seen_receipts.push_back(data_to_commit);
@@ -546,7 +761,6 @@ where
             seen_block_headers.pop_front();
             let receipts = seen_receipts.pop_front().unwrap();
             self.ledger_db.commit_slot(receipts)?;
-            self.storage_manager.finalize_l2(height)?;
 
             height += 1;
         }
@@ -564,16 +778,11 @@ where
         error_interval_index: &mut usize,
         error_msg: &str,
     ) {
-        let now = Instant::now();
-        if now.duration_since(*last_error_log)
-            >= Duration::from_secs(error_log_intervals[*error_interval_index] * 60)
-        {
-            error!(
-                "{} : {} minutes",
-                error_msg, error_log_intervals[*error_interval_index]
-            );
-            *last_error_log = now; // Update the value pointed by the reference
-            *error_interval_index = (*error_interval_index + 1).min(error_log_intervals.len() - 1);
-        }
+        crate::prefetch::log_error(
+            last_error_log,
+            error_log_intervals,
+            error_interval_index,
+            error_msg,
+        )
     }
 }
@@ -0,0 +1,158 @@
+//! Opt-in reporting of node stats to the endpoint set in [`TelemetryConfig`].
+//!
+//! Reports only hold the node version, its mode and counters derived from the ledger,
+//! never addresses, keys or anything else identifying the node or its operator.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sov_db::ledger_db::LedgerDB;
+use tracing::{debug, info};
+
+use crate::TelemetryConfig;
+
+/// Number of proofs produced by this process
+static PROOFS_COMPLETED: AtomicU64 = AtomicU64::new(0);
+
+/// Counts a proof produced by the prover service.
+pub(crate) fn record_proof() {
+    PROOFS_COMPLETED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The role of a node, as reported by telemetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeMode {
+    /// The node produces soft confirmations
+    Sequencer,
+    /// The node follows the sequencer
+    FullNode,
+    /// The node follows the sequencer and proves its blocks
+    Prover,
+}
+
+/// Stats posted at each telemetry interval
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TelemetryReport {
+    version: &'static str,
+    mode: NodeMode,
+    /// Height of the latest soft confirmation of the node
+    l2_height: Option<u64>,
+    /// DA height of the latest soft confirmation of the node
+    l1_height: Option<u64>,
+    /// Soft confirmations processed per minute over the last interval
+    l2_blocks_per_minute: f64,
+    /// Proofs produced per hour over the last interval
+    proofs_per_hour: f64,
+}
+
+/// Counters at one point in time, the rates of a report are computed between two samples
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    l2_height: u64,
+    proofs: u64,
+}
+
+fn report(
+    mode: NodeMode,
+    l1_height: Option<u64>,
+    l2_height: Option<u64>,
+    previous: Sample,
+    current: Sample,
+) -> TelemetryReport {
+    let elapsed = current.at.duration_since(previous.at).as_secs_f64();
+    let rate = |previous: u64, current: u64, per: f64| {
+        if elapsed > 0.0 {
+            current.saturating_sub(previous) as f64 * per / elapsed
+        } else {
+            0.0
+        }
+    };
+
+    TelemetryReport {
+        version: env!("CARGO_PKG_VERSION"),
+        mode,
+        l2_height,
+        l1_height,
+        l2_blocks_per_minute: rate(previous.l2_height, current.l2_height, 60.0),
+        proofs_per_hour: rate(previous.proofs, current.proofs, 3600.0),
+    }
+}
+
+/// Spawns the task posting the stats of the node to `config.endpoint` every
+/// `config.interval_secs`. Failing to reach the endpoint never affects the node.
+pub fn spawn_telemetry(config: TelemetryConfig, mode: NodeMode, ledger_db: LedgerDB) {
+    info!(
+        "Telemetry enabled, reporting to {} every {}s",
+        config.endpoint, config.interval_secs
+    );
+
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+        let mut previous: Option<Sample> = None;
+
+        loop {
+            interval.tick().await;
+
+            let head = match ledger_db.get_head_soft_batch() {
+                Ok(head) => head,
+                Err(e) => {
+                    debug!("Telemetry: failed to read the ledger head: {:?}", e);
+                    continue;
+                }
+            };
+            let l2_height = head.as_ref().map(|(number, _)| number.0);
+            let l1_height = head.as_ref().map(|(_, batch)| batch.da_slot_height);
+            let current = Sample {
+                at: Instant::now(),
+                l2_height: l2_height.unwrap_or_default(),
+                proofs: PROOFS_COMPLETED.load(Ordering::Relaxed),
+            };
+
+            // The first tick only takes the sample the rates start from
+            if let Some(previous) = previous.replace(current) {
+                let stats = report(mode, l1_height, l2_height, previous, current);
+                if let Err(e) = client.post(&config.endpoint).json(&stats).send().await {
+                    debug!("Telemetry: failed to post report: {:?}", e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_rates() {
+        let start = Instant::now();
+        let previous = Sample {
+            at: start,
+            l2_height: 100,
+            proofs: 2,
+        };
+        let current = Sample {
+            at: start + Duration::from_secs(30),
+            l2_height: 130,
+            proofs: 3,
+        };
+
+        let stats = report(NodeMode::Prover, Some(7), Some(130), previous, current);
+        assert_eq!(stats.l2_blocks_per_minute, 60.0);
+        assert_eq!(stats.proofs_per_hour, 120.0);
+
+        // A rolled back head does not produce negative rates
+        let rolled_back = Sample {
+            at: start + Duration::from_secs(60),
+            l2_height: 120,
+            proofs: 3,
+        };
+        let stats = report(NodeMode::Prover, Some(7), Some(120), current, rolled_back);
+        assert_eq!(stats.l2_blocks_per_minute, 0.0);
+    }
+}
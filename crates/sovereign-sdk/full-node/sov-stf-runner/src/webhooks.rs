@@ -0,0 +1,166 @@
+//! Webhooks posting the chain events a full node sees to the endpoints set in
+//! [`WebhookConfig`], so that alerting and downstream pipelines don't have to poll the RPC.
+//!
+//! Each webhook is delivered to by its own task, in the order the events happened. A failed
+//! delivery is retried with exponential backoff, and the event is dropped once the retries
+//! are exhausted. Events are dropped as well if a webhook falls too far behind, delivering
+//! never slows the node down.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::events::ChainEvent;
+use crate::{ChainEventKind, WebhookConfig};
+
+/// Number of events queued for a webhook before new ones are dropped
+const WEBHOOK_QUEUE_SIZE: usize = 1024;
+
+/// Longest delay between two delivery attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Header holding the hex encoded HMAC-SHA256 of the body, for webhooks with a secret
+const SIGNATURE_HEADER: &str = "X-Citrea-Signature";
+
+/// Header holding the kind of the event, as in [`ChainEventKind`]
+const EVENT_HEADER: &str = "X-Citrea-Event";
+
+fn event_name(kind: ChainEventKind) -> &'static str {
+    match kind {
+        ChainEventKind::NewBlock => "new_block",
+        ChainEventKind::NewCommitment => "new_commitment",
+        ChainEventKind::NewProof => "new_proof",
+    }
+}
+
+/// Returns the hex encoded HMAC-SHA256 of `body` keyed with `secret`.
+fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Returns the delay before the retry following the failed attempt `attempt`, counted from 0.
+fn backoff(initial_backoff_ms: u64, attempt: u32) -> Duration {
+    let delay_ms = initial_backoff_ms.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+    Duration::from_millis(delay_ms).min(MAX_BACKOFF)
+}
+
+/// A webhook and the queue of the events to deliver to it
+struct Webhook {
+    events: Vec<ChainEventKind>,
+    queue: mpsc::Sender<ChainEvent>,
+}
+
+/// Hands the chain events to the tasks delivering them to the webhooks.
+pub(crate) struct WebhookDispatcher {
+    webhooks: Vec<Webhook>,
+}
+
+impl WebhookDispatcher {
+    /// Spawns the delivery task of each webhook.
+    pub(crate) fn spawn(configs: Vec<WebhookConfig>) -> Self {
+        let client = reqwest::Client::new();
+        let webhooks = configs
+            .into_iter()
+            .map(|config| {
+                info!("Webhook enabled, posting events to {}", config.url);
+                let (queue, events_rx) = mpsc::channel(WEBHOOK_QUEUE_SIZE);
+                let events = config.events.clone();
+                tokio::spawn(deliver(client.clone(), config, events_rx));
+                Webhook { events, queue }
+            })
+            .collect();
+
+        Self { webhooks }
+    }
+
+    /// Queues the event for the webhooks subscribed to its kind.
+    pub(crate) fn notify(&self, event: ChainEvent) {
+        let kind = event.kind();
+        for webhook in &self.webhooks {
+            if !webhook.events.is_empty() && !webhook.events.contains(&kind) {
+                continue;
+            }
+            if let Err(e) = webhook.queue.try_send(event.clone()) {
+                warn!("Webhook: dropping {} event: {}", event_name(kind), e);
+            }
+        }
+    }
+}
+
+/// Posts the events received on `events_rx` to the webhook, one at a time.
+async fn deliver(
+    client: reqwest::Client,
+    config: WebhookConfig,
+    mut events_rx: mpsc::Receiver<ChainEvent>,
+) {
+    while let Some(event) = events_rx.recv().await {
+        let name = event_name(event.kind());
+        let body = serde_json::to_vec(&event).expect("Webhook events are serializable");
+        let signature = config
+            .secret
+            .as_ref()
+            .map(|secret| sign(secret.as_bytes(), &body));
+
+        let mut attempt = 0;
+        loop {
+            let mut request = client
+                .post(&config.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, name)
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => break,
+                Ok(response) => format!("status {}", response.status()),
+                Err(e) => e.to_string(),
+            };
+
+            if attempt == config.max_retries {
+                warn!(
+                    "Webhook: failed to post {} event to {} after {} attempts, dropping it: {}",
+                    name,
+                    config.url,
+                    attempt + 1,
+                    error
+                );
+                break;
+            }
+            debug!(
+                "Webhook: failed to post {} event to {}, retrying: {}",
+                name, config.url, error
+            );
+            tokio::time::sleep(backoff(config.initial_backoff_ms, attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(500, 0), Duration::from_millis(500));
+        assert_eq!(backoff(500, 3), Duration::from_secs(4));
+        assert_eq!(backoff(500, 10), MAX_BACKOFF);
+        assert_eq!(backoff(500, 100), MAX_BACKOFF);
+    }
+}
@@ -85,6 +85,7 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
 
     fn apply_soft_batch_txs(
         &self,
+        _sequencer_public_key: &[u8],
         _txs: Vec<Vec<u8>>,
         _batch_workspace: sov_modules_api::WorkingSet<C>,
     ) -> (
@@ -94,6 +95,30 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
         unimplemented!()
     }
 
+    fn soft_batch_signature_scheme(
+        &self,
+        _pre_state: Self::PreState,
+        _da_slot_height: u64,
+    ) -> sov_modules_api::SignatureScheme {
+        unimplemented!()
+    }
+
+    fn soft_batch_timestamped(&self, _pre_state: Self::PreState, _da_slot_height: u64) -> bool {
+        unimplemented!()
+    }
+
+    fn soft_batch_ordering_committed(
+        &self,
+        _pre_state: Self::PreState,
+        _da_slot_height: u64,
+    ) -> bool {
+        unimplemented!()
+    }
+
+    fn is_sequencer_da_sender(&self, _pre_state: Self::PreState, _sender: &Da::Address) -> bool {
+        unimplemented!()
+    }
+
     fn end_soft_batch(
         &self,
         _sequencer_public_key: &[u8],
@@ -127,6 +152,40 @@ impl<C: Context, Da: DaSpec, Vm: Zkvm, Cond: ValidityCondition> StfBlueprintTrai
     > {
         unimplemented!()
     }
+
+    fn apply_verified_soft_batch(
+        &self,
+        _sequencer_public_key: &[u8],
+        _pre_state_root: &Self::StateRoot,
+        _pre_state: Self::PreState,
+        _witness: Self::Witness,
+        _slot_header: &<Da as DaSpec>::BlockHeader,
+        _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        _soft_batch: &mut sov_modules_api::SignedSoftConfirmationBatch,
+        _verified_scheme: sov_modules_api::SignatureScheme,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    > {
+        unimplemented!()
+    }
+
+    fn dry_run_soft_batch(
+        &self,
+        _sequencer_public_key: &[u8],
+        _pre_state_root: &Self::StateRoot,
+        _pre_state: Self::PreState,
+        _slot_header: &<Da as DaSpec>::BlockHeader,
+        _soft_batch: &mut sov_modules_api::SignedSoftConfirmationBatch,
+    ) -> Result<
+        sov_modules_stf_blueprint::BatchReceipt<(), sov_modules_stf_blueprint::TxEffect>,
+        sov_modules_api::hooks::ApplySoftConfirmationError,
+    > {
+        unimplemented!()
+    }
 }
 
 impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm, Da>
@@ -109,6 +109,41 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_prover_memory_limit() -> Result<(), anyhow::Error> {
+    // Any witness is over the limit, so jobs are only admitted one at a time
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover_with_memory_limit(Some(1));
+
+    let header_hash = MockHash::from([1; 32]);
+    prover_service
+        .submit_witness(make_transition_data(header_hash))
+        .await;
+    let status = prover_service.prove(header_hash).await?;
+    assert_eq!(ProofProcessingStatus::ProvingInProgress, status);
+
+    // Threads are free, but the memory of the running job is reserved.
+    let next_header_hash = MockHash::from([2; 32]);
+    prover_service
+        .submit_witness(make_transition_data(next_header_hash))
+        .await;
+    let status = prover_service.prove(next_header_hash).await?;
+    assert_eq!(ProofProcessingStatus::Busy, status);
+
+    vm.make_proof();
+    wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+
+    // Retry once the memory of the first job is released.
+    prover_service
+        .submit_witness(make_transition_data(next_header_hash))
+        .await;
+    let status = prover_service.prove(next_header_hash).await?;
+    assert_eq!(ProofProcessingStatus::ProvingInProgress, status);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_missing_witness() -> Result<(), anyhow::Error> {
     let TestProver { prover_service, .. } = make_new_prover();
@@ -194,6 +229,10 @@ async fn wait_for_proof_proof_da_submission(
 }
 
 fn make_new_prover() -> TestProver {
+    make_new_prover_with_memory_limit(None)
+}
+
+fn make_new_prover_with_memory_limit(max_memory_bytes: Option<u64>) -> TestProver {
     let num_threads = num_cpus::get();
     let vm = MockZkvm::new(MockValidityCond::default());
 
@@ -210,6 +249,8 @@ fn make_new_prover() -> TestProver {
             num_threads,
             ProverServiceConfig {
                 aggregated_proof_block_jump: 1,
+                max_memory_bytes,
+                memory_per_witness_byte: 1,
             },
         ),
         vm,
@@ -122,6 +122,7 @@ async fn runner_execution(
 ) -> ([u8; 32], [u8; 32]) {
     let rollup_config = RollupConfig::<MockDaConfig> {
         sequencer_public_key: vec![0u8; 32],
+        sequencer_schnorr_public_key: None,
         storage: StorageConfig {
             path: path.to_path_buf(),
         },
@@ -130,16 +131,28 @@ async fn runner_execution(
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
+                response_cache_entries: 0,
             },
+            golden_batches_path: None,
+            prover_id: None,
+            webhooks: vec![],
+            prefetch_state: false,
         },
         da: MockDaConfig {
             sender_address: da_service.get_sequencer_address(),
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            max_memory_bytes: None,
+            memory_per_witness_byte: 16,
         },
         sequencer_client: None,
         include_tx_body: true,
+        telemetry: None,
+        wallet_chain: None,
+        streaming: None,
+        indexer: None,
+        gas_oracle: None,
     };
 
     let ledger_db = LedgerDB::with_path(path).unwrap();
@@ -177,7 +190,9 @@ async fn runner_execution(
             Some(prover_service),
             None,
             vec![0u8; 32],
+            None,
             true,
+            Default::default(),
         )
         .unwrap();
 
@@ -7,7 +7,7 @@ use sov_modules_api::macros::DefaultRuntime;
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
     AccessoryWorkingSet, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, MessageCodec,
-    PublicKey, Spec,
+    PublicKey, SoftConfirmationOutcome, Spec,
 };
 use sov_modules_stf_blueprint::{Runtime, RuntimeTxHook, SequencerOutcome};
 use sov_state::Storage;
@@ -75,8 +75,7 @@ impl<C: Context, B: BlobReaderTrait> ApplyBlobHooks<B> for TestRuntime<C> {
 impl<C: Context, Da: DaSpec> ApplySoftConfirmationHooks<Da> for TestRuntime<C> {
     type Context = C;
 
-    type SoftConfirmationResult =
-        SequencerOutcome<<<Da as DaSpec>::BlobTransaction as BlobReaderTrait>::Address>;
+    type SoftConfirmationResult = SoftConfirmationOutcome;
 
     fn begin_soft_confirmation_hook(
         &self,
@@ -88,8 +87,9 @@ impl<C: Context, Da: DaSpec> ApplySoftConfirmationHooks<Da> for TestRuntime<C> {
 
     fn end_soft_confirmation_hook(
         &self,
+        _soft_batch: &sov_modules_api::hooks::HookSoftConfirmationInfo,
         _working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
-    ) -> Result<(), ApplySoftConfirmationError> {
+    ) -> Result<SoftConfirmationOutcome, ApplySoftConfirmationError> {
         todo!()
     }
 }
@@ -108,6 +108,10 @@ pub(crate) fn setup(
     let chain_state_config = sov_chain_state::ChainStateConfig {
         initial_slot_height: INIT_HEIGHT,
         current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
     };
 
     let chain_state = sov_chain_state::ChainState::<C, MockDaSpec>::default();
@@ -19,6 +19,10 @@ fn empty_test() {
     let chain_state_config = ChainStateConfig {
         initial_slot_height,
         current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
     };
     chain_state
         .genesis(&chain_state_config, &mut working_set)
@@ -42,6 +46,10 @@ fn store_and_retrieve_standard() {
     let chain_state_config = ChainStateConfig {
         initial_slot_height,
         current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
     };
     chain_state
         .genesis(&chain_state_config, &mut working_set)
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::da::Time;
 use sov_modules_api::{StateValueAccessor, WorkingSet};
 
-use crate::{ChainState, TransitionHeight};
+use crate::{ChainState, DaChainTip, TransitionHeight};
 
 /// Initial configuration of the chain state
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -12,6 +12,23 @@ pub struct ChainStateConfig {
     pub initial_slot_height: TransitionHeight,
     /// The time at genesis
     pub current_time: Time,
+    /// First DA height whose soft confirmations are signed with Schnorr signatures instead
+    /// of Ed25519. Soft confirmations stay signed with Ed25519 if not set.
+    #[serde(default)]
+    pub schnorr_activation_height: Option<u64>,
+    /// First DA height whose soft confirmations carry a signed timestamp, which becomes the
+    /// timestamp of their L2 block. Soft confirmations carry no timestamp if not set.
+    #[serde(default)]
+    pub soft_batch_timestamp_activation_height: Option<u64>,
+    /// First DA height whose soft confirmations carry a signed commitment to the order in
+    /// which the sequencer received their transactions. Soft confirmations carry no
+    /// ordering commitment if not set.
+    #[serde(default)]
+    pub soft_batch_ordering_commitment_activation_height: Option<u64>,
+    /// Hex encoded DA address the sequencer posts its commitments and proofs from.
+    /// Blobs from other senders are ignored. Blobs from any sender are read if not set.
+    #[serde(default)]
+    pub sequencer_da_address: Option<String>,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -27,6 +44,36 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
             .set(&config.initial_slot_height, working_set);
 
         self.time.set_genesis(&config.current_time, working_set);
+
+        if let Some(height) = config.schnorr_activation_height {
+            self.schnorr_activation_height.set(&height, working_set);
+        }
+
+        if let Some(height) = config.soft_batch_timestamp_activation_height {
+            self.soft_batch_timestamp_activation_height
+                .set(&height, working_set);
+        }
+
+        if let Some(height) = config.soft_batch_ordering_commitment_activation_height {
+            self.soft_batch_ordering_commitment_activation_height
+                .set(&height, working_set);
+        }
+
+        if let Some(address) = &config.sequencer_da_address {
+            let address = hex::decode(address)
+                .map_err(|e| anyhow::anyhow!("Invalid sequencer DA address {}: {}", address, e))?;
+            let address = Da::Address::try_from(address.as_slice())?;
+            self.sequencer_da_address.set(&address, working_set);
+        }
         Ok(())
     }
 }
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+    /// Enables the DA light client, starting from a trusted DA header.
+    /// Called by kernels at genesis.
+    pub fn init_da_light_client(&self, tip: &DaChainTip<Da>, working_set: &mut WorkingSet<C>) {
+        self.da_chain_tip.set(tip, working_set);
+        self.remember_da_chain_tip(tip, working_set);
+    }
+}
@@ -1,12 +1,15 @@
-use sov_modules_api::da::BlockHeaderTrait;
+use sov_modules_api::da::{BlockHeaderTrait, LightClientError, LightClientHeader};
 use sov_modules_api::hooks::FinalizeHook;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{AccessoryWorkingSet, Context, Spec};
+use sov_modules_api::{
+    AccessoryWorkingSet, Context, SoftConfirmationTimestampError, Spec, WorkingSet,
+    MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS,
+};
 use sov_state::storage::KernelWorkingSet;
 use sov_state::Storage;
 
 use super::ChainState;
-use crate::{StateTransitionId, TransitionInProgress};
+use crate::{DaChainTip, StateTransitionId, TransitionInProgress, DA_REORG_WINDOW};
 
 impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
     /// Update the chain state at the beginning of the slot
@@ -56,6 +59,114 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
         );
     }
 
+    /// Extends the DA header chain with the DA block a soft confirmation builds on.
+    /// Several soft confirmations build on the same DA block, which is only validated once.
+    /// Does nothing if the DA light client is disabled.
+    ///
+    /// The DA block may also build on one of the last [`DA_REORG_WINDOW`] tips instead of the
+    /// current one, when the DA chain reorganized. It then becomes the tip, so soft
+    /// confirmations follow the new branch. DA blocks forking off older tips are rejected:
+    /// the window bounds the depth of the reorganizations the rollup follows.
+    pub fn extend_da_chain(
+        &self,
+        slot_header: &Da::BlockHeader,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), LightClientError> {
+        let Some(tip) = self.da_chain_tip.get(working_set) else {
+            return Ok(());
+        };
+        let hash: [u8; 32] = slot_header.hash().into();
+        if <Da::BlockHeader as LightClientHeader>::tip_hash(&tip) == hash {
+            return Ok(());
+        }
+
+        let prev_hash: [u8; 32] = slot_header.prev_hash().into();
+        let parent = if <Da::BlockHeader as LightClientHeader>::tip_hash(&tip) == prev_hash {
+            tip
+        } else {
+            // The DA chain reorganized onto a branch forking off a recent tip
+            self.recent_da_chain_tips
+                .get(&prev_hash, working_set)
+                .ok_or(LightClientError::NotSuccessor)?
+        };
+
+        let tip = slot_header.extend_tip(&parent)?;
+        self.da_chain_tip.set(&tip, working_set);
+        self.remember_da_chain_tip(&tip, working_set);
+        Ok(())
+    }
+
+    /// Records `tip` as one of the last [`DA_REORG_WINDOW`] tips of the DA header chain and
+    /// forgets the oldest one if the window is full.
+    pub(crate) fn remember_da_chain_tip(
+        &self,
+        tip: &DaChainTip<Da>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let hash = <Da::BlockHeader as LightClientHeader>::tip_hash(tip);
+        let mut hashes = self
+            .recent_da_chain_tip_hashes
+            .get(working_set)
+            .unwrap_or_default();
+        hashes.push(hash);
+        self.recent_da_chain_tips.set(&hash, tip, working_set);
+
+        if hashes.len() > DA_REORG_WINDOW {
+            let expired = hashes.remove(0);
+            self.recent_da_chain_tips.delete(&expired, working_set);
+        }
+        self.recent_da_chain_tip_hashes.set(&hashes, working_set);
+    }
+
+    /// Checks that a soft confirmation carries a timestamp if and only if it builds on a DA
+    /// block from the activation height on. Then checks that the timestamp is not before
+    /// the timestamp of the previous one, and at most [`MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS`]
+    /// away from the time of the DA block it builds on. Records it as the parent of the next
+    /// soft confirmation.
+    pub fn check_soft_batch_timestamp(
+        &self,
+        slot_header: &Da::BlockHeader,
+        timestamp: Option<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), SoftConfirmationTimestampError> {
+        let timestamp = match (
+            timestamp,
+            self.soft_batch_timestamp_activation_height.get(working_set),
+        ) {
+            (Some(timestamp), Some(activation_height))
+                if slot_header.height() >= activation_height =>
+            {
+                timestamp
+            }
+            (None, Some(activation_height)) if slot_header.height() >= activation_height => {
+                return Err(SoftConfirmationTimestampError::Missing { activation_height });
+            }
+            (Some(_), _) => return Err(SoftConfirmationTimestampError::Unexpected),
+            (None, _) => return Ok(()),
+        };
+
+        if let Some(parent_timestamp) = self.last_soft_batch_timestamp.get(working_set) {
+            if timestamp < parent_timestamp {
+                return Err(SoftConfirmationTimestampError::BeforeParent { parent_timestamp });
+            }
+        }
+
+        let da_block_time = slot_header.time().secs().max(0) as u64;
+        if timestamp.abs_diff(da_block_time) > MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS {
+            return Err(SoftConfirmationTimestampError::TooFarFromDaBlock { da_block_time });
+        }
+
+        self.last_soft_batch_timestamp.set(&timestamp, working_set);
+        Ok(())
+    }
+
+    /// Counts the soft confirmation being applied, whose L2 height is then the soft
+    /// confirmation count.
+    pub fn count_soft_batch(&self, working_set: &mut WorkingSet<C>) {
+        let count = self.soft_batch_count.get(working_set).unwrap_or_default();
+        self.soft_batch_count.set(&(count + 1), working_set);
+    }
+
     /// Update the chain state at the end of each slot, if necessary
     pub fn end_slot_hook(&self, _working_set: &mut KernelWorkingSet<C>) {}
 }
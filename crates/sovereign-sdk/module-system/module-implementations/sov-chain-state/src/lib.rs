@@ -21,9 +21,11 @@ use borsh::{BorshDeserialize, BorshSerialize};
 pub use query::*;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sov_modules_api::da::Time;
+use sov_modules_api::da::{LightClientHeader, Time};
 use sov_modules_api::prelude::*;
-use sov_modules_api::{DaSpec, Error, KernelModuleInfo, ValidityConditionChecker, WorkingSet};
+use sov_modules_api::{
+    DaSpec, Error, KernelModuleInfo, SignatureScheme, ValidityConditionChecker, WorkingSet,
+};
 use sov_state::codec::BcsCodec;
 use sov_state::storage::kernel_state::VersionReader;
 use sov_state::storage::KernelWorkingSet;
@@ -32,6 +34,11 @@ use sov_state::Storage;
 /// Type alias that contains the height of a given transition
 pub type TransitionHeight = u64;
 
+/// Number of recent tips of the DA header chain remembered by the DA light client. A DA block
+/// forking off one of them is accepted, so the light client follows reorganizations of the
+/// DA chain as long as the fork point is among them.
+pub const DA_REORG_WINDOW: usize = 16;
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 /// Structure that contains the information needed to represent a single state transition.
 pub struct StateTransitionId<Da: DaSpec, StateRoot> {
@@ -162,8 +169,53 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     // TODO: This should be made read-only
     #[state]
     genesis_height: sov_modules_api::StateValue<TransitionHeight>,
+
+    /// The tip of the DA header chain, as validated by the DA light client.
+    /// Not set when the light client is disabled.
+    #[state]
+    da_chain_tip: sov_modules_api::StateValue<DaChainTip<Da>, BcsCodec>,
+
+    /// The last [`DA_REORG_WINDOW`] tips of the DA header chain, by hash, including the
+    /// ones of branches the DA chain reorganized away from
+    #[state]
+    recent_da_chain_tips: sov_modules_api::StateMap<[u8; 32], DaChainTip<Da>, BcsCodec>,
+
+    /// Hashes of the tips in `recent_da_chain_tips`, oldest first
+    #[state]
+    recent_da_chain_tip_hashes: sov_modules_api::StateValue<Vec<[u8; 32]>>,
+
+    /// The timestamp of the last soft confirmation, in seconds since the unix epoch
+    #[state]
+    last_soft_batch_timestamp: sov_modules_api::StateValue<u64>,
+
+    /// First DA height whose soft confirmations carry a timestamp.
+    /// Not set while the activation is not scheduled.
+    #[state]
+    soft_batch_timestamp_activation_height: sov_modules_api::StateValue<u64>,
+
+    /// First DA height whose soft confirmations carry an ordering commitment.
+    /// Not set while the activation is not scheduled.
+    #[state]
+    soft_batch_ordering_commitment_activation_height: sov_modules_api::StateValue<u64>,
+
+    /// First DA height whose soft confirmations are signed with Schnorr signatures.
+    /// Not set while the activation is not scheduled.
+    #[state]
+    schnorr_activation_height: sov_modules_api::StateValue<u64>,
+
+    /// DA address the sequencer posts its commitments and proofs from.
+    /// Not set if blobs from any sender are read.
+    #[state]
+    sequencer_da_address: sov_modules_api::StateValue<Da::Address, BcsCodec>,
+
+    /// Number of soft confirmations applied since genesis
+    #[state]
+    soft_batch_count: sov_modules_api::StateValue<u64>,
 }
 
+/// What the DA light client remembers about the tip of the DA chain
+pub type DaChainTip<Da> = <<Da as DaSpec>::BlockHeader as LightClientHeader>::ChainTip;
+
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
     /// Returns transition height in the current slot
     pub fn true_slot_height(&self, working_set: &mut WorkingSet<C>) -> TransitionHeight {
@@ -195,6 +247,79 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.genesis_height.get(working_set)
     }
 
+    /// Returns the tip of the DA header chain, if the DA light client is enabled.
+    pub fn get_da_chain_tip(&self, working_set: &mut WorkingSet<C>) -> Option<DaChainTip<Da>> {
+        self.da_chain_tip.get(working_set)
+    }
+
+    /// Returns the timestamp of the last soft confirmation, if any was applied.
+    pub fn get_last_soft_batch_timestamp(&self, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.last_soft_batch_timestamp.get(working_set)
+    }
+
+    /// Returns the number of soft confirmations applied since genesis. While a soft
+    /// confirmation is applied, this is its L2 height once it was counted.
+    pub fn get_soft_batch_count(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.soft_batch_count.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the signature scheme of the soft confirmations given for the DA block at
+    /// `da_slot_height`.
+    pub fn signature_scheme(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> SignatureScheme {
+        match self.schnorr_activation_height.get(working_set) {
+            Some(activation_height) if da_slot_height >= activation_height => {
+                SignatureScheme::Secp256k1Schnorr
+            }
+            _ => SignatureScheme::Ed25519,
+        }
+    }
+
+    /// Returns whether the soft confirmations given for the DA block at `da_slot_height`
+    /// carry a timestamp.
+    pub fn soft_batch_timestamped(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.soft_batch_timestamp_activation_height
+            .get(working_set)
+            .is_some_and(|activation_height| da_slot_height >= activation_height)
+    }
+
+    /// Returns whether the soft confirmations given for the DA block at `da_slot_height`
+    /// carry an ordering commitment.
+    pub fn soft_batch_ordering_committed(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.soft_batch_ordering_commitment_activation_height
+            .get(working_set)
+            .is_some_and(|activation_height| da_slot_height >= activation_height)
+    }
+
+    /// Returns the DA address the sequencer posts its commitments and proofs from, if it was
+    /// set at genesis.
+    pub fn get_sequencer_da_address(&self, working_set: &mut WorkingSet<C>) -> Option<Da::Address> {
+        self.sequencer_da_address.get(working_set)
+    }
+
+    /// Returns whether blobs sent by `sender` are read as sequencer data, which is the case
+    /// of any sender if no sequencer DA address was set.
+    pub fn is_sequencer_da_sender(
+        &self,
+        sender: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.sequencer_da_address
+            .get(working_set)
+            .map_or(true, |address| address == *sender)
+    }
+
     /// Returns the transition in progress of the module.
     pub fn get_in_progress_transition(
         &self,
@@ -1,6 +1,13 @@
-use sov_modules_api::da::{NanoSeconds, Time};
+use sov_mock_da::{MockAddress, MockBlockHeader, MockDaSpec, MockHash};
+use sov_modules_api::da::{BlockHeaderTrait, LightClientError, LinkedChainTip, NanoSeconds, Time};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{
+    KernelModule, SignatureScheme, SoftConfirmationTimestampError, WorkingSet,
+    MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS,
+};
+use sov_prover_storage_manager::new_orphan_storage;
 
-use crate::ChainStateConfig;
+use crate::{ChainState, ChainStateConfig, DA_REORG_WINDOW};
 
 #[test]
 fn test_config_serialization() {
@@ -8,6 +15,10 @@ fn test_config_serialization() {
     let config = ChainStateConfig {
         initial_slot_height: 1,
         current_time: time,
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
     };
 
     let data = r#"
@@ -22,3 +33,321 @@ fn test_config_serialization() {
     let parsed_config: ChainStateConfig = serde_json::from_str(data).unwrap();
     assert_eq!(config, parsed_config)
 }
+
+#[test]
+fn test_da_chain_extension() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    // The light client is disabled until it is initialized
+    chain_state
+        .extend_da_chain(&MockBlockHeader::from_height(5), &mut working_set)
+        .unwrap();
+    assert_eq!(chain_state.get_da_chain_tip(&mut working_set), None);
+
+    let genesis = MockBlockHeader::from_height(1);
+    chain_state.init_da_light_client(
+        &LinkedChainTip {
+            height: 1,
+            hash: genesis.hash().into(),
+        },
+        &mut working_set,
+    );
+
+    // Soft confirmations on the same DA block
+    chain_state
+        .extend_da_chain(&genesis, &mut working_set)
+        .unwrap();
+
+    let next = MockBlockHeader::from_height(2);
+    chain_state
+        .extend_da_chain(&next, &mut working_set)
+        .unwrap();
+    chain_state
+        .extend_da_chain(&next, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.get_da_chain_tip(&mut working_set),
+        Some(LinkedChainTip {
+            height: 2,
+            hash: next.hash().into(),
+        })
+    );
+
+    assert_eq!(
+        chain_state.extend_da_chain(&MockBlockHeader::from_height(4), &mut working_set),
+        Err(LightClientError::NotSuccessor)
+    );
+}
+
+/// Returns a header building on `parent`, on a branch told apart by `branch`. Its hash
+/// differs from the ones of [`MockBlockHeader::from_height`].
+fn child_header(parent: &MockBlockHeader, branch: u8) -> MockBlockHeader {
+    let mut hash = [0xf0 | branch; 32];
+    hash[..8].copy_from_slice(&(parent.height + 1).to_be_bytes());
+    MockBlockHeader {
+        prev_hash: parent.hash,
+        hash: MockHash(hash),
+        height: parent.height + 1,
+        time: Time::now(),
+    }
+}
+
+fn tip_of(header: &MockBlockHeader) -> LinkedChainTip {
+    LinkedChainTip {
+        height: header.height,
+        hash: header.hash().into(),
+    }
+}
+
+#[test]
+fn test_da_chain_reorg() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    let genesis = MockBlockHeader::from_height(1);
+    chain_state.init_da_light_client(&tip_of(&genesis), &mut working_set);
+
+    let mut headers = vec![genesis];
+    for _ in 0..3 {
+        let header = child_header(headers.last().unwrap(), 0);
+        chain_state
+            .extend_da_chain(&header, &mut working_set)
+            .unwrap();
+        headers.push(header);
+    }
+
+    // The DA chain reorganizes onto a branch forking off the block at height 2
+    let fork = child_header(&headers[1], 1);
+    chain_state
+        .extend_da_chain(&fork, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.get_da_chain_tip(&mut working_set),
+        Some(tip_of(&fork))
+    );
+
+    // And back onto the original branch, which grew longer
+    let longer = child_header(&headers[3], 0);
+    chain_state
+        .extend_da_chain(&longer, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.get_da_chain_tip(&mut working_set),
+        Some(tip_of(&longer))
+    );
+    headers.push(longer);
+
+    // Once the fork point left the window, a branch forking off it is rejected
+    for _ in 0..DA_REORG_WINDOW {
+        let header = child_header(headers.last().unwrap(), 0);
+        chain_state
+            .extend_da_chain(&header, &mut working_set)
+            .unwrap();
+        headers.push(header);
+    }
+    assert_eq!(
+        chain_state.extend_da_chain(&child_header(&headers[1], 2), &mut working_set),
+        Err(LightClientError::NotSuccessor)
+    );
+}
+
+#[test]
+fn test_soft_batch_timestamp() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: Some(0),
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    let da_block_time = 1_700_000_000;
+    let header = MockBlockHeader {
+        time: Time::from_secs(da_block_time as i64),
+        ..MockBlockHeader::from_height(1)
+    };
+
+    chain_state
+        .check_soft_batch_timestamp(&header, Some(da_block_time + 10), &mut working_set)
+        .unwrap();
+    // Soft confirmations may share a timestamp
+    chain_state
+        .check_soft_batch_timestamp(&header, Some(da_block_time + 10), &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.get_last_soft_batch_timestamp(&mut working_set),
+        Some(da_block_time + 10)
+    );
+
+    assert_eq!(
+        chain_state.check_soft_batch_timestamp(&header, Some(da_block_time + 9), &mut working_set),
+        Err(SoftConfirmationTimestampError::BeforeParent {
+            parent_timestamp: da_block_time + 10
+        })
+    );
+    assert_eq!(
+        chain_state.check_soft_batch_timestamp(
+            &header,
+            Some(da_block_time + MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS + 1),
+            &mut working_set
+        ),
+        Err(SoftConfirmationTimestampError::TooFarFromDaBlock { da_block_time })
+    );
+    // Rejected timestamps are not recorded
+    assert_eq!(
+        chain_state.get_last_soft_batch_timestamp(&mut working_set),
+        Some(da_block_time + 10)
+    );
+}
+
+#[test]
+fn test_signature_scheme_activation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        schnorr_activation_height: Some(10),
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    assert_eq!(
+        chain_state.signature_scheme(9, &mut working_set),
+        SignatureScheme::Ed25519
+    );
+    assert_eq!(
+        chain_state.signature_scheme(10, &mut working_set),
+        SignatureScheme::Secp256k1Schnorr
+    );
+}
+
+#[test]
+fn test_soft_batch_timestamp_activation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: Some(10),
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: None,
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    let da_block_time = 1_700_000_000;
+    let header_at = |height| MockBlockHeader {
+        time: Time::from_secs(da_block_time as i64),
+        ..MockBlockHeader::from_height(height)
+    };
+
+    // Before the activation soft confirmations carry no timestamp, and none is recorded
+    assert!(!chain_state.soft_batch_timestamped(9, &mut working_set));
+    chain_state
+        .check_soft_batch_timestamp(&header_at(9), None, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.check_soft_batch_timestamp(
+            &header_at(9),
+            Some(da_block_time),
+            &mut working_set
+        ),
+        Err(SoftConfirmationTimestampError::Unexpected)
+    );
+    assert_eq!(
+        chain_state.get_last_soft_batch_timestamp(&mut working_set),
+        None
+    );
+
+    // From the activation on they must carry one
+    assert!(chain_state.soft_batch_timestamped(10, &mut working_set));
+    assert_eq!(
+        chain_state.check_soft_batch_timestamp(&header_at(10), None, &mut working_set),
+        Err(SoftConfirmationTimestampError::Missing {
+            activation_height: 10
+        })
+    );
+    chain_state
+        .check_soft_batch_timestamp(&header_at(10), Some(da_block_time), &mut working_set)
+        .unwrap();
+    assert_eq!(
+        chain_state.get_last_soft_batch_timestamp(&mut working_set),
+        Some(da_block_time)
+    );
+}
+
+#[test]
+fn test_soft_batch_ordering_commitment_activation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: Some(10),
+        sequencer_da_address: None,
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    assert!(!chain_state.soft_batch_ordering_committed(9, &mut working_set));
+    assert!(chain_state.soft_batch_ordering_committed(10, &mut working_set));
+}
+
+#[test]
+fn test_sequencer_da_address() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+    let sequencer = MockAddress::new([1; 32]);
+
+    // Blobs from any sender are read until a sequencer DA address is set
+    assert!(chain_state.is_sequencer_da_sender(&MockAddress::new([2; 32]), &mut working_set));
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        schnorr_activation_height: None,
+        soft_batch_timestamp_activation_height: None,
+        soft_batch_ordering_commitment_activation_height: None,
+        sequencer_da_address: Some(hex::encode([1; 32])),
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    assert_eq!(
+        chain_state.get_sequencer_da_address(&mut working_set),
+        Some(sequencer)
+    );
+    assert!(chain_state.is_sequencer_da_sender(&sequencer, &mut working_set));
+    assert!(!chain_state.is_sequencer_da_sender(&MockAddress::new([2; 32]), &mut working_set));
+}
+
+#[test]
+fn test_soft_batch_count() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    assert_eq!(chain_state.get_soft_batch_count(&mut working_set), 0);
+    for height in 1..=3 {
+        chain_state.count_soft_batch(&mut working_set);
+        assert_eq!(chain_state.get_soft_batch_count(&mut working_set), height);
+    }
+}
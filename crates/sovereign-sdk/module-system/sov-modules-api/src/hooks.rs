@@ -1,8 +1,10 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use sov_modules_core::{AccessoryWorkingSet, Context, Spec, Storage, WorkingSet};
-use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
-use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use sov_rollup_interface::da::{BlobReaderTrait, DaSpec, LightClientError};
+use sov_rollup_interface::soft_confirmation::{
+    SignedSoftConfirmationBatch, SoftConfirmationTimestampError,
+};
 use thiserror::Error;
 
 use crate::transaction::Transaction;
@@ -34,6 +36,38 @@ pub enum ApplySoftConfirmationError {
         l1_fee_rate: u64,
         l1_fee_rate_change_percentage: u64,
     },
+    /// The DA block the soft confirmation builds on is rejected by the DA light client
+    #[error(
+        "DA block at height {} rejected by the light client: {}",
+        height,
+        error
+    )]
+    InvalidDaBlock {
+        /// Height of the DA block
+        height: u64,
+        /// Why the DA block is rejected
+        error: LightClientError,
+    },
+    /// The timestamp of the soft confirmation is rejected by the kernel
+    #[error("Soft confirmation timestamp {:?} rejected: {}", timestamp, error)]
+    InvalidTimestamp {
+        /// Timestamp of the soft confirmation, if it carries one
+        timestamp: Option<u64>,
+        /// Why the timestamp is rejected
+        error: SoftConfirmationTimestampError,
+    },
+    /// The soft confirmation contains more transactions than the protocol allows
+    #[error(
+        "Soft confirmation has {} transactions, more than the limit of {}",
+        tx_count,
+        max_tx_count
+    )]
+    TooManyTransactions {
+        /// Number of transactions in the soft confirmation
+        tx_count: u64,
+        /// Max. number of transactions per soft confirmation
+        max_tx_count: u64,
+    },
 }
 
 /// Hooks that execute within the `StateTransitionFunction::apply_blob` function for each processed transaction.
@@ -55,7 +89,8 @@ pub trait TxHooks {
     ) -> anyhow::Result<Self::PreResult>;
 
     /// Runs after the tx is dispatched to an appropriate module.
-    /// IF this hook returns error rollup panics
+    /// If this hook returns an error, the changes of the transaction are reverted and the hook
+    /// runs again on the reverted state. The rollup only panics if it fails again.
     fn post_dispatch_tx_hook(
         &self,
         tx: &Transaction<Self::Context>,
@@ -97,12 +132,14 @@ pub trait ApplySoftConfirmationHooks<Da: DaSpec> {
         working_set: &mut WorkingSet<Self::Context>,
     ) -> Result<(), ApplySoftConfirmationError>;
 
-    /// Executes at the end of apply_blob and rewards or slashes the sequencer
-    /// If this hook returns Err rollup panics
+    /// Executes at the end of apply_soft_confirmation, and returns its outcome, such as the
+    /// reward of the sequencer.
+    /// If this hook returns Err, an empty outcome is recorded for the soft confirmation
     fn end_soft_confirmation_hook(
         &self,
+        soft_batch: &HookSoftConfirmationInfo,
         working_set: &mut WorkingSet<Self::Context>,
-    ) -> Result<(), ApplySoftConfirmationError>;
+    ) -> Result<Self::SoftConfirmationResult, ApplySoftConfirmationError>;
 }
 
 /// Information about the soft confirmation block
@@ -119,6 +156,8 @@ pub struct HookSoftConfirmationInfo {
     pub pub_key: Vec<u8>,
     /// L1 fee rate
     pub l1_fee_rate: u64,
+    /// Block timestamp, in seconds since the unix epoch, if the soft confirmation carries one
+    pub timestamp: Option<u64>,
 }
 
 impl From<SignedSoftConfirmationBatch> for HookSoftConfirmationInfo {
@@ -129,6 +168,7 @@ impl From<SignedSoftConfirmationBatch> for HookSoftConfirmationInfo {
             pre_state_root: signed_soft_confirmation_batch.pre_state_root(),
             pub_key: signed_soft_confirmation_batch.sequencer_pub_key().to_vec(),
             l1_fee_rate: signed_soft_confirmation_batch.l1_fee_rate(),
+            timestamp: signed_soft_confirmation_batch.timestamp(),
         }
     }
 }
@@ -144,6 +184,9 @@ impl From<HookSoftConfirmationInfo> for SignedSoftConfirmationBatch {
             vec![],
             vec![],
             val.pub_key.clone(),
+            val.timestamp,
+            // Only known once all the transactions are read
+            None,
         )
     }
 }
@@ -172,6 +215,11 @@ impl HookSoftConfirmationInfo {
     pub fn l1_fee_rate(&self) -> u64 {
         self.l1_fee_rate
     }
+
+    /// Block timestamp, in seconds since the unix epoch, if the soft confirmation carries one
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
 }
 
 /// Hooks that execute during the `StateTransitionFunction::begin_slot` and `end_slot` functions.
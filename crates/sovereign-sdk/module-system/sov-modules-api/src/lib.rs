@@ -35,14 +35,16 @@ pub use sov_modules_core::{
     archival_state, runtime, AccessoryWorkingSet, Address, AddressBech32, CallResponse, Context,
     DispatchCall, EncodeCall, Genesis, KernelModule, KernelWorkingSet, Module,
     ModuleCallJsonSchema, ModuleError, ModuleError as Error, ModuleInfo, ModulePrefix, PublicKey,
-    Signature, Spec, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet, WorkingSet,
+    Signature, Spec, StateAccessCounts, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet,
+    WorkingSet,
 };
 pub use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 pub use sov_rollup_interface::services::da::SlotData;
 pub use sov_rollup_interface::soft_confirmation::{
-    SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+    SignatureScheme, SignedSoftConfirmationBatch, SoftConfirmationTimestampError,
+    UnsignedSoftConfirmationBatch, MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS,
 };
-pub use sov_rollup_interface::stf::Event;
+pub use sov_rollup_interface::stf::{Event, SoftConfirmationEnforcement, SoftConfirmationOutcome};
 pub use sov_rollup_interface::zk::{
     StateTransition, ValidityCondition, ValidityConditionChecker, Zkvm,
 };
@@ -57,7 +59,9 @@ pub mod optimistic {
 }
 
 pub mod da {
-    pub use sov_rollup_interface::da::{BlockHeaderTrait, NanoSeconds, Time};
+    pub use sov_rollup_interface::da::{
+        BlockHeaderTrait, LightClientError, LightClientHeader, LinkedChainTip, NanoSeconds, Time,
+    };
 }
 
 pub mod storage {
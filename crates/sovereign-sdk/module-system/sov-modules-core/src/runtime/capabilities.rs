@@ -6,7 +6,8 @@
 //! and write a state transition function from scratch.
 //! [See here for docs](https://github.com/Sovereign-Labs/sovereign-sdk/blob/nightly/examples/demo-stf/README.md)
 
-use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+use sov_rollup_interface::da::{BlobReaderTrait, DaSpec, LightClientError};
+use sov_rollup_interface::soft_confirmation::{SignatureScheme, SoftConfirmationTimestampError};
 
 use crate::{Context, KernelWorkingSet, Spec, Storage, WorkingSet};
 
@@ -47,6 +48,78 @@ pub trait KernelSlotHooks<C: Context, Da: DaSpec>: Kernel<C, Da> {
     );
     /// Called at the end of a slot
     fn end_slot_hook(&self, working_set: &mut WorkingSet<Self::Context>);
+
+    /// Called at the beginning of a soft confirmation, with the DA block it builds on.
+    /// Returns an error if the kernel rejects the DA block.
+    fn begin_soft_batch_hook(
+        &self,
+        _slot_header: &Da::BlockHeader,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> Result<(), LightClientError> {
+        Ok(())
+    }
+
+    /// Called at the beginning of a soft confirmation, after `begin_soft_batch_hook`, with
+    /// the timestamp of the soft confirmation, if it carries one.
+    /// Returns an error if the kernel rejects the timestamp.
+    fn check_soft_batch_timestamp(
+        &self,
+        _slot_header: &Da::BlockHeader,
+        _timestamp: Option<u64>,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> Result<(), SoftConfirmationTimestampError> {
+        Ok(())
+    }
+
+    /// Returns whether the soft confirmations given for the DA block at `da_slot_height`
+    /// carry a timestamp.
+    fn soft_batch_timestamped(
+        &self,
+        _da_slot_height: u64,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> bool {
+        false
+    }
+
+    /// Returns whether the soft confirmations given for the DA block at `da_slot_height`
+    /// carry an ordering commitment.
+    fn soft_batch_ordering_committed(
+        &self,
+        _da_slot_height: u64,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> bool {
+        false
+    }
+
+    /// Returns the signature scheme of the soft confirmations given for the DA block at
+    /// `da_slot_height`.
+    fn soft_batch_signature_scheme(
+        &self,
+        _da_slot_height: u64,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
+
+    /// Counts the soft batch being applied, before its transactions.
+    fn count_soft_batch(&self, _working_set: &mut WorkingSet<Self::Context>) {}
+
+    /// Returns the L2 height of the soft confirmation being applied, once it was counted by
+    /// `count_soft_batch`.
+    /// Kernels which don't count the soft confirmations return 0.
+    fn soft_batch_height(&self, _working_set: &mut WorkingSet<Self::Context>) -> u64 {
+        0
+    }
+
+    /// Returns whether blobs sent by `sender` on the DA layer come from the registered
+    /// sequencer, and may be read as its commitments and proofs.
+    fn is_sequencer_da_sender(
+        &self,
+        _sender: &Da::Address,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> bool {
+        true
+    }
 }
 
 /// BlobSelector decides which blobs to process in a current slot.
@@ -31,6 +31,18 @@ pub struct CacheValue {
     pub value: RefCount<Vec<u8>>,
 }
 
+impl CacheValue {
+    /// Returns the length of the underlying value, in bytes.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Returns `true` if the underlying value is empty.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
 impl fmt::Display for CacheValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // TODO revisit how we display values
@@ -64,6 +76,17 @@ impl Access {
         }
     }
 
+    /// Returns the number of bytes held by the values tracked in this access.
+    pub fn values_size(&self) -> usize {
+        let value_size = |value: &Option<CacheValue>| value.as_ref().map_or(0, CacheValue::len);
+        match self {
+            Access::Read(value) | Access::Write(value) => value_size(value),
+            Access::ReadThenWrite { original, modified } => {
+                value_size(original) + value_size(modified)
+            }
+        }
+    }
+
     pub fn write_value(&mut self, new_value: Option<CacheValue>) {
         match self {
             // If we've already read this slot, turn it into a readThenWrite access
@@ -356,6 +379,14 @@ impl CacheLog {
     pub fn is_empty(&self) -> bool {
         self.log.is_empty()
     }
+
+    /// Returns an estimate of the memory held by the keys and values of the cache, in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.log
+            .iter()
+            .map(|(key, access)| key.key.len() + access.values_size())
+            .sum()
+    }
 }
 
 /// Caches reads and writes for a (key, value) pair. On the first read the value is fetched
@@ -422,6 +453,11 @@ impl StorageInternalCache {
         self.tx_cache.add_write(cache_key, None);
     }
 
+    /// Returns an estimate of the memory held by the cached reads and writes, in bytes.
+    pub fn estimated_size(&self) -> usize {
+        self.tx_cache.estimated_size()
+    }
+
     fn get_value_from_cache(&self, cache_key: &CacheKey) -> ValueExists {
         self.tx_cache.get_value(cache_key)
     }
@@ -528,6 +564,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cache_log_estimated_size() {
+        let mut cache_log = CacheLog::default();
+        assert_eq!(cache_log.estimated_size(), 0);
+
+        cache_log.add_read(create_key(1), create_value(2)).unwrap();
+        cache_log.add_read(create_key(2), None).unwrap();
+        assert_eq!(cache_log.estimated_size(), 3);
+
+        // Both the original and the modified values of a slot are held
+        cache_log.add_write(create_key(1), create_value(3));
+        assert_eq!(cache_log.estimated_size(), 4);
+    }
+
     #[derive(PartialEq, Eq, Clone, Debug)]
     pub(crate) struct CacheEntry {
         key: CacheKey,
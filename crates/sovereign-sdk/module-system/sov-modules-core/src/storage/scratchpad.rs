@@ -148,12 +148,33 @@ pub trait StateReaderAndWriter {
     }
 }
 
+/// Number of reads and writes of the provable state made through a [`WorkingSet`], including
+/// the reverted ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateAccessCounts {
+    /// Number of values read
+    pub reads: u64,
+    /// Number of values set or deleted
+    pub writes: u64,
+}
+
+impl StateAccessCounts {
+    /// Returns the accesses made since `earlier` was taken.
+    pub fn since(&self, earlier: StateAccessCounts) -> StateAccessCounts {
+        StateAccessCounts {
+            reads: self.reads.saturating_sub(earlier.reads),
+            writes: self.writes.saturating_sub(earlier.writes),
+        }
+    }
+}
+
 /// A working set accumulates reads and writes on top of the underlying DB,
 /// automating witness creation.
 pub struct Delta<S: Storage> {
     inner: S,
     witness: S::Witness,
     cache: StorageInternalCache,
+    access_counts: StateAccessCounts,
 }
 
 impl<S: Storage> Delta<S> {
@@ -169,6 +190,7 @@ impl<S: Storage> Delta<S> {
                 None => Default::default(),
                 Some(v) => StorageInternalCache::new_with_version(v),
             },
+            access_counts: Default::default(),
         }
     }
 
@@ -178,6 +200,10 @@ impl<S: Storage> Delta<S> {
 
         (cache.into(), witness)
     }
+
+    fn estimated_size(&self) -> usize {
+        self.cache.estimated_size()
+    }
 }
 
 impl<S: Storage> fmt::Debug for Delta<S> {
@@ -238,6 +264,18 @@ impl<S: Storage> AccessoryDelta<S> {
 
         reads_and_writes
     }
+
+    fn estimated_size(&self) -> usize {
+        writes_size(&self.writes.cache)
+    }
+}
+
+/// Estimates the memory held by a set of pending writes, in bytes.
+fn writes_size(writes: &HashMap<CacheKey, Option<CacheValue>>) -> usize {
+    writes
+        .iter()
+        .map(|(key, value)| key.key.len() + value.as_ref().map_or(0, CacheValue::len))
+        .sum()
 }
 
 impl<S: Storage> StateReaderAndWriter for AccessoryDelta<S> {
@@ -295,6 +333,11 @@ impl<C: Context> StateCheckpoint<C> {
         }
     }
 
+    /// Returns the number of reads and writes made through the working sets of this checkpoint.
+    pub fn state_access_counts(&self) -> StateAccessCounts {
+        self.delta.access_counts
+    }
+
     /// Transforms this [`StateCheckpoint`] back into a [`WorkingSet`].
     pub fn to_revertable(self) -> WorkingSet<C> {
         WorkingSet {
@@ -432,6 +475,21 @@ impl<C: Context> WorkingSet<C> {
         }
     }
 
+    /// Returns an estimate of the memory held by the reads and writes accumulated
+    /// in this [`WorkingSet`], in bytes. Events and the witness are not accounted for.
+    pub fn estimated_size(&self) -> usize {
+        self.delta.inner.estimated_size()
+            + writes_size(&self.delta.writes)
+            + self.accessory_delta.inner.estimated_size()
+            + writes_size(&self.accessory_delta.writes)
+    }
+
+    /// Returns the number of reads and writes of the provable state made through this
+    /// [`WorkingSet`] and the ones it was checkpointed from.
+    pub fn state_access_counts(&self) -> StateAccessCounts {
+        self.delta.inner.access_counts
+    }
+
     /// Adds an event to the working set.
     pub fn add_event(&mut self, key: &str, value: &str) {
         self.events.push(Event::new(key, value));
@@ -463,6 +521,7 @@ impl<C: Context> WorkingSet<C> {
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        self.delta.inner.access_counts.reads += 1;
         match &mut self.archival_working_set {
             None => self.delta.get(key),
             Some(ref mut archival_working_set) => archival_working_set.get(key),
@@ -470,6 +529,7 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
+        self.delta.inner.access_counts.writes += 1;
         match &mut self.archival_working_set {
             None => self.delta.set(key, value),
             Some(ref mut archival_working_set) => archival_working_set.set(key, value),
@@ -477,6 +537,7 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 
     fn delete(&mut self, key: &StorageKey) {
+        self.delta.inner.access_counts.writes += 1;
         match &mut self.archival_working_set {
             None => self.delta.delete(key),
             Some(ref mut archival_working_set) => archival_working_set.delete(key),
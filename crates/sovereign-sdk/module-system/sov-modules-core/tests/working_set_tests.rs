@@ -2,7 +2,8 @@ use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_core::capabilities::mocks::MockKernel;
 use sov_modules_core::{
-    Address, Context, KernelWorkingSet, StateReaderAndWriter, StorageKey, StorageValue, WorkingSet,
+    Address, Context, KernelWorkingSet, StateAccessCounts, StateReaderAndWriter, StorageKey,
+    StorageValue, WorkingSet,
 };
 use sov_prover_storage_manager::new_orphan_storage;
 use sov_state::codec::BcsCodec;
@@ -59,3 +60,69 @@ fn test_kernel_workingset_get() {
 
     assert_eq!(Some(storage_value), working_set.get(&storage_key));
 }
+
+#[test]
+fn test_workingset_state_access_counts() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let codec = BcsCodec {};
+    let storage = new_orphan_storage(tempdir.path()).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let storage_key = StorageKey::new(&prefix, &vec![4, 5, 6], &codec);
+    let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
+    working_set.set(&storage_key, storage_value.clone());
+    working_set.get(&storage_key);
+    let before = working_set.state_access_counts();
+
+    // Counts are kept across checkpoints and reverts
+    let mut working_set = working_set.checkpoint().to_revertable();
+    working_set.get(&storage_key);
+    working_set.delete(&storage_key);
+    let checkpoint = working_set.revert();
+
+    assert_eq!(
+        before,
+        StateAccessCounts {
+            reads: 1,
+            writes: 1
+        }
+    );
+    assert_eq!(
+        checkpoint.state_access_counts().since(before),
+        StateAccessCounts {
+            reads: 1,
+            writes: 1
+        }
+    );
+}
+
+#[test]
+fn test_workingset_estimated_size() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let codec = BcsCodec {};
+    let storage = new_orphan_storage(tempdir.path()).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let storage_key = StorageKey::new(&prefix, &vec![4, 5, 6], &codec);
+    let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
+    let entry_size = storage_key.key().len() + storage_value.value().len();
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
+    assert_eq!(working_set.estimated_size(), 0);
+    working_set.set(&storage_key, storage_value.clone());
+    assert_eq!(working_set.estimated_size(), entry_size);
+
+    // Checkpointed writes are still held, and accessory writes are accounted for
+    let mut working_set = working_set.checkpoint().to_revertable();
+    assert_eq!(working_set.estimated_size(), entry_size);
+    working_set
+        .accessory_state()
+        .set(&storage_key, storage_value.clone());
+    assert_eq!(working_set.estimated_size(), 2 * entry_size);
+
+    // Reverted writes are released
+    let working_set = working_set.revert().to_revertable();
+    assert_eq!(working_set.estimated_size(), entry_size);
+}
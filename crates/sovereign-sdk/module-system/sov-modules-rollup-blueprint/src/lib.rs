@@ -3,24 +3,27 @@
 
 mod runtime_rpc;
 mod wallet;
+use std::collections::HashMap;
 use std::net::SocketAddr;
 
 use async_trait::async_trait;
-use citrea_sequencer::{CitreaSequencer, SequencerConfig};
+use citrea_sequencer::{CitreaSequencer, FileLease, SequencerConfig};
 use const_rollup_config::TEST_PRIVATE_KEY;
 pub use runtime_rpc::*;
 use sequencer_client::SequencerClient;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::recovery::recover_ledger_and_state;
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{Context, DaSpec, Spec};
 use sov_modules_stf_blueprint::{GenesisParams, Runtime as RuntimeTrait, StfBlueprint};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
-use sov_rollup_interface::zk::ZkvmHost;
+use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{
-    InitVariant, ProverService, RollupConfig, RollupProverConfig, StateTransitionRunner,
+    checkpoint_status_rpc, prover_stats_rpc, spawn_telemetry, InitVariant, NodeMode, ProverService,
+    RollupConfig, RollupProverConfig, StateTransitionRunner,
 };
 use tokio::sync::oneshot;
 pub use wallet::*;
@@ -106,6 +109,19 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         })
     }
 
+    /// Returns the chain id of the genesis config, recorded in the ledger database so that a
+    /// node refuses to start on the database of another chain.
+    fn chain_id(
+        &self,
+        rt_genesis: &<Self::NativeRuntime as RuntimeTrait<Self::NativeContext, Self::DaSpec>>::GenesisConfig,
+    ) -> u64;
+
+    /// Returns the code commitments of the guests whose proofs posted to DA by any prover are
+    /// accepted, by guest version. No proof is accepted by default.
+    fn proof_code_commitments(&self) -> HashMap<u32, <Self::Vm as Zkvm>::CodeCommitment> {
+        HashMap::new()
+    }
+
     /// Creates instance of [`DaService`].
     async fn create_da_service(
         &self,
@@ -160,6 +176,13 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             &rollup_config,
         )?;
 
+        let unwound_soft_batches =
+            recover_ledger_and_state(&ledger_db, &rollup_config.storage.path)?;
+
+        if let Some(telemetry) = rollup_config.telemetry.clone() {
+            spawn_telemetry(telemetry, NodeMode::Sequencer, ledger_db.clone());
+        }
+
         let mut storage_manager = self.create_storage_manager(&rollup_config)?;
         let prover_storage = storage_manager.create_finalized_storage()?;
 
@@ -176,6 +199,11 @@ pub trait RollupBlueprint: Sized + Send + Sync {
 
         let genesis_root = prover_storage.get_root_hash(0);
 
+        ledger_db.check_chain_identity(
+            self.chain_id(&genesis_config.runtime),
+            genesis_root.as_ref().ok().map(AsRef::as_ref),
+        )?;
+
         let init_variant = match prev_root {
             Some(root_hash) => InitVariant::Initialized(root_hash),
             None => match genesis_root {
@@ -187,6 +215,19 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             },
         };
 
+        // The standby and the primary use separate storage, so the default lease path
+        // would not be shared between them
+        anyhow::ensure!(
+            sequencer_config.standby.is_none() || sequencer_config.lease.path.is_some(),
+            "Standby sequencer requires `lease.path` to point to the lease of the primary"
+        );
+
+        let lease_path = sequencer_config
+            .lease
+            .path
+            .clone()
+            .unwrap_or_else(|| rollup_config.storage.path.join("sequencer.lock"));
+
         let seq =
             CitreaSequencer::new(
                 da_service,
@@ -202,8 +243,12 @@ pub trait RollupBlueprint: Sized + Send + Sync {
                 rollup_config.sequencer_public_key,
                 ledger_db,
                 rollup_config.runner,
-            )
-            .unwrap();
+                Box::new(FileLease::new(lease_path)),
+            )?;
+
+        // Transactions of the unwound soft batches would be lost otherwise
+        seq.resubmit_unwound_soft_batches(unwound_soft_batches)
+            .await;
 
         Ok(Sequencer {
             runner: seq,
@@ -248,6 +293,18 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             &rollup_config,
         )?;
 
+        // Full nodes receive the unwound soft batches again from the sequencer
+        recover_ledger_and_state(&ledger_db, &rollup_config.storage.path)?;
+
+        if let Some(telemetry) = rollup_config.telemetry.clone() {
+            let mode = if is_prover {
+                NodeMode::Prover
+            } else {
+                NodeMode::FullNode
+            };
+            spawn_telemetry(telemetry, mode, ledger_db.clone());
+        }
+
         let mut storage_manager = self.create_storage_manager(&rollup_config)?;
         let prover_storage = storage_manager.create_finalized_storage()?;
 
@@ -262,17 +319,32 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             .map(|s| SequencerClient::new(s.url));
 
         // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218)
-        let rpc_methods = self.create_rpc_methods(
+        let mut rpc_methods = self.create_rpc_methods(
             &prover_storage,
             &ledger_db,
             &da_service,
             sequencer_client.clone(),
         )?;
+        if is_prover {
+            rpc_methods.merge(prover_stats_rpc())?;
+        }
+        if let Some(client) = &sequencer_client {
+            rpc_methods.merge(checkpoint_status_rpc::<Self::NativeContext>(
+                ledger_db.clone(),
+                client.clone(),
+                rollup_config.sequencer_public_key.clone(),
+            ))?;
+        }
 
         let native_stf = StfBlueprint::new();
 
         let genesis_root = prover_storage.get_root_hash(0);
 
+        ledger_db.check_chain_identity(
+            self.chain_id(&genesis_config.runtime),
+            genesis_root.as_ref().ok().map(AsRef::as_ref),
+        )?;
+
         let init_variant = match prev_root {
             Some(root_hash) => InitVariant::Initialized(root_hash),
             None => match genesis_root {
@@ -294,7 +366,9 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             prover_service,
             sequencer_client,
             rollup_config.sequencer_public_key,
+            rollup_config.sequencer_schnorr_public_key,
             rollup_config.include_tx_body,
+            self.proof_code_commitments(),
         )?;
 
         Ok(Rollup {
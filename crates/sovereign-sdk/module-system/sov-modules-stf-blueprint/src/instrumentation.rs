@@ -0,0 +1,138 @@
+//! Timing of the phases of applying a soft confirmation and of the module hooks they call,
+//! exported as tracing spans and prometheus metrics to find which hook slows block production.
+//!
+//! Everything is a no-op without the `native` feature, so that proving is not slowed down.
+
+use sov_modules_api::{Context, StateAccessCounts, WorkingSet};
+
+#[cfg(feature = "native")]
+fn record(scope: &'static str, elapsed: std::time::Duration, accesses: StateAccessCounts) {
+    use crate::metrics::STF_STATE_ACCESSES;
+
+    tracing::debug!(
+        scope,
+        elapsed_us = elapsed.as_micros() as u64,
+        reads = accesses.reads,
+        writes = accesses.writes,
+        "STF step done"
+    );
+    STF_STATE_ACCESSES
+        .with_label_values(&[scope, "read"])
+        .inc_by(accesses.reads);
+    STF_STATE_ACCESSES
+        .with_label_values(&[scope, "write"])
+        .inc_by(accesses.writes);
+}
+
+/// Runs `f`, a module hook or a step of a transaction named `hook`, and records its duration
+/// and the state reads and writes it made through `working_set`.
+#[cfg(feature = "native")]
+pub fn time_hook<C: Context, T>(
+    hook: &'static str,
+    working_set: &mut WorkingSet<C>,
+    f: impl FnOnce(&mut WorkingSet<C>) -> T,
+) -> T {
+    let _span = tracing::debug_span!("stf_hook", hook).entered();
+    let accesses_before = working_set.state_access_counts();
+    let start = std::time::Instant::now();
+
+    let result = f(working_set);
+
+    let elapsed = start.elapsed();
+    crate::metrics::STF_HOOK_DURATION_SECONDS
+        .with_label_values(&[hook])
+        .observe(elapsed.as_secs_f64());
+    record(
+        hook,
+        elapsed,
+        working_set.state_access_counts().since(accesses_before),
+    );
+    result
+}
+
+/// Runs `f`, a module hook or a step of a transaction named `hook`.
+#[cfg(not(feature = "native"))]
+pub fn time_hook<C: Context, T>(
+    _hook: &'static str,
+    working_set: &mut WorkingSet<C>,
+    f: impl FnOnce(&mut WorkingSet<C>) -> T,
+) -> T {
+    f(working_set)
+}
+
+/// Measures a phase of applying a soft confirmation, from its creation to [`PhaseTimer::finish`].
+pub(crate) struct PhaseTimer {
+    #[cfg(feature = "native")]
+    phase: &'static str,
+    #[cfg(feature = "native")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "native")]
+    start: std::time::Instant,
+    #[cfg(feature = "native")]
+    accesses_before: StateAccessCounts,
+}
+
+impl PhaseTimer {
+    /// Starts measuring `phase`, given the state access counts of its working set.
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    pub(crate) fn start(phase: &'static str, accesses_before: StateAccessCounts) -> Self {
+        Self {
+            #[cfg(feature = "native")]
+            phase,
+            #[cfg(feature = "native")]
+            span: tracing::debug_span!("stf_phase", phase).entered(),
+            #[cfg(feature = "native")]
+            start: std::time::Instant::now(),
+            #[cfg(feature = "native")]
+            accesses_before,
+        }
+    }
+
+    /// Records the phase, given the state access counts of its working set at its end.
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    pub(crate) fn finish(self, accesses_after: StateAccessCounts) {
+        #[cfg(feature = "native")]
+        {
+            let elapsed = self.start.elapsed();
+            crate::metrics::STF_PHASE_DURATION_SECONDS
+                .with_label_values(&[self.phase])
+                .observe(elapsed.as_secs_f64());
+            record(
+                self.phase,
+                elapsed,
+                accesses_after.since(self.accesses_before),
+            );
+            drop(self.span);
+        }
+    }
+}
+
+/// Measures the application of a transaction, hooks included.
+pub(crate) struct TxTimer {
+    #[cfg(feature = "native")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "native")]
+    start: std::time::Instant,
+}
+
+impl TxTimer {
+    /// Starts measuring the transaction with hash `tx_hash`.
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    pub(crate) fn start(tx_hash: &[u8; 32]) -> Self {
+        Self {
+            #[cfg(feature = "native")]
+            span: tracing::debug_span!("stf_tx", tx_hash = %hex::encode(tx_hash)).entered(),
+            #[cfg(feature = "native")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Records the duration of the transaction.
+    pub(crate) fn finish(self) {
+        #[cfg(feature = "native")]
+        {
+            crate::metrics::STF_TX_DURATION_SECONDS.observe(self.start.elapsed().as_secs_f64());
+            drop(self.span);
+        }
+    }
+}
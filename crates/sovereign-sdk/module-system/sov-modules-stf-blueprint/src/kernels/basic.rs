@@ -2,11 +2,15 @@
 use std::path::PathBuf;
 
 use sov_blob_storage::BlobStorage;
-use sov_chain_state::ChainState;
+use sov_chain_state::{ChainState, DaChainTip};
+use sov_modules_api::da::LightClientError;
 use sov_modules_api::runtime::capabilities::{
     BlobRefOrOwned, BlobSelector, Kernel, KernelSlotHooks,
 };
-use sov_modules_api::{Context, DaSpec, KernelModule, WorkingSet};
+use sov_modules_api::{
+    BlobReaderTrait, Context, DaSpec, KernelModule, SignatureScheme,
+    SoftConfirmationTimestampError, WorkingSet,
+};
 use sov_state::Storage;
 
 /// The simplest imaginable kernel. It does not do any batching or reordering of blobs.
@@ -36,6 +40,8 @@ pub struct BasicKernelGenesisPaths {
 pub struct BasicKernelGenesisConfig<C: Context, Da: DaSpec> {
     /// The chain state genesis config
     pub chain_state: <ChainState<C, Da> as KernelModule>::Config,
+    /// The trusted DA block the DA light client starts from, if enabled
+    pub da_light_client: Option<DaChainTip<Da>>,
 }
 
 impl<C: Context, Da: DaSpec> Kernel<C, Da> for BasicKernel<C, Da> {
@@ -56,7 +62,11 @@ impl<C: Context, Da: DaSpec> Kernel<C, Da> for BasicKernel<C, Da> {
         config: &Self::GenesisConfig,
         working_set: &mut WorkingSet<C>,
     ) -> Result<(), anyhow::Error> {
-        Ok(self.chain_state.genesis(&config.chain_state, working_set)?)
+        self.chain_state.genesis(&config.chain_state, working_set)?;
+        if let Some(tip) = &config.da_light_client {
+            self.chain_state.init_da_light_client(tip, working_set);
+        }
+        Ok(())
     }
 }
 
@@ -66,13 +76,21 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BasicKernel<C, Da> {
     fn get_blobs_for_this_slot<'a, 'k, I>(
         &self,
         current_blobs: I,
-        _working_set: &mut sov_modules_api::KernelWorkingSet<'k, Self::Context>,
+        working_set: &mut sov_modules_api::KernelWorkingSet<'k, Self::Context>,
     ) -> anyhow::Result<Vec<BlobRefOrOwned<'a, Da::BlobTransaction>>>
     where
         I: IntoIterator<Item = &'a mut Da::BlobTransaction>,
     {
+        // Blobs posted by anyone but the registered sequencer are never read
+        let current_blobs: Vec<_> = current_blobs
+            .into_iter()
+            .filter(|blob| {
+                self.chain_state
+                    .is_sequencer_da_sender(&blob.sender(), working_set.inner)
+            })
+            .collect();
         self.blob_storage
-            .get_blobs_for_this_slot(current_blobs, _working_set)
+            .get_blobs_for_this_slot(current_blobs, working_set)
     }
 }
 
@@ -93,4 +111,68 @@ impl<C: Context, Da: DaSpec> KernelSlotHooks<C, Da> for BasicKernel<C, Da> {
         let mut ws = sov_modules_api::KernelWorkingSet::from_kernel(self, working_set);
         self.chain_state.end_slot_hook(&mut ws);
     }
+
+    fn begin_soft_batch_hook(
+        &self,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> Result<(), LightClientError> {
+        self.chain_state.extend_da_chain(slot_header, working_set)
+    }
+
+    fn check_soft_batch_timestamp(
+        &self,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        timestamp: Option<u64>,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> Result<(), SoftConfirmationTimestampError> {
+        self.chain_state
+            .check_soft_batch_timestamp(slot_header, timestamp, working_set)
+    }
+
+    fn soft_batch_timestamped(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> bool {
+        self.chain_state
+            .soft_batch_timestamped(da_slot_height, working_set)
+    }
+
+    fn soft_batch_ordering_committed(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> bool {
+        self.chain_state
+            .soft_batch_ordering_committed(da_slot_height, working_set)
+    }
+
+    fn soft_batch_signature_scheme(
+        &self,
+        da_slot_height: u64,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> SignatureScheme {
+        self.chain_state
+            .signature_scheme(da_slot_height, working_set)
+    }
+
+    fn count_soft_batch(&self, working_set: &mut sov_modules_api::WorkingSet<Self::Context>) {
+        self.chain_state.count_soft_batch(working_set)
+    }
+
+    fn soft_batch_height(
+        &self,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> u64 {
+        self.chain_state.get_soft_batch_count(working_set)
+    }
+
+    fn is_sequencer_da_sender(
+        &self,
+        sender: &<Da as DaSpec>::Address,
+        working_set: &mut sov_modules_api::WorkingSet<Self::Context>,
+    ) -> bool {
+        self.chain_state.is_sequencer_da_sender(sender, working_set)
+    }
 }
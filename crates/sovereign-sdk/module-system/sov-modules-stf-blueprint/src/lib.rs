@@ -2,7 +2,10 @@
 #![doc = include_str!("../README.md")]
 
 mod batch;
+mod instrumentation;
 pub mod kernels;
+#[cfg(feature = "native")]
+mod metrics;
 mod stf_blueprint;
 mod tx_verifier;
 
@@ -10,6 +13,9 @@ use std::marker::PhantomData;
 
 pub use batch::Batch;
 use borsh::BorshSerialize;
+pub use instrumentation::time_hook;
+use secp256k1::{schnorr, Message, XOnlyPublicKey, SECP256K1};
+use sha2::Digest;
 use sov_modules_api::da::BlockHeaderTrait;
 use sov_modules_api::hooks::{
     ApplyBlobHooks, ApplySoftConfirmationError, ApplySoftConfirmationHooks, FinalizeHook,
@@ -17,10 +23,12 @@ use sov_modules_api::hooks::{
 };
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{
-    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, Signature, Spec,
-    StateCheckpoint, UnsignedSoftConfirmationBatch, WorkingSet, Zkvm,
+    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, Signature,
+    SoftConfirmationOutcome, Spec, StateCheckpoint, UnsignedSoftConfirmationBatch, WorkingSet,
+    Zkvm,
 };
-use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use sov_rollup_interface::rpc::SignedCheckpoint;
+use sov_rollup_interface::soft_confirmation::{SignatureScheme, SignedSoftConfirmationBatch};
 pub use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
 use sov_state::storage::KernelWorkingSet;
@@ -28,14 +36,15 @@ use sov_state::Storage;
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 pub use stf_blueprint::StfBlueprint;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 pub use tx_verifier::RawTx;
 
 /// The tx hook for a blueprint runtime
 pub struct RuntimeTxHook<C: Context> {
-    /// Height to initialize the context
+    /// Height to initialize the context, the L2 height of the soft confirmation for its
+    /// transactions
     pub height: u64,
-    /// Sequencer public key
+    /// Sequencer public key, the key which signed the soft confirmation for its transactions
     pub sequencer: C::PublicKey,
 }
 
@@ -49,13 +58,8 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     + TxHooks<Context = C, PreArg = RuntimeTxHook<C>, PreResult = C>
     + SlotHooks<Da, Context = C>
     + FinalizeHook<Da, Context = C>
-    + ApplySoftConfirmationHooks<
-        Da,
-        Context = C,
-        SoftConfirmationResult = SequencerOutcome<
-            <<Da as DaSpec>::BlobTransaction as BlobReaderTrait>::Address,
-        >,
-    > + ApplyBlobHooks<
+    + ApplySoftConfirmationHooks<Da, Context = C, SoftConfirmationResult = SoftConfirmationOutcome>
+    + ApplyBlobHooks<
         Da::BlobTransaction,
         Context = C,
         BlobResult = SequencerOutcome<
@@ -141,13 +145,42 @@ pub trait StfBlueprintTrait<C: Context, Da: DaSpec, Vm: Zkvm>:
         soft_batch: &mut SignedSoftConfirmationBatch,
     ) -> (Result<(), ApplySoftConfirmationError>, WorkingSet<C>);
 
-    /// Apply soft batch transactions
+    /// Apply soft batch transactions, given the public key of the sequencer of the soft batch.
+    /// Can be called several times between `begin_soft_batch` and `end_soft_batch` with
+    /// consecutive chunks of the transactions, which gives the same state and receipts,
+    /// once concatenated, as applying them all at once.
     fn apply_soft_batch_txs(
         &self,
+        sequencer_public_key: &[u8],
         txs: Vec<Vec<u8>>,
         batch_workspace: WorkingSet<C>,
     ) -> (WorkingSet<C>, Vec<TransactionReceipt<TxEffect>>);
 
+    /// Returns the signature scheme of the soft batches given for the DA block at
+    /// `da_slot_height`, as activated in `pre_state`
+    fn soft_batch_signature_scheme(
+        &self,
+        pre_state: Self::PreState,
+        da_slot_height: u64,
+    ) -> SignatureScheme;
+
+    /// Returns whether the soft batches given for the DA block at `da_slot_height` carry a
+    /// timestamp, as activated in `pre_state`
+    fn soft_batch_timestamped(&self, pre_state: Self::PreState, da_slot_height: u64) -> bool;
+
+    /// Returns whether the soft batches given for the DA block at `da_slot_height` carry an
+    /// ordering commitment, as activated in `pre_state`
+    fn soft_batch_ordering_committed(&self, pre_state: Self::PreState, da_slot_height: u64)
+        -> bool;
+
+    /// Returns whether blobs sent by `sender` on the DA layer come from the sequencer
+    /// registered in `pre_state`
+    fn is_sequencer_da_sender(
+        &self,
+        pre_state: Self::PreState,
+        sender: &<Da as DaSpec>::Address,
+    ) -> bool;
+
     /// End a soft batch
     fn end_soft_batch(
         &self,
@@ -171,6 +204,43 @@ pub trait StfBlueprintTrait<C: Context, Da: DaSpec, Vm: Zkvm>:
         Self::TxReceiptContents,
         Self::Witness,
     >;
+
+    /// Applies a soft batch like [`StateTransitionFunction::apply_soft_batch`], when its
+    /// signature was already verified with `verified_scheme`, as the full node does while
+    /// fetching soft batches. Instead of verifying the signature again, only checks that
+    /// `verified_scheme` is the scheme active at the DA height of the soft batch.
+    #[allow(clippy::type_complexity)]
+    #[allow(clippy::too_many_arguments)]
+    fn apply_verified_soft_batch(
+        &self,
+        sequencer_public_key: &[u8],
+        pre_state_root: &Self::StateRoot,
+        pre_state: Self::PreState,
+        witness: Self::Witness,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        validity_condition: &<Da as DaSpec>::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+        verified_scheme: SignatureScheme,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    >;
+
+    /// Applies a candidate soft batch on a throwaway checkpoint of `pre_state` and returns
+    /// its receipt, with the gas used and fees in its outcome. Nothing is persisted.
+    /// The signature of the soft batch is not checked, so that a block can be validated
+    /// before it is signed.
+    fn dry_run_soft_batch(
+        &self,
+        sequencer_public_key: &[u8],
+        pre_state_root: &Self::StateRoot,
+        pre_state: Self::PreState,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> Result<BatchReceipt<(), TxEffect>, ApplySoftConfirmationError>;
 }
 
 impl<C, RT, Vm, Da, K> StfBlueprintTrait<C, Da, Vm> for StfBlueprint<C, Da, Vm, RT, K>
@@ -215,15 +285,87 @@ where
 
         let checkpoint = StateCheckpoint::with_witness(pre_state, witness);
 
-        self.begin_soft_confirmation_inner(checkpoint, soft_batch)
+        // then extend the DA header chain of the kernel
+        let mut working_set = checkpoint.to_revertable();
+        if let Err(error) = self
+            .kernel
+            .begin_soft_batch_hook(slot_header, &mut working_set)
+        {
+            error!(
+                "Error: The DA block of the soft batch was rejected by the kernel: {}",
+                error
+            );
+            return (
+                Err(ApplySoftConfirmationError::InvalidDaBlock {
+                    height: slot_header.height(),
+                    error,
+                }),
+                // Reverted in apply_soft_batch and sequencer
+                working_set,
+            );
+        }
+
+        // then check the timestamp against the previous soft batch and the DA block
+        let timestamp = soft_batch.timestamp();
+        if let Err(error) =
+            self.kernel
+                .check_soft_batch_timestamp(slot_header, timestamp, &mut working_set)
+        {
+            error!(
+                "Error: The timestamp {:?} of the soft batch was rejected by the kernel: {}",
+                timestamp, error
+            );
+            return (
+                Err(ApplySoftConfirmationError::InvalidTimestamp { timestamp, error }),
+                // Reverted in apply_soft_batch and sequencer
+                working_set,
+            );
+        }
+
+        // The transactions of the soft batch see its L2 height
+        self.kernel.count_soft_batch(&mut working_set);
+
+        self.begin_soft_confirmation_inner(working_set.checkpoint(), soft_batch)
     }
 
     fn apply_soft_batch_txs(
         &self,
+        sequencer_public_key: &[u8],
         txs: Vec<Vec<u8>>,
         batch_workspace: WorkingSet<C>,
     ) -> (WorkingSet<C>, Vec<TransactionReceipt<TxEffect>>) {
-        self.apply_sov_txs_inner(txs, batch_workspace)
+        self.apply_sov_txs_inner(sequencer_public_key, txs, batch_workspace)
+    }
+
+    fn soft_batch_signature_scheme(
+        &self,
+        pre_state: <C>::Storage,
+        da_slot_height: u64,
+    ) -> SignatureScheme {
+        let mut working_set = WorkingSet::new(pre_state);
+        self.kernel
+            .soft_batch_signature_scheme(da_slot_height, &mut working_set)
+    }
+
+    fn soft_batch_timestamped(&self, pre_state: <C>::Storage, da_slot_height: u64) -> bool {
+        let mut working_set = WorkingSet::new(pre_state);
+        self.kernel
+            .soft_batch_timestamped(da_slot_height, &mut working_set)
+    }
+
+    fn soft_batch_ordering_committed(&self, pre_state: <C>::Storage, da_slot_height: u64) -> bool {
+        let mut working_set = WorkingSet::new(pre_state);
+        self.kernel
+            .soft_batch_ordering_committed(da_slot_height, &mut working_set)
+    }
+
+    fn is_sequencer_da_sender(
+        &self,
+        pre_state: <C>::Storage,
+        sender: &<Da as DaSpec>::Address,
+    ) -> bool {
+        let mut working_set = WorkingSet::new(pre_state);
+        self.kernel.is_sequencer_da_sender(sender, &mut working_set)
     }
 
     fn end_soft_batch(
@@ -233,16 +375,13 @@ where
         tx_receipts: Vec<TransactionReceipt<TxEffect>>,
         batch_workspace: WorkingSet<C>,
     ) -> (BatchReceipt<(), TxEffect>, StateCheckpoint<C>) {
-        // verify signature
-        assert!(
-            verify_soft_batch_signature::<C>(soft_batch, sequencer_public_key).is_ok(),
-            "Signature verification must succeed"
-        );
-
-        let (apply_soft_batch_result, checkpoint) =
-            self.end_soft_confirmation_inner(soft_batch, tx_receipts, batch_workspace);
-
-        (apply_soft_batch_result.unwrap(), checkpoint)
+        self.end_soft_batch_inner(
+            sequencer_public_key,
+            soft_batch,
+            tx_receipts,
+            batch_workspace,
+            None,
+        )
     }
     fn finalize_soft_batch(
         &self,
@@ -307,6 +446,62 @@ where
             witness,
         }
     }
+
+    fn apply_verified_soft_batch(
+        &self,
+        sequencer_public_key: &[u8],
+        pre_state_root: &<C::Storage as Storage>::Root,
+        pre_state: <C>::Storage,
+        witness: <<C as Spec>::Storage as Storage>::Witness,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        _validity_condition: &<Da as DaSpec>::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+        verified_scheme: SignatureScheme,
+    ) -> SlotResult<
+        <C::Storage as Storage>::Root,
+        C::Storage,
+        (),
+        TxEffect,
+        <<C as Spec>::Storage as Storage>::Witness,
+    > {
+        self.apply_soft_batch_inner(
+            sequencer_public_key,
+            pre_state_root,
+            pre_state,
+            witness,
+            slot_header,
+            soft_batch,
+            Some(verified_scheme),
+        )
+    }
+
+    fn dry_run_soft_batch(
+        &self,
+        sequencer_public_key: &[u8],
+        pre_state_root: &<C::Storage as Storage>::Root,
+        pre_state: <C>::Storage,
+        slot_header: &<Da as DaSpec>::BlockHeader,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> Result<BatchReceipt<(), TxEffect>, ApplySoftConfirmationError> {
+        let batch_workspace = match self.begin_soft_batch(
+            sequencer_public_key,
+            pre_state_root,
+            pre_state,
+            Default::default(),
+            slot_header,
+            soft_batch,
+        ) {
+            (Ok(()), batch_workspace) => batch_workspace,
+            (Err(err), _) => return Err(err),
+        };
+        let (batch_workspace, tx_receipts) =
+            self.apply_soft_batch_txs(sequencer_public_key, soft_batch.txs(), batch_workspace);
+
+        // The checkpoint is dropped without being frozen, so the pre state is left untouched
+        let (result, _checkpoint) =
+            self.end_soft_confirmation_inner(soft_batch, tx_receipts, batch_workspace);
+        result
+    }
 }
 
 impl<C, RT, Vm, Da, K> StfBlueprint<C, Da, Vm, RT, K>
@@ -317,6 +512,111 @@ where
     RT: Runtime<C, Da>,
     K: KernelSlotHooks<C, Da>,
 {
+    /// Applies a soft batch. Its signature is verified unless the caller verified it with
+    /// `verified_scheme`.
+    #[allow(clippy::type_complexity)]
+    fn apply_soft_batch_inner(
+        &self,
+        sequencer_public_key: &[u8],
+        pre_state_root: &<C::Storage as Storage>::Root,
+        pre_state: C::Storage,
+        witness: <<C as Spec>::Storage as Storage>::Witness,
+        slot_header: &Da::BlockHeader,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+        verified_scheme: Option<SignatureScheme>,
+    ) -> SlotResult<
+        <C::Storage as Storage>::Root,
+        C::Storage,
+        (),
+        TxEffect,
+        <<C as Spec>::Storage as Storage>::Witness,
+    > {
+        match self.begin_soft_batch(
+            sequencer_public_key,
+            pre_state_root,
+            pre_state.clone(),
+            witness,
+            slot_header,
+            soft_batch,
+        ) {
+            (Ok(()), batch_workspace) => {
+                // TODO: the working set is held in memory until the soft batch is finalized.
+                // Full nodes rely on the sequencer bounding it with its `max_working_set_bytes`,
+                // streaming the commit of larger soft batches to disk is out of scope for now.
+                let (batch_workspace, tx_receipts) = self.apply_soft_batch_txs(
+                    sequencer_public_key,
+                    soft_batch.txs(),
+                    batch_workspace,
+                );
+
+                let (batch_receipt, checkpoint) = self.end_soft_batch_inner(
+                    sequencer_public_key,
+                    soft_batch,
+                    tx_receipts,
+                    batch_workspace,
+                    verified_scheme,
+                );
+
+                self.finalize_soft_batch(batch_receipt, checkpoint, pre_state, soft_batch)
+            }
+            (Err(err), batch_workspace) => {
+                warn!(
+                    "Error applying soft batch: {:?} \n reverting batch workspace",
+                    err
+                );
+                batch_workspace.revert();
+                SlotResult {
+                    state_root: pre_state_root.clone(),
+                    change_set: pre_state, // should be empty
+                    batch_receipts: vec![],
+                    witness: <<C as Spec>::Storage as Storage>::Witness::default(),
+                }
+            }
+        }
+    }
+
+    /// Ends a soft batch. Its signature is verified unless the caller verified it with
+    /// `verified_scheme`.
+    fn end_soft_batch_inner(
+        &self,
+        sequencer_public_key: &[u8],
+        soft_batch: &mut SignedSoftConfirmationBatch,
+        tx_receipts: Vec<TransactionReceipt<TxEffect>>,
+        mut batch_workspace: WorkingSet<C>,
+        verified_scheme: Option<SignatureScheme>,
+    ) -> (BatchReceipt<(), TxEffect>, StateCheckpoint<C>) {
+        // check the signature against the scheme active at the DA height of the soft batch
+        let scheme = self
+            .kernel
+            .soft_batch_signature_scheme(soft_batch.da_slot_height(), &mut batch_workspace);
+        assert!(
+            check_soft_batch_signature::<C>(
+                soft_batch,
+                sequencer_public_key,
+                scheme,
+                verified_scheme
+            )
+            .is_ok(),
+            "Signature verification must succeed"
+        );
+
+        // then check that the signed message carries an ordering commitment if and only if
+        // the DA height of the soft batch is past its activation
+        let ordering_committed = self
+            .kernel
+            .soft_batch_ordering_committed(soft_batch.da_slot_height(), &mut batch_workspace);
+        assert_eq!(
+            soft_batch.ordering_commitment().is_some(),
+            ordering_committed,
+            "Ordering commitment must be carried from its activation height on"
+        );
+
+        let (apply_soft_batch_result, checkpoint) =
+            self.end_soft_confirmation_inner(soft_batch, tx_receipts, batch_workspace);
+
+        (apply_soft_batch_result.unwrap(), checkpoint)
+    }
+
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     fn begin_slot(
         &self,
@@ -503,6 +803,7 @@ where
                 batch_hash: batch_receipt.batch_hash,
                 tx_receipts: batch_receipt.tx_receipts,
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: batch_receipt.soft_confirmation_outcome,
             });
         }
 
@@ -531,47 +832,27 @@ where
         Self::TxReceiptContents,
         Self::Witness,
     > {
-        match self.begin_soft_batch(
+        self.apply_soft_batch_inner(
             sequencer_public_key,
             pre_state_root,
-            pre_state.clone(),
+            pre_state,
             witness,
             slot_header,
             soft_batch,
-        ) {
-            (Ok(()), batch_workspace) => {
-                let (batch_workspace, tx_receipts) =
-                    self.apply_soft_batch_txs(soft_batch.txs(), batch_workspace);
-
-                let (batch_receipt, checkpoint) = self.end_soft_batch(
-                    sequencer_public_key,
-                    soft_batch,
-                    tx_receipts,
-                    batch_workspace,
-                );
-
-                self.finalize_soft_batch(batch_receipt, checkpoint, pre_state, soft_batch)
-            }
-            (Err(err), batch_workspace) => {
-                warn!(
-                    "Error applying soft batch: {:?} \n reverting batch workspace",
-                    err
-                );
-                batch_workspace.revert();
-                SlotResult {
-                    state_root: pre_state_root.clone(),
-                    change_set: pre_state, // should be empty
-                    batch_receipts: vec![],
-                    witness: <<C as Spec>::Storage as Storage>::Witness::default(),
-                }
-            }
-        }
+            None,
+        )
     }
 }
 
-fn verify_soft_batch_signature<C: Context>(
+/// Verifies that the soft batch is signed by the sequencer with the given public key,
+/// using `scheme`.
+///
+/// With [`SignatureScheme::Secp256k1Schnorr`], the public key is a 32 bytes x-only key
+/// and the signature is a BIP-340 signature of [`schnorr_message_digest`].
+pub fn verify_soft_batch_signature<C: Context>(
     soft_batch: &SignedSoftConfirmationBatch,
     sequencer_public_key: &[u8],
+    scheme: SignatureScheme,
 ) -> Result<(), anyhow::Error> {
     let unsigned = UnsignedSoftConfirmationBatch::new(
         soft_batch.da_slot_height(),
@@ -579,11 +860,69 @@ fn verify_soft_batch_signature<C: Context>(
         soft_batch.pre_state_root(),
         soft_batch.txs(),
         soft_batch.l1_fee_rate(),
+        soft_batch.timestamp(),
+        soft_batch.ordering_commitment(),
     );
 
     let message = unsigned.try_to_vec().unwrap();
 
-    let signature = C::Signature::try_from(soft_batch.signature().as_slice())?;
+    match scheme {
+        SignatureScheme::Ed25519 => {
+            let signature = C::Signature::try_from(soft_batch.signature().as_slice())?;
+            signature.verify(
+                &C::PublicKey::try_from(sequencer_public_key)?,
+                message.as_slice(),
+            )?;
+        }
+        SignatureScheme::Secp256k1Schnorr => {
+            let signature = schnorr::Signature::from_slice(soft_batch.signature().as_slice())?;
+            let public_key = XOnlyPublicKey::from_slice(sequencer_public_key)?;
+            SECP256K1.verify_schnorr(&signature, &schnorr_message_digest(&message), &public_key)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the signature of the soft batch against the active `scheme`.
+///
+/// When `verified_scheme` is set, the signature was already verified with that scheme by the
+/// caller, so only the scheme is checked against the active one.
+pub fn check_soft_batch_signature<C: Context>(
+    soft_batch: &SignedSoftConfirmationBatch,
+    sequencer_public_key: &[u8],
+    scheme: SignatureScheme,
+    verified_scheme: Option<SignatureScheme>,
+) -> Result<(), anyhow::Error> {
+    match verified_scheme {
+        Some(verified_scheme) => {
+            anyhow::ensure!(
+                verified_scheme == scheme,
+                "Soft batch signature was verified with {:?} but {:?} is active",
+                verified_scheme,
+                scheme
+            );
+            Ok(())
+        }
+        None => verify_soft_batch_signature::<C>(soft_batch, sequencer_public_key, scheme),
+    }
+}
+
+/// Verifies that the checkpoint is signed by the sequencer with the given public key.
+///
+/// Checkpoints are always signed with the ed25519 key of the sequencer, whatever the
+/// signature scheme of soft batches is.
+pub fn verify_checkpoint_signature<C: Context>(
+    checkpoint: &SignedCheckpoint,
+    sequencer_public_key: &[u8],
+) -> Result<(), anyhow::Error> {
+    anyhow::ensure!(
+        checkpoint.pub_key.as_slice() == sequencer_public_key,
+        "Checkpoint is not signed by the sequencer"
+    );
+
+    let message = checkpoint.checkpoint.try_to_vec().unwrap();
+    let signature = C::Signature::try_from(checkpoint.signature.as_slice())?;
     signature.verify(
         &C::PublicKey::try_from(sequencer_public_key)?,
         message.as_slice(),
@@ -591,3 +930,134 @@ fn verify_soft_batch_signature<C: Context>(
 
     Ok(())
 }
+
+/// Returns the message signed with Schnorr signatures for the borsh serialized
+/// unsigned soft batch `message`: its SHA-256 digest.
+pub fn schnorr_message_digest(message: &[u8]) -> Message {
+    let digest: [u8; 32] = sha2::Sha256::digest(message).into();
+    Message::from_slice(&digest).expect("SHA-256 digests are 32 bytes")
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::PrivateKey;
+
+    use super::*;
+
+    fn signed_soft_batch(
+        key: &DefaultPrivateKey,
+        ordering_commitment: Option<[u8; 32]>,
+    ) -> SignedSoftConfirmationBatch {
+        let unsigned = UnsignedSoftConfirmationBatch::new(
+            3,
+            [2; 32],
+            vec![4; 32],
+            vec![],
+            10,
+            Some(5),
+            Some([6; 32]),
+        );
+        let signature = key.sign(&unsigned.try_to_vec().unwrap());
+        SignedSoftConfirmationBatch::new(
+            [1; 32],
+            3,
+            [2; 32],
+            vec![4; 32],
+            10,
+            vec![],
+            signature.try_to_vec().unwrap(),
+            key.pub_key().try_to_vec().unwrap(),
+            Some(5),
+            ordering_commitment,
+        )
+    }
+
+    #[test]
+    fn test_check_soft_batch_signature() {
+        let key = DefaultPrivateKey::generate();
+        let other_key = DefaultPrivateKey::generate()
+            .pub_key()
+            .try_to_vec()
+            .unwrap();
+        let soft_batch = signed_soft_batch(&key, Some([6; 32]));
+        let public_key = key.pub_key().try_to_vec().unwrap();
+
+        assert!(check_soft_batch_signature::<DefaultContext>(
+            &soft_batch,
+            &public_key,
+            SignatureScheme::Ed25519,
+            None
+        )
+        .is_ok());
+        assert!(check_soft_batch_signature::<DefaultContext>(
+            &soft_batch,
+            &other_key,
+            SignatureScheme::Ed25519,
+            None
+        )
+        .is_err());
+        // a signature verified by the caller is not verified again
+        assert!(check_soft_batch_signature::<DefaultContext>(
+            &soft_batch,
+            &other_key,
+            SignatureScheme::Ed25519,
+            Some(SignatureScheme::Ed25519)
+        )
+        .is_ok());
+        // but it must have been verified with the active scheme
+        assert!(check_soft_batch_signature::<DefaultContext>(
+            &soft_batch,
+            &public_key,
+            SignatureScheme::Secp256k1Schnorr,
+            Some(SignatureScheme::Ed25519)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_untimestamped_soft_batch_signature() {
+        // Soft batches given before the chain required a timestamp are signed without it
+        let key = DefaultPrivateKey::generate();
+        let message = (3u64, [2u8; 32], vec![4u8; 32], Vec::<Vec<u8>>::new(), 10u64)
+            .try_to_vec()
+            .unwrap();
+        let soft_batch = SignedSoftConfirmationBatch::new(
+            [1; 32],
+            3,
+            [2; 32],
+            vec![4; 32],
+            10,
+            vec![],
+            key.sign(&message).try_to_vec().unwrap(),
+            key.pub_key().try_to_vec().unwrap(),
+            None,
+            None,
+        );
+
+        assert!(check_soft_batch_signature::<DefaultContext>(
+            &soft_batch,
+            &key.pub_key().try_to_vec().unwrap(),
+            SignatureScheme::Ed25519,
+            None
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_ordering_commitment_is_signed() {
+        let key = DefaultPrivateKey::generate();
+        let public_key = key.pub_key().try_to_vec().unwrap();
+
+        for ordering_commitment in [Some([7; 32]), None] {
+            assert!(check_soft_batch_signature::<DefaultContext>(
+                &signed_soft_batch(&key, ordering_commitment),
+                &public_key,
+                SignatureScheme::Ed25519,
+                None
+            )
+            .is_err());
+        }
+    }
+}
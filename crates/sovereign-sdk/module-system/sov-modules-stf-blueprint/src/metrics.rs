@@ -0,0 +1,54 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter_vec,
+    Histogram, HistogramVec, IntCounterVec,
+};
+
+pub static STF_PHASE_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "stf_phase_duration_seconds",
+        // metric description
+        "Time spent in each phase of applying a soft confirmation",
+        // metric labels (dimensions)
+        &["phase"],
+        exponential_buckets(/*start=*/ 1e-5, /*factor=*/ 2.0, /*count=*/ 22).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static STF_HOOK_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "stf_hook_duration_seconds",
+        // metric description
+        "Time spent in each module hook and transaction step",
+        // metric labels (dimensions)
+        &["hook"],
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 24).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static STF_TX_DURATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "stf_tx_duration_seconds",
+        // metric description
+        "Time spent applying a transaction of a soft confirmation, hooks included",
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 24).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static STF_STATE_ACCESSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "stf_state_accesses_total",
+        // metric description
+        "State reads and writes made by each phase, module hook and transaction step",
+        // metric labels (dimensions)
+        &["scope", "access"]
+    )
+    .unwrap()
+});
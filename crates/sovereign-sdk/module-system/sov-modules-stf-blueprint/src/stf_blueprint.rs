@@ -3,13 +3,16 @@ use std::marker::PhantomData;
 use borsh::{BorshDeserialize, BorshSerialize};
 use sov_modules_api::hooks::{ApplySoftConfirmationError, HookSoftConfirmationInfo};
 use sov_modules_api::runtime::capabilities::KernelSlotHooks;
+use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, StateCheckpoint, WorkingSet,
+    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, SoftConfirmationOutcome,
+    StateCheckpoint, WorkingSet,
 };
 use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use tracing::{debug, error};
 
+use crate::instrumentation::{time_hook, PhaseTimer, TxTimer};
 use crate::tx_verifier::{verify_txs_stateless, TransactionAndRawHash};
 use crate::{Batch, RawTx, Runtime, RuntimeTxHook, SequencerOutcome, SlashingReason, TxEffect};
 
@@ -54,6 +57,7 @@ impl<A: BasicAddress> From<ApplyBatchError<A>> for BatchReceipt<SequencerOutcome
                 batch_hash: hash,
                 tx_receipts: Vec::new(),
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: None,
             },
             ApplyBatchError::Slashed {
                 hash,
@@ -63,6 +67,7 @@ impl<A: BasicAddress> From<ApplyBatchError<A>> for BatchReceipt<SequencerOutcome
                 batch_hash: hash,
                 tx_receipts: Vec::new(),
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: None,
             },
         }
     }
@@ -100,12 +105,16 @@ where
         }
     }
 
-    /// Applies sov txs to the state
+    /// Applies sov txs to the state, given the public key of the sequencer of the soft batch
     pub fn apply_sov_txs_inner(
         &self,
+        sequencer_public_key: &[u8],
         txs: Vec<Vec<u8>>,
         mut batch_workspace: WorkingSet<C>,
     ) -> (WorkingSet<C>, Vec<TransactionReceipt<TxEffect>>) {
+        let phase = PhaseTimer::start("apply_sov_txs", batch_workspace.state_access_counts());
+        let height = self.kernel.soft_batch_height(&mut batch_workspace);
+        let sequencer = sequencer_hook_key::<C>(sequencer_public_key);
         let txs = self.verify_txs_stateless_soft(&txs);
 
         let messages = self
@@ -123,16 +132,15 @@ where
         for (TransactionAndRawHash { tx, raw_tx_hash }, msg) in
             txs.into_iter().zip(messages.into_iter())
         {
+            let tx_timer = TxTimer::start(&raw_tx_hash);
             // Pre dispatch hook
-            // TODO set the sequencer pubkey
             let hook = RuntimeTxHook {
-                height: 1,
-                sequencer: tx.pub_key().clone(),
+                height,
+                sequencer: sequencer.clone().unwrap_or_else(|| tx.pub_key().clone()),
             };
-            let ctx = match self
-                .runtime
-                .pre_dispatch_tx_hook(&tx, &mut batch_workspace, &hook)
-            {
+            let ctx = match time_hook("pre_dispatch_tx", &mut batch_workspace, |ws| {
+                self.runtime.pre_dispatch_tx_hook(&tx, ws, &hook)
+            }) {
                 Ok(verified_tx) => verified_tx,
                 Err(e) => {
                     // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
@@ -146,15 +154,18 @@ where
                     };
 
                     tx_receipts.push(receipt);
+                    tx_timer.finish();
                     continue;
                 }
             };
             // Commit changes after pre_dispatch_tx_hook
             batch_workspace = batch_workspace.checkpoint().to_revertable();
 
-            let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
+            let tx_result = time_hook("dispatch_call", &mut batch_workspace, |ws| {
+                self.runtime.dispatch_call(msg, ws, &ctx)
+            });
 
-            let events = batch_workspace.take_events();
+            let mut events = batch_workspace.take_events();
             let tx_effect = match tx_result {
                 Ok(_) => TxEffect::Successful,
                 Err(e) => {
@@ -169,6 +180,11 @@ where
                     TxEffect::Reverted
                 }
             };
+
+            let (tx_effect, workspace) =
+                self.post_dispatch_tx(&tx, &ctx, raw_tx_hash, tx_effect, batch_workspace);
+            batch_workspace = workspace;
+            events.extend(batch_workspace.take_events());
             debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
 
             let receipt = TransactionReceipt {
@@ -181,13 +197,9 @@ where
             tx_receipts.push(receipt);
             // We commit after events have been extracted into receipt.
             batch_workspace = batch_workspace.checkpoint().to_revertable();
-
-            // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            // TODO: Check if we need to put this in end_soft_onfirmation, becuase I am not sure if we can call pre_dispatch again for new txs after this
-            self.runtime
-                .post_dispatch_tx_hook(&tx, &ctx, &mut batch_workspace)
-                .expect("inconsistent state: error in post_dispatch_tx_hook");
+            tx_timer.finish();
         }
+        phase.finish(batch_workspace.state_access_counts());
         (batch_workspace, tx_receipts)
     }
 
@@ -204,6 +216,7 @@ where
             hex::encode(soft_batch.sequencer_pub_key())
         );
 
+        let phase = PhaseTimer::start("begin_soft_confirmation", checkpoint.state_access_counts());
         let mut batch_workspace = checkpoint.to_revertable();
 
         // ApplySoftConfirmationHook: begin
@@ -215,6 +228,7 @@ where
                 "Error: The batch was rejected by the 'begin_soft_confirmation_hook'. Skipping batch with error: {}",
                 e
             );
+            phase.finish(batch_workspace.state_access_counts());
 
             return (
                 Err(e),
@@ -229,6 +243,7 @@ where
         // TODO: don't ignore these events: https://github.com/Sovereign-Labs/sovereign/issues/350
         let _ = batch_workspace.take_events();
 
+        phase.finish(batch_workspace.state_access_counts());
         (Ok(()), batch_workspace)
     }
 
@@ -240,23 +255,38 @@ where
         tx_receipts: Vec<TransactionReceipt<TxEffect>>,
         mut batch_workspace: WorkingSet<C>,
     ) -> (ApplySoftConfirmationResult, StateCheckpoint<C>) {
-        // TODO: calculate the amount based of gas and fees
+        let phase = PhaseTimer::start(
+            "end_soft_confirmation",
+            batch_workspace.state_access_counts(),
+        );
 
-        if let Err(e) = self
-            .runtime
-            .end_soft_confirmation_hook(&mut batch_workspace)
-        {
-            // TODO: will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            error!("Failed on `end_blob_hook`: {}", e);
+        let mut outcome = match self.runtime.end_soft_confirmation_hook(
+            &HookSoftConfirmationInfo::from(soft_batch.clone()),
+            &mut batch_workspace,
+        ) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                // TODO: will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
+                error!("Failed on `end_blob_hook`: {}", e);
+                SoftConfirmationOutcome::default()
+            }
         };
-
+        outcome.successful_txs = tx_receipts
+            .iter()
+            .filter(|receipt| receipt.receipt == TxEffect::Successful)
+            .count() as u64;
+        outcome.reverted_txs = tx_receipts.len() as u64 - outcome.successful_txs;
+
+        let checkpoint = batch_workspace.checkpoint();
+        phase.finish(checkpoint.state_access_counts());
         (
             Ok(BatchReceipt {
                 batch_hash: soft_batch.hash(),
                 tx_receipts,
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: Some(outcome),
             }),
-            batch_workspace.checkpoint(),
+            checkpoint,
         )
     }
 
@@ -268,9 +298,13 @@ where
     ) -> (ApplySoftConfirmationResult, StateCheckpoint<C>) {
         match self.begin_soft_confirmation_inner(checkpoint, soft_batch) {
             (Ok(()), batch_workspace) => {
-                // TODO: wait for txs here, apply_sov_txs can be called multiple times
-                let (batch_workspace, tx_receipts) =
-                    self.apply_sov_txs_inner(soft_batch.txs(), batch_workspace);
+                // The sequencer may have applied the txs in several chunks, which is
+                // equivalent to applying them all at once
+                let (batch_workspace, tx_receipts) = self.apply_sov_txs_inner(
+                    soft_batch.sequencer_pub_key(),
+                    soft_batch.txs(),
+                    batch_workspace,
+                );
 
                 self.end_soft_confirmation_inner(soft_batch, tx_receipts, batch_workspace)
             }
@@ -388,7 +422,7 @@ where
 
             let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
 
-            let events = batch_workspace.take_events();
+            let mut events = batch_workspace.take_events();
             let tx_effect = match tx_result {
                 Ok(_) => TxEffect::Successful,
                 Err(e) => {
@@ -403,6 +437,11 @@ where
                     TxEffect::Reverted
                 }
             };
+
+            let (tx_effect, workspace) =
+                self.post_dispatch_tx(&tx, &ctx, raw_tx_hash, tx_effect, batch_workspace);
+            batch_workspace = workspace;
+            events.extend(batch_workspace.take_events());
             debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
 
             let receipt = TransactionReceipt {
@@ -415,11 +454,6 @@ where
             tx_receipts.push(receipt);
             // We commit after events have been extracted into receipt.
             batch_workspace = batch_workspace.checkpoint().to_revertable();
-
-            // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            self.runtime
-                .post_dispatch_tx_hook(&tx, &ctx, &mut batch_workspace)
-                .expect("inconsistent state: error in post_dispatch_tx_hook");
         }
 
         if let Err(e) = self.runtime.end_blob_hook(&mut batch_workspace) {
@@ -432,11 +466,28 @@ where
                 batch_hash: blob.hash(),
                 tx_receipts,
                 phantom_data: PhantomData,
+                soft_confirmation_outcome: None,
             }),
             batch_workspace.checkpoint(),
         )
     }
 
+    /// Runs the post dispatch hook of a transaction, see [`run_post_dispatch_tx_hook`].
+    fn post_dispatch_tx(
+        &self,
+        tx: &Transaction<C>,
+        ctx: &C,
+        raw_tx_hash: [u8; 32],
+        tx_effect: TxEffect,
+        batch_workspace: WorkingSet<C>,
+    ) -> (TxEffect, WorkingSet<C>) {
+        run_post_dispatch_tx_hook(raw_tx_hash, tx_effect, batch_workspace, |batch_workspace| {
+            time_hook("post_dispatch_tx", batch_workspace, |ws| {
+                self.runtime.post_dispatch_tx_hook(tx, ctx, ws)
+            })
+        })
+    }
+
     // Do all stateless checks and data formatting, that can be results in sequencer slashing
     fn pre_process_batch(
         &self,
@@ -525,6 +576,53 @@ where
     }
 }
 
+/// Returns the public key of the sequencer given to the tx hooks, from the public key which
+/// signed the soft batch.
+/// Keys which aren't public keys of the rollup, such as Schnorr keys, give `None`, in which
+/// case the signer of each transaction is given instead.
+pub(crate) fn sequencer_hook_key<C: Context>(sequencer_public_key: &[u8]) -> Option<C::PublicKey> {
+    C::PublicKey::try_from(sequencer_public_key).ok()
+}
+
+/// Runs the post dispatch hook of a transaction, on top of the changes made by the
+/// transaction since its pre dispatch hook. If the hook fails, these changes are reverted,
+/// the transaction is marked as reverted and the hook runs again on the reverted state, so
+/// that the transaction is still accounted for, e.g. its nonce is still incremented.
+///
+/// If the hook also fails on the reverted state, or fails after a reverted transaction, the
+/// transaction is marked as reverted without any of its changes or the hook's. Natively and
+/// in the guest alike, so that both always reach the same state.
+fn run_post_dispatch_tx_hook<C: Context>(
+    raw_tx_hash: [u8; 32],
+    tx_effect: TxEffect,
+    mut batch_workspace: WorkingSet<C>,
+    mut post_dispatch_tx_hook: impl FnMut(&mut WorkingSet<C>) -> anyhow::Result<()>,
+) -> (TxEffect, WorkingSet<C>) {
+    let Err(e) = post_dispatch_tx_hook(&mut batch_workspace) else {
+        return (tx_effect, batch_workspace);
+    };
+    error!(
+        "Tx 0x{} was reverted, error in post_dispatch_tx_hook: {}",
+        hex::encode(raw_tx_hash),
+        e
+    );
+    let mut batch_workspace = batch_workspace.revert().to_revertable();
+
+    if tx_effect == TxEffect::Successful {
+        match post_dispatch_tx_hook(&mut batch_workspace) {
+            Ok(()) => return (TxEffect::Reverted, batch_workspace),
+            Err(e) => error!(
+                "Error in post_dispatch_tx_hook of reverted tx 0x{}: {}",
+                hex::encode(raw_tx_hash),
+                e
+            ),
+        }
+        batch_workspace = batch_workspace.revert().to_revertable();
+    }
+
+    (TxEffect::Reverted, batch_workspace)
+}
+
 #[cfg(feature = "native")]
 fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
     blob.full_data()
@@ -534,3 +632,96 @@ fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
 fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
     blob.verified_data()
 }
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::{ModulePrefix, PrivateKey, StateValue, StateValueAccessor};
+    use sov_prover_storage_manager::new_orphan_storage;
+
+    use super::*;
+
+    fn state_value(name: &str) -> StateValue<u64> {
+        StateValue::new(ModulePrefix::new_storage("sov_modules_stf_blueprint", "Test", name).into())
+    }
+
+    /// Runs a transaction writing to `tx_value` when it succeeds, then the post dispatch hook,
+    /// which writes its run count to `hook_value` and fails on the runs listed in `failing_runs`.
+    /// Returns the effect of the transaction and the values left in the state.
+    fn run_tx(tx_effect: TxEffect, failing_runs: &[u64]) -> (TxEffect, Option<u64>, Option<u64>) {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = new_orphan_storage(tmpdir.path()).unwrap();
+        let mut batch_workspace: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+        let tx_value = state_value("tx");
+        let hook_value = state_value("hook");
+
+        if tx_effect == TxEffect::Successful {
+            tx_value.set(&1, &mut batch_workspace);
+        }
+        let mut runs = 0;
+        let (tx_effect, mut batch_workspace) =
+            run_post_dispatch_tx_hook([0; 32], tx_effect, batch_workspace, |ws| {
+                runs += 1;
+                hook_value.set(&runs, ws);
+                anyhow::ensure!(!failing_runs.contains(&runs), "hook failed");
+                Ok(())
+            });
+
+        (
+            tx_effect,
+            tx_value.get(&mut batch_workspace),
+            hook_value.get(&mut batch_workspace),
+        )
+    }
+
+    #[test]
+    fn test_post_dispatch_tx_hook() {
+        assert_eq!(
+            run_tx(TxEffect::Successful, &[]),
+            (TxEffect::Successful, Some(1), Some(1))
+        );
+        assert_eq!(
+            run_tx(TxEffect::Reverted, &[]),
+            (TxEffect::Reverted, None, Some(1))
+        );
+    }
+
+    #[test]
+    fn test_post_dispatch_tx_hook_fails_after_successful_tx() {
+        // The changes of the transaction are reverted and the hook runs again
+        assert_eq!(
+            run_tx(TxEffect::Successful, &[1]),
+            (TxEffect::Reverted, None, Some(2))
+        );
+    }
+
+    #[test]
+    fn test_post_dispatch_tx_hook_fails_after_reverted_tx() {
+        // Nothing is left to revert, so the hook does not run again
+        assert_eq!(
+            run_tx(TxEffect::Reverted, &[1]),
+            (TxEffect::Reverted, None, None)
+        );
+    }
+
+    #[test]
+    fn test_post_dispatch_tx_hook_fails_twice() {
+        assert_eq!(
+            run_tx(TxEffect::Successful, &[1, 2]),
+            (TxEffect::Reverted, None, None)
+        );
+    }
+
+    #[test]
+    fn test_sequencer_hook_key() {
+        let sequencer = DefaultPrivateKey::generate().pub_key();
+        let sequencer_public_key = sequencer.try_to_vec().unwrap();
+
+        assert_eq!(
+            sequencer_hook_key::<DefaultContext>(&sequencer_public_key),
+            Some(sequencer)
+        );
+        assert_eq!(sequencer_hook_key::<DefaultContext>(&[1, 2, 3]), None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Helpers to unit test the soft confirmation hooks of modules and runtimes on canned
+//! state, without running a node.
+//!
+//! ```ignore
+//! let mut kit = HookTestKit::new().with_genesis(&rule_enforcer, &config);
+//! let batch = SoftConfirmationBuilder::default().l1_fee_rate(100).build();
+//!
+//! kit.run_hook(|working_set| {
+//!     rule_enforcer.begin_soft_confirmation_hook(&mut batch.clone().into(), working_set)
+//! })?;
+//! ```
+
+use borsh::BorshSerialize;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::hooks::{
+    ApplySoftConfirmationError, ApplySoftConfirmationHooks, HookSoftConfirmationInfo,
+};
+use sov_modules_api::{
+    DaSpec, Module, PrivateKey, SignedSoftConfirmationBatch, Spec, UnsignedSoftConfirmationBatch,
+    WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+use tempfile::TempDir;
+
+type C = DefaultContext;
+
+/// State the hooks under test run against, backed by storage in a temporary directory.
+///
+/// Changes are layered like in the STF: a hook run through the kit is applied on top
+/// of the last checkpoint, and reverted if the hook fails.
+pub struct HookTestKit {
+    // Keeps the storage directory alive as long as the kit
+    _tmpdir: TempDir,
+    // Only `None` while being converted to or from a checkpoint
+    working_set: Option<WorkingSet<C>>,
+}
+
+impl Default for HookTestKit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HookTestKit {
+    /// Creates a kit with empty state.
+    pub fn new() -> Self {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = new_orphan_storage(tmpdir.path()).unwrap();
+
+        Self {
+            _tmpdir: tmpdir,
+            working_set: Some(WorkingSet::new(storage)),
+        }
+    }
+
+    /// Runs the genesis of `module` with `config`.
+    pub fn with_genesis<M: Module<Context = C>>(mut self, module: &M, config: &M::Config) -> Self {
+        module
+            .genesis(config, self.working_set())
+            .expect("Module genesis must succeed");
+        self.checkpoint();
+        self
+    }
+
+    /// Writes canned state with `setup`, e.g. by calling the setters of a module.
+    pub fn with_state(mut self, setup: impl FnOnce(&mut WorkingSet<C>)) -> Self {
+        setup(self.working_set());
+        self.checkpoint();
+        self
+    }
+
+    /// Returns the working set, to read the state left by the hooks or change it.
+    pub fn working_set(&mut self) -> &mut WorkingSet<C> {
+        self.working_set
+            .as_mut()
+            .expect("Working set is only taken while checkpointing")
+    }
+
+    /// Makes the changes written so far survive a later failing hook.
+    pub fn checkpoint(&mut self) {
+        let working_set = self.working_set.take().unwrap();
+        self.working_set = Some(working_set.checkpoint().to_revertable());
+    }
+
+    /// Drops the changes written since the last checkpoint.
+    pub fn revert(&mut self) {
+        let working_set = self.working_set.take().unwrap();
+        self.working_set = Some(working_set.revert().to_revertable());
+    }
+
+    /// Runs a single hook, keeping its changes if it succeeds and reverting them otherwise.
+    pub fn run_hook<T, E>(
+        &mut self,
+        hook: impl FnOnce(&mut WorkingSet<C>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        self.checkpoint();
+        let result = hook(self.working_set());
+        match result {
+            Ok(_) => self.checkpoint(),
+            Err(_) => self.revert(),
+        }
+        result
+    }
+
+    /// Runs the `begin_soft_confirmation_hook` of `hooks` for `batch`, as the STF does
+    /// before applying its transactions.
+    pub fn begin_soft_confirmation<Da, H>(
+        &mut self,
+        hooks: &H,
+        batch: &SignedSoftConfirmationBatch,
+    ) -> Result<(), ApplySoftConfirmationError>
+    where
+        Da: DaSpec,
+        H: ApplySoftConfirmationHooks<Da, Context = C>,
+    {
+        let mut info = HookSoftConfirmationInfo::from(batch.clone());
+        self.run_hook(|working_set| hooks.begin_soft_confirmation_hook(&mut info, working_set))
+    }
+
+    /// Runs the `end_soft_confirmation_hook` of `hooks` for `batch`, as the STF does after
+    /// applying its transactions, and returns the outcome of the batch.
+    pub fn end_soft_confirmation<Da, H>(
+        &mut self,
+        hooks: &H,
+        batch: &SignedSoftConfirmationBatch,
+    ) -> Result<H::SoftConfirmationResult, ApplySoftConfirmationError>
+    where
+        Da: DaSpec,
+        H: ApplySoftConfirmationHooks<Da, Context = C>,
+    {
+        let info = HookSoftConfirmationInfo::from(batch.clone());
+        self.run_hook(|working_set| hooks.end_soft_confirmation_hook(&info, working_set))
+    }
+
+    /// Runs both hooks of `hooks` around `body`, which stands for the transactions of
+    /// `batch`. Nothing is applied if the begin hook rejects the batch.
+    pub fn run_soft_confirmation<Da, H>(
+        &mut self,
+        hooks: &H,
+        batch: &SignedSoftConfirmationBatch,
+        body: impl FnOnce(&mut WorkingSet<C>),
+    ) -> Result<H::SoftConfirmationResult, ApplySoftConfirmationError>
+    where
+        Da: DaSpec,
+        H: ApplySoftConfirmationHooks<Da, Context = C>,
+    {
+        self.begin_soft_confirmation(hooks, batch)?;
+        body(self.working_set());
+        self.end_soft_confirmation(hooks, batch)
+    }
+}
+
+/// Builds [`SignedSoftConfirmationBatch`] values for hook tests.
+/// Every field defaults to zero or empty, and batches carry no timestamp nor ordering
+/// commitment.
+#[derive(Debug, Clone, Default)]
+pub struct SoftConfirmationBuilder {
+    da_slot_height: u64,
+    da_slot_hash: [u8; 32],
+    pre_state_root: Vec<u8>,
+    l1_fee_rate: u64,
+    timestamp: Option<u64>,
+    ordering_commitment: Option<[u8; 32]>,
+    txs: Vec<Vec<u8>>,
+}
+
+impl SoftConfirmationBuilder {
+    /// Sets the height of the DA block the batch is given for.
+    pub fn da_slot_height(mut self, da_slot_height: u64) -> Self {
+        self.da_slot_height = da_slot_height;
+        self
+    }
+
+    /// Sets the hash of the DA block the batch is given for.
+    pub fn da_slot_hash(mut self, da_slot_hash: [u8; 32]) -> Self {
+        self.da_slot_hash = da_slot_hash;
+        self
+    }
+
+    /// Sets the state root the batch is applied on.
+    pub fn pre_state_root(mut self, pre_state_root: Vec<u8>) -> Self {
+        self.pre_state_root = pre_state_root;
+        self
+    }
+
+    /// Sets the L1 fee rate of the batch.
+    pub fn l1_fee_rate(mut self, l1_fee_rate: u64) -> Self {
+        self.l1_fee_rate = l1_fee_rate;
+        self
+    }
+
+    /// Sets the timestamp of the batch, in seconds since the unix epoch.
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the commitment to the order in which the transactions of the batch were received.
+    pub fn ordering_commitment(mut self, ordering_commitment: [u8; 32]) -> Self {
+        self.ordering_commitment = Some(ordering_commitment);
+        self
+    }
+
+    /// Sets the serialized transactions of the batch.
+    pub fn txs(mut self, txs: Vec<Vec<u8>>) -> Self {
+        self.txs = txs;
+        self
+    }
+
+    fn unsigned(self) -> UnsignedSoftConfirmationBatch {
+        UnsignedSoftConfirmationBatch::new(
+            self.da_slot_height,
+            self.da_slot_hash,
+            self.pre_state_root,
+            self.txs,
+            self.l1_fee_rate,
+            self.timestamp,
+            self.ordering_commitment,
+        )
+    }
+
+    /// Builds the batch with an empty signature and public key, for hooks that don't
+    /// check who produced it.
+    pub fn build(self) -> SignedSoftConfirmationBatch {
+        let unsigned = self.unsigned();
+        let hash = <C as Spec>::Hasher::digest(unsigned.try_to_vec().unwrap()).into();
+
+        SignedSoftConfirmationBatch::new(
+            hash,
+            unsigned.da_slot_height(),
+            unsigned.da_slot_hash(),
+            unsigned.pre_state_root(),
+            unsigned.l1_fee_rate(),
+            unsigned.txs(),
+            vec![],
+            vec![],
+            unsigned.timestamp(),
+            unsigned.ordering_commitment(),
+        )
+    }
+
+    /// Builds the batch signed by `key`, the way the sequencer signs soft confirmations.
+    pub fn sign(self, key: &DefaultPrivateKey) -> SignedSoftConfirmationBatch {
+        let unsigned = self.unsigned();
+        let raw = unsigned.try_to_vec().unwrap();
+        let hash = <C as Spec>::Hasher::digest(raw.as_slice()).into();
+        let signature = key.sign(&raw);
+
+        SignedSoftConfirmationBatch::new(
+            hash,
+            unsigned.da_slot_height(),
+            unsigned.da_slot_hash(),
+            unsigned.pre_state_root(),
+            unsigned.l1_fee_rate(),
+            unsigned.txs(),
+            signature.try_to_vec().unwrap(),
+            key.pub_key().try_to_vec().unwrap(),
+            unsigned.timestamp(),
+            unsigned.ordering_commitment(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_mock_da::MockDaSpec;
+    use sov_modules_api::{StateValue, StateValueAccessor};
+    use sov_state::Prefix;
+
+    use super::*;
+
+    /// Counts the soft confirmations whose fee rate is not zero
+    struct CountingHooks {
+        count: StateValue<u64>,
+    }
+
+    impl ApplySoftConfirmationHooks<MockDaSpec> for CountingHooks {
+        type Context = C;
+        type SoftConfirmationResult = ();
+
+        fn begin_soft_confirmation_hook(
+            &self,
+            soft_batch: &mut HookSoftConfirmationInfo,
+            working_set: &mut WorkingSet<C>,
+        ) -> Result<(), ApplySoftConfirmationError> {
+            let count = self.count.get(working_set).unwrap_or_default();
+            self.count.set(&(count + 1), working_set);
+
+            if soft_batch.l1_fee_rate() == 0 {
+                return Err(
+                    ApplySoftConfirmationError::L1FeeRateChangeMoreThanAllowedPercentage {
+                        l1_fee_rate: 0,
+                        l1_fee_rate_change_percentage: 0,
+                    },
+                );
+            }
+            Ok(())
+        }
+
+        fn end_soft_confirmation_hook(
+            &self,
+            _soft_batch: &HookSoftConfirmationInfo,
+            _working_set: &mut WorkingSet<C>,
+        ) -> Result<(), ApplySoftConfirmationError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failing_hook_is_reverted() {
+        let hooks = CountingHooks {
+            count: StateValue::new(Prefix::new(b"count".to_vec())),
+        };
+        let mut kit = HookTestKit::new().with_state(|working_set| hooks.count.set(&5, working_set));
+
+        let batch = SoftConfirmationBuilder::default().l1_fee_rate(1).build();
+        kit.run_soft_confirmation(&hooks, &batch, |_| {}).unwrap();
+        assert_eq!(hooks.count.get(kit.working_set()), Some(6));
+
+        let batch = SoftConfirmationBuilder::default().build();
+        assert!(kit.begin_soft_confirmation(&hooks, &batch).is_err());
+        assert_eq!(hooks.count.get(kit.working_set()), Some(6));
+    }
+
+    #[test]
+    fn signed_batch_is_signed_like_the_sequencer() {
+        let key = DefaultPrivateKey::generate();
+        let builder = SoftConfirmationBuilder::default()
+            .da_slot_height(3)
+            .l1_fee_rate(10);
+        let batch = builder.clone().sign(&key);
+
+        let raw = builder.clone().unsigned().try_to_vec().unwrap();
+        assert_eq!(batch.signature(), key.sign(&raw).try_to_vec().unwrap());
+        assert_eq!(batch.pub_key(), key.pub_key().try_to_vec().unwrap());
+        // Signing does not change the hash of the batch
+        assert_eq!(batch.hash(), builder.build().hash());
+    }
+}
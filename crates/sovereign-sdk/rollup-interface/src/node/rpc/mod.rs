@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::maybestd::vec::Vec;
 #[cfg(feature = "native")]
 use crate::stf::Event;
-use crate::stf::EventKey;
+use crate::stf::{EventKey, SoftConfirmationOutcome};
 
 /// A struct containing enough information to uniquely specify single batch.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -191,6 +191,12 @@ pub struct SoftBatchResponse {
     pub pub_key: Vec<u8>,
     /// Base layer fee rate sats/wei etc. per byte.
     pub l1_fee_rate: u64,
+    /// Block timestamp, in seconds since the unix epoch, if the soft batch carries one
+    pub timestamp: Option<u64>,
+    /// Commitment to the order in which the sequencer received the transactions, if the
+    /// soft batch carries one
+    #[serde(default, with = "utils::hex_option")]
+    pub ordering_commitment: Option<[u8; 32]>,
 }
 
 /// The response to a JSON-RPC request for a particular batch.
@@ -248,6 +254,61 @@ pub enum SoftConfirmationStatus {
     Proven,
 }
 
+/// L2 heights of the soft confirmations anchored to an L1 block
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct SoftConfirmationRange {
+    /// L2 height of the first soft confirmation
+    pub start: u64,
+    /// L2 height of the last soft confirmation
+    pub end: u64,
+}
+
+/// State of the chain after an L2 block, as signed by the sequencer in a [`SignedCheckpoint`]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct Checkpoint {
+    /// L2 height of the block
+    pub l2_height: u64,
+    /// Hash of the soft batch of the block
+    #[serde(with = "hex::serde")]
+    pub soft_batch_hash: [u8; 32],
+    /// State root after the block
+    #[serde(with = "hex::serde")]
+    pub state_root: Vec<u8>,
+}
+
+/// A [`Checkpoint`] signed by the sequencer, which nodes bootstrapping from a snapshot can
+/// choose to trust until its L2 block is proven
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct SignedCheckpoint {
+    /// The signed checkpoint
+    pub checkpoint: Checkpoint,
+    /// Signature of the borsh serialized checkpoint
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+    /// Public key of the signer
+    #[serde(with = "hex::serde")]
+    pub pub_key: Vec<u8>,
+}
+
+/// How a full node trusts a [`SignedCheckpoint`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckpointStatus {
+    /// Only the signature of the sequencer vouches for the checkpoint
+    TrustedCheckpoint,
+    /// The L2 block of the checkpoint has been ZK-proven
+    Proven,
+}
+
+/// A [`SignedCheckpoint`] verified by a full node, with how far it can be trusted
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct CheckpointWithStatus {
+    /// The checkpoint
+    pub checkpoint: SignedCheckpoint,
+    /// How the checkpoint can be trusted
+    pub status: CheckpointStatus,
+}
+
 /// A LedgerRpcProvider provides a way to query the ledger for information about slots, batches, transactions, and events.
 #[cfg(feature = "native")]
 pub trait LedgerRpcProvider {
@@ -383,6 +444,29 @@ pub trait LedgerRpcProvider {
         soft_batch_receipt: u64,
     ) -> Result<SoftConfirmationStatus, anyhow::Error>;
 
+    /// Takes an L2 Height and returns the outcome of applying the soft batch, if it is known
+    fn get_soft_confirmation_outcome(
+        &self,
+        l2_height: u64,
+    ) -> Result<Option<SoftConfirmationOutcome>, anyhow::Error>;
+
+    /// Takes an L1 height and returns the range of L2 heights of the soft confirmations
+    /// anchored to that L1 block. Returns `None` if none are known.
+    fn get_soft_confirmations_by_l1_height(
+        &self,
+        l1_height: u64,
+    ) -> Result<Option<SoftConfirmationRange>, anyhow::Error>;
+
+    /// Takes an L2 height and the hashes of the transactions of its soft batch, in the order
+    /// the sequencer claims to have received them, and returns whether they match the
+    /// ordering commitment of the soft batch. Returns `None` if the soft batch or its
+    /// commitment is unknown.
+    fn verify_ordering_commitment(
+        &self,
+        l2_height: u64,
+        tx_hashes: &[[u8; 32]],
+    ) -> Result<Option<bool>, anyhow::Error>;
+
     /// Get a notification each time a slot is processed
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 }
@@ -460,6 +544,41 @@ pub mod utils {
             deserializer.deserialize_str(HexStrVisitor(PhantomData))
         }
     }
+
+    /// Serialization and deserialization logic for optional hex strings, `null` if unset.
+    pub mod hex_option {
+        use core::fmt;
+
+        use hex::{FromHex, ToHex};
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use crate::maybestd::string::String;
+
+        /// Serializes `data` as a lowercase hex string without prefix, or as `null` if unset.
+        pub fn serialize<S, T>(data: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: ToHex,
+        {
+            match data {
+                Some(data) => serializer.serialize_some(&data.encode_hex::<String>()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserializes an optional hex string, with or without `0x` prefix, into raw bytes.
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: Deserializer<'de>,
+            T: FromHex,
+            <T as FromHex>::Error: fmt::Display,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|data| T::from_hex(data.trim_start_matches("0x")).map_err(Error::custom))
+                .transpose()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -487,6 +606,29 @@ mod rpc_hex_tests {
         assert_eq!(deserialized, test_data)
     }
 
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct OptionalTestStruct {
+        #[serde(default, with = "super::utils::hex_option")]
+        data: Option<[u8; 2]>,
+    }
+
+    #[test]
+    fn test_optional_hex_roundtrip() {
+        for test_data in [
+            OptionalTestStruct {
+                data: Some([0x01, 0x02]),
+            },
+            OptionalTestStruct { data: None },
+        ] {
+            let serialized = serde_json::to_string(&test_data).unwrap();
+            let deserialized: OptionalTestStruct = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, test_data)
+        }
+
+        let deserialized: OptionalTestStruct = serde_json::from_str(r#"{}"#).unwrap();
+        assert_eq!(deserialized, OptionalTestStruct { data: None })
+    }
+
     #[test]
     fn test_accepts_hex_without_0x_prefix() {
         let test_data = TestStruct {
@@ -6,6 +6,8 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::io;
+use crate::maybestd::vec::Vec;
 use crate::zk::ValidityCondition;
 use crate::BasicAddress;
 
@@ -21,14 +23,24 @@ pub struct SequencerCommitment {
     pub l1_end_block_hash: [u8; 32],
 }
 
-/// ZK proof of execution of a set of soft confirmations
+/// ZK proof of execution of a range of soft confirmations
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, BorshDeserialize, BorshSerialize)]
 pub struct BatchProof {
-    // TODO - implement this
+    /// Version of the guest program which generated the proof, telling which code
+    /// commitment it is verified against
+    pub guest_version: u32,
+    /// L2 height of the first soft confirmation proven
+    pub l2_start_height: u64,
+    /// L2 height of the last soft confirmation proven, inclusive
+    pub l2_end_height: u64,
+    /// Serialized zkVM proof, whose public output is the
+    /// [`StateTransition`](crate::zk::StateTransition) of the range
+    pub proof: Vec<u8>,
 }
 
 /// Data written to DA can only be one of these two types
-/// Data written to DA and read from DA is must be borsh serialization of this enum
+/// Data written to DA and read from DA must be wrapped in a versioned envelope,
+/// see [`DaData::to_envelope`]
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, BorshDeserialize, BorshSerialize)]
 pub enum DaData {
     /// A commitment from the sequencer
@@ -37,13 +49,102 @@ pub enum DaData {
     ZKProof(BatchProof),
 }
 
+/// Version of the [`DaData`] envelope written by this software.
+/// Bumped whenever the layout of [`DaData`] changes.
+pub const DA_DATA_VERSION: u8 = 2;
+
+/// Layout of [`DaData`] in envelopes of version 1 and in data written before envelopes,
+/// whose proofs carried no data
+#[derive(BorshDeserialize)]
+enum DaDataV1 {
+    SequencerCommitment(SequencerCommitment),
+    ZKProof,
+}
+
+impl DaDataV1 {
+    fn read(bytes: &[u8]) -> Result<DaData, io::Error> {
+        match DaDataV1::try_from_slice(bytes)? {
+            DaDataV1::SequencerCommitment(commitment) => {
+                Ok(DaData::SequencerCommitment(commitment))
+            }
+            DaDataV1::ZKProof => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "DA data is an empty proof of version 1",
+            )),
+        }
+    }
+}
+
+/// Prefix of the data written to DA in a versioned envelope, followed by the version and the
+/// borsh serialization of the [`DaData`] of that version. Data written before envelopes is
+/// the plain borsh serialization of [`DaData`], which starts with its variant index instead.
+const DA_DATA_ENVELOPE_MAGIC: [u8; 3] = *b"CTR";
+
+/// Data read from DA
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DaDataEnvelope {
+    /// Data of a version this software can read
+    Known(DaData),
+    /// Data written by newer software, left unparsed
+    UnknownVersion(u8),
+}
+
+impl DaDataEnvelope {
+    /// Returns the data if its version is known, fails otherwise.
+    /// For the provers, which can't skip data they don't understand.
+    pub fn into_known(self) -> Result<DaData, io::Error> {
+        match self {
+            DaDataEnvelope::Known(data) => Ok(data),
+            DaDataEnvelope::UnknownVersion(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "DA data written by a newer version",
+            )),
+        }
+    }
+}
+
+impl DaData {
+    /// Serializes the data in the envelope of [`DA_DATA_VERSION`], as written to DA.
+    pub fn to_envelope(&self) -> Vec<u8> {
+        let mut bytes = DA_DATA_ENVELOPE_MAGIC.to_vec();
+        bytes.push(DA_DATA_VERSION);
+        self.serialize(&mut bytes)
+            .expect("Serialization to vec is infallible");
+        bytes
+    }
+
+    /// Reads data written to DA, with or without an envelope.
+    ///
+    /// Data of a version newer than [`DA_DATA_VERSION`] is returned as
+    /// [`DaDataEnvelope::UnknownVersion`] instead of an error, so that nodes upgraded after
+    /// the sequencer can skip it rather than fail on it.
+    pub fn from_envelope(bytes: &[u8]) -> Result<DaDataEnvelope, io::Error> {
+        let Some(enveloped) = bytes.strip_prefix(DA_DATA_ENVELOPE_MAGIC.as_slice()) else {
+            return DaDataV1::read(bytes).map(DaDataEnvelope::Known);
+        };
+        match enveloped.split_first() {
+            Some((&DA_DATA_VERSION, data)) => {
+                DaData::try_from_slice(data).map(DaDataEnvelope::Known)
+            }
+            Some((&1, data)) => DaDataV1::read(data).map(DaDataEnvelope::Known),
+            Some((&version, _)) if version > DA_DATA_VERSION => {
+                Ok(DaDataEnvelope::UnknownVersion(version))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid DA data envelope",
+            )),
+        }
+    }
+}
+
 /// A specification for the types used by a DA layer.
 pub trait DaSpec: 'static + Debug + PartialEq + Eq + Clone {
     /// The hash of a DA layer block
     type SlotHash: BlockHashTrait;
 
     /// The block header type used by the DA layer
-    type BlockHeader: BlockHeaderTrait<Hash = Self::SlotHash> + Send + Sync;
+    type BlockHeader: BlockHeaderTrait<Hash = Self::SlotHash> + LightClientHeader + Send + Sync;
 
     /// The transaction type used by the DA layer.
     type BlobTransaction: BlobReaderTrait<Address = Self::Address> + Send + Sync;
@@ -230,6 +331,62 @@ pub trait BlockHeaderTrait: PartialEq + Debug + Clone + Serialize + DeserializeO
     fn time(&self) -> Time;
 }
 
+/// Consensus rules of the header chain of a DA layer.
+/// They let the rollup follow the DA chain in its own state, like a light client, instead of
+/// trusting the DA service of the node.
+pub trait LightClientHeader: BlockHeaderTrait {
+    /// What the light client remembers about the tip of the chain to validate the next header,
+    /// such as the current difficulty.
+    type ChainTip: Serialize + DeserializeOwned + Clone + Debug + PartialEq + Send + Sync;
+
+    /// Returns the hash of the header at the tip of the chain.
+    fn tip_hash(tip: &Self::ChainTip) -> [u8; 32];
+
+    /// Checks that the header extends the chain at `tip` and returns the new tip.
+    fn extend_tip(&self, tip: &Self::ChainTip) -> Result<Self::ChainTip, LightClientError>;
+}
+
+/// A header rejected by the light client of a DA layer
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum LightClientError {
+    /// The header doesn't build on the tip of the chain
+    #[cfg_attr(feature = "std", error("Header doesn't build on the tip of the chain"))]
+    NotSuccessor,
+    /// The difficulty of the header doesn't follow the rules of the chain
+    #[cfg_attr(feature = "std", error("Header has an unexpected difficulty"))]
+    InvalidDifficulty,
+    /// The header hash doesn't meet its difficulty target
+    #[cfg_attr(feature = "std", error("Header has an invalid proof of work"))]
+    InvalidProofOfWork,
+    /// The header timestamp is too old
+    #[cfg_attr(feature = "std", error("Header timestamp is too old"))]
+    InvalidTimestamp,
+}
+
+/// Chain tip for DA layers whose light client only checks that headers are linked.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LinkedChainTip {
+    /// Height of the header at the tip
+    pub height: u64,
+    /// Hash of the header at the tip
+    pub hash: [u8; 32],
+}
+
+impl LinkedChainTip {
+    /// Checks that `header` is the child of the tip and returns the new tip.
+    pub fn extend<H: BlockHeaderTrait>(&self, header: &H) -> Result<Self, LightClientError> {
+        let prev_hash: [u8; 32] = header.prev_hash().into();
+        if header.height() != self.height + 1 || prev_hash != self.hash {
+            return Err(LightClientError::NotSuccessor);
+        }
+        Ok(Self {
+            height: header.height(),
+            hash: header.hash().into(),
+        })
+    }
+}
+
 #[derive(
     Serialize, Deserialize, Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, Default,
 )]
@@ -307,3 +464,79 @@ impl Time {
         self.nanos
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commitment() -> DaData {
+        DaData::SequencerCommitment(SequencerCommitment {
+            merkle_root: [1; 32],
+            l1_start_block_hash: [2; 32],
+            l1_end_block_hash: [3; 32],
+        })
+    }
+
+    #[test]
+    fn test_da_data_envelope_roundtrip() {
+        let data = commitment();
+        assert_eq!(
+            DaData::from_envelope(&data.to_envelope()).unwrap(),
+            DaDataEnvelope::Known(data)
+        );
+    }
+
+    #[test]
+    fn test_da_data_without_envelope_is_read() {
+        let data = commitment();
+        assert_eq!(
+            DaData::from_envelope(&data.try_to_vec().unwrap()).unwrap(),
+            DaDataEnvelope::Known(data)
+        );
+    }
+
+    #[test]
+    fn test_da_data_of_version_1_is_read() {
+        let data = commitment();
+        let mut bytes = DA_DATA_ENVELOPE_MAGIC.to_vec();
+        bytes.push(1);
+        bytes.extend(data.try_to_vec().unwrap());
+        assert_eq!(
+            DaData::from_envelope(&bytes).unwrap(),
+            DaDataEnvelope::Known(data)
+        );
+
+        // Proofs of version 1 had no content
+        let mut bytes = DA_DATA_ENVELOPE_MAGIC.to_vec();
+        bytes.extend([1, 1]);
+        assert!(DaData::from_envelope(&bytes).is_err());
+
+        let proof = DaData::ZKProof(BatchProof {
+            guest_version: 1,
+            l2_start_height: 1,
+            l2_end_height: 2,
+            proof: vec![3],
+        });
+        assert_eq!(
+            DaData::from_envelope(&proof.to_envelope()).unwrap(),
+            DaDataEnvelope::Known(proof)
+        );
+    }
+
+    #[test]
+    fn test_newer_da_data_version_is_skipped() {
+        let mut bytes = DA_DATA_ENVELOPE_MAGIC.to_vec();
+        bytes.extend([DA_DATA_VERSION + 1, 0xff, 0xff]);
+        let envelope = DaData::from_envelope(&bytes).unwrap();
+        assert_eq!(
+            envelope,
+            DaDataEnvelope::UnknownVersion(DA_DATA_VERSION + 1)
+        );
+        assert!(envelope.into_known().is_err());
+
+        // Garbage in an envelope of a known version is still an error
+        let mut bytes = DA_DATA_ENVELOPE_MAGIC.to_vec();
+        bytes.extend([DA_DATA_VERSION, 0xff, 0xff]);
+        assert!(DaData::from_envelope(&bytes).is_err());
+    }
+}
@@ -4,18 +4,43 @@
 use core::fmt::Debug;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use digest::typenum::U32;
+use digest::Digest;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::io;
 use crate::maybestd::vec::Vec;
 
 /// Contains raw transactions and information about the soft confirmation block
-#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct UnsignedSoftConfirmationBatch {
     da_slot_height: u64,
     da_slot_hash: [u8; 32],
     pre_state_root: Vec<u8>,
     txs: Vec<Vec<u8>>,
     l1_fee_rate: u64,
+    timestamp: Option<u64>,
+    ordering_commitment: Option<[u8; 32]>,
+}
+
+/// The signed message. Soft confirmations without a timestamp or an ordering commitment,
+/// given before the chain required them, serialize as they did before the fields existed,
+/// so that their signatures keep verifying.
+impl BorshSerialize for UnsignedSoftConfirmationBatch {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.da_slot_height.serialize(writer)?;
+        self.da_slot_hash.serialize(writer)?;
+        self.pre_state_root.serialize(writer)?;
+        self.txs.serialize(writer)?;
+        self.l1_fee_rate.serialize(writer)?;
+        if let Some(timestamp) = self.timestamp {
+            timestamp.serialize(writer)?;
+        }
+        match self.ordering_commitment {
+            Some(ordering_commitment) => ordering_commitment.serialize(writer),
+            None => Ok(()),
+        }
+    }
 }
 
 impl UnsignedSoftConfirmationBatch {
@@ -26,6 +51,8 @@ impl UnsignedSoftConfirmationBatch {
         pre_state_root: Vec<u8>,
         txs: Vec<Vec<u8>>,
         l1_fee_rate: u64,
+        timestamp: Option<u64>,
+        ordering_commitment: Option<[u8; 32]>,
     ) -> Self {
         Self {
             da_slot_height,
@@ -33,6 +60,8 @@ impl UnsignedSoftConfirmationBatch {
             pre_state_root,
             txs,
             l1_fee_rate,
+            timestamp,
+            ordering_commitment,
         }
     }
     /// DA block to build on
@@ -55,6 +84,17 @@ impl UnsignedSoftConfirmationBatch {
     pub fn l1_fee_rate(&self) -> u64 {
         self.l1_fee_rate
     }
+    /// Block timestamp, in seconds since the unix epoch. `None` before the chain requires
+    /// soft confirmations to carry one.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+    /// Commitment to the order in which the sequencer received the transactions, see
+    /// [`ordering_commitment`]. `None` before the chain requires soft confirmations to
+    /// carry one.
+    pub fn ordering_commitment(&self) -> Option<[u8; 32]> {
+        self.ordering_commitment
+    }
 }
 
 /// Signed version of the `UnsignedSoftConfirmationBatch`
@@ -69,6 +109,8 @@ pub struct SignedSoftConfirmationBatch {
     txs: Vec<Vec<u8>>,
     signature: Vec<u8>,
     pub_key: Vec<u8>,
+    timestamp: Option<u64>,
+    ordering_commitment: Option<[u8; 32]>,
 }
 
 impl SignedSoftConfirmationBatch {
@@ -83,6 +125,8 @@ impl SignedSoftConfirmationBatch {
         txs: Vec<Vec<u8>>,
         signature: Vec<u8>,
         pub_key: Vec<u8>,
+        timestamp: Option<u64>,
+        ordering_commitment: Option<[u8; 32]>,
     ) -> SignedSoftConfirmationBatch {
         Self {
             hash,
@@ -93,6 +137,8 @@ impl SignedSoftConfirmationBatch {
             txs,
             signature,
             pub_key,
+            timestamp,
+            ordering_commitment,
         }
     }
 
@@ -146,6 +192,19 @@ impl SignedSoftConfirmationBatch {
         self.pub_key.clone()
     }
 
+    /// Block timestamp, in seconds since the unix epoch. `None` before the chain requires
+    /// soft confirmations to carry one.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// Commitment to the order in which the sequencer received the transactions, see
+    /// [`ordering_commitment`]. `None` before the chain requires soft confirmations to
+    /// carry one.
+    pub fn ordering_commitment(&self) -> Option<[u8; 32]> {
+        self.ordering_commitment
+    }
+
     /// Sets l1 fee rate
     pub fn set_l1_fee_rate(&mut self, l1_fee_rate: u64) {
         self.l1_fee_rate = l1_fee_rate;
@@ -156,3 +215,92 @@ impl SignedSoftConfirmationBatch {
         self.da_slot_hash = da_slot_hash;
     }
 }
+
+/// Commits to the order in which the sequencer received the transactions of a soft
+/// confirmation, by hashing their hashes concatenated in that order.
+///
+/// From its activation height on, the commitment is part of the message signed by the
+/// sequencer, and the state transition function requires it, but does not check it
+/// against the transactions, whose order of arrival it can't know.
+/// It lets anyone holding the transactions of a block, in the order the sequencer claims
+/// to have received them, check that claim against the signed commitment and compare it
+/// with the order the transactions were executed in.
+pub fn ordering_commitment<H: Digest<OutputSize = U32>>(
+    tx_hashes: impl IntoIterator<Item = [u8; 32]>,
+) -> [u8; 32] {
+    let mut hasher = H::new();
+    for tx_hash in tx_hashes {
+        hasher.update(tx_hash);
+    }
+    hasher.finalize().into()
+}
+
+/// Signature schemes the sequencer can sign soft confirmations with.
+/// Which one is used at a DA height is decided by the chain, so that blocks signed
+/// before a switch keep verifying with the scheme they were signed with.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// Ed25519, used since genesis
+    #[default]
+    Ed25519,
+    /// BIP-340 Schnorr signatures over secp256k1, as used by Bitcoin
+    Secp256k1Schnorr,
+}
+
+/// How far, in seconds, the timestamp of a soft confirmation may be from the time of
+/// the DA block it builds on
+pub const MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS: u64 = 2 * 60 * 60;
+
+/// A soft confirmation timestamp rejected by the kernel
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum SoftConfirmationTimestampError {
+    /// The timestamp is lower than the one of the previous soft confirmation
+    #[cfg_attr(
+        feature = "std",
+        error("Timestamp is before the parent timestamp {parent_timestamp}")
+    )]
+    BeforeParent {
+        /// Timestamp of the previous soft confirmation
+        parent_timestamp: u64,
+    },
+    /// The timestamp is more than [`MAX_SOFT_CONFIRMATION_TIME_DRIFT_SECS`] away from
+    /// the time of the DA block
+    #[cfg_attr(
+        feature = "std",
+        error("Timestamp is too far from the DA block time {da_block_time}")
+    )]
+    TooFarFromDaBlock {
+        /// Time of the DA block the soft confirmation builds on
+        da_block_time: u64,
+    },
+    /// The soft confirmation has no timestamp, but builds on a DA block from which the
+    /// chain requires one
+    #[cfg_attr(
+        feature = "std",
+        error("Timestamp is required from DA height {activation_height}")
+    )]
+    Missing {
+        /// DA height from which soft confirmations carry a timestamp
+        activation_height: u64,
+    },
+    /// The soft confirmation has a timestamp, but builds on a DA block before the chain
+    /// requires one
+    #[cfg_attr(
+        feature = "std",
+        error("Timestamp is not allowed before the chain requires one")
+    )]
+    Unexpected,
+}
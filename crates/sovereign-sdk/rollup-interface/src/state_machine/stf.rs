@@ -74,6 +74,48 @@ pub struct BatchReceipt<BatchReceiptContents, TxReceiptContents> {
     pub tx_receipts: Vec<TransactionReceipt<TxReceiptContents>>,
     /// Any additional structured data to be saved in the database and served over RPC
     pub phantom_data: PhantomData<BatchReceiptContents>,
+    /// The outcome of the batch, for soft confirmations
+    pub soft_confirmation_outcome: Option<SoftConfirmationOutcome>,
+}
+
+/// Results of the soft confirmation rules enforced on a soft confirmation
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftConfirmationEnforcement {
+    /// Number of soft confirmations given for the DA block of the soft confirmation,
+    /// itself included
+    pub l2_blocks_on_da_slot: u64,
+    /// Maximum number of soft confirmations given for a DA block
+    pub max_l2_blocks_on_da_slot: u64,
+    /// L1 fee rate accepted for the soft confirmation, which the fee rate of the next one
+    /// is checked against
+    pub l1_fee_rate: u64,
+    /// Maximum change of the L1 fee rate from one soft confirmation to the next, in percent
+    pub max_l1_fee_rate_change_percentage: u64,
+}
+
+/// Outcome of applying a soft confirmation, stored in the rollup's database and served over RPC
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftConfirmationOutcome {
+    /// Gas used by the transactions of the soft confirmation
+    pub gas_used: u64,
+    /// Fees paid by the transactions of the soft confirmation, L1 fees included
+    pub fees_collected: u128,
+    /// Amount rewarded to the sequencer for the soft confirmation
+    pub sequencer_reward: u128,
+    /// Amount the sequencer was penalized for the soft confirmation
+    pub sequencer_penalty: u128,
+    /// Number of transactions applied successfully
+    pub successful_txs: u64,
+    /// Number of transactions reverted
+    pub reverted_txs: u64,
+    /// Results of the soft confirmation rules enforced on the soft confirmation
+    pub enforcement: SoftConfirmationEnforcement,
 }
 
 /// A receipt for a soft batch of transactions. These receipts are stored in the rollup's database
@@ -99,6 +141,13 @@ pub struct SoftBatchReceipt<BatchReceiptContents, TxReceiptContents, DS: DaSpec>
     pub pub_key: Vec<u8>,
     /// Base layer fee rate sats/wei etc. per byte.
     pub l1_fee_rate: u64,
+    /// Block timestamp, in seconds since the unix epoch, if the soft batch carries one
+    pub timestamp: Option<u64>,
+    /// Commitment to the order in which the sequencer received the transactions, if the
+    /// soft batch carries one
+    pub ordering_commitment: Option<[u8; 32]>,
+    /// The outcome of the soft batch
+    pub outcome: Option<SoftConfirmationOutcome>,
 }
 
 /// Result of applying a slot to current state
@@ -196,6 +196,7 @@ impl<B: Arbitrary + 'static, R: Arbitrary + 'static> Arbitrary for BatchReceipt<
                         batch_hash,
                         tx_receipts: txs,
                         phantom_data: PhantomData,
+                        soft_confirmation_outcome: None,
                     }
                 })
                 .boxed()
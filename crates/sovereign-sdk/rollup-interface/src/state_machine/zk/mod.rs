@@ -45,6 +45,11 @@ pub trait ZkvmHost: Zkvm + Clone {
     /// with some mild performance overhead and is not as easy to debug as [`simulate_with_hints`](ZkvmHost::simulate_with_hints).
     fn run(&mut self, with_proof: bool) -> Result<Proof, anyhow::Error>;
 
+    /// Returns the number of cycles of the last guest run, if the zkVM reports them.
+    fn cycle_count(&self) -> Option<u64> {
+        None
+    }
+
     /// Extracts public input form the proof.
     fn extract_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
         proof: &Proof,